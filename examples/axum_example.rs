@@ -14,12 +14,12 @@ use axum::{
     response::IntoResponse,
     routing::get,
 };
+use html2pdf_api::integrations::axum::{shutdown_signal, with_graceful_shutdown_pool};
 use html2pdf_api::{
     BrowserPool, BrowserPoolConfigBuilder, ChromeBrowserFactory, SharedBrowserPool,
 };
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::signal;
 
 /// Handler that generates a PDF from a URL.
 async fn generate_pdf(
@@ -97,40 +97,6 @@ async fn health() -> &'static str {
     "OK"
 }
 
-/// Shutdown signal handler.
-async fn shutdown_signal(pool: SharedBrowserPool) {
-    let ctrl_c = async {
-        signal::ctrl_c()
-            .await
-            .expect("Failed to install Ctrl+C handler");
-    };
-
-    #[cfg(unix)]
-    let terminate = async {
-        signal::unix::signal(signal::unix::SignalKind::terminate())
-            .expect("Failed to install signal handler")
-            .recv()
-            .await;
-    };
-
-    #[cfg(not(unix))]
-    let terminate = std::future::pending::<()>();
-
-    tokio::select! {
-        _ = ctrl_c => {},
-        _ = terminate => {},
-    }
-
-    log::info!("Shutdown signal received, cleaning up...");
-
-    // Cleanup pool
-    if let Ok(mut pool_guard) = pool.lock() {
-        pool_guard.shutdown();
-    }
-
-    log::info!("Cleanup complete");
-}
-
 #[tokio::main]
 async fn main() {
     // Initialize logging
@@ -173,13 +139,16 @@ async fn main() {
 
     log::info!("Starting server on http://localhost:3000");
 
-    // Start server with graceful shutdown
+    // Start server; on Ctrl+C/SIGTERM the pool drains, then shuts down
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
         .await
         .expect("Failed to bind");
 
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal(shutdown_pool))
+        .with_graceful_shutdown(with_graceful_shutdown_pool(
+            shutdown_pool,
+            shutdown_signal(),
+        ))
         .await
         .expect("Server error");
 }
@@ -71,7 +71,7 @@ use html2pdf_api::config::BrowserPoolConfigBuilder;
 use html2pdf_api::factory::ChromeBrowserFactory;
 use html2pdf_api::integrations::rocket::SharedPool;
 use html2pdf_api::pool::BrowserPool;
-use html2pdf_api::service::{PdfFromUrlRequest, generate_pdf_from_url};
+use html2pdf_api::service::{Bytes, PdfFromUrlRequest, generate_pdf_from_url};
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -97,7 +97,7 @@ struct CustomPdfQuery {
 ///
 /// Demonstrates how to create custom responders with extra metadata.
 struct CustomPdfResponse {
-    data: Vec<u8>,
+    data: Bytes,
     request_id: String,
     size: usize,
     content_disposition: String,
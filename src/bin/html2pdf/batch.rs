@@ -0,0 +1,464 @@
+//! Batch rendering from a manifest file.
+//!
+//! `html2pdf batch manifest.yaml` reads a list of jobs, renders them
+//! concurrently across a shared pool with
+//! [`render_parallel_with`](html2pdf_api::service::render_parallel_with),
+//! writing each PDF as soon as it is rendered, and prints a per-job summary
+//! with timings.
+//!
+//! # Manifest Format
+//!
+//! ```yaml
+//! concurrency: 4          # optional, defaults to 4
+//! jobs:
+//!   - url: https://example.com
+//!     output: out/example.pdf
+//!     landscape: true
+//!   - name: invoice
+//!     html_file: templates/invoice.html
+//!     output: out/invoice.pdf
+//!     waitsecs: 1
+//!   - html: "<h1>Inline</h1>"
+//!     output: out/inline.pdf
+//!     print_background: false
+//! ```
+//!
+//! Each job needs exactly one of `url`, `html`, or `html_file`, plus an
+//! `output` path. Relative `html_file` and `output` paths are resolved
+//! against the manifest's directory. JSON manifests are accepted too,
+//! since JSON is valid YAML.
+//!
+//! # Job Fields
+//!
+//! | Field | Type | Required | Description |
+//! |-------|------|----------|-------------|
+//! | `name` | string | No | Label for the summary (defaults to the output path) |
+//! | `url` | string | One of | URL to convert |
+//! | `html` | string | One of | Inline HTML to convert |
+//! | `html_file` | path | One of | HTML file to convert |
+//! | `output` | path | **Yes** | Where to write the PDF |
+//! | `waitsecs` | u64 | No | Seconds to wait for JavaScript |
+//! | `wait_for_expression` | string | No | JavaScript expression awaited until truthy |
+//! | `inject_css` | string | No | CSS added once the page is ready |
+//! | `inject_js` | string | No | JavaScript run once the page is ready |
+//! | `landscape` | bool | No | Use landscape orientation |
+//! | `print_background` | bool | No | Include background graphics |
+//! | `paper_format` | string | No | Named paper size (`A4`, `Letter`, ...) |
+//! | `paper_width`, `paper_height` | f64 | No | Paper size in inches |
+//! | `margin_top`, `margin_bottom`, `margin_left`, `margin_right` | f64 | No | Margins in inches |
+//! | `scale` | f64 | No | Rendering scale, 0.1-2.0 |
+//! | `page_ranges` | string | No | Pages to print (e.g. `1-5, 8`) |
+//! | `display_header_footer` | bool | No | Print the header and footer (default: true with a template) |
+//! | `header_template`, `footer_template` | string | No | HTML printed in the top/bottom margin |
+//! | `emulate_media` | string | No | CSS media type: `print` (default) or `screen` |
+//! | `color_scheme` | string | No | `prefers-color-scheme`: `light` (default) or `dark` |
+//! | `generate_tagged_pdf` | bool | No | Write a tagged (accessible) PDF |
+//! | `generate_document_outline` | bool | No | Add bookmarks built from the headings |
+//! | `pdf_a` | bool | No | Convert the PDF toward PDF/A-2b (needs Ghostscript) |
+//! | `watermark` | map | No | `text` or `image` stamped on every page, plus `opacity`, `rotation`, `position`, `font_size`, `color` |
+//! | `metadata` | map | No | `title`, `author`, `subject`, `keywords`, `creation_date` written into the PDF |
+//! | `javascript_enabled` | bool | No | Run the page's scripts (default: true) |
+//! | `block_resources` | list | No | Resource types to block (e.g. `[image, font]`) |
+//! | `block_urls` | list | No | Domains or URL patterns to block |
+//! | `ignore_tls_errors` | bool | No | Accept invalid TLS certificates (default: false) |
+//! | `offline` | bool | No | No network after a URL loads; none at all for HTML (default: false) |
+//! | `bypass_cache` | bool | No | Ignore the browser's HTTP cache (default: false) |
+//! | `headers` | map | No | Extra headers sent to the URL's origin (`url` jobs only) |
+//! | `cookies` | list | No | Cookies (`name`, `value`, `domain`, `path`, ...) for the URL's site (`url` jobs only) |
+//! | `auth` | map | No | `{type: basic, username, password}` or `{type: bearer, token}` for the URL's origin (`url` jobs only) |
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use html2pdf_api::service::{
+    self, AuthSpec, CookieSpec, PdfFromHtmlRequest, PdfFromUrlRequest, PdfMetadata, PdfResponse,
+    RenderJob, Watermark,
+};
+use serde::Deserialize;
+
+use crate::{build_pool, shutdown_pool};
+
+/// Concurrency used when neither the manifest nor the command line sets one.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+// ============================================================================
+// Manifest Types
+// ============================================================================
+
+/// Top-level manifest document.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    /// Maximum number of jobs rendered at once.
+    #[serde(default)]
+    concurrency: Option<usize>,
+
+    /// Jobs to render, in order.
+    jobs: Vec<Job>,
+}
+
+/// A single conversion job.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Job {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    html: Option<String>,
+    #[serde(default)]
+    html_file: Option<PathBuf>,
+    output: PathBuf,
+    #[serde(default)]
+    waitsecs: Option<u64>,
+    #[serde(default)]
+    wait_for_expression: Option<String>,
+    #[serde(default)]
+    inject_css: Option<String>,
+    #[serde(default)]
+    inject_js: Option<String>,
+    #[serde(default)]
+    landscape: Option<bool>,
+    #[serde(default)]
+    print_background: Option<bool>,
+    #[serde(default)]
+    paper_format: Option<String>,
+    #[serde(default)]
+    paper_width: Option<f64>,
+    #[serde(default)]
+    paper_height: Option<f64>,
+    #[serde(default)]
+    margin_top: Option<f64>,
+    #[serde(default)]
+    margin_bottom: Option<f64>,
+    #[serde(default)]
+    margin_left: Option<f64>,
+    #[serde(default)]
+    margin_right: Option<f64>,
+    #[serde(default)]
+    scale: Option<f64>,
+    #[serde(default)]
+    page_ranges: Option<String>,
+    #[serde(default)]
+    display_header_footer: Option<bool>,
+    #[serde(default)]
+    header_template: Option<String>,
+    #[serde(default)]
+    footer_template: Option<String>,
+    #[serde(default)]
+    emulate_media: Option<String>,
+    #[serde(default)]
+    color_scheme: Option<String>,
+    #[serde(default)]
+    generate_tagged_pdf: Option<bool>,
+    #[serde(default)]
+    generate_document_outline: Option<bool>,
+    #[serde(default)]
+    pdf_a: Option<bool>,
+    #[serde(default)]
+    watermark: Option<Watermark>,
+    #[serde(default)]
+    metadata: Option<PdfMetadata>,
+    #[serde(default)]
+    javascript_enabled: Option<bool>,
+    #[serde(default)]
+    block_resources: Option<Vec<String>>,
+    #[serde(default)]
+    block_urls: Option<Vec<String>>,
+    #[serde(default)]
+    ignore_tls_errors: Option<bool>,
+    #[serde(default)]
+    offline: Option<bool>,
+    #[serde(default)]
+    bypass_cache: Option<bool>,
+    #[serde(default)]
+    headers: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    cookies: Option<Vec<CookieSpec>>,
+    #[serde(default)]
+    auth: Option<AuthSpec>,
+}
+
+impl Manifest {
+    /// Read and parse a manifest from disk.
+    fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+        let manifest: Manifest = serde_yaml::from_str(&text)
+            .map_err(|e| format!("invalid manifest {}: {}", path.display(), e))?;
+
+        if manifest.jobs.is_empty() {
+            return Err(format!("manifest {} contains no jobs", path.display()).into());
+        }
+
+        Ok(manifest)
+    }
+}
+
+impl Job {
+    /// Label used in the summary table.
+    fn label(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.output.display().to_string())
+    }
+
+    /// Validate the job and build the matching service request.
+    ///
+    /// Relative `html_file` paths are resolved against `base_dir`.
+    fn into_render_job(self, base_dir: &Path) -> Result<RenderJob, String> {
+        let filename = self
+            .output
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+
+        match (self.url, self.html, self.html_file) {
+            (Some(url), None, None) => Ok(RenderJob::Url(PdfFromUrlRequest {
+                url,
+                filename,
+                waitsecs: self.waitsecs,
+                landscape: self.landscape,
+                print_background: self.print_background,
+                paper_format: self.paper_format,
+                paper_width: self.paper_width,
+                paper_height: self.paper_height,
+                margin_top: self.margin_top,
+                margin_bottom: self.margin_bottom,
+                margin_left: self.margin_left,
+                margin_right: self.margin_right,
+                scale: self.scale,
+                page_ranges: self.page_ranges,
+                display_header_footer: self.display_header_footer,
+                header_template: self.header_template,
+                footer_template: self.footer_template,
+                emulate_media: self.emulate_media,
+                color_scheme: self.color_scheme,
+                generate_tagged_pdf: self.generate_tagged_pdf,
+                generate_document_outline: self.generate_document_outline,
+                pdf_a: self.pdf_a,
+                watermark: self.watermark,
+                metadata: self.metadata,
+                javascript_enabled: self.javascript_enabled,
+                block_resources: self.block_resources,
+                block_urls: self.block_urls,
+                ignore_tls_errors: self.ignore_tls_errors,
+                offline: self.offline,
+                bypass_cache: self.bypass_cache,
+                headers: self.headers,
+                cookies: self.cookies,
+                auth: self.auth,
+                wait_for_expression: self.wait_for_expression,
+                inject_css: self.inject_css,
+                inject_js: self.inject_js,
+                ..Default::default()
+            })),
+            (None, _, _)
+                if self.headers.is_some() || self.cookies.is_some() || self.auth.is_some() =>
+            {
+                Err("`headers`, `cookies` and `auth` only apply to `url` jobs".to_string())
+            }
+            (None, html, html_file) if html.is_some() ^ html_file.is_some() => {
+                let html = match (html, html_file) {
+                    (Some(html), _) => html,
+                    (_, Some(file)) => {
+                        let path = base_dir.join(file);
+                        std::fs::read_to_string(&path)
+                            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?
+                    }
+                    (None, None) => unreachable!("guarded above"),
+                };
+                Ok(RenderJob::Html(PdfFromHtmlRequest {
+                    html,
+                    filename,
+                    waitsecs: self.waitsecs,
+                    landscape: self.landscape,
+                    print_background: self.print_background,
+                    paper_format: self.paper_format,
+                    paper_width: self.paper_width,
+                    paper_height: self.paper_height,
+                    margin_top: self.margin_top,
+                    margin_bottom: self.margin_bottom,
+                    margin_left: self.margin_left,
+                    margin_right: self.margin_right,
+                    scale: self.scale,
+                    page_ranges: self.page_ranges,
+                    display_header_footer: self.display_header_footer,
+                    header_template: self.header_template,
+                    footer_template: self.footer_template,
+                    emulate_media: self.emulate_media,
+                    color_scheme: self.color_scheme,
+                    generate_tagged_pdf: self.generate_tagged_pdf,
+                    generate_document_outline: self.generate_document_outline,
+                    pdf_a: self.pdf_a,
+                    watermark: self.watermark,
+                    metadata: self.metadata,
+                    javascript_enabled: self.javascript_enabled,
+                    block_resources: self.block_resources,
+                    block_urls: self.block_urls,
+                    ignore_tls_errors: self.ignore_tls_errors,
+                    offline: self.offline,
+                    bypass_cache: self.bypass_cache,
+                    wait_for_expression: self.wait_for_expression,
+                    inject_css: self.inject_css,
+                    inject_js: self.inject_js,
+                    ..Default::default()
+                }))
+            }
+            _ => Err("job needs exactly one of `url`, `html`, or `html_file`".to_string()),
+        }
+    }
+}
+
+// ============================================================================
+// Execution
+// ============================================================================
+
+/// Outcome of a single job, collected for the summary.
+struct JobResult {
+    label: String,
+    elapsed: Duration,
+    outcome: Result<usize, String>,
+}
+
+/// Run every job in the manifest and print a summary.
+///
+/// Returns an error if the manifest is invalid or any job failed.
+pub async fn run(
+    manifest_path: &Path,
+    concurrency: Option<usize>,
+    chrome_path: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let manifest = Manifest::load(manifest_path)?;
+    let base_dir = manifest_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+
+    let concurrency = concurrency
+        .or(manifest.concurrency)
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, manifest.jobs.len());
+
+    log::info!(
+        "Running {} jobs with concurrency {}",
+        manifest.jobs.len(),
+        concurrency
+    );
+
+    // Validate every job up front; invalid jobs fail without rendering
+    let mut prepared = Vec::with_capacity(manifest.jobs.len());
+    let mut render_jobs = Vec::with_capacity(manifest.jobs.len());
+    let mut outputs = Vec::with_capacity(manifest.jobs.len());
+    for job in manifest.jobs {
+        let label = job.label();
+        let output = base_dir.join(&job.output);
+        let valid = job.into_render_job(&base_dir).map(|render_job| {
+            render_jobs.push(render_job);
+            outputs.push(output);
+        });
+        prepared.push((label, valid));
+    }
+
+    let pool = build_pool(concurrency, chrome_path)?;
+    let start = Instant::now();
+
+    // Each PDF is written as soon as its render finishes
+    let finished = {
+        let pool = Arc::clone(&pool);
+        tokio::task::spawn_blocking(move || {
+            let finished: Vec<OnceLock<(Duration, Result<usize, String>)>> =
+                render_jobs.iter().map(|_| OnceLock::new()).collect();
+            service::render_parallel_with(
+                &pool,
+                &render_jobs,
+                concurrency,
+                |index, result, elapsed| {
+                    let write_start = Instant::now();
+                    let outcome = result
+                        .map_err(|e| e.to_string())
+                        .and_then(|response| write_job_output(&outputs[index], &response));
+                    let _ = finished[index].set((elapsed + write_start.elapsed(), outcome));
+                },
+            );
+            finished
+        })
+        .await?
+    };
+
+    shutdown_pool(pool).await;
+
+    // Merge render results back into manifest order
+    let mut finished = finished.into_iter();
+    let results: Vec<JobResult> = prepared
+        .into_iter()
+        .map(|(label, job)| {
+            let (elapsed, outcome) = match job {
+                Ok(()) => finished
+                    .next()
+                    .and_then(OnceLock::into_inner)
+                    .expect("one render result per valid job"),
+                Err(e) => (Duration::ZERO, Err(e)),
+            };
+            JobResult {
+                label,
+                elapsed,
+                outcome,
+            }
+        })
+        .collect();
+
+    print_summary(&results, start.elapsed());
+
+    let failed = results.iter().filter(|r| r.outcome.is_err()).count();
+    if failed > 0 {
+        return Err(format!("{} of {} jobs failed", failed, results.len()).into());
+    }
+
+    Ok(())
+}
+
+/// Write a job's PDF, creating parent directories as needed.
+fn write_job_output(path: &Path, response: &PdfResponse) -> Result<usize, String> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+    }
+
+    std::fs::write(path, &response.data)
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+
+    Ok(response.size())
+}
+
+/// Print the per-job status table and totals.
+fn print_summary(results: &[JobResult], total: Duration) {
+    println!("{:<4} {:<7} {:>9}  JOB", "#", "STATUS", "TIME");
+
+    for (index, result) in results.iter().enumerate() {
+        let (status, detail) = match &result.outcome {
+            Ok(bytes) => ("ok", format!("{} ({} bytes)", result.label, bytes)),
+            Err(e) => ("FAILED", format!("{}: {}", result.label, e)),
+        };
+        println!(
+            "{:<4} {:<7} {:>8.2}s  {}",
+            index + 1,
+            status,
+            result.elapsed.as_secs_f64(),
+            detail
+        );
+    }
+
+    let failed = results.iter().filter(|r| r.outcome.is_err()).count();
+    println!(
+        "\n{} jobs, {} succeeded, {} failed in {:.2}s",
+        results.len(),
+        results.len() - failed,
+        failed,
+        total.as_secs_f64()
+    );
+}
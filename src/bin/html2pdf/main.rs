@@ -0,0 +1,661 @@
+//! `html2pdf` command-line tool.
+//!
+//! Converts a URL or a local HTML file to PDF using a small internal
+//! [`BrowserPool`]. The flags mirror the fields of
+//! [`PdfFromUrlRequest`] and [`PdfFromHtmlRequest`], so anything the HTTP
+//! endpoints can do is available from the shell as well.
+//!
+//! # Usage
+//!
+//! ```text
+//! html2pdf url https://example.com -o example.pdf
+//! html2pdf html invoice.html -o invoice.pdf --landscape
+//! html2pdf batch manifest.yaml --concurrency 4
+//! html2pdf serve --bind 0.0.0.0:8080 --framework axum --schedule schedules.yaml
+//! html2pdf doctor
+//! ```
+//!
+//! See [`batch`] for the manifest format and [`serve`] for server options.
+//!
+//! # Flags
+//!
+//! | Flag | Request Field | Default | Description |
+//! |------|---------------|---------|-------------|
+//! | `-o, --output` | - | `document.pdf` | Output file path |
+//! | `--waitsecs` | `waitsecs` | `5` (url) / `2` (html) | Seconds to wait for JavaScript |
+//! | `--wait-for` | `wait_for_expression` | `window.isPageDone === true` | JavaScript expression awaited until truthy |
+//! | `--inject-css` | `inject_css` | - | CSS added once the page is ready |
+//! | `--inject-js` | `inject_js` | - | JavaScript run once the page is ready |
+//! | `--landscape` | `landscape` | `false` | Use landscape orientation |
+//! | `--no-background` | `print_background` | `false` | Omit background graphics |
+//! | `--paper-format` | `paper_format` | Letter | Named paper size (`A4`, `Letter`, ...) |
+//! | `--paper-width`, `--paper-height` | `paper_width`, `paper_height` | - | Paper size in inches |
+//! | `--margin-top`, `--margin-bottom`, `--margin-left`, `--margin-right` | `margin_*` | `0` | Margins in inches |
+//! | `--scale` | `scale` | `1.0` | Rendering scale, 0.1-2.0 |
+//! | `--page-ranges` | `page_ranges` | all pages | Pages to print (e.g. `1-5,8`) |
+//! | `--header-template`, `--footer-template` | `header_template`, `footer_template` | - | HTML printed in the top/bottom margin |
+//! | `--emulate-media` | `emulate_media` | `print` | CSS media type: `print` or `screen` |
+//! | `--color-scheme` | `color_scheme` | `light` | `prefers-color-scheme`: `light` or `dark` |
+//! | `--tagged-pdf` | `generate_tagged_pdf` | `false` | Write a tagged (accessible) PDF |
+//! | `--outline` | `generate_document_outline` | `false` | Add bookmarks built from the headings (implies `--tagged-pdf`) |
+//! | `--pdf-a` | `pdf_a` | `false` | Convert the PDF toward PDF/A-2b (needs Ghostscript) |
+//! | `--watermark` | `watermark.text` | - | Text stamped on every page, e.g. `DRAFT` |
+//! | `--watermark-image` | `watermark.image` | - | `http(s)` or `data:image/` URL of an image stamped on every page |
+//! | `--watermark-opacity` | `watermark.opacity` | `0.2` | Watermark opacity, 0.0-1.0 |
+//! | `--watermark-rotation` | `watermark.rotation` | `-45` (text) / `0` (image) | Watermark rotation in degrees |
+//! | `--watermark-position` | `watermark.position` | `center` | `center`, `top`, `bottom-right`, ... |
+//! | `--title`, `--author`, `--subject` | `metadata` | - | Document properties written into the PDF |
+//! | `--keywords` | `metadata.keywords` | - | Comma-separated search keywords |
+//! | `--creation-date` | `metadata.creation_date` | - | Creation date, RFC 3339 or `YYYY-MM-DD` |
+//! | `--no-javascript` | `javascript_enabled` | `false` | Don't run the page's scripts |
+//! | `--block-resources` | `block_resources` | - | Comma-separated resource types to block |
+//! | `--block-urls` | `block_urls` | - | Comma-separated domains/URL patterns to block |
+//! | `--ignore-tls-errors` | `ignore_tls_errors` | `false` | Accept invalid TLS certificates |
+//! | `--offline` | `offline` | `false` | No network after a URL loads; none at all for HTML |
+//! | `--bypass-cache` | `bypass_cache` | `false` | Ignore the browser's HTTP cache |
+//! | `--header` | `headers` | - | Extra header for the URL's origin, `"Name: value"` (repeatable, URL only) |
+//! | `--cookie` | `cookies` | - | Cookie for the URL's site, `name=value` (repeatable, URL only) |
+//! | `--user` | `auth` | - | Basic auth for the URL's origin, `username:password` (URL only) |
+//! | `--bearer` | `auth` | `HTML2PDF_BEARER_TOKEN` | Bearer token for the URL's origin (URL only) |
+//! | `--chrome-path` | - | `CHROME_PATH` / auto | Custom Chrome binary |
+//!
+//! # Building
+//!
+//! ```bash
+//! cargo install html2pdf-api --features cli
+//! ```
+//!
+//! Logging is controlled with `RUST_LOG` (default: `warn`). Set
+//! `CHROME_NO_SANDBOX=true` when Chrome cannot run sandboxed, e.g. as root
+//! in a container, and `CHROME_CA_BUNDLE` to a PEM file to trust a private CA.
+//! `file://` URLs are rejected unless their directory is listed in
+//! `BROWSER_ALLOWED_FILE_DIRS`. Built with the `pdf-signing` feature,
+//! `PDF_SIGN_CERT` and `PDF_SIGN_KEY` sign every PDF written.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod batch;
+mod serve;
+
+use clap::{Args, Parser, Subcommand};
+use html2pdf_api::service::{
+    self, AuthSpec, CookieSpec, PdfFromHtmlRequest, PdfFromUrlRequest, PdfMetadata, PdfResponse,
+    PdfServiceError, Watermark,
+};
+use html2pdf_api::{
+    BrowserPool, BrowserPoolConfigBuilder, ChromeBrowserFactory, FontDirectory, SandboxOptions,
+    StealthOptions, TrustedCa, diagnostics,
+};
+
+/// Convert web pages and HTML files to PDF using headless Chrome.
+#[derive(Debug, Parser)]
+#[command(name = "html2pdf", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Convert a URL to PDF.
+    Url {
+        /// URL to convert (must include the scheme, e.g. `https://`).
+        url: String,
+
+        #[command(flatten)]
+        render: RenderArgs,
+    },
+
+    /// Convert a local HTML file to PDF.
+    Html {
+        /// Path to the HTML file to convert.
+        file: PathBuf,
+
+        #[command(flatten)]
+        render: RenderArgs,
+    },
+
+    /// Run every job listed in a YAML manifest.
+    Batch {
+        /// Path to the manifest file.
+        manifest: PathBuf,
+
+        /// Maximum number of jobs rendered at once (overrides the manifest).
+        #[arg(long)]
+        concurrency: Option<usize>,
+
+        /// Custom Chrome/Chromium binary (defaults to `CHROME_PATH` or auto-detect).
+        #[arg(long)]
+        chrome_path: Option<String>,
+    },
+
+    /// Run the HTTP API server using `app.env` / environment configuration.
+    Serve {
+        /// Address to listen on.
+        #[arg(long, default_value = "0.0.0.0:8080")]
+        bind: SocketAddr,
+
+        /// Web framework to serve the pre-built routes with.
+        #[arg(long, value_enum, default_value_t = serve::Framework::Axum)]
+        framework: serve::Framework,
+
+        /// YAML file of recurring renders to run while serving.
+        #[arg(long)]
+        schedule: Option<PathBuf>,
+    },
+
+    /// Check that Chrome, fonts, and the host environment can render PDFs.
+    Doctor {
+        /// Custom Chrome/Chromium binary (defaults to `CHROME_PATH` or auto-detect).
+        #[arg(long)]
+        chrome_path: Option<String>,
+    },
+}
+
+/// Rendering options shared by all conversion subcommands.
+///
+/// These mirror the optional fields of the service request types.
+#[derive(Debug, Args)]
+struct RenderArgs {
+    /// Output file path.
+    #[arg(short, long, default_value = "document.pdf")]
+    output: PathBuf,
+
+    /// Seconds to wait for `window.isPageDone` before printing.
+    #[arg(long)]
+    waitsecs: Option<u64>,
+
+    /// JavaScript expression awaited until truthy (e.g. `window.__APP_READY__`).
+    #[arg(long = "wait-for")]
+    wait_for_expression: Option<String>,
+
+    /// CSS added once the page is ready (e.g. `#cookie-banner { display: none }`).
+    #[arg(long)]
+    inject_css: Option<String>,
+
+    /// JavaScript run once the page is ready, before printing.
+    #[arg(long)]
+    inject_js: Option<String>,
+
+    /// Use landscape page orientation.
+    #[arg(long)]
+    landscape: bool,
+
+    /// Omit background colors and images.
+    #[arg(long)]
+    no_background: bool,
+
+    /// Named paper size: Letter, Legal, Tabloid, Ledger, or A0-A6.
+    #[arg(long)]
+    paper_format: Option<String>,
+
+    /// Paper width in inches (overrides `--paper-format`).
+    #[arg(long)]
+    paper_width: Option<f64>,
+
+    /// Paper height in inches (overrides `--paper-format`).
+    #[arg(long)]
+    paper_height: Option<f64>,
+
+    /// Top margin in inches.
+    #[arg(long)]
+    margin_top: Option<f64>,
+
+    /// Bottom margin in inches.
+    #[arg(long)]
+    margin_bottom: Option<f64>,
+
+    /// Left margin in inches.
+    #[arg(long)]
+    margin_left: Option<f64>,
+
+    /// Right margin in inches.
+    #[arg(long)]
+    margin_right: Option<f64>,
+
+    /// Rendering scale, from 0.1 to 2.0.
+    #[arg(long)]
+    scale: Option<f64>,
+
+    /// Pages to print (e.g. `1-5,8`).
+    #[arg(long)]
+    page_ranges: Option<String>,
+
+    /// HTML printed in the top margin of each page (e.g.
+    /// `<span class="pageNumber"></span>`).
+    #[arg(long)]
+    header_template: Option<String>,
+
+    /// HTML printed in the bottom margin of each page.
+    #[arg(long)]
+    footer_template: Option<String>,
+
+    /// CSS media type to render with: `print` or `screen`.
+    #[arg(long)]
+    emulate_media: Option<String>,
+
+    /// Color scheme the page sees: `light` or `dark`.
+    #[arg(long)]
+    color_scheme: Option<String>,
+
+    /// Write a tagged (accessible) PDF.
+    #[arg(long)]
+    tagged_pdf: bool,
+
+    /// Add bookmarks built from the page's headings; implies `--tagged-pdf`.
+    #[arg(long)]
+    outline: bool,
+
+    /// Convert the PDF toward PDF/A-2b for archiving (needs Ghostscript).
+    #[arg(long)]
+    pdf_a: bool,
+
+    /// Text stamped across every page, e.g. `DRAFT`.
+    #[arg(long, conflicts_with = "watermark_image")]
+    watermark: Option<String>,
+
+    /// Image stamped across every page, as an `http(s)` or `data:image/`
+    /// URL.
+    #[arg(long)]
+    watermark_image: Option<String>,
+
+    /// Watermark opacity, from 0.0 to 1.0 (default: 0.2).
+    #[arg(long)]
+    watermark_opacity: Option<f64>,
+
+    /// Watermark rotation in degrees (default: -45 for text, 0 for images).
+    #[arg(long, allow_hyphen_values = true)]
+    watermark_rotation: Option<f64>,
+
+    /// Where the watermark sits: `center`, `top`, `bottom`, `left`,
+    /// `right`, `top-left`, `top-right`, `bottom-left`, or `bottom-right`.
+    #[arg(long)]
+    watermark_position: Option<String>,
+
+    /// Document title written into the PDF's properties.
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Document author written into the PDF's properties.
+    #[arg(long)]
+    author: Option<String>,
+
+    /// Document subject written into the PDF's properties.
+    #[arg(long)]
+    subject: Option<String>,
+
+    /// Search keywords written into the PDF's properties, comma-separated.
+    #[arg(long, value_delimiter = ',')]
+    keywords: Vec<String>,
+
+    /// Creation date written into the PDF's properties, as RFC 3339 or
+    /// `YYYY-MM-DD`.
+    #[arg(long)]
+    creation_date: Option<String>,
+
+    /// Don't run the page's scripts (for untrusted HTML).
+    #[arg(long)]
+    no_javascript: bool,
+
+    /// Resource types to block, comma-separated (e.g. `image,font,trackers`).
+    #[arg(long, value_delimiter = ',')]
+    block_resources: Vec<String>,
+
+    /// Domains or URL patterns to block, comma-separated (e.g. `tracker.example`).
+    #[arg(long, value_delimiter = ',')]
+    block_urls: Vec<String>,
+
+    /// Accept invalid TLS certificates (prefer `CHROME_CA_BUNDLE` for private CAs).
+    #[arg(long)]
+    ignore_tls_errors: bool,
+
+    /// Cut the network once a URL has loaded; HTML gets none at all.
+    #[arg(long)]
+    offline: bool,
+
+    /// Fetch everything from the network, ignoring the browser's HTTP cache.
+    #[arg(long)]
+    bypass_cache: bool,
+
+    /// Extra header sent to the URL's origin, as `"Name: value"`
+    /// (repeatable).
+    #[arg(long = "header", value_parser = parse_header)]
+    headers: Vec<(String, String)>,
+
+    /// Cookie set for the URL's site, as `name=value` (repeatable).
+    #[arg(long = "cookie")]
+    cookies: Vec<CookieSpec>,
+
+    /// HTTP Basic credentials for the URL's origin, as `username:password`.
+    #[arg(long, value_parser = parse_basic_auth, conflicts_with = "bearer")]
+    user: Option<AuthSpec>,
+
+    /// Bearer token for the URL's origin. `HTML2PDF_BEARER_TOKEN` is used
+    /// when not given, keeping the token out of the process list.
+    #[arg(long)]
+    bearer: Option<String>,
+
+    /// Custom Chrome/Chromium binary (defaults to `CHROME_PATH` or auto-detect).
+    #[arg(long)]
+    chrome_path: Option<String>,
+}
+
+impl RenderArgs {
+    /// Build a URL request from these options.
+    fn url_request(&self, url: String) -> PdfFromUrlRequest {
+        PdfFromUrlRequest {
+            url,
+            filename: file_name(&self.output),
+            waitsecs: self.waitsecs,
+            landscape: Some(self.landscape),
+            print_background: Some(!self.no_background),
+            paper_format: self.paper_format.clone(),
+            paper_width: self.paper_width,
+            paper_height: self.paper_height,
+            margin_top: self.margin_top,
+            margin_bottom: self.margin_bottom,
+            margin_left: self.margin_left,
+            margin_right: self.margin_right,
+            scale: self.scale,
+            page_ranges: self.page_ranges.clone(),
+            header_template: self.header_template.clone(),
+            footer_template: self.footer_template.clone(),
+            emulate_media: self.emulate_media.clone(),
+            color_scheme: self.color_scheme.clone(),
+            generate_tagged_pdf: self.tagged_pdf.then_some(true),
+            generate_document_outline: self.outline.then_some(true),
+            pdf_a: self.pdf_a.then_some(true),
+            watermark: self.watermark(),
+            metadata: self.metadata(),
+            javascript_enabled: Some(!self.no_javascript),
+            block_resources: non_empty(&self.block_resources),
+            block_urls: non_empty(&self.block_urls),
+            ignore_tls_errors: Some(self.ignore_tls_errors),
+            offline: Some(self.offline),
+            bypass_cache: Some(self.bypass_cache),
+            headers: (!self.headers.is_empty())
+                .then(|| self.headers.iter().cloned().collect::<BTreeMap<_, _>>()),
+            cookies: (!self.cookies.is_empty()).then(|| self.cookies.clone()),
+            auth: self.user.clone().or_else(|| {
+                self.bearer
+                    .clone()
+                    .or_else(|| std::env::var("HTML2PDF_BEARER_TOKEN").ok())
+                    .map(|token| AuthSpec::Bearer { token })
+            }),
+            wait_for_expression: self.wait_for_expression.clone(),
+            inject_css: self.inject_css.clone(),
+            inject_js: self.inject_js.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Build an HTML request from these options.
+    fn html_request(&self, html: String) -> PdfFromHtmlRequest {
+        PdfFromHtmlRequest {
+            html,
+            filename: file_name(&self.output),
+            waitsecs: self.waitsecs,
+            landscape: Some(self.landscape),
+            print_background: Some(!self.no_background),
+            paper_format: self.paper_format.clone(),
+            paper_width: self.paper_width,
+            paper_height: self.paper_height,
+            margin_top: self.margin_top,
+            margin_bottom: self.margin_bottom,
+            margin_left: self.margin_left,
+            margin_right: self.margin_right,
+            scale: self.scale,
+            page_ranges: self.page_ranges.clone(),
+            header_template: self.header_template.clone(),
+            footer_template: self.footer_template.clone(),
+            emulate_media: self.emulate_media.clone(),
+            color_scheme: self.color_scheme.clone(),
+            generate_tagged_pdf: self.tagged_pdf.then_some(true),
+            generate_document_outline: self.outline.then_some(true),
+            pdf_a: self.pdf_a.then_some(true),
+            watermark: self.watermark(),
+            metadata: self.metadata(),
+            javascript_enabled: Some(!self.no_javascript),
+            block_resources: non_empty(&self.block_resources),
+            block_urls: non_empty(&self.block_urls),
+            ignore_tls_errors: Some(self.ignore_tls_errors),
+            offline: Some(self.offline),
+            bypass_cache: Some(self.bypass_cache),
+            wait_for_expression: self.wait_for_expression.clone(),
+            inject_css: self.inject_css.clone(),
+            inject_js: self.inject_js.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// The watermark flags as a request field; `None` without
+    /// `--watermark` or `--watermark-image`.
+    fn watermark(&self) -> Option<Watermark> {
+        if self.watermark.is_none() && self.watermark_image.is_none() {
+            return None;
+        }
+        Some(Watermark {
+            text: self.watermark.clone(),
+            image: self.watermark_image.clone(),
+            opacity: self.watermark_opacity,
+            rotation: self.watermark_rotation,
+            position: self.watermark_position.clone(),
+            ..Default::default()
+        })
+    }
+
+    /// The document properties flags as a request field; `None` when none
+    /// are given.
+    fn metadata(&self) -> Option<PdfMetadata> {
+        let metadata = PdfMetadata {
+            title: self.title.clone(),
+            author: self.author.clone(),
+            subject: self.subject.clone(),
+            keywords: non_empty(&self.keywords),
+            creation_date: self.creation_date.clone(),
+        };
+        (metadata != PdfMetadata::default()).then_some(metadata)
+    }
+}
+
+/// Parse a `--header` flag: `Name: value`.
+fn parse_header(s: &str) -> Result<(String, String), String> {
+    match s.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("invalid header {:?}: expected \"Name: value\"", s)),
+    }
+}
+
+/// Parse a `--user` flag: `username:password`.
+fn parse_basic_auth(s: &str) -> Result<AuthSpec, String> {
+    match s.split_once(':') {
+        Some((username, password)) => Ok(AuthSpec::Basic {
+            username: username.to_string(),
+            password: password.to_string(),
+        }),
+        None => Err("expected username:password".to_string()),
+    }
+}
+
+/// A repeatable list flag as a request field; `None` when not given.
+fn non_empty(values: &[String]) -> Option<Vec<String>> {
+    (!values.is_empty()).then(|| values.to_vec())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    let cli = Cli::parse();
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Dispatch a parsed command line.
+async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    match cli.command {
+        Command::Url { url, render } => {
+            let request = render.url_request(url);
+            let pool = build_pool(1, render.chrome_path.clone())?;
+            let response = render_blocking(&pool, move |pool| {
+                service::generate_pdf_from_url(pool, &request)
+            })
+            .await;
+            shutdown_pool(pool).await;
+            write_output(&render.output, &response?)
+        }
+        Command::Html { file, render } => {
+            let html = std::fs::read_to_string(&file)
+                .map_err(|e| format!("failed to read {}: {}", file.display(), e))?;
+            let request = render.html_request(html);
+            let pool = build_pool(1, render.chrome_path.clone())?;
+            let response = render_blocking(&pool, move |pool| {
+                service::generate_pdf_from_html(pool, &request)
+            })
+            .await;
+            shutdown_pool(pool).await;
+            write_output(&render.output, &response?)
+        }
+        Command::Batch {
+            manifest,
+            concurrency,
+            chrome_path,
+        } => batch::run(&manifest, concurrency, chrome_path).await,
+        Command::Serve {
+            bind,
+            framework,
+            schedule,
+        } => serve::run(bind, framework, schedule.as_deref()).await,
+        Command::Doctor { chrome_path } => {
+            let chrome_path = chrome_path.or_else(html2pdf_api::chrome_path_from_env);
+            let report = tokio::task::spawn_blocking(move || {
+                diagnostics::check_environment_with(chrome_path.as_deref())
+            })
+            .await?;
+
+            println!("{}", report);
+            if !report.is_ok() {
+                return Err("environment checks failed".into());
+            }
+            Ok(())
+        }
+    }
+}
+
+// ============================================================================
+// Pool Helpers
+// ============================================================================
+
+/// Build a small pool sized for command-line use.
+///
+/// Warmup is skipped: browsers are created on first checkout, so a single
+/// conversion only pays for one browser launch. `file://` URLs are allowed
+/// from the directories in `BROWSER_ALLOWED_FILE_DIRS`, and PDFs are signed
+/// when `PDF_SIGN_CERT` and `PDF_SIGN_KEY` are set.
+fn build_pool(
+    size: usize,
+    chrome_path: Option<String>,
+) -> Result<Arc<Mutex<BrowserPool>>, Box<dyn Error>> {
+    let allowed_file_dirs: Vec<PathBuf> = std::env::var_os("BROWSER_ALLOWED_FILE_DIRS")
+        .map(|dirs| {
+            std::env::split_paths(&dirs)
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let builder = BrowserPoolConfigBuilder::new()
+        .max_pool_size(size)
+        .warmup_count(0)
+        .allowed_file_dirs(allowed_file_dirs);
+    #[cfg(feature = "pdf-signing")]
+    let builder = match service::PdfSigner::from_env()? {
+        Some(signer) => builder.post_processor(signer),
+        None => builder,
+    };
+    let config = builder.build()?;
+
+    let factory = ChromeBrowserFactory::with_stealth(
+        chrome_path.or_else(html2pdf_api::chrome_path_from_env),
+        SandboxOptions::from_env(),
+        TrustedCa::from_env()?,
+        FontDirectory::from_env()?,
+        StealthOptions::from_env(),
+    );
+
+    let pool = BrowserPool::builder()
+        .config(config)
+        .factory(Box::new(factory))
+        .build()?;
+
+    Ok(pool.into_shared())
+}
+
+/// Run a blocking service call on the blocking thread pool.
+async fn render_blocking<F>(
+    pool: &Arc<Mutex<BrowserPool>>,
+    render: F,
+) -> Result<PdfResponse, PdfServiceError>
+where
+    F: FnOnce(&Mutex<BrowserPool>) -> Result<PdfResponse, PdfServiceError> + Send + 'static,
+{
+    let pool = Arc::clone(pool);
+
+    tokio::task::spawn_blocking(move || render(&pool))
+        .await
+        .unwrap_or_else(|e| Err(PdfServiceError::Internal(e.to_string())))
+}
+
+/// How long shutdown waits for in-flight renders to return their browsers;
+/// below Kubernetes' default 30s termination grace period.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(25);
+
+/// Shut the pool down once all outstanding work has released it.
+async fn shutdown_pool(pool: Arc<Mutex<BrowserPool>>) {
+    // Let renders still holding a browser (background jobs) finish
+    let drained = pool.lock().ok().map(|pool| pool.start_drain(DRAIN_TIMEOUT));
+    if let Some(drained) = drained {
+        drained.await;
+    }
+
+    match Arc::try_unwrap(pool) {
+        Ok(mutex) => match mutex.into_inner() {
+            Ok(mut pool) => pool.shutdown_async().await,
+            Err(e) => log::warn!("Pool lock poisoned during shutdown: {}", e),
+        },
+        Err(_) => log::warn!("Pool still shared at shutdown, relying on Drop cleanup"),
+    }
+}
+
+// ============================================================================
+// Output Helpers
+// ============================================================================
+
+/// Write the generated PDF to disk and report the result.
+fn write_output(path: &Path, response: &PdfResponse) -> Result<(), Box<dyn Error>> {
+    std::fs::write(path, &response.data)
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+
+    println!("{} ({} bytes)", path.display(), response.size());
+    Ok(())
+}
+
+/// Extract the file name component of the output path for the response metadata.
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
@@ -0,0 +1,207 @@
+//! Standalone HTTP server.
+//!
+//! `html2pdf serve` starts one of the framework integrations with its
+//! pre-built routes, using the pool configuration from `app.env` and the
+//! `BROWSER_*` environment variables (see [`html2pdf_api::init_browser_pool`]).
+//!
+//! ```text
+//! html2pdf serve --bind 0.0.0.0:8080 --framework axum
+//! ```
+//!
+//! Only frameworks whose integration feature was enabled at build time are
+//! available:
+//!
+//! | `--framework` | Required Feature |
+//! |---------------|------------------|
+//! | `axum` | `axum-integration` |
+//! | `actix` | `actix-integration` |
+//! | `rocket` | `rocket-integration` |
+//!
+//! # Schedules
+//!
+//! `--schedule schedules.yaml` also runs recurring renders (see
+//! [`ScheduledRender`] for the fields, [`CronSchedule`](html2pdf_api::service::CronSchedule) for the syntax):
+//!
+//! ```yaml
+//! schedules:
+//!   - name: nightly-report
+//!     cron: "0 2 * * *"        # UTC
+//!     url: https://example.com/report
+//!     output_dir: reports      # optional
+//!     callback_url: https://app.example.com/hooks/pdf  # optional
+//!     landscape: true
+//! ```
+//!
+//! Each run can be fetched from `GET /jobs/{name}/result` until the next
+//! one replaces it. Relative `output_dir` paths are resolved against the
+//! schedule file's directory.
+
+use std::error::Error;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+use clap::ValueEnum;
+use html2pdf_api::SharedBrowserPool;
+use html2pdf_api::service::{self, ScheduledRender};
+use serde::Deserialize;
+
+use crate::shutdown_pool;
+
+/// Web framework used to serve the pre-built routes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Framework {
+    /// Axum (`axum-integration` feature).
+    Axum,
+    /// Actix-web (`actix-integration` feature).
+    Actix,
+    /// Rocket (`rocket-integration` feature).
+    Rocket,
+}
+
+/// Schedule file document.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleFile {
+    schedules: Vec<ScheduledRender>,
+}
+
+impl ScheduleFile {
+    /// Read and parse a schedule file from disk.
+    fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+        let mut file: ScheduleFile = serde_yaml::from_str(&text)
+            .map_err(|e| format!("invalid schedule file {}: {}", path.display(), e))?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        for schedule in &mut file.schedules {
+            if let Some(dir) = schedule.output_dir.as_mut().filter(|dir| dir.is_relative()) {
+                *dir = base.join(&*dir);
+            }
+        }
+        Ok(file)
+    }
+}
+
+/// Initialize the pool from the environment and serve until interrupted.
+pub async fn run(
+    bind: SocketAddr,
+    framework: Framework,
+    schedule: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    // Fail on a bad schedule file before launching any browsers
+    let schedules = schedule.map(ScheduleFile::load).transpose()?;
+    let pool = html2pdf_api::init_browser_pool().await?;
+
+    let scheduler = match schedules {
+        Some(file) => match service::spawn_scheduler(&pool, file.schedules) {
+            Ok(scheduler) => Some(scheduler),
+            Err(e) => {
+                shutdown_pool(pool).await;
+                return Err(e.into());
+            }
+        },
+        None => None,
+    };
+
+    log::info!("Serving {:?} routes on http://{}", framework, bind);
+
+    let result = match framework {
+        Framework::Axum => serve_axum(bind, Arc::clone(&pool)).await,
+        Framework::Actix => serve_actix(bind, Arc::clone(&pool)).await,
+        Framework::Rocket => serve_rocket(bind, Arc::clone(&pool)).await,
+    };
+
+    if let Some(scheduler) = scheduler {
+        scheduler.abort();
+    }
+    shutdown_pool(pool).await;
+    result
+}
+
+/// Error returned when a framework was not compiled in.
+#[cfg(not(all(
+    feature = "axum-integration",
+    feature = "actix-integration",
+    feature = "rocket-integration"
+)))]
+fn not_enabled(feature: &str) -> Box<dyn Error> {
+    format!("this binary was built without `{feature}`; rebuild with `--features cli,{feature}`")
+        .into()
+}
+
+// ============================================================================
+// Axum
+// ============================================================================
+
+#[cfg(feature = "axum-integration")]
+async fn serve_axum(bind: SocketAddr, pool: SharedBrowserPool) -> Result<(), Box<dyn Error>> {
+    use html2pdf_api::integrations::axum::{router, shutdown_signal};
+
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    let app = router().with_state(pool);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "axum-integration"))]
+async fn serve_axum(_bind: SocketAddr, _pool: SharedBrowserPool) -> Result<(), Box<dyn Error>> {
+    Err(not_enabled("axum-integration"))
+}
+
+// ============================================================================
+// Actix-web
+// ============================================================================
+
+#[cfg(feature = "actix-integration")]
+async fn serve_actix(bind: SocketAddr, pool: SharedBrowserPool) -> Result<(), Box<dyn Error>> {
+    use actix_web::{App, HttpServer, web};
+    use html2pdf_api::integrations::actix::configure_routes;
+
+    // Actix installs its own signal handlers and stops on Ctrl+C.
+    HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(Arc::clone(&pool)))
+            .configure(configure_routes)
+    })
+    .bind(bind)?
+    .run()
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "actix-integration"))]
+async fn serve_actix(_bind: SocketAddr, _pool: SharedBrowserPool) -> Result<(), Box<dyn Error>> {
+    Err(not_enabled("actix-integration"))
+}
+
+// ============================================================================
+// Rocket
+// ============================================================================
+
+#[cfg(feature = "rocket-integration")]
+async fn serve_rocket(bind: SocketAddr, pool: SharedBrowserPool) -> Result<(), Box<dyn Error>> {
+    use html2pdf_api::integrations::rocket::configure_routes;
+
+    let figment = rocket::Config::figment()
+        .merge(("address", bind.ip()))
+        .merge(("port", bind.port()));
+
+    // Rocket installs its own signal handlers and stops on Ctrl+C.
+    let rocket = rocket::custom(figment).manage(pool);
+    configure_routes(rocket).launch().await?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "rocket-integration"))]
+async fn serve_rocket(_bind: SocketAddr, _pool: SharedBrowserPool) -> Result<(), Box<dyn Error>> {
+    Err(not_enabled("rocket-integration"))
+}
@@ -0,0 +1,214 @@
+//! Cancelling renders that are queued or in progress.
+//!
+//! A [`CancelHandle`] is shared between the code running a render and
+//! whoever may want to stop it. Cancelling takes effect wherever the render
+//! is:
+//!
+//! | Render is... | On cancel |
+//! |--------------|-----------|
+//! | Waiting in the checkout queue | Leaves the queue at once |
+//! | Loading or waiting for the page | Its tab is closed, failing the render |
+//! | Finished | Nothing; the result stands |
+//!
+//! Either way the browser goes back to the pool, so an abandoned request
+//! stops using pool capacity as soon as it is cancelled.
+//!
+//! # Example
+//!
+//! ```rust
+//! use html2pdf_api::CancelHandle;
+//!
+//! let cancel = CancelHandle::new();
+//! let for_render = cancel.clone();
+//!
+//! cancel.cancel();
+//! assert!(for_render.is_cancelled());
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use headless_chrome::Tab;
+use tokio::sync::Notify;
+
+/// Shared flag that stops a render when set.
+///
+/// Clones share the same flag. Attach one to a request (the service
+/// layer's `cancel` request field) and keep a clone to call
+/// [`cancel()`](Self::cancel) from another thread or task.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    inner: Arc<CancelState>,
+}
+
+#[derive(Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+
+    /// Wakes async renders waiting in [`CancelHandle::cancelled`].
+    notify: Notify,
+
+    /// Tab the render is currently using, closed on cancel.
+    tab: Mutex<Option<Arc<Tab>>>,
+}
+
+impl std::fmt::Debug for CancelState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancelState")
+            .field("cancelled", &self.cancelled)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CancelHandle {
+    /// Create a handle that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the render.
+    ///
+    /// Idempotent. A render waiting for a browser gives up; one in
+    /// progress has its tab closed, which fails whatever Chrome call it is
+    /// blocked on.
+    pub fn cancel(&self) {
+        if !self.inner.cancelled.swap(true, Ordering::SeqCst) {
+            self.inner.notify.notify_waiters();
+            self.close_tab();
+        }
+    }
+
+    /// Whether [`cancel()`](Self::cancel) was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the handle is cancelled.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub(crate) async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            // Register before checking, so a cancel in between is not missed
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Close the attached tab, if any.
+    fn close_tab(&self) {
+        let tab = self
+            .inner
+            .tab
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(tab) = tab {
+            log::debug!("Render cancelled, closing its tab");
+            if let Err(e) = tab.close(false) {
+                log::debug!("Failed to close cancelled tab: {}", e);
+            }
+        }
+    }
+
+    /// Record the tab the render is using, so cancelling closes it.
+    ///
+    /// If the handle is already cancelled, the tab is closed right away.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub(crate) fn attach_tab(&self, tab: &Arc<Tab>) {
+        *self
+            .inner
+            .tab
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Arc::clone(tab));
+
+        // cancel() may have run before the tab was stored
+        if self.is_cancelled() {
+            self.close_tab();
+        }
+    }
+
+    /// Whether `other` is a clone of this handle.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub(crate) fn is_same(&self, other: &CancelHandle) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Forget the attached tab once the render is done with it.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub(crate) fn detach_tab(&self) {
+        self.inner
+            .tab
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies clones share the flag and cancelling twice is harmless.
+    #[test]
+    fn test_cancel_shared_between_clones() {
+        let cancel = CancelHandle::new();
+        let clone = cancel.clone();
+        assert!(!clone.is_cancelled());
+
+        clone.cancel();
+        clone.cancel();
+        assert!(cancel.is_cancelled());
+    }
+
+    /// Verifies async waiters wake on cancel, including ones arriving late.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    #[tokio::test]
+    async fn test_cancelled_wakes_waiters() {
+        let cancel = CancelHandle::new();
+        let waiter = tokio::spawn({
+            let cancel = cancel.clone();
+            async move { cancel.cancelled().await }
+        });
+        tokio::task::yield_now().await;
+
+        cancel.cancel();
+        tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+            .await
+            .expect("Waiter should wake")
+            .unwrap();
+        cancel.cancelled().await;
+    }
+}
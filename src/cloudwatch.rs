@@ -0,0 +1,286 @@
+//! Pool and render metrics for Amazon CloudWatch.
+//!
+//! [`CloudWatchPublisher`] writes a record in the
+//! [CloudWatch Embedded Metric Format](https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/CloudWatch_Embedded_Metric_Format_Specification.html)
+//! (EMF) to stdout at a fixed interval. Lambda, ECS and EKS with the
+//! CloudWatch agent turn these log lines into metrics, so AWS deployments
+//! get dashboards and alarms without a Prometheus stack or the AWS SDK
+//! (which `PutMetricData` would need).
+//!
+//! # Metrics
+//!
+//! | Metric | Unit | Description |
+//! |--------|------|-------------|
+//! | `AvailableBrowsers` | Count | Idle browsers ready for checkout |
+//! | `ActiveBrowsers` | Count | All browsers (idle + checked out) |
+//! | `MaxPoolSize` | Count | Configured pool capacity |
+//! | `QueuedCheckouts` | Count | Checkouts waiting for a browser |
+//! | `Renders` | Count | Successful renders since the previous record |
+//! | `PdfBytes` | Bytes | PDF bytes produced since the previous record |
+//! | `RenderTime` | Milliseconds | Average time a render held a browser (omitted without renders) |
+//!
+//! Render metrics come from the pool's [`UsageTracker`](crate::UsageTracker),
+//! which the service layer records successful renders into.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use html2pdf_api::cloudwatch::CloudWatchPublisher;
+//! use std::time::Duration;
+//!
+//! let publisher = CloudWatchPublisher::new("Html2Pdf")
+//!     .dimension("Service", "pdf-renderer")
+//!     .interval(Duration::from_secs(60));
+//!
+//! let metrics = publisher.spawn(&shared_pool);
+//! // ...
+//! metrics.abort();
+//! ```
+
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{Map, Value, json};
+use tokio::task::JoinHandle;
+
+use crate::SharedBrowserPool;
+use crate::pool::BrowserPool;
+use crate::stats::PoolStats;
+use crate::usage::Usage;
+
+/// Default time between records.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically publishes pool metrics in CloudWatch Embedded Metric Format.
+///
+/// See the [module documentation](self) for the metrics.
+pub struct CloudWatchPublisher {
+    /// CloudWatch namespace the metrics are filed under.
+    namespace: String,
+
+    /// Dimension names and values attached to every metric.
+    dimensions: Vec<(String, String)>,
+
+    /// Time between records.
+    interval: Duration,
+
+    /// Destination of the records (stdout unless replaced).
+    writer: Box<dyn Write + Send>,
+
+    /// Render totals at the previous record, to report the difference.
+    last_usage: Usage,
+}
+
+impl CloudWatchPublisher {
+    /// Create a publisher filing metrics under `namespace`, once a minute,
+    /// to stdout.
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            dimensions: Vec::new(),
+            interval: DEFAULT_INTERVAL,
+            writer: Box::new(std::io::stdout()),
+            last_usage: Usage::default(),
+        }
+    }
+
+    /// Attach a dimension (e.g. service or environment name) to every metric.
+    ///
+    /// Names must not clash with the metric names.
+    pub fn dimension(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.dimensions.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the time between records (default: 60 seconds).
+    ///
+    /// CloudWatch stores standard-resolution metrics per minute, so
+    /// intervals below that only help with high-resolution alarms.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Write records to `writer` instead of stdout, e.g. a file tailed by
+    /// the CloudWatch agent.
+    pub fn writer(mut self, writer: impl Write + Send + 'static) -> Self {
+        self.writer = Box::new(writer);
+        self
+    }
+
+    /// Build one EMF record from the pool's current state.
+    ///
+    /// Render metrics cover the renders since the previous call.
+    pub fn record(&mut self, pool: &BrowserPool) -> Value {
+        let usage = pool.usage().total();
+        let since_last = Usage {
+            renders: usage.renders.saturating_sub(self.last_usage.renders),
+            bytes: usage.bytes.saturating_sub(self.last_usage.bytes),
+            browser_time: usage
+                .browser_time
+                .saturating_sub(self.last_usage.browser_time),
+        };
+        self.last_usage = usage;
+
+        self.emf(
+            &pool.stats(),
+            pool.config().max_pool_size,
+            pool.queued_checkouts(),
+            &since_last,
+            SystemTime::now(),
+        )
+    }
+
+    /// Publish a record every [`interval`](Self::interval) until the
+    /// returned task is aborted.
+    ///
+    /// The pool lock is only held while the numbers are read.
+    pub fn spawn(mut self, pool: &SharedBrowserPool) -> JoinHandle<()> {
+        let pool = SharedBrowserPool::clone(pool);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            // The first tick completes immediately; skip the empty record
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let record = match pool.lock() {
+                    Ok(pool) => self.record(&pool),
+                    Err(e) => {
+                        log::warn!("⚠️ Skipping CloudWatch metrics, pool lock failed: {}", e);
+                        continue;
+                    }
+                };
+                if let Err(e) =
+                    writeln!(self.writer, "{}", record).and_then(|_| self.writer.flush())
+                {
+                    log::warn!("⚠️ Failed to write CloudWatch metrics: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Lay out the metrics as an EMF record taken at `at`.
+    fn emf(
+        &self,
+        stats: &PoolStats,
+        max_pool_size: usize,
+        queued: usize,
+        since_last: &Usage,
+        at: SystemTime,
+    ) -> Value {
+        let mut metrics = vec![
+            ("AvailableBrowsers", "Count", json!(stats.available)),
+            ("ActiveBrowsers", "Count", json!(stats.active)),
+            ("MaxPoolSize", "Count", json!(max_pool_size)),
+            ("QueuedCheckouts", "Count", json!(queued)),
+            ("Renders", "Count", json!(since_last.renders)),
+            ("PdfBytes", "Bytes", json!(since_last.bytes)),
+        ];
+        if since_last.renders > 0 {
+            let average =
+                since_last.browser_time.as_secs_f64() * 1000.0 / since_last.renders as f64;
+            metrics.push(("RenderTime", "Milliseconds", json!(average)));
+        }
+
+        let mut record = Map::new();
+        for (name, value) in &self.dimensions {
+            record.insert(name.clone(), json!(value));
+        }
+        let definitions: Vec<Value> = metrics
+            .iter()
+            .map(|(name, unit, _)| json!({ "Name": name, "Unit": unit }))
+            .collect();
+        for (name, _, value) in metrics {
+            record.insert(name.to_string(), value);
+        }
+
+        let timestamp = at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let dimension_names: Vec<&str> = self.dimensions.iter().map(|(n, _)| n.as_str()).collect();
+        record.insert(
+            "_aws".to_string(),
+            json!({
+                "Timestamp": timestamp,
+                "CloudWatchMetrics": [{
+                    "Namespace": self.namespace,
+                    "Dimensions": [dimension_names],
+                    "Metrics": definitions,
+                }],
+            }),
+        );
+        Value::Object(record)
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies the record follows the EMF layout, with dimension and
+    /// metric values at the top level.
+    #[test]
+    fn test_emf_record() {
+        let publisher = CloudWatchPublisher::new("Html2Pdf").dimension("Service", "pdf");
+        let stats = PoolStats {
+            available: 2,
+            active: 3,
+            total: 3,
+        };
+        let since_last = Usage {
+            renders: 4,
+            bytes: 4096,
+            browser_time: Duration::from_secs(2),
+        };
+        let at = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+
+        let record = publisher.emf(&stats, 5, 1, &since_last, at);
+        let aws = &record["_aws"];
+        assert_eq!(aws["Timestamp"], 1_700_000_000_123u64);
+        let directive = &aws["CloudWatchMetrics"][0];
+        assert_eq!(directive["Namespace"], "Html2Pdf");
+        assert_eq!(directive["Dimensions"], json!([["Service"]]));
+        assert_eq!(directive["Metrics"].as_array().unwrap().len(), 7);
+        assert_eq!(
+            directive["Metrics"][6],
+            json!({ "Name": "RenderTime", "Unit": "Milliseconds" })
+        );
+
+        assert_eq!(record["Service"], "pdf");
+        assert_eq!(record["AvailableBrowsers"], 2);
+        assert_eq!(record["ActiveBrowsers"], 3);
+        assert_eq!(record["MaxPoolSize"], 5);
+        assert_eq!(record["QueuedCheckouts"], 1);
+        assert_eq!(record["Renders"], 4);
+        assert_eq!(record["PdfBytes"], 4096);
+        assert_eq!(record["RenderTime"], 500.0);
+    }
+
+    /// Verifies render time is left out of intervals without renders.
+    #[test]
+    fn test_emf_record_without_renders() {
+        let publisher = CloudWatchPublisher::new("Html2Pdf");
+        let record = publisher.emf(
+            &PoolStats {
+                available: 0,
+                active: 0,
+                total: 0,
+            },
+            5,
+            0,
+            &Usage::default(),
+            UNIX_EPOCH,
+        );
+        assert_eq!(
+            record["_aws"]["CloudWatchMetrics"][0]["Dimensions"],
+            json!([[]])
+        );
+        assert_eq!(record["Renders"], 0);
+        assert!(record.get("RenderTime").is_none());
+    }
+}
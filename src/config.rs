@@ -33,8 +33,14 @@
 //!
 //! See [`mod@env`] module for available environment variables.
 
+use headless_chrome::types::PrintToPdfOptions;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::traits::{FnPostProcessor, PdfPostProcessor, PostProcessError};
+use crate::url_policy::UrlPolicy;
+
 /// Configuration for browser pool behavior and limits.
 ///
 /// Controls pool size, browser lifecycle, and health monitoring parameters.
@@ -50,6 +56,37 @@ use std::time::Duration;
 /// | `browser_ttl` | 1 hour | Browser lifetime |
 /// | `max_ping_failures` | 3 | Failures before removal |
 /// | `warmup_timeout` | 60s | Warmup time limit |
+/// | `warmup_concurrency` | 1 | Browsers launched at once during warmup |
+/// | `ttl_jitter` | 0s | Random TTL reduction per browser |
+/// | `reuse_tabs` | false | Keep one persistent tab per browser |
+/// | `tabs_per_browser` | 1 | Renders one browser serves at once, each in its own tab |
+/// | `deep_health_check` | false | Full tab check on every checkout |
+/// | `prefetch_urls` | empty | URLs loaded ahead of time in every browser |
+/// | `prefetch_interval` | 10 min | How often prefetch URLs are reloaded |
+/// | `block_urls` | empty | URL patterns blocked in every render |
+/// | `max_redirects` | 20 | Redirects allowed for a URL render's navigation |
+/// | `allow_cross_origin_redirects` | true | Whether redirects may change origin |
+/// | `allowed_file_dirs` | empty | Directories `file://` URLs may be rendered from |
+/// | `url_policy` | allow all | Hosts and networks URL renders may reach |
+/// | `trace_dir` | none | Where `trace` requests save performance traces |
+/// | `daily_render_quota` | 0 | Renders per API key per UTC day (0 = unlimited) |
+/// | `monthly_render_quota` | 0 | Renders per API key per UTC month (0 = unlimited) |
+/// | `idempotency_window` | 5 minutes | How long `Idempotency-Key` responses are replayed (0 = disabled) |
+/// | `print_options` | zero margins, no header/footer | Base print settings that request fields override |
+/// | `coalesce_requests` | false | Share one render among identical concurrent requests |
+/// | `checkout_queue_timeout` | 0s | How long checkouts wait for a browser in a saturated pool (0 = never wait) |
+/// | `job_retention` | 1 hour | How long async job results are kept for download (0 = disabled) |
+/// | `max_pending_jobs` | 256 | Async jobs that may be running or waiting for a browser at once |
+/// | `ready_min_available` | 1 | Browsers a checkout must be able to get for `/ready` to pass |
+/// | `ready_max_queue_depth` | none | Queued checkouts above which `/ready` fails |
+/// | `ready_require_warmup` | false | Whether `/ready` fails until warmup has finished |
+/// | `recycle_after_failed_cycles` | 0 | Keep-alive cycles with every browser failing before a full pool recycle (0 = never) |
+/// | `min_free_disk_mb` | 0 | Free megabytes the temp volume needs for checkouts to succeed (0 = unchecked) |
+/// | `min_pool_size` | 0 | Idle browsers the keep-alive thread keeps ready (0 = only replace retired ones) |
+/// | `max_browser_uses` | none | Checkouts after which a browser is retired |
+/// | `response_cache_ttl` | 0s | How long identical render results are served from memory (0 = disabled) |
+/// | `response_cache_max_mb` | 64 | Megabytes of PDFs the response cache holds |
+/// | `post_processors` | empty | Steps applied to every rendered PDF, such as signing |
 ///
 /// # Example
 ///
@@ -146,6 +183,570 @@ pub struct BrowserPoolConfig {
     /// - Should be at least `warmup_count * ~5 seconds` per browser
     /// - Increase if running on slow hardware or with many warmup browsers
     pub warmup_timeout: Duration,
+
+    /// Number of browsers launched in parallel during warmup.
+    ///
+    /// With the default of 1, warmup launches browsers one at a time with a
+    /// 30-second stagger so their TTLs are offset. With more than 1, the
+    /// stagger is skipped and [`ttl_jitter`](Self::ttl_jitter) is what keeps
+    /// the browsers from expiring together.
+    ///
+    /// # Default
+    ///
+    /// 1 (sequential, staggered)
+    ///
+    /// # Considerations
+    ///
+    /// - Sequential warmup of 5 browsers takes over two minutes
+    /// - Each concurrent launch is a full Chrome startup (CPU and memory spike)
+    /// - Set `ttl_jitter` as well, or all warmup browsers retire at once
+    pub warmup_concurrency: usize,
+
+    /// Maximum random amount subtracted from each browser's TTL.
+    ///
+    /// Every browser draws its own offset in `0..=ttl_jitter` when it is
+    /// created, so browsers created together still expire at different
+    /// times.
+    ///
+    /// # Default
+    ///
+    /// 0 seconds (no jitter)
+    ///
+    /// # Considerations
+    ///
+    /// - Must be less than `browser_ttl`
+    /// - A few minutes is plenty for typical pool sizes
+    pub ttl_jitter: Duration,
+
+    /// Keep one persistent tab per browser instead of a tab per render.
+    ///
+    /// When enabled, [`BrowserHandle::checkout_tab()`](crate::BrowserHandle::checkout_tab)
+    /// hands out the browser's persistent tab, and
+    /// [`BrowserHandle::release_tab()`](crate::BrowserHandle::release_tab)
+    /// navigates it back to `about:blank` instead of closing it.
+    ///
+    /// # Default
+    ///
+    /// `false` (a fresh tab per render)
+    ///
+    /// # Considerations
+    ///
+    /// - Saves ~100ms of tab creation and ~100ms of teardown per render,
+    ///   which dominates latency for small documents
+    /// - Page state that survives navigation (e.g. service workers, cache,
+    ///   `localStorage` for the same origin) can leak between renders
+    /// - A tab that fails during a render is discarded and recreated
+    pub reuse_tabs: bool,
+
+    /// Number of renders one browser serves at the same time, each in its
+    /// own tab.
+    ///
+    /// With more than one, a checkout that finds no idle browser shares a
+    /// checked-out browser that still has a free tab before launching a
+    /// new one, and up to `max_pool_size × tabs_per_browser` renders run
+    /// at once. Chrome's per-process overhead is paid once per browser, so
+    /// 3 browsers × 3 tabs uses much less memory than 9 browsers.
+    ///
+    /// # Default
+    ///
+    /// 1 (a browser serves one render at a time)
+    ///
+    /// # Considerations
+    ///
+    /// - Tabs of one browser share its CPU time, cache, and cookies
+    /// - A browser crash fails every render running in it
+    /// - With [`reuse_tabs`](Self::reuse_tabs) only one tab per browser is
+    ///   kept between renders
+    pub tabs_per_browser: usize,
+
+    /// Run the full new_tab + navigate + close check on every checkout.
+    ///
+    /// When disabled, checkout only pings the browser with CDP
+    /// `Browser.getVersion` and leaves tab-level checks to the keep-alive
+    /// thread.
+    ///
+    /// # Default
+    ///
+    /// `false` (version ping only)
+    ///
+    /// # Considerations
+    ///
+    /// - The full check adds 150-300ms to every request, even when the
+    ///   browser is healthy
+    /// - The ping catches crashed or disconnected browsers, but not a
+    ///   browser that can no longer open tabs; such a browser fails the
+    ///   render and is caught by the next keep-alive ping
+    pub deep_health_check: bool,
+
+    /// URLs loaded ahead of time in every healthy browser.
+    ///
+    /// The keep-alive thread navigates each pooled browser to these URLs
+    /// every [`prefetch_interval`](Self::prefetch_interval), populating its
+    /// HTTP cache and JIT so recurring reports render faster at request time.
+    ///
+    /// # Default
+    ///
+    /// Empty (no prefetching)
+    ///
+    /// # Considerations
+    ///
+    /// - Each URL must be absolute (e.g. `https://reports.internal/daily`)
+    /// - Only helps pages whose resources are cacheable
+    /// - Prefetching runs on the keep-alive thread, delaying the next ping
+    ///   by the time it takes to load the list
+    /// - See `service::prewarm_url()` for one-off prefetching
+    pub prefetch_urls: Vec<String>,
+
+    /// How often the keep-alive thread reloads [`prefetch_urls`](Self::prefetch_urls).
+    ///
+    /// The first prefetch happens on the first keep-alive cycle. Browsers
+    /// created later are prefetched on the next run.
+    ///
+    /// # Default
+    ///
+    /// 10 minutes
+    ///
+    /// # Considerations
+    ///
+    /// - Must be greater than 0 when `prefetch_urls` is set
+    /// - Checked once per `ping_interval`, so shorter values have no effect
+    pub prefetch_interval: Duration,
+
+    /// URL patterns blocked in every render, in addition to the request's
+    /// own `block_urls`.
+    ///
+    /// Enforced per tab with CDP `Network.setBlockedURLs`, so third-party
+    /// trackers and analytics never load and can't receive data from
+    /// untrusted HTML. Entries are either a bare domain (`tracker.example`,
+    /// which also blocks its subdomains) or a URL pattern where `*` matches
+    /// any characters (`*://*.doubleclick.net/*`).
+    ///
+    /// # Default
+    ///
+    /// Empty (nothing blocked)
+    ///
+    /// # Considerations
+    ///
+    /// - Entries must not be blank or contain whitespace
+    /// - Blocked requests fail in the page like a network error; the
+    ///   render itself still succeeds
+    pub block_urls: Vec<String>,
+
+    /// Maximum redirects followed by a URL render's main navigation.
+    ///
+    /// A navigation that redirects more often is stopped and the render
+    /// fails with `REDIRECT_REJECTED`. Lowering this also limits how far
+    /// an open redirector can bounce a render away from the requested URL.
+    ///
+    /// # Default
+    ///
+    /// 20 (Chrome's own limit)
+    ///
+    /// # Considerations
+    ///
+    /// - Must be at most 20; values below 20 are enforced by pausing each
+    ///   main-frame document request with CDP `Fetch`
+    /// - 0 forbids redirects entirely
+    /// - Redirects of subresources and iframes are not counted
+    pub max_redirects: u32,
+
+    /// Whether the main navigation may be redirected to another origin.
+    ///
+    /// When `false`, a redirect that changes scheme, host, or port is
+    /// stopped and the render fails with `REDIRECT_REJECTED`, so an allowed
+    /// URL can't be used to reach a different site via an open redirect.
+    ///
+    /// # Default
+    ///
+    /// `true`
+    ///
+    /// # Considerations
+    ///
+    /// - `http://` to `https://` upgrades on the same host are cross-origin
+    pub allow_cross_origin_redirects: bool,
+
+    /// Directories whose files may be rendered through `file://` URLs.
+    ///
+    /// Lets batch pipelines that already have HTML on disk render it
+    /// directly, without serving it over HTTP or inlining every asset. A
+    /// `file://` URL is accepted only if the file, with symlinks resolved,
+    /// lies inside one of these directories; anything else fails with
+    /// `INVALID_URL`.
+    ///
+    /// # Default
+    ///
+    /// Empty (`file://` URLs are rejected)
+    ///
+    /// # Considerations
+    ///
+    /// - Entries must be absolute paths
+    /// - Only the page itself is checked: a local page can still load
+    ///   images and stylesheets from elsewhere on disk, so list only
+    ///   directories whose content you trust
+    pub allowed_file_dirs: Vec<PathBuf>,
+
+    /// Hosts and networks URL renders may reach.
+    ///
+    /// Services that render caller-supplied URLs should at least deny
+    /// private networks ([`UrlPolicy::public`]), or a request for
+    /// `http://169.254.169.254/` or `http://localhost/` renders the
+    /// server's own network. Forbidden URLs fail with `INVALID_URL`, or
+    /// `REDIRECT_REJECTED` when reached by a redirect.
+    ///
+    /// # Default
+    ///
+    /// [`UrlPolicy::default()`] (every host allowed)
+    ///
+    /// # Considerations
+    ///
+    /// - Document requests are checked, including redirects and iframes;
+    ///   images, scripts and `fetch()` are not. See [`crate::url_policy`]
+    ///   for the limits
+    /// - A restrictive policy pauses every document request with CDP
+    ///   `Fetch` to check where it goes
+    pub url_policy: UrlPolicy,
+
+    /// Directory where renders requested with `trace` save their
+    /// DevTools performance trace.
+    ///
+    /// Each trace is written as `<trace_id>.json`, and the pre-built
+    /// handlers return the ID in the `X-Trace-Id` header. Open the file in
+    /// `chrome://tracing` to analyze a slow page.
+    ///
+    /// # Default
+    ///
+    /// `None` (requests with `trace` are rejected with `INVALID_REQUEST`)
+    ///
+    /// # Considerations
+    ///
+    /// - Traces are several megabytes each and are never deleted; clean
+    ///   the directory up externally
+    /// - Traces contain the URLs and timing of everything the page loaded
+    /// - Tracing slows the render down somewhat
+    pub trace_dir: Option<PathBuf>,
+
+    /// Successful renders allowed per API key per UTC day.
+    ///
+    /// Once a key reaches the quota, the pre-built handlers reject its
+    /// renders with `QUOTA_EXCEEDED` (HTTP 429) until the next UTC day.
+    /// Usage is tracked per pool in [`BrowserPool::usage()`](crate::BrowserPool::usage).
+    ///
+    /// # Default
+    ///
+    /// 0 (unlimited)
+    ///
+    /// # Considerations
+    ///
+    /// - Keys come from the `X-API-Key` header and are not authenticated;
+    ///   validate them in front of the service when quotas matter
+    /// - Concurrent requests are checked before they render, so a key can
+    ///   overshoot by the number of renders it has in flight
+    /// - Counters are in memory and reset when the process restarts
+    pub daily_render_quota: u64,
+
+    /// Successful renders allowed per API key per UTC month.
+    ///
+    /// Works like [`daily_render_quota`](Self::daily_render_quota), resetting
+    /// on the first day of each UTC month.
+    ///
+    /// # Default
+    ///
+    /// 0 (unlimited)
+    pub monthly_render_quota: u64,
+
+    /// How long a render sent with an `Idempotency-Key` header is replayed.
+    ///
+    /// Within the window, repeating `POST /pdf/html` with the same key
+    /// returns the first PDF instead of rendering (and billing) it again,
+    /// so clients can safely retry after a network error.
+    ///
+    /// # Default
+    ///
+    /// 5 minutes
+    ///
+    /// # Considerations
+    ///
+    /// - Zero disables idempotency keys; the header is then ignored
+    /// - Cached PDFs are held in memory for the whole window (at most 256)
+    /// - Failed renders are not cached, so retrying them renders again
+    pub idempotency_window: Duration,
+
+    /// Base print settings for every PDF the service renders.
+    ///
+    /// Request fields (`landscape`, `print_background`) override the
+    /// matching settings here; everything else (paper size, margins,
+    /// scale, header/footer templates, ...) applies to all renders.
+    /// Set with [`BrowserPoolConfigBuilder::print_options`].
+    ///
+    /// # Default
+    ///
+    /// Zero margins, no header or footer, and Chrome's defaults otherwise
+    /// (US Letter, portrait, scale 1, backgrounds printed)
+    ///
+    /// # Considerations
+    ///
+    /// - `transfer_mode` is ignored; PDFs are always returned inline
+    /// - Thumbnails are laid out at the configured paper size
+    pub print_options: Arc<PrintToPdfOptions>,
+
+    /// Whether identical requests in flight at the same time share one render.
+    ///
+    /// When a popular report is requested many times at once, the first
+    /// request renders and the others wait for its PDF instead of each
+    /// taking a browser. See
+    /// [`render_coalesced`](crate::service::render_coalesced).
+    ///
+    /// # Default
+    ///
+    /// `false`
+    ///
+    /// # Considerations
+    ///
+    /// - Only overlapping renders are shared; nothing is cached afterward
+    /// - A request joining late gets a PDF that started rendering before it
+    ///   arrived, which matters only for pages that change second to second
+    /// - Failures are shared as well
+    pub coalesce_requests: bool,
+
+    /// How long a checkout waits for a browser when `max_pool_size`
+    /// browsers are already checked out.
+    ///
+    /// Zero keeps the default behavior: a checkout never waits and launches
+    /// an extra browser instead. Otherwise checkouts beyond
+    /// `max_pool_size` wait for a browser to be returned, served by
+    /// [`Priority`](crate::Priority) and then arrival order, and fail with
+    /// [`BrowserPoolError::CheckoutTimeout`] after this long.
+    ///
+    /// # Default
+    ///
+    /// Zero (never wait)
+    ///
+    /// # Considerations
+    ///
+    /// - Caps Chrome processes at `max_pool_size` under load, at the cost
+    ///   of latency for requests that have to wait
+    /// - Requests set their priority with the `priority` field; see the
+    ///   [`queue`](crate::queue) module
+    pub checkout_queue_timeout: Duration,
+
+    /// How long the result of an async job (`POST /jobs`) is kept.
+    ///
+    /// A finished job's PDF, or its error, can be fetched from
+    /// `GET /jobs/{id}/result` until this long after it finished, then it
+    /// is dropped. See [`submit_job`](crate::service::submit_job).
+    ///
+    /// # Default
+    ///
+    /// 1 hour
+    ///
+    /// # Considerations
+    ///
+    /// - Zero disables async jobs; `POST /jobs` is then rejected
+    /// - The default [`MemoryJobStore`](crate::service::MemoryJobStore)
+    ///   holds at most 1024 results and loses them on restart; see
+    ///   [`BrowserPoolBuilder::job_store`](crate::BrowserPoolBuilder::job_store)
+    pub job_retention: Duration,
+
+    /// Most async jobs (`POST /jobs`) queued or running at once.
+    ///
+    /// Submitting another fails with `JOB_QUEUE_FULL` (503) until one
+    /// finishes, so a burst of submissions cannot pile up unbounded work.
+    /// With a shared job store the limit covers every instance's jobs.
+    ///
+    /// # Default
+    ///
+    /// 256
+    ///
+    /// # Considerations
+    ///
+    /// - Each instance renders at most `max_pool_size × tabs_per_browser`
+    ///   jobs at once; the rest wait in the job store
+    /// - Scheduled renders do not wait in the store and are not counted
+    /// - Must be greater than 0
+    pub max_pending_jobs: usize,
+
+    /// Browsers a checkout must be able to get without waiting for the
+    /// pool to count as ready: idle ones plus those it may still launch
+    /// before reaching `max_pool_size`.
+    ///
+    /// See [`is_pool_ready`](crate::service::is_pool_ready), which backs the
+    /// `/ready` endpoints.
+    ///
+    /// # Default
+    ///
+    /// 1 (ready until every browser is checked out)
+    ///
+    /// # Considerations
+    ///
+    /// - Raise it to take an instance out of rotation while it still has
+    ///   headroom, so requests in flight to it do not queue
+    /// - Zero ignores capacity; cannot exceed `max_pool_size`
+    /// - With [`tabs_per_browser`](Self::tabs_per_browser) above 1 this
+    ///   counts tabs, including free tabs of busy browsers, and may go up
+    ///   to `max_pool_size × tabs_per_browser`
+    pub ready_min_available: usize,
+
+    /// Checkouts waiting for a browser above which the pool is not ready.
+    ///
+    /// Checkouts only wait when
+    /// [`checkout_queue_timeout`](Self::checkout_queue_timeout) is set.
+    ///
+    /// # Default
+    ///
+    /// `None` (queue depth is not checked)
+    pub ready_max_queue_depth: Option<usize>,
+
+    /// Whether the pool is not ready until
+    /// [`BrowserPool::warmup`](crate::BrowserPool::warmup) has finished.
+    ///
+    /// # Default
+    ///
+    /// `false`
+    ///
+    /// # Considerations
+    ///
+    /// - Keeps first requests off a fresh instance while it launches
+    ///   browsers
+    /// - An application that never calls `warmup()` is then never ready
+    pub ready_require_warmup: bool,
+
+    /// Consecutive keep-alive cycles in which every browser fails its
+    /// health check before the pool recycles itself.
+    ///
+    /// When all browsers fail together the cause is usually systemic (a
+    /// wedged Chrome zygote, a full `/dev/shm`), and replacing them one by
+    /// one does not help. A recycle drops every browser, including checked
+    /// out ones once they are returned, cancels pending replacements,
+    /// clears health check state, and launches `warmup_count` fresh
+    /// browsers. Recycles are counted in
+    /// [`KeepAliveStatus`](crate::KeepAliveStatus) and reported to the
+    /// hook set with
+    /// [`BrowserPoolBuilder::on_recycle`](crate::BrowserPoolBuilder::on_recycle).
+    ///
+    /// # Default
+    ///
+    /// 0 (never recycle)
+    ///
+    /// # Considerations
+    ///
+    /// - Needs the keep-alive thread; a cycle runs every `ping_interval`
+    /// - Renders in progress on the old browsers are not interrupted
+    pub recycle_after_failed_cycles: u32,
+
+    /// Free space, in megabytes, the temp directory's volume must have for
+    /// a checkout to succeed.
+    ///
+    /// Below it, stale Chrome profiles and scratch directories are removed,
+    /// and if space is still short the checkout fails with
+    /// [`BrowserPoolError::DiskFull`] (`DISK_FULL` in the HTTP API) instead
+    /// of Chrome failing to launch or crashing mid-render. See the
+    /// [`disk`](crate::disk) module.
+    ///
+    /// # Default
+    ///
+    /// 0 (not checked)
+    ///
+    /// # Considerations
+    ///
+    /// - Free space is read with `df` at most every 5 seconds; without
+    ///   `df` the check is skipped
+    /// - A few hundred megabytes covers a pool of browsers with room to spare
+    pub min_free_disk_mb: u64,
+
+    /// Idle browsers the keep-alive thread keeps ready in the pool.
+    ///
+    /// Without it, browsers are only created to replace retired or failed
+    /// ones, and a burst that checks out every idle browser makes the next
+    /// requests pay for a Chrome launch. When fewer than this many browsers
+    /// are available, each keep-alive cycle launches the difference, up to
+    /// `max_pool_size` browsers in total.
+    ///
+    /// Must be d `max_pool_size`.
+    ///
+    /// # Default
+    ///
+    /// 0 (only replace retired browsers)
+    ///
+    /// # Considerations
+    ///
+    /// - Needs the keep-alive thread; a cycle runs every `ping_interval`
+    /// - Skipped while replacement browsers are still being launched
+    pub min_pool_size: usize,
+
+    /// Checkouts after which a browser is retired and replaced.
+    ///
+    /// Chrome's memory use creeps up over many renders even with TTL
+    /// enforcement. With a ceiling set, a browser that has served this many
+    /// checkouts is retired when it is returned, like one past its TTL, and
+    /// is no longer shared with new renders.
+    ///
+    /// # Default
+    ///
+    /// `None` (browsers are retired by TTL only)
+    ///
+    /// # Considerations
+    ///
+    /// - Each retirement costs a Chrome launch; pick a ceiling that
+    ///   retires browsers every few minutes at peak load, not every render
+    /// - With `reuse_tabs`, a few hundred uses is a common starting point
+    pub max_browser_uses: Option<u32>,
+
+    /// How long a rendered PDF is served from memory to identical requests.
+    ///
+    /// Unlike [`coalesce_requests`](Self::coalesce_requests), which only
+    /// shares renders that overlap, a successful PDF is kept for this long
+    /// and repeats of the same request (URL or HTML plus every render
+    /// option) get it without touching a browser. See
+    /// [`render_cached`](crate::service::render_cached).
+    ///
+    /// # Default
+    ///
+    /// Zero (disabled)
+    ///
+    /// # Considerations
+    ///
+    /// - A URL's content may change within the TTL; keep it short for
+    ///   live pages
+    /// - Failed renders are not cached
+    /// - Hits and misses are reported by `GET /pool/stats`
+    pub response_cache_ttl: Duration,
+
+    /// Megabytes of PDFs the response cache holds.
+    ///
+    /// The oldest entries are evicted once the cached PDFs exceed this
+    /// size. PDFs larger than the whole cache are not cached.
+    ///
+    /// # Default
+    ///
+    /// 64
+    ///
+    /// # Considerations
+    ///
+    /// - Only used when `response_cache_ttl` is set
+    /// - Only applies to the in-memory cache, not to a custom
+    ///   [`PdfCache`](crate::service::PdfCache) backend
+    pub response_cache_max_mb: usize,
+
+    /// Steps applied to every rendered PDF, in order.
+    ///
+    /// Each gets the finished bytes after the request's `metadata`,
+    /// `pdf_a`, and `linearize` steps, and before `split_pages`; each step
+    /// gets the previous one's output. Add with
+    /// [`BrowserPoolConfigBuilder::post_processor`] or
+    /// [`post_processor_fn`](BrowserPoolConfigBuilder::post_processor_fn),
+    /// or set all at once with
+    /// [`post_processors`](BrowserPoolConfigBuilder::post_processors).
+    ///
+    /// # Default
+    ///
+    /// Empty (PDFs are sent as rendered)
+    ///
+    /// # Considerations
+    ///
+    /// - Streamed renders are refused while any are set
+    /// - Merged PDFs are processed once merged, and so are their parts
+    /// - A cached response is processed once, when first rendered
+    pub post_processors: Vec<Arc<dyn PdfPostProcessor>>,
 }
 
 impl Default for BrowserPoolConfig {
@@ -157,6 +758,28 @@ impl Default for BrowserPoolConfig {
     /// - TTL: 1 hour
     /// - Failure tolerance: 3 consecutive failures
     /// - Warmup timeout: 60 seconds
+    /// - Warmup concurrency: 1 (sequential)
+    /// - TTL jitter: none
+    /// - Tab reuse: disabled
+    /// - Checkout health check: version ping
+    /// - Prefetch: none (interval 10 minutes)
+    /// - URL blocklist: empty
+    /// - Redirects: up to 20, cross-origin allowed
+    /// - `file://` URLs: rejected
+    /// - Performance traces: disabled
+    /// - Render quotas: unlimited
+    /// - Idempotency window: 5 minutes
+    /// - Print options: zero margins, no header/footer
+    /// - Request coalescing: disabled
+    /// - Checkout queue: disabled
+    /// - Async job results: kept 1 hour
+    /// - Pending async jobs: at most 256
+    /// - Readiness: at least 1 browser obtainable, queue depth and warmup
+    ///   not checked
+    /// - Pool recycle on systemic failure: disabled
+    /// - Free disk space: not checked
+    /// - Minimum idle browsers: none
+    /// - Uses per browser: unlimited
     ///
     /// # Example
     ///
@@ -172,6 +795,36 @@ impl Default for BrowserPoolConfig {
     /// assert_eq!(config.browser_ttl, Duration::from_secs(3600));
     /// assert_eq!(config.max_ping_failures, 3);
     /// assert_eq!(config.warmup_timeout, Duration::from_secs(60));
+    /// assert_eq!(config.warmup_concurrency, 1);
+    /// assert_eq!(config.ttl_jitter, Duration::ZERO);
+    /// assert!(!config.reuse_tabs);
+    /// assert_eq!(config.tabs_per_browser, 1);
+    /// assert!(!config.deep_health_check);
+    /// assert!(config.prefetch_urls.is_empty());
+    /// assert_eq!(config.prefetch_interval, Duration::from_secs(600));
+    /// assert!(config.block_urls.is_empty());
+    /// assert_eq!(config.max_redirects, 20);
+    /// assert!(config.allow_cross_origin_redirects);
+    /// assert!(config.allowed_file_dirs.is_empty());
+    /// assert!(!config.url_policy.is_restricted());
+    /// assert!(config.trace_dir.is_none());
+    /// assert_eq!(config.daily_render_quota, 0);
+    /// assert_eq!(config.monthly_render_quota, 0);
+    /// assert_eq!(config.idempotency_window, Duration::from_secs(300));
+    /// assert_eq!(config.print_options.margin_top, Some(0.0));
+    /// assert!(!config.coalesce_requests);
+    /// assert!(config.checkout_queue_timeout.is_zero());
+    /// assert_eq!(config.job_retention, Duration::from_secs(3600));
+    /// assert_eq!(config.max_pending_jobs, 256);
+    /// assert_eq!(config.ready_min_available, 1);
+    /// assert!(config.ready_max_queue_depth.is_none());
+    /// assert!(!config.ready_require_warmup);
+    /// assert_eq!(config.recycle_after_failed_cycles, 0);
+    /// assert_eq!(config.min_free_disk_mb, 0);
+    /// assert_eq!(config.min_pool_size, 0);
+    /// assert!(config.max_browser_uses.is_none());
+    /// assert!(config.response_cache_ttl.is_zero());
+    /// assert_eq!(config.response_cache_max_mb, 64);
     /// ```
     fn default() -> Self {
         Self {
@@ -181,6 +834,45 @@ impl Default for BrowserPoolConfig {
             browser_ttl: Duration::from_secs(3600), // 1 hour
             max_ping_failures: 3,
             warmup_timeout: Duration::from_secs(60),
+            warmup_concurrency: 1,
+            ttl_jitter: Duration::ZERO,
+            reuse_tabs: false,
+            tabs_per_browser: 1,
+            deep_health_check: false,
+            prefetch_urls: Vec::new(),
+            prefetch_interval: Duration::from_secs(600),
+            block_urls: Vec::new(),
+            max_redirects: 20,
+            allow_cross_origin_redirects: true,
+            allowed_file_dirs: Vec::new(),
+            url_policy: UrlPolicy::default(),
+            trace_dir: None,
+            daily_render_quota: 0,
+            monthly_render_quota: 0,
+            idempotency_window: Duration::from_secs(300),
+            print_options: Arc::new(PrintToPdfOptions {
+                display_header_footer: Some(false),
+                // Zero margins for full-page output
+                margin_top: Some(0.0),
+                margin_bottom: Some(0.0),
+                margin_left: Some(0.0),
+                margin_right: Some(0.0),
+                ..Default::default()
+            }),
+            coalesce_requests: false,
+            checkout_queue_timeout: Duration::ZERO,
+            job_retention: Duration::from_secs(3600),
+            max_pending_jobs: 256,
+            ready_min_available: 1,
+            ready_max_queue_depth: None,
+            ready_require_warmup: false,
+            recycle_after_failed_cycles: 0,
+            min_free_disk_mb: 0,
+            min_pool_size: 0,
+            max_browser_uses: None,
+            response_cache_ttl: Duration::ZERO,
+            response_cache_max_mb: 64,
+            post_processors: Vec::new(),
         }
     }
 }
@@ -209,6 +901,9 @@ impl Default for BrowserPoolConfig {
 /// The [`build()`](Self::build) method validates:
 /// - `max_pool_size` must be greater than 0
 /// - `warmup_count` must be d `max_pool_size`
+/// - `warmup_concurrency` must be greater than 0
+/// - `tabs_per_browser` must be greater than 0
+/// - `ttl_jitter` must be less than `browser_ttl`
 pub struct BrowserPoolConfigBuilder {
     config: BrowserPoolConfig,
 }
@@ -375,26 +1070,878 @@ impl BrowserPoolConfigBuilder {
         self
     }
 
-    /// Build and validate the configuration.
+    /// Set how many browsers warmup launches in parallel (must be > 0).
     ///
-    /// # Errors
+    /// See [`BrowserPoolConfig::warmup_concurrency`] for details.
     ///
-    /// - Returns error if `max_pool_size` is 0
-    /// - Returns error if `warmup_count` > `max_pool_size`
+    /// # Parameters
+    ///
+    /// * `concurrency` - Browsers launched at once; 1 keeps the staggered
+    ///   sequential warmup.
     ///
     /// # Example
     ///
     /// ```rust
+    /// use std::time::Duration;
     /// use html2pdf_api::BrowserPoolConfigBuilder;
     ///
-    /// // Valid configuration
     /// let config = BrowserPoolConfigBuilder::new()
-    ///     .max_pool_size(10)
     ///     .warmup_count(5)
-    ///     .build();
-    /// assert!(config.is_ok());
+    ///     .warmup_concurrency(5)
+    ///     .ttl_jitter(Duration::from_secs(300))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.warmup_concurrency, 5);
+    /// ```
+    pub fn warmup_concurrency(mut self, concurrency: usize) -> Self {
+        self.config.warmup_concurrency = concurrency;
+        self
+    }
+
+    /// Set the maximum random TTL reduction per browser.
+    ///
+    /// See [`BrowserPoolConfig::ttl_jitter`] for details.
+    ///
+    /// # Parameters
+    ///
+    /// * `jitter` - Upper bound of the per-browser offset (must be < `browser_ttl`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .ttl_jitter(Duration::from_secs(120))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.ttl_jitter, Duration::from_secs(120));
+    /// ```
+    pub fn ttl_jitter(mut self, jitter: Duration) -> Self {
+        self.config.ttl_jitter = jitter;
+        self
+    }
+
+    /// Enable or disable persistent tab reuse.
+    ///
+    /// See [`BrowserPoolConfig::reuse_tabs`] for the trade-offs.
+    ///
+    /// # Parameters
+    ///
+    /// * `enabled` - Keep one tab per browser and reset it between renders.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .reuse_tabs(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(config.reuse_tabs);
+    /// ```
+    pub fn reuse_tabs(mut self, enabled: bool) -> Self {
+        self.config.reuse_tabs = enabled;
+        self
+    }
+
+    /// Set how many renders one browser serves at the same time.
+    ///
+    /// See [`BrowserPoolConfig::tabs_per_browser`] for the trade-offs.
+    ///
+    /// # Parameters
+    ///
+    /// * `tabs` - Concurrent renders (tabs) per browser. Must be > 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .max_pool_size(3)
+    ///     .tabs_per_browser(3)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.tabs_per_browser, 3);
+    /// ```
+    pub fn tabs_per_browser(mut self, tabs: usize) -> Self {
+        self.config.tabs_per_browser = tabs;
+        self
+    }
+
+    /// Enable or disable the full health check on checkout.
+    ///
+    /// See [`BrowserPoolConfig::deep_health_check`] for the trade-offs.
+    ///
+    /// # Parameters
+    ///
+    /// * `enabled` - Open, navigate, and close a tab before every checkout.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .deep_health_check(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(config.deep_health_check);
+    /// ```
+    pub fn deep_health_check(mut self, enabled: bool) -> Self {
+        self.config.deep_health_check = enabled;
+        self
+    }
+
+    /// Set the URLs the keep-alive thread loads ahead of time.
+    ///
+    /// # Parameters
+    ///
+    /// * `urls` - Absolute URLs rendered often enough to be worth caching.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .prefetch_urls(["https://example.com/reports/daily"])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.prefetch_urls.len(), 1);
+    /// ```
+    pub fn prefetch_urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.prefetch_urls = urls.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set how often prefetch URLs are reloaded.
+    ///
+    /// # Parameters
+    ///
+    /// * `interval` - Time between prefetch runs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .prefetch_interval(Duration::from_secs(300))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.prefetch_interval, Duration::from_secs(300));
+    /// ```
+    pub fn prefetch_interval(mut self, interval: Duration) -> Self {
+        self.config.prefetch_interval = interval;
+        self
+    }
+
+    /// Set the URL patterns blocked in every render.
+    ///
+    /// # Parameters
+    ///
+    /// * `patterns` - Bare domains or `*` wildcard URL patterns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .block_urls(["google-analytics.com", "*://*.doubleclick.net/*"])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.block_urls.len(), 2);
+    /// ```
+    pub fn block_urls<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.block_urls = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the maximum redirects a URL render's navigation may follow.
+    ///
+    /// # Parameters
+    ///
+    /// * `max` - Redirect hops allowed (0-20).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .max_redirects(3)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.max_redirects, 3);
+    /// ```
+    pub fn max_redirects(mut self, max: u32) -> Self {
+        self.config.max_redirects = max;
+        self
+    }
+
+    /// Allow or forbid redirects to a different origin.
+    ///
+    /// # Parameters
+    ///
+    /// * `allowed` - `false` to stop navigations that leave the requested origin.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .allow_cross_origin_redirects(false)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(!config.allow_cross_origin_redirects);
+    /// ```
+    pub fn allow_cross_origin_redirects(mut self, allowed: bool) -> Self {
+        self.config.allow_cross_origin_redirects = allowed;
+        self
+    }
+
+    /// Set the directories `file://` URLs may be rendered from.
+    ///
+    /// # Parameters
+    ///
+    /// * `dirs` - Absolute paths of directories holding HTML to render.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .allowed_file_dirs(["/srv/reports/html"])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.allowed_file_dirs.len(), 1);
+    /// ```
+    pub fn allowed_file_dirs<I, P>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.config.allowed_file_dirs = dirs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the hosts and networks URL renders may reach.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - Host lists and whether private networks are denied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::{BrowserPoolConfigBuilder, UrlPolicy};
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .url_policy(UrlPolicy::public().deny_host("internal.example.com"))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(config.url_policy.deny_private_networks);
+    /// ```
+    pub fn url_policy(mut self, policy: UrlPolicy) -> Self {
+        self.config.url_policy = policy;
+        self
+    }
+
+    /// Set the directory where `trace` requests save performance traces.
+    ///
+    /// # Parameters
+    ///
+    /// * `dir` - Existing directory writable by the server.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .trace_dir("/var/lib/html2pdf/traces")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(config.trace_dir.is_some());
+    /// ```
+    pub fn trace_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.trace_dir = Some(dir.into());
+        self
+    }
+
+    /// Set the successful renders allowed per API key per UTC day.
+    ///
+    /// # Parameters
+    ///
+    /// * `quota` - Renders per day, or 0 for unlimited.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .daily_render_quota(500)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.daily_render_quota, 500);
+    /// ```
+    pub fn daily_render_quota(mut self, quota: u64) -> Self {
+        self.config.daily_render_quota = quota;
+        self
+    }
+
+    /// Set the successful renders allowed per API key per UTC month.
+    ///
+    /// # Parameters
+    ///
+    /// * `quota` - Renders per month, or 0 for unlimited.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .monthly_render_quota(10_000)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.monthly_render_quota, 10_000);
+    /// ```
+    pub fn monthly_render_quota(mut self, quota: u64) -> Self {
+        self.config.monthly_render_quota = quota;
+        self
+    }
+
+    /// Set how long `Idempotency-Key` responses are replayed.
+    ///
+    /// # Parameters
+    ///
+    /// * `window` - Replay window, or zero to disable idempotency keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .idempotency_window(Duration::from_secs(60))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.idempotency_window, Duration::from_secs(60));
+    /// ```
+    pub fn idempotency_window(mut self, window: Duration) -> Self {
+        self.config.idempotency_window = window;
+        self
+    }
+
+    /// Set the base print settings for every render.
+    ///
+    /// Replaces the defaults entirely, so include zero margins again if
+    /// you still want them. Request fields override `landscape` and
+    /// `print_background`.
+    ///
+    /// # Parameters
+    ///
+    /// * `options` - Print settings; lengths are in inches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::{BrowserPoolConfigBuilder, PrintToPdfOptions};
+    ///
+    /// // A4 with 1cm margins and a page-number footer
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .print_options(PrintToPdfOptions {
+    ///         paper_width: Some(8.27),
+    ///         paper_height: Some(11.69),
+    ///         margin_top: Some(0.39),
+    ///         margin_bottom: Some(0.39),
+    ///         margin_left: Some(0.39),
+    ///         margin_right: Some(0.39),
+    ///         display_header_footer: Some(true),
+    ///         header_template: Some("<span></span>".to_string()),
+    ///         footer_template: Some(
+    ///             "<div style=\"font-size:8px;margin:auto\"><span class=\"pageNumber\"></span></div>"
+    ///                 .to_string(),
+    ///         ),
+    ///         ..Default::default()
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.print_options.paper_width, Some(8.27));
+    /// ```
+    pub fn print_options(mut self, options: PrintToPdfOptions) -> Self {
+        self.config.print_options = Arc::new(options);
+        self
+    }
+
+    /// Enable or disable sharing renders among identical concurrent requests.
+    ///
+    /// # Parameters
+    ///
+    /// * `enabled` - `true` to coalesce identical requests in flight.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .coalesce_requests(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(config.coalesce_requests);
+    /// ```
+    pub fn coalesce_requests(mut self, enabled: bool) -> Self {
+        self.config.coalesce_requests = enabled;
+        self
+    }
+
+    /// Set how long checkouts wait for a browser in a saturated pool.
+    ///
+    /// # Parameters
+    ///
+    /// * `timeout` - Longest wait, or zero to launch extra browsers instead
+    ///   of waiting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .checkout_queue_timeout(Duration::from_secs(30))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.checkout_queue_timeout, Duration::from_secs(30));
+    /// ```
+    pub fn checkout_queue_timeout(mut self, timeout: Duration) -> Self {
+        self.config.checkout_queue_timeout = timeout;
+        self
+    }
+
+    /// Set how long async job results are kept for download.
+    ///
+    /// # Parameters
+    ///
+    /// * `retention` - How long after finishing a job's result is kept, or
+    ///   zero to disable async jobs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .job_retention(Duration::from_secs(24 * 3600))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.job_retention, Duration::from_secs(86400));
+    /// ```
+    pub fn job_retention(mut self, retention: Duration) -> Self {
+        self.config.job_retention = retention;
+        self
+    }
+
+    /// Set how many async jobs may be running or waiting at once.
+    ///
+    /// # Parameters
+    ///
+    /// * `max` - Pending jobs beyond which `POST /jobs` is rejected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .max_pending_jobs(32)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.max_pending_jobs, 32);
+    /// ```
+    pub fn max_pending_jobs(mut self, max: usize) -> Self {
+        self.config.max_pending_jobs = max;
+        self
+    }
+
+    /// Set how many browsers a checkout must be able to get for the pool
+    /// to count as ready.
+    ///
+    /// # Parameters
+    ///
+    /// * `count` - Idle browsers plus those the pool may still launch, or
+    ///   zero to ignore capacity. Cannot exceed `max_pool_size`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .max_pool_size(10)
+    ///     .ready_min_available(2)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.ready_min_available, 2);
+    /// ```
+    pub fn ready_min_available(mut self, count: usize) -> Self {
+        self.config.ready_min_available = count;
+        self
+    }
+
+    /// Set how many checkouts may wait for a browser before the pool is
+    /// not ready.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .ready_max_queue_depth(5)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.ready_max_queue_depth, Some(5));
+    /// ```
+    pub fn ready_max_queue_depth(mut self, depth: usize) -> Self {
+        self.config.ready_max_queue_depth = Some(depth);
+        self
+    }
+
+    /// Set whether the pool is not ready until warmup has finished.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
     ///
-    /// // Invalid: pool size is 0
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .ready_require_warmup(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(config.ready_require_warmup);
+    /// ```
+    pub fn ready_require_warmup(mut self, required: bool) -> Self {
+        self.config.ready_require_warmup = required;
+        self
+    }
+
+    /// Set after how many consecutive keep-alive cycles with every browser
+    /// failing the pool recycles itself.
+    ///
+    /// # Parameters
+    ///
+    /// * `cycles` - Failed cycles in a row, or zero to never recycle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .recycle_after_failed_cycles(3)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.recycle_after_failed_cycles, 3);
+    /// ```
+    pub fn recycle_after_failed_cycles(mut self, cycles: u32) -> Self {
+        self.config.recycle_after_failed_cycles = cycles;
+        self
+    }
+
+    /// Set the free space the temp volume needs for checkouts to succeed.
+    ///
+    /// # Parameters
+    ///
+    /// * `megabytes` - Minimum free space, or zero to not check.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .min_free_disk_mb(500)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.min_free_disk_mb, 500);
+    /// ```
+    pub fn min_free_disk_mb(mut self, megabytes: u64) -> Self {
+        self.config.min_free_disk_mb = megabytes;
+        self
+    }
+
+    /// Set how many idle browsers the keep-alive thread keeps ready.
+    ///
+    /// # Parameters
+    ///
+    /// * `size` - Minimum available browsers, or zero to only replace
+    ///   retired ones.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .max_pool_size(5)
+    ///     .min_pool_size(2)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.min_pool_size, 2);
+    /// ```
+    pub fn min_pool_size(mut self, size: usize) -> Self {
+        self.config.min_pool_size = size;
+        self
+    }
+
+    /// Set how many checkouts a browser serves before it is retired.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .max_browser_uses(500)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.max_browser_uses, Some(500));
+    /// ```
+    pub fn max_browser_uses(mut self, uses: u32) -> Self {
+        self.config.max_browser_uses = Some(uses);
+        self
+    }
+
+    /// Set how long identical requests are served a cached PDF.
+    ///
+    /// # Parameters
+    ///
+    /// * `ttl` - How long each PDF is kept, or zero to disable the cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .response_cache_ttl(Duration::from_secs(60))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.response_cache_ttl, Duration::from_secs(60));
+    /// ```
+    pub fn response_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.config.response_cache_ttl = ttl;
+        self
+    }
+
+    /// Set how many megabytes of PDFs the response cache holds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .response_cache_ttl(Duration::from_secs(60))
+    ///     .response_cache_max_mb(256)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.response_cache_max_mb, 256);
+    /// ```
+    pub fn response_cache_max_mb(mut self, mb: usize) -> Self {
+        self.config.response_cache_max_mb = mb;
+        self
+    }
+
+    /// Add a step applied to every rendered PDF.
+    ///
+    /// Steps run in the order they are added. See [`PdfPostProcessor`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::error::Error;
+    /// use html2pdf_api::{BrowserPoolConfigBuilder, PdfPostProcessor};
+    ///
+    /// #[derive(Debug)]
+    /// struct Audit;
+    ///
+    /// impl PdfPostProcessor for Audit {
+    ///     fn name(&self) -> &str {
+    ///         "audit"
+    ///     }
+    ///
+    ///     fn process(&self, pdf: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    ///         log::info!("served a {} byte PDF", pdf.len());
+    ///         Ok(pdf)
+    ///     }
+    /// }
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .post_processor(Audit)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.post_processors[0].name(), "audit");
+    /// ```
+    pub fn post_processor(mut self, processor: impl PdfPostProcessor + 'static) -> Self {
+        self.config.post_processors.push(Arc::new(processor));
+        self
+    }
+
+    /// Add a closure as a step applied to every rendered PDF.
+    ///
+    /// Shorthand for a step that would otherwise need a type implementing
+    /// [`PdfPostProcessor`]. The closure is called for every PDF, possibly
+    /// from several threads at once.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - Names the step in logs and errors.
+    /// * `process` - Returns the PDF to send in place of the one it gets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .post_processor_fn("archive", |pdf| {
+    ///         std::fs::write(std::env::temp_dir().join("last.pdf"), &pdf)?;
+    ///         Ok(pdf)
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.post_processors[0].name(), "archive");
+    /// ```
+    pub fn post_processor_fn<F>(self, name: impl Into<String>, process: F) -> Self
+    where
+        F: Fn(Vec<u8>) -> Result<Vec<u8>, PostProcessError> + Send + Sync + 'static,
+    {
+        self.post_processor(FnPostProcessor {
+            name: name.into(),
+            process,
+        })
+    }
+
+    /// Set the whole post-processing pipeline, replacing any steps added
+    /// earlier.
+    ///
+    /// Steps run in the order given. Useful for assembling the pipeline
+    /// from configuration, or sharing one between several pools.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::sync::Arc;
+    /// use html2pdf_api::{BrowserPoolConfigBuilder, PdfPostProcessor};
+    ///
+    /// let pipeline: Vec<Arc<dyn PdfPostProcessor>> = vec![Arc::new(Archive::new(dir)), Arc::new(signer)];
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .post_processors(pipeline)
+    ///     .build()?;
+    /// ```
+    pub fn post_processors<I>(mut self, processors: I) -> Self
+    where
+        I: IntoIterator<Item = Arc<dyn PdfPostProcessor>>,
+    {
+        self.config.post_processors = processors.into_iter().collect();
+        self
+    }
+
+    /// Build and validate the configuration.
+    ///
+    /// # Errors
+    ///
+    /// - Returns error if `max_pool_size` is 0
+    /// - Returns error if `warmup_count` > `max_pool_size`
+    /// - Returns error if `min_pool_size` > `max_pool_size`
+    /// - Returns error if `max_browser_uses` is 0
+    /// - Returns error if `max_pending_jobs` is 0
+    /// - Returns error if `response_cache_ttl` is set and
+    ///   `response_cache_max_mb` is 0
+    /// - Returns error if `ready_min_available` > `max_pool_size` × `tabs_per_browser`
+    /// - Returns error if `warmup_concurrency` is 0
+    /// - Returns error if `tabs_per_browser` is 0
+    /// - Returns error if `ttl_jitter` >= `browser_ttl`
+    /// - Returns error if a `prefetch_urls` entry is not an absolute URL
+    /// - Returns error if `prefetch_urls` is set and `prefetch_interval` is 0
+    /// - Returns error if a `block_urls` entry is blank or contains whitespace
+    /// - Returns error if `max_redirects` is greater than 20
+    /// - Returns error if an `allowed_file_dirs` entry is not an absolute path
+    /// - Returns error if a `url_policy` host entry is blank or contains
+    ///   whitespace or `/`
+    /// - Returns error if `print_options` has a scale outside 0.1-2, a
+    ///   non-positive paper size, or a negative margin
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolConfigBuilder;
+    ///
+    /// // Valid configuration
+    /// let config = BrowserPoolConfigBuilder::new()
+    ///     .max_pool_size(10)
+    ///     .warmup_count(5)
+    ///     .build();
+    /// assert!(config.is_ok());
+    ///
+    /// // Invalid: pool size is 0
     /// let config = BrowserPoolConfigBuilder::new()
     ///     .max_pool_size(0)
     ///     .build();
@@ -418,6 +1965,141 @@ impl BrowserPoolConfigBuilder {
             return Err("warmup_count cannot exceed max_pool_size".to_string());
         }
 
+        // Validation: Can't keep more idle browsers than pool can hold
+        if self.config.min_pool_size > self.config.max_pool_size {
+            return Err("min_pool_size cannot exceed max_pool_size".to_string());
+        }
+
+        // Validation: A browser must serve at least one checkout
+        if self.config.max_browser_uses == Some(0) {
+            return Err("max_browser_uses must be greater than 0".to_string());
+        }
+
+        // Validation: The job queue must admit at least one job
+        if self.config.max_pending_jobs == 0 {
+            return Err("max_pending_jobs must be greater than 0".to_string());
+        }
+
+        // Validation: An enabled response cache must hold something
+        if !self.config.response_cache_ttl.is_zero() && self.config.response_cache_max_mb == 0 {
+            return Err(
+                "response_cache_max_mb must be greater than 0 when response_cache_ttl is set"
+                    .to_string(),
+            );
+        }
+
+        // Validation: A browser serves at least one render
+        if self.config.tabs_per_browser == 0 {
+            return Err("tabs_per_browser must be greater than 0".to_string());
+        }
+
+        // Validation: A pool can never have more tabs obtainable than it holds
+        if self.config.ready_min_available
+            > self
+                .config
+                .max_pool_size
+                .saturating_mul(self.config.tabs_per_browser)
+        {
+            return Err(
+                "ready_min_available cannot exceed max_pool_size * tabs_per_browser".to_string(),
+            );
+        }
+
+        // Validation: Warmup needs at least one launch slot
+        if self.config.warmup_concurrency == 0 {
+            return Err("warmup_concurrency must be greater than 0".to_string());
+        }
+
+        // Validation: Jitter must leave a positive TTL
+        if self.config.ttl_jitter >= self.config.browser_ttl {
+            return Err("ttl_jitter must be less than browser_ttl".to_string());
+        }
+
+        // Validation: Prefetch URLs must be loadable and periodically refreshed
+        if let Some(url) = self
+            .config
+            .prefetch_urls
+            .iter()
+            .find(|url| url::Url::parse(url).is_err())
+        {
+            return Err(format!("prefetch URL is not a valid absolute URL: {}", url));
+        }
+        if !self.config.prefetch_urls.is_empty() && self.config.prefetch_interval.is_zero() {
+            return Err("prefetch_interval must be greater than 0".to_string());
+        }
+
+        // Validation: Blocklist entries must be usable as URL patterns
+        if let Some(pattern) = self
+            .config
+            .block_urls
+            .iter()
+            .find(|pattern| pattern.is_empty() || pattern.contains(char::is_whitespace))
+        {
+            return Err(format!("invalid block_urls pattern: {:?}", pattern));
+        }
+
+        // Validation: Chrome never follows more than 20 redirects
+        if self.config.max_redirects > 20 {
+            return Err("max_redirects cannot exceed 20".to_string());
+        }
+
+        // Validation: File URL directories must not depend on the working directory
+        if let Some(dir) = self
+            .config
+            .allowed_file_dirs
+            .iter()
+            .find(|dir| !dir.is_absolute())
+        {
+            return Err(format!(
+                "allowed_file_dirs entry must be an absolute path: {}",
+                dir.display()
+            ));
+        }
+
+        // Validation: Host entries are bare host names, not URLs
+        let policy = &self.config.url_policy;
+        if let Some(host) = policy
+            .allowed_hosts
+            .iter()
+            .chain(&policy.denied_hosts)
+            .find(|host| {
+                host.trim().is_empty() || host.contains(|c: char| c.is_whitespace() || c == '/')
+            })
+        {
+            return Err(format!(
+                "url_policy host entry must be a bare host name: {:?}",
+                host
+            ));
+        }
+
+        // Validation: Print settings Chrome would reject at render time
+        let print = &self.config.print_options;
+        if print
+            .scale
+            .is_some_and(|scale| !(0.1..=2.0).contains(&scale))
+        {
+            return Err("print_options scale must be between 0.1 and 2".to_string());
+        }
+        if [print.paper_width, print.paper_height]
+            .into_iter()
+            .flatten()
+            .any(|size| size <= 0.0)
+        {
+            return Err("print_options paper size must be positive".to_string());
+        }
+        if [
+            print.margin_top,
+            print.margin_bottom,
+            print.margin_left,
+            print.margin_right,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|margin| margin < 0.0)
+        {
+            return Err("print_options margins cannot be negative".to_string());
+        }
+
         Ok(self.config)
     }
 }
@@ -452,7 +2134,45 @@ impl Default for BrowserPoolConfigBuilder {
 /// | `BROWSER_WARMUP_TIMEOUT_SECONDS` | u64 | 60 | Warmup timeout |
 /// | `BROWSER_PING_INTERVAL_SECONDS` | u64 | 15 | Health check interval |
 /// | `BROWSER_MAX_PING_FAILURES` | u32 | 3 | Max ping failures |
+/// | `BROWSER_WARMUP_CONCURRENCY` | usize | 1 | Parallel warmup launches |
+/// | `BROWSER_TTL_JITTER_SECONDS` | u64 | 0 | Max random TTL reduction |
+/// | `BROWSER_REUSE_TABS` | bool | false | Reuse one tab per browser |
+/// | `BROWSER_TABS_PER_BROWSER` | usize | 1 | Concurrent renders per browser |
+/// | `BROWSER_DEEP_HEALTH_CHECK` | bool | false | Full tab check on checkout |
+/// | `BROWSER_PREFETCH_URLS` | String | empty | Comma-separated URLs to prefetch |
+/// | `BROWSER_PREFETCH_INTERVAL_SECONDS` | u64 | 600 | Prefetch interval |
+/// | `BROWSER_BLOCK_URLS` | String | empty | Comma-separated domains/URL patterns to block |
+/// | `BROWSER_MAX_REDIRECTS` | u32 | 20 | Redirects allowed per URL navigation |
+/// | `BROWSER_ALLOW_CROSS_ORIGIN_REDIRECTS` | bool | true | Allow redirects to other origins |
+/// | `BROWSER_ALLOWED_FILE_DIRS` | String | empty | Directories `file://` URLs may be rendered from, separated like `PATH` |
+/// | `BROWSER_DENY_PRIVATE_NETWORKS` | bool | false | Reject URLs on private networks and `localhost` |
+/// | `BROWSER_ALLOWED_HOSTS` | String | empty | Comma-separated hosts URL renders are limited to (with subdomains) |
+/// | `BROWSER_DENIED_HOSTS` | String | empty | Comma-separated hosts URL renders may never reach (with subdomains) |
+/// | `BROWSER_TRACE_DIR` | String | none | Directory for `trace` request performance traces |
+/// | `BROWSER_DAILY_RENDER_QUOTA` | u64 | 0 | Renders per API key per UTC day (0 = unlimited) |
+/// | `BROWSER_MONTHLY_RENDER_QUOTA` | u64 | 0 | Renders per API key per UTC month (0 = unlimited) |
+/// | `BROWSER_IDEMPOTENCY_WINDOW_SECONDS` | u64 | 300 | `Idempotency-Key` replay window (0 = disabled) |
+/// | `BROWSER_COALESCE_REQUESTS` | bool | false | Share renders among identical concurrent requests |
+/// | `BROWSER_CHECKOUT_QUEUE_TIMEOUT_SECONDS` | u64 | 0 | How long checkouts wait for a browser when the pool is saturated (0 = never wait) |
+/// | `BROWSER_JOB_RETENTION_SECONDS` | u64 | 3600 | How long async job results are kept (0 = async jobs disabled) |
+/// | `BROWSER_MAX_PENDING_JOBS` | usize | 256 | Async jobs running or waiting at once |
+/// | `BROWSER_READY_MIN_AVAILABLE` | usize | 1 | Browsers a checkout must be able to get for the pool to be ready |
+/// | `BROWSER_READY_MAX_QUEUE_DEPTH` | usize | none | Queued checkouts above which the pool is not ready |
+/// | `BROWSER_READY_REQUIRE_WARMUP` | bool | false | Pool is not ready until warmup has finished |
+/// | `BROWSER_RECYCLE_AFTER_FAILED_CYCLES` | u32 | 0 | Keep-alive cycles with every browser failing before the pool recycles itself (0 = never) |
+/// | `BROWSER_MIN_FREE_DISK_MB` | u64 | 0 | Free space the temp volume needs for checkouts (0 = not checked) |
+/// | `BROWSER_MIN_POOL_SIZE` | usize | 0 | Idle browsers the keep-alive thread keeps ready (0 = only replace retired ones) |
+/// | `BROWSER_MAX_USES` | u32 | none | Checkouts after which a browser is retired |
+/// | `BROWSER_RESPONSE_CACHE_TTL_SECONDS` | u64 | 0 | How long identical render results are served from memory (0 = disabled) |
+/// | `BROWSER_RESPONSE_CACHE_MAX_MB` | usize | 64 | Megabytes of PDFs the response cache holds |
+/// | `CHROME_WS_URL` | String | none | Comma-separated DevTools WebSocket URLs to connect to instead of launching Chrome (read by [`RemoteCdpBrowserFactory::from_env`](crate::RemoteCdpBrowserFactory::from_env)) |
 /// | `CHROME_PATH` | String | auto | Custom Chrome binary path |
+/// | `CHROME_NO_SANDBOX` | bool | false | Disable Chrome's sandbox (read by [`SandboxOptions::from_env`](crate::SandboxOptions::from_env)) |
+/// | `CHROME_CA_BUNDLE` | String | none | PEM file of extra CAs to trust (read by [`TrustedCa::from_env`](crate::TrustedCa::from_env)) |
+/// | `CHROME_FONT_DIR` | String | none | Directory of extra fonts (read by [`FontDirectory::from_env`](crate::FontDirectory::from_env)) |
+/// | `CHROME_STEALTH` | bool | false | Hide automation signals (read by [`StealthOptions::from_env`](crate::StealthOptions::from_env)) |
+/// | `CHROME_USER_AGENT` | String | browser's own | User agent sent in stealth mode |
+/// | `CHROME_LANGUAGES` | String | en-US,en | Comma-separated languages sent in stealth mode |
 ///
 /// # Example `app.env` File
 ///
@@ -464,9 +2184,40 @@ impl Default for BrowserPoolConfigBuilder {
 /// BROWSER_WARMUP_TIMEOUT_SECONDS=60
 /// BROWSER_PING_INTERVAL_SECONDS=15
 /// BROWSER_MAX_PING_FAILURES=3
+/// BROWSER_WARMUP_CONCURRENCY=1
+/// BROWSER_TTL_JITTER_SECONDS=0
+/// BROWSER_REUSE_TABS=false
+/// BROWSER_TABS_PER_BROWSER=1
+/// BROWSER_DEEP_HEALTH_CHECK=false
+/// BROWSER_PREFETCH_URLS=https://example.com/reports/daily
+/// BROWSER_PREFETCH_INTERVAL_SECONDS=600
+/// BROWSER_BLOCK_URLS=google-analytics.com,*://*.doubleclick.net/*
+/// BROWSER_MAX_REDIRECTS=20
+/// BROWSER_ALLOW_CROSS_ORIGIN_REDIRECTS=true
+/// BROWSER_ALLOWED_FILE_DIRS=/srv/reports/html
+/// BROWSER_DENY_PRIVATE_NETWORKS=true
+/// BROWSER_ALLOWED_HOSTS=
+/// BROWSER_DENIED_HOSTS=internal.example.com
+/// BROWSER_TRACE_DIR=/var/lib/html2pdf/traces
+/// BROWSER_DAILY_RENDER_QUOTA=0
+/// BROWSER_MONTHLY_RENDER_QUOTA=0
+/// BROWSER_IDEMPOTENCY_WINDOW_SECONDS=300
+/// BROWSER_COALESCE_REQUESTS=false
+/// BROWSER_CHECKOUT_QUEUE_TIMEOUT_SECONDS=0
+/// BROWSER_JOB_RETENTION_SECONDS=3600
+/// BROWSER_MAX_PENDING_JOBS=256
+/// BROWSER_READY_MIN_AVAILABLE=1
+/// BROWSER_READY_MAX_QUEUE_DEPTH=10
+/// BROWSER_READY_REQUIRE_WARMUP=false
+/// BROWSER_RECYCLE_AFTER_FAILED_CYCLES=3
+/// BROWSER_MIN_FREE_DISK_MB=500
 ///
 /// # Chrome Configuration (optional)
 /// # CHROME_PATH=/usr/bin/google-chrome
+/// # CHROME_NO_SANDBOX=false
+/// # CHROME_CA_BUNDLE=/etc/ssl/internal-ca.pem
+/// # CHROME_FONT_DIR=/opt/brand/fonts
+/// # CHROME_STEALTH=false
 /// ```
 #[cfg(feature = "env-config")]
 pub mod env {
@@ -524,6 +2275,37 @@ pub mod env {
     /// - `BROWSER_WARMUP_TIMEOUT_SECONDS`: Warmup timeout (default: 60)
     /// - `BROWSER_PING_INTERVAL_SECONDS`: Health check interval (default: 15)
     /// - `BROWSER_MAX_PING_FAILURES`: Max ping failures (default: 3)
+    /// - `BROWSER_WARMUP_CONCURRENCY`: Parallel warmup launches (default: 1)
+    /// - `BROWSER_TTL_JITTER_SECONDS`: Max random TTL reduction (default: 0)
+    /// - `BROWSER_REUSE_TABS`: Reuse one tab per browser (default: false)
+    /// - `BROWSER_TABS_PER_BROWSER`: Concurrent renders per browser (default: 1)
+    /// - `BROWSER_DEEP_HEALTH_CHECK`: Full tab check on checkout (default: false)
+    /// - `BROWSER_PREFETCH_URLS`: Comma-separated URLs to prefetch (default: none)
+    /// - `BROWSER_PREFETCH_INTERVAL_SECONDS`: Prefetch interval (default: 600)
+    /// - `BROWSER_BLOCK_URLS`: Comma-separated domains/URL patterns to block (default: none)
+    /// - `BROWSER_MAX_REDIRECTS`: Redirects allowed per URL navigation (default: 20)
+    /// - `BROWSER_ALLOW_CROSS_ORIGIN_REDIRECTS`: Allow redirects to other origins (default: true)
+    /// - `BROWSER_ALLOWED_FILE_DIRS`: Directories `file://` URLs may be rendered from, separated like `PATH` (default: none)
+    /// - `BROWSER_DENY_PRIVATE_NETWORKS`: Reject URLs on private networks and `localhost` (default: false)
+    /// - `BROWSER_ALLOWED_HOSTS`: Comma-separated hosts URL renders are limited to (default: any)
+    /// - `BROWSER_DENIED_HOSTS`: Comma-separated hosts URL renders may never reach (default: none)
+    /// - `BROWSER_TRACE_DIR`: Directory for `trace` request performance traces (default: none)
+    /// - `BROWSER_DAILY_RENDER_QUOTA`: Renders per API key per UTC day (default: 0, unlimited)
+    /// - `BROWSER_MONTHLY_RENDER_QUOTA`: Renders per API key per UTC month (default: 0, unlimited)
+    /// - `BROWSER_IDEMPOTENCY_WINDOW_SECONDS`: `Idempotency-Key` replay window (default: 300)
+    /// - `BROWSER_COALESCE_REQUESTS`: Share renders among identical concurrent requests (default: false)
+    /// - `BROWSER_CHECKOUT_QUEUE_TIMEOUT_SECONDS`: How long checkouts wait for a browser when the pool is saturated (default: 0, never wait)
+    /// - `BROWSER_JOB_RETENTION_SECONDS`: How long async job results are kept (default: 3600, 0 disables async jobs)
+    /// - `BROWSER_MAX_PENDING_JOBS`: Async jobs running or waiting at once (default: 256)
+    /// - `BROWSER_READY_MIN_AVAILABLE`: Browsers a checkout must be able to get for the pool to be ready (default: 1)
+    /// - `BROWSER_READY_MAX_QUEUE_DEPTH`: Queued checkouts above which the pool is not ready (default: not checked)
+    /// - `BROWSER_READY_REQUIRE_WARMUP`: Pool is not ready until warmup has finished (default: false)
+    /// - `BROWSER_RECYCLE_AFTER_FAILED_CYCLES`: Keep-alive cycles with every browser failing before the pool recycles itself (default: 0, never)
+    /// - `BROWSER_MIN_FREE_DISK_MB`: Free space the temp volume needs for checkouts (default: 0, not checked)
+    /// - `BROWSER_MIN_POOL_SIZE`: Idle browsers the keep-alive thread keeps ready (default: 0)
+    /// - `BROWSER_MAX_USES`: Checkouts after which a browser is retired (default: unset, unlimited)
+    /// - `BROWSER_RESPONSE_CACHE_TTL_SECONDS`: Response cache TTL (default: 0, disabled)
+    /// - `BROWSER_RESPONSE_CACHE_MAX_MB`: Response cache size (default: 64)
     ///
     /// # Errors
     ///
@@ -555,39 +2337,213 @@ pub mod env {
             }
         }
 
-        let max_pool_size = std::env::var("BROWSER_POOL_SIZE")
+        let max_pool_size = std::env::var("BROWSER_POOL_SIZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(5);
+
+        let warmup_count = std::env::var("BROWSER_WARMUP_COUNT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(3);
+
+        let ttl_seconds = std::env::var("BROWSER_TTL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(3600u64);
+
+        let warmup_timeout_seconds = std::env::var("BROWSER_WARMUP_TIMEOUT_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(60u64);
+
+        let ping_interval_seconds = std::env::var("BROWSER_PING_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(15u64);
+
+        let max_ping_failures = std::env::var("BROWSER_MAX_PING_FAILURES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(3);
+
+        let warmup_concurrency = std::env::var("BROWSER_WARMUP_CONCURRENCY")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1);
+
+        let ttl_jitter_seconds = std::env::var("BROWSER_TTL_JITTER_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0u64);
+
+        let reuse_tabs = std::env::var("BROWSER_REUSE_TABS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(false);
+
+        let tabs_per_browser = std::env::var("BROWSER_TABS_PER_BROWSER")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1);
+
+        let deep_health_check = std::env::var("BROWSER_DEEP_HEALTH_CHECK")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(false);
+
+        let prefetch_urls: Vec<String> = std::env::var("BROWSER_PREFETCH_URLS")
+            .map(|s| {
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|url| !url.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let prefetch_interval_seconds = std::env::var("BROWSER_PREFETCH_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(600u64);
+
+        let block_urls: Vec<String> = std::env::var("BROWSER_BLOCK_URLS")
+            .map(|s| {
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|pattern| !pattern.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let max_redirects = std::env::var("BROWSER_MAX_REDIRECTS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(20);
+
+        let allow_cross_origin_redirects = std::env::var("BROWSER_ALLOW_CROSS_ORIGIN_REDIRECTS")
             .ok()
             .and_then(|s| s.parse().ok())
-            .unwrap_or(5);
+            .unwrap_or(true);
 
-        let warmup_count = std::env::var("BROWSER_WARMUP_COUNT")
+        let allowed_file_dirs: Vec<std::path::PathBuf> =
+            std::env::var_os("BROWSER_ALLOWED_FILE_DIRS")
+                .map(|dirs| {
+                    std::env::split_paths(&dirs)
+                        .filter(|dir| !dir.as_os_str().is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+        let deny_private_networks = std::env::var("BROWSER_DENY_PRIVATE_NETWORKS")
             .ok()
             .and_then(|s| s.parse().ok())
-            .unwrap_or(3);
+            .unwrap_or(false);
 
-        let ttl_seconds = std::env::var("BROWSER_TTL_SECONDS")
+        let host_list = |name: &str| -> Vec<String> {
+            std::env::var(name)
+                .map(|s| {
+                    s.split(',')
+                        .map(str::trim)
+                        .filter(|host| !host.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let url_policy = UrlPolicy {
+            deny_private_networks,
+            allowed_hosts: host_list("BROWSER_ALLOWED_HOSTS"),
+            denied_hosts: host_list("BROWSER_DENIED_HOSTS"),
+        };
+
+        let trace_dir = std::env::var_os("BROWSER_TRACE_DIR").filter(|dir| !dir.is_empty());
+
+        let daily_render_quota = std::env::var("BROWSER_DAILY_RENDER_QUOTA")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0u64);
+
+        let monthly_render_quota = std::env::var("BROWSER_MONTHLY_RENDER_QUOTA")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0u64);
+
+        let idempotency_window_seconds = std::env::var("BROWSER_IDEMPOTENCY_WINDOW_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(300u64);
+
+        let coalesce_requests = std::env::var("BROWSER_COALESCE_REQUESTS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(false);
+
+        let checkout_queue_timeout_seconds =
+            std::env::var("BROWSER_CHECKOUT_QUEUE_TIMEOUT_SECONDS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0u64);
+
+        let job_retention_seconds = std::env::var("BROWSER_JOB_RETENTION_SECONDS")
             .ok()
             .and_then(|s| s.parse().ok())
             .unwrap_or(3600u64);
 
-        let warmup_timeout_seconds = std::env::var("BROWSER_WARMUP_TIMEOUT_SECONDS")
+        let max_pending_jobs = std::env::var("BROWSER_MAX_PENDING_JOBS")
             .ok()
             .and_then(|s| s.parse().ok())
-            .unwrap_or(60u64);
+            .unwrap_or(256);
 
-        let ping_interval_seconds = std::env::var("BROWSER_PING_INTERVAL_SECONDS")
+        let ready_min_available = std::env::var("BROWSER_READY_MIN_AVAILABLE")
             .ok()
             .and_then(|s| s.parse().ok())
-            .unwrap_or(15u64);
+            .unwrap_or(1);
 
-        let max_ping_failures = std::env::var("BROWSER_MAX_PING_FAILURES")
+        let ready_max_queue_depth: Option<usize> = std::env::var("BROWSER_READY_MAX_QUEUE_DEPTH")
+            .ok()
+            .and_then(|s| s.parse().ok());
+
+        let ready_require_warmup = std::env::var("BROWSER_READY_REQUIRE_WARMUP")
             .ok()
             .and_then(|s| s.parse().ok())
-            .unwrap_or(3);
+            .unwrap_or(false);
+
+        let recycle_after_failed_cycles = std::env::var("BROWSER_RECYCLE_AFTER_FAILED_CYCLES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        let min_free_disk_mb = std::env::var("BROWSER_MIN_FREE_DISK_MB")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0u64);
+
+        let min_pool_size = std::env::var("BROWSER_MIN_POOL_SIZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        let max_browser_uses: Option<u32> = std::env::var("BROWSER_MAX_USES")
+            .ok()
+            .and_then(|s| s.parse().ok());
+
+        let response_cache_ttl_seconds = std::env::var("BROWSER_RESPONSE_CACHE_TTL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0u64);
+
+        let response_cache_max_mb = std::env::var("BROWSER_RESPONSE_CACHE_MAX_MB")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(64);
 
         log::info!("' Loading pool configuration from environment:");
         log::info!("   - Max pool size: {}", max_pool_size);
         log::info!("   - Warmup count: {}", warmup_count);
+        log::info!("   - Min pool size: {}", min_pool_size);
+        log::info!("   - Max uses per browser: {:?}", max_browser_uses);
         log::info!(
             "   - Browser TTL: {}s ({}min)",
             ttl_seconds,
@@ -596,16 +2552,118 @@ pub mod env {
         log::info!("   - Warmup timeout: {}s", warmup_timeout_seconds);
         log::info!("   - Ping interval: {}s", ping_interval_seconds);
         log::info!("   - Max ping failures: {}", max_ping_failures);
+        log::info!("   - Warmup concurrency: {}", warmup_concurrency);
+        log::info!("   - TTL jitter: {}s", ttl_jitter_seconds);
+        log::info!("   - Reuse tabs: {}", reuse_tabs);
+        log::info!("   - Tabs per browser: {}", tabs_per_browser);
+        log::info!("   - Deep health check: {}", deep_health_check);
+        log::info!(
+            "   - Prefetch URLs: {} (every {}s)",
+            prefetch_urls.len(),
+            prefetch_interval_seconds
+        );
+        log::info!("   - Blocked URL patterns: {}", block_urls.len());
+        log::info!(
+            "   - Max redirects: {} (cross-origin {})",
+            max_redirects,
+            if allow_cross_origin_redirects {
+                "allowed"
+            } else {
+                "forbidden"
+            }
+        );
+        log::info!("   - Allowed file:// directories: {:?}", allowed_file_dirs);
+        log::info!(
+            "   - URL policy: private networks {}, allowed hosts {:?}, denied hosts {:?}",
+            if url_policy.deny_private_networks {
+                "denied"
+            } else {
+                "allowed"
+            },
+            url_policy.allowed_hosts,
+            url_policy.denied_hosts
+        );
+        log::info!("   - Trace directory: {:?}", trace_dir);
+        log::info!(
+            "   - Render quota per key: {}/day, {}/month (0 = unlimited)",
+            daily_render_quota,
+            monthly_render_quota
+        );
+        log::info!("   - Idempotency window: {}s", idempotency_window_seconds);
+        log::info!("   - Coalesce requests: {}", coalesce_requests);
+        log::info!(
+            "   - Response cache: {}s TTL, {} MB",
+            response_cache_ttl_seconds,
+            response_cache_max_mb
+        );
+        log::info!(
+            "   - Checkout queue timeout: {}s (0 = never wait)",
+            checkout_queue_timeout_seconds
+        );
+        log::info!(
+            "   - Job result retention: {}s (0 = async jobs disabled)",
+            job_retention_seconds
+        );
+        log::info!("   - Max pending jobs: {}", max_pending_jobs);
+        log::info!(
+            "   - Readiness: {} browser(s) obtainable, max queue depth {:?}, require warmup {}",
+            ready_min_available,
+            ready_max_queue_depth,
+            ready_require_warmup
+        );
+        log::info!(
+            "   - Recycle after failed cycles: {} (0 = never)",
+            recycle_after_failed_cycles
+        );
+        log::info!(
+            "   - Min free disk: {} MB (0 = not checked)",
+            min_free_disk_mb
+        );
 
-        BrowserPoolConfigBuilder::new()
+        let mut builder = BrowserPoolConfigBuilder::new()
             .max_pool_size(max_pool_size)
             .warmup_count(warmup_count)
             .browser_ttl(Duration::from_secs(ttl_seconds))
             .warmup_timeout(Duration::from_secs(warmup_timeout_seconds))
             .ping_interval(Duration::from_secs(ping_interval_seconds))
             .max_ping_failures(max_ping_failures)
-            .build()
-            .map_err(BrowserPoolError::Configuration)
+            .warmup_concurrency(warmup_concurrency)
+            .ttl_jitter(Duration::from_secs(ttl_jitter_seconds))
+            .reuse_tabs(reuse_tabs)
+            .tabs_per_browser(tabs_per_browser)
+            .deep_health_check(deep_health_check)
+            .prefetch_urls(prefetch_urls)
+            .prefetch_interval(Duration::from_secs(prefetch_interval_seconds))
+            .block_urls(block_urls)
+            .max_redirects(max_redirects)
+            .allow_cross_origin_redirects(allow_cross_origin_redirects)
+            .allowed_file_dirs(allowed_file_dirs)
+            .url_policy(url_policy)
+            .daily_render_quota(daily_render_quota)
+            .monthly_render_quota(monthly_render_quota)
+            .idempotency_window(Duration::from_secs(idempotency_window_seconds))
+            .coalesce_requests(coalesce_requests)
+            .checkout_queue_timeout(Duration::from_secs(checkout_queue_timeout_seconds))
+            .job_retention(Duration::from_secs(job_retention_seconds))
+            .max_pending_jobs(max_pending_jobs)
+            .ready_min_available(ready_min_available)
+            .ready_require_warmup(ready_require_warmup)
+            .recycle_after_failed_cycles(recycle_after_failed_cycles)
+            .min_free_disk_mb(min_free_disk_mb)
+            .min_pool_size(min_pool_size)
+            .response_cache_ttl(Duration::from_secs(response_cache_ttl_seconds))
+            .response_cache_max_mb(response_cache_max_mb);
+        if let Some(dir) = trace_dir {
+            builder = builder.trace_dir(dir);
+        }
+        if let Some(depth) = ready_max_queue_depth {
+            builder = builder.ready_max_queue_depth(depth);
+        }
+        if let Some(uses) = max_browser_uses {
+            builder = builder.max_browser_uses(uses);
+        }
+
+        builder.build().map_err(BrowserPoolError::Configuration)
     }
 
     /// Get Chrome path from environment.
@@ -732,6 +2790,24 @@ mod tests {
             Duration::from_secs(60),
             "Default warmup timeout should be 60s"
         );
+        assert_eq!(
+            config.warmup_concurrency, 1,
+            "Default warmup should be sequential"
+        );
+        assert_eq!(
+            config.ttl_jitter,
+            Duration::ZERO,
+            "Default jitter should be 0"
+        );
+        assert!(!config.reuse_tabs, "Tab reuse should be opt-in");
+        assert!(
+            !config.deep_health_check,
+            "Checkout should use the cheap version ping by default"
+        );
+        assert!(
+            config.prefetch_urls.is_empty(),
+            "Prefetching should be opt-in"
+        );
     }
 
     /// Verifies that config builder supports method chaining.
@@ -747,6 +2823,8 @@ mod tests {
             .browser_ttl(Duration::from_secs(1800))
             .max_ping_failures(5)
             .warmup_timeout(Duration::from_secs(90))
+            .reuse_tabs(true)
+            .deep_health_check(true)
             .build()
             .unwrap();
 
@@ -757,6 +2835,365 @@ mod tests {
         assert_eq!(config.browser_ttl.as_secs(), 1800);
         assert_eq!(config.max_ping_failures, 5);
         assert_eq!(config.warmup_timeout.as_secs(), 90);
+        assert!(config.reuse_tabs);
+        assert!(config.deep_health_check);
+    }
+
+    /// Verifies validation of warmup concurrency and TTL jitter.
+    #[test]
+    fn test_config_warmup_concurrency_and_jitter_validation() {
+        let result = BrowserPoolConfigBuilder::new()
+            .warmup_concurrency(0)
+            .build();
+        assert!(result.is_err(), "Zero warmup concurrency should fail");
+
+        let result = BrowserPoolConfigBuilder::new()
+            .browser_ttl(Duration::from_secs(600))
+            .ttl_jitter(Duration::from_secs(600))
+            .build();
+        assert!(result.is_err(), "Jitter equal to TTL should fail");
+
+        let config = BrowserPoolConfigBuilder::new()
+            .warmup_concurrency(3)
+            .browser_ttl(Duration::from_secs(600))
+            .ttl_jitter(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        assert_eq!(config.warmup_concurrency, 3);
+        assert_eq!(config.ttl_jitter, Duration::from_secs(60));
+    }
+
+    /// Verifies validation of prefetch URLs and interval.
+    #[test]
+    fn test_config_prefetch_validation() {
+        let result = BrowserPoolConfigBuilder::new()
+            .prefetch_urls(["/reports/daily"])
+            .build();
+        assert!(result.is_err(), "Relative prefetch URL should fail");
+
+        let result = BrowserPoolConfigBuilder::new()
+            .prefetch_urls(["https://example.com/reports/daily"])
+            .prefetch_interval(Duration::ZERO)
+            .build();
+        assert!(result.is_err(), "Zero prefetch interval should fail");
+
+        let config = BrowserPoolConfigBuilder::new()
+            .prefetch_interval(Duration::ZERO)
+            .build()
+            .unwrap();
+        assert!(
+            config.prefetch_urls.is_empty(),
+            "Interval is ignored without URLs"
+        );
+    }
+
+    /// Verifies `allowed_file_dirs` entries must be absolute.
+    #[test]
+    fn test_config_allowed_file_dirs_validation() {
+        let result = BrowserPoolConfigBuilder::new()
+            .allowed_file_dirs(["reports/html"])
+            .build();
+        assert!(result.is_err(), "Relative directory should fail");
+
+        let config = BrowserPoolConfigBuilder::new()
+            .allowed_file_dirs(["/srv/reports/html"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.allowed_file_dirs,
+            vec![PathBuf::from("/srv/reports/html")]
+        );
+    }
+
+    /// Verifies `url_policy` host entries must be bare host names.
+    #[test]
+    fn test_config_url_policy_validation() {
+        for host in ["", "https://example.com", "example.com/path", "a b"] {
+            let result = BrowserPoolConfigBuilder::new()
+                .url_policy(UrlPolicy::default().allow_host(host))
+                .build();
+            assert!(result.is_err(), "{:?} should fail", host);
+        }
+
+        let config = BrowserPoolConfigBuilder::new()
+            .url_policy(UrlPolicy::public().deny_host("*.internal.example.com"))
+            .build()
+            .unwrap();
+        assert!(config.url_policy.is_restricted());
+    }
+
+    /// Verifies validation of URL blocklist patterns.
+    #[test]
+    fn test_config_block_urls_validation() {
+        let result = BrowserPoolConfigBuilder::new().block_urls([""]).build();
+        assert!(result.is_err(), "Blank pattern should fail");
+
+        let result = BrowserPoolConfigBuilder::new()
+            .block_urls(["tracker.example /x"])
+            .build();
+        assert!(result.is_err(), "Pattern with whitespace should fail");
+
+        let config = BrowserPoolConfigBuilder::new()
+            .block_urls(["tracker.example", "*://*.ads.example/*"])
+            .build()
+            .unwrap();
+        assert_eq!(config.block_urls.len(), 2);
+    }
+
+    /// Verifies the redirect limit can't exceed Chrome's own.
+    #[test]
+    fn test_config_max_redirects_validation() {
+        let result = BrowserPoolConfigBuilder::new().max_redirects(21).build();
+        assert!(result.is_err(), "More than 20 redirects should fail");
+
+        let config = BrowserPoolConfigBuilder::new()
+            .max_redirects(0)
+            .allow_cross_origin_redirects(false)
+            .build()
+            .unwrap();
+        assert_eq!(config.max_redirects, 0);
+        assert!(!config.allow_cross_origin_redirects);
+    }
+
+    /// Verifies render quotas default to unlimited and can be set.
+    #[test]
+    fn test_config_render_quotas() {
+        let config = BrowserPoolConfigBuilder::new().build().unwrap();
+        assert_eq!(config.daily_render_quota, 0);
+        assert_eq!(config.monthly_render_quota, 0);
+
+        let config = BrowserPoolConfigBuilder::new()
+            .daily_render_quota(100)
+            .monthly_render_quota(2000)
+            .build()
+            .unwrap();
+        assert_eq!(config.daily_render_quota, 100);
+        assert_eq!(config.monthly_render_quota, 2000);
+    }
+
+    /// Verifies the idempotency window can be disabled.
+    #[test]
+    fn test_config_idempotency_window() {
+        let config = BrowserPoolConfigBuilder::new()
+            .idempotency_window(Duration::ZERO)
+            .build()
+            .unwrap();
+        assert!(config.idempotency_window.is_zero());
+    }
+
+    /// Verifies the checkout queue is disabled by default.
+    #[test]
+    fn test_config_checkout_queue_timeout() {
+        assert!(
+            BrowserPoolConfig::default()
+                .checkout_queue_timeout
+                .is_zero()
+        );
+
+        let config = BrowserPoolConfigBuilder::new()
+            .checkout_queue_timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        assert_eq!(config.checkout_queue_timeout, Duration::from_secs(5));
+    }
+
+    /// Verifies async job results can be kept longer or disabled.
+    #[test]
+    fn test_config_job_retention() {
+        let config = BrowserPoolConfigBuilder::new()
+            .job_retention(Duration::ZERO)
+            .build()
+            .unwrap();
+        assert!(config.job_retention.is_zero());
+    }
+
+    /// Verifies the job queue bound is configurable and must be positive.
+    #[test]
+    fn test_config_max_pending_jobs() {
+        let config = BrowserPoolConfigBuilder::new()
+            .max_pending_jobs(8)
+            .build()
+            .unwrap();
+        assert_eq!(config.max_pending_jobs, 8);
+
+        let result = BrowserPoolConfigBuilder::new().max_pending_jobs(0).build();
+        assert_eq!(
+            result.unwrap_err(),
+            "max_pending_jobs must be greater than 0"
+        );
+    }
+
+    /// Verifies readiness thresholds default to the old criterion and are
+    /// validated against the pool size.
+    #[test]
+    fn test_config_readiness_thresholds() {
+        let config = BrowserPoolConfigBuilder::new()
+            .max_pool_size(4)
+            .ready_min_available(4)
+            .ready_max_queue_depth(0)
+            .ready_require_warmup(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.ready_min_available, 4);
+        assert_eq!(config.ready_max_queue_depth, Some(0));
+        assert!(config.ready_require_warmup);
+
+        let result = BrowserPoolConfigBuilder::new()
+            .max_pool_size(4)
+            .ready_min_available(5)
+            .build();
+        assert!(result.is_err());
+
+        // Every tab slot counts when browsers serve several renders
+        let config = BrowserPoolConfigBuilder::new()
+            .max_pool_size(4)
+            .tabs_per_browser(2)
+            .ready_min_available(8)
+            .build()
+            .unwrap();
+        assert_eq!(config.ready_min_available, 8);
+    }
+
+    /// Verifies a browser must serve at least one render at a time.
+    #[test]
+    fn test_config_tabs_per_browser() {
+        let result = BrowserPoolConfigBuilder::new().tabs_per_browser(0).build();
+        assert_eq!(
+            result.unwrap_err(),
+            "tabs_per_browser must be greater than 0"
+        );
+
+        let config = BrowserPoolConfigBuilder::new()
+            .tabs_per_browser(3)
+            .build()
+            .unwrap();
+        assert_eq!(config.tabs_per_browser, 3);
+    }
+
+    /// Verifies the minimum idle count must fit in the pool.
+    #[test]
+    fn test_config_min_pool_size() {
+        let result = BrowserPoolConfigBuilder::new()
+            .max_pool_size(3)
+            .min_pool_size(4)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            "min_pool_size cannot exceed max_pool_size"
+        );
+
+        let config = BrowserPoolConfigBuilder::new()
+            .max_pool_size(3)
+            .min_pool_size(3)
+            .build()
+            .unwrap();
+        assert_eq!(config.min_pool_size, 3);
+    }
+
+    /// Verifies a browser must be allowed at least one checkout.
+    #[test]
+    fn test_config_max_browser_uses() {
+        let result = BrowserPoolConfigBuilder::new().max_browser_uses(0).build();
+        assert_eq!(
+            result.unwrap_err(),
+            "max_browser_uses must be greater than 0"
+        );
+
+        let config = BrowserPoolConfigBuilder::new()
+            .max_browser_uses(100)
+            .build()
+            .unwrap();
+        assert_eq!(config.max_browser_uses, Some(100));
+    }
+
+    /// Verifies an enabled response cache needs room for PDFs.
+    #[test]
+    fn test_config_response_cache() {
+        let result = BrowserPoolConfigBuilder::new()
+            .response_cache_ttl(Duration::from_secs(60))
+            .response_cache_max_mb(0)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            "response_cache_max_mb must be greater than 0 when response_cache_ttl is set"
+        );
+
+        // Size is irrelevant while the cache is disabled
+        let config = BrowserPoolConfigBuilder::new()
+            .response_cache_max_mb(0)
+            .build()
+            .unwrap();
+        assert!(config.response_cache_ttl.is_zero());
+    }
+
+    /// Verifies post-processors keep their order, closures become steps,
+    /// and setting the pipeline replaces it.
+    #[test]
+    fn test_config_post_processors() {
+        assert!(BrowserPoolConfig::default().post_processors.is_empty());
+
+        let config = BrowserPoolConfigBuilder::new()
+            .post_processor_fn("compress", Ok)
+            .post_processor_fn("stamp", |mut pdf| {
+                pdf.extend_from_slice(b"%stamped");
+                Ok(pdf)
+            })
+            .build()
+            .unwrap();
+        let names: Vec<&str> = config.post_processors.iter().map(|p| p.name()).collect();
+        assert_eq!(names, ["compress", "stamp"]);
+        assert_eq!(
+            config.post_processors[1].process(b"%PDF".to_vec()).unwrap(),
+            b"%PDF%stamped"
+        );
+        assert!(format!("{:?}", config.post_processors[1]).contains("stamp"));
+
+        let config = BrowserPoolConfigBuilder::new()
+            .post_processor_fn("compress", Ok)
+            .post_processors(config.post_processors[1..].to_vec())
+            .build()
+            .unwrap();
+        assert_eq!(config.post_processors.len(), 1);
+        assert_eq!(config.post_processors[0].name(), "stamp");
+    }
+
+    /// Verifies print options replace the defaults and are validated.
+    #[test]
+    fn test_config_print_options() {
+        let config = BrowserPoolConfigBuilder::new().build().unwrap();
+        assert_eq!(config.print_options.display_header_footer, Some(false));
+        assert_eq!(config.print_options.margin_left, Some(0.0));
+
+        let config = BrowserPoolConfigBuilder::new()
+            .print_options(PrintToPdfOptions {
+                margin_top: Some(0.5),
+                scale: Some(0.8),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        assert_eq!(config.print_options.margin_top, Some(0.5));
+        assert_eq!(config.print_options.margin_left, None);
+
+        for invalid in [
+            PrintToPdfOptions {
+                scale: Some(3.0),
+                ..Default::default()
+            },
+            PrintToPdfOptions {
+                paper_width: Some(0.0),
+                ..Default::default()
+            },
+            PrintToPdfOptions {
+                margin_bottom: Some(-1.0),
+                ..Default::default()
+            },
+        ] {
+            let result = BrowserPoolConfigBuilder::new()
+                .print_options(invalid)
+                .build();
+            assert!(result.is_err());
+        }
     }
 
     /// Verifies that BrowserPoolConfigBuilder implements Default.
@@ -0,0 +1,697 @@
+//! Environment diagnostics.
+//!
+//! This module provides [`check_environment()`], which verifies that the
+//! host can actually run the browser pool and produce PDFs. It is the
+//! library side of the `html2pdf doctor` command and is handy as a
+//! startup self-test in containers.
+//!
+//! # Checks
+//!
+//! | Check | What It Verifies | Failure Means |
+//! |-------|------------------|---------------|
+//! | `chrome-binary` | Chrome/Chromium can be located | Will be downloaded on first launch |
+//! | `browser-launch` | A throwaway browser starts and reports its version | Pool cannot create browsers |
+//! | `test-render` | A small page renders to a valid PDF | PDF generation will fail |
+//! | `fonts` | Glyphs exist for common non-Latin scripts | Some text will render as boxes |
+//! | `font-families` | Families in `CHROME_REQUIRED_FONTS` resolve to an installed font | Branded documents fall back to a default font |
+//! | `sandbox` | Sandbox settings vs. user namespaces / container setup | Chrome runs unsandboxed, or cannot start sandboxed |
+//! | `memory` | cgroup memory limit leaves room for browsers | Browsers may be OOM-killed |
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use html2pdf_api::diagnostics::check_environment;
+//!
+//! let report = check_environment();
+//! println!("{}", report);
+//!
+//! if !report.is_ok() {
+//!     std::process::exit(1);
+//! }
+//! ```
+//!
+//! # Blocking Behavior
+//!
+//! [`check_environment()`] launches Chrome and renders a page, so it blocks
+//! for a few seconds. Call it from `spawn_blocking` in async code.
+
+use std::fmt;
+use std::path::Path;
+use std::time::Instant;
+
+use headless_chrome::Browser;
+
+use crate::factory::{BrowserFactory, ChromeBrowserFactory, FontDirectory, SandboxOptions};
+
+/// Text samples used to probe font coverage, keyed by script name.
+const FONT_SAMPLES: &[(&str, &str)] = &[
+    ("Chinese", "中文字体测试"),
+    ("Japanese", "ひらがなカタカナ"),
+    ("Korean", "한국어 글꼴"),
+    ("Arabic", "العربية"),
+    ("Hebrew", "עברית"),
+    ("Devanagari", "हिन्दी"),
+    ("Thai", "ภาษาไทย"),
+    ("Cyrillic", "Кириллица"),
+];
+
+/// Memory limit below which the `memory` check warns (1 GiB).
+const MIN_RECOMMENDED_MEMORY_BYTES: u64 = 1024 * 1024 * 1024;
+
+// ============================================================================
+// Report Types
+// ============================================================================
+
+/// Outcome of a single diagnostic check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// The check passed.
+    Pass,
+    /// The environment works, but something may cause problems.
+    Warn,
+    /// The environment cannot render PDFs until this is fixed.
+    Fail,
+    /// The check could not run because an earlier check failed.
+    Skipped,
+}
+
+impl CheckStatus {
+    /// Short symbol used when printing a report.
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Pass => "✅",
+            Self::Warn => "⚠️",
+            Self::Fail => "❌",
+            Self::Skipped => "⏭️",
+        }
+    }
+}
+
+/// Result of a single diagnostic check.
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// Stable identifier of the check (e.g. `"browser-launch"`).
+    pub name: &'static str,
+
+    /// Outcome of the check.
+    pub status: CheckStatus,
+
+    /// What was observed.
+    pub detail: String,
+
+    /// Suggested fix, when the check did not pass.
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+/// Collected results of [`check_environment()`].
+///
+/// Implements [`Display`](fmt::Display) as a human-readable checklist.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsReport {
+    /// Individual check results, in the order they ran.
+    pub checks: Vec<Check>,
+}
+
+impl DiagnosticsReport {
+    /// Returns `true` if no check failed.
+    ///
+    /// Warnings do not make a report fail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::diagnostics::DiagnosticsReport;
+    ///
+    /// assert!(DiagnosticsReport::default().is_ok());
+    /// ```
+    pub fn is_ok(&self) -> bool {
+        !self.checks.iter().any(|c| c.status == CheckStatus::Fail)
+    }
+
+    /// Returns the checks with the given status.
+    pub fn with_status(&self, status: CheckStatus) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(move |c| c.status == status)
+    }
+
+    /// Look up a check by name.
+    pub fn get(&self, name: &str) -> Option<&Check> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+}
+
+impl fmt::Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(
+                f,
+                "{} {:<15} {}",
+                check.status.symbol(),
+                check.name,
+                check.detail
+            )?;
+            if let Some(hint) = &check.hint {
+                writeln!(f, "   → {}", hint)?;
+            }
+        }
+
+        let count = |status| self.with_status(status).count();
+        write!(
+            f,
+            "\n{} passed, {} warnings, {} failed, {} skipped",
+            count(CheckStatus::Pass),
+            count(CheckStatus::Warn),
+            count(CheckStatus::Fail),
+            count(CheckStatus::Skipped)
+        )
+    }
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Run all environment checks using `CHROME_PATH` or auto-detection.
+///
+/// See the [module documentation](self) for the list of checks.
+///
+/// # Blocking Behavior
+///
+/// **This function blocks** while Chrome launches and renders a test page.
+pub fn check_environment() -> DiagnosticsReport {
+    let chrome_path = std::env::var("CHROME_PATH").ok();
+    check_environment_with(chrome_path.as_deref())
+}
+
+/// Run all environment checks against a specific Chrome binary.
+///
+/// Sandbox settings and the font directory are read with
+/// [`SandboxOptions::from_env`] and [`FontDirectory::from_env`], matching
+/// how [`init_browser_pool`](crate::init_browser_pool) launches Chrome.
+/// The `font-families` check verifies the comma-separated families in
+/// `CHROME_REQUIRED_FONTS`.
+///
+/// # Parameters
+///
+/// * `chrome_path` - Chrome/Chromium binary to test, or `None` to auto-detect.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use html2pdf_api::diagnostics::check_environment_with;
+///
+/// let report = check_environment_with(Some("/usr/bin/chromium"));
+/// assert!(report.is_ok(), "{}", report);
+/// ```
+pub fn check_environment_with(chrome_path: Option<&str>) -> DiagnosticsReport {
+    let mut report = DiagnosticsReport::default();
+
+    report.checks.push(check_chrome_binary(chrome_path));
+
+    let sandbox = SandboxOptions::from_env();
+    let required_fonts = required_font_families();
+    let (fonts, fonts_error) = match FontDirectory::from_env() {
+        Ok(fonts) => (fonts, None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let factory =
+        ChromeBrowserFactory::with_fonts(chrome_path.map(str::to_string), sandbox, None, fonts);
+
+    match launch_browser(&factory) {
+        Ok((browser, check)) => {
+            report.checks.push(check);
+            report.checks.push(check_test_render(&browser));
+            report.checks.push(check_fonts(&browser));
+            report.checks.push(match fonts_error {
+                Some(e) => Check::new("font-families", CheckStatus::Fail, e)
+                    .with_hint("Fix CHROME_FONT_DIR or unset it to use only the system fonts"),
+                None => check_font_families(&browser, &required_fonts),
+            });
+        }
+        Err(check) => {
+            report.checks.push(check);
+            for name in ["test-render", "fonts", "font-families"] {
+                report.checks.push(Check::new(
+                    name,
+                    CheckStatus::Skipped,
+                    "browser could not be launched",
+                ));
+            }
+        }
+    }
+
+    report.checks.push(check_sandbox(&sandbox));
+    report.checks.push(check_memory());
+
+    for check in &report.checks {
+        match check.status {
+            CheckStatus::Fail => log::error!("❌ Diagnostics {}: {}", check.name, check.detail),
+            CheckStatus::Warn => log::warn!("⚠️ Diagnostics {}: {}", check.name, check.detail),
+            _ => log::debug!("Diagnostics {}: {}", check.name, check.detail),
+        }
+    }
+
+    report
+}
+
+// ============================================================================
+// Individual Checks
+// ============================================================================
+
+/// Locate the Chrome binary.
+fn check_chrome_binary(chrome_path: Option<&str>) -> Check {
+    const NAME: &str = "chrome-binary";
+
+    match chrome_path {
+        Some(path) if Path::new(path).is_file() => Check::new(NAME, CheckStatus::Pass, path),
+        Some(path) => Check::new(NAME, CheckStatus::Fail, format!("{} does not exist", path))
+            .with_hint("Fix CHROME_PATH or unset it to auto-detect Chrome"),
+        None => match headless_chrome::browser::default_executable() {
+            Ok(path) => Check::new(NAME, CheckStatus::Pass, path.display().to_string()),
+            Err(e) => Check::new(NAME, CheckStatus::Warn, format!("not found ({})", e)).with_hint(
+                "Chromium will be downloaded on first launch (~170MB); \
+                     install Chrome or set CHROME_PATH to avoid the download",
+            ),
+        },
+    }
+}
+
+/// Launch a throwaway browser and read its version.
+fn launch_browser(factory: &ChromeBrowserFactory) -> Result<(Browser, Check), Check> {
+    const NAME: &str = "browser-launch";
+
+    let start = Instant::now();
+    let browser = factory.create().map_err(|e| {
+        let message = e.to_string();
+        let check = Check::new(NAME, CheckStatus::Fail, message.clone());
+        match launch_failure_hint(&message) {
+            Some(hint) => check.with_hint(hint),
+            None => check,
+        }
+    })?;
+
+    let detail = match browser.get_version() {
+        Ok(version) => format!("{} (started in {:?})", version.product, start.elapsed()),
+        Err(e) => format!("started in {:?}, version unknown ({})", start.elapsed(), e),
+    };
+
+    Ok((browser, Check::new(NAME, CheckStatus::Pass, detail)))
+}
+
+/// Render a small page and verify the output is a PDF.
+fn check_test_render(browser: &Browser) -> Check {
+    const NAME: &str = "test-render";
+
+    let start = Instant::now();
+    let result = browser.new_tab().and_then(|tab| {
+        tab.navigate_to("data:text/html,<html><body><h1>html2pdf doctor</h1></body></html>")?
+            .wait_until_navigated()?;
+        let pdf = tab.print_to_pdf(None)?;
+        let _ = tab.close(true);
+        Ok(pdf)
+    });
+
+    match result {
+        Ok(pdf) if pdf.starts_with(b"%PDF-") => Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{} bytes in {:?}", pdf.len(), start.elapsed()),
+        ),
+        Ok(pdf) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("output is not a PDF ({} bytes)", pdf.len()),
+        ),
+        Err(e) => Check::new(NAME, CheckStatus::Fail, e.to_string())
+            .with_hint("Run with RUST_LOG=html2pdf_api=debug for Chrome launch details"),
+    }
+}
+
+/// Detect scripts whose glyphs fall back to "tofu" boxes.
+///
+/// Measures each sample in a canvas and compares it with a run of the same
+/// length of an unassigned code point. Equal widths mean every character
+/// was drawn with the missing-glyph box.
+fn check_fonts(browser: &Browser) -> Check {
+    const NAME: &str = "fonts";
+
+    let samples = FONT_SAMPLES
+        .iter()
+        .map(|(name, text)| format!("[{:?},{:?}]", name, text))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let script = format!(
+        r#"(() => {{
+            const ctx = document.createElement('canvas').getContext('2d');
+            ctx.font = '32px sans-serif';
+            const missing = [];
+            for (const [name, text] of [{samples}]) {{
+                const tofu = ctx.measureText('\u0378'.repeat([...text].length)).width;
+                if (Math.abs(ctx.measureText(text).width - tofu) < 0.5) missing.push(name);
+            }}
+            return missing.join(',');
+        }})()"#
+    );
+
+    let result = browser.new_tab().and_then(|tab| {
+        tab.navigate_to("data:text/html,<html><body></body></html>")?
+            .wait_until_navigated()?;
+        let value = tab.evaluate(&script, false)?.value;
+        let _ = tab.close(true);
+        Ok(value)
+    });
+
+    let missing = match result {
+        Ok(value) => value
+            .as_ref()
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        Err(e) => {
+            return Check::new(
+                NAME,
+                CheckStatus::Warn,
+                format!("could not probe fonts ({})", e),
+            );
+        }
+    };
+
+    if missing.is_empty() {
+        Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{} scripts covered", FONT_SAMPLES.len()),
+        )
+    } else {
+        Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("missing glyphs for: {}", missing.replace(',', ", ")),
+        )
+        .with_hint(
+            "Install a broad font set, e.g. `apt-get install fonts-noto fonts-noto-cjk` \
+             or `apk add font-noto font-noto-cjk`",
+        )
+    }
+}
+
+/// Verify that each required font family resolves to an installed font.
+///
+/// A family is missing when text set in it measures the same as the
+/// generic fallback for every generic family tried, i.e. Chrome substituted
+/// the fallback font.
+fn check_font_families(browser: &Browser, families: &[String]) -> Check {
+    const NAME: &str = "font-families";
+
+    if families.is_empty() {
+        return Check::new(NAME, CheckStatus::Pass, "no required families configured")
+            .with_hint("Set CHROME_REQUIRED_FONTS to the families your templates use");
+    }
+
+    let list = families
+        .iter()
+        .map(|family| format!("{:?}", family))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let script = format!(
+        r#"(() => {{
+            const ctx = document.createElement('canvas').getContext('2d');
+            const sample = 'mmmmmmmmmmlli1WQ@#';
+            const width = (font) => {{ ctx.font = font; return ctx.measureText(sample).width; }};
+            const missing = [];
+            for (const family of [{list}]) {{
+                const quoted = '"' + family.replace(/["\\]/g, '\\$&') + '"';
+                const found = ['serif', 'sans-serif', 'monospace'].some(
+                    (generic) => width(`32px ${{quoted}}, ${{generic}}`) !== width(`32px ${{generic}}`));
+                if (!found) missing.push(family);
+            }}
+            return missing.join('\n');
+        }})()"#
+    );
+
+    let result = browser.new_tab().and_then(|tab| {
+        tab.navigate_to("data:text/html,<html><body></body></html>")?
+            .wait_until_navigated()?;
+        let value = tab.evaluate(&script, false)?.value;
+        let _ = tab.close(true);
+        Ok(value)
+    });
+
+    let missing = match result {
+        Ok(value) => value
+            .as_ref()
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        Err(e) => {
+            return Check::new(
+                NAME,
+                CheckStatus::Warn,
+                format!("could not probe font families ({})", e),
+            );
+        }
+    };
+
+    if missing.is_empty() {
+        Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("all resolve: {}", families.join(", ")),
+        )
+    } else {
+        Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("not installed: {}", missing.replace('\n', ", ")),
+        )
+        .with_hint(
+            "Install the fonts, or put the font files in a directory and set CHROME_FONT_DIR to it",
+        )
+    }
+}
+
+/// Report the sandbox settings and whether the host supports them (Linux only).
+fn check_sandbox(sandbox: &SandboxOptions) -> Check {
+    const NAME: &str = "sandbox";
+
+    if !cfg!(target_os = "linux") {
+        return Check::new(NAME, CheckStatus::Pass, "not applicable on this platform");
+    }
+
+    let userns_disabled = read_trimmed("/proc/sys/kernel/unprivileged_userns_clone")
+        .is_some_and(|v| v == "0")
+        || read_trimmed("/proc/sys/user/max_user_namespaces").is_some_and(|v| v == "0");
+
+    let in_container = Path::new("/.dockerenv").exists()
+        || read_trimmed("/proc/1/cgroup")
+            .is_some_and(|v| v.contains("docker") || v.contains("kubepods"));
+
+    let mut detail = String::from(if sandbox.is_disabled() {
+        "Chrome sandbox disabled (CHROME_NO_SANDBOX)"
+    } else {
+        "Chrome sandbox enabled"
+    });
+    if in_container {
+        detail.push_str("; running in a container");
+    }
+
+    if sandbox.is_disabled() {
+        Check::new(NAME, CheckStatus::Warn, detail).with_hint(
+            "Only render trusted content, or run Chrome as a non-root user \
+             with the sandbox enabled",
+        )
+    } else if userns_disabled {
+        Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{}; unprivileged user namespaces are disabled", detail),
+        )
+        .with_hint(
+            "Enable user namespaces (sysctl kernel.unprivileged_userns_clone=1), \
+             or set CHROME_NO_SANDBOX=true if Chrome cannot start",
+        )
+    } else {
+        Check::new(NAME, CheckStatus::Pass, detail)
+    }
+}
+
+/// Warn when the cgroup memory limit is too small for a browser pool.
+fn check_memory() -> Check {
+    const NAME: &str = "memory";
+
+    let limit = read_trimmed("/sys/fs/cgroup/memory.max")
+        .or_else(|| read_trimmed("/sys/fs/cgroup/memory/memory.limit_in_bytes"))
+        .and_then(|raw| parse_cgroup_limit(&raw));
+
+    match limit {
+        Some(bytes) if bytes < MIN_RECOMMENDED_MEMORY_BYTES => Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("cgroup memory limit is {} MiB", bytes / (1024 * 1024)),
+        )
+        .with_hint(
+            "Each Chrome instance typically needs 100-300 MiB; \
+             lower BROWSER_POOL_SIZE or raise the container memory limit",
+        ),
+        Some(bytes) => Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("cgroup memory limit is {} MiB", bytes / (1024 * 1024)),
+        ),
+        None => Check::new(NAME, CheckStatus::Pass, "no cgroup memory limit detected"),
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Parse a cgroup memory limit, treating `max` and huge v1 sentinels as unlimited.
+fn parse_cgroup_limit(raw: &str) -> Option<u64> {
+    let bytes = raw.trim().parse::<u64>().ok()?;
+    // cgroup v1 reports "unlimited" as a page-aligned i64::MAX
+    (bytes < (1 << 62)).then_some(bytes)
+}
+
+/// Map common Chrome launch errors to an actionable hint.
+fn launch_failure_hint(message: &str) -> Option<&'static str> {
+    if message.contains("error while loading shared libraries") {
+        Some(
+            "Chrome's shared libraries are missing; install them, e.g. \
+             `apt-get install libnss3 libatk-bridge2.0-0 libgbm1 libasound2`",
+        )
+    } else if message.contains("No usable sandbox") {
+        Some(
+            "Chrome's sandbox cannot start here; enable user namespaces, \
+             or set CHROME_NO_SANDBOX=true",
+        )
+    } else if message.contains("running as root") || message.contains("sandbox(false)") {
+        Some(
+            "Chrome cannot run sandboxed as root; run as a non-root user or set CHROME_NO_SANDBOX=true",
+        )
+    } else if message.contains("Permission denied") {
+        Some("The Chrome binary is not executable by this user; check file permissions")
+    } else if message.contains("Could not auto detect") || message.contains("No such file") {
+        Some("Install Chrome/Chromium or set CHROME_PATH to its location")
+    } else {
+        None
+    }
+}
+
+/// Font families listed in `CHROME_REQUIRED_FONTS`.
+fn required_font_families() -> Vec<String> {
+    std::env::var("CHROME_REQUIRED_FONTS")
+        .map(|value| parse_font_families(&value))
+        .unwrap_or_default()
+}
+
+/// Split a comma-separated family list, dropping quotes and blanks.
+fn parse_font_families(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|family| family.trim().trim_matches(|c| c == '"' || c == '\''))
+        .filter(|family| !family.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies cgroup limits parse and "unlimited" sentinels are ignored.
+    #[test]
+    fn test_parse_cgroup_limit() {
+        assert_eq!(parse_cgroup_limit("536870912"), Some(536_870_912));
+        assert_eq!(parse_cgroup_limit("max"), None);
+        assert_eq!(parse_cgroup_limit("9223372036854771712"), None);
+    }
+
+    /// Verifies known launch errors produce hints.
+    #[test]
+    fn test_launch_failure_hint() {
+        assert!(
+            launch_failure_hint("chrome: error while loading shared libraries: libnss3.so")
+                .is_some()
+        );
+        assert!(
+            launch_failure_hint("You need to set the sandbox(false) option when running as root")
+                .is_some()
+        );
+        assert!(launch_failure_hint("something unexpected").is_none());
+    }
+
+    /// Verifies a report with only warnings is still OK.
+    #[test]
+    fn test_report_is_ok() {
+        let mut report = DiagnosticsReport::default();
+        report
+            .checks
+            .push(Check::new("fonts", CheckStatus::Warn, "missing Thai"));
+        assert!(report.is_ok());
+
+        report
+            .checks
+            .push(Check::new("browser-launch", CheckStatus::Fail, "boom"));
+        assert!(!report.is_ok());
+        assert_eq!(report.with_status(CheckStatus::Fail).count(), 1);
+        assert!(report.get("fonts").is_some());
+    }
+
+    /// Verifies the report renders hints and totals.
+    #[test]
+    fn test_report_display() {
+        let report = DiagnosticsReport {
+            checks: vec![Check::new("memory", CheckStatus::Warn, "512 MiB").with_hint("raise it")],
+        };
+        let text = report.to_string();
+        assert!(text.contains("memory"));
+        assert!(text.contains("→ raise it"));
+        assert!(text.contains("1 warnings"));
+    }
+
+    /// Verifies required font families parse from a CSS-like list.
+    #[test]
+    fn test_parse_font_families() {
+        assert_eq!(
+            parse_font_families("Brand Sans, \"Brand Serif\" ,, 'Noto Sans CJK JP'"),
+            vec!["Brand Sans", "Brand Serif", "Noto Sans CJK JP"]
+        );
+        assert!(parse_font_families(" ").is_empty());
+    }
+
+    /// Verifies a missing explicit Chrome path fails the binary check.
+    #[test]
+    fn test_check_chrome_binary_missing_path() {
+        let check = check_chrome_binary(Some("/definitely/not/chrome"));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.hint.is_some());
+    }
+}
@@ -0,0 +1,324 @@
+//! Free space checks and cleanup for Chrome's temporary files.
+//!
+//! Every pooled browser gets a fresh profile directory under the system
+//! temp directory (`rust-headless-chrome-profile*`), and Chrome keeps
+//! scratch files there too (`.org.chromium.Chromium.*`,
+//! `.com.google.Chrome.*`). When Chrome is killed these are left behind,
+//! and a full temp volume then shows up as random, cryptic launch failures.
+//!
+//! With [`min_free_disk_mb`](crate::BrowserPoolConfig::min_free_disk_mb)
+//! set, the pool checks free space before each checkout. Below the
+//! threshold it first removes stale leftovers with
+//! [`clean_stale_profiles()`], and if that does not free enough, the
+//! checkout fails with [`BrowserPoolError::DiskFull`].
+//!
+//! Free space is read with `df -Pk`, so it works wherever a POSIX `df` is
+//! installed; elsewhere the check is skipped.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use html2pdf_api::disk::{clean_stale_profiles, free_space};
+//!
+//! let tmp = std::env::temp_dir();
+//! if free_space(&tmp).is_some_and(|free| free < 500 * 1024 * 1024) {
+//!     let removed = clean_stale_profiles(&tmp);
+//!     println!("removed {} stale Chrome directories", removed);
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::error::{BrowserPoolError, Result};
+
+/// Prefix of the profile directories `headless_chrome` creates.
+pub(crate) const PROFILE_PREFIX: &str = "rust-headless-chrome-profile";
+
+/// Prefixes of Chrome's own scratch directories in the temp directory.
+const SCRATCH_PREFIXES: &[&str] = &[".org.chromium.Chromium.", ".com.google.Chrome."];
+
+/// Age after which a profile without a live lock holder is stale.
+///
+/// Chrome takes the lock within seconds of starting, so only a directory
+/// from a launch that never got that far is this old without one.
+const UNLOCKED_PROFILE_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// Age after which Chrome's scratch directories are considered abandoned.
+const SCRATCH_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// How long a free space reading is reused before `df` runs again.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Bytes available to unprivileged users on the volume holding `dir`.
+///
+/// Returns `None` if `df` is missing or its output cannot be read.
+pub fn free_space(dir: &Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(dir).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_df_available(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Remove Chrome profile and scratch directories left behind in `dir`.
+///
+/// A profile directory is stale when the process holding its
+/// `SingletonLock` no longer runs on this host (checked on Linux), or when
+/// it has no lock and is older than ten minutes. Profiles locked by another
+/// host or container sharing `dir` are kept. Scratch directories are removed
+/// once they are an hour old. Directories of running browsers are kept.
+///
+/// # Returns
+///
+/// The number of directories removed.
+pub fn clean_stale_profiles(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let path = entry.path();
+        if !entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            continue;
+        }
+
+        let stale = if name.starts_with(PROFILE_PREFIX) {
+            is_stale_profile(&path)
+        } else if SCRATCH_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        {
+            older_than(&path, SCRATCH_AGE)
+        } else {
+            false
+        };
+
+        if stale {
+            match std::fs::remove_dir_all(&path) {
+                Ok(()) => {
+                    log::info!("🧹 Removed stale Chrome directory {}", path.display());
+                    removed += 1;
+                }
+                Err(e) => log::warn!("⚠️ Failed to remove {}: {}", path.display(), e),
+            }
+        }
+    }
+    removed
+}
+
+/// Whether a profile directory no longer belongs to a running browser.
+fn is_stale_profile(path: &Path) -> bool {
+    match std::fs::read_link(path.join("SingletonLock")) {
+        Ok(target) => target
+            .to_str()
+            .is_some_and(|target| is_stale_lock(target, local_hostname().as_deref())),
+        Err(_) => older_than(path, UNLOCKED_PROFILE_AGE),
+    }
+}
+
+/// Whether a `SingletonLock` target, `{hostname}-{pid}`, names a process of
+/// this host that no longer runs.
+///
+/// A lock taken on another host is never stale: a temp directory shared
+/// with another container has its own PID namespace, so its PIDs say
+/// nothing about processes here.
+fn is_stale_lock(target: &str, hostname: Option<&str>) -> bool {
+    let Some((host, pid)) = target.rsplit_once('-') else {
+        return false;
+    };
+    hostname == Some(host) && pid.parse().is_ok_and(|pid| !process_running(pid))
+}
+
+/// This host's name as Chrome writes it into `SingletonLock`, or `None`
+/// if it can't be read.
+fn local_hostname() -> Option<String> {
+    let hostname = std::fs::read_to_string("/proc/sys/kernel/hostname").ok()?;
+    let hostname = hostname.trim();
+    (!hostname.is_empty()).then(|| hostname.to_string())
+}
+
+/// Whether a process with `pid` exists, or `true` if that can't be told.
+fn process_running(pid: u32) -> bool {
+    let proc = Path::new("/proc");
+    !proc.is_dir() || proc.join(pid.to_string()).exists()
+}
+
+/// Whether `path` was last modified more than `age` ago.
+fn older_than(path: &Path, age: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|elapsed| elapsed > age)
+}
+
+/// The `Available` column of `df -Pk` output, in bytes.
+fn parse_df_available(output: &str) -> Option<u64> {
+    // Filesystem 1024-blocks Used Available Capacity Mounted on
+    let line = output.lines().nth(1)?;
+    let kib: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Refuses checkouts while the temp volume is nearly full.
+///
+/// Owned by the pool; see the [module documentation](self).
+pub(crate) struct DiskGuard {
+    /// Directory whose volume is checked (the system temp directory).
+    dir: PathBuf,
+
+    /// Threshold in bytes; zero disables the guard.
+    min_free_bytes: u64,
+
+    /// Last reading and when it was taken (`None` free space = unknown).
+    last: Mutex<Option<(Instant, Option<u64>)>>,
+}
+
+impl DiskGuard {
+    /// Create a guard requiring `min_free_mb` megabytes in the temp
+    /// directory's volume.
+    pub(crate) fn new(min_free_mb: u64) -> Self {
+        Self {
+            dir: std::env::temp_dir(),
+            min_free_bytes: min_free_mb * 1024 * 1024,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Fail with [`BrowserPoolError::DiskFull`] if free space is below the
+    /// threshold even after cleaning up.
+    pub(crate) fn check(&self) -> Result<()> {
+        if self.min_free_bytes == 0 {
+            return Ok(());
+        }
+
+        let free = self.free_bytes();
+        match free {
+            Some(free) if free < self.min_free_bytes => Err(BrowserPoolError::DiskFull(format!(
+                "{} MB free in {} (minimum {} MB)",
+                free / (1024 * 1024),
+                self.dir.display(),
+                self.min_free_bytes / (1024 * 1024)
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Free space, measured at most every [`CHECK_INTERVAL`]. A low
+    /// reading triggers a cleanup and is then measured again.
+    fn free_bytes(&self) -> Option<u64> {
+        let mut last = self.last.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((at, free)) = *last {
+            if at.elapsed() < CHECK_INTERVAL {
+                return free;
+            }
+        }
+
+        let mut free = free_space(&self.dir);
+        if free.is_some_and(|free| free < self.min_free_bytes) {
+            log::warn!(
+                "⚠️ Low disk space in {}: {:?} bytes free, cleaning up stale Chrome directories",
+                self.dir.display(),
+                free
+            );
+            if clean_stale_profiles(&self.dir) > 0 {
+                free = free_space(&self.dir);
+            }
+        }
+        *last = Some((Instant::now(), free));
+        free
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies the available column is read from POSIX `df` output.
+    #[test]
+    fn test_parse_df_available() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      /dev/sda1         41152736  30012212   9027040      77% /\n";
+        assert_eq!(parse_df_available(output), Some(9027040 * 1024));
+        assert_eq!(parse_df_available("Filesystem\n"), None);
+        assert_eq!(parse_df_available(""), None);
+    }
+
+    /// Verifies only abandoned Chrome directories are removed.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_clean_stale_profiles() {
+        let dir = std::env::temp_dir().join(format!("html2pdf-disk-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let host = local_hostname().unwrap();
+
+        // Lock held by a process that no longer exists
+        let dead = dir.join(format!("{}dead", PROFILE_PREFIX));
+        std::fs::create_dir(&dead).unwrap();
+        std::os::unix::fs::symlink(format!("{}-4294967", host), dead.join("SingletonLock"))
+            .unwrap();
+
+        // Lock held by this process
+        let live = dir.join(format!("{}live", PROFILE_PREFIX));
+        std::fs::create_dir(&live).unwrap();
+        std::os::unix::fs::symlink(
+            format!("{}-{}", host, std::process::id()),
+            live.join("SingletonLock"),
+        )
+        .unwrap();
+
+        // Lock held by another host sharing the directory, whose PIDs
+        // can't be checked from here
+        let remote = dir.join(format!("{}remote", PROFILE_PREFIX));
+        std::fs::create_dir(&remote).unwrap();
+        std::os::unix::fs::symlink(
+            format!("other-{}-4294967", host),
+            remote.join("SingletonLock"),
+        )
+        .unwrap();
+
+        // Fresh, without a lock yet, and an unrelated directory
+        std::fs::create_dir(dir.join(format!("{}new", PROFILE_PREFIX))).unwrap();
+        std::fs::create_dir(dir.join("other")).unwrap();
+
+        assert_eq!(clean_stale_profiles(&dir), 1);
+        assert!(!dead.exists());
+        assert!(live.exists());
+        assert!(remote.exists());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 4);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Verifies a lock is only stale for a dead process of this host.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_is_stale_lock() {
+        let pid = std::process::id();
+        assert!(is_stale_lock("web-1-4294967", Some("web-1")));
+        assert!(!is_stale_lock(&format!("web-1-{}", pid), Some("web-1")));
+        assert!(!is_stale_lock("web-2-4294967", Some("web-1")));
+        assert!(!is_stale_lock("web-1-4294967", None));
+        assert!(!is_stale_lock("web-1-x", Some("web-1")));
+        assert!(!is_stale_lock("4294967", Some("web-1")));
+    }
+
+    /// Verifies a zero threshold never touches the disk.
+    #[test]
+    fn test_disabled_guard() {
+        let guard = DiskGuard::new(0);
+        assert!(guard.check().is_ok());
+        assert!(guard.last.lock().unwrap().is_none());
+    }
+}
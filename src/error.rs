@@ -38,9 +38,21 @@
 ///         BrowserPoolError::HealthCheckFailed(msg) => {
 ///             eprintln!("Health check failed: {}", msg);
 ///         }
+///         BrowserPoolError::TabCreation(msg) => {
+///             eprintln!("Tab creation failed: {}", msg);
+///         }
 ///         BrowserPoolError::ShuttingDown => {
 ///             eprintln!("Pool is shutting down");
 ///         }
+///         BrowserPoolError::CheckoutTimeout(waited) => {
+///             eprintln!("No browser freed up within {:?}", waited);
+///         }
+///         BrowserPoolError::Cancelled => {
+///             eprintln!("Checkout was cancelled");
+///         }
+///         BrowserPoolError::DiskFull(msg) => {
+///             eprintln!("Not enough disk space: {}", msg);
+///         }
 ///         BrowserPoolError::Configuration(msg) => {
 ///             eprintln!("Configuration error: {}", msg);
 ///         }
@@ -104,6 +116,23 @@ pub enum BrowserPoolError {
     #[error("Browser health check failed: {0}")]
     HealthCheckFailed(String),
 
+    /// Failed to open a tab in a checked-out browser.
+    ///
+    /// Returned by [`BrowserHandle::checkout_tab()`](crate::BrowserHandle::checkout_tab).
+    /// The browser process is usually dead or unresponsive; it will be
+    /// caught by the next health check.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolError;
+    ///
+    /// let error = BrowserPoolError::TabCreation("connection closed".to_string());
+    /// println!("{}", error); // "Failed to create tab: connection closed"
+    /// ```
+    #[error("Failed to create tab: {0}")]
+    TabCreation(String),
+
     /// Operation attempted during pool shutdown.
     ///
     /// All operations are rejected once shutdown begins.
@@ -129,6 +158,73 @@ pub enum BrowserPoolError {
     #[error("Pool is shutting down")]
     ShuttingDown,
 
+    /// Timed out waiting for a browser in a saturated pool.
+    ///
+    /// Only returned when
+    /// [`checkout_queue_timeout`](crate::BrowserPoolConfig::checkout_queue_timeout)
+    /// is set: checkouts beyond `max_pool_size` wait for a browser to be
+    /// returned, and give up after the configured timeout (the value
+    /// carried here).
+    ///
+    /// # Handling
+    ///
+    /// The pool is overloaded. Retry later, or raise `max_pool_size` or
+    /// the timeout if this happens regularly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolError;
+    /// use std::time::Duration;
+    ///
+    /// let error = BrowserPoolError::CheckoutTimeout(Duration::from_secs(30));
+    /// println!("{}", error); // "Timed out after 30s waiting for a browser"
+    /// ```
+    #[error("Timed out after {0:?} waiting for a browser")]
+    CheckoutTimeout(std::time::Duration),
+
+    /// The checkout was cancelled while waiting for a browser.
+    ///
+    /// Returned when the render's [`CancelHandle`](crate::CancelHandle)
+    /// is cancelled while it waits in the checkout queue.
+    ///
+    /// # Handling
+    ///
+    /// Someone asked for the work to stop; don't retry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolError;
+    ///
+    /// let error = BrowserPoolError::Cancelled;
+    /// println!("{}", error); // "Checkout was cancelled"
+    /// ```
+    #[error("Checkout was cancelled")]
+    Cancelled,
+
+    /// The temp volume has less free space than
+    /// [`min_free_disk_mb`](crate::BrowserPoolConfig::min_free_disk_mb).
+    ///
+    /// Checked before each checkout, after removing stale Chrome profiles;
+    /// see the [`disk`](crate::disk) module. Chrome needs room for its
+    /// profile and scratch files, and fails in obscure ways without it.
+    ///
+    /// # Handling
+    ///
+    /// Free space on the server (or enlarge the volume), then retry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use html2pdf_api::BrowserPoolError;
+    ///
+    /// let error = BrowserPoolError::DiskFull("12 MB free in /tmp (minimum 200 MB)".to_string());
+    /// println!("{}", error); // "Not enough disk space: 12 MB free in /tmp (minimum 200 MB)"
+    /// ```
+    #[error("Not enough disk space: {0}")]
+    DiskFull(String),
+
     /// Invalid configuration provided.
     ///
     /// This error occurs when pool configuration values are invalid.
@@ -259,9 +355,27 @@ mod tests {
             "Browser health check failed: ping failed"
         );
 
+        let error = BrowserPoolError::TabCreation("connection closed".to_string());
+        assert_eq!(error.to_string(), "Failed to create tab: connection closed");
+
         let error = BrowserPoolError::ShuttingDown;
         assert_eq!(error.to_string(), "Pool is shutting down");
 
+        let error = BrowserPoolError::CheckoutTimeout(std::time::Duration::from_secs(30));
+        assert_eq!(
+            error.to_string(),
+            "Timed out after 30s waiting for a browser"
+        );
+
+        let error = BrowserPoolError::Cancelled;
+        assert_eq!(error.to_string(), "Checkout was cancelled");
+
+        let error = BrowserPoolError::DiskFull("12 MB free in /tmp".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Not enough disk space: 12 MB free in /tmp"
+        );
+
         let error = BrowserPoolError::Configuration("bad config".to_string());
         assert_eq!(error.to_string(), "Configuration error: bad config");
     }
@@ -21,12 +21,777 @@
 //! // Or specify custom path
 //! let factory = ChromeBrowserFactory::with_path("/usr/bin/google-chrome".to_string());
 //! ```
+//!
+//! # Sandboxing
+//!
+//! Chrome's sandbox is **enabled** by default. Containers that cannot
+//! provide it (e.g. Chrome running as root, or user namespaces disabled)
+//! opt out explicitly with [`SandboxOptions`], and every factory created
+//! that way logs a warning:
+//!
+//! ```rust,ignore
+//! use html2pdf_api::{ChromeBrowserFactory, SandboxOptions};
+//!
+//! let factory = ChromeBrowserFactory::with_sandbox(None, SandboxOptions::default().no_sandbox(true));
+//! ```
+//!
+//! # Private Certificate Authorities
+//!
+//! Internal sites signed by a private CA can be trusted with [`TrustedCa`]
+//! instead of ignoring TLS errors altogether:
+//!
+//! ```rust,ignore
+//! use html2pdf_api::{ChromeBrowserFactory, SandboxOptions, TrustedCa};
+//!
+//! let ca = TrustedCa::from_pem_file("/etc/ssl/internal-ca.pem")?;
+//! let factory = ChromeBrowserFactory::with_trusted_ca(None, SandboxOptions::default(), Some(ca));
+//! ```
+//!
+//! # Custom Fonts
+//!
+//! Fonts that are not installed on the host (e.g. a corporate typeface)
+//! can be added from a directory with [`FontDirectory`]:
+//!
+//! ```rust,ignore
+//! use html2pdf_api::{ChromeBrowserFactory, FontDirectory, SandboxOptions};
+//!
+//! let fonts = FontDirectory::new("/opt/brand/fonts")?;
+//! let factory = ChromeBrowserFactory::with_fonts(None, SandboxOptions::default(), None, Some(fonts));
+//! ```
+//!
+//! # Launch Flags
+//!
+//! Settings without a dedicated type, such as the window size or extra
+//! Chrome flags, go through [`ChromeBrowserFactory::builder`]:
+//!
+//! ```rust,ignore
+//! use html2pdf_api::ChromeBrowserFactory;
+//!
+//! let factory = ChromeBrowserFactory::builder()
+//!     .sandbox(false)
+//!     .window_size(1920, 1080)
+//!     .arg("--disable-gpu")
+//!     .build();
+//! ```
+//!
+//! # Stealth
+//!
+//! Public sites that turn away obvious headless browsers can be rendered
+//! with [`StealthOptions`], which hides the usual automation tells:
+//!
+//! ```rust,ignore
+//! use html2pdf_api::{ChromeBrowserFactory, SandboxOptions, StealthOptions};
+//!
+//! let factory = ChromeBrowserFactory::with_stealth(
+//!     None,
+//!     SandboxOptions::default(),
+//!     None,
+//!     None,
+//!     Some(StealthOptions::default().languages(["de-DE", "de"])),
+//! );
+//! ```
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 
-use headless_chrome::{Browser, LaunchOptions};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use headless_chrome::protocol::cdp::{Browser as BrowserDomain, Emulation};
+use headless_chrome::{Browser, LaunchOptions, Tab};
+use ring::rand::{SecureRandom, SystemRandom};
 
 use super::BrowserFactory;
+use crate::disk::PROFILE_PREFIX;
 use crate::error::{BrowserPoolError, Result};
 
+/// Chrome sandbox settings for a [`ChromeBrowserFactory`].
+///
+/// The default keeps every layer of Chrome's sandbox enabled. Each setter
+/// turns one layer off; a factory built with weakened settings logs a
+/// warning, because a renderer compromised by a malicious page then runs
+/// with the service's own privileges.
+///
+/// # Settings
+///
+/// | Setter | Default | Chrome Flag When Changed |
+/// |--------|---------|--------------------------|
+/// | [`no_sandbox`](Self::no_sandbox) | `false` | `--no-sandbox --disable-setuid-sandbox` |
+/// | [`user_namespaces`](Self::user_namespaces) | `true` | `--disable-namespace-sandbox` |
+/// | [`seccomp_filter`](Self::seccomp_filter) | `true` | `--disable-seccomp-filter-sandbox` |
+///
+/// # Seccomp Profiles
+///
+/// Chrome's own seccomp-bpf filter is controlled by
+/// [`seccomp_filter`](Self::seccomp_filter). A seccomp *profile* covering
+/// the whole process tree is applied by the container runtime instead
+/// (e.g. `docker run --security-opt seccomp=chrome.json`); Chrome has no
+/// flag to load one.
+///
+/// # Example
+///
+/// ```rust
+/// use html2pdf_api::SandboxOptions;
+///
+/// // Safe default: full sandbox
+/// let sandbox = SandboxOptions::default();
+/// assert!(!sandbox.is_weakened());
+///
+/// // Container without user namespaces: keep seccomp, drop namespace isolation
+/// let sandbox = SandboxOptions::default().user_namespaces(false);
+/// assert!(sandbox.is_weakened());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxOptions {
+    no_sandbox: bool,
+    user_namespaces: bool,
+    seccomp_filter: bool,
+}
+
+impl Default for SandboxOptions {
+    fn default() -> Self {
+        Self {
+            no_sandbox: false,
+            user_namespaces: true,
+            seccomp_filter: true,
+        }
+    }
+}
+
+impl SandboxOptions {
+    /// Disable Chrome's sandbox entirely.
+    ///
+    /// Required when Chrome runs as root, which Chrome refuses to do with
+    /// the sandbox enabled. Prefer running as a non-root user instead.
+    pub fn no_sandbox(mut self, no_sandbox: bool) -> Self {
+        self.no_sandbox = no_sandbox;
+        self
+    }
+
+    /// Allow the sandbox to isolate renderers in new user namespaces.
+    ///
+    /// Set to `false` on hosts where unprivileged user namespace cloning is
+    /// disabled; Chrome then relies on its setuid helper, if installed.
+    pub fn user_namespaces(mut self, enabled: bool) -> Self {
+        self.user_namespaces = enabled;
+        self
+    }
+
+    /// Keep Chrome's seccomp-bpf system call filter.
+    ///
+    /// Only disable this for kernels or container runtimes that reject it.
+    pub fn seccomp_filter(mut self, enabled: bool) -> Self {
+        self.seccomp_filter = enabled;
+        self
+    }
+
+    /// Read sandbox settings from the environment.
+    ///
+    /// `CHROME_NO_SANDBOX=true` disables the sandbox; anything else keeps
+    /// the default.
+    pub fn from_env() -> Self {
+        let no_sandbox = std::env::var("CHROME_NO_SANDBOX")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(false);
+
+        Self::default().no_sandbox(no_sandbox)
+    }
+
+    /// Whether the sandbox is disabled.
+    pub fn is_disabled(&self) -> bool {
+        self.no_sandbox
+    }
+
+    /// Whether any sandbox layer is turned off.
+    pub fn is_weakened(&self) -> bool {
+        self.no_sandbox || !self.user_namespaces || !self.seccomp_filter
+    }
+
+    /// Extra Chrome flags for the weakened layers.
+    ///
+    /// `--no-sandbox` itself is passed by `headless_chrome` when
+    /// `LaunchOptions::sandbox` is `false`.
+    fn args(&self) -> Vec<&'static str> {
+        let mut args = Vec::new();
+        if !self.no_sandbox {
+            if !self.user_namespaces {
+                args.push("--disable-namespace-sandbox");
+            }
+            if !self.seccomp_filter {
+                args.push("--disable-seccomp-filter-sandbox");
+            }
+        }
+        args
+    }
+
+    /// Log a warning for every disabled layer.
+    fn warn_if_weakened(&self) {
+        if self.no_sandbox {
+            log::warn!(
+                "⚠️ Chrome sandbox is DISABLED (--no-sandbox): a compromised page \
+                 runs with this process's privileges. Only render trusted content, \
+                 or run as a non-root user with the sandbox enabled."
+            );
+            return;
+        }
+        if !self.user_namespaces {
+            log::warn!(
+                "⚠️ Chrome user namespace sandbox is disabled; renderer isolation \
+                 depends on the setuid sandbox helper"
+            );
+        }
+        if !self.seccomp_filter {
+            log::warn!("⚠️ Chrome seccomp filter is disabled; renderers can make any system call");
+        }
+    }
+}
+
+/// Additional certificate authorities trusted by pooled browsers.
+///
+/// Chrome has no flag to load a CA bundle, so each certificate's public key
+/// is pinned with `--ignore-certificate-errors-spki-list` instead: any
+/// chain that contains one of these certificates is accepted, exactly as
+/// if the CA were installed in the system trust store. Chrome only honors
+/// the flag with a dedicated profile directory, which `headless_chrome`
+/// always creates.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::TrustedCa;
+///
+/// let ca = TrustedCa::from_pem_file("/etc/ssl/internal-ca.pem")?;
+/// assert!(!ca.spki_hashes().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrustedCa {
+    /// Base64 SHA-256 hashes of each certificate's SubjectPublicKeyInfo.
+    spki_hashes: Vec<String>,
+    /// The Chrome flag, allocated once and shared by every launch.
+    flag: &'static OsStr,
+}
+
+impl TrustedCa {
+    /// Trust every certificate in a PEM bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::Configuration`] if the bundle contains no
+    /// certificates or a certificate can't be parsed.
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        let certificates = pem_certificates(pem)?;
+        if certificates.is_empty() {
+            return Err(BrowserPoolError::Configuration(
+                "CA bundle contains no certificates".to_string(),
+            ));
+        }
+
+        let spki_hashes = certificates
+            .iter()
+            .map(|der| {
+                subject_public_key_info(der)
+                    .map(|spki| BASE64.encode(ring::digest::digest(&ring::digest::SHA256, spki)))
+                    .ok_or_else(|| {
+                        BrowserPoolError::Configuration(
+                            "CA bundle contains a malformed certificate".to_string(),
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Launch options borrow their arguments for 'static; the flag is
+        // built once per bundle, not per browser
+        let flag = format!(
+            "--ignore-certificate-errors-spki-list={}",
+            spki_hashes.join(",")
+        );
+        let flag: &'static OsStr = OsStr::new(Box::leak(flag.into_boxed_str()));
+
+        Ok(Self { spki_hashes, flag })
+    }
+
+    /// Trust every certificate in a PEM file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::Configuration`] if the file can't be
+    /// read or parsed.
+    pub fn from_pem_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let pem = std::fs::read_to_string(path).map_err(|e| {
+            BrowserPoolError::Configuration(format!(
+                "failed to read CA bundle {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_pem(&pem)
+    }
+
+    /// Read the CA bundle named by `CHROME_CA_BUNDLE`, if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::Configuration`] if the variable is set
+    /// but the file can't be loaded; a typo should not silently fall back
+    /// to the system trust store.
+    pub fn from_env() -> Result<Option<Self>> {
+        match std::env::var("CHROME_CA_BUNDLE") {
+            Ok(path) if !path.trim().is_empty() => Self::from_pem_file(path.trim()).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Base64 SHA-256 SPKI hashes passed to Chrome, one per certificate.
+    pub fn spki_hashes(&self) -> &[String] {
+        &self.spki_hashes
+    }
+}
+
+/// Settings that make pooled browsers look like a regular desktop Chrome.
+///
+/// Some public sites serve empty pages or CAPTCHAs to browsers that
+/// announce automation. A [`ChromeBrowserFactory`] with stealth enabled
+/// hides the common tells:
+///
+/// | Tell | With Stealth |
+/// |------|--------------|
+/// | `navigator.webdriver` is `true` | Launched without `--enable-automation`, with `--disable-blink-features=AutomationControlled` |
+/// | `HeadlessChrome` in `User-Agent` / `navigator.userAgent` | The browser's own user agent with `Chrome` instead, or [`user_agent`](Self::user_agent) |
+/// | `HeadlessChrome` brand in UA client hints (`Sec-CH-UA`, `navigator.userAgentData`) | Chrome brands of the same version and the host's platform |
+/// | `Accept-Language` / `navigator.languages` | [`languages`](Self::languages), `en-US, en` by default |
+///
+/// The user agent and languages are set on each tab as it is checked out
+/// (see [`BrowserFactory::prepare_tab`]). Other fingerprinting signals are
+/// left alone.
+///
+/// # Example
+///
+/// ```rust
+/// use html2pdf_api::StealthOptions;
+///
+/// let stealth = StealthOptions::default()
+///     .languages(["de-DE", "de"])
+///     .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36");
+/// # let _ = stealth;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StealthOptions {
+    user_agent: Option<String>,
+    languages: Vec<String>,
+}
+
+impl Default for StealthOptions {
+    fn default() -> Self {
+        Self {
+            user_agent: None,
+            languages: vec!["en-US".to_string(), "en".to_string()],
+        }
+    }
+}
+
+impl StealthOptions {
+    /// Send this user agent instead of the browser's own.
+    ///
+    /// Client hints are derived from its `Chrome/<version>` token; a user
+    /// agent without one sends no client hints.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Preferred languages, most preferred first (e.g. `["de-DE", "de"]`).
+    pub fn languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.languages = languages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Read stealth settings from the environment.
+    ///
+    /// Returns `None` unless `CHROME_STEALTH=true`. `CHROME_USER_AGENT`
+    /// and `CHROME_LANGUAGES` (comma-separated) override the defaults.
+    pub fn from_env() -> Option<Self> {
+        let enabled = std::env::var("CHROME_STEALTH")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let mut stealth = Self::default();
+        if let Ok(user_agent) = std::env::var("CHROME_USER_AGENT") {
+            if !user_agent.trim().is_empty() {
+                stealth = stealth.user_agent(user_agent.trim());
+            }
+        }
+        if let Ok(languages) = std::env::var("CHROME_LANGUAGES") {
+            let languages: Vec<&str> = languages
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .collect();
+            if !languages.is_empty() {
+                stealth = stealth.languages(languages);
+            }
+        }
+        Some(stealth)
+    }
+
+    /// Set the user agent, client hints, and languages on `tab`.
+    pub(super) fn apply(&self, tab: &Tab) -> Result<()> {
+        let user_agent = match &self.user_agent {
+            Some(user_agent) => user_agent.clone(),
+            None => tab
+                .call_method(BrowserDomain::GetVersion(None))
+                .map_err(|e| BrowserPoolError::TabCreation(e.to_string()))?
+                .user_agent
+                .replace("HeadlessChrome", "Chrome"),
+        };
+
+        tab.call_method(self.user_agent_override(&user_agent))
+            .map(|_| ())
+            .map_err(|e| {
+                BrowserPoolError::TabCreation(format!("failed to override user agent: {}", e))
+            })
+    }
+
+    /// The CDP override sending `user_agent` and matching client hints.
+    fn user_agent_override(&self, user_agent: &str) -> Emulation::SetUserAgentOverride {
+        let (platform, navigator_platform) = if cfg!(target_os = "macos") {
+            ("macOS", "MacIntel")
+        } else if cfg!(target_os = "windows") {
+            ("Windows", "Win32")
+        } else {
+            ("Linux", "Linux x86_64")
+        };
+
+        let metadata = chrome_version(user_agent).map(|version| {
+            let major = version.split('.').next().unwrap_or(version);
+            let brand = |brand: &str, version: &str| Emulation::UserAgentBrandVersion {
+                brand: brand.to_string(),
+                version: version.to_string(),
+            };
+            Emulation::UserAgentMetadata {
+                brands: Some(vec![
+                    brand("Not_A Brand", "8"),
+                    brand("Chromium", major),
+                    brand("Google Chrome", major),
+                ]),
+                full_version_list: Some(vec![
+                    brand("Not_A Brand", "8.0.0.0"),
+                    brand("Chromium", version),
+                    brand("Google Chrome", version),
+                ]),
+                full_version: None,
+                platform: platform.to_string(),
+                platform_version: String::new(),
+                architecture: if cfg!(target_arch = "aarch64") {
+                    "arm"
+                } else {
+                    "x86"
+                }
+                .to_string(),
+                model: String::new(),
+                mobile: false,
+                bitness: Some("64".to_string()),
+                wow_64: Some(false),
+            }
+        });
+
+        Emulation::SetUserAgentOverride {
+            user_agent: user_agent.to_string(),
+            accept_language: Some(accept_language(&self.languages)),
+            platform: Some(navigator_platform.to_string()),
+            user_agent_metadata: metadata,
+        }
+    }
+}
+
+/// The version in a user agent's `Chrome/<version>` token.
+fn chrome_version(user_agent: &str) -> Option<&str> {
+    let version = user_agent.split_once("Chrome/")?.1;
+    let version = version.split(' ').next()?;
+    (!version.is_empty()).then_some(version)
+}
+
+/// `Accept-Language` value for `languages`, e.g. `de-DE,de;q=0.9`.
+fn accept_language(languages: &[String]) -> String {
+    languages
+        .iter()
+        .enumerate()
+        .map(|(i, language)| match i {
+            0 => language.clone(),
+            _ => format!("{};q=0.{}", language, 10usize.saturating_sub(i).max(1)),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Extra fonts made available to pooled browsers.
+///
+/// Minimal container images ship a single fallback family (usually DejaVu),
+/// so documents styled with a corporate typeface render in the wrong font.
+/// A `FontDirectory` adds a directory of font files (TrueType, OpenType,
+/// collections; subdirectories included) on top of the system fonts,
+/// without installing them system-wide.
+///
+/// Chrome on Linux finds fonts through fontconfig, so this writes a small
+/// fontconfig file that includes the system configuration plus the
+/// directory, and launches Chrome with `FONTCONFIG_FILE` pointing at it.
+/// Chrome on macOS and Windows does not use fontconfig; install fonts
+/// through the OS there.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::FontDirectory;
+///
+/// let fonts = FontDirectory::new("/opt/brand/fonts")?;
+/// println!("{} font files", fonts.font_count());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FontDirectory {
+    /// The directory holding the fonts.
+    dir: PathBuf,
+    /// Font files found in `dir` when it was loaded.
+    font_count: usize,
+    /// The generated fontconfig file.
+    config_file: PathBuf,
+}
+
+impl FontDirectory {
+    /// Use the fonts in `dir`.
+    ///
+    /// The fontconfig file (and fontconfig's cache for the directory) is
+    /// written to a new private directory under the system temp dir.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::Configuration`] if `dir` is not a
+    /// readable directory, contains no font files, or the fontconfig file
+    /// can't be written.
+    pub fn new(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let dir = dir.canonicalize().map_err(|e| {
+            BrowserPoolError::Configuration(format!(
+                "failed to read font directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        let font_count = count_font_files(&dir).map_err(|e| {
+            BrowserPoolError::Configuration(format!(
+                "failed to read font directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        if font_count == 0 {
+            return Err(BrowserPoolError::Configuration(format!(
+                "font directory {} contains no font files",
+                dir.display()
+            )));
+        }
+
+        let config_file = write_fontconfig(&dir).map_err(|e| {
+            BrowserPoolError::Configuration(format!("failed to write fontconfig file: {}", e))
+        })?;
+
+        Ok(Self {
+            dir,
+            font_count,
+            config_file,
+        })
+    }
+
+    /// Use the font directory named by `CHROME_FONT_DIR`, if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::Configuration`] if the variable is set
+    /// but the directory can't be used; a typo should not silently fall
+    /// back to the system fonts.
+    pub fn from_env() -> Result<Option<Self>> {
+        match std::env::var("CHROME_FONT_DIR") {
+            Ok(dir) if !dir.trim().is_empty() => Self::new(dir.trim()).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// The directory holding the fonts.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of font files found when the directory was loaded.
+    pub fn font_count(&self) -> usize {
+        self.font_count
+    }
+
+    /// The generated fontconfig file passed to Chrome.
+    pub fn config_file(&self) -> &Path {
+        &self.config_file
+    }
+}
+
+/// File extensions fontconfig loads.
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc", "pfb", "pfa"];
+
+/// Count the font files in `dir` and its subdirectories.
+fn count_font_files(dir: &Path) -> std::io::Result<usize> {
+    let mut count = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            count += count_font_files(&path)?;
+        } else if path
+            .extension()
+            .and_then(OsStr::to_str)
+            .is_some_and(|ext| FONT_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Write a fontconfig file adding `dir` to the system configuration.
+fn write_fontconfig(dir: &Path) -> std::io::Result<PathBuf> {
+    let config_dir = std::env::temp_dir().join(format!("html2pdf-fonts-{}", random_name()?));
+
+    let mut builder = std::fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(&config_dir)?;
+
+    let config_file = config_dir.join("fonts.conf");
+    std::fs::write(
+        &config_file,
+        fontconfig_xml(
+            &dir.to_string_lossy(),
+            &config_dir.join("cache").to_string_lossy(),
+        ),
+    )?;
+    Ok(config_file)
+}
+
+/// fontconfig configuration including the system fonts plus `dir`.
+fn fontconfig_xml(dir: &str, cache_dir: &str) -> String {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    format!(
+        "<?xml version=\"1.0\"?>\n\
+         <!DOCTYPE fontconfig SYSTEM \"urn:fontconfig:fonts.dtd\">\n\
+         <fontconfig>\n  \
+         <include ignore_missing=\"yes\">/etc/fonts/fonts.conf</include>\n  \
+         <dir>{}</dir>\n  \
+         <cachedir>{}</cachedir>\n\
+         </fontconfig>\n",
+        escape(dir),
+        escape(cache_dir)
+    )
+}
+
+/// Decode the `CERTIFICATE` blocks of a PEM bundle to DER.
+fn pem_certificates(pem: &str) -> Result<Vec<Vec<u8>>> {
+    let blocks = pem_blocks(pem).map_err(|e| {
+        BrowserPoolError::Configuration(format!("CA bundle contains invalid base64: {}", e))
+    })?;
+    Ok(blocks
+        .into_iter()
+        .filter(|(label, _)| label == "CERTIFICATE")
+        .map(|(_, der)| der)
+        .collect())
+}
+
+/// Decode every block of a PEM file to DER, with its label (such as
+/// `CERTIFICATE` or `PRIVATE KEY`).
+pub(crate) fn pem_blocks(
+    pem: &str,
+) -> std::result::Result<Vec<(String, Vec<u8>)>, base64::DecodeError> {
+    let mut blocks = Vec::new();
+    let mut body: Option<(&str, String)> = None;
+
+    for line in pem.lines().map(str::trim) {
+        let begin = line
+            .strip_prefix("-----BEGIN ")
+            .and_then(|rest| rest.strip_suffix("-----"));
+        let end = line
+            .strip_prefix("-----END ")
+            .and_then(|rest| rest.strip_suffix("-----"));
+        match (begin, end) {
+            (Some(label), _) => body = Some((label, String::new())),
+            (_, Some(label)) => {
+                if let Some((begun, encoded)) = body.take().filter(|(begun, _)| *begun == label) {
+                    blocks.push((begun.to_string(), BASE64.decode(encoded)?));
+                }
+            }
+            _ => {
+                if let Some((_, encoded)) = body.as_mut() {
+                    encoded.push_str(line);
+                }
+            }
+        }
+    }
+
+    Ok(blocks)
+}
+
+/// Split the first DER element off `der`.
+///
+/// Returns the whole element (header included), its contents, and the
+/// bytes after it.
+pub(crate) fn der_element(der: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    let first = usize::from(*der.get(1)?);
+    let (header, length) = if first < 0x80 {
+        (2, first)
+    } else {
+        let count = first & 0x7f;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let length = der
+            .get(2..2 + count)?
+            .iter()
+            .fold(0usize, |acc, byte| (acc << 8) | usize::from(*byte));
+        (2 + count, length)
+    };
+    let end = header.checked_add(length)?;
+    let element = der.get(..end)?;
+    Some((element, &element[header..], &der[end..]))
+}
+
+/// Find the SubjectPublicKeyInfo of a DER certificate.
+///
+/// Walks `Certificate → TBSCertificate`, skipping the optional version and
+/// the serial number, signature algorithm, issuer, validity, and subject.
+pub(crate) fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const VERSION: u8 = 0xa0;
+
+    let (_, certificate, _) = der_element(certificate).filter(|(e, _, _)| e[0] == SEQUENCE)?;
+    let (_, mut tbs, _) = der_element(certificate).filter(|(e, _, _)| e[0] == SEQUENCE)?;
+    if tbs.first() == Some(&VERSION) {
+        tbs = der_element(tbs)?.2;
+    }
+    for _ in 0..5 {
+        tbs = der_element(tbs)?.2;
+    }
+    der_element(tbs)
+        .filter(|(e, _, _)| e[0] == SEQUENCE)
+        .map(|(spki, _, _)| spki)
+}
+
 /// Factory for creating Chrome/Chromium browser instances.
 ///
 /// Handles Chrome-specific launch options and path detection.
@@ -52,6 +817,9 @@ pub struct ChromeBrowserFactory {
     ///
     /// This allows dynamic configuration per browser instance.
     launch_options_fn: Box<dyn Fn() -> Result<LaunchOptions<'static>> + Send + Sync>,
+
+    /// Stealth settings applied to each checked-out tab.
+    stealth: Option<StealthOptions>,
 }
 
 impl ChromeBrowserFactory {
@@ -81,9 +849,32 @@ impl ChromeBrowserFactory {
     {
         Self {
             launch_options_fn: Box::new(launch_options_fn),
+            stealth: None,
         }
     }
 
+    /// Start building a factory with custom launch settings.
+    ///
+    /// The builder starts from the same flags as [`with_defaults`](Self::with_defaults)
+    /// and adds to them; see [`ChromeBrowserFactoryBuilder`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::{ChromeBrowserFactory, HeadlessMode};
+    ///
+    /// let factory = ChromeBrowserFactory::builder()
+    ///     .path("/usr/bin/chromium")
+    ///     .sandbox(false)
+    ///     .window_size(1280, 1024)
+    ///     .headless_mode(HeadlessMode::New)
+    ///     .arg("--disable-gpu")
+    ///     .build();
+    /// ```
+    pub fn builder() -> ChromeBrowserFactoryBuilder {
+        ChromeBrowserFactoryBuilder::default()
+    }
+
     /// Create factory with auto-detected Chrome path.
     ///
     /// This is the recommended default - lets headless_chrome find Chrome.
@@ -108,9 +899,7 @@ impl ChromeBrowserFactory {
     /// ```
     pub fn with_defaults() -> Self {
         log::debug!(" Creating ChromeBrowserFactory with auto-detect");
-        Self::new(|| {
-            create_chrome_options(None).map_err(|e| BrowserPoolError::Configuration(e.to_string()))
-        })
+        Self::with_sandbox(None, SandboxOptions::default())
     }
 
     /// Create factory with custom Chrome binary path.
@@ -144,10 +933,140 @@ impl ChromeBrowserFactory {
             " Creating ChromeBrowserFactory with custom path: {}",
             chrome_path
         );
-        Self::new(move || {
-            create_chrome_options(Some(&chrome_path))
-                .map_err(|e| BrowserPoolError::Configuration(e.to_string()))
-        })
+        Self::with_sandbox(Some(chrome_path), SandboxOptions::default())
+    }
+
+    /// Create factory with explicit sandbox settings.
+    ///
+    /// Logs a warning once, when the factory is created, if `sandbox`
+    /// turns off any part of Chrome's sandbox.
+    ///
+    /// # Parameters
+    ///
+    /// * `chrome_path` - Chrome/Chromium binary, or `None` to auto-detect.
+    /// * `sandbox` - Sandbox settings. See [`SandboxOptions`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::{ChromeBrowserFactory, SandboxOptions};
+    ///
+    /// // Docker image running Chrome as root
+    /// let factory = ChromeBrowserFactory::with_sandbox(
+    ///     Some("/usr/bin/chromium".to_string()),
+    ///     SandboxOptions::default().no_sandbox(true),
+    /// );
+    /// ```
+    pub fn with_sandbox(chrome_path: Option<String>, sandbox: SandboxOptions) -> Self {
+        Self::with_trusted_ca(chrome_path, sandbox, None)
+    }
+
+    /// Create factory with explicit sandbox settings and extra trusted CAs.
+    ///
+    /// # Parameters
+    ///
+    /// * `chrome_path` - Chrome/Chromium binary, or `None` to auto-detect.
+    /// * `sandbox` - Sandbox settings. See [`SandboxOptions`].
+    /// * `trusted_ca` - Private CAs to trust in addition to the system
+    ///   store. See [`TrustedCa`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::{ChromeBrowserFactory, SandboxOptions, TrustedCa};
+    ///
+    /// let factory = ChromeBrowserFactory::with_trusted_ca(
+    ///     None,
+    ///     SandboxOptions::from_env(),
+    ///     TrustedCa::from_env()?,
+    /// );
+    /// ```
+    pub fn with_trusted_ca(
+        chrome_path: Option<String>,
+        sandbox: SandboxOptions,
+        trusted_ca: Option<TrustedCa>,
+    ) -> Self {
+        Self::with_fonts(chrome_path, sandbox, trusted_ca, None)
+    }
+
+    /// Create factory with explicit sandbox settings, extra trusted CAs,
+    /// and an extra font directory.
+    ///
+    /// # Parameters
+    ///
+    /// * `chrome_path` - Chrome/Chromium binary, or `None` to auto-detect.
+    /// * `sandbox` - Sandbox settings. See [`SandboxOptions`].
+    /// * `trusted_ca` - Private CAs to trust in addition to the system
+    ///   store. See [`TrustedCa`].
+    /// * `fonts` - Fonts to offer in addition to the system fonts. See
+    ///   [`FontDirectory`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::{ChromeBrowserFactory, FontDirectory, SandboxOptions, TrustedCa};
+    ///
+    /// let factory = ChromeBrowserFactory::with_fonts(
+    ///     None,
+    ///     SandboxOptions::from_env(),
+    ///     TrustedCa::from_env()?,
+    ///     FontDirectory::from_env()?,
+    /// );
+    /// ```
+    pub fn with_fonts(
+        chrome_path: Option<String>,
+        sandbox: SandboxOptions,
+        trusted_ca: Option<TrustedCa>,
+        fonts: Option<FontDirectory>,
+    ) -> Self {
+        Self::with_stealth(chrome_path, sandbox, trusted_ca, fonts, None)
+    }
+
+    /// Create factory with explicit sandbox settings, extra trusted CAs,
+    /// an extra font directory, and stealth settings.
+    ///
+    /// # Parameters
+    ///
+    /// * `chrome_path` - Chrome/Chromium binary, or `None` to auto-detect.
+    /// * `sandbox` - Sandbox settings. See [`SandboxOptions`].
+    /// * `trusted_ca` - Private CAs to trust in addition to the system
+    ///   store. See [`TrustedCa`].
+    /// * `fonts` - Fonts to offer in addition to the system fonts. See
+    ///   [`FontDirectory`].
+    /// * `stealth` - Hide automation from the pages rendered. See
+    ///   [`StealthOptions`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::{
+    ///     ChromeBrowserFactory, FontDirectory, SandboxOptions, StealthOptions, TrustedCa,
+    /// };
+    ///
+    /// let factory = ChromeBrowserFactory::with_stealth(
+    ///     None,
+    ///     SandboxOptions::from_env(),
+    ///     TrustedCa::from_env()?,
+    ///     FontDirectory::from_env()?,
+    ///     StealthOptions::from_env(),
+    /// );
+    /// ```
+    pub fn with_stealth(
+        chrome_path: Option<String>,
+        sandbox: SandboxOptions,
+        trusted_ca: Option<TrustedCa>,
+        fonts: Option<FontDirectory>,
+        stealth: Option<StealthOptions>,
+    ) -> Self {
+        ChromeBrowserFactoryBuilder {
+            chrome_path,
+            sandbox,
+            trusted_ca,
+            fonts,
+            stealth,
+            ..Default::default()
+        }
+        .build()
     }
 }
 
@@ -173,6 +1092,304 @@ impl BrowserFactory for ChromeBrowserFactory {
             BrowserPoolError::BrowserCreation(e.to_string())
         })
     }
+
+    /// Apply the [`StealthOptions`], if any, to a checked-out tab.
+    fn prepare_tab(&self, tab: &Tab) -> Result<()> {
+        match &self.stealth {
+            Some(stealth) => stealth.apply(tab),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Which headless implementation Chrome runs.
+///
+/// Without an explicit mode, Chrome is started with a bare `--headless`,
+/// which means [`New`](Self::New) on current Chrome releases.
+///
+/// # Example
+///
+/// ```rust
+/// use html2pdf_api::HeadlessMode;
+///
+/// assert_eq!(HeadlessMode::New.flag(), "--headless=new");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadlessMode {
+    /// The full browser without a window (`--headless=new`).
+    New,
+
+    /// The legacy, separate headless implementation (`--headless=old`).
+    ///
+    /// Chrome 132 removed it from the main binary; use the
+    /// `chrome-headless-shell` binary as [`path`](ChromeBrowserFactoryBuilder::path)
+    /// there instead.
+    Old,
+}
+
+impl HeadlessMode {
+    /// The Chrome flag selecting this mode.
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Self::New => "--headless=new",
+            Self::Old => "--headless=old",
+        }
+    }
+}
+
+/// Builder for a [`ChromeBrowserFactory`] with custom launch settings.
+///
+/// Created by [`ChromeBrowserFactory::builder`]. Every browser the factory
+/// launches gets the flags of [`create_chrome_options_with`], then the
+/// settings below; extra flags from [`arg`](Self::arg) come last, so they
+/// override earlier ones where Chrome lets the last value win.
+///
+/// # Settings
+///
+/// | Setter | Default | Effect |
+/// |--------|---------|--------|
+/// | [`path`](Self::path) | auto-detect | Chrome/Chromium binary |
+/// | [`sandbox`](Self::sandbox) | `true` | `false` adds `--no-sandbox` |
+/// | [`sandbox_options`](Self::sandbox_options) | full sandbox | See [`SandboxOptions`] |
+/// | [`window_size`](Self::window_size) | Chrome's default | `--window-size`, also the default viewport |
+/// | [`user_data_dir`](Self::user_data_dir) | temporary directory | Parent of each browser's profile |
+/// | [`headless_mode`](Self::headless_mode) | `--headless` | See [`HeadlessMode`] |
+/// | [`arg`](Self::arg) / [`args`](Self::args) | none | Extra Chrome flags |
+/// | [`trusted_ca`](Self::trusted_ca) | none | See [`TrustedCa`] |
+/// | [`fonts`](Self::fonts) | none | See [`FontDirectory`] |
+/// | [`stealth`](Self::stealth) | off | See [`StealthOptions`] |
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::ChromeBrowserFactory;
+///
+/// // Container without a GPU and with a small /tmp
+/// let factory = ChromeBrowserFactory::builder()
+///     .sandbox(false)
+///     .user_data_dir("/var/cache/html2pdf")
+///     .args(["--disable-gpu", "--disk-cache-size=0"])
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChromeBrowserFactoryBuilder {
+    chrome_path: Option<String>,
+    sandbox: SandboxOptions,
+    trusted_ca: Option<TrustedCa>,
+    fonts: Option<FontDirectory>,
+    stealth: Option<StealthOptions>,
+    window_size: Option<(u32, u32)>,
+    user_data_dir: Option<PathBuf>,
+    headless_mode: Option<HeadlessMode>,
+
+    /// Extra flags, allocated once and shared by every launch.
+    args: Vec<&'static OsStr>,
+}
+
+impl ChromeBrowserFactoryBuilder {
+    /// Use the Chrome/Chromium binary at `path` instead of auto-detecting.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.chrome_path = Some(path.into());
+        self
+    }
+
+    /// Enable or disable Chrome's sandbox entirely.
+    ///
+    /// `sandbox(false)` is shorthand for
+    /// `sandbox_options(SandboxOptions::default().no_sandbox(true))`, and
+    /// logs the same warning when the factory is built.
+    pub fn sandbox(mut self, enabled: bool) -> Self {
+        self.sandbox = self.sandbox.no_sandbox(!enabled);
+        self
+    }
+
+    /// Set each layer of Chrome's sandbox. See [`SandboxOptions`].
+    pub fn sandbox_options(mut self, sandbox: SandboxOptions) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Trust private CAs in addition to the system store. See [`TrustedCa`].
+    pub fn trusted_ca(mut self, trusted_ca: TrustedCa) -> Self {
+        self.trusted_ca = Some(trusted_ca);
+        self
+    }
+
+    /// Offer fonts in addition to the system fonts. See [`FontDirectory`].
+    pub fn fonts(mut self, fonts: FontDirectory) -> Self {
+        self.fonts = Some(fonts);
+        self
+    }
+
+    /// Hide automation from the pages rendered. See [`StealthOptions`].
+    pub fn stealth(mut self, stealth: StealthOptions) -> Self {
+        self.stealth = Some(stealth);
+        self
+    }
+
+    /// Set the browser window size in pixels.
+    ///
+    /// Pages are laid out in a viewport of this size unless a request sets
+    /// its own.
+    pub fn window_size(mut self, width: u32, height: u32) -> Self {
+        self.window_size = Some((width, height));
+        self
+    }
+
+    /// Keep browser profiles under `dir` instead of the system temporary
+    /// directory.
+    ///
+    /// Chrome lets only one process use a profile, so each browser gets its
+    /// own `rust-headless-chrome-profile-*` subdirectory, created when it
+    /// launches. These are not removed when the browser exits;
+    /// [`clean_stale_profiles`](crate::disk::clean_stale_profiles) removes
+    /// those of browsers no longer running.
+    pub fn user_data_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.user_data_dir = Some(dir.into());
+        self
+    }
+
+    /// Pick Chrome's headless implementation. See [`HeadlessMode`].
+    pub fn headless_mode(mut self, mode: HeadlessMode) -> Self {
+        self.headless_mode = Some(mode);
+        self
+    }
+
+    /// Add a Chrome flag, e.g. `--disable-gpu`.
+    ///
+    /// Flags are passed as given, after the factory's own.
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        // Launch options borrow their arguments for 'static; each flag is
+        // allocated once per factory, not per browser
+        let arg: &'static OsStr = Box::leak(arg.as_ref().to_os_string().into_boxed_os_str());
+        self.args.push(arg);
+        self
+    }
+
+    /// Add several Chrome flags. See [`arg`](Self::arg).
+    pub fn args<I>(self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<OsStr>,
+    {
+        args.into_iter().fold(self, Self::arg)
+    }
+
+    /// Build the factory.
+    ///
+    /// Logs a warning if the sandbox is weakened. Launch settings are only
+    /// checked by Chrome itself, when [`create`](BrowserFactory::create)
+    /// starts a browser.
+    pub fn build(self) -> ChromeBrowserFactory {
+        let Self {
+            chrome_path,
+            sandbox,
+            trusted_ca,
+            fonts,
+            stealth,
+            window_size,
+            user_data_dir,
+            headless_mode,
+            args,
+        } = self;
+
+        sandbox.warn_if_weakened();
+        if let Some(ca) = &trusted_ca {
+            log::info!(
+                " Trusting {} additional CA certificate(s)",
+                ca.spki_hashes().len()
+            );
+        }
+        if let Some(fonts) = &fonts {
+            log::info!(
+                " Adding {} font file(s) from {}",
+                fonts.font_count(),
+                fonts.dir().display()
+            );
+        }
+
+        if let Some(stealth) = &stealth {
+            log::info!(
+                " Stealth enabled (languages: {})",
+                stealth.languages.join(", ")
+            );
+        }
+        if !args.is_empty() {
+            log::info!(
+                " Extra Chrome flags: {}",
+                args.iter()
+                    .map(|arg| arg.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        }
+
+        let hide_automation = stealth.is_some();
+        let mut factory = ChromeBrowserFactory::new(move || {
+            let mut options = create_chrome_options_with(chrome_path.as_deref(), &sandbox)
+                .map_err(|e| BrowserPoolError::Configuration(e.to_string()))?;
+            if let Some(ca) = &trusted_ca {
+                options.args.push(ca.flag);
+            }
+            if let Some(fonts) = &fonts {
+                options.process_envs = Some(HashMap::from([(
+                    "FONTCONFIG_FILE".to_string(),
+                    fonts.config_file().to_string_lossy().into_owned(),
+                )]));
+            }
+            if hide_automation {
+                options.args.retain(|arg| *arg != "--enable-automation");
+                options
+                    .args
+                    .push(OsStr::new("--disable-blink-features=AutomationControlled"));
+            }
+            if let Some(mode) = headless_mode {
+                // headless_chrome appends a bare `--headless` after our
+                // flags, which would override the mode
+                options.headless = false;
+                options.args.push(OsStr::new(mode.flag()));
+            }
+            if window_size.is_some() {
+                options.window_size = window_size;
+            }
+            if let Some(dir) = &user_data_dir {
+                let profile = create_profile_dir(dir).map_err(|e| {
+                    BrowserPoolError::Configuration(format!(
+                        "cannot create profile directory in {}: {}",
+                        dir.display(),
+                        e
+                    ))
+                })?;
+                options.user_data_dir = Some(profile);
+            }
+            options.args.extend(args.iter().copied());
+            Ok(options)
+        });
+        factory.stealth = stealth;
+        factory
+    }
+}
+
+/// Create a fresh profile directory under `parent`, named like the ones
+/// `headless_chrome` creates so the same cleanup finds it.
+fn create_profile_dir(parent: &Path) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(parent)?;
+    let dir = parent.join(format!("{}-{}", PROFILE_PREFIX, random_name()?));
+
+    let mut builder = std::fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(&dir)?;
+    Ok(dir)
+}
+
+/// 16 random hex digits for a directory name.
+fn random_name() -> std::io::Result<String> {
+    let mut name = [0u8; 8];
+    SystemRandom::new()
+        .fill(&mut name)
+        .map_err(|_| std::io::Error::other("cannot generate directory name"))?;
+    Ok(name.iter().map(|b| format!("{:02x}", b)).collect())
 }
 
 /// Create Chrome launch options with optional custom path.
@@ -217,14 +1434,18 @@ impl BrowserFactory for ChromeBrowserFactory {
 /// - `--disable-default-apps`
 ///
 /// ## Security and Automation
+/// - Chrome's sandbox stays enabled; see [`create_chrome_options_with`]
 /// - `--disable-web-security` - Allow cross-origin requests (for scraping)
 /// - `--enable-automation` - Mark as automated browser
+/// - `--block-new-web-contents` - Fail `window.open()` and other popups, so a
+///   page cannot leave extra windows behind in a pooled browser
 ///
 /// ## Stability
 /// - `--disable-background-timer-throttling`
 /// - `--disable-backgrounding-occluded-windows`
+/// - `--disable-features=IntensiveWakeUpThrottling` - Keep timers in
+///   background tabs firing more than once a minute
 /// - `--disable-hang-monitor`
-/// - `--disable-popup-blocking`
 /// - `--disable-renderer-backgrounding`
 /// - `--disable-ipc-flooding-protection`
 ///
@@ -241,6 +1462,26 @@ impl BrowserFactory for ChromeBrowserFactory {
 /// ```
 pub fn create_chrome_options(
     chrome_path: Option<&str>,
+) -> std::result::Result<LaunchOptions<'static>, Box<dyn std::error::Error + Send + Sync>> {
+    create_chrome_options_with(chrome_path, &SandboxOptions::default())
+}
+
+/// Create Chrome launch options with explicit sandbox settings.
+///
+/// Same flags as [`create_chrome_options`], plus the flags for any sandbox
+/// layer that `sandbox` turns off. Unlike
+/// [`ChromeBrowserFactory::with_sandbox`], this does not log a warning.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::{create_chrome_options_with, SandboxOptions};
+///
+/// let options = create_chrome_options_with(None, &SandboxOptions::default().no_sandbox(true))?;
+/// ```
+pub fn create_chrome_options_with(
+    chrome_path: Option<&str>,
+    sandbox: &SandboxOptions,
 ) -> std::result::Result<LaunchOptions<'static>, Box<dyn std::error::Error + Send + Sync>> {
     match chrome_path {
         Some(path) => log::debug!(" Creating Chrome options with custom path: {}", path),
@@ -258,42 +1499,49 @@ pub fn create_chrome_options(
     }
 
     // Configure launch options for stable headless operation
+    let mut args: Vec<&std::ffi::OsStr> = vec![
+        // ===== Memory and Performance Optimization =====
+        "--disable-dev-shm-usage".as_ref(), // Use /tmp instead of /dev/shm (container-friendly)
+        "--disable-crash-reporter".as_ref(), // No crash reporting
+        "--max_old_space_size=1024".as_ref(), // Limit V8 heap to 1GB
+        // ===== GPU and Rendering Flags =====
+        // Disable GPU features for headless stability
+        "--disable-gpu-compositing".as_ref(),
+        "--disable-software-rasterizer".as_ref(),
+        "--disable-accelerated-2d-canvas".as_ref(),
+        "--disable-gl-drawing-for-tests".as_ref(),
+        "--disable-webgl".as_ref(),
+        "--disable-webgl2".as_ref(),
+        // ===== Disable Unnecessary Features =====
+        "--disable-extensions".as_ref(),   // No browser extensions
+        "--disable-plugins".as_ref(),      // No plugins
+        "--disable-sync".as_ref(),         // No Chrome sync
+        "--disable-default-apps".as_ref(), // No default apps
+        // ===== Security and Functionality =====
+        "--disable-web-security".as_ref(), // Allow cross-origin requests (for scraping)
+        // ===== Automation and Debugging =====
+        "--enable-automation".as_ref(), // Mark as automated browser
+        // ===== Popups =====
+        // Chrome's popup blocker stays on, and any window that would still
+        // open is refused so nothing lingers between pooled renders
+        "--block-new-web-contents".as_ref(),
+        // ===== Stability and Performance =====
+        "--disable-background-timer-throttling".as_ref(), // Don't throttle background tabs
+        "--disable-backgrounding-occluded-windows".as_ref(), // Don't suspend hidden windows
+        // Don't align background timers to one wake-up per minute
+        "--disable-features=IntensiveWakeUpThrottling".as_ref(),
+        "--disable-hang-monitor".as_ref(), // Disable hang detection
+        // ===== Better CDP (Chrome DevTools Protocol) Stability =====
+        "--disable-renderer-backgrounding".as_ref(), // Don't deprioritize renderer
+        "--disable-ipc-flooding-protection".as_ref(), // Allow rapid IPC messages
+    ];
+    args.extend(sandbox.args().into_iter().map(std::ffi::OsStr::new));
+
     builder
         .headless(true) // Run in headless mode
-        .sandbox(false) // Disable sandbox (required in containers)
+        .sandbox(!sandbox.is_disabled()) // `false` adds --no-sandbox
         .disable_default_args(true) // Use our custom args only
-        .args(vec![
-            // ===== Memory and Performance Optimization =====
-            "--disable-dev-shm-usage".as_ref(), // Use /tmp instead of /dev/shm (container-friendly)
-            "--disable-crash-reporter".as_ref(), // No crash reporting
-            "--max_old_space_size=1024".as_ref(), // Limit V8 heap to 1GB
-            // ===== GPU and Rendering Flags =====
-            // Disable GPU features for headless stability
-            "--disable-gpu-compositing".as_ref(),
-            "--disable-software-rasterizer".as_ref(),
-            "--disable-accelerated-2d-canvas".as_ref(),
-            "--disable-gl-drawing-for-tests".as_ref(),
-            "--disable-webgl".as_ref(),
-            "--disable-webgl2".as_ref(),
-            // ===== Disable Unnecessary Features =====
-            "--disable-extensions".as_ref(), // No browser extensions
-            "--disable-plugins".as_ref(),    // No plugins
-            "--disable-sync".as_ref(),       // No Chrome sync
-            "--disable-default-apps".as_ref(), // No default apps
-            // ===== Security and Functionality =====
-            "--disable-web-security".as_ref(), // Allow cross-origin requests (for scraping)
-            // ===== Automation and Debugging =====
-            "--enable-automation".as_ref(), // Mark as automated browser
-            // ===== Stability and Performance =====
-            "--disable-background-timer-throttling".as_ref(), // Don't throttle background tabs
-            "--disable-backgrounding-occluded-windows".as_ref(), // Don't suspend hidden windows
-            "--disable-hang-monitor".as_ref(),                // Disable hang detection
-            // ===== UI Flags =====
-            "--disable-popup-blocking".as_ref(), // Allow popups
-            // ===== Better CDP (Chrome DevTools Protocol) Stability =====
-            "--disable-renderer-backgrounding".as_ref(), // Don't deprioritize renderer
-            "--disable-ipc-flooding-protection".as_ref(), // Allow rapid IPC messages
-        ])
+        .args(args)
         .build()
         .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
             let path_msg = chrome_path.unwrap_or("auto-detect");
@@ -351,4 +1599,232 @@ mod tests {
             result.err()
         );
     }
+
+    /// Verifies CA bundles are pinned by the SPKI hash Chrome expects.
+    ///
+    /// The expected hash was produced with
+    /// `openssl x509 -pubkey -noout | openssl pkey -pubin -outform der |
+    /// openssl dgst -sha256 -binary | base64`.
+    #[test]
+    fn test_trusted_ca_from_pem() {
+        const CA: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBjjCCATOgAwIBAgIUAaUk89bLGGE5I1AR7oQOThY0yNswCgYIKoZIzj0EAwIw
+GzEZMBcGA1UEAwwQaHRtbDJwZGYgdGVzdCBDQTAgFw0yNjEwMTYxOTQ5MThaGA8y
+MTI2MDkyMjE5NDkxOFowGzEZMBcGA1UEAwwQaHRtbDJwZGYgdGVzdCBDQTBZMBMG
+ByqGSM49AgEGCCqGSM49AwEHA0IABLyjyPdzVEeZ8hiU4X1KxS67kPDn5Y/8jhFU
+D2YDpZTOXmtFNMcdO+xeg5FxVJOWehXMXnPfe0leITX3hMAN2SGjUzBRMB0GA1Ud
+DgQWBBR49XQaWHhT2kVF/pg4Rp/w24YnDzAfBgNVHSMEGDAWgBR49XQaWHhT2kVF
+/pg4Rp/w24YnDzAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQCH
+8utlvYVyJTkrJdmSx4/aGZaKy0JN01IeZW1pdetY5wIhANPmM9G3tW7yIMOgyAcc
+E0DC5MFZNofZd4hUn3yZKAQl
+-----END CERTIFICATE-----
+";
+        let ca = TrustedCa::from_pem(CA).unwrap();
+        assert_eq!(
+            ca.spki_hashes(),
+            ["VoQzSi5qciMSSLREhyqdxzLyg7oOF3xfAL2F87SzOSc="]
+        );
+        assert_eq!(
+            ca.flag,
+            "--ignore-certificate-errors-spki-list=VoQzSi5qciMSSLREhyqdxzLyg7oOF3xfAL2F87SzOSc="
+        );
+
+        assert!(TrustedCa::from_pem("no certificates here").is_err());
+        assert!(
+            TrustedCa::from_pem("-----BEGIN CERTIFICATE-----\nMAA=\n-----END CERTIFICATE-----")
+                .is_err()
+        );
+    }
+
+    /// Verifies font directories are counted and written into a fontconfig
+    /// file that keeps the system fonts.
+    #[test]
+    fn test_font_directory() {
+        let dir = std::env::temp_dir().join(format!("html2pdf-font-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("bold")).unwrap();
+        std::fs::write(dir.join("Brand-Regular.ttf"), b"").unwrap();
+        std::fs::write(dir.join("bold").join("Brand-Bold.OTF"), b"").unwrap();
+        std::fs::write(dir.join("LICENSE.txt"), b"").unwrap();
+
+        let fonts = FontDirectory::new(&dir).unwrap();
+        assert_eq!(fonts.font_count(), 2);
+        let config = std::fs::read_to_string(fonts.config_file()).unwrap();
+        assert!(config.contains("<include ignore_missing=\"yes\">/etc/fonts/fonts.conf</include>"));
+        assert!(config.contains(&format!("<dir>{}</dir>", fonts.dir().display())));
+
+        std::fs::remove_file(dir.join("Brand-Regular.ttf")).unwrap();
+        std::fs::remove_file(dir.join("bold").join("Brand-Bold.OTF")).unwrap();
+        assert!(FontDirectory::new(&dir).is_err());
+        assert!(FontDirectory::new(dir.join("missing")).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_dir_all(fonts.config_file().parent().unwrap());
+    }
+
+    /// Verifies paths are escaped in the fontconfig file.
+    #[test]
+    fn test_fontconfig_xml_escaping() {
+        let xml = fontconfig_xml("/fonts/R&D <new>", "/tmp/cache");
+        assert!(xml.contains("<dir>/fonts/R&amp;D &lt;new&gt;</dir>"));
+        assert!(xml.contains("<cachedir>/tmp/cache</cachedir>"));
+    }
+
+    /// Verifies the sandbox is on by default and each setter adds its flag.
+    #[test]
+    fn test_sandbox_options() {
+        let options = create_chrome_options(None).unwrap();
+        assert!(options.sandbox);
+
+        let sandbox = SandboxOptions::default()
+            .user_namespaces(false)
+            .seccomp_filter(false);
+        let options = create_chrome_options_with(None, &sandbox).unwrap();
+        assert!(options.sandbox);
+        assert!(
+            options
+                .args
+                .iter()
+                .any(|a| *a == "--disable-namespace-sandbox")
+        );
+        assert!(
+            options
+                .args
+                .iter()
+                .any(|a| *a == "--disable-seccomp-filter-sandbox")
+        );
+
+        let options =
+            create_chrome_options_with(None, &SandboxOptions::default().no_sandbox(true)).unwrap();
+        assert!(!options.sandbox);
+    }
+
+    /// Verifies stealth client hints follow the user agent's Chrome version.
+    #[test]
+    fn test_stealth_user_agent_override() {
+        let stealth = StealthOptions::default().languages(["de-DE", "de", "en"]);
+        let ua = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 \
+                  (KHTML, like Gecko) Chrome/120.0.6099.109 Safari/537.36";
+
+        let over = stealth.user_agent_override(ua);
+        assert_eq!(over.user_agent, ua);
+        assert_eq!(
+            over.accept_language.as_deref(),
+            Some("de-DE,de;q=0.9,en;q=0.8")
+        );
+        let metadata = over.user_agent_metadata.unwrap();
+        let brands = metadata.brands.unwrap();
+        assert!(
+            brands
+                .iter()
+                .any(|b| b.brand == "Google Chrome" && b.version == "120")
+        );
+        assert!(!brands.iter().any(|b| b.brand.contains("Headless")));
+        assert!(
+            metadata
+                .full_version_list
+                .unwrap()
+                .iter()
+                .any(|b| b.brand == "Chromium" && b.version == "120.0.6099.109")
+        );
+
+        let over = stealth.user_agent_override("Mozilla/5.0 (X11; Linux x86_64) Firefox/120.0");
+        assert!(over.user_agent_metadata.is_none());
+    }
+
+    /// Verifies builder settings reach the launch options, with extra
+    /// flags after the factory's own and one profile per browser.
+    #[test]
+    fn test_factory_builder() {
+        let dir =
+            std::env::temp_dir().join(format!("html2pdf-profile-test-{}", std::process::id()));
+        let factory = ChromeBrowserFactory::builder()
+            .path("/custom/chrome/path")
+            .sandbox(false)
+            .window_size(1280, 1024)
+            .user_data_dir(&dir)
+            .headless_mode(HeadlessMode::New)
+            .arg("--disable-gpu")
+            .args(["--disk-cache-size=0"])
+            .build();
+
+        let options = (factory.launch_options_fn)().unwrap();
+        assert_eq!(options.path, Some(PathBuf::from("/custom/chrome/path")));
+        assert!(!options.sandbox);
+        assert!(!options.headless);
+        assert_eq!(options.window_size, Some((1280, 1024)));
+        assert_eq!(
+            options.args[options.args.len() - 3..],
+            ["--headless=new", "--disable-gpu", "--disk-cache-size=0"]
+        );
+
+        let profile = options.user_data_dir.unwrap();
+        assert!(profile.is_dir());
+        assert!(profile.starts_with(&dir));
+        let other = (factory.launch_options_fn)().unwrap().user_data_dir;
+        assert_ne!(other.as_ref(), Some(&profile));
+
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let options = (ChromeBrowserFactory::builder().build().launch_options_fn)().unwrap();
+        assert!(options.sandbox);
+        assert!(options.headless);
+        assert_eq!(options.window_size, None);
+        assert_eq!(options.user_data_dir, None);
+    }
+
+    /// Verifies stealth factories drop the automation flags.
+    #[test]
+    fn test_stealth_launch_args() {
+        let factory = ChromeBrowserFactory::with_stealth(
+            None,
+            SandboxOptions::default(),
+            None,
+            None,
+            Some(StealthOptions::default()),
+        );
+        let options = (factory.launch_options_fn)().unwrap();
+        assert!(!options.args.iter().any(|a| *a == "--enable-automation"));
+        assert!(
+            options
+                .args
+                .iter()
+                .any(|a| *a == "--disable-blink-features=AutomationControlled")
+        );
+
+        let options = create_chrome_options(None).unwrap();
+        assert!(options.args.iter().any(|a| *a == "--enable-automation"));
+    }
+
+    /// Verifies timers in background tabs are not throttled.
+    #[test]
+    fn test_background_throttling_disabled() {
+        let options = create_chrome_options(None).unwrap();
+        for flag in [
+            "--disable-background-timer-throttling",
+            "--disable-backgrounding-occluded-windows",
+            "--disable-renderer-backgrounding",
+            "--disable-features=IntensiveWakeUpThrottling",
+        ] {
+            assert!(options.args.iter().any(|a| *a == flag), "{}", flag);
+        }
+    }
+
+    /// Verifies pooled browsers launch with popups blocked.
+    #[test]
+    fn test_popups_blocked() {
+        let options = create_chrome_options(None).unwrap();
+        assert!(
+            options
+                .args
+                .iter()
+                .any(|a| *a == "--block-new-web-contents")
+        );
+        assert!(
+            !options
+                .args
+                .iter()
+                .any(|a| *a == "--disable-popup-blocking")
+        );
+    }
 }
@@ -0,0 +1,677 @@
+//! Firefox browser factory over WebDriver.
+//!
+//! This module provides [`FirefoxBrowserFactory`], which opens Firefox
+//! sessions through a running WebDriver server (`geckodriver`, or a
+//! Selenium Grid), for deployments that cannot ship Chrome.
+//!
+//! # Overview
+//!
+//! Each [`create`](BrowserFactory::create) call starts a new WebDriver
+//! session. `geckodriver` serves one session at a time, so run one per pool
+//! slot and list them all; sessions are spread over the endpoints
+//! round-robin. Each tab of a [`FirefoxBrowser`] is a WebDriver window, and
+//! retiring the browser deletes the session, which quits its Firefox.
+//!
+//! # Scope
+//!
+//! The backend plugs into the pool only: sizing, TTL, health checks,
+//! warmup and [`BrowserHandle::checkout_tab`](crate::BrowserHandle::checkout_tab)
+//! work as with Chrome, and tabs can navigate, print and take screenshots.
+//! The [`service`](crate::service) layer and the web integrations use
+//! Chrome DevTools Protocol features WebDriver lacks (request interception,
+//! emulation, cookies per request) and still require Chrome. Requires the
+//! `firefox` feature.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use html2pdf_api::{BrowserPoolBuilder, BrowserTab, FirefoxBrowser, FirefoxBrowserFactory};
+//!
+//! // geckodriver --port 4444 & geckodriver --port 4445 &
+//! let factory = FirefoxBrowserFactory::with_endpoints([
+//!     "http://localhost:4444",
+//!     "http://localhost:4445",
+//! ]);
+//!
+//! let pool = BrowserPoolBuilder::<FirefoxBrowser>::with_backend()
+//!     .factory(Box::new(factory))
+//!     .build()?;
+//! let browser = pool.get()?;
+//! let tab = browser.checkout_tab()?;
+//! tab.navigate("https://example.com")?;
+//! let pdf = tab.print_to_pdf(None)?;
+//! ```
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use headless_chrome::types::PrintToPdfOptions;
+use serde_json::{Value, json};
+
+use super::BrowserFactory;
+use crate::error::{BrowserPoolError, Result};
+use crate::traits::{BrowserTab, PooledBrowser};
+
+/// Default time a WebDriver command may take, page loads included.
+const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Centimetres per inch; Chrome's print options are in inches, WebDriver's
+/// in centimetres.
+const CM_PER_INCH: f64 = 2.54;
+
+/// Factory that opens Firefox sessions through WebDriver servers.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::FirefoxBrowserFactory;
+///
+/// let factory = FirefoxBrowserFactory::new("http://localhost:4444");
+/// ```
+pub struct FirefoxBrowserFactory {
+    /// WebDriver server URLs, used round-robin.
+    endpoints: Vec<String>,
+
+    /// Index of the endpoint the next session goes to.
+    next: AtomicUsize,
+
+    /// Whether Firefox runs headless.
+    headless: bool,
+
+    /// Firefox binary, when the WebDriver server should not pick one.
+    binary: Option<PathBuf>,
+
+    /// Longest a single WebDriver command may take.
+    command_timeout: Duration,
+}
+
+impl FirefoxBrowserFactory {
+    /// Create factory for a single WebDriver server.
+    ///
+    /// # Parameters
+    ///
+    /// * `url` - `http://` or `https://` URL of the server, such as
+    ///   `http://localhost:4444` for `geckodriver --port 4444`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_endpoints([url.into()])
+    }
+
+    /// Create factory that spreads sessions over several WebDriver servers.
+    ///
+    /// An empty list is accepted here and reported by
+    /// [`create`](BrowserFactory::create).
+    pub fn with_endpoints<I, S>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            endpoints: urls.into_iter().map(Into::into).collect(),
+            next: AtomicUsize::new(0),
+            headless: true,
+            binary: None,
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
+        }
+    }
+
+    /// Run Firefox with a visible window (default: headless).
+    pub fn headless(mut self, headless: bool) -> Self {
+        self.headless = headless;
+        self
+    }
+
+    /// Use the Firefox binary at `path` instead of the server's default.
+    pub fn binary(mut self, path: impl Into<PathBuf>) -> Self {
+        self.binary = Some(path.into());
+        self
+    }
+
+    /// Set the longest a single WebDriver command may take.
+    ///
+    /// Navigation waits for the page to load within this limit.
+    pub fn command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    /// The configured endpoints.
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    /// Pick the endpoint for the next session.
+    fn next_endpoint(&self) -> Result<&str> {
+        if self.endpoints.is_empty() {
+            return Err(BrowserPoolError::Configuration(
+                "FirefoxBrowserFactory has no endpoints".to_string(),
+            ));
+        }
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.endpoints.len();
+        let endpoint = &self.endpoints[index];
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err(BrowserPoolError::Configuration(format!(
+                "WebDriver endpoint must be an http:// or https:// URL: {}",
+                endpoint
+            )));
+        }
+        Ok(endpoint.trim_end_matches('/'))
+    }
+
+    /// The `New Session` capabilities.
+    fn capabilities(&self) -> Value {
+        let mut options = json!({ "args": [] });
+        if self.headless {
+            options["args"] = json!(["-headless"]);
+        }
+        if let Some(binary) = &self.binary {
+            options["binary"] = json!(binary.to_string_lossy());
+        }
+        json!({
+            "capabilities": {
+                "alwaysMatch": {
+                    "browserName": "firefox",
+                    "pageLoadStrategy": "normal",
+                    "moz:firefoxOptions": options,
+                }
+            }
+        })
+    }
+}
+
+impl BrowserFactory<FirefoxBrowser> for FirefoxBrowserFactory {
+    /// Start a session on the next WebDriver server.
+    ///
+    /// # Errors
+    ///
+    /// * Returns [`BrowserPoolError::Configuration`] if no endpoints are
+    ///   configured or the chosen one is not an HTTP URL.
+    /// * Returns [`BrowserPoolError::BrowserCreation`] if the server refuses
+    ///   the session (busy `geckodriver`, Firefox not found).
+    fn create(&self) -> Result<FirefoxBrowser> {
+        let endpoint = self.next_endpoint()?;
+
+        log::debug!(" Starting Firefox session at {}...", endpoint);
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .timeout_global(Some(self.command_timeout))
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        let created = command(
+            &agent,
+            "POST",
+            &format!("{}/session", endpoint),
+            Some(self.capabilities()),
+        )
+        .and_then(|value| {
+            value["sessionId"]
+                .as_str()
+                .map(String::from)
+                .ok_or_else(|| "response has no sessionId".to_string())
+        });
+        let id = created.map_err(|e| {
+            log::error!("❌ Firefox session at {} failed: {}", endpoint, e);
+            BrowserPoolError::BrowserCreation(e)
+        })?;
+        log::debug!("✅ Firefox session {} started", id);
+
+        Ok(FirefoxBrowser {
+            session: Arc::new(Session {
+                agent,
+                url: format!("{}/session/{}", endpoint, id),
+                current_window: Mutex::new(None),
+            }),
+        })
+    }
+}
+
+impl std::fmt::Debug for FirefoxBrowserFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FirefoxBrowserFactory")
+            .field("endpoints", &self.endpoints)
+            .field("headless", &self.headless)
+            .field("binary", &self.binary)
+            .field("command_timeout", &self.command_timeout)
+            .finish()
+    }
+}
+
+/// A Firefox instance driven through a WebDriver session.
+///
+/// Created by [`FirefoxBrowserFactory`]. The session's first window is kept
+/// open and never handed out, since closing a session's last window ends
+/// the session.
+pub struct FirefoxBrowser {
+    session: Arc<Session>,
+}
+
+impl PooledBrowser for FirefoxBrowser {
+    type Tab = FirefoxTab;
+
+    fn new_tab(&self) -> Result<Arc<FirefoxTab>> {
+        let value = self
+            .session
+            .send("POST", "/window/new", Some(json!({ "type": "tab" })))
+            .map_err(|e| BrowserPoolError::TabCreation(format!("new window failed: {}", e)))?;
+        let handle = value["handle"]
+            .as_str()
+            .ok_or_else(|| BrowserPoolError::TabCreation("new window has no handle".to_string()))?;
+        Ok(Arc::new(FirefoxTab {
+            session: Arc::clone(&self.session),
+            handle: handle.to_string(),
+        }))
+    }
+
+    /// A `Get Window Handles` round-trip, which proves the session and its
+    /// Firefox are alive.
+    fn ping(&self) -> Result<()> {
+        self.session
+            .window_handles()
+            .map(|_| ())
+            .map_err(|e| BrowserPoolError::HealthCheckFailed(format!("session ping failed: {}", e)))
+    }
+
+    fn open_tab_count(&self) -> usize {
+        self.session
+            .window_handles()
+            .map(|handles| handles.saturating_sub(1))
+            .unwrap_or(0)
+    }
+}
+
+/// A tab of a [`FirefoxBrowser`]: one WebDriver window.
+pub struct FirefoxTab {
+    session: Arc<Session>,
+    handle: String,
+}
+
+impl FirefoxTab {
+    /// Run a command against this window.
+    fn send(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<Value>,
+    ) -> std::result::Result<Value, String> {
+        self.session.send_in(&self.handle, method, path, body)
+    }
+}
+
+impl BrowserTab for FirefoxTab {
+    /// `Navigate To`, which returns once the page has loaded.
+    fn navigate(&self, url: &str) -> Result<()> {
+        self.send("POST", "/url", Some(json!({ "url": url })))
+            .map(|_| ())
+            .map_err(|e| BrowserPoolError::TabCreation(format!("navigation failed: {}", e)))
+    }
+
+    /// `Print Page`, with the options WebDriver supports: orientation,
+    /// scale, backgrounds, paper size, margins and page ranges.
+    fn print_to_pdf(&self, options: Option<PrintToPdfOptions>) -> Result<Vec<u8>> {
+        let parameters = print_parameters(&options.unwrap_or_default());
+        self.send("POST", "/print", Some(parameters))
+            .and_then(|value| decode_base64(&value))
+            .map_err(|e| BrowserPoolError::TabCreation(format!("printing failed: {}", e)))
+    }
+
+    fn screenshot(&self) -> Result<Vec<u8>> {
+        self.send("GET", "/screenshot", None)
+            .and_then(|value| decode_base64(&value))
+            .map_err(|e| BrowserPoolError::TabCreation(format!("screenshot failed: {}", e)))
+    }
+
+    fn close(&self) -> Result<()> {
+        let closed = self.send("DELETE", "/window", None);
+        // Whatever happened, no window is current any more
+        if let Ok(mut current) = self.session.current_window.lock() {
+            *current = None;
+        }
+        closed
+            .map(|_| ())
+            .map_err(|e| BrowserPoolError::TabCreation(format!("tab close failed: {}", e)))
+    }
+}
+
+/// A WebDriver session shared by a browser and its tabs.
+///
+/// WebDriver commands act on the session's current window, so tabs switch
+/// to their own window first; the lock keeps the switch and the command
+/// together.
+struct Session {
+    agent: ureq::Agent,
+    /// `{endpoint}/session/{id}`.
+    url: String,
+    /// Window commands currently act on.
+    current_window: Mutex<Option<String>>,
+}
+
+impl Session {
+    /// Run a command that does not depend on the current window.
+    fn send(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<Value>,
+    ) -> std::result::Result<Value, String> {
+        command(&self.agent, method, &format!("{}{}", self.url, path), body)
+    }
+
+    /// Run a command against window `handle`.
+    fn send_in(
+        &self,
+        handle: &str,
+        method: &str,
+        path: &str,
+        body: Option<Value>,
+    ) -> std::result::Result<Value, String> {
+        let mut current = self
+            .current_window
+            .lock()
+            .map_err(|_| "session lock poisoned".to_string())?;
+        if current.as_deref() != Some(handle) {
+            self.send("POST", "/window", Some(json!({ "handle": handle })))?;
+            *current = Some(handle.to_string());
+        }
+        self.send(method, path, body)
+    }
+
+    /// Number of windows open in the session.
+    fn window_handles(&self) -> std::result::Result<usize, String> {
+        let value = self.send("GET", "/window/handles", None)?;
+        value
+            .as_array()
+            .map(Vec::len)
+            .ok_or_else(|| "malformed window handles".to_string())
+    }
+}
+
+impl Drop for Session {
+    /// Delete the session, which quits its Firefox.
+    fn drop(&mut self) {
+        log::debug!(" Deleting Firefox session {}", self.url);
+        if let Err(e) = command(&self.agent, "DELETE", &self.url, None) {
+            log::warn!("⚠️ Failed to delete Firefox session {}: {}", self.url, e);
+        }
+    }
+}
+
+/// Send a WebDriver command and return its `value`.
+///
+/// Errors carry the WebDriver error code and message.
+fn command(
+    agent: &ureq::Agent,
+    method: &str,
+    url: &str,
+    body: Option<Value>,
+) -> std::result::Result<Value, String> {
+    let response = match (method, body) {
+        ("POST", body) => {
+            let body = serde_json::to_vec(&body.unwrap_or_else(|| json!({})))
+                .map_err(|e| e.to_string())?;
+            agent
+                .post(url)
+                .header("Content-Type", "application/json")
+                .send(&body[..])
+        }
+        ("DELETE", _) => agent.delete(url).call(),
+        _ => agent.get(url).call(),
+    };
+    let mut response = response.map_err(|e| e.to_string())?;
+    let status = response.status();
+    let text = response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| e.to_string())?;
+    let mut json: Value = serde_json::from_str(&text)
+        .map_err(|_| format!("HTTP {}: malformed WebDriver response", status))?;
+    let value = json["value"].take();
+    if !status.is_success() {
+        return Err(format!(
+            "{}: {}",
+            value["error"].as_str().unwrap_or("unknown error"),
+            value["message"].as_str().unwrap_or_default()
+        ));
+    }
+    Ok(value)
+}
+
+/// Map Chrome's print options onto WebDriver's `Print Page` parameters.
+///
+/// Header and footer templates, tagged PDFs and document outlines have no
+/// WebDriver equivalent and are ignored.
+fn print_parameters(options: &PrintToPdfOptions) -> Value {
+    let mut parameters = json!({});
+    if let Some(landscape) = options.landscape {
+        parameters["orientation"] = json!(if landscape { "landscape" } else { "portrait" });
+    }
+    if let Some(scale) = options.scale {
+        parameters["scale"] = json!(scale);
+    }
+    if let Some(background) = options.print_background {
+        parameters["background"] = json!(background);
+    }
+
+    let mut page = json!({});
+    for (key, inches) in [
+        ("width", options.paper_width),
+        ("height", options.paper_height),
+    ] {
+        if let Some(inches) = inches {
+            page[key] = json!(inches * CM_PER_INCH);
+        }
+    }
+    if page.as_object().is_some_and(|page| !page.is_empty()) {
+        parameters["page"] = page;
+    }
+
+    let mut margin = json!({});
+    for (key, inches) in [
+        ("top", options.margin_top),
+        ("bottom", options.margin_bottom),
+        ("left", options.margin_left),
+        ("right", options.margin_right),
+    ] {
+        if let Some(inches) = inches {
+            margin[key] = json!(inches * CM_PER_INCH);
+        }
+    }
+    if margin.as_object().is_some_and(|margin| !margin.is_empty()) {
+        parameters["margin"] = margin;
+    }
+
+    if let Some(ranges) = &options.page_ranges {
+        let ranges: Vec<&str> = ranges
+            .split(',')
+            .map(str::trim)
+            .filter(|range| !range.is_empty())
+            .collect();
+        if !ranges.is_empty() {
+            parameters["pageRanges"] = json!(ranges);
+        }
+    }
+    parameters
+}
+
+/// Decode a command's base64 `value` (a PDF or PNG).
+fn decode_base64(value: &Value) -> std::result::Result<Vec<u8>, String> {
+    let data = value.as_str().ok_or("response is not base64 data")?;
+    BASE64.decode(data).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve canned WebDriver responses, one connection per request, and
+    /// return the server's URL and the requests it received.
+    fn webdriver_stub(
+        responses: Vec<(u16, Value)>,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, value) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                requests.push(format!(
+                    "{} {}",
+                    request_line.trim(),
+                    String::from_utf8(body).unwrap()
+                ));
+
+                let body = json!({ "value": value }).to_string();
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    /// Verifies a session is created, tabs switch to their own window
+    /// before each command, and dropping the browser deletes the session.
+    #[test]
+    fn test_session_lifecycle() {
+        let pdf = BASE64.encode(b"%PDF-1.7");
+        let (url, server) = webdriver_stub(vec![
+            (200, json!({ "sessionId": "s1", "capabilities": {} })),
+            (200, json!({ "handle": "w2", "type": "tab" })),
+            (200, Value::Null),
+            (200, Value::Null),
+            (200, json!(pdf)),
+            (200, json!(["w1", "w2"])),
+            (200, Value::Null),
+            (200, Value::Null),
+        ]);
+
+        let factory = FirefoxBrowserFactory::new(format!("{}/", url));
+        let browser = factory.create().unwrap();
+        let tab = browser.new_tab().unwrap();
+        tab.navigate("https://example.com").unwrap();
+        let printed = tab
+            .print_to_pdf(Some(PrintToPdfOptions {
+                landscape: Some(true),
+                ..Default::default()
+            }))
+            .unwrap();
+        assert_eq!(printed, b"%PDF-1.7");
+        assert_eq!(browser.open_tab_count(), 1);
+        tab.close().unwrap();
+        drop(tab);
+        drop(browser);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /session "));
+        assert!(requests[0].contains("\"-headless\""));
+        assert!(requests[1].starts_with("POST /session/s1/window/new "));
+        assert!(requests[2].starts_with("POST /session/s1/window "));
+        assert!(requests[2].contains("\"w2\""));
+        assert!(requests[3].starts_with("POST /session/s1/url "));
+        assert!(requests[4].starts_with("POST /session/s1/print "));
+        assert!(requests[4].contains("landscape"));
+        assert!(requests[5].starts_with("GET /session/s1/window/handles "));
+        // Still on w2, so no switch before closing it
+        assert!(requests[6].starts_with("DELETE /session/s1/window "));
+        assert!(requests[7].starts_with("DELETE /session/s1 "));
+    }
+
+    /// Verifies WebDriver errors surface with their code and message.
+    #[test]
+    fn test_session_refused() {
+        let (url, server) = webdriver_stub(vec![(
+            500,
+            json!({ "error": "session not created", "message": "Session is already started" }),
+        )]);
+
+        match FirefoxBrowserFactory::new(url).create() {
+            Err(BrowserPoolError::BrowserCreation(msg)) => {
+                assert_eq!(msg, "session not created: Session is already started")
+            }
+            other => panic!("expected BrowserCreation, got {:?}", other.err()),
+        }
+        server.join().unwrap();
+    }
+
+    /// Verifies that sessions rotate over the endpoints and bad lists fail
+    /// as configuration errors.
+    #[test]
+    fn test_endpoints() {
+        let factory = FirefoxBrowserFactory::with_endpoints(["http://a:4444", "http://b:4444"]);
+        assert_eq!(factory.next_endpoint().unwrap(), "http://a:4444");
+        assert_eq!(factory.next_endpoint().unwrap(), "http://b:4444");
+
+        for factory in [
+            FirefoxBrowserFactory::with_endpoints(Vec::<String>::new()),
+            FirefoxBrowserFactory::new("ws://a:4444"),
+        ] {
+            assert!(matches!(
+                factory.create(),
+                Err(BrowserPoolError::Configuration(_))
+            ));
+        }
+    }
+
+    /// Verifies Chrome's print options become WebDriver parameters in
+    /// centimetres.
+    #[test]
+    fn test_print_parameters() {
+        assert_eq!(print_parameters(&PrintToPdfOptions::default()), json!({}));
+
+        let parameters = print_parameters(&PrintToPdfOptions {
+            landscape: Some(false),
+            scale: Some(0.5),
+            print_background: Some(true),
+            paper_width: Some(10.0),
+            margin_top: Some(1.0),
+            page_ranges: Some("1-3, 5".to_string()),
+            header_template: Some("<span></span>".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            parameters,
+            json!({
+                "orientation": "portrait",
+                "scale": 0.5,
+                "background": true,
+                "page": { "width": 25.4 },
+                "margin": { "top": 2.54 },
+                "pageRanges": ["1-3", "5"],
+            })
+        );
+    }
+}
@@ -15,7 +15,10 @@
 //! | Factory | Description |
 //! |---------|-------------|
 //! | [`ChromeBrowserFactory`] | Creates Chrome/Chromium browsers |
+//! | [`RemoteCdpBrowserFactory`] | Connects to already-running browsers over CDP |
+//! | `FirefoxBrowserFactory` | Opens Firefox sessions over WebDriver (`firefox` feature) |
 //! | [`mock::MockBrowserFactory`] | For testing (feature-gated) |
+//! | Closure | Via [`BrowserPoolBuilder::factory_fn`](crate::BrowserPoolBuilder::factory_fn) |
 //!
 //! # Example
 //!
@@ -48,21 +51,37 @@
 //!     }
 //! }
 //! ```
+//!
+//! For launch logic that needs no state of its own, pass a closure to
+//! [`BrowserPoolBuilder::factory_fn`](crate::BrowserPoolBuilder::factory_fn)
+//! instead of defining a type.
 
-mod chrome;
+pub(crate) mod chrome;
+#[cfg(feature = "firefox")]
+mod firefox;
+mod remote;
 
 #[cfg(any(test, feature = "test-utils"))]
 pub mod mock;
 
-pub use chrome::{ChromeBrowserFactory, create_chrome_options};
+pub use chrome::{
+    ChromeBrowserFactory, ChromeBrowserFactoryBuilder, FontDirectory, HeadlessMode, SandboxOptions,
+    StealthOptions, TrustedCa, create_chrome_options, create_chrome_options_with,
+};
+#[cfg(feature = "firefox")]
+pub use firefox::{FirefoxBrowser, FirefoxBrowserFactory, FirefoxTab};
+pub use remote::RemoteCdpBrowserFactory;
 
 use crate::error::Result;
+use crate::traits::PooledBrowser;
 use headless_chrome::Browser;
 
 /// Trait for browser factory pattern.
 ///
 /// Abstracts browser creation to allow different implementations
-/// (Chrome, Firefox, mock browsers for testing, etc.)
+/// (Chrome, Firefox, mock browsers for testing, etc.). `B` is the browser
+/// backend the factory creates, Chrome unless another
+/// [`PooledBrowser`] is named.
 ///
 /// # Thread Safety
 ///
@@ -72,6 +91,9 @@ use headless_chrome::Browser;
 /// # Implementors
 ///
 /// - [`ChromeBrowserFactory`] - Creates Chrome/Chromium browsers
+/// - [`RemoteCdpBrowserFactory`] - Connects to already-running browsers
+/// - `FirefoxBrowserFactory` - Opens Firefox sessions over WebDriver
+///   (when `firefox` feature enabled)
 /// - [`mock::MockBrowserFactory`] - For testing (when `test-utils` feature enabled)
 ///
 /// # Example
@@ -89,7 +111,7 @@ use headless_chrome::Browser;
 /// let factory = ChromeBrowserFactory::with_defaults();
 /// use_factory(&factory);
 /// ```
-pub trait BrowserFactory: Send + Sync {
+pub trait BrowserFactory<B: PooledBrowser = Browser>: Send + Sync {
     /// Create a new browser instance.
     ///
     /// # Errors
@@ -109,5 +131,37 @@ pub trait BrowserFactory: Send + Sync {
     /// let browser = factory.create()?;
     /// // Use browser...
     /// ```
-    fn create(&self) -> Result<Browser>;
+    fn create(&self) -> Result<B>;
+
+    /// Prepare a tab before it is handed to a render.
+    ///
+    /// Called by [`BrowserHandle::checkout_tab`](crate::BrowserHandle::checkout_tab)
+    /// for every tab it returns, including reused ones. An error discards
+    /// the tab and fails the checkout. The default does nothing;
+    /// [`ChromeBrowserFactory`] applies its [`StealthOptions`] here.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`](crate::BrowserPoolError::TabCreation)
+    /// if the tab can't be prepared.
+    fn prepare_tab(&self, tab: &B::Tab) -> Result<()> {
+        let _ = tab;
+        Ok(())
+    }
+}
+
+/// [`BrowserFactory`] calling a closure for every browser.
+///
+/// Created by [`BrowserPoolBuilder::factory_fn`](crate::BrowserPoolBuilder::factory_fn);
+/// tabs are used as-is (no [`prepare_tab`](BrowserFactory::prepare_tab) step).
+pub(crate) struct FnBrowserFactory<F>(pub(crate) F);
+
+impl<B, F> BrowserFactory<B> for FnBrowserFactory<F>
+where
+    B: PooledBrowser,
+    F: Fn() -> Result<B> + Send + Sync,
+{
+    fn create(&self) -> Result<B> {
+        (self.0)()
+    }
 }
@@ -0,0 +1,244 @@
+//! Remote Chrome DevTools Protocol (CDP) browser factory.
+//!
+//! This module provides [`RemoteCdpBrowserFactory`], which connects to
+//! Chrome/Chromium instances that are already running - for example a fleet
+//! of `browserless/chrome` containers - instead of spawning local processes.
+//!
+//! # Overview
+//!
+//! Each [`create`](BrowserFactory::create) call opens a new DevTools
+//! WebSocket connection. With several endpoints configured, connections are
+//! spread over them round-robin, so the pool's browsers end up distributed
+//! across the fleet.
+//!
+//! # Lifecycle
+//!
+//! The pool does not own the remote processes. Retiring a browser (TTL,
+//! failed health check, shutdown) closes its tabs and drops the connection;
+//! the remote Chrome keeps running. Launch settings such as
+//! [`SandboxOptions`](crate::SandboxOptions), [`TrustedCa`](crate::TrustedCa)
+//! and [`FontDirectory`](crate::FontDirectory) are the remote side's concern
+//! and are not applied.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use html2pdf_api::{BrowserPool, RemoteCdpBrowserFactory};
+//!
+//! let factory = RemoteCdpBrowserFactory::with_endpoints([
+//!     "ws://chrome-0:3000",
+//!     "ws://chrome-1:3000",
+//! ]);
+//!
+//! let pool = BrowserPool::builder()
+//!     .factory(Box::new(factory))
+//!     .build()?;
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use headless_chrome::{Browser, Tab};
+
+use super::{BrowserFactory, StealthOptions};
+use crate::error::{BrowserPoolError, Result};
+
+/// Default time a connection may go without DevTools traffic before it is
+/// dropped; matches `headless_chrome`'s own default.
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Factory that connects to already-running Chrome instances over CDP.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::RemoteCdpBrowserFactory;
+///
+/// let factory = RemoteCdpBrowserFactory::new("ws://localhost:3000");
+/// ```
+pub struct RemoteCdpBrowserFactory {
+    /// DevTools WebSocket URLs, used round-robin.
+    endpoints: Vec<String>,
+
+    /// Index of the endpoint the next connection goes to.
+    next: AtomicUsize,
+
+    /// Idle timeout passed to `Browser::connect_with_timeout`.
+    idle_timeout: Duration,
+
+    /// Stealth settings applied to each checked-out tab.
+    stealth: Option<StealthOptions>,
+}
+
+impl RemoteCdpBrowserFactory {
+    /// Create factory for a single DevTools WebSocket endpoint.
+    ///
+    /// # Parameters
+    ///
+    /// * `ws_url` - `ws://` or `wss://` URL of the browser's debugging
+    ///   endpoint. browserless accepts its token as a query parameter
+    ///   (`ws://host:3000?token=...`).
+    pub fn new(ws_url: impl Into<String>) -> Self {
+        Self::with_endpoints([ws_url.into()])
+    }
+
+    /// Create factory that spreads connections over several endpoints.
+    ///
+    /// An empty list is accepted here and reported by
+    /// [`create`](BrowserFactory::create).
+    pub fn with_endpoints<I, S>(ws_urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            endpoints: ws_urls.into_iter().map(Into::into).collect(),
+            next: AtomicUsize::new(0),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            stealth: None,
+        }
+    }
+
+    /// Set how long a connection may stay silent before it is dropped.
+    ///
+    /// Keep this above the pool's ping interval so the keep-alive task
+    /// holds idle connections open.
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Apply [`StealthOptions`] to every checked-out tab.
+    pub fn stealth(mut self, stealth: StealthOptions) -> Self {
+        self.stealth = Some(stealth);
+        self
+    }
+
+    /// Read endpoints from the `CHROME_WS_URL` environment variable.
+    ///
+    /// The variable holds one or more comma-separated WebSocket URLs.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(factory)` if `CHROME_WS_URL` names at least one endpoint
+    /// - `None` if it is unset or empty (launch Chrome locally instead)
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var("CHROME_WS_URL").ok()?;
+        let endpoints: Vec<String> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(String::from)
+            .collect();
+        if endpoints.is_empty() {
+            return None;
+        }
+        Some(Self::with_endpoints(endpoints))
+    }
+
+    /// The configured endpoints.
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    /// Pick the endpoint for the next connection.
+    fn next_endpoint(&self) -> Result<&str> {
+        if self.endpoints.is_empty() {
+            return Err(BrowserPoolError::Configuration(
+                "RemoteCdpBrowserFactory has no endpoints".to_string(),
+            ));
+        }
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.endpoints.len();
+        let endpoint = &self.endpoints[index];
+        if !endpoint.starts_with("ws://") && !endpoint.starts_with("wss://") {
+            return Err(BrowserPoolError::Configuration(format!(
+                "CDP endpoint must be a ws:// or wss:// URL: {}",
+                redact(endpoint)
+            )));
+        }
+        Ok(endpoint)
+    }
+}
+
+impl BrowserFactory for RemoteCdpBrowserFactory {
+    /// Connect to the next remote browser.
+    ///
+    /// # Errors
+    ///
+    /// * Returns [`BrowserPoolError::Configuration`] if no endpoints are
+    ///   configured or the chosen one is not a WebSocket URL.
+    /// * Returns [`BrowserPoolError::BrowserCreation`] if the connection fails.
+    fn create(&self) -> Result<Browser> {
+        let endpoint = self.next_endpoint()?;
+
+        log::debug!(" Connecting to remote Chrome at {}...", redact(endpoint));
+        Browser::connect_with_timeout(endpoint.to_string(), self.idle_timeout).map_err(|e| {
+            log::error!(
+                "❌ Remote Chrome connection to {} failed: {}",
+                redact(endpoint),
+                e
+            );
+            BrowserPoolError::BrowserCreation(e.to_string())
+        })
+    }
+
+    /// Apply the [`StealthOptions`], if any, to a checked-out tab.
+    fn prepare_tab(&self, tab: &Tab) -> Result<()> {
+        match &self.stealth {
+            Some(stealth) => stealth.apply(tab),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Debug for RemoteCdpBrowserFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let endpoints: Vec<&str> = self.endpoints.iter().map(|e| redact(e)).collect();
+        f.debug_struct("RemoteCdpBrowserFactory")
+            .field("endpoints", &endpoints)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("stealth", &self.stealth.is_some())
+            .finish()
+    }
+}
+
+/// Strip the query string, which may carry an access token, for logging.
+fn redact(endpoint: &str) -> &str {
+    endpoint.split('?').next().unwrap_or(endpoint)
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that connections rotate over the configured endpoints.
+    #[test]
+    fn test_endpoints_round_robin() {
+        let factory = RemoteCdpBrowserFactory::with_endpoints(["ws://a:3000", "ws://b:3000"]);
+
+        assert_eq!(factory.next_endpoint().unwrap(), "ws://a:3000");
+        assert_eq!(factory.next_endpoint().unwrap(), "ws://b:3000");
+        assert_eq!(factory.next_endpoint().unwrap(), "ws://a:3000");
+    }
+
+    /// Verifies that bad endpoint lists fail as configuration errors.
+    #[test]
+    fn test_invalid_endpoints() {
+        let empty = RemoteCdpBrowserFactory::with_endpoints(Vec::<String>::new());
+        assert!(matches!(
+            empty.create(),
+            Err(BrowserPoolError::Configuration(_))
+        ));
+
+        let http = RemoteCdpBrowserFactory::new("http://chrome:9222?token=secret");
+        match http.create() {
+            Err(BrowserPoolError::Configuration(msg)) => assert!(!msg.contains("secret")),
+            other => panic!("expected Configuration, got {:?}", other.err()),
+        }
+        assert!(!format!("{:?}", http).contains("secret"));
+    }
+}
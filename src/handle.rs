@@ -32,8 +32,9 @@
 //!
 //! # Deref Behavior
 //!
-//! `BrowserHandle` implements [`Deref<Target = Browser>`](std::ops::Deref),
-//! allowing transparent access to all [`Browser`] methods:
+//! `BrowserHandle` implements [`Deref<Target = Browser>`](std::ops::Deref)
+//! (or the pool's [`PooledBrowser`] backend), allowing transparent access
+//! to all [`Browser`] methods:
 //!
 //! ```rust,ignore
 //! let browser = pool.get()?;
@@ -43,13 +44,34 @@
 //! let tabs = browser.get_tabs();          // Browser::get_tabs
 //! let version = browser.get_version()?;   // Browser::get_version
 //! ```
+//!
+//! # Tab Reuse
+//!
+//! [`BrowserHandle::checkout_tab()`] and [`BrowserHandle::release_tab()`]
+//! honor [`BrowserPoolConfig::reuse_tabs`](crate::BrowserPoolConfig::reuse_tabs).
+//! With reuse enabled, each browser keeps one persistent tab that is reset to
+//! `about:blank` between renders; otherwise a fresh tab is created and closed
+//! every time. Code that uses this pair works unchanged in both modes:
+//!
+//! ```rust,ignore
+//! let browser = pool.get()?;
+//! let tab = browser.checkout_tab()?;
+//!
+//! match tab.navigate_to(url).and_then(|t| t.wait_until_navigated()) {
+//!     Ok(_) => browser.release_tab(tab),  // reset (reuse) or close
+//!     Err(_) => browser.discard_tab(tab), // always close
+//! }
+//! ```
 
 use std::sync::Arc;
 
 use headless_chrome::Browser;
 
+use crate::error::Result;
 use crate::pool::BrowserPoolInner;
+use crate::queue::CheckoutSlot;
 use crate::tracked::TrackedBrowser;
+use crate::traits::{BrowserTab, PooledBrowser};
 
 /// RAII handle for browser instances.
 ///
@@ -106,21 +128,27 @@ use crate::tracked::TrackedBrowser;
 ///
 /// // ...the browser is still returned to the pool during unwinding
 /// ```
-pub struct BrowserHandle {
+pub struct BrowserHandle<B: PooledBrowser = Browser> {
     /// The tracked browser (Option allows taking in Drop).
     ///
     /// This is `Option` so we can `take()` it in the `Drop` implementation
     /// without requiring `&mut self` to be valid after drop.
-    tracked: Option<TrackedBrowser>,
+    tracked: Option<TrackedBrowser<B>>,
 
     /// Reference to pool for returning browser.
     ///
     /// We keep an `Arc` reference to the pool's inner state so we can
     /// return the browser even if the original `BrowserPool` has been dropped.
-    pool: Arc<BrowserPoolInner>,
+    pool: Arc<BrowserPoolInner<B>>,
+
+    /// The checkout's place in the pool's capacity.
+    ///
+    /// Dropped after `Drop::drop` has returned the browser, so a checkout
+    /// waiting in the queue finds it back in the pool.
+    _slot: CheckoutSlot,
 }
 
-impl BrowserHandle {
+impl<B: PooledBrowser> BrowserHandle<B> {
     /// Create a new browser handle.
     ///
     /// This is called internally by [`BrowserPool::get()`](crate::BrowserPool::get).
@@ -130,10 +158,16 @@ impl BrowserHandle {
     ///
     /// * `tracked` - The tracked browser instance.
     /// * `pool` - Arc reference to the pool's inner state.
-    pub(crate) fn new(tracked: TrackedBrowser, pool: Arc<BrowserPoolInner>) -> Self {
+    /// * `slot` - The checkout slot reserved for this browser.
+    pub(crate) fn new(
+        tracked: TrackedBrowser<B>,
+        pool: Arc<BrowserPoolInner<B>>,
+        slot: CheckoutSlot,
+    ) -> Self {
         Self {
             tracked: Some(tracked),
             pool,
+            _slot: slot,
         }
     }
 
@@ -186,10 +220,119 @@ impl BrowserHandle {
     pub fn age_minutes(&self) -> u64 {
         self.tracked.as_ref().map(|t| t.age_minutes()).unwrap_or(0)
     }
+
+    /// Configuration of the pool this browser belongs to.
+    ///
+    /// Lets per-render code apply pool-wide policy without taking the
+    /// pool lock again.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    #[inline]
+    pub(crate) fn pool_config(&self) -> &crate::BrowserPoolConfig {
+        self.pool.config()
+    }
+
+    /// Get a tab to render in.
+    ///
+    /// Returns the tab parked on this browser if there is one: the standby
+    /// tab opened during warmup/replacement, or with
+    /// [`reuse_tabs`](crate::BrowserPoolConfig::reuse_tabs) enabled the
+    /// persistent tab from the previous render. Otherwise creates a new tab.
+    /// The tab is readied with [`BrowserTab::prepare`]: for Chrome,
+    /// downloads are denied on it, and it is kept active and focused so its
+    /// timers and animations run at full speed.
+    ///
+    /// Pair every call with [`release_tab()`](Self::release_tab) or
+    /// [`discard_tab()`](Self::discard_tab).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`](crate::BrowserPoolError::TabCreation)
+    /// if a new tab cannot be opened or prepared (for Chrome, downloads
+    /// cannot be denied on it), or if the factory fails to prepare it (see [`BrowserFactory::prepare_tab`](crate::BrowserFactory::prepare_tab)).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let browser = pool.get()?;
+    /// let tab = browser.checkout_tab()?;
+    /// tab.navigate_to("https://example.com")?;
+    /// browser.release_tab(tab);
+    /// ```
+    pub fn checkout_tab(&self) -> Result<Arc<B::Tab>> {
+        let tab = match self.tracked.as_ref().and_then(|t| t.take_reusable_tab()) {
+            Some(tab) => {
+                log::trace!("Using parked tab of browser {}", self.id());
+                tab
+            }
+            None => PooledBrowser::new_tab(&**self)?,
+        };
+
+        if let Err(e) = tab.prepare() {
+            self.discard_tab(tab);
+            return Err(e);
+        }
+
+        if let Err(e) = self.pool.factory().prepare_tab(&tab) {
+            self.discard_tab(tab);
+            return Err(e);
+        }
+
+        Ok(tab)
+    }
+
+    /// Return a tab after a successful render.
+    ///
+    /// With tab reuse enabled, navigates the tab to `about:blank` and parks
+    /// it for the next render; if the reset fails the tab is closed instead.
+    /// Otherwise the tab is closed. Errors are logged, never returned.
+    pub fn release_tab(&self, tab: Arc<B::Tab>) {
+        let Some(tracked) = self.tracked.as_ref().filter(|_| self.reuse_tabs()) else {
+            self.discard_tab(tab);
+            return;
+        };
+
+        match tab.reset() {
+            Ok(_) => {
+                log::trace!("Parked persistent tab of browser {}", tracked.id());
+                tracked.store_reusable_tab(tab);
+            }
+            Err(e) => {
+                log::warn!(
+                    "⚠️ Failed to reset tab of browser {} (closing it): {}",
+                    tracked.id(),
+                    e
+                );
+                self.discard_tab(tab);
+            }
+        }
+    }
+
+    /// Close a tab without reusing it.
+    ///
+    /// Use after a failed render so a tab in an unknown state is never
+    /// handed to the next request. Errors are logged, never returned.
+    pub fn discard_tab(&self, tab: Arc<B::Tab>) {
+        if let Err(e) = tab.close() {
+            log::warn!(
+                "Failed to close tab (continuing anyway, resources will be cleaned up): {}",
+                e
+            );
+        }
+    }
+
+    /// Whether the owning pool is configured for tab reuse.
+    fn reuse_tabs(&self) -> bool {
+        self.pool.config().reuse_tabs
+    }
 }
 
-impl std::ops::Deref for BrowserHandle {
-    type Target = Browser;
+impl<B: PooledBrowser> std::ops::Deref for BrowserHandle<B> {
+    type Target = B;
 
     /// Transparently access the underlying Browser.
     ///
@@ -212,7 +355,7 @@ impl std::ops::Deref for BrowserHandle {
     }
 }
 
-impl Drop for BrowserHandle {
+impl<B: PooledBrowser> Drop for BrowserHandle<B> {
     /// Automatically return browser to pool when handle is dropped.
     ///
     /// This is the critical RAII pattern that ensures browsers are always
@@ -236,7 +379,7 @@ impl Drop for BrowserHandle {
     }
 }
 
-impl std::fmt::Debug for BrowserHandle {
+impl<B: PooledBrowser> std::fmt::Debug for BrowserHandle<B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.tracked {
             Some(tracked) => f
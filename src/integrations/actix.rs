@@ -36,7 +36,11 @@
 //! |--------|------|-------------|
 //! | GET | `/pdf?url=...` | Convert URL to PDF |
 //! | POST | `/pdf/html` | Convert HTML to PDF |
+//! | DELETE | `/jobs/{id}` | Cancel a running render |
 //! | GET | `/pool/stats` | Pool statistics |
+//! | GET | `/pool/debug` | Pool diagnostics snapshot |
+//! | GET | `/pool/stats/detailed` | Per-browser statistics and lifetime counters |
+//! | GET | `/usage` | Usage of the caller's API key |
 //! | GET | `/health` | Health check |
 //! | GET | `/ready` | Readiness check |
 //!
@@ -155,12 +159,15 @@
 //!
 //! # Graceful Shutdown
 //!
-//! For proper cleanup, shutdown the pool when the server stops:
+//! For proper cleanup, drain and shutdown the pool when the server stops.
+//! Actix stops on SIGTERM; draining lets renders still holding a browser
+//! finish first:
 //!
 //! ```rust,ignore
 //! use actix_web::{App, HttpServer, web};
 //! use html2pdf_api::prelude::*;
 //! use std::sync::Arc;
+//! use std::time::Duration;
 //!
 //! #[actix_web::main]
 //! async fn main() -> std::io::Result<()> {
@@ -182,6 +189,8 @@
 //!     let result = server.await;
 //!
 //!     // Cleanup pool after server stops
+//!     let drained = shutdown_pool.lock().unwrap().start_drain(Duration::from_secs(25));
+//!     drained.await;
 //!     if let Ok(mut pool) = shutdown_pool.lock() {
 //!         pool.shutdown();
 //!     }
@@ -198,7 +207,16 @@
 //! |---------|--------|--------------|-------------|
 //! | [`pdf_from_url`] | GET | `/pdf` | Convert URL to PDF |
 //! | [`pdf_from_html`] | POST | `/pdf/html` | Convert HTML to PDF |
+//! | [`pdf_from_html_assets`] | POST | `/pdf/html/assets` | Convert HTML uploaded with its assets to PDF |
+//! | [`pdf_merge`] | POST | `/pdf/merge` | Render several documents into one PDF |
+//! | [`submit_job`] | POST | `/jobs` | Start a background render |
+//! | [`job_status`] | GET | `/jobs/{id}` | Status of a job |
+//! | [`job_result`] | GET | `/jobs/{id}/result` | Download a finished job's PDF |
+//! | [`cancel_job`] | DELETE | `/jobs/{id}` | Cancel a job or delete its result |
 //! | [`pool_stats`] | GET | `/pool/stats` | Pool statistics |
+//! | [`pool_debug`] | GET | `/pool/debug` | Pool diagnostics snapshot |
+//! | [`pool_stats_detailed`] | GET | `/pool/stats/detailed` | Per-browser statistics and lifetime counters |
+//! | [`usage_stats`] | GET | `/usage` | Usage of the caller's API key |
 //! | [`health_check`] | GET | `/health` | Health check (always 200) |
 //! | [`readiness_check`] | GET | `/ready` | Readiness check (checks pool) |
 //!
@@ -223,15 +241,18 @@
 //! |-------|-------------|
 //! | [`BrowserPoolActixExt`] | Adds `into_actix_data()` to `BrowserPool` |
 
-use actix_web::{HttpResponse, Responder, http::header, web};
+use actix_web::{HttpRequest, HttpResponse, HttpResponseBuilder, Responder, http::header, web};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use crate::SharedBrowserPool;
+use crate::cancel::CancelHandle;
 use crate::pool::BrowserPool;
 use crate::service::{
-    self, DEFAULT_TIMEOUT_SECS, ErrorResponse, HealthResponse, PdfFromHtmlRequest,
-    PdfFromUrlRequest, PdfServiceError,
+    self, API_KEY_HEADER, ByteRange, DEFAULT_TIMEOUT_SECS, ErrorResponse, FallbackPdf,
+    HealthResponse, IDEMPOTENCY_KEY_HEADER, JOB_ID_HEADER, JobRequest, PageInfo,
+    PdfFromAssetsRequest, PdfFromHtmlRequest, PdfFromUrlRequest, PdfMergeRequest, PdfResponse,
+    PdfServiceError, PdfStream, REQUEST_ID_HEADER, ResponseBody, TRACE_ID_HEADER,
 };
 
 // ============================================================================
@@ -301,6 +322,41 @@ pub type BrowserPoolData = web::Data<SharedBrowserPool>;
 /// | `landscape` | bool | No | `false` | Use landscape orientation |
 /// | `download` | bool | No | `false` | Force download vs inline display |
 /// | `print_background` | bool | No | `true` | Include background graphics |
+/// | `paper_format` | string | No | - | Named paper size: `Letter`, `Legal`, `Tabloid`, `Ledger`, `A0`-`A6` |
+/// | `paper_width` | f64 | No | - | Paper width in inches (overrides `paper_format`) |
+/// | `paper_height` | f64 | No | - | Paper height in inches (overrides `paper_format`) |
+/// | `margin_top`, `margin_bottom`, `margin_left`, `margin_right` | f64 | No | - | Margins in inches |
+/// | `scale` | f64 | No | `1.0` | Rendering scale, 0.1-2.0 |
+/// | `page_ranges` | string | No | all pages | Pages to print, e.g. `1-5, 8` |
+/// | `display_header_footer` | bool | No | with a template | Print the header and footer templates |
+/// | `header_template` | string | No | - | HTML printed in the top margin (classes `pageNumber`, `totalPages`, `date`, `title`, `url`) |
+/// | `footer_template` | string | No | - | HTML printed in the bottom margin |
+/// | `emulate_media` | string | No | `print` | CSS media type: `print` or `screen` |
+/// | `color_scheme` | string | No | `light` | `prefers-color-scheme`: `light` or `dark` |
+/// | `generate_tagged_pdf` | bool | No | pool default | Write a tagged (accessible) PDF |
+/// | `generate_document_outline` | bool | No | pool default | Add bookmarks built from the headings |
+/// | `javascript_enabled` | bool | No | `true` | Run the page's scripts (`false` for untrusted content) |
+/// | `block_resources` | string | No | - | Comma-separated resource types to block (`image,font,media`) |
+/// | `block_urls` | string | No | - | Comma-separated domains or URL patterns to block |
+/// | `ignore_tls_errors` | bool | No | `false` | Accept invalid TLS certificates (logged as a warning) |
+/// | `offline` | bool | No | `false` | Cut the network once the page has loaded |
+/// | `bypass_cache` | bool | No | `false` | Ignore the browser's HTTP cache |
+/// | `fallback_pdf` | bool | No | `false` | On failure, return a placeholder PDF (HTTP 200) instead of a JSON error |
+/// | `thumbnail` | bool | No | `false` | Respond with an image of the first page instead of the PDF |
+/// | `thumbnail_width` | u32 | No | `256` | Thumbnail width in pixels (16-2048) |
+/// | `thumbnail_format` | string | No | `png` | Thumbnail format: `png`, `jpeg`, or `webp` |
+/// | `thumbnail_quality` | u32 | No | - | JPEG/WebP thumbnail quality (1-100) |
+/// | `watermark` | string | No | - | Text stamped on every page, e.g. `DRAFT` (JSON bodies take an object, see [`Watermark`](crate::service::Watermark)) |
+/// | `linearize` | bool | No | `false` | Linearize the PDF for fast web view (needs qpdf) |
+/// | `pdf_a` | bool | No | `false` | Convert the PDF toward PDF/A-2b (needs Ghostscript) |
+/// | `split_pages` | bool | No | `false` | Return a ZIP with one PDF per page (needs qpdf) |
+/// | `stream` | bool | No | `false` | Send the PDF in chunks as Chrome hands it over |
+/// | `wait_for_expression` | string | No | `window.isPageDone === true` | JavaScript readiness check |
+/// | `inject_css` | string | No | - | CSS added once the page is ready (e.g. to hide a cookie banner) |
+/// | `inject_js` | string | No | - | JavaScript run once the page is ready, before printing |
+/// | `trace` | bool | No | `false` | Save a DevTools performance trace (needs `BROWSER_TRACE_DIR`) |
+/// | `debug` | bool | No | `false` | Respond with a JSON render report instead of the PDF |
+/// | `priority` | string | No | `normal` | `high`, `normal`, or `low`; order in the checkout queue when the pool is saturated |
 ///
 /// # Response
 ///
@@ -311,13 +367,36 @@ pub type BrowserPoolData = web::Data<SharedBrowserPool>;
 /// - `Content-Disposition: inline; filename="document.pdf"` (or `attachment` if `download=true`)
 /// - `Cache-Control: no-cache`
 ///
+/// ## Streamed (200 OK)
+///
+/// With `stream=true`, the same headers without `Content-Length`; the PDF
+/// is sent in chunks as it is read from Chrome. See
+/// [`PdfFromUrlRequest::stream`] for what cannot be streamed.
+///
+/// ## Fallback (200 OK)
+///
+/// With `fallback_pdf=true`, any failure below instead returns a one-page
+/// "generation failed" PDF with the requested filename, showing a request
+/// ID (the `X-Request-Id` header, or a generated one that is logged) and the
+/// error code and message.
+///
+/// ## Cancellation
+///
+/// Send an `X-Job-Id` header to be able to cancel the render from another
+/// request with [`cancel_job`]. A render that hits the timeout below is
+/// cancelled too, so it stops holding a browser.
+///
 /// ## Errors
 ///
 /// | Status | Code | Description |
 /// |--------|------|-------------|
 /// | 400 | `INVALID_URL` | URL is empty or malformed |
+/// | 400 | `INVALID_REQUEST` | `X-Job-Id` is already used by a running render |
+/// | 409 | `CANCELLED` | Cancelled with `DELETE /jobs/{id}` |
+/// | 429 | `QUOTA_EXCEEDED` | The `X-API-Key` key used up its render quota |
 /// | 502 | `NAVIGATION_FAILED` | Failed to load the URL |
 /// | 503 | `BROWSER_UNAVAILABLE` | No browsers available in pool |
+/// | 507 | `DISK_FULL` | Temp volume nearly full |
 /// | 504 | `TIMEOUT` | Operation timed out |
 ///
 /// # Examples
@@ -348,37 +427,81 @@ pub type BrowserPoolData = web::Data<SharedBrowserPool>;
 ///     .route("/pdf", web::get().to(pdf_from_url))
 /// ```
 pub async fn pdf_from_url(
+    req: HttpRequest,
     pool: web::Data<SharedPool>,
     query: web::Query<PdfFromUrlRequest>,
 ) -> impl Responder {
-    let request = query.into_inner();
+    let mut request = query.into_inner();
     let pool = pool.into_inner();
+    let api_key = api_key(&req);
+    let job_id = header_value(&req, JOB_ID_HEADER);
+    let cancel = CancelHandle::new();
+    request.cancel = Some(cancel.clone());
+    let fallback = request.fallback_pdf().then(|| {
+        FallbackPdf::new(
+            header_value(&req, REQUEST_ID_HEADER).as_deref(),
+            request.filename_or_default(),
+            request.is_download(),
+        )
+    });
 
     log::debug!("PDF from URL request: {}", request.url);
 
+    if request.stream() {
+        return stream_with_timeout(&req, fallback, cancel, move || {
+            service::stream_metered(&pool, api_key.as_deref(), || {
+                service::stream_pdf_from_url(&pool, &request)
+            })
+        })
+        .await;
+    }
+
     // Run blocking PDF generation with timeout
     let result = tokio::time::timeout(
         Duration::from_secs(DEFAULT_TIMEOUT_SECS),
-        web::block(move || service::generate_pdf_from_url(&pool, &request)),
+        web::block({
+            let cancel = cancel.clone();
+            move || {
+                let api_key = api_key.as_deref();
+                service::render_cancellable(&pool, api_key, job_id.as_deref(), &cancel, || {
+                    service::render_metered(&pool, api_key, || {
+                        service::render_cached(&pool, &request, || {
+                            service::render_coalesced(&pool, &request, || {
+                                service::generate_pdf_from_url(&pool, &request)
+                            })
+                        })
+                    })
+                })
+            }
+        }),
     )
     .await;
 
     match result {
         Ok(Ok(Ok(response))) => build_pdf_response(response),
-        Ok(Ok(Err(e))) => build_error_response(e),
+        Ok(Ok(Err(e))) => build_failure_response(e, fallback, &req),
         Ok(Err(blocking_err)) => {
             log::error!("Blocking task error: {}", blocking_err);
-            build_error_response(PdfServiceError::Internal(blocking_err.to_string()))
+            build_failure_response(
+                PdfServiceError::Internal(blocking_err.to_string()),
+                fallback,
+                &req,
+            )
         }
         Err(_timeout) => {
             log::error!(
                 "PDF generation timed out after {} seconds",
                 DEFAULT_TIMEOUT_SECS
             );
-            build_error_response(PdfServiceError::Timeout(format!(
-                "Operation timed out after {} seconds",
-                DEFAULT_TIMEOUT_SECS
-            )))
+            cancel.cancel();
+            build_failure_response(
+                PdfServiceError::Timeout(format!(
+                    "Operation timed out after {} seconds",
+                    DEFAULT_TIMEOUT_SECS
+                )),
+                fallback,
+                &req,
+            )
         }
     }
 }
@@ -416,18 +539,66 @@ pub async fn pdf_from_url(
 /// | `landscape` | bool | No | `false` | Use landscape orientation |
 /// | `download` | bool | No | `false` | Force download vs inline display |
 /// | `print_background` | bool | No | `true` | Include background graphics |
+/// | `paper_format` | string | No | - | Named paper size: `Letter`, `Legal`, `Tabloid`, `Ledger`, `A0`-`A6` |
+/// | `paper_width` | f64 | No | - | Paper width in inches (overrides `paper_format`) |
+/// | `paper_height` | f64 | No | - | Paper height in inches (overrides `paper_format`) |
+/// | `margin_top`, `margin_bottom`, `margin_left`, `margin_right` | f64 | No | - | Margins in inches |
+/// | `scale` | f64 | No | `1.0` | Rendering scale, 0.1-2.0 |
+/// | `page_ranges` | string | No | all pages | Pages to print, e.g. `1-5, 8` |
+/// | `display_header_footer` | bool | No | with a template | Print the header and footer templates |
+/// | `header_template` | string | No | - | HTML printed in the top margin (classes `pageNumber`, `totalPages`, `date`, `title`, `url`) |
+/// | `footer_template` | string | No | - | HTML printed in the bottom margin |
+/// | `emulate_media` | string | No | `print` | CSS media type: `print` or `screen` |
+/// | `color_scheme` | string | No | `light` | `prefers-color-scheme`: `light` or `dark` |
+/// | `generate_tagged_pdf` | bool | No | pool default | Write a tagged (accessible) PDF |
+/// | `generate_document_outline` | bool | No | pool default | Add bookmarks built from the headings |
+/// | `javascript_enabled` | bool | No | `true` | Run the page's scripts (`false` for untrusted content) |
+/// | `block_resources` | string[] | No | - | Resource types to block (`["image", "font"]`) |
+/// | `block_urls` | string[] | No | - | Domains or URL patterns to block (`["tracker.example"]`) |
+/// | `ignore_tls_errors` | bool | No | `false` | Accept invalid TLS certificates (logged as a warning) |
+/// | `offline` | bool | No | `false` | Render without any network access |
+/// | `bypass_cache` | bool | No | `false` | Ignore the browser's HTTP cache |
+/// | `fallback_pdf` | bool | No | `false` | On failure, return a placeholder PDF (HTTP 200) instead of a JSON error |
+/// | `thumbnail` | bool | No | `false` | Respond with an image of the first page instead of the PDF |
+/// | `thumbnail_width` | u32 | No | `256` | Thumbnail width in pixels (16-2048) |
+/// | `thumbnail_format` | string | No | `png` | Thumbnail format: `png`, `jpeg`, or `webp` |
+/// | `thumbnail_quality` | u32 | No | - | JPEG/WebP thumbnail quality (1-100) |
+/// | `watermark` | string | No | - | Text stamped on every page, e.g. `DRAFT` (JSON bodies take an object, see [`Watermark`](crate::service::Watermark)) |
+/// | `linearize` | bool | No | `false` | Linearize the PDF for fast web view (needs qpdf) |
+/// | `pdf_a` | bool | No | `false` | Convert the PDF toward PDF/A-2b (needs Ghostscript) |
+/// | `split_pages` | bool | No | `false` | Return a ZIP with one PDF per page (needs qpdf) |
+/// | `stream` | bool | No | `false` | Send the PDF in chunks as Chrome hands it over |
+/// | `wait_for_expression` | string | No | `window.isPageDone === true` | JavaScript readiness check |
+/// | `inject_css` | string | No | - | CSS added once the page is ready (e.g. to hide a cookie banner) |
+/// | `inject_js` | string | No | - | JavaScript run once the page is ready, before printing |
+/// | `trace` | bool | No | `false` | Save a DevTools performance trace (needs `BROWSER_TRACE_DIR`) |
+/// | `debug` | bool | No | `false` | Respond with a JSON render report instead of the PDF |
+/// | `priority` | string | No | `normal` | `high`, `normal`, or `low`; order in the checkout queue when the pool is saturated |
+///
+/// # Idempotency
+///
+/// Send an `Idempotency-Key` header to make retries safe: a repeat with the
+/// same key (and the same `X-API-Key`) within the pool's
+/// [`idempotency_window`](crate::BrowserPoolConfig::idempotency_window)
+/// returns the first PDF without rendering or billing it again. See
+/// [`service::render_idempotent`].
 ///
 /// # Response
 ///
-/// Same as [`pdf_from_url`].
+/// Same as [`pdf_from_url`], including cancellation with `X-Job-Id`.
 ///
 /// # Errors
 ///
 /// | Status | Code | Description |
 /// |--------|------|-------------|
 /// | 400 | `EMPTY_HTML` | HTML content is empty or whitespace |
+/// | 400 | `INVALID_REQUEST` | `Idempotency-Key` reused with a different body |
+/// | 409 | `IDEMPOTENCY_CONFLICT` | A request with the same `Idempotency-Key` is still rendering |
+/// | 409 | `CANCELLED` | Cancelled with `DELETE /jobs/{id}` |
+/// | 429 | `QUOTA_EXCEEDED` | The `X-API-Key` key used up its render quota |
 /// | 502 | `PDF_GENERATION_FAILED` | Failed to generate PDF |
 /// | 503 | `BROWSER_UNAVAILABLE` | No browsers available |
+/// | 507 | `DISK_FULL` | Temp volume nearly full |
 /// | 504 | `TIMEOUT` | Operation timed out |
 ///
 /// # Example Request
@@ -447,34 +618,485 @@ pub async fn pdf_from_url(
 ///     .route("/pdf/html", web::post().to(pdf_from_html))
 /// ```
 pub async fn pdf_from_html(
+    req: HttpRequest,
     pool: web::Data<SharedPool>,
     body: web::Json<PdfFromHtmlRequest>,
 ) -> impl Responder {
-    let request = body.into_inner();
+    let mut request = body.into_inner();
     let pool = pool.into_inner();
+    let api_key = api_key(&req);
+    let job_id = header_value(&req, JOB_ID_HEADER);
+    let cancel = CancelHandle::new();
+    request.cancel = Some(cancel.clone());
+    let fallback = request.fallback_pdf().then(|| {
+        FallbackPdf::new(
+            header_value(&req, REQUEST_ID_HEADER).as_deref(),
+            request.filename_or_default(),
+            request.is_download(),
+        )
+    });
+    let idempotency_key = idempotency_key(&req);
 
     log::debug!("PDF from HTML request: {} bytes", request.html.len());
 
+    if request.stream() {
+        return stream_with_timeout(&req, fallback, cancel, move || {
+            service::stream_metered(&pool, api_key.as_deref(), || {
+                service::stream_pdf_from_html(&pool, &request)
+            })
+        })
+        .await;
+    }
+
     let result = tokio::time::timeout(
         Duration::from_secs(DEFAULT_TIMEOUT_SECS),
-        web::block(move || service::generate_pdf_from_html(&pool, &request)),
+        web::block({
+            let cancel = cancel.clone();
+            move || {
+                let api_key = api_key.as_deref();
+                service::render_cancellable(&pool, api_key, job_id.as_deref(), &cancel, || {
+                    service::render_idempotent(
+                        &pool,
+                        api_key,
+                        idempotency_key.as_deref(),
+                        &request,
+                        || {
+                            service::render_metered(&pool, api_key, || {
+                                service::render_cached(&pool, &request, || {
+                                    service::render_coalesced(&pool, &request, || {
+                                        service::generate_pdf_from_html(&pool, &request)
+                                    })
+                                })
+                            })
+                        },
+                    )
+                })
+            }
+        }),
     )
     .await;
 
     match result {
         Ok(Ok(Ok(response))) => build_pdf_response(response),
-        Ok(Ok(Err(e))) => build_error_response(e),
+        Ok(Ok(Err(e))) => build_failure_response(e, fallback, &req),
         Ok(Err(blocking_err)) => {
             log::error!("Blocking task error: {}", blocking_err);
-            build_error_response(PdfServiceError::Internal(blocking_err.to_string()))
+            build_failure_response(
+                PdfServiceError::Internal(blocking_err.to_string()),
+                fallback,
+                &req,
+            )
         }
         Err(_timeout) => {
             log::error!("PDF generation timed out");
-            build_error_response(PdfServiceError::Timeout(format!(
-                "Operation timed out after {} seconds",
-                DEFAULT_TIMEOUT_SECS
-            )))
+            cancel.cancel();
+            build_failure_response(
+                PdfServiceError::Timeout(format!(
+                    "Operation timed out after {} seconds",
+                    DEFAULT_TIMEOUT_SECS
+                )),
+                fallback,
+                &req,
+            )
+        }
+    }
+}
+
+/// Generate PDF from HTML uploaded together with its assets.
+///
+/// Like [`pdf_from_html`], but the document's images, stylesheets, and
+/// fonts are uploaded alongside it and relative URLs resolve against them,
+/// so nothing has to be inlined as a data URL. See
+/// [`PdfFromAssetsRequest::from_multipart`] and
+/// [`service::generate_pdf_from_assets`].
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /pdf/html/assets
+/// Content-Type: multipart/form-data
+/// ```
+///
+/// # Request Body
+///
+/// | Part | Description |
+/// |------|-------------|
+/// | File `index.html` | The document (required) |
+/// | Other files | Assets, each with its path relative to `index.html` as filename |
+/// | `options` | Optional JSON with the other fields of [`PdfFromHtmlRequest`] |
+///
+/// ```text
+/// curl -X POST http://localhost:8080/pdf/html/assets \
+///     -F "file=@index.html" \
+///     -F "file=@logo.png;filename=images/logo.png" \
+///     -F 'options={"filename": "report.pdf"}'
+/// ```
+///
+/// Uploads are subject to Actix's payload limit (256 kB by default; raise
+/// it with `web::PayloadConfig`).
+///
+/// # Response
+///
+/// The PDF, as for [`pdf_from_html`]. Usage and `X-Job-Id` work like
+/// [`pdf_from_url`].
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 400 | `INVALID_REQUEST` | Not multipart, no `index.html`, an invalid path, or invalid options |
+/// | 400 | `EMPTY_HTML` | `index.html` is empty |
+/// | 503 | `BROWSER_UNAVAILABLE` | No browser available |
+/// | 504 | `TIMEOUT` | Operation timed out |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/pdf/html/assets", web::post().to(pdf_from_html_assets))
+/// ```
+pub async fn pdf_from_html_assets(
+    req: HttpRequest,
+    pool: web::Data<SharedPool>,
+    body: web::Bytes,
+) -> impl Responder {
+    let pool = pool.into_inner();
+    let api_key = api_key(&req);
+    let job_id = header_value(&req, JOB_ID_HEADER);
+    let content_type = header_value(&req, header::CONTENT_TYPE.as_str()).unwrap_or_default();
+    let cancel = CancelHandle::new();
+
+    log::debug!("PDF from HTML with assets request: {} bytes", body.len());
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        web::block({
+            let cancel = cancel.clone();
+            move || {
+                let mut request = PdfFromAssetsRequest::from_multipart(&content_type, body)?;
+                request.document.cancel = Some(cancel.clone());
+                let api_key = api_key.as_deref();
+                service::render_cancellable(&pool, api_key, job_id.as_deref(), &cancel, || {
+                    service::render_metered(&pool, api_key, || {
+                        service::generate_pdf_from_assets(&pool, &request)
+                    })
+                })
+            }
+        }),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(Ok(response))) => build_pdf_response(response),
+        Ok(Ok(Err(e))) => build_error_response(e, &req),
+        Ok(Err(blocking_err)) => {
+            log::error!("Blocking task error: {}", blocking_err);
+            build_error_response(PdfServiceError::Internal(blocking_err.to_string()), &req)
+        }
+        Err(_timeout) => {
+            log::error!("PDF generation timed out");
+            cancel.cancel();
+            build_error_response(
+                PdfServiceError::Timeout(format!(
+                    "Operation timed out after {} seconds",
+                    DEFAULT_TIMEOUT_SECS
+                )),
+                &req,
+            )
+        }
+    }
+}
+
+/// Render several documents and merge them into one PDF.
+///
+/// Each document is rendered like a request to [`pdf_from_url`] or
+/// [`pdf_from_html`], in parallel across the pool, and their pages are
+/// joined in order with qpdf. See [`service::merge_pdfs`].
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /pdf/merge
+/// Content-Type: application/json
+/// ```
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "documents": [
+///         {"html": "<h1>Annual Report</h1>"},
+///         {"url": "https://example.com/report", "landscape": true}
+///     ],
+///     "filename": "annual-report.pdf"
+/// }
+/// ```
+///
+/// # Response
+///
+/// The merged PDF. The merge counts as one render toward the `X-API-Key`
+/// quota, and can be cancelled with `X-Job-Id` like [`pdf_from_url`].
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 400 | `INVALID_REQUEST` | No documents, or more than 32 |
+/// | 4xx/5xx | Any | The first failing document's error |
+/// | 502 | `PDF_GENERATION_FAILED` | qpdf is missing or could not merge the PDFs |
+/// | 504 | `TIMEOUT` | Operation timed out |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/pdf/merge", web::post().to(pdf_merge))
+/// ```
+pub async fn pdf_merge(
+    req: HttpRequest,
+    pool: web::Data<SharedPool>,
+    body: web::Json<PdfMergeRequest>,
+) -> impl Responder {
+    let mut request = body.into_inner();
+    let pool = pool.into_inner();
+    let api_key = api_key(&req);
+    let job_id = header_value(&req, JOB_ID_HEADER);
+    let cancel = CancelHandle::new();
+    request.set_cancel(&cancel);
+
+    log::debug!("PDF merge request: {} documents", request.documents.len());
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        web::block({
+            let cancel = cancel.clone();
+            move || {
+                let api_key = api_key.as_deref();
+                service::render_cancellable(&pool, api_key, job_id.as_deref(), &cancel, || {
+                    service::render_metered(&pool, api_key, || service::merge_pdfs(&pool, &request))
+                })
+            }
+        }),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(Ok(response))) => build_pdf_response(response),
+        Ok(Ok(Err(e))) => build_error_response(e, &req),
+        Ok(Err(blocking_err)) => {
+            log::error!("Blocking task error: {}", blocking_err);
+            build_error_response(PdfServiceError::Internal(blocking_err.to_string()), &req)
+        }
+        Err(_timeout) => {
+            log::error!("PDF merge timed out");
+            cancel.cancel();
+            build_error_response(
+                PdfServiceError::Timeout(format!(
+                    "Operation timed out after {} seconds",
+                    DEFAULT_TIMEOUT_SECS
+                )),
+                &req,
+            )
+        }
+    }
+}
+
+/// Start a render in the background.
+///
+/// Takes the same JSON body as [`pdf_from_html`], or the fields of
+/// [`pdf_from_url`]'s query as JSON (told apart by `url` or `html`), and
+/// returns at once. Poll [`job_status`] and download from [`job_result`],
+/// or add a `callback_url` to have the result POSTed there. An `X-Job-Id`
+/// header picks the job ID, otherwise one is generated. See
+/// [`service::submit_job`].
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /jobs
+/// Content-Type: application/json
+/// ```
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "url": "https://example.com/report",
+///     "filename": "report.pdf",
+///     "callback_url": "https://app.example.com/hooks/pdf"
+/// }
+/// ```
+///
+/// # Response (202 Accepted)
+///
+/// ```json
+/// {
+///     "id": "3f9c2a1b-...",
+///     "status": "running"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 400 | `INVALID_REQUEST` | Jobs disabled, bad job ID, or ID already running |
+/// | 400 | `INVALID_URL` | `callback_url` is not an allowed http(s) URL |
+/// | 503 | `JOB_QUEUE_FULL` | Too many jobs pending |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/jobs", web::post().to(submit_job))
+/// ```
+pub async fn submit_job(
+    req: HttpRequest,
+    pool: web::Data<SharedPool>,
+    body: web::Json<JobRequest>,
+) -> impl Responder {
+    let job_id = header_value(&req, JOB_ID_HEADER);
+    match service::submit_job(
+        &pool,
+        api_key(&req).as_deref(),
+        job_id.as_deref(),
+        body.into_inner(),
+    ) {
+        Ok(job) => HttpResponse::Accepted().json(job),
+        Err(e) => build_error_response(e, &req),
+    }
+}
+
+/// Get the status of a job.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /jobs/{id}
+/// ```
+///
+/// # Response (200 OK)
+///
+/// ```json
+/// {
+///     "id": "report-42",
+///     "status": "succeeded",
+///     "expires_in_seconds": 3512
+/// }
+/// ```
+///
+/// `status` is `running`, `succeeded`, `failed`, or `cancelled`; the last
+/// two include the job's `error`.
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 404 | `JOB_NOT_FOUND` | No such job for the API key, or its result expired |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/jobs/{id}", web::get().to(job_status))
+/// ```
+pub async fn job_status(
+    req: HttpRequest,
+    pool: web::Data<SharedPool>,
+    id: web::Path<String>,
+) -> impl Responder {
+    match service::job_status(&pool, api_key(&req).as_deref(), &id) {
+        Ok(job) => HttpResponse::Ok().json(job),
+        Err(e) => build_error_response(e, &req),
+    }
+}
+
+/// Download the result of a finished job.
+///
+/// Responds like [`pdf_from_url`]: the PDF on success, or the job's JSON
+/// error if its render failed. Results can be downloaded repeatedly until
+/// they expire.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /jobs/{id}/result
+/// ```
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 404 | `JOB_NOT_FOUND` | No such job for the API key, or its result expired |
+/// | 409 | `JOB_PENDING` | The job is still running |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/jobs/{id}/result", web::get().to(job_result))
+/// ```
+pub async fn job_result(
+    req: HttpRequest,
+    pool: web::Data<SharedPool>,
+    id: web::Path<String>,
+) -> impl Responder {
+    match service::job_result(&pool, api_key(&req).as_deref(), &id) {
+        Ok(response) => {
+            build_ranged_pdf_response(response, header_value(&req, header::RANGE.as_str()))
         }
+        Err(e) => build_error_response(e, &req),
+    }
+}
+
+/// Cancel a running job, or delete a finished job's result.
+///
+/// Stops the render sent with an `X-Job-Id: {id}` header, or submitted to
+/// [`submit_job`], under the same `X-API-Key`: it leaves the checkout queue
+/// or has its tab closed, and fails with `CANCELLED`. A finished job's kept
+/// result is deleted instead. See [`service::cancel_job`].
+///
+/// # Endpoint
+///
+/// ```text
+/// DELETE /jobs/{id}
+/// ```
+///
+/// # Response (202 Accepted)
+///
+/// ```json
+/// {
+///     "id": "report-42",
+///     "status": "cancelled"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 404 | `JOB_NOT_FOUND` | No job with this ID for the API key |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/jobs/{id}", web::delete().to(cancel_job))
+/// ```
+pub async fn cancel_job(
+    req: HttpRequest,
+    pool: web::Data<SharedPool>,
+    id: web::Path<String>,
+) -> impl Responder {
+    match service::cancel_job(&pool, api_key(&req).as_deref(), &id) {
+        Ok(job) => HttpResponse::Accepted().json(job),
+        Err(e) => build_error_response(e, &req),
     }
 }
 
@@ -525,10 +1147,165 @@ pub async fn pdf_from_html(
 ///     .app_data(web::Data::new(pool.clone()))
 ///     .route("/pool/stats", web::get().to(pool_stats))
 /// ```
-pub async fn pool_stats(pool: web::Data<SharedPool>) -> impl Responder {
+pub async fn pool_stats(req: HttpRequest, pool: web::Data<SharedPool>) -> impl Responder {
     match service::get_pool_stats(&pool) {
         Ok(stats) => HttpResponse::Ok().json(stats),
-        Err(e) => build_error_response(e),
+        Err(e) => build_error_response(e, &req),
+    }
+}
+
+/// Get a detailed diagnostic snapshot of the browser pool.
+///
+/// Reports each browser's age, uses, memory, open tabs, and last health
+/// check, plus waiting requests, replacement tasks in flight, and
+/// keep-alive timing. Attach it to support requests about slow or failing
+/// renders.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /pool/debug
+/// ```
+///
+/// # Response (200 OK)
+///
+/// A JSON [`PoolDebugResponse`](crate::service::PoolDebugResponse):
+///
+/// ```json
+/// {
+///     "available": 1,
+///     "active": 2,
+///     "max_pool_size": 5,
+///     "browsers": [
+///         {
+///             "id": 3,
+///             "age_seconds": 812.4,
+///             "uses": 57,
+///             "checked_out": true,
+///             "open_tabs": 2,
+///             "memory_bytes": 183500800,
+///             "last_health_check": { "seconds_ago": 4.1, "healthy": true, "error": null }
+///         }
+///     ],
+///     "queued_waiters": 0,
+///     "replacement_tasks": 1,
+///     "keep_alive": {
+///         "interval_seconds": 15.0,
+///         "cycles": 54,
+///         "last_cycle_seconds_ago": 4.2,
+///         "last_cycle_duration_seconds": 0.31,
+///         "next_cycle_in_seconds": 10.8
+///     },
+///     "shutting_down": false
+/// }
+/// ```
+///
+/// The response includes internal details (error messages, memory), so
+/// expose it only to operators.
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/pool/debug", web::get().to(pool_debug))
+/// ```
+pub async fn pool_debug(req: HttpRequest, pool: web::Data<SharedPool>) -> impl Responder {
+    match service::get_pool_debug(&pool) {
+        Ok(snapshot) => HttpResponse::Ok().json(snapshot),
+        Err(e) => build_error_response(e, &req),
+    }
+}
+
+/// Get per-browser statistics and lifetime counters of the pool.
+///
+/// Reports each browser's age, uses, time since its last ping, consecutive
+/// ping failures, memory, and idle/busy state, plus how many browsers have
+/// been created, retired, and removed as failed since startup. Suited to
+/// dashboards; scrape it every few seconds at most.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /pool/stats/detailed
+/// ```
+///
+/// # Response (200 OK)
+///
+/// A JSON [`PoolDetailedStatsResponse`](crate::service::PoolDetailedStatsResponse):
+///
+/// ```json
+/// {
+///     "available": 1,
+///     "active": 2,
+///     "total": 3,
+///     "total_created": 14,
+///     "total_retired": 11,
+///     "total_failed": 1,
+///     "browsers": [
+///         {
+///             "id": 12,
+///             "age_seconds": 812.4,
+///             "uses": 57,
+///             "last_ping_seconds_ago": 4.1,
+///             "ping_failures": 0,
+///             "memory_bytes": 183500800,
+///             "state": "busy"
+///         }
+///     ]
+/// }
+/// ```
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/pool/stats/detailed", web::get().to(pool_stats_detailed))
+/// ```
+pub async fn pool_stats_detailed(req: HttpRequest, pool: web::Data<SharedPool>) -> impl Responder {
+    match service::get_pool_detailed_stats(&pool) {
+        Ok(stats) => HttpResponse::Ok().json(stats),
+        Err(e) => build_error_response(e, &req),
+    }
+}
+
+/// Get the caller's API key usage.
+///
+/// Reports renders, PDF bytes, and browser time for the key in the
+/// `X-API-Key` header (or the shared anonymous account without one), plus
+/// the configured quotas. Other keys' usage is never returned.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /usage
+/// X-API-Key: team-a
+/// ```
+///
+/// # Response (200 OK)
+///
+/// ```json
+/// {
+///     "today": { "renders": 12, "bytes": 580112, "browser_seconds": 18.4 },
+///     "this_month": { "renders": 240, "bytes": 11902234, "browser_seconds": 351.0 },
+///     "total": { "renders": 240, "bytes": 11902234, "browser_seconds": 351.0 },
+///     "daily_quota": 500,
+///     "monthly_quota": null
+/// }
+/// ```
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// App::new()
+///     .app_data(web::Data::new(pool.clone()))
+///     .route("/usage", web::get().to(usage_stats))
+/// ```
+pub async fn usage_stats(req: HttpRequest, pool: web::Data<SharedPool>) -> impl Responder {
+    match service::get_usage(&pool, api_key(&req).as_deref()) {
+        Ok(usage) => HttpResponse::Ok().json(usage),
+        Err(e) => build_error_response(e, &req),
     }
 }
 
@@ -613,10 +1390,13 @@ pub async fn health_check() -> impl Responder {
 ///
 /// # Readiness Criteria
 ///
-/// The service is "ready" if either:
+/// By default the service is "ready" if either:
 /// - There are idle browsers available (`available > 0`), OR
 /// - There is capacity to create new browsers (`active < max_pool_size`)
 ///
+/// The `ready_*` fields of [`BrowserPoolConfig`](crate::BrowserPoolConfig)
+/// tighten this; see [`service::is_pool_ready`].
+///
 /// # Use Cases
 ///
 /// - Kubernetes readiness probe
@@ -641,7 +1421,7 @@ pub async fn health_check() -> impl Responder {
 ///     .app_data(web::Data::new(pool.clone()))
 ///     .route("/ready", web::get().to(readiness_check))
 /// ```
-pub async fn readiness_check(pool: web::Data<SharedPool>) -> impl Responder {
+pub async fn readiness_check(req: HttpRequest, pool: web::Data<SharedPool>) -> impl Responder {
     match service::is_pool_ready(&pool) {
         Ok(true) => HttpResponse::Ok().json(serde_json::json!({
             "status": "ready"
@@ -650,7 +1430,7 @@ pub async fn readiness_check(pool: web::Data<SharedPool>) -> impl Responder {
             "status": "not_ready",
             "reason": "no_available_capacity"
         })),
-        Err(e) => HttpResponse::ServiceUnavailable().json(ErrorResponse::from(e)),
+        Err(e) => build_localized_response(HttpResponse::ServiceUnavailable(), e.into(), &req),
     }
 }
 
@@ -669,7 +1449,16 @@ pub async fn readiness_check(pool: web::Data<SharedPool>) -> impl Responder {
 /// |--------|------|---------|-------------|
 /// | GET | `/pdf` | [`pdf_from_url`] | Convert URL to PDF |
 /// | POST | `/pdf/html` | [`pdf_from_html`] | Convert HTML to PDF |
+/// | POST | `/pdf/html/assets` | [`pdf_from_html_assets`] | Convert HTML uploaded with its assets to PDF |
+/// | POST | `/pdf/merge` | [`pdf_merge`] | Render several documents into one PDF |
+/// | POST | `/jobs` | [`submit_job`] | Start a background render |
+/// | GET | `/jobs/{id}` | [`job_status`] | Status of a job |
+/// | DELETE | `/jobs/{id}` | [`cancel_job`] | Cancel a job or delete its result |
+/// | GET | `/jobs/{id}/result` | [`job_result`] | Download a finished job's PDF |
 /// | GET | `/pool/stats` | [`pool_stats`] | Pool statistics |
+/// | GET | `/pool/debug` | [`pool_debug`] | Pool diagnostics snapshot |
+/// | GET | `/pool/stats/detailed` | [`pool_stats_detailed`] | Per-browser statistics and lifetime counters |
+/// | GET | `/usage` | [`usage_stats`] | Usage of the caller's API key |
 /// | GET | `/health` | [`health_check`] | Health check |
 /// | GET | `/ready` | [`readiness_check`] | Readiness check |
 ///
@@ -722,7 +1511,16 @@ pub async fn readiness_check(pool: web::Data<SharedPool>) -> impl Responder {
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.route("/pdf", web::get().to(pdf_from_url))
         .route("/pdf/html", web::post().to(pdf_from_html))
+        .route("/pdf/html/assets", web::post().to(pdf_from_html_assets))
+        .route("/pdf/merge", web::post().to(pdf_merge))
+        .route("/jobs", web::post().to(submit_job))
+        .route("/jobs/{id}", web::get().to(job_status))
+        .route("/jobs/{id}", web::delete().to(cancel_job))
+        .route("/jobs/{id}/result", web::get().to(job_result))
         .route("/pool/stats", web::get().to(pool_stats))
+        .route("/pool/debug", web::get().to(pool_debug))
+        .route("/pool/stats/detailed", web::get().to(pool_stats_detailed))
+        .route("/usage", web::get().to(usage_stats))
         .route("/health", web::get().to(health_check))
         .route("/ready", web::get().to(readiness_check));
 }
@@ -731,35 +1529,187 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
 // Response Builders (Internal)
 // ============================================================================
 
+/// Read the API key from the `X-API-Key` header, ignoring blank values.
+fn api_key(req: &HttpRequest) -> Option<String> {
+    header_value(req, API_KEY_HEADER)
+}
+
+/// Read the `Idempotency-Key` header, ignoring blank values.
+fn idempotency_key(req: &HttpRequest) -> Option<String> {
+    header_value(req, IDEMPOTENCY_KEY_HEADER)
+}
+
+/// Read a header as trimmed text, ignoring blank and non-UTF-8 values.
+fn header_value(req: &HttpRequest, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(String::from)
+}
+
 /// Build HTTP response for successful PDF generation.
-fn build_pdf_response(response: crate::service::PdfResponse) -> HttpResponse {
+fn build_pdf_response(response: PdfResponse) -> HttpResponse {
     log::info!(
         "PDF generated successfully: {} bytes, filename={}",
         response.size(),
         response.filename
     );
 
+    build_body_response(HttpResponse::Ok(), response.body(), &response)
+}
+
+/// Open a streamed render on the blocking pool, giving up after
+/// [`DEFAULT_TIMEOUT_SECS`], and send its PDF as it is read from Chrome.
+///
+/// The timeout covers rendering only; failures are answered like the
+/// buffered endpoints do.
+async fn stream_with_timeout<F>(
+    req: &HttpRequest,
+    fallback: Option<FallbackPdf>,
+    cancel: CancelHandle,
+    open: F,
+) -> HttpResponse
+where
+    F: FnOnce() -> Result<PdfStream, PdfServiceError> + Send + 'static,
+{
+    let result =
+        tokio::time::timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS), web::block(open)).await;
+
+    let error = match result {
+        Ok(Ok(Ok(stream))) => return build_stream_response(stream),
+        Ok(Ok(Err(e))) => e,
+        Ok(Err(blocking_err)) => {
+            log::error!("Blocking task error: {}", blocking_err);
+            PdfServiceError::Internal(blocking_err.to_string())
+        }
+        Err(_timeout) => {
+            log::error!(
+                "PDF generation timed out after {} seconds",
+                DEFAULT_TIMEOUT_SECS
+            );
+            cancel.cancel();
+            PdfServiceError::Timeout(format!(
+                "Operation timed out after {} seconds",
+                DEFAULT_TIMEOUT_SECS
+            ))
+        }
+    };
+    build_failure_response(error, fallback, req)
+}
+
+/// Build the chunked HTTP response for a streamed PDF.
+fn build_stream_response(stream: PdfStream) -> HttpResponse {
+    log::info!("Streaming PDF, filename={}", stream.filename());
+
     HttpResponse::Ok()
         .content_type("application/pdf")
         .insert_header((header::CACHE_CONTROL, "no-cache"))
-        .insert_header((header::CONTENT_DISPOSITION, response.content_disposition()))
-        .body(response.data)
+        .insert_header((header::CONTENT_DISPOSITION, stream.content_disposition()))
+        .streaming(stream.into_chunks())
+}
+
+/// Build the HTTP response sending `body`, with `response`'s trace ID and
+/// page count headers; see [`PdfResponse::body`].
+fn build_body_response(
+    mut builder: actix_web::HttpResponseBuilder,
+    body: ResponseBody,
+    response: &PdfResponse,
+) -> HttpResponse {
+    builder
+        .content_type(body.content_type)
+        .insert_header((header::CACHE_CONTROL, "no-cache"));
+    if let Some(disposition) = body.content_disposition {
+        builder.insert_header((header::CONTENT_DISPOSITION, disposition));
+    }
+    if let Some(trace_id) = &response.trace_id {
+        builder.insert_header((TRACE_ID_HEADER, trace_id.clone()));
+    }
+    for header in response.page_info().iter().flat_map(PageInfo::headers) {
+        builder.insert_header(header);
+    }
+    builder.body(body.data)
+}
+
+/// Build the response for a kept result, honouring a `Range` header.
+fn build_ranged_pdf_response(response: PdfResponse, range: Option<String>) -> HttpResponse {
+    let body = response.body();
+    let range = ByteRange::from_header(range.as_deref(), body.data.len());
+
+    let mut http_response = match range {
+        ByteRange::Full => build_body_response(HttpResponse::Ok(), body, &response),
+        ByteRange::Partial { .. } => {
+            let data = range.slice(&body.data);
+            build_body_response(
+                HttpResponse::PartialContent(),
+                ResponseBody { data, ..body },
+                &response,
+            )
+        }
+        ByteRange::Unsatisfiable { .. } => HttpResponse::RangeNotSatisfiable().finish(),
+    };
+
+    let headers = http_response.headers_mut();
+    headers.insert(
+        header::ACCEPT_RANGES,
+        header::HeaderValue::from_static("bytes"),
+    );
+    if let Some(value) = range
+        .content_range()
+        .and_then(|r| header::HeaderValue::from_str(&r).ok())
+    {
+        headers.insert(header::CONTENT_RANGE, value);
+    }
+    http_response
 }
 
-/// Build HTTP response for errors.
-fn build_error_response(error: PdfServiceError) -> HttpResponse {
+/// Build the response for a failed render: the fallback PDF when one was
+/// requested, otherwise the JSON error.
+fn build_failure_response(
+    error: PdfServiceError,
+    fallback: Option<FallbackPdf>,
+    req: &HttpRequest,
+) -> HttpResponse {
+    match fallback {
+        Some(fallback) => build_pdf_response(fallback.render(&error)),
+        None => build_error_response(error, req),
+    }
+}
+
+/// Build HTTP response for errors, in the language `req` accepts.
+fn build_error_response(error: PdfServiceError, req: &HttpRequest) -> HttpResponse {
     let status_code = error.status_code();
     let body = ErrorResponse::from(&error);
 
     log::warn!("PDF generation error: {} (HTTP {})", error, status_code);
 
-    match status_code {
-        400 => HttpResponse::BadRequest().json(body),
-        502 => HttpResponse::BadGateway().json(body),
-        503 => HttpResponse::ServiceUnavailable().json(body),
-        504 => HttpResponse::GatewayTimeout().json(body),
-        _ => HttpResponse::InternalServerError().json(body),
-    }
+    let builder = match status_code {
+        400 => HttpResponse::BadRequest(),
+        404 => HttpResponse::NotFound(),
+        409 => HttpResponse::Conflict(),
+        429 => HttpResponse::TooManyRequests(),
+        502 => HttpResponse::BadGateway(),
+        503 => HttpResponse::ServiceUnavailable(),
+        504 => HttpResponse::GatewayTimeout(),
+        507 => HttpResponse::InsufficientStorage(),
+        _ => HttpResponse::InternalServerError(),
+    };
+    build_localized_response(builder, body, req)
+}
+
+/// Translate an error body for the request's `Accept-Language` header and
+/// name the language in `Content-Language`.
+fn build_localized_response(
+    mut builder: HttpResponseBuilder,
+    body: ErrorResponse,
+    req: &HttpRequest,
+) -> HttpResponse {
+    let locale =
+        service::negotiate_locale(header_value(req, header::ACCEPT_LANGUAGE.as_str()).as_deref());
+    builder
+        .insert_header((header::CONTENT_LANGUAGE, locale))
+        .json(body.in_locale(locale))
 }
 
 // ============================================================================
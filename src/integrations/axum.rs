@@ -1,6 +1,46 @@
 //! Axum framework integration.
 //!
-//! This module provides helpers for using `BrowserPool` with Axum.
+//! This module provides helpers and pre-built handlers for using `BrowserPool`
+//! with Axum.
+//!
+//! # Quick Start: Pre-built Routes
+//!
+//! Use [`router`] to get all PDF endpoints in one call:
+//!
+//! ```rust,ignore
+//! use html2pdf_api::prelude::*;
+//! use html2pdf_api::integrations::axum::router;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let pool = init_browser_pool().await
+//!         .expect("Failed to initialize browser pool");
+//!
+//!     let app = router().with_state(pool);
+//!
+//!     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await.unwrap();
+//!     axum::serve(listener, app).await.unwrap();
+//! }
+//! ```
+//!
+//! This gives you the following endpoints:
+//!
+//! | Method | Path | Handler | Description |
+//! |--------|------|---------|-------------|
+//! | GET | `/pdf?url=...` | [`pdf_from_url`] | Convert URL to PDF |
+//! | POST | `/pdf/html` | [`pdf_from_html`] | Convert HTML to PDF |
+//! | POST | `/pdf/html/assets` | [`pdf_from_html_assets`] | Convert HTML uploaded with its assets to PDF |
+//! | POST | `/pdf/merge` | [`pdf_merge`] | Render several documents into one PDF |
+//! | POST | `/jobs` | [`submit_job`] | Start a background render |
+//! | GET | `/jobs/{id}` | [`job_status`] | Status of a job |
+//! | GET | `/jobs/{id}/result` | [`job_result`] | Download a finished job's PDF |
+//! | DELETE | `/jobs/{id}` | [`cancel_job`] | Cancel a job or delete its result |
+//! | GET | `/pool/stats` | [`pool_stats`] | Pool statistics |
+//! | GET | `/pool/debug` | [`pool_debug`] | Pool diagnostics snapshot |
+//! | GET | `/pool/stats/detailed` | [`pool_stats_detailed`] | Per-browser statistics and lifetime counters |
+//! | GET | `/usage` | [`usage_stats`] | Usage of the caller's API key |
+//! | GET | `/health` | [`health_check`] | Health check |
+//! | GET | `/ready` | [`readiness_check`] | Readiness check |
 //!
 //! # Setup
 //!
@@ -132,65 +172,35 @@
 //!
 //! # Graceful Shutdown
 //!
-//! For proper cleanup with graceful shutdown:
+//! [`with_graceful_shutdown_pool`] drains and shuts down the pool when the
+//! server stops: on Ctrl+C or SIGTERM ([`shutdown_signal`]), new renders
+//! are refused (and `/ready` fails) while in-flight ones finish, then the
+//! keep-alive thread and replacement tasks are stopped:
 //!
 //! ```rust,ignore
-//! use axum::Router;
 //! use html2pdf_api::prelude::*;
+//! use html2pdf_api::integrations::axum::{router, shutdown_signal, with_graceful_shutdown_pool};
 //! use std::sync::Arc;
-//! use tokio::signal;
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let pool = BrowserPool::builder()
-//!         .factory(Box::new(ChromeBrowserFactory::with_defaults()))
-//!         .build()
-//!         .expect("Failed to create pool");
-//!
-//!     pool.warmup().await.expect("Failed to warmup");
-//!
-//!     let shared_pool = Arc::new(std::sync::Mutex::new(pool));
-//!     let shutdown_pool = Arc::clone(&shared_pool);
+//!     let pool = init_browser_pool().await
+//!         .expect("Failed to initialize browser pool");
 //!
-//!     let app = Router::new()
-//!         .with_state(shared_pool);
+//!     let app = router().with_state(Arc::clone(&pool));
 //!
 //!     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await.unwrap();
-//!     
+//!
 //!     axum::serve(listener, app)
-//!         .with_graceful_shutdown(shutdown_signal(shutdown_pool))
+//!         .with_graceful_shutdown(with_graceful_shutdown_pool(pool, shutdown_signal()))
 //!         .await
 //!         .unwrap();
 //! }
-//!
-//! async fn shutdown_signal(pool: SharedBrowserPool) {
-//!     let ctrl_c = async {
-//!         signal::ctrl_c().await.expect("Failed to listen for ctrl+c");
-//!     };
-//!
-//!     #[cfg(unix)]
-//!     let terminate = async {
-//!         signal::unix::signal(signal::unix::SignalKind::terminate())
-//!             .expect("Failed to install signal handler")
-//!             .recv()
-//!             .await;
-//!     };
-//!
-//!     #[cfg(not(unix))]
-//!     let terminate = std::future::pending::<()>();
-//!
-//!     tokio::select! {
-//!         _ = ctrl_c => {},
-//!         _ = terminate => {},
-//!     }
-//!
-//!     println!("Shutting down...");
-//!     if let Ok(mut pool) = pool.lock() {
-//!         pool.shutdown_async().await;
-//!     }
-//! }
 //! ```
 //!
+//! Any future can stand in for [`shutdown_signal`], for example one
+//! resolving on an admin endpoint.
+//!
 //! # Custom Extractor
 //!
 //! For cleaner handler signatures, create a custom extractor:
@@ -226,10 +236,35 @@
 //! }
 //! ```
 
-use axum::extract::State;
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::SharedBrowserPool;
+use crate::cancel::CancelHandle;
 use crate::pool::BrowserPool;
+use crate::service::{
+    self, API_KEY_HEADER, ByteRange, Bytes, DEFAULT_TIMEOUT_SECS, ErrorResponse, FallbackPdf,
+    HealthResponse, IDEMPOTENCY_KEY_HEADER, JOB_ID_HEADER, JobRequest, PageInfo,
+    PdfFromAssetsRequest, PdfFromHtmlRequest, PdfFromUrlRequest, PdfMergeRequest, PdfResponse,
+    PdfServiceError, PdfStream, REQUEST_ID_HEADER, ResponseBody, TRACE_ID_HEADER, negotiate_locale,
+};
+
+// ============================================================================
+// Type Aliases
+// ============================================================================
+
+/// Type alias for shared browser pool.
+///
+/// This is the state type used by the pre-built handlers and [`router`].
+/// It's the same `Arc<Mutex<BrowserPool>>` used by the service functions.
+pub type SharedPool = Arc<Mutex<BrowserPool>>;
 
 /// Type alias for Axum `State` extractor with the shared pool.
 ///
@@ -246,6 +281,831 @@ use crate::pool::BrowserPool;
 /// ```
 pub type BrowserPoolState = State<SharedBrowserPool>;
 
+// ============================================================================
+// Pre-built Handlers
+// ============================================================================
+
+/// Generate PDF from a URL.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /pdf?url=https://example.com&filename=output.pdf
+/// ```
+///
+/// Accepts the same query parameters as [`PdfFromUrlRequest`]. Responds with
+/// the PDF on success, or a JSON [`ErrorResponse`] with the status code from
+/// [`PdfServiceError::status_code`]. The render is accounted to the
+/// `X-API-Key` header's key and subject to its quota (see [`usage_stats`]).
+/// With `fallback_pdf=true`, failures return a placeholder [`FallbackPdf`]
+/// instead of the JSON error; with `thumbnail=true`, the response is an
+/// image of the first page instead of the PDF (PNG, or as chosen by
+/// `thumbnail_format` and `thumbnail_quality`). With `stream=true`, the
+/// PDF is sent in chunks as it is read from Chrome (see
+/// [`PdfFromUrlRequest::stream`]). An `X-Job-Id` header lets
+/// [`cancel_job`] stop the render; it is also cancelled if it times out.
+///
+/// # Usage in Router
+///
+/// ```rust,ignore
+/// Router::new()
+///     .route("/pdf", get(pdf_from_url))
+///     .with_state(pool)
+/// ```
+pub async fn pdf_from_url(
+    State(pool): State<SharedPool>,
+    headers: HeaderMap,
+    Query(mut request): Query<PdfFromUrlRequest>,
+) -> Response {
+    log::debug!("PDF from URL request: {}", request.url);
+
+    let api_key = api_key(&headers);
+    let job_id = header_value(&headers, JOB_ID_HEADER);
+    let cancel = CancelHandle::new();
+    request.cancel = Some(cancel.clone());
+    let fallback = request.fallback_pdf().then(|| {
+        FallbackPdf::new(
+            header_value(&headers, REQUEST_ID_HEADER).as_deref(),
+            request.filename_or_default(),
+            request.is_download(),
+        )
+    });
+    if request.stream() {
+        return run_stream_with_timeout(fallback, cancel, &headers, move || {
+            service::stream_metered(&pool, api_key.as_deref(), || {
+                service::stream_pdf_from_url(&pool, &request)
+            })
+        })
+        .await;
+    }
+    run_with_timeout(fallback, cancel.clone(), &headers, move || {
+        let api_key = api_key.as_deref();
+        service::render_cancellable(&pool, api_key, job_id.as_deref(), &cancel, || {
+            service::render_metered(&pool, api_key, || {
+                service::render_cached(&pool, &request, || {
+                    service::render_coalesced(&pool, &request, || {
+                        service::generate_pdf_from_url(&pool, &request)
+                    })
+                })
+            })
+        })
+    })
+    .await
+}
+
+/// Generate PDF from HTML content.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /pdf/html
+/// Content-Type: application/json
+/// ```
+///
+/// The body is a JSON [`PdfFromHtmlRequest`]. Usage, `fallback_pdf`,
+/// `thumbnail`, and `stream` work like [`pdf_from_url`]. An
+/// `Idempotency-Key` header makes retries safe; see
+/// [`service::render_idempotent`]. `X-Job-Id` works like [`pdf_from_url`].
+///
+/// # Usage in Router
+///
+/// ```rust,ignore
+/// Router::new()
+///     .route("/pdf/html", post(pdf_from_html))
+///     .with_state(pool)
+/// ```
+pub async fn pdf_from_html(
+    State(pool): State<SharedPool>,
+    headers: HeaderMap,
+    Json(mut request): Json<PdfFromHtmlRequest>,
+) -> Response {
+    log::debug!("PDF from HTML request: {} bytes", request.html.len());
+
+    let api_key = api_key(&headers);
+    let idempotency_key = idempotency_key(&headers);
+    let job_id = header_value(&headers, JOB_ID_HEADER);
+    let cancel = CancelHandle::new();
+    request.cancel = Some(cancel.clone());
+    let fallback = request.fallback_pdf().then(|| {
+        FallbackPdf::new(
+            header_value(&headers, REQUEST_ID_HEADER).as_deref(),
+            request.filename_or_default(),
+            request.is_download(),
+        )
+    });
+    if request.stream() {
+        return run_stream_with_timeout(fallback, cancel, &headers, move || {
+            service::stream_metered(&pool, api_key.as_deref(), || {
+                service::stream_pdf_from_html(&pool, &request)
+            })
+        })
+        .await;
+    }
+    run_with_timeout(fallback, cancel.clone(), &headers, move || {
+        let api_key = api_key.as_deref();
+        service::render_cancellable(&pool, api_key, job_id.as_deref(), &cancel, || {
+            service::render_idempotent(&pool, api_key, idempotency_key.as_deref(), &request, || {
+                service::render_metered(&pool, api_key, || {
+                    service::render_cached(&pool, &request, || {
+                        service::render_coalesced(&pool, &request, || {
+                            service::generate_pdf_from_html(&pool, &request)
+                        })
+                    })
+                })
+            })
+        })
+    })
+    .await
+}
+
+/// Generate PDF from HTML uploaded together with its assets.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /pdf/html/assets
+/// Content-Type: multipart/form-data
+/// ```
+///
+/// The body holds `index.html` and the images, stylesheets, and fonts it
+/// refers to as file parts, each named by its path (`css/site.css`), plus
+/// an optional `options` part with the other fields of a
+/// [`PdfFromHtmlRequest`] as JSON. Relative URLs in the document resolve
+/// against the uploaded files; see [`PdfFromAssetsRequest::from_multipart`]
+/// and [`service::generate_pdf_from_assets`]. Usage and `X-Job-Id` work
+/// like [`pdf_from_url`]. Uploads are subject to axum's body limit (2 MB by
+/// default; raise it with `DefaultBodyLimit`).
+///
+/// # Usage in Router
+///
+/// ```rust,ignore
+/// Router::new()
+///     .route("/pdf/html/assets", post(pdf_from_html_assets))
+///     .with_state(pool)
+/// ```
+pub async fn pdf_from_html_assets(
+    State(pool): State<SharedPool>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    log::debug!("PDF from HTML with assets request: {} bytes", body.len());
+
+    let api_key = api_key(&headers);
+    let job_id = header_value(&headers, JOB_ID_HEADER);
+    let content_type = header_value(&headers, header::CONTENT_TYPE.as_str()).unwrap_or_default();
+    let cancel = CancelHandle::new();
+    run_with_timeout(None, cancel.clone(), &headers, move || {
+        let mut request = PdfFromAssetsRequest::from_multipart(&content_type, body)?;
+        request.document.cancel = Some(cancel.clone());
+        let api_key = api_key.as_deref();
+        service::render_cancellable(&pool, api_key, job_id.as_deref(), &cancel, || {
+            service::render_metered(&pool, api_key, || {
+                service::generate_pdf_from_assets(&pool, &request)
+            })
+        })
+    })
+    .await
+}
+
+/// Render several documents and merge them into one PDF.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /pdf/merge
+/// Content-Type: application/json
+/// ```
+///
+/// The body is a JSON [`PdfMergeRequest`]: the documents, in page order,
+/// each a [`PdfFromUrlRequest`] or [`PdfFromHtmlRequest`]. Responds with the
+/// merged PDF, or a JSON [`ErrorResponse`] with the first failing
+/// document's error. The merge counts as one render toward the `X-API-Key`
+/// quota, and `X-Job-Id` works like [`pdf_from_url`]. See
+/// [`service::merge_pdfs`].
+///
+/// # Usage in Router
+///
+/// ```rust,ignore
+/// Router::new()
+///     .route("/pdf/merge", post(pdf_merge))
+///     .with_state(pool)
+/// ```
+pub async fn pdf_merge(
+    State(pool): State<SharedPool>,
+    headers: HeaderMap,
+    Json(mut request): Json<PdfMergeRequest>,
+) -> Response {
+    log::debug!("PDF merge request: {} documents", request.documents.len());
+
+    let api_key = api_key(&headers);
+    let job_id = header_value(&headers, JOB_ID_HEADER);
+    let cancel = CancelHandle::new();
+    request.set_cancel(&cancel);
+    run_with_timeout(None, cancel.clone(), &headers, move || {
+        let api_key = api_key.as_deref();
+        service::render_cancellable(&pool, api_key, job_id.as_deref(), &cancel, || {
+            service::render_metered(&pool, api_key, || service::merge_pdfs(&pool, &request))
+        })
+    })
+    .await
+}
+
+/// Start a render in the background.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /jobs
+/// Content-Type: application/json
+/// X-API-Key: team-a
+/// ```
+///
+/// The body is a [`PdfFromUrlRequest`] or [`PdfFromHtmlRequest`] (told apart
+/// by its `url` or `html` field), optionally with a `callback_url`; see
+/// [`JobRequest`]. Returns 202 with a JSON
+/// [`JobResponse`](crate::service::JobResponse) right away; poll
+/// [`job_status`] and download from [`job_result`], or wait for the result
+/// to be POSTed to the callback. An `X-Job-Id` header picks the job ID,
+/// otherwise one is generated. See [`service::submit_job`].
+pub async fn submit_job(
+    State(pool): State<SharedPool>,
+    headers: HeaderMap,
+    Json(job): Json<JobRequest>,
+) -> Response {
+    let job_id = header_value(&headers, JOB_ID_HEADER);
+    match service::submit_job(&pool, api_key(&headers).as_deref(), job_id.as_deref(), job) {
+        Ok(job) => (StatusCode::ACCEPTED, Json(job)).into_response(),
+        Err(e) => build_error_response(e, &headers),
+    }
+}
+
+/// Get the status of a job.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /jobs/{id}
+/// X-API-Key: team-a
+/// ```
+///
+/// Returns a JSON [`JobResponse`](crate::service::JobResponse), or 404
+/// `JOB_NOT_FOUND` for an unknown or expired job.
+pub async fn job_status(
+    State(pool): State<SharedPool>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    match service::job_status(&pool, api_key(&headers).as_deref(), &id) {
+        Ok(job) => Json(job).into_response(),
+        Err(e) => build_error_response(e, &headers),
+    }
+}
+
+/// Download the result of a finished job.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /jobs/{id}/result
+/// X-API-Key: team-a
+/// ```
+///
+/// Responds like [`pdf_from_url`] with the job's PDF or error, 409
+/// `JOB_PENDING` while it is still running, or 404 `JOB_NOT_FOUND` for an
+/// unknown or expired job.
+///
+/// Supports `Range` requests (`Accept-Ranges: bytes`), so PDF viewers can
+/// fetch a large document in pieces; see [`ByteRange`].
+pub async fn job_result(
+    State(pool): State<SharedPool>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    match service::job_result(&pool, api_key(&headers).as_deref(), &id) {
+        Ok(response) => {
+            build_ranged_pdf_response(response, header_value(&headers, header::RANGE.as_str()))
+        }
+        Err(e) => build_error_response(e, &headers),
+    }
+}
+
+/// Cancel a running job, or delete a finished job's result.
+///
+/// # Endpoint
+///
+/// ```text
+/// DELETE /jobs/{id}
+/// X-API-Key: team-a
+/// ```
+///
+/// Cancels the render sent with `X-Job-Id: {id}` or submitted to
+/// [`submit_job`] under the same API key; see [`service::cancel_job`].
+/// Returns 202 with a JSON [`JobResponse`](crate::service::JobResponse), or
+/// 404 `JOB_NOT_FOUND` for an unknown job.
+pub async fn cancel_job(
+    State(pool): State<SharedPool>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    match service::cancel_job(&pool, api_key(&headers).as_deref(), &id) {
+        Ok(job) => (StatusCode::ACCEPTED, Json(job)).into_response(),
+        Err(e) => build_error_response(e, &headers),
+    }
+}
+
+/// Get browser pool statistics.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /pool/stats
+/// ```
+///
+/// Returns a JSON [`PoolStatsResponse`](crate::service::PoolStatsResponse).
+pub async fn pool_stats(State(pool): State<SharedPool>, headers: HeaderMap) -> Response {
+    match service::get_pool_stats(&pool) {
+        Ok(stats) => Json(stats).into_response(),
+        Err(e) => build_error_response(e, &headers),
+    }
+}
+
+/// Get a detailed diagnostic snapshot of the browser pool.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /pool/debug
+/// ```
+///
+/// Returns a JSON [`PoolDebugResponse`](crate::service::PoolDebugResponse)
+/// with per-browser age, uses, memory, open tabs, and last health check,
+/// plus waiting requests, replacement tasks, and keep-alive timing. It
+/// includes internal details, so expose it only to operators.
+pub async fn pool_debug(State(pool): State<SharedPool>, headers: HeaderMap) -> Response {
+    match service::get_pool_debug(&pool) {
+        Ok(snapshot) => Json(snapshot).into_response(),
+        Err(e) => build_error_response(e, &headers),
+    }
+}
+
+/// Get per-browser statistics and lifetime counters of the pool.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /pool/stats/detailed
+/// ```
+///
+/// Returns a JSON [`PoolDetailedStatsResponse`](crate::service::PoolDetailedStatsResponse)
+/// with per-browser age, uses, last ping, ping failures, memory, and
+/// idle/busy state, plus browsers created, retired, and failed since
+/// startup.
+pub async fn pool_stats_detailed(State(pool): State<SharedPool>, headers: HeaderMap) -> Response {
+    match service::get_pool_detailed_stats(&pool) {
+        Ok(stats) => Json(stats).into_response(),
+        Err(e) => build_error_response(e, &headers),
+    }
+}
+
+/// Get the caller's API key usage.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /usage
+/// X-API-Key: team-a
+/// ```
+///
+/// Returns a JSON [`UsageResponse`](crate::service::UsageResponse) for the
+/// key in the `X-API-Key` header, or for the shared anonymous account
+/// without one.
+pub async fn usage_stats(State(pool): State<SharedPool>, headers: HeaderMap) -> Response {
+    match service::get_usage(&pool, api_key(&headers).as_deref()) {
+        Ok(usage) => Json(usage).into_response(),
+        Err(e) => build_error_response(e, &headers),
+    }
+}
+
+/// Health check endpoint.
+///
+/// Always returns 200 OK while the service is running. Does not check
+/// pool health - use [`readiness_check`] for that.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /health
+/// ```
+pub async fn health_check() -> Json<HealthResponse> {
+    Json(HealthResponse::default())
+}
+
+/// Readiness check endpoint.
+///
+/// Returns 200 OK if the pool has capacity to handle requests,
+/// 503 Service Unavailable otherwise.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /ready
+/// ```
+pub async fn readiness_check(State(pool): State<SharedPool>, headers: HeaderMap) -> Response {
+    match service::is_pool_ready(&pool) {
+        Ok(true) => Json(serde_json::json!({
+            "status": "ready"
+        }))
+        .into_response(),
+        Ok(false) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "status": "not_ready",
+                "reason": "no_available_capacity"
+            })),
+        )
+            .into_response(),
+        Err(e) => build_localized_response(StatusCode::SERVICE_UNAVAILABLE, e.into(), &headers),
+    }
+}
+
+// ============================================================================
+// Route Configuration
+// ============================================================================
+
+/// Create a router with all pre-built routes.
+///
+/// The returned router still needs its state; call
+/// `.with_state(pool)` before serving.
+///
+/// # Routes Added
+///
+/// | Method | Path | Handler |
+/// |--------|------|---------|
+/// | GET | `/pdf` | [`pdf_from_url`] |
+/// | POST | `/pdf/html` | [`pdf_from_html`] |
+/// | POST | `/pdf/html/assets` | [`pdf_from_html_assets`] |
+/// | POST | `/pdf/merge` | [`pdf_merge`] |
+/// | POST | `/jobs` | [`submit_job`] |
+/// | GET | `/jobs/{id}` | [`job_status`] |
+/// | DELETE | `/jobs/{id}` | [`cancel_job`] |
+/// | GET | `/jobs/{id}/result` | [`job_result`] |
+/// | GET | `/pool/stats` | [`pool_stats`] |
+/// | GET | `/pool/debug` | [`pool_debug`] |
+/// | GET | `/pool/stats/detailed` | [`pool_stats_detailed`] |
+/// | GET | `/usage` | [`usage_stats`] |
+/// | GET | `/health` | [`health_check`] |
+/// | GET | `/ready` | [`readiness_check`] |
+///
+/// # Custom Path Prefix
+///
+/// ```rust,ignore
+/// let app = Router::new()
+///     .nest("/api/v1", router())
+///     .with_state(pool);
+/// ```
+pub fn router() -> Router<SharedPool> {
+    Router::new()
+        .route("/pdf", get(pdf_from_url))
+        .route("/pdf/html", post(pdf_from_html))
+        .route("/pdf/html/assets", post(pdf_from_html_assets))
+        .route("/pdf/merge", post(pdf_merge))
+        .route("/jobs", post(submit_job))
+        .route("/jobs/{id}", get(job_status).delete(cancel_job))
+        .route("/jobs/{id}/result", get(job_result))
+        .route("/pool/stats", get(pool_stats))
+        .route("/pool/debug", get(pool_debug))
+        .route("/pool/stats/detailed", get(pool_stats_detailed))
+        .route("/usage", get(usage_stats))
+        .route("/health", get(health_check))
+        .route("/ready", get(readiness_check))
+}
+
+// ============================================================================
+// Graceful Shutdown
+// ============================================================================
+
+/// How long [`with_graceful_shutdown_pool`] waits for in-flight renders;
+/// below Kubernetes' default 30s termination grace period.
+pub const DRAIN_TIMEOUT: Duration = Duration::from_secs(25);
+
+/// Wait for `signal`, then drain and shut down the pool.
+///
+/// Pass the returned future to `axum::serve(...).with_graceful_shutdown()`.
+/// Once `signal` resolves, the pool stops handing out browsers (and
+/// [`readiness_check`] fails), renders already holding a browser get up to
+/// [`DRAIN_TIMEOUT`] to finish, and the pool is shut down: the keep-alive
+/// thread is stopped and replacement tasks are aborted, so no Chrome
+/// processes outlive the server.
+///
+/// # Parameters
+///
+/// * `pool` - The pool serving the router.
+/// * `signal` - Future resolving when the server should stop, such as
+///   [`shutdown_signal()`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::integrations::axum::{router, shutdown_signal, with_graceful_shutdown_pool};
+///
+/// let app = router().with_state(Arc::clone(&pool));
+///
+/// axum::serve(listener, app)
+///     .with_graceful_shutdown(with_graceful_shutdown_pool(pool, shutdown_signal()))
+///     .await?;
+/// ```
+pub async fn with_graceful_shutdown_pool<F>(pool: SharedPool, signal: F)
+where
+    F: Future<Output = ()>,
+{
+    signal.await;
+    log::info!("Shutdown signal received, draining browser pool...");
+
+    // Lock released before awaiting the drain
+    let drained = pool.lock().ok().map(|pool| pool.start_drain(DRAIN_TIMEOUT));
+    if let Some(drained) = drained {
+        drained.await;
+    }
+
+    // Joining the keep-alive thread blocks
+    let result = tokio::task::spawn_blocking(move || match pool.lock() {
+        Ok(mut pool) => pool.shutdown(),
+        Err(e) => log::error!("Failed to lock browser pool for shutdown: {}", e),
+    })
+    .await;
+    if let Err(e) = result {
+        log::error!("Browser pool shutdown task failed: {}", e);
+    }
+}
+
+/// Resolve on Ctrl+C or, on Unix, SIGTERM (sent by container runtimes and
+/// Kubernetes).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// axum::serve(listener, app)
+///     .with_graceful_shutdown(shutdown_signal())
+///     .await?;
+/// ```
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            log::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                log::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+// ============================================================================
+// Response Builders (Internal)
+// ============================================================================
+
+/// Read the API key from the `X-API-Key` header, ignoring blank values.
+fn api_key(headers: &HeaderMap) -> Option<String> {
+    header_value(headers, API_KEY_HEADER)
+}
+
+/// Read the `Idempotency-Key` header, ignoring blank values.
+fn idempotency_key(headers: &HeaderMap) -> Option<String> {
+    header_value(headers, IDEMPOTENCY_KEY_HEADER)
+}
+
+/// Read a header as trimmed text, ignoring blank and non-UTF-8 values.
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(String::from)
+}
+
+/// Run a blocking service call with the default timeout and build the response.
+///
+/// Failures become `fallback`'s placeholder PDF when one is given, and
+/// otherwise an error in the language `headers` accept. On timeout
+/// `cancel` is cancelled, so the abandoned render frees its browser.
+async fn run_with_timeout<F>(
+    fallback: Option<FallbackPdf>,
+    cancel: CancelHandle,
+    headers: &HeaderMap,
+    job: F,
+) -> Response
+where
+    F: FnOnce() -> Result<PdfResponse, PdfServiceError> + Send + 'static,
+{
+    let error = match run_blocking_with_timeout(cancel, job).await {
+        Ok(response) => return build_pdf_response(response),
+        Err(e) => e,
+    };
+
+    match fallback {
+        Some(fallback) => build_pdf_response(fallback.render(&error)),
+        None => build_error_response(error, headers),
+    }
+}
+
+/// Like [`run_with_timeout`], for a streamed render: the timeout covers
+/// rendering, after which the PDF is sent as it is read from Chrome.
+async fn run_stream_with_timeout<F>(
+    fallback: Option<FallbackPdf>,
+    cancel: CancelHandle,
+    headers: &HeaderMap,
+    open: F,
+) -> Response
+where
+    F: FnOnce() -> Result<PdfStream, PdfServiceError> + Send + 'static,
+{
+    let error = match run_blocking_with_timeout(cancel, open).await {
+        Ok(stream) => return build_stream_response(stream),
+        Err(e) => e,
+    };
+
+    match fallback {
+        Some(fallback) => build_pdf_response(fallback.render(&error)),
+        None => build_error_response(error, headers),
+    }
+}
+
+/// Run `job` on the blocking pool, giving up after
+/// [`DEFAULT_TIMEOUT_SECS`] and cancelling `cancel`.
+async fn run_blocking_with_timeout<T, F>(cancel: CancelHandle, job: F) -> Result<T, PdfServiceError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, PdfServiceError> + Send + 'static,
+{
+    let result = tokio::time::timeout(
+        Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        tokio::task::spawn_blocking(job),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(result)) => result,
+        Ok(Err(join_err)) => {
+            log::error!("Blocking task error: {}", join_err);
+            Err(PdfServiceError::Internal(join_err.to_string()))
+        }
+        Err(_timeout) => {
+            log::error!(
+                "PDF generation timed out after {} seconds",
+                DEFAULT_TIMEOUT_SECS
+            );
+            cancel.cancel();
+            Err(PdfServiceError::Timeout(format!(
+                "Operation timed out after {} seconds",
+                DEFAULT_TIMEOUT_SECS
+            )))
+        }
+    }
+}
+
+/// Build HTTP response for successful PDF generation.
+fn build_pdf_response(response: PdfResponse) -> Response {
+    log::info!(
+        "PDF generated successfully: {} bytes, filename={}",
+        response.size(),
+        response.filename
+    );
+
+    build_body_response(response.body(), &response)
+}
+
+/// Build the chunked HTTP response for a streamed PDF.
+fn build_stream_response(stream: PdfStream) -> Response {
+    log::info!("Streaming PDF, filename={}", stream.filename());
+
+    let disposition = HeaderValue::from_str(&stream.content_disposition()).ok();
+    let mut http_response = (
+        [
+            (header::CONTENT_TYPE, "application/pdf"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        axum::body::Body::from_stream(stream.into_chunks()),
+    )
+        .into_response();
+
+    if let Some(value) = disposition {
+        http_response
+            .headers_mut()
+            .insert(header::CONTENT_DISPOSITION, value);
+    }
+    http_response
+}
+
+/// Build the HTTP response sending `body`, with `response`'s trace ID and
+/// page count headers; see [`PdfResponse::body`].
+fn build_body_response(body: ResponseBody, response: &PdfResponse) -> Response {
+    let mut http_response = (
+        [
+            (header::CONTENT_TYPE, body.content_type),
+            (header::CACHE_CONTROL, "no-cache".to_string()),
+        ],
+        body.data,
+    )
+        .into_response();
+
+    let headers = http_response.headers_mut();
+    if let Some(value) = body
+        .content_disposition
+        .and_then(|d| HeaderValue::from_str(&d).ok())
+    {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+    if let Some(value) = response
+        .trace_id
+        .as_deref()
+        .and_then(|id| HeaderValue::from_str(id).ok())
+    {
+        headers.insert(TRACE_ID_HEADER, value);
+    }
+    for (name, value) in response.page_info().iter().flat_map(PageInfo::headers) {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    }
+    http_response
+}
+
+/// Build the response for a kept result, honouring a `Range` header.
+fn build_ranged_pdf_response(response: PdfResponse, range: Option<String>) -> Response {
+    let body = response.body();
+    let range = ByteRange::from_header(range.as_deref(), body.data.len());
+
+    let mut http_response = match range {
+        ByteRange::Full => build_body_response(body, &response),
+        ByteRange::Partial { .. } => {
+            let data = range.slice(&body.data);
+            let mut http_response = build_body_response(ResponseBody { data, ..body }, &response);
+            *http_response.status_mut() = StatusCode::PARTIAL_CONTENT;
+            http_response
+        }
+        ByteRange::Unsatisfiable { .. } => StatusCode::RANGE_NOT_SATISFIABLE.into_response(),
+    };
+
+    let headers = http_response.headers_mut();
+    headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Some(value) = range
+        .content_range()
+        .and_then(|r| HeaderValue::from_str(&r).ok())
+    {
+        headers.insert(header::CONTENT_RANGE, value);
+    }
+    http_response
+}
+
+/// Build HTTP response for errors, in the language `headers` accept.
+fn build_error_response(error: PdfServiceError, headers: &HeaderMap) -> Response {
+    let status =
+        StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    log::warn!("PDF generation error: {} (HTTP {})", error, status.as_u16());
+
+    build_localized_response(status, ErrorResponse::from(error), headers)
+}
+
+/// Translate an error body for the request's `Accept-Language` header and
+/// name the language in `Content-Language`.
+fn build_localized_response(
+    status: StatusCode,
+    body: ErrorResponse,
+    headers: &HeaderMap,
+) -> Response {
+    let locale =
+        negotiate_locale(header_value(headers, header::ACCEPT_LANGUAGE.as_str()).as_deref());
+    let mut response = (status, Json(body.in_locale(locale))).into_response();
+    response
+        .headers_mut()
+        .insert(header::CONTENT_LANGUAGE, HeaderValue::from_static(locale));
+    response
+}
+
+// ============================================================================
+// Extension Trait
+// ============================================================================
+
 /// Extension trait for `BrowserPool` with Axum helpers.
 ///
 /// Provides convenient methods for integrating with Axum.
@@ -330,10 +1190,163 @@ pub fn create_extension(pool: SharedBrowserPool) -> axum::Extension<SharedBrowse
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::service::{PDF_PAGE_SIZE_HEADER, PDF_PAGES_HEADER};
 
     #[test]
     fn test_type_alias_compiles() {
         // This test just verifies the type alias is valid
         fn _accepts_pool_state(_: BrowserPoolState) {}
+        fn _accepts_shared_pool(_: SharedPool) {}
+    }
+
+    #[test]
+    fn test_error_response_status() {
+        let headers = HeaderMap::new();
+        let response =
+            build_error_response(PdfServiceError::InvalidUrl("bad".to_string()), &headers);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = build_error_response(PdfServiceError::Timeout("slow".to_string()), &headers);
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let response =
+            build_error_response(PdfServiceError::QuotaExceeded("used".to_string()), &headers);
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = build_error_response(
+            PdfServiceError::IdempotencyConflict("busy".to_string()),
+            &headers,
+        );
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response =
+            build_error_response(PdfServiceError::JobNotFound("gone".to_string()), &headers);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response =
+            build_error_response(PdfServiceError::JobPending("busy".to_string()), &headers);
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_error_response_language() {
+        let error = || PdfServiceError::NavigationFailed("net::ERR_FAILED".to_string());
+
+        let response = build_error_response(error(), &HeaderMap::new());
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "en");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_LANGUAGE, "de-DE, en;q=0.5".parse().unwrap());
+        let response = build_error_response(error(), &headers);
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "de");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.code, "NAVIGATION_FAILED");
+        assert_eq!(body.message, "Die Seite konnte nicht geladen werden.");
+        assert!(body.error.contains("net::ERR_FAILED"));
+    }
+
+    #[test]
+    fn test_api_key_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(api_key(&headers), None);
+
+        headers.insert(API_KEY_HEADER, " team-a ".parse().unwrap());
+        assert_eq!(api_key(&headers).as_deref(), Some("team-a"));
+
+        headers.insert(API_KEY_HEADER, "  ".parse().unwrap());
+        assert_eq!(api_key(&headers), None);
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "order-1".parse().unwrap());
+        assert_eq!(idempotency_key(&headers).as_deref(), Some("order-1"));
+    }
+
+    #[test]
+    fn test_pdf_response_headers() {
+        let response = build_pdf_response(PdfResponse::new(
+            b"%PDF-".to_vec(),
+            "doc.pdf".to_string(),
+            true,
+        ));
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"doc.pdf\""
+        );
+    }
+
+    #[test]
+    fn test_page_info_headers() {
+        let pdf = b"%PDF-1.4\n<</Type /Page /MediaBox [0 0 612 792]>>".to_vec();
+        let response = build_pdf_response(PdfResponse::new(pdf, "doc.pdf".to_string(), false));
+
+        assert_eq!(response.headers()[PDF_PAGES_HEADER], "1");
+        assert_eq!(response.headers()[PDF_PAGE_SIZE_HEADER], "612x792");
+
+        let response = build_pdf_response(PdfResponse::new(
+            b"%PDF-".to_vec(),
+            "doc.pdf".to_string(),
+            false,
+        ));
+        assert!(!response.headers().contains_key(PDF_PAGES_HEADER));
+    }
+
+    #[test]
+    fn test_thumbnail_response_headers() {
+        let response = build_pdf_response(
+            PdfResponse::new(b"%PDF-".to_vec(), "doc.pdf".to_string(), false)
+                .with_thumbnail(b"\x89PNG".to_vec()),
+        );
+
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "inline; filename=\"doc.png\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ranged_pdf_response() {
+        let pdf = || PdfResponse::new(b"%PDF-1.7 body".to_vec(), "doc.pdf".to_string(), false);
+
+        let response = build_ranged_pdf_response(pdf(), None);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+
+        let response = build_ranged_pdf_response(pdf(), Some("bytes=0-4".to_string()));
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 0-4/13");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"%PDF-");
+
+        let response = build_ranged_pdf_response(pdf(), Some("bytes=100-".to_string()));
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes */13");
+    }
+
+    #[tokio::test]
+    async fn test_run_with_timeout_fallback() {
+        let failing = || Err(PdfServiceError::BrowserUnavailable("busy".to_string()));
+
+        let response =
+            run_with_timeout(None, CancelHandle::new(), &HeaderMap::new(), failing).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let fallback = FallbackPdf::new(Some("req-1"), "report.pdf".to_string(), false);
+        let response = run_with_timeout(
+            Some(fallback),
+            CancelHandle::new(),
+            &HeaderMap::new(),
+            failing,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
     }
 }
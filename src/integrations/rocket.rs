@@ -31,7 +31,11 @@
 //! |--------|------|-------------|
 //! | GET | `/pdf?url=...` | Convert URL to PDF |
 //! | POST | `/pdf/html` | Convert HTML to PDF |
+//! | DELETE | `/jobs/<id>` | Cancel a running render |
 //! | GET | `/pool/stats` | Pool statistics |
+//! | GET | `/pool/debug` | Pool diagnostics snapshot |
+//! | GET | `/pool/stats/detailed` | Per-browser statistics and lifetime counters |
+//! | GET | `/usage` | Usage of the caller's API key |
 //! | GET | `/health` | Health check |
 //! | GET | `/ready` | Readiness check |
 //!
@@ -96,7 +100,7 @@
 //!     match result {
 //!         Ok(Ok(pdf)) => {
 //!             // Custom post-processing
-//!             Ok(pdf.data)
+//!             Ok(pdf.data.into())
 //!         }
 //!         Ok(Err(_)) => Err(Status::BadRequest),
 //!         Err(_) => Err(Status::InternalServerError),
@@ -148,12 +152,14 @@
 //!
 //! # Graceful Shutdown
 //!
-//! For proper cleanup, use Rocket's shutdown fairing:
+//! For proper cleanup, use Rocket's shutdown fairing. Draining lets
+//! renders still holding a browser finish before the pool shuts down:
 //!
 //! ```rust,ignore
 //! use rocket::{fairing::{Fairing, Info, Kind}, launch, Orbit, Rocket};
 //! use html2pdf_api::prelude::*;
 //! use std::sync::Arc;
+//! use std::time::Duration;
 //!
 //! struct ShutdownFairing {
 //!     pool: SharedBrowserPool,
@@ -169,6 +175,8 @@
 //!     }
 //!
 //!     async fn on_shutdown(&self, _rocket: &Rocket<Orbit>) {
+//!         let drained = self.pool.lock().unwrap().start_drain(Duration::from_secs(25));
+//!         drained.await;
 //!         if let Ok(mut pool) = self.pool.lock() {
 //!             pool.shutdown();
 //!         }
@@ -197,7 +205,16 @@
 //! |---------|--------|--------------|-------------|
 //! | [`pdf_from_url`] | GET | `/pdf` | Convert URL to PDF |
 //! | [`pdf_from_html`] | POST | `/pdf/html` | Convert HTML to PDF |
+//! | [`pdf_from_html_assets`] | POST | `/pdf/html/assets` | Convert HTML uploaded with its assets to PDF |
+//! | [`pdf_merge`] | POST | `/pdf/merge` | Render several documents into one PDF |
+//! | [`submit_job`] | POST | `/jobs` | Start a background render |
+//! | [`job_status`] | GET | `/jobs/<id>` | Status of a job |
+//! | [`job_result`] | GET | `/jobs/<id>/result` | Download a finished job's PDF |
+//! | [`cancel_job`] | DELETE | `/jobs/<id>` | Cancel a job or delete its result |
 //! | [`pool_stats`] | GET | `/pool/stats` | Pool statistics |
+//! | [`pool_debug`] | GET | `/pool/debug` | Pool diagnostics snapshot |
+//! | [`pool_stats_detailed`] | GET | `/pool/stats/detailed` | Per-browser statistics and lifetime counters |
+//! | [`usage_stats`] | GET | `/usage` | Usage of the caller's API key |
 //! | [`health_check`] | GET | `/health` | Health check (always 200) |
 //! | [`readiness_check`] | GET | `/ready` | Readiness check (checks pool) |
 //!
@@ -224,12 +241,16 @@
 //! | [`BrowserPoolRocketExt`] | Adds `into_rocket_data()` to `BrowserPool` |
 
 use rocket::{
-    Build, Request, Rocket, State,
+    Build, Data, Either, Request, Rocket, State,
+    data::{Limits, ToByteUnit},
+    delete,
     form::FromForm,
+    futures::{StreamExt, future},
     get,
     http::{ContentType, Header, Status},
     post,
-    response::{self, Responder},
+    request::{FromRequest, Outcome},
+    response::{self, Responder, status::Accepted, stream::ByteStream},
     routes,
     serde::json::Json,
 };
@@ -237,10 +258,14 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use crate::SharedBrowserPool;
+use crate::cancel::CancelHandle;
 use crate::pool::BrowserPool;
 use crate::service::{
-    self, DEFAULT_TIMEOUT_SECS, ErrorResponse, HealthResponse, PdfFromHtmlRequest,
-    PdfFromUrlRequest, PdfResponse, PdfServiceError, PoolStatsResponse,
+    self, API_KEY_HEADER, ByteRange, Bytes, DEFAULT_TIMEOUT_SECS, ErrorResponse, FallbackPdf,
+    Geolocation, HealthResponse, IDEMPOTENCY_KEY_HEADER, JOB_ID_HEADER, JobRequest, JobResponse,
+    PageInfo, PdfFromAssetsRequest, PdfFromHtmlRequest, PdfFromUrlRequest, PdfMergeRequest,
+    PdfResponse, PdfServiceError, PdfStream, PoolDebugResponse, PoolDetailedStatsResponse,
+    PoolStatsResponse, REQUEST_ID_HEADER, TRACE_ID_HEADER, UsageResponse, Watermark,
 };
 
 // ============================================================================
@@ -317,6 +342,104 @@ pub struct PdfFromUrlQuery {
     pub download: Option<bool>,
     /// Include background graphics (optional, defaults to true).
     pub print_background: Option<bool>,
+    /// Named paper size, e.g. "A4" or "Letter" (optional).
+    pub paper_format: Option<String>,
+    /// Paper width in inches (optional).
+    pub paper_width: Option<f64>,
+    /// Paper height in inches (optional).
+    pub paper_height: Option<f64>,
+    /// Top margin in inches (optional).
+    pub margin_top: Option<f64>,
+    /// Bottom margin in inches (optional).
+    pub margin_bottom: Option<f64>,
+    /// Left margin in inches (optional).
+    pub margin_left: Option<f64>,
+    /// Right margin in inches (optional).
+    pub margin_right: Option<f64>,
+    /// Rendering scale, 0.1-2.0 (optional, defaults to 1.0).
+    pub scale: Option<f64>,
+    /// Pages to print, e.g. "1-5,8" (optional, defaults to all pages).
+    pub page_ranges: Option<String>,
+    /// Print the header and footer (optional, defaults to true with a template).
+    pub display_header_footer: Option<bool>,
+    /// HTML printed at the top of each page (optional).
+    pub header_template: Option<String>,
+    /// HTML printed at the bottom of each page (optional).
+    pub footer_template: Option<String>,
+    /// CSS media type: print or screen (optional, defaults to print).
+    pub emulate_media: Option<String>,
+    /// prefers-color-scheme: light or dark (optional, defaults to light).
+    pub color_scheme: Option<String>,
+    /// Write a tagged (accessible) PDF (optional).
+    pub generate_tagged_pdf: Option<bool>,
+    /// Add bookmarks built from the headings (optional).
+    pub generate_document_outline: Option<bool>,
+    /// Run the page's scripts (optional, defaults to true).
+    pub javascript_enabled: Option<bool>,
+    /// Comma-separated resource types to block (optional, e.g. "image,font").
+    pub block_resources: Option<String>,
+    /// Comma-separated domains or URL patterns to block (optional).
+    pub block_urls: Option<String>,
+    /// Accept invalid TLS certificates (optional, defaults to false).
+    pub ignore_tls_errors: Option<bool>,
+    /// Cut the network once the page has loaded (optional, defaults to false).
+    pub offline: Option<bool>,
+    /// Ignore the browser's HTTP cache (optional, defaults to false).
+    pub bypass_cache: Option<bool>,
+    /// Emulated position as "lat,lon[,accuracy]" (optional).
+    pub geolocation: Option<Geolocation>,
+    /// IANA timezone the page runs in (optional, defaults to the server's).
+    pub timezone: Option<String>,
+    /// Emulated viewport width in CSS pixels (optional).
+    pub viewport_width: Option<u32>,
+    /// Emulated viewport height in CSS pixels (optional).
+    pub viewport_height: Option<u32>,
+    /// Device pixel ratio (optional, defaults to the browser's).
+    pub device_scale_factor: Option<f64>,
+    /// Emulate a mobile device (optional, defaults to false).
+    pub mobile: Option<bool>,
+    /// User-Agent for this render (optional, defaults to the browser's).
+    pub user_agent: Option<String>,
+    /// Navigation method: GET or POST (optional, defaults to GET).
+    pub method: Option<String>,
+    /// Body of a POST navigation (optional).
+    pub post_data: Option<String>,
+    /// Content-Type of `post_data` (optional, defaults to form-encoded).
+    pub content_type: Option<String>,
+    /// Return a placeholder PDF on failure (optional, defaults to false).
+    pub fallback_pdf: Option<bool>,
+    /// Respond with an image of the first page (optional, defaults to false).
+    pub thumbnail: Option<bool>,
+    /// Thumbnail width in pixels (optional, defaults to 256).
+    pub thumbnail_width: Option<u32>,
+    /// Thumbnail format: png, jpeg, or webp (optional, defaults to png).
+    pub thumbnail_format: Option<String>,
+    /// JPEG/WebP thumbnail quality, 1-100 (optional).
+    pub thumbnail_quality: Option<u32>,
+    /// Comma-separated artifacts to return, e.g. "pdf,png,html" (optional).
+    pub outputs: Option<String>,
+    /// Text stamped on every page, e.g. "DRAFT" (optional).
+    pub watermark: Option<String>,
+    /// Linearize the PDF for fast web view (optional, defaults to false).
+    pub linearize: Option<bool>,
+    /// Convert the PDF toward PDF/A-2b (optional, defaults to false).
+    pub pdf_a: Option<bool>,
+    /// Return a ZIP with one PDF per page (optional, defaults to false).
+    pub split_pages: Option<bool>,
+    /// Send the PDF in chunks as Chrome hands it over (optional, defaults to false).
+    pub stream: Option<bool>,
+    /// JavaScript readiness check (optional, defaults to `window.isPageDone === true`).
+    pub wait_for_expression: Option<String>,
+    /// CSS added once the page is ready (optional).
+    pub inject_css: Option<String>,
+    /// JavaScript run once the page is ready (optional).
+    pub inject_js: Option<String>,
+    /// Save a DevTools performance trace (optional, defaults to false).
+    pub trace: Option<bool>,
+    /// Return a JSON debug report instead of the PDF (optional, defaults to false).
+    pub debug: Option<bool>,
+    /// Checkout priority: high, normal, or low (optional, defaults to normal).
+    pub priority: Option<String>,
 }
 
 impl From<PdfFromUrlQuery> for PdfFromUrlRequest {
@@ -328,10 +451,170 @@ impl From<PdfFromUrlQuery> for PdfFromUrlRequest {
             landscape: query.landscape,
             download: query.download,
             print_background: query.print_background,
+            paper_format: query.paper_format,
+            paper_width: query.paper_width,
+            paper_height: query.paper_height,
+            margin_top: query.margin_top,
+            margin_bottom: query.margin_bottom,
+            margin_left: query.margin_left,
+            margin_right: query.margin_right,
+            scale: query.scale,
+            page_ranges: query.page_ranges,
+            display_header_footer: query.display_header_footer,
+            header_template: query.header_template,
+            footer_template: query.footer_template,
+            emulate_media: query.emulate_media,
+            color_scheme: query.color_scheme,
+            generate_tagged_pdf: query.generate_tagged_pdf,
+            generate_document_outline: query.generate_document_outline,
+            javascript_enabled: query.javascript_enabled,
+            block_resources: query.block_resources.as_deref().map(split_list),
+            block_urls: query.block_urls.as_deref().map(split_list),
+            ignore_tls_errors: query.ignore_tls_errors,
+            offline: query.offline,
+            bypass_cache: query.bypass_cache,
+            geolocation: query.geolocation,
+            timezone: query.timezone,
+            viewport_width: query.viewport_width,
+            viewport_height: query.viewport_height,
+            device_scale_factor: query.device_scale_factor,
+            mobile: query.mobile,
+            user_agent: query.user_agent,
+            // JSON bodies only: storage, headers, cookies and auth usually
+            // carry secrets, and mocks, actions and metadata are structured
+            storage: None,
+            headers: None,
+            cookies: None,
+            auth: None,
+            mock_responses: None,
+            actions: None,
+            metadata: None,
+            method: query.method,
+            post_data: query.post_data,
+            content_type: query.content_type,
+            fallback_pdf: query.fallback_pdf,
+            thumbnail: query.thumbnail,
+            thumbnail_width: query.thumbnail_width,
+            thumbnail_format: query.thumbnail_format,
+            thumbnail_quality: query.thumbnail_quality,
+            outputs: query.outputs.as_deref().map(split_list),
+            watermark: query.watermark.map(Watermark::text),
+            linearize: query.linearize,
+            pdf_a: query.pdf_a,
+            split_pages: query.split_pages,
+            stream: query.stream,
+            wait_for_expression: query.wait_for_expression,
+            inject_css: query.inject_css,
+            inject_js: query.inject_js,
+            trace: query.trace,
+            debug: query.debug,
+            priority: query.priority,
+            cancel: None,
         }
     }
 }
 
+/// API key from the `X-API-Key` header, or `None` when absent or blank.
+///
+/// A request guard that never fails; use it in custom handlers to account
+/// renders with [`service::render_metered`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[get("/custom")]
+/// fn custom(api_key: ApiKey) -> String {
+///     api_key.0.unwrap_or_default()
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ApiKey(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ApiKey {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(ApiKey(header_value(req, API_KEY_HEADER)))
+    }
+}
+
+/// Key from the `Idempotency-Key` header, or `None` when absent or blank.
+///
+/// A request guard that never fails; pass it to
+/// [`service::render_idempotent`] in custom handlers.
+#[derive(Debug, Clone, Default)]
+pub struct IdempotencyKey(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKey {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(IdempotencyKey(header_value(req, IDEMPOTENCY_KEY_HEADER)))
+    }
+}
+
+/// ID from the `X-Job-Id` header, or `None` when absent or blank.
+///
+/// A request guard that never fails; pass it to
+/// [`service::render_cancellable`] in custom handlers.
+#[derive(Debug, Clone, Default)]
+pub struct JobId(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for JobId {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(JobId(header_value(req, JOB_ID_HEADER)))
+    }
+}
+
+/// Value of the `X-Request-Id` header, or `None` when absent or blank.
+///
+/// A request guard that never fails; the pre-built handlers show it on
+/// [`FallbackPdf`] placeholders.
+#[derive(Debug, Clone, Default)]
+pub struct RequestId(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestId {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(RequestId(header_value(req, REQUEST_ID_HEADER)))
+    }
+}
+
+/// Read a header as trimmed text, ignoring blank values.
+fn header_value(req: &Request<'_>, name: &str) -> Option<String> {
+    req.headers()
+        .get_one(name)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+/// Parses the `geolocation` query value, `lat,lon[,accuracy]`.
+impl<'v> rocket::form::FromFormField<'v> for Geolocation {
+    fn from_value(field: rocket::form::ValueField<'v>) -> rocket::form::Result<'v, Self> {
+        field
+            .value
+            .parse()
+            .map_err(|e: String| rocket::form::Error::validation(e).into())
+    }
+}
+
+/// Split a comma-separated query value into trimmed, non-empty items.
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 // ============================================================================
 // Custom Response Types
 // ============================================================================
@@ -342,6 +625,8 @@ impl From<PdfFromUrlQuery> for PdfFromUrlRequest {
 /// - `Content-Type: application/pdf`
 /// - `Content-Disposition: inline` or `attachment` based on `force_download`
 /// - `Cache-Control: no-cache`
+/// - `X-Trace-Id` when a performance trace was saved
+/// - `X-Pdf-Pages` and `X-Pdf-Page-Size` when the page count is known
 ///
 /// # Example
 ///
@@ -350,42 +635,127 @@ impl From<PdfFromUrlQuery> for PdfFromUrlRequest {
 ///
 /// fn create_pdf_response(data: Vec<u8>) -> PdfResponder {
 ///     PdfResponder {
-///         data,
+///         data: data.into(),
 ///         filename: "document.pdf".to_string(),
 ///         force_download: false,
+///         content_type: None,
+///         trace_id: None,
+///         page_info: None,
 ///     }
 /// }
 /// ```
 pub struct PdfResponder {
-    /// The PDF binary data.
-    pub data: Vec<u8>,
-    /// The filename to suggest to the browser.
+    /// The PDF binary data (shared, not copied, from [`PdfResponse::data`]).
+    pub data: Bytes,
+    /// The filename to suggest to the browser. Empty for a multipart body,
+    /// which is sent without `Content-Disposition`.
     pub filename: String,
     /// Whether to force download (attachment) or allow inline display.
     pub force_download: bool,
+    /// `Content-Type` of `data` when it is not the PDF: a thumbnail, HTML,
+    /// a debug report, or a multipart body (see [`PdfResponse::body`]).
+    pub content_type: Option<String>,
+    /// ID of the render's saved performance trace, sent as `X-Trace-Id`.
+    pub trace_id: Option<String>,
+    /// Page count and size of the PDF, sent as `X-Pdf-Pages` and
+    /// `X-Pdf-Page-Size`; see [`PdfResponse::page_info`].
+    pub page_info: Option<PageInfo>,
 }
 
-impl<'r> Responder<'r, 'static> for PdfResponder {
-    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
-        let disposition = if self.force_download {
-            format!("attachment; filename=\"{}\"", self.filename)
-        } else {
-            format!("inline; filename=\"{}\"", self.filename)
+impl<'r, 'o: 'r> Responder<'r, 'o> for PdfResponder {
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'o> {
+        let content_type = match self.content_type {
+            None => ContentType::PDF,
+            Some(content_type) => {
+                ContentType::parse_flexible(&content_type).unwrap_or(ContentType::Binary)
+            }
         };
 
-        response::Response::build()
-            .header(ContentType::PDF)
-            .header(Header::new("Cache-Control", "no-cache"))
-            .header(Header::new("Content-Disposition", disposition))
+        let mut builder = response::Response::build();
+        builder
+            .header(content_type)
+            .header(Header::new("Cache-Control", "no-cache"));
+        if !self.filename.is_empty() {
+            let disposition = if self.force_download {
+                format!("attachment; filename=\"{}\"", self.filename)
+            } else {
+                format!("inline; filename=\"{}\"", self.filename)
+            };
+            builder.header(Header::new("Content-Disposition", disposition));
+        }
+        if let Some(trace_id) = self.trace_id {
+            builder.header(Header::new(TRACE_ID_HEADER, trace_id));
+        }
+        for (name, value) in self.page_info.iter().flat_map(PageInfo::headers) {
+            builder.header(Header::new(name, value));
+        }
+        builder
             .sized_body(self.data.len(), std::io::Cursor::new(self.data))
             .ok()
     }
 }
 
+/// Responder sending a [`PdfStream`] as a chunked body, as the PDF is read
+/// from Chrome.
+///
+/// Sent for requests with `stream=true`. The body has no `Content-Length`;
+/// if reading the PDF fails midway, the body ends early.
+pub struct PdfStreamResponder(pub PdfStream);
+
+impl<'r> Responder<'r, 'r> for PdfStreamResponder {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        let disposition = self.0.content_disposition();
+        let chunks = self
+            .0
+            .into_chunks()
+            .scan((), |_, chunk| future::ready(chunk.ok()));
+
+        let mut response = ByteStream(chunks).respond_to(request)?;
+        response.set_header(ContentType::PDF);
+        response.set_header(Header::new("Cache-Control", "no-cache"));
+        response.set_header(Header::new("Content-Disposition", disposition));
+        Ok(response)
+    }
+}
+
+/// [`PdfResponder`] that honours the request's `Range` header.
+///
+/// Sends `Accept-Ranges: bytes`, and only the requested part of the body
+/// with 206 Partial Content when a single range is asked for; see
+/// [`ByteRange`]. Used by [`job_result`] so PDF viewers can fetch a large
+/// kept result in pieces.
+pub struct RangedPdfResponder(pub PdfResponder);
+
+impl<'r> Responder<'r, 'static> for RangedPdfResponder {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let range = ByteRange::from_header(request.headers().get_one("Range"), self.0.data.len());
+        let body = range.slice(&self.0.data);
+
+        let mut response = match range {
+            ByteRange::Unsatisfiable { .. } => response::Response::build()
+                .status(Status::RangeNotSatisfiable)
+                .finalize(),
+            _ => self.0.respond_to(request)?,
+        };
+        if let ByteRange::Partial { .. } = range {
+            response.set_status(Status::PartialContent);
+            response.set_sized_body(body.len(), std::io::Cursor::new(body));
+        }
+
+        response.set_header(Header::new("Accept-Ranges", "bytes"));
+        if let Some(content_range) = range.content_range() {
+            response.set_header(Header::new("Content-Range", content_range));
+        }
+        Ok(response)
+    }
+}
+
 /// Error response wrapper for Rocket.
 ///
 /// This responder automatically sets the correct HTTP status code based on
-/// the error type and returns a JSON error body.
+/// the error type and returns a JSON error body. The body's `message` is
+/// translated for the request's `Accept-Language` header, which is echoed
+/// as `Content-Language`; see [`service::negotiate_locale`].
 ///
 /// # Example
 ///
@@ -400,6 +770,7 @@ impl<'r> Responder<'r, 'static> for PdfResponder {
 ///         body: ErrorResponse {
 ///             error: msg.to_string(),
 ///             code: "INVALID_REQUEST".to_string(),
+///             message: String::new(), // filled in from the catalog
 ///         },
 ///     }
 /// }
@@ -413,8 +784,10 @@ pub struct ErrorResponder {
 
 impl<'r> Responder<'r, 'static> for ErrorResponder {
     fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
-        response::Response::build_from(Json(self.body).respond_to(request)?)
+        let locale = service::negotiate_locale(request.headers().get_one("Accept-Language"));
+        response::Response::build_from(Json(self.body.in_locale(locale)).respond_to(request)?)
             .status(self.status)
+            .header(Header::new("Content-Language", locale))
             .ok()
     }
 }
@@ -449,6 +822,41 @@ pub type HandlerResult<T> = Result<T, ErrorResponder>;
 /// | `landscape` | bool | No | `false` | Use landscape orientation |
 /// | `download` | bool | No | `false` | Force download vs inline display |
 /// | `print_background` | bool | No | `true` | Include background graphics |
+/// | `paper_format` | string | No | - | Named paper size: `Letter`, `Legal`, `Tabloid`, `Ledger`, `A0`-`A6` |
+/// | `paper_width` | f64 | No | - | Paper width in inches (overrides `paper_format`) |
+/// | `paper_height` | f64 | No | - | Paper height in inches (overrides `paper_format`) |
+/// | `margin_top`, `margin_bottom`, `margin_left`, `margin_right` | f64 | No | - | Margins in inches |
+/// | `scale` | f64 | No | `1.0` | Rendering scale, 0.1-2.0 |
+/// | `page_ranges` | string | No | all pages | Pages to print, e.g. `1-5, 8` |
+/// | `display_header_footer` | bool | No | with a template | Print the header and footer templates |
+/// | `header_template` | string | No | - | HTML printed in the top margin (classes `pageNumber`, `totalPages`, `date`, `title`, `url`) |
+/// | `footer_template` | string | No | - | HTML printed in the bottom margin |
+/// | `emulate_media` | string | No | `print` | CSS media type: `print` or `screen` |
+/// | `color_scheme` | string | No | `light` | `prefers-color-scheme`: `light` or `dark` |
+/// | `generate_tagged_pdf` | bool | No | pool default | Write a tagged (accessible) PDF |
+/// | `generate_document_outline` | bool | No | pool default | Add bookmarks built from the headings |
+/// | `javascript_enabled` | bool | No | `true` | Run the page's scripts (`false` for untrusted content) |
+/// | `block_resources` | string | No | - | Comma-separated resource types to block (`image,font,media`) |
+/// | `block_urls` | string | No | - | Comma-separated domains or URL patterns to block |
+/// | `ignore_tls_errors` | bool | No | `false` | Accept invalid TLS certificates (logged as a warning) |
+/// | `offline` | bool | No | `false` | Cut the network once the page has loaded |
+/// | `bypass_cache` | bool | No | `false` | Ignore the browser's HTTP cache |
+/// | `fallback_pdf` | bool | No | `false` | On failure, return a placeholder PDF (HTTP 200) instead of a JSON error |
+/// | `thumbnail` | bool | No | `false` | Respond with an image of the first page instead of the PDF |
+/// | `thumbnail_width` | u32 | No | `256` | Thumbnail width in pixels (16-2048) |
+/// | `thumbnail_format` | string | No | `png` | Thumbnail format: `png`, `jpeg`, or `webp` |
+/// | `thumbnail_quality` | u32 | No | - | JPEG/WebP thumbnail quality (1-100) |
+/// | `watermark` | string | No | - | Text stamped on every page, e.g. `DRAFT` (JSON bodies take an object, see [`Watermark`](crate::service::Watermark)) |
+/// | `linearize` | bool | No | `false` | Linearize the PDF for fast web view (needs qpdf) |
+/// | `pdf_a` | bool | No | `false` | Convert the PDF toward PDF/A-2b (needs Ghostscript) |
+/// | `split_pages` | bool | No | `false` | Return a ZIP with one PDF per page (needs qpdf) |
+/// | `stream` | bool | No | `false` | Send the PDF in chunks as Chrome hands it over |
+/// | `wait_for_expression` | string | No | `window.isPageDone === true` | JavaScript readiness check |
+/// | `inject_css` | string | No | - | CSS added once the page is ready (e.g. to hide a cookie banner) |
+/// | `inject_js` | string | No | - | JavaScript run once the page is ready, before printing |
+/// | `trace` | bool | No | `false` | Save a DevTools performance trace (needs `BROWSER_TRACE_DIR`) |
+/// | `debug` | bool | No | `false` | Respond with a JSON render report instead of the PDF |
+/// | `priority` | string | No | `normal` | `high`, `normal`, or `low`; order in the checkout queue when the pool is saturated |
 ///
 /// # Response
 ///
@@ -459,13 +867,36 @@ pub type HandlerResult<T> = Result<T, ErrorResponder>;
 /// - `Content-Disposition: inline; filename="document.pdf"` (or `attachment` if `download=true`)
 /// - `Cache-Control: no-cache`
 ///
+/// ## Streamed (200 OK)
+///
+/// With `stream=true`, the same headers without `Content-Length`; the PDF
+/// is sent in chunks as it is read from Chrome. See
+/// [`PdfFromUrlRequest::stream`] for what cannot be streamed.
+///
+/// ## Fallback (200 OK)
+///
+/// With `fallback_pdf=true`, any failure below instead returns a one-page
+/// "generation failed" PDF with the requested filename, showing a request
+/// ID (the `X-Request-Id` header, or a generated one that is logged) and the
+/// error code and message.
+///
+/// ## Cancellation
+///
+/// Send an `X-Job-Id` header to be able to cancel the render from another
+/// request with [`cancel_job`]. A render that hits the timeout below is
+/// cancelled too, so it stops holding a browser.
+///
 /// ## Errors
 ///
 /// | Status | Code | Description |
 /// |--------|------|-------------|
 /// | 400 | `INVALID_URL` | URL is empty or malformed |
+/// | 400 | `INVALID_REQUEST` | `X-Job-Id` is already used by a running render |
+/// | 409 | `CANCELLED` | Cancelled with `DELETE /jobs/<id>` |
+/// | 429 | `QUOTA_EXCEEDED` | The `X-API-Key` key used up its render quota |
 /// | 502 | `NAVIGATION_FAILED` | Failed to load the URL |
 /// | 503 | `BROWSER_UNAVAILABLE` | No browsers available in pool |
+/// | 507 | `DISK_FULL` | Temp volume nearly full |
 /// | 504 | `TIMEOUT` | Operation timed out |
 ///
 /// # Examples
@@ -498,40 +929,78 @@ pub type HandlerResult<T> = Result<T, ErrorResponder>;
 #[get("/pdf?<query..>")]
 pub async fn pdf_from_url(
     pool: &State<SharedPool>,
+    api_key: ApiKey,
+    job_id: JobId,
+    request_id: RequestId,
     query: PdfFromUrlQuery,
-) -> HandlerResult<PdfResponder> {
-    let request: PdfFromUrlRequest = query.into();
+) -> HandlerResult<Either<PdfResponder, PdfStreamResponder>> {
+    let mut request: PdfFromUrlRequest = query.into();
     let pool = Arc::clone(pool.inner());
+    let cancel = CancelHandle::new();
+    request.cancel = Some(cancel.clone());
+    let fallback = request.fallback_pdf().then(|| {
+        FallbackPdf::new(
+            request_id.0.as_deref(),
+            request.filename_or_default(),
+            request.is_download(),
+        )
+    });
 
     log::debug!("PDF from URL request: {}", request.url);
 
+    if request.stream() {
+        return stream_with_timeout(fallback, cancel, move || {
+            service::stream_metered(&pool, api_key.0.as_deref(), || {
+                service::stream_pdf_from_url(&pool, &request)
+            })
+        })
+        .await;
+    }
+
     // Run blocking PDF generation with timeout
     let result = tokio::time::timeout(
         Duration::from_secs(DEFAULT_TIMEOUT_SECS),
-        tokio::task::spawn_blocking(move || service::generate_pdf_from_url(&pool, &request)),
+        tokio::task::spawn_blocking({
+            let cancel = cancel.clone();
+            move || {
+                let api_key = api_key.0.as_deref();
+                service::render_cancellable(&pool, api_key, job_id.0.as_deref(), &cancel, || {
+                    service::render_metered(&pool, api_key, || {
+                        service::render_cached(&pool, &request, || {
+                            service::render_coalesced(&pool, &request, || {
+                                service::generate_pdf_from_url(&pool, &request)
+                            })
+                        })
+                    })
+                })
+            }
+        }),
     )
     .await;
 
-    match result {
+    let response = match result {
         Ok(Ok(Ok(response))) => Ok(build_pdf_response(response)),
-        Ok(Ok(Err(e))) => Err(build_error_response(e)),
+        Ok(Ok(Err(e))) => build_failure_response(e, fallback),
         Ok(Err(join_err)) => {
             log::error!("Blocking task error: {}", join_err);
-            Err(build_error_response(PdfServiceError::Internal(
-                join_err.to_string(),
-            )))
+            build_failure_response(PdfServiceError::Internal(join_err.to_string()), fallback)
         }
         Err(_timeout) => {
             log::error!(
                 "PDF generation timed out after {} seconds",
                 DEFAULT_TIMEOUT_SECS
             );
-            Err(build_error_response(PdfServiceError::Timeout(format!(
-                "Operation timed out after {} seconds",
-                DEFAULT_TIMEOUT_SECS
-            ))))
+            cancel.cancel();
+            build_failure_response(
+                PdfServiceError::Timeout(format!(
+                    "Operation timed out after {} seconds",
+                    DEFAULT_TIMEOUT_SECS
+                )),
+                fallback,
+            )
         }
-    }
+    };
+    response.map(Either::Left)
 }
 
 /// Generate PDF from HTML content.
@@ -567,18 +1036,66 @@ pub async fn pdf_from_url(
 /// | `landscape` | bool | No | `false` | Use landscape orientation |
 /// | `download` | bool | No | `false` | Force download vs inline display |
 /// | `print_background` | bool | No | `true` | Include background graphics |
+/// | `paper_format` | string | No | - | Named paper size: `Letter`, `Legal`, `Tabloid`, `Ledger`, `A0`-`A6` |
+/// | `paper_width` | f64 | No | - | Paper width in inches (overrides `paper_format`) |
+/// | `paper_height` | f64 | No | - | Paper height in inches (overrides `paper_format`) |
+/// | `margin_top`, `margin_bottom`, `margin_left`, `margin_right` | f64 | No | - | Margins in inches |
+/// | `scale` | f64 | No | `1.0` | Rendering scale, 0.1-2.0 |
+/// | `page_ranges` | string | No | all pages | Pages to print, e.g. `1-5, 8` |
+/// | `display_header_footer` | bool | No | with a template | Print the header and footer templates |
+/// | `header_template` | string | No | - | HTML printed in the top margin (classes `pageNumber`, `totalPages`, `date`, `title`, `url`) |
+/// | `footer_template` | string | No | - | HTML printed in the bottom margin |
+/// | `emulate_media` | string | No | `print` | CSS media type: `print` or `screen` |
+/// | `color_scheme` | string | No | `light` | `prefers-color-scheme`: `light` or `dark` |
+/// | `generate_tagged_pdf` | bool | No | pool default | Write a tagged (accessible) PDF |
+/// | `generate_document_outline` | bool | No | pool default | Add bookmarks built from the headings |
+/// | `javascript_enabled` | bool | No | `true` | Run the page's scripts (`false` for untrusted content) |
+/// | `block_resources` | string[] | No | - | Resource types to block (`["image", "font"]`) |
+/// | `block_urls` | string[] | No | - | Domains or URL patterns to block (`["tracker.example"]`) |
+/// | `ignore_tls_errors` | bool | No | `false` | Accept invalid TLS certificates (logged as a warning) |
+/// | `offline` | bool | No | `false` | Render without any network access |
+/// | `bypass_cache` | bool | No | `false` | Ignore the browser's HTTP cache |
+/// | `fallback_pdf` | bool | No | `false` | On failure, return a placeholder PDF (HTTP 200) instead of a JSON error |
+/// | `thumbnail` | bool | No | `false` | Respond with an image of the first page instead of the PDF |
+/// | `thumbnail_width` | u32 | No | `256` | Thumbnail width in pixels (16-2048) |
+/// | `thumbnail_format` | string | No | `png` | Thumbnail format: `png`, `jpeg`, or `webp` |
+/// | `thumbnail_quality` | u32 | No | - | JPEG/WebP thumbnail quality (1-100) |
+/// | `watermark` | string | No | - | Text stamped on every page, e.g. `DRAFT` (JSON bodies take an object, see [`Watermark`](crate::service::Watermark)) |
+/// | `linearize` | bool | No | `false` | Linearize the PDF for fast web view (needs qpdf) |
+/// | `pdf_a` | bool | No | `false` | Convert the PDF toward PDF/A-2b (needs Ghostscript) |
+/// | `split_pages` | bool | No | `false` | Return a ZIP with one PDF per page (needs qpdf) |
+/// | `stream` | bool | No | `false` | Send the PDF in chunks as Chrome hands it over |
+/// | `wait_for_expression` | string | No | `window.isPageDone === true` | JavaScript readiness check |
+/// | `inject_css` | string | No | - | CSS added once the page is ready (e.g. to hide a cookie banner) |
+/// | `inject_js` | string | No | - | JavaScript run once the page is ready, before printing |
+/// | `trace` | bool | No | `false` | Save a DevTools performance trace (needs `BROWSER_TRACE_DIR`) |
+/// | `debug` | bool | No | `false` | Respond with a JSON render report instead of the PDF |
+/// | `priority` | string | No | `normal` | `high`, `normal`, or `low`; order in the checkout queue when the pool is saturated |
+///
+/// # Idempotency
+///
+/// Send an `Idempotency-Key` header to make retries safe: a repeat with the
+/// same key (and the same `X-API-Key`) within the pool's
+/// [`idempotency_window`](crate::BrowserPoolConfig::idempotency_window)
+/// returns the first PDF without rendering or billing it again. See
+/// [`service::render_idempotent`].
 ///
 /// # Response
 ///
-/// Same as [`pdf_from_url`].
+/// Same as [`pdf_from_url`], including cancellation with `X-Job-Id`.
 ///
 /// # Errors
 ///
 /// | Status | Code | Description |
 /// |--------|------|-------------|
 /// | 400 | `EMPTY_HTML` | HTML content is empty or whitespace |
+/// | 400 | `INVALID_REQUEST` | `Idempotency-Key` reused with a different body |
+/// | 409 | `IDEMPOTENCY_CONFLICT` | A request with the same `Idempotency-Key` is still rendering |
+/// | 409 | `CANCELLED` | Cancelled with `DELETE /jobs/<id>` |
+/// | 429 | `QUOTA_EXCEEDED` | The `X-API-Key` key used up its render quota |
 /// | 502 | `PDF_GENERATION_FAILED` | Failed to generate PDF |
 /// | 503 | `BROWSER_UNAVAILABLE` | No browsers available |
+/// | 507 | `DISK_FULL` | Temp volume nearly full |
 /// | 504 | `TIMEOUT` | Operation timed out |
 ///
 /// # Example Request
@@ -600,17 +1117,183 @@ pub async fn pdf_from_url(
 #[post("/pdf/html", data = "<body>")]
 pub async fn pdf_from_html(
     pool: &State<SharedPool>,
+    api_key: ApiKey,
+    idempotency_key: IdempotencyKey,
+    job_id: JobId,
+    request_id: RequestId,
     body: Json<PdfFromHtmlRequest>,
-) -> HandlerResult<PdfResponder> {
-    let request = body.into_inner();
+) -> HandlerResult<Either<PdfResponder, PdfStreamResponder>> {
+    let mut request = body.into_inner();
     let pool = Arc::clone(pool.inner());
+    let cancel = CancelHandle::new();
+    request.cancel = Some(cancel.clone());
+    let fallback = request.fallback_pdf().then(|| {
+        FallbackPdf::new(
+            request_id.0.as_deref(),
+            request.filename_or_default(),
+            request.is_download(),
+        )
+    });
 
     log::debug!("PDF from HTML request: {} bytes", request.html.len());
 
+    if request.stream() {
+        return stream_with_timeout(fallback, cancel, move || {
+            service::stream_metered(&pool, api_key.0.as_deref(), || {
+                service::stream_pdf_from_html(&pool, &request)
+            })
+        })
+        .await;
+    }
+
     // Run blocking PDF generation with timeout
     let result = tokio::time::timeout(
         Duration::from_secs(DEFAULT_TIMEOUT_SECS),
-        tokio::task::spawn_blocking(move || service::generate_pdf_from_html(&pool, &request)),
+        tokio::task::spawn_blocking({
+            let cancel = cancel.clone();
+            move || {
+                let api_key = api_key.0.as_deref();
+                service::render_cancellable(&pool, api_key, job_id.0.as_deref(), &cancel, || {
+                    service::render_idempotent(
+                        &pool,
+                        api_key,
+                        idempotency_key.0.as_deref(),
+                        &request,
+                        || {
+                            service::render_metered(&pool, api_key, || {
+                                service::render_cached(&pool, &request, || {
+                                    service::render_coalesced(&pool, &request, || {
+                                        service::generate_pdf_from_html(&pool, &request)
+                                    })
+                                })
+                            })
+                        },
+                    )
+                })
+            }
+        }),
+    )
+    .await;
+
+    let response = match result {
+        Ok(Ok(Ok(response))) => Ok(build_pdf_response(response)),
+        Ok(Ok(Err(e))) => build_failure_response(e, fallback),
+        Ok(Err(join_err)) => {
+            log::error!("Blocking task error: {}", join_err);
+            build_failure_response(PdfServiceError::Internal(join_err.to_string()), fallback)
+        }
+        Err(_timeout) => {
+            log::error!("PDF generation timed out");
+            cancel.cancel();
+            build_failure_response(
+                PdfServiceError::Timeout(format!(
+                    "Operation timed out after {} seconds",
+                    DEFAULT_TIMEOUT_SECS
+                )),
+                fallback,
+            )
+        }
+    };
+    response.map(Either::Left)
+}
+
+/// Generate PDF from HTML uploaded together with its assets.
+///
+/// Like [`pdf_from_html`], but the document's images, stylesheets, and
+/// fonts are uploaded alongside it and relative URLs resolve against them,
+/// so nothing has to be inlined as a data URL. See
+/// [`PdfFromAssetsRequest::from_multipart`] and
+/// [`service::generate_pdf_from_assets`].
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /pdf/html/assets
+/// Content-Type: multipart/form-data
+/// ```
+///
+/// # Request Body
+///
+/// | Part | Description |
+/// |------|-------------|
+/// | File `index.html` | The document (required) |
+/// | Other files | Assets, each with its path relative to `index.html` as filename |
+/// | `options` | Optional JSON with the other fields of [`PdfFromHtmlRequest`] |
+///
+/// ```text
+/// curl -X POST http://localhost:8000/pdf/html/assets \
+///     -F "file=@index.html" \
+///     -F "file=@logo.png;filename=images/logo.png" \
+///     -F 'options={"filename": "report.pdf"}'
+/// ```
+///
+/// Uploads are limited by Rocket's `data-form` limit (2 MiB by default).
+///
+/// # Response
+///
+/// The PDF, as for [`pdf_from_html`]. Usage and `X-Job-Id` work like
+/// [`pdf_from_url`].
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 400 | `INVALID_REQUEST` | Not multipart, too large, no `index.html`, an invalid path, or invalid options |
+/// | 400 | `EMPTY_HTML` | `index.html` is empty |
+/// | 503 | `BROWSER_UNAVAILABLE` | No browser available |
+/// | 504 | `TIMEOUT` | Operation timed out |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![pdf_from_html_assets])
+/// ```
+#[post("/pdf/html/assets", data = "<body>")]
+pub async fn pdf_from_html_assets(
+    pool: &State<SharedPool>,
+    api_key: ApiKey,
+    job_id: JobId,
+    content_type: Option<&ContentType>,
+    limits: &Limits,
+    body: Data<'_>,
+) -> HandlerResult<PdfResponder> {
+    let limit = limits.get("data-form").unwrap_or(2.mebibytes());
+    let body = match body.open(limit).into_bytes().await {
+        Ok(body) if body.is_complete() => Bytes::from(body.into_inner()),
+        Ok(_) => {
+            return Err(build_error_response(PdfServiceError::InvalidRequest(
+                format!("upload exceeds the data-form limit of {}", limit),
+            )));
+        }
+        Err(e) => {
+            return Err(build_error_response(PdfServiceError::InvalidRequest(
+                format!("cannot read upload: {}", e),
+            )));
+        }
+    };
+    let content_type = content_type.map(ToString::to_string).unwrap_or_default();
+    let pool = Arc::clone(pool.inner());
+    let cancel = CancelHandle::new();
+
+    log::debug!("PDF from HTML with assets request: {} bytes", body.len());
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        tokio::task::spawn_blocking({
+            let cancel = cancel.clone();
+            move || {
+                let mut request = PdfFromAssetsRequest::from_multipart(&content_type, body)?;
+                request.document.cancel = Some(cancel.clone());
+                let api_key = api_key.0.as_deref();
+                service::render_cancellable(&pool, api_key, job_id.0.as_deref(), &cancel, || {
+                    service::render_metered(&pool, api_key, || {
+                        service::generate_pdf_from_assets(&pool, &request)
+                    })
+                })
+            }
+        }),
     )
     .await;
 
@@ -625,6 +1308,7 @@ pub async fn pdf_from_html(
         }
         Err(_timeout) => {
             log::error!("PDF generation timed out");
+            cancel.cancel();
             Err(build_error_response(PdfServiceError::Timeout(format!(
                 "Operation timed out after {} seconds",
                 DEFAULT_TIMEOUT_SECS
@@ -633,6 +1317,295 @@ pub async fn pdf_from_html(
     }
 }
 
+/// Render several documents and merge them into one PDF.
+///
+/// Each document is rendered like a request to [`pdf_from_url`] or
+/// [`pdf_from_html`], in parallel across the pool, and their pages are
+/// joined in order with qpdf. See [`service::merge_pdfs`].
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /pdf/merge
+/// Content-Type: application/json
+/// ```
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "documents": [
+///         {"html": "<h1>Annual Report</h1>"},
+///         {"url": "https://example.com/report", "landscape": true}
+///     ],
+///     "filename": "annual-report.pdf"
+/// }
+/// ```
+///
+/// # Response
+///
+/// The merged PDF. The merge counts as one render toward the `X-API-Key`
+/// quota, and can be cancelled with `X-Job-Id` like [`pdf_from_url`].
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 400 | `INVALID_REQUEST` | No documents, or more than 32 |
+/// | 4xx/5xx | Any | The first failing document's error |
+/// | 502 | `PDF_GENERATION_FAILED` | qpdf is missing or could not merge the PDFs |
+/// | 504 | `TIMEOUT` | Operation timed out |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![pdf_merge])
+/// ```
+#[post("/pdf/merge", data = "<body>")]
+pub async fn pdf_merge(
+    pool: &State<SharedPool>,
+    api_key: ApiKey,
+    job_id: JobId,
+    body: Json<PdfMergeRequest>,
+) -> HandlerResult<PdfResponder> {
+    let mut request = body.into_inner();
+    let pool = Arc::clone(pool.inner());
+    let cancel = CancelHandle::new();
+    request.set_cancel(&cancel);
+
+    log::debug!("PDF merge request: {} documents", request.documents.len());
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        tokio::task::spawn_blocking({
+            let cancel = cancel.clone();
+            move || {
+                let api_key = api_key.0.as_deref();
+                service::render_cancellable(&pool, api_key, job_id.0.as_deref(), &cancel, || {
+                    service::render_metered(&pool, api_key, || service::merge_pdfs(&pool, &request))
+                })
+            }
+        }),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(Ok(response))) => Ok(build_pdf_response(response)),
+        Ok(Ok(Err(e))) => Err(build_error_response(e)),
+        Ok(Err(join_err)) => {
+            log::error!("Blocking task error: {}", join_err);
+            Err(build_error_response(PdfServiceError::Internal(
+                join_err.to_string(),
+            )))
+        }
+        Err(_timeout) => {
+            log::error!("PDF merge timed out");
+            cancel.cancel();
+            Err(build_error_response(PdfServiceError::Timeout(format!(
+                "Operation timed out after {} seconds",
+                DEFAULT_TIMEOUT_SECS
+            ))))
+        }
+    }
+}
+
+/// Start a render in the background.
+///
+/// Takes the same JSON body as [`pdf_from_html`], or the fields of
+/// [`pdf_from_url`]'s query as JSON (told apart by `url` or `html`), and
+/// returns at once. Poll [`job_status`] and download from [`job_result`],
+/// or add a `callback_url` to have the result POSTed there. An `X-Job-Id`
+/// header picks the job ID, otherwise one is generated. See
+/// [`service::submit_job`].
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /jobs
+/// Content-Type: application/json
+/// ```
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "url": "https://example.com/report",
+///     "filename": "report.pdf",
+///     "callback_url": "https://app.example.com/hooks/pdf"
+/// }
+/// ```
+///
+/// # Response (202 Accepted)
+///
+/// ```json
+/// {
+///     "id": "3f9c2a1b-...",
+///     "status": "running"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 400 | `INVALID_REQUEST` | Jobs disabled, bad job ID, or ID already running |
+/// | 400 | `INVALID_URL` | `callback_url` is not an allowed http(s) URL |
+/// | 503 | `JOB_QUEUE_FULL` | Too many jobs pending |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![submit_job])
+/// ```
+#[post("/jobs", data = "<body>")]
+pub async fn submit_job(
+    pool: &State<SharedPool>,
+    api_key: ApiKey,
+    job_id: JobId,
+    body: Json<JobRequest>,
+) -> HandlerResult<Accepted<Json<JobResponse>>> {
+    service::submit_job(
+        pool.inner(),
+        api_key.0.as_deref(),
+        job_id.0.as_deref(),
+        body.into_inner(),
+    )
+    .map(|job| Accepted(Json(job)))
+    .map_err(build_error_response)
+}
+
+/// Get the status of a job.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /jobs/<id>
+/// ```
+///
+/// # Response (200 OK)
+///
+/// ```json
+/// {
+///     "id": "report-42",
+///     "status": "succeeded",
+///     "expires_in_seconds": 3512
+/// }
+/// ```
+///
+/// `status` is `running`, `succeeded`, `failed`, or `cancelled`; the last
+/// two include the job's `error`.
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 404 | `JOB_NOT_FOUND` | No such job for the API key, or its result expired |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![job_status])
+/// ```
+#[get("/jobs/<id>")]
+pub fn job_status(
+    pool: &State<SharedPool>,
+    api_key: ApiKey,
+    id: &str,
+) -> HandlerResult<Json<JobResponse>> {
+    service::job_status(pool.inner(), api_key.0.as_deref(), id)
+        .map(Json)
+        .map_err(build_error_response)
+}
+
+/// Download the result of a finished job.
+///
+/// Responds like [`pdf_from_url`]: the PDF on success, or the job's JSON
+/// error if its render failed. Results can be downloaded repeatedly until
+/// they expire, in pieces with `Range` requests (see [`RangedPdfResponder`]).
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /jobs/<id>/result
+/// ```
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 404 | `JOB_NOT_FOUND` | No such job for the API key, or its result expired |
+/// | 409 | `JOB_PENDING` | The job is still running |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![job_result])
+/// ```
+#[get("/jobs/<id>/result")]
+pub fn job_result(
+    pool: &State<SharedPool>,
+    api_key: ApiKey,
+    id: &str,
+) -> HandlerResult<RangedPdfResponder> {
+    service::job_result(pool.inner(), api_key.0.as_deref(), id)
+        .map(|response| RangedPdfResponder(build_pdf_response(response)))
+        .map_err(build_error_response)
+}
+
+/// Cancel a running job, or delete a finished job's result.
+///
+/// Stops the render sent with an `X-Job-Id: <id>` header, or submitted to
+/// [`submit_job`], under the same `X-API-Key`: it leaves the checkout queue
+/// or has its tab closed, and fails with `CANCELLED`. A finished job's kept
+/// result is deleted instead. See [`service::cancel_job`].
+///
+/// # Endpoint
+///
+/// ```text
+/// DELETE /jobs/<id>
+/// ```
+///
+/// # Response (202 Accepted)
+///
+/// ```json
+/// {
+///     "id": "report-42",
+///     "status": "cancelled"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// | Status | Code | Description |
+/// |--------|------|-------------|
+/// | 404 | `JOB_NOT_FOUND` | No job with this ID for the API key |
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![cancel_job])
+/// ```
+#[delete("/jobs/<id>")]
+pub fn cancel_job(
+    pool: &State<SharedPool>,
+    api_key: ApiKey,
+    id: &str,
+) -> HandlerResult<Accepted<Json<JobResponse>>> {
+    service::cancel_job(pool.inner(), api_key.0.as_deref(), id)
+        .map(|job| Accepted(Json(job)))
+        .map_err(build_error_response)
+}
+
 /// Get browser pool statistics.
 ///
 /// Returns real-time metrics about the browser pool including available
@@ -687,6 +1660,165 @@ pub fn pool_stats(pool: &State<SharedPool>) -> HandlerResult<Json<PoolStatsRespo
         .map_err(build_error_response)
 }
 
+/// Get a detailed diagnostic snapshot of the browser pool.
+///
+/// Reports each browser's age, uses, memory, open tabs, and last health
+/// check, plus waiting requests, replacement tasks in flight, and
+/// keep-alive timing. Attach it to support requests about slow or failing
+/// renders.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /pool/debug
+/// ```
+///
+/// # Response (200 OK)
+///
+/// A JSON [`PoolDebugResponse`](crate::service::PoolDebugResponse):
+///
+/// ```json
+/// {
+///     "available": 1,
+///     "active": 2,
+///     "max_pool_size": 5,
+///     "browsers": [
+///         {
+///             "id": 3,
+///             "age_seconds": 812.4,
+///             "uses": 57,
+///             "checked_out": true,
+///             "open_tabs": 2,
+///             "memory_bytes": 183500800,
+///             "last_health_check": { "seconds_ago": 4.1, "healthy": true, "error": null }
+///         }
+///     ],
+///     "queued_waiters": 0,
+///     "replacement_tasks": 1,
+///     "keep_alive": {
+///         "interval_seconds": 15.0,
+///         "cycles": 54,
+///         "last_cycle_seconds_ago": 4.2,
+///         "last_cycle_duration_seconds": 0.31,
+///         "next_cycle_in_seconds": 10.8
+///     },
+///     "shutting_down": false
+/// }
+/// ```
+///
+/// The response includes internal details (error messages, memory), so
+/// expose it only to operators.
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![pool_debug])
+/// ```
+#[get("/pool/debug")]
+pub fn pool_debug(pool: &State<SharedPool>) -> HandlerResult<Json<PoolDebugResponse>> {
+    service::get_pool_debug(pool.inner())
+        .map(Json)
+        .map_err(build_error_response)
+}
+
+/// Get per-browser statistics and lifetime counters of the pool.
+///
+/// Reports each browser's age, uses, time since its last ping, consecutive
+/// ping failures, memory, and idle/busy state, plus how many browsers have
+/// been created, retired, and removed as failed since startup.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /pool/stats/detailed
+/// ```
+///
+/// # Response (200 OK)
+///
+/// A JSON [`PoolDetailedStatsResponse`](crate::service::PoolDetailedStatsResponse):
+///
+/// ```json
+/// {
+///     "available": 1,
+///     "active": 2,
+///     "total": 3,
+///     "total_created": 14,
+///     "total_retired": 11,
+///     "total_failed": 1,
+///     "browsers": [
+///         {
+///             "id": 12,
+///             "age_seconds": 812.4,
+///             "uses": 57,
+///             "last_ping_seconds_ago": 4.1,
+///             "ping_failures": 0,
+///             "memory_bytes": 183500800,
+///             "state": "busy"
+///         }
+///     ]
+/// }
+/// ```
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![pool_stats_detailed])
+/// ```
+#[get("/pool/stats/detailed")]
+pub fn pool_stats_detailed(
+    pool: &State<SharedPool>,
+) -> HandlerResult<Json<PoolDetailedStatsResponse>> {
+    service::get_pool_detailed_stats(pool.inner())
+        .map(Json)
+        .map_err(build_error_response)
+}
+
+/// Get the caller's API key usage.
+///
+/// Reports renders, PDF bytes, and browser time for the key in the
+/// `X-API-Key` header (or the shared anonymous account without one), plus
+/// the configured quotas. Other keys' usage is never returned.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /usage
+/// X-API-Key: team-a
+/// ```
+///
+/// # Response (200 OK)
+///
+/// ```json
+/// {
+///     "today": { "renders": 12, "bytes": 580112, "browser_seconds": 18.4 },
+///     "this_month": { "renders": 240, "bytes": 11902234, "browser_seconds": 351.0 },
+///     "total": { "renders": 240, "bytes": 11902234, "browser_seconds": 351.0 },
+///     "daily_quota": 500,
+///     "monthly_quota": null
+/// }
+/// ```
+///
+/// # Usage in App
+///
+/// ```rust,ignore
+/// rocket::build()
+///     .manage(pool)
+///     .mount("/", routes![usage_stats])
+/// ```
+#[get("/usage")]
+pub fn usage_stats(
+    pool: &State<SharedPool>,
+    api_key: ApiKey,
+) -> HandlerResult<Json<UsageResponse>> {
+    service::get_usage(pool.inner(), api_key.0.as_deref())
+        .map(Json)
+        .map_err(build_error_response)
+}
+
 /// Health check endpoint.
 ///
 /// Simple endpoint that returns 200 OK if the service is running.
@@ -769,10 +1901,13 @@ pub fn health_check() -> Json<HealthResponse> {
 ///
 /// # Readiness Criteria
 ///
-/// The service is "ready" if either:
+/// By default the service is "ready" if either:
 /// - There are idle browsers available (`available > 0`), OR
 /// - There is capacity to create new browsers (`active < max_pool_size`)
 ///
+/// The `ready_*` fields of [`BrowserPoolConfig`](crate::BrowserPoolConfig)
+/// tighten this; see [`service::is_pool_ready`].
+///
 /// # Use Cases
 ///
 /// - Kubernetes readiness probe
@@ -810,6 +1945,7 @@ pub fn readiness_check(
             body: ErrorResponse {
                 error: "No available capacity".to_string(),
                 code: "NOT_READY".to_string(),
+                message: String::new(),
             },
         }),
         Err(e) => Err(build_error_response(e)),
@@ -831,7 +1967,16 @@ pub fn readiness_check(
 /// |--------|------|---------|-------------|
 /// | GET | `/pdf` | [`pdf_from_url`] | Convert URL to PDF |
 /// | POST | `/pdf/html` | [`pdf_from_html`] | Convert HTML to PDF |
+/// | POST | `/pdf/html/assets` | [`pdf_from_html_assets`] | Convert HTML uploaded with its assets to PDF |
+/// | POST | `/pdf/merge` | [`pdf_merge`] | Render several documents into one PDF |
+/// | POST | `/jobs` | [`submit_job`] | Start a background render |
+/// | GET | `/jobs/<id>` | [`job_status`] | Status of a job |
+/// | DELETE | `/jobs/<id>` | [`cancel_job`] | Cancel a job or delete its result |
+/// | GET | `/jobs/<id>/result` | [`job_result`] | Download a finished job's PDF |
 /// | GET | `/pool/stats` | [`pool_stats`] | Pool statistics |
+/// | GET | `/pool/debug` | [`pool_debug`] | Pool diagnostics snapshot |
+/// | GET | `/pool/stats/detailed` | [`pool_stats_detailed`] | Per-browser statistics and lifetime counters |
+/// | GET | `/usage` | [`usage_stats`] | Usage of the caller's API key |
 /// | GET | `/health` | [`health_check`] | Health check |
 /// | GET | `/ready` | [`readiness_check`] | Readiness check |
 ///
@@ -906,14 +2051,32 @@ pub fn configure_routes(rocket: Rocket<Build>) -> Rocket<Build> {
 ///
 /// - `GET /pdf` - [`pdf_from_url`]
 /// - `POST /pdf/html` - [`pdf_from_html`]
+/// - `POST /pdf/html/assets` - [`pdf_from_html_assets`]
+/// - `POST /pdf/merge` - [`pdf_merge`]
+/// - `POST /jobs` - [`submit_job`]
+/// - `GET /jobs/<id>` - [`job_status`]
+/// - `DELETE /jobs/<id>` - [`cancel_job`]
+/// - `GET /jobs/<id>/result` - [`job_result`]
 /// - `GET /pool/stats` - [`pool_stats`]
+/// - `GET /pool/debug` - [`pool_debug`]
+/// - `GET /pool/stats/detailed` - [`pool_stats_detailed`]
+/// - `GET /usage` - [`usage_stats`]
 /// - `GET /health` - [`health_check`]
 /// - `GET /ready` - [`readiness_check`]
 pub fn routes() -> Vec<rocket::Route> {
     routes![
         pdf_from_url,
         pdf_from_html,
+        pdf_from_html_assets,
+        pdf_merge,
+        submit_job,
+        job_status,
+        cancel_job,
+        job_result,
         pool_stats,
+        pool_debug,
+        pool_stats_detailed,
+        usage_stats,
         health_check,
         readiness_check
     ]
@@ -931,10 +2094,68 @@ fn build_pdf_response(response: PdfResponse) -> PdfResponder {
         response.filename
     );
 
+    let body = response.body();
+    let content_type = (body.content_type != "application/pdf").then_some(body.content_type);
     PdfResponder {
-        data: response.data,
-        filename: response.filename,
+        data: body.data,
+        filename: body.filename.unwrap_or_default(),
         force_download: response.force_download,
+        content_type,
+        page_info: response.page_info(),
+        trace_id: response.trace_id,
+    }
+}
+
+/// Build the result for a failed render: the fallback PDF when one was
+/// requested, otherwise the error responder.
+/// Open a streamed render on the blocking pool, giving up after
+/// [`DEFAULT_TIMEOUT_SECS`]; the timeout covers rendering only.
+async fn stream_with_timeout<F>(
+    fallback: Option<FallbackPdf>,
+    cancel: CancelHandle,
+    open: F,
+) -> HandlerResult<Either<PdfResponder, PdfStreamResponder>>
+where
+    F: FnOnce() -> Result<PdfStream, PdfServiceError> + Send + 'static,
+{
+    let result = tokio::time::timeout(
+        Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        tokio::task::spawn_blocking(open),
+    )
+    .await;
+
+    let error = match result {
+        Ok(Ok(Ok(stream))) => {
+            log::info!("Streaming PDF, filename={}", stream.filename());
+            return Ok(Either::Right(PdfStreamResponder(stream)));
+        }
+        Ok(Ok(Err(e))) => e,
+        Ok(Err(join_err)) => {
+            log::error!("Blocking task error: {}", join_err);
+            PdfServiceError::Internal(join_err.to_string())
+        }
+        Err(_timeout) => {
+            log::error!(
+                "PDF generation timed out after {} seconds",
+                DEFAULT_TIMEOUT_SECS
+            );
+            cancel.cancel();
+            PdfServiceError::Timeout(format!(
+                "Operation timed out after {} seconds",
+                DEFAULT_TIMEOUT_SECS
+            ))
+        }
+    };
+    build_failure_response(error, fallback).map(Either::Left)
+}
+
+fn build_failure_response(
+    error: PdfServiceError,
+    fallback: Option<FallbackPdf>,
+) -> HandlerResult<PdfResponder> {
+    match fallback {
+        Some(fallback) => Ok(build_pdf_response(fallback.render(&error))),
+        None => Err(build_error_response(error)),
     }
 }
 
@@ -942,9 +2163,13 @@ fn build_pdf_response(response: PdfResponse) -> PdfResponder {
 fn build_error_response(error: PdfServiceError) -> ErrorResponder {
     let status = match error.status_code() {
         400 => Status::BadRequest,
+        404 => Status::NotFound,
+        409 => Status::Conflict,
+        429 => Status::TooManyRequests,
         502 => Status::BadGateway,
         503 => Status::ServiceUnavailable,
         504 => Status::GatewayTimeout,
+        507 => Status::InsufficientStorage,
         _ => Status::InternalServerError,
     };
 
@@ -1078,6 +2303,7 @@ pub fn create_pool_data_from_arc(pool: Arc<Mutex<BrowserPool>>) -> SharedBrowser
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::service::{ColorScheme, ImageFormat, MediaType, PaperFormat};
 
     #[test]
     fn test_type_alias_compiles() {
@@ -1092,6 +2318,18 @@ mod tests {
                 PdfServiceError::InvalidUrl("".to_string()),
                 Status::BadRequest,
             ),
+            (
+                PdfServiceError::JobNotFound("".to_string()),
+                Status::NotFound,
+            ),
+            (
+                PdfServiceError::IdempotencyConflict("".to_string()),
+                Status::Conflict,
+            ),
+            (
+                PdfServiceError::QuotaExceeded("".to_string()),
+                Status::TooManyRequests,
+            ),
             (
                 PdfServiceError::NavigationFailed("".to_string()),
                 Status::BadGateway,
@@ -1125,6 +2363,55 @@ mod tests {
             landscape: Some(true),
             download: Some(false),
             print_background: Some(true),
+            paper_format: Some("A4".to_string()),
+            paper_width: None,
+            paper_height: None,
+            margin_top: Some(0.5),
+            margin_bottom: None,
+            margin_left: None,
+            margin_right: None,
+            scale: Some(0.8),
+            page_ranges: Some("1-2".to_string()),
+            display_header_footer: None,
+            header_template: None,
+            footer_template: Some("<span class=\"pageNumber\"></span>".to_string()),
+            emulate_media: Some("screen".to_string()),
+            color_scheme: Some("dark".to_string()),
+            generate_tagged_pdf: None,
+            generate_document_outline: Some(true),
+            javascript_enabled: Some(false),
+            block_resources: Some("image, font".to_string()),
+            block_urls: Some("tracker.example".to_string()),
+            ignore_tls_errors: Some(true),
+            offline: Some(true),
+            bypass_cache: Some(true),
+            geolocation: Some("40.7128,-74.006".parse().unwrap()),
+            timezone: Some("America/New_York".to_string()),
+            viewport_width: Some(390),
+            viewport_height: None,
+            device_scale_factor: Some(3.0),
+            mobile: Some(true),
+            user_agent: None,
+            method: Some("POST".to_string()),
+            post_data: Some("id=42".to_string()),
+            content_type: None,
+            fallback_pdf: Some(true),
+            thumbnail: Some(true),
+            thumbnail_width: Some(320),
+            thumbnail_format: Some("webp".to_string()),
+            thumbnail_quality: Some(70),
+            outputs: Some("pdf, html".to_string()),
+            watermark: Some("DRAFT".to_string()),
+            linearize: Some(true),
+            pdf_a: Some(false),
+            split_pages: Some(true),
+            stream: Some(true),
+            wait_for_expression: Some("window.__APP_READY__".to_string()),
+            inject_css: Some("#banner { display: none }".to_string()),
+            inject_js: None,
+            trace: Some(true),
+            debug: Some(true),
+            priority: Some("high".to_string()),
         };
 
         let request: PdfFromUrlRequest = query.into();
@@ -1135,6 +2422,70 @@ mod tests {
         assert_eq!(request.landscape, Some(true));
         assert_eq!(request.download, Some(false));
         assert_eq!(request.print_background, Some(true));
+        assert_eq!(request.paper_format().unwrap(), Some(PaperFormat::A4));
+        assert_eq!(request.margin_top, Some(0.5));
+        assert_eq!(request.scale, Some(0.8));
+        assert_eq!(request.page_ranges.as_deref(), Some("1-2"));
+        assert_eq!(
+            request.footer_template.as_deref(),
+            Some("<span class=\"pageNumber\"></span>")
+        );
+        assert_eq!(request.emulate_media().unwrap(), Some(MediaType::Screen));
+        assert_eq!(request.color_scheme().unwrap(), Some(ColorScheme::Dark));
+        assert_eq!(request.javascript_enabled, Some(false));
+        assert_eq!(
+            request.block_resources,
+            Some(vec!["image".to_string(), "font".to_string()])
+        );
+        assert_eq!(
+            request.block_urls,
+            Some(vec!["tracker.example".to_string()])
+        );
+        assert_eq!(request.ignore_tls_errors, Some(true));
+        assert!(request.offline());
+        assert!(request.bypass_cache());
+        assert_eq!(request.geolocation().unwrap().unwrap().lon, -74.006);
+        assert_eq!(request.timezone.as_deref(), Some("America/New_York"));
+        assert_eq!(request.viewport_width, Some(390));
+        assert_eq!(request.device_scale_factor, Some(3.0));
+        assert_eq!(request.mobile, Some(true));
+        assert_eq!(request.method.as_deref(), Some("POST"));
+        assert_eq!(request.post_data.as_deref(), Some("id=42"));
+        assert_eq!(request.fallback_pdf, Some(true));
+        assert_eq!(request.thumbnail_width(), Some(320));
+        assert_eq!(request.thumbnail_format().unwrap(), ImageFormat::Webp);
+        assert_eq!(request.thumbnail_quality, Some(70));
+        let outputs = request.outputs().unwrap().unwrap();
+        assert!(outputs.pdf && outputs.html);
+        assert_eq!(outputs.image, None);
+        assert_eq!(request.watermark, Some(Watermark::text("DRAFT")));
+        assert!(request.linearize());
+        assert!(!request.pdf_a());
+        assert_eq!(request.generate_document_outline, Some(true));
+        assert!(request.split_pages());
+        assert!(request.stream());
+        assert_eq!(request.wait_for_expression(), "window.__APP_READY__");
+        assert_eq!(
+            request.inject_css.as_deref(),
+            Some("#banner { display: none }")
+        );
+        assert_eq!(request.inject_js, None);
+        assert!(request.trace());
+        assert!(request.debug());
+        assert_eq!(request.priority().unwrap(), crate::Priority::High);
+    }
+
+    #[test]
+    fn test_failure_response_fallback() {
+        let error = || PdfServiceError::Timeout("slow".to_string());
+        assert!(build_failure_response(error(), None).is_err());
+
+        let fallback = FallbackPdf::new(Some("req-1"), "report.pdf".to_string(), false);
+        let responder = build_failure_response(error(), Some(fallback))
+            .ok()
+            .unwrap();
+        assert_eq!(responder.filename, "report.pdf");
+        assert!(responder.data.starts_with(b"%PDF-"));
     }
 
     #[tokio::test]
@@ -1158,6 +2509,6 @@ mod tests {
     #[test]
     fn test_routes_returns_all_endpoints() {
         let all_routes = routes();
-        assert_eq!(all_routes.len(), 5);
+        assert_eq!(all_routes.len(), 14);
     }
 }
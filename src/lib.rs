@@ -122,7 +122,43 @@
 //! | `BROWSER_WARMUP_TIMEOUT_SECONDS` | u64 | 60 | Warmup timeout |
 //! | `BROWSER_PING_INTERVAL_SECONDS` | u64 | 15 | Health check interval |
 //! | `BROWSER_MAX_PING_FAILURES` | u32 | 3 | Failures before removal |
+//! | `BROWSER_WARMUP_CONCURRENCY` | usize | 1 | Parallel warmup launches |
+//! | `BROWSER_TTL_JITTER_SECONDS` | u64 | 0 | Max random TTL reduction |
+//! | `BROWSER_REUSE_TABS` | bool | false | Reuse one tab per browser |
+//! | `BROWSER_TABS_PER_BROWSER` | usize | 1 | Concurrent renders per browser |
+//! | `BROWSER_DEEP_HEALTH_CHECK` | bool | false | Full tab check on checkout |
+//! | `BROWSER_PREFETCH_URLS` | String | empty | Comma-separated URLs to prefetch |
+//! | `BROWSER_PREFETCH_INTERVAL_SECONDS` | u64 | 600 | Prefetch interval |
+//! | `BROWSER_BLOCK_URLS` | String | empty | Comma-separated domains/URL patterns to block |
+//! | `BROWSER_MAX_REDIRECTS` | u32 | 20 | Redirects allowed per URL navigation |
+//! | `BROWSER_ALLOW_CROSS_ORIGIN_REDIRECTS` | bool | true | Allow redirects to other origins |
+//! | `BROWSER_ALLOWED_FILE_DIRS` | String | empty | Directories `file://` URLs may be rendered from, separated like `PATH` |
+//! | `BROWSER_DENY_PRIVATE_NETWORKS` | bool | false | Reject URLs on private networks and `localhost` |
+//! | `BROWSER_ALLOWED_HOSTS` | String | empty | Comma-separated hosts URL renders are limited to (with subdomains) |
+//! | `BROWSER_DENIED_HOSTS` | String | empty | Comma-separated hosts URL renders may never reach (with subdomains) |
+//! | `BROWSER_TRACE_DIR` | String | none | Directory for `trace` request performance traces |
+//! | `BROWSER_DAILY_RENDER_QUOTA` | u64 | 0 | Renders per API key per UTC day (0 = unlimited) |
+//! | `BROWSER_MONTHLY_RENDER_QUOTA` | u64 | 0 | Renders per API key per UTC month (0 = unlimited) |
+//! | `BROWSER_IDEMPOTENCY_WINDOW_SECONDS` | u64 | 300 | `Idempotency-Key` replay window (0 = disabled) |
+//! | `BROWSER_COALESCE_REQUESTS` | bool | false | Share renders among identical concurrent requests |
+//! | `BROWSER_CHECKOUT_QUEUE_TIMEOUT_SECONDS` | u64 | 0 | How long checkouts wait for a browser when the pool is saturated (0 = never wait) |
+//! | `BROWSER_JOB_RETENTION_SECONDS` | u64 | 3600 | How long async job results are kept (0 = async jobs disabled) |
+//! | `BROWSER_READY_MIN_AVAILABLE` | usize | 1 | Browsers a checkout must be able to get for the pool to be ready |
+//! | `BROWSER_READY_MAX_QUEUE_DEPTH` | usize | none | Queued checkouts above which the pool is not ready |
+//! | `BROWSER_READY_REQUIRE_WARMUP` | bool | false | Pool is not ready until warmup has finished |
+//! | `BROWSER_RECYCLE_AFTER_FAILED_CYCLES` | u32 | 0 | Keep-alive cycles with every browser failing before the pool recycles itself (0 = never) |
+//! | `BROWSER_MIN_FREE_DISK_MB` | u64 | 0 | Free space the temp volume needs for checkouts (0 = not checked) |
 //! | `CHROME_PATH` | String | auto | Custom Chrome binary path |
+//! | `CHROME_NO_SANDBOX` | bool | false | Disable Chrome's sandbox (logs a warning) |
+//! | `CHROME_CA_BUNDLE` | String | none | PEM file of extra CAs to trust |
+//! | `CHROME_FONT_DIR` | String | none | Directory of extra fonts |
+//! | `CHROME_STEALTH` | bool | false | Hide automation signals from rendered pages |
+//! | `CHROME_USER_AGENT` | String | browser's own | User agent sent in stealth mode |
+//! | `CHROME_LANGUAGES` | String | en-US,en | Languages sent in stealth mode |
+//! | `QPDF_PATH` | String | `qpdf` | qpdf binary for `linearize` requests |
+//! | `GHOSTSCRIPT_PATH` | String | `gs` | Ghostscript binary for `pdf_a` requests |
+//! | `PDF_SIGN_CERT` | String | none | PEM certificate chain that signs every PDF (`pdf-signing`) |
+//! | `PDF_SIGN_KEY` | String | none | PEM private key of `PDF_SIGN_CERT` (`pdf-signing`) |
 //!
 //! ## Feature Flags
 //!
@@ -132,6 +168,12 @@
 //! | `actix-integration` | Actix-web framework integration |
 //! | `rocket-integration` | Rocket framework integration |
 //! | `axum-integration` | Axum framework integration |
+//! | `cli` | `html2pdf` command-line binary |
+//! | `cloudwatch` | Publish metrics in CloudWatch Embedded Metric Format |
+//! | `redis-cache` | Share the response cache across instances through Redis (with an integration or `cli`) |
+//! | `redis-jobs` | Keep async jobs in Redis, shared across instances and restarts (with an integration or `cli`) |
+//! | `pdf-signing` | Digitally sign every rendered PDF with `PdfSigner` (with an integration or `cli`) |
+//! | `firefox` | Pool Firefox over WebDriver with `FirefoxBrowserFactory` |
 //! | `test-utils` | Enable mock factory for testing |
 //!
 //! ## Web Framework Integration
@@ -208,6 +250,18 @@
 //! }
 //! ```
 //!
+//! ## Diagnostics
+//!
+//! [`diagnostics::check_environment()`] verifies that Chrome can be found,
+//! launched, and can render a test page, and reports missing fonts and
+//! container constraints. The `html2pdf doctor` command prints the same report.
+//!
+//! ## CloudWatch Metrics
+//!
+//! With the `cloudwatch` feature, [`cloudwatch::CloudWatchPublisher`]
+//! writes pool and render metrics to stdout in CloudWatch Embedded Metric
+//! Format, for AWS deployments without a Prometheus stack.
+//!
 //! ## Testing
 //!
 //! For testing without Chrome, enable the `test-utils` feature and use
@@ -231,14 +285,20 @@
 // Modules
 // ============================================================================
 
+pub mod cancel;
 pub mod config;
+pub mod diagnostics;
+pub mod disk;
 pub mod error;
 pub mod factory;
 pub mod handle;
 pub mod pool;
 pub mod prelude;
+pub mod queue;
 pub mod stats;
 pub mod traits;
+pub mod url_policy;
+pub mod usage;
 
 // Internal modules (not publicly exposed)
 pub(crate) mod tracked;
@@ -265,26 +325,92 @@ pub mod integrations;
 /// Core PDF generation service.
 ///
 /// Provides framework-agnostic types and functions for PDF generation.
-/// Used by the framework integrations.
+/// Used by the framework integrations and the `html2pdf` command-line tool.
 #[cfg(any(
     feature = "actix-integration",
     feature = "rocket-integration",
-    feature = "axum-integration"
+    feature = "axum-integration",
+    feature = "cli"
 ))]
 pub mod service;
 
+// Features that only add to the service layer, which the integrations and
+// the CLI enable; on their own they would build without their types
+#[cfg(all(
+    feature = "pdf-signing",
+    not(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))
+))]
+compile_error!(
+    "the `pdf-signing` feature needs the service layer: enable it together with \
+     `actix-integration`, `rocket-integration`, `axum-integration`, or `cli`"
+);
+#[cfg(all(
+    feature = "redis-cache",
+    not(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))
+))]
+compile_error!(
+    "the `redis-cache` feature needs the service layer: enable it together with \
+     `actix-integration`, `rocket-integration`, `axum-integration`, or `cli`"
+);
+#[cfg(all(
+    feature = "redis-jobs",
+    not(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))
+))]
+compile_error!(
+    "the `redis-jobs` feature needs the service layer: enable it together with \
+     `actix-integration`, `rocket-integration`, `axum-integration`, or `cli`"
+);
+
+/// CloudWatch metrics publisher.
+///
+/// Writes pool and render metrics in CloudWatch Embedded Metric Format.
+/// Requires the `cloudwatch` feature.
+#[cfg(feature = "cloudwatch")]
+pub mod cloudwatch;
+
 // ============================================================================
 // Re-exports (Public API)
 // ============================================================================
 
 // Core types
+pub use cancel::CancelHandle;
 pub use config::{BrowserPoolConfig, BrowserPoolConfigBuilder};
 pub use error::{BrowserPoolError, Result};
-pub use factory::{BrowserFactory, ChromeBrowserFactory, create_chrome_options};
+pub use factory::{
+    BrowserFactory, ChromeBrowserFactory, ChromeBrowserFactoryBuilder, FontDirectory, HeadlessMode,
+    RemoteCdpBrowserFactory, SandboxOptions, StealthOptions, TrustedCa, create_chrome_options,
+    create_chrome_options_with,
+};
+#[cfg(feature = "firefox")]
+pub use factory::{FirefoxBrowser, FirefoxBrowserFactory, FirefoxTab};
 pub use handle::BrowserHandle;
-pub use pool::{BrowserPool, BrowserPoolBuilder};
-pub use stats::PoolStats;
-pub use traits::Healthcheck;
+pub use pool::{BrowserPool, BrowserPoolBuilder, Pool};
+pub use queue::Priority;
+pub use stats::{
+    BrowserDebugInfo, BrowserState, BrowserStats, DetailedPoolStats, HealthCheckResult,
+    KeepAliveStatus, PoolDebugSnapshot, PoolRecycle, PoolStats,
+};
+pub use traits::{BrowserTab, Healthcheck, PdfPostProcessor, PooledBrowser, PostProcessError};
+pub use url_policy::UrlPolicy;
+pub use usage::{KeyUsage, Usage, UsageTracker};
+
+/// Re-exported for [`BrowserPoolConfigBuilder::print_options`].
+pub use headless_chrome::types::PrintToPdfOptions;
 
 // Feature-gated re-exports
 #[cfg(feature = "env-config")]
@@ -18,15 +18,21 @@
 //! ```text
 //! BrowserPool
 //!   ├─ BrowserPoolInner (shared state)
-//!   │   ├─ available: Vec<TrackedBrowser>  (pooled, ready to use)
-//!   │   ├─ active: HashMap<id, TrackedBrowser>  (in-use, tracked for health)
+//!   │   ├─ shards: [PoolShard]  (selected by browser ID)
+//!   │   │   ├─ available: Vec<TrackedBrowser>  (pooled, ready to use)
+//!   │   │   └─ active: HashMap<id, TrackedBrowser>  (in-use, tracked for health)
+//!   │   ├─ available_total: AtomicUsize  (pool size across all shards)
 //!   │   └─ replacement_tasks: Vec<JoinHandle>  (async replacement creators)
 //!   └─ keep_alive_handle: JoinHandle  (health monitoring thread)
 //! ```
 //!
+//! Splitting the collections into shards means handler threads checking
+//! different browsers in and out contend on different mutexes.
+//!
 //! # Critical Invariants
 //!
-//! 1. **Lock Order**: Always acquire `active` before `available` to prevent deadlocks
+//! 1. **Lock Order**: Within a shard, always acquire `active` before `available`;
+//!    never hold locks of two shards at once
 //! 2. **Shutdown Flag**: Check before all expensive operations
 //! 3. **Health Checks**: Never hold locks during I/O operations
 //!
@@ -65,20 +71,104 @@
 //! }
 //! ```
 
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use headless_chrome::Browser;
+use tokio::sync::Semaphore;
 use tokio::task::JoinHandle as TokioJoinHandle;
 
 use crate::config::BrowserPoolConfig;
+use crate::disk::DiskGuard;
 use crate::error::{BrowserPoolError, Result};
-use crate::factory::BrowserFactory;
+use crate::factory::{BrowserFactory, FnBrowserFactory};
 use crate::handle::BrowserHandle;
-use crate::stats::PoolStats;
+use crate::queue::{CheckoutQueue, CheckoutSlot, Priority};
+use crate::stats::{
+    BrowserDebugInfo, BrowserState, BrowserStats, DetailedPoolStats, KeepAliveStatus,
+    PoolDebugSnapshot, PoolRecycle, PoolStats,
+};
 use crate::tracked::TrackedBrowser;
+use crate::traits::{BrowserTab, PooledBrowser};
+use crate::usage::UsageTracker;
+
+/// Upper bound on the number of pool shards.
+///
+/// Beyond this, extra shards only add scanning work on checkout.
+const MAX_SHARDS: usize = 16;
+
+/// Hook called by the keep-alive thread after a full pool recycle; see
+/// [`BrowserPoolBuilder::on_recycle`].
+type RecycleHook = Arc<dyn Fn(&PoolRecycle) + Send + Sync>;
+
+// ============================================================================
+// PoolShard
+// ============================================================================
+
+/// One partition of the pool's browser collections.
+///
+/// A browser always lives in the shard selected by its ID (see
+/// [`BrowserPoolInner::shard_for()`]), in both `active` and `available`.
+///
+/// # Lock Ordering (CRITICAL)
+///
+/// Acquire `active` before `available`, and never hold locks of two
+/// different shards at the same time.
+struct PoolShard<B: PooledBrowser> {
+    /// Browsers in this shard available for checkout (not currently in use).
+    available: Mutex<Vec<TrackedBrowser<B>>>,
+
+    /// All browsers in this shard (both pooled and checked out).
+    active: Mutex<HashMap<u64, TrackedBrowser<B>>>,
+}
+
+impl<B: PooledBrowser> PoolShard<B> {
+    fn new() -> Self {
+        Self {
+            available: Mutex::new(Vec::new()),
+            active: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// Keep-alive cycle timing, reported by the debug snapshot.
+#[derive(Debug, Default)]
+struct KeepAliveTiming {
+    /// Cycles completed.
+    cycles: u64,
+
+    /// When the current or last cycle started.
+    last_started: Option<Instant>,
+
+    /// How long the last completed cycle took.
+    last_duration: Option<Duration>,
+
+    /// When the thread started waiting for the next cycle (`None` mid-cycle).
+    idle_since: Option<Instant>,
+
+    /// Full pool recycles performed.
+    recycles: u64,
+
+    /// When the last recycle happened.
+    last_recycle: Option<Instant>,
+}
+
+/// Browsers launched and removed since the pool started, reported by the
+/// detailed stats.
+#[derive(Debug, Default)]
+struct LifetimeCounters {
+    /// Browsers launched and validated.
+    created: AtomicU64,
+
+    /// Healthy browsers removed (TTL, use ceiling, full pool).
+    retired: AtomicU64,
+
+    /// Browsers removed after failing health checks.
+    failed: AtomicU64,
+}
 
 // ============================================================================
 // BrowserPoolInner
@@ -91,11 +181,12 @@ use crate::tracked::TrackedBrowser;
 ///
 /// # Lock Ordering (CRITICAL)
 ///
-/// Always acquire locks in this order to prevent deadlocks:
+/// Always acquire a shard's locks in this order to prevent deadlocks:
 /// 1. `active` (browsers currently in use)
 /// 2. `available` (browsers in pool ready for use)
 ///
-/// Never hold locks during I/O operations or browser creation.
+/// Never hold locks of two shards at once, and never hold locks during
+/// I/O operations or browser creation.
 ///
 /// # Thread Safety
 ///
@@ -103,30 +194,40 @@ use crate::tracked::TrackedBrowser;
 /// - `Mutex` for mutable collections
 /// - `AtomicBool` for shutdown flag
 /// - `Arc` for shared ownership
-pub(crate) struct BrowserPoolInner {
+pub(crate) struct BrowserPoolInner<B: PooledBrowser = Browser> {
     /// Configuration (immutable after creation).
     config: BrowserPoolConfig,
 
-    /// Browsers available for checkout (not currently in use).
+    /// Partitioned `available`/`active` collections.
     ///
-    /// Protected by Mutex. Browsers are moved from here when checked out
-    /// and returned here when released (if pool not full).
-    available: Mutex<Vec<TrackedBrowser>>,
+    /// Browsers are moved out of a shard's `available` list when checked out
+    /// and returned there when released (if pool not full). A shard's
+    /// `active` map tracks all its browsers for health monitoring.
+    shards: Box<[PoolShard<B>]>,
 
-    /// All browsers that exist (both pooled and checked out).
+    /// Total number of browsers in all `available` lists.
     ///
-    /// Protected by Mutex. Used for health monitoring and lifecycle tracking.
-    /// Maps browser ID -> TrackedBrowser for fast lookup.
-    active: Mutex<HashMap<u64, TrackedBrowser>>,
+    /// Only changed while holding the affected shard's `available` lock,
+    /// so it enforces `max_pool_size` across shards.
+    available_total: AtomicUsize,
+
+    /// Shard where the next checkout starts looking (round-robin).
+    next_shard: AtomicUsize,
 
     /// Factory for creating new browser instances.
-    factory: Box<dyn BrowserFactory>,
+    factory: Box<dyn BrowserFactory<B>>,
 
     /// Atomic flag indicating shutdown in progress.
     ///
     /// Checked before expensive operations. Once set, no new operations start.
     shutting_down: AtomicBool,
 
+    /// Set by [`BrowserPool::start_drain`]; no more checkouts are served.
+    draining: AtomicBool,
+
+    /// Set once [`BrowserPool::warmup`] has succeeded.
+    warmed_up: AtomicBool,
+
     /// Background tasks creating replacement browsers.
     ///
     /// Tracked so we can abort them during shutdown.
@@ -142,9 +243,21 @@ pub(crate) struct BrowserPoolInner {
     /// Tuple of (flag, condvar) allows immediate wake-up on shutdown
     /// instead of waiting for full ping_interval.
     shutdown_signal: Arc<(Mutex<bool>, Condvar)>,
+
+    /// Keep-alive cycle timing (updated by the keep-alive thread).
+    keep_alive_timing: Mutex<KeepAliveTiming>,
+
+    /// Checked-out browser count and the checkouts waiting for one.
+    checkout_queue: Arc<CheckoutQueue>,
+
+    /// Free space check run before each checkout.
+    disk_guard: DiskGuard,
+
+    /// Browsers launched and removed since the pool started.
+    lifetime: LifetimeCounters,
 }
 
-impl BrowserPoolInner {
+impl<B: PooledBrowser> BrowserPoolInner<B> {
     /// Create a new browser pool inner state.
     ///
     /// # Parameters
@@ -155,7 +268,7 @@ impl BrowserPoolInner {
     /// # Panics
     ///
     /// Panics if called outside a tokio runtime context.
-    pub(crate) fn new(config: BrowserPoolConfig, factory: Box<dyn BrowserFactory>) -> Arc<Self> {
+    pub(crate) fn new(config: BrowserPoolConfig, factory: Box<dyn BrowserFactory<B>>) -> Arc<Self> {
         log::info!(
             " Initializing browser pool with capacity {}",
             config.max_pool_size
@@ -167,19 +280,37 @@ impl BrowserPoolInner {
             config.ping_interval.as_secs()
         );
 
+        let shard_count = config.max_pool_size.clamp(1, MAX_SHARDS);
+        log::debug!(" Pool split into {} shards", shard_count);
+
         // Capture runtime handle for spawning async tasks
         // This allows us to spawn from sync contexts (like Drop)
         let runtime_handle = tokio::runtime::Handle::current();
 
+        // Every browser serves up to `tabs_per_browser` checkouts at once
+        let checkout_queue = Arc::new(CheckoutQueue::new(
+            config.max_pool_size.saturating_mul(config.tabs_per_browser),
+            config.checkout_queue_timeout,
+        ));
+
+        let disk_guard = DiskGuard::new(config.min_free_disk_mb);
+
         Arc::new(Self {
             config,
-            available: Mutex::new(Vec::new()),
-            active: Mutex::new(HashMap::new()),
+            shards: (0..shard_count).map(|_| PoolShard::new()).collect(),
+            available_total: AtomicUsize::new(0),
+            next_shard: AtomicUsize::new(0),
             factory,
             shutting_down: AtomicBool::new(false),
+            draining: AtomicBool::new(false),
+            warmed_up: AtomicBool::new(false),
             replacement_tasks: Mutex::new(Vec::new()),
             runtime_handle,
             shutdown_signal: Arc::new((Mutex::new(false), Condvar::new())),
+            keep_alive_timing: Mutex::new(KeepAliveTiming::default()),
+            checkout_queue,
+            disk_guard,
+            lifetime: LifetimeCounters::default(),
         })
     }
 
@@ -198,7 +329,7 @@ impl BrowserPoolInner {
     ///
     /// - Returns [`BrowserPoolError::ShuttingDown`] if pool is shutting down.
     /// - Returns [`BrowserPoolError::BrowserCreation`] if factory fails.
-    pub(crate) fn create_browser_direct(&self) -> Result<TrackedBrowser> {
+    pub(crate) fn create_browser_direct(&self) -> Result<TrackedBrowser<B>> {
         // Early exit if shutting down (don't waste time creating browsers)
         if self.shutting_down.load(Ordering::Acquire) {
             log::debug!(" Skipping browser creation - pool is shutting down");
@@ -210,13 +341,14 @@ impl BrowserPoolInner {
         // Factory handles all Chrome launch complexity
         let browser = self.factory.create()?;
 
-        // Wrap with tracking metadata
-        let tracked = TrackedBrowser::new(browser)?;
+        // Wrap with tracking metadata (jitter spreads out TTL expiry)
+        let tracked = TrackedBrowser::new(browser)?.with_ttl_jitter(self.config.ttl_jitter);
         let id = tracked.id();
+        self.lifetime.created.fetch_add(1, Ordering::Relaxed);
 
         // Add to active tracking immediately for health monitoring
         // This ensures keep-alive thread will monitor it
-        if let Ok(mut active) = self.active.lock() {
+        if let Ok(mut active) = self.shard_for(id).active.lock() {
             active.insert(id, tracked.clone());
             log::debug!(
                 " Browser {} added to active tracking (total active: {})",
@@ -242,7 +374,8 @@ impl BrowserPoolInner {
     /// 2. **Grace Period Check**: Check if browser is within 30s of TTL.
     ///    - If near expiry: Skip (drop) it immediately.
     ///    - It remains in `active` tracking so the `keep_alive` thread handles standard retirement/replacement.
-    /// 3. For valid browsers, perform detailed health check (without holding locks)
+    /// 3. For valid browsers, perform a health check (without holding locks):
+    ///    a `Browser.getVersion` ping, or the full tab check if `deep_health_check` is set
     /// 4. If healthy, return it
     /// 5. If unhealthy, remove from active tracking and try next
     /// 6. If pool empty or all skipped/unhealthy, create new browser
@@ -258,21 +391,74 @@ impl BrowserPoolInner {
     ///
     /// # Errors
     ///
-    /// - Returns [`BrowserPoolError::ShuttingDown`] if pool is shutting down.
+    /// - Returns [`BrowserPoolError::ShuttingDown`] if pool is shutting down or draining.
     /// - Returns [`BrowserPoolError::BrowserCreation`] if new browser creation fails.
-    pub(crate) fn get_or_create_browser(self: &Arc<Self>) -> Result<BrowserHandle> {
+    /// - Returns [`BrowserPoolError::CheckoutTimeout`] if the pool stayed
+    ///   saturated for the whole
+    ///   [`checkout_queue_timeout`](crate::BrowserPoolConfig::checkout_queue_timeout).
+    /// - Returns [`BrowserPoolError::DiskFull`] if the temp volume is below
+    ///   [`min_free_disk_mb`](crate::BrowserPoolConfig::min_free_disk_mb).
+    pub(crate) fn get_or_create_browser(
+        self: &Arc<Self>,
+        priority: Priority,
+    ) -> Result<BrowserHandle<B>> {
+        let slot = self.checkout_queue.admit(priority, None)?;
+        self.checkout(slot)
+    }
+
+    /// Check out a browser into an already reserved slot.
+    ///
+    /// The pooled/new browser part of
+    /// [`get_or_create_browser()`](Self::get_or_create_browser). The slot
+    /// is released if no browser can be obtained.
+    pub(crate) fn checkout(self: &Arc<Self>, slot: CheckoutSlot) -> Result<BrowserHandle<B>> {
         log::debug!(" Attempting to get browser from pool...");
 
+        if self.is_draining() {
+            log::debug!("Refusing checkout - pool is draining");
+            return Err(BrowserPoolError::ShuttingDown);
+        }
+
+        self.disk_guard.check()?;
+
+        if let Some(tracked) = self.take_healthy_pooled() {
+            // Return healthy browser wrapped in RAII handle
+            tracked.claim_tab();
+            tracked.record_use();
+            return Ok(BrowserHandle::new(tracked, Arc::clone(self), slot));
+        }
+
+        // Share a checked-out browser with a free tab before launching one
+        if let Some(tracked) = self.take_shared() {
+            tracked.record_use();
+            return Ok(BrowserHandle::new(tracked, Arc::clone(self), slot));
+        }
+
+        // Pool is empty or no healthy browsers found
+        log::info!("️ Creating new browser (pool was empty or all browsers unhealthy)");
+
+        let tracked = self.create_browser_direct()?;
+
+        log::info!("✅ Returning newly created browser {}", tracked.id());
+        tracked.claim_tab();
+        tracked.record_use();
+        Ok(BrowserHandle::new(tracked, Arc::clone(self), slot))
+    }
+
+    /// Pop pooled browsers until one passes its checkout health check.
+    ///
+    /// Browsers near TTL expiry are skipped and unhealthy ones removed from
+    /// active tracking (see [`get_or_create_browser()`](Self::get_or_create_browser)).
+    ///
+    /// # Returns
+    ///
+    /// A healthy browser, or `None` once the pool is empty.
+    fn take_healthy_pooled(&self) -> Option<TrackedBrowser<B>> {
         // Try to get from pool - LOOP pattern to avoid holding lock during health checks
         // This is critical for concurrency: we release the lock between attempts
         loop {
-            // Acquire lock briefly to pop one browser
-            let tracked_opt = {
-                let mut available = self.available.lock().unwrap();
-                let popped = available.pop();
-                log::trace!(" Pool size after pop: {}", available.len());
-                popped
-            }; // Lock released here - critical for performance
+            // Acquire shard locks briefly to pop one browser
+            let tracked_opt = self.pop_available(); // Lock released here - critical for performance
 
             if let Some(tracked) = tracked_opt {
                 // === LOGIC START: Grace Period Check ===
@@ -282,8 +468,8 @@ impl BrowserPoolInner {
                 // Safety margin matching your stagger interval
                 let safety_margin = Duration::from_secs(30);
 
-                // If browser is about to expire, don't use it.
-                if age + safety_margin > ttl {
+                // If browser is about to expire (including its TTL jitter), don't use it.
+                if tracked.is_expired(ttl.saturating_sub(safety_margin)) {
                     log::debug!(
                         "⏳ Browser {} is near expiry (Age: {}s, Margin: 30s), skipping.",
                         tracked.id(),
@@ -301,72 +487,25 @@ impl BrowserPoolInner {
 
                 log::debug!(" Testing browser {} from pool for health...", tracked.id());
 
-                // Detailed health check WITHOUT holding any locks
+                // Health check WITHOUT holding any locks
                 // This prevents blocking other threads during I/O
-                match tracked.browser().new_tab() {
-                    Ok(tab) => {
-                        log::trace!(
-                            "✅ Browser {} health check: new_tab() successful",
-                            tracked.id()
-                        );
+                let health = self.checkout_health_check(&tracked);
+                tracked.record_health(health.clone().err());
+                match health {
+                    Ok(()) => {
+                        // Get pool size for logging (lock-free)
+                        let pool_size = self.available_count();
 
-                        // Test navigation capability (full health check)
-                        match tab
-                            .navigate_to("data:text/html,<html><body>Health check</body></html>")
-                        {
-                            Ok(_) => {
-                                log::trace!(
-                                    "✅ Browser {} health check: navigation successful",
-                                    tracked.id()
-                                );
+                        log::info!(
+                            "♻️ Reusing healthy browser {} from pool (pool size: {})",
+                            tracked.id(),
+                            pool_size
+                        );
 
-                                // Test cleanup capability
-                                match tab.close(true) {
-                                    Ok(_) => {
-                                        log::debug!(
-                                            "✅ Browser {} passed full health check - ready for use",
-                                            tracked.id()
-                                        );
-
-                                        // Get pool size for logging (brief lock)
-                                        let pool_size = {
-                                            let available = self.available.lock().unwrap();
-                                            available.len()
-                                        };
-
-                                        log::info!(
-                                            "♻️ Reusing healthy browser {} from pool (pool size: {})",
-                                            tracked.id(),
-                                            pool_size
-                                        );
-
-                                        // Return healthy browser wrapped in RAII handle
-                                        return Ok(BrowserHandle::new(tracked, Arc::clone(self)));
-                                    }
-                                    Err(e) => {
-                                        log::warn!(
-                                            "❌ Browser {} health check: tab close failed: {}",
-                                            tracked.id(),
-                                            e
-                                        );
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                log::warn!(
-                                    "❌ Browser {} health check: navigation failed: {}",
-                                    tracked.id(),
-                                    e
-                                );
-                            }
-                        }
+                        return Some(tracked);
                     }
                     Err(e) => {
-                        log::warn!(
-                            "❌ Browser {} health check: new_tab() failed: {}",
-                            tracked.id(),
-                            e
-                        );
+                        log::warn!("❌ Browser {} health check failed: {}", tracked.id(), e);
                     }
                 }
 
@@ -377,41 +516,208 @@ impl BrowserPoolInner {
                     tracked.id()
                 );
                 {
-                    let mut active = self.active.lock().unwrap();
-                    active.remove(&tracked.id());
-                    log::debug!(" Active browsers after removal: {}", active.len());
+                    let mut active = self.shard_for(tracked.id()).active.lock().unwrap();
+                    if active.remove(&tracked.id()).is_some() {
+                        self.lifetime.failed.fetch_add(1, Ordering::Relaxed);
+                    }
+                    log::debug!(" Shard active browsers after removal: {}", active.len());
                 }
 
                 // Continue loop to try next browser in pool
                 log::debug!(" Trying next browser from pool...");
             } else {
-                // Pool is empty, break to create new browser
-                log::debug!(" Pool is empty, will create new browser");
-                break;
+                // Pool is empty, caller creates a new browser
+                log::debug!(" Pool is empty");
+                return None;
             }
         }
+    }
 
-        // Pool is empty or no healthy browsers found
-        log::info!("️ Creating new browser (pool was empty or all browsers unhealthy)");
+    /// Claim a free tab on a browser that is already checked out.
+    ///
+    /// Only used with [`tabs_per_browser`](BrowserPoolConfig::tabs_per_browser)
+    /// above 1. Browsers near TTL expiry are passed over, as in
+    /// [`take_healthy_pooled()`](Self::take_healthy_pooled), and so are
+    /// browsers at [`max_browser_uses`](BrowserPoolConfig::max_browser_uses); no health
+    /// check runs since the browser is serving renders right now.
+    ///
+    /// # Returns
+    ///
+    /// The busy browser with the fewest tabs in use that has room for
+    /// another, already claimed, or `None`.
+    fn take_shared(&self) -> Option<TrackedBrowser<B>> {
+        let limit = self.config.tabs_per_browser;
+        if limit <= 1 {
+            return None;
+        }
 
-        let tracked = self.create_browser_direct()?;
+        let ttl = self
+            .config
+            .browser_ttl
+            .saturating_sub(Duration::from_secs(30));
+        let mut candidates: Vec<TrackedBrowser<B>> = self
+            .get_active_browsers_snapshot()
+            .into_iter()
+            .map(|(_, tracked)| tracked)
+            .filter(|tracked| {
+                (1..limit).contains(&tracked.tabs_in_use())
+                    && !tracked.is_expired(ttl)
+                    && !tracked.is_used_up(self.config.max_browser_uses)
+            })
+            .collect();
+        candidates.sort_by_key(TrackedBrowser::tabs_in_use);
+
+        let tracked = candidates
+            .into_iter()
+            .find(|tracked| tracked.try_share_tab(limit))?;
+        log::debug!(
+            " Sharing browser {} ({}/{} tabs in use)",
+            tracked.id(),
+            tracked.tabs_in_use(),
+            limit
+        );
+        Some(tracked)
+    }
 
-        log::info!("✅ Returning newly created browser {}", tracked.id());
-        Ok(BrowserHandle::new(tracked, Arc::clone(self)))
+    /// Free tab slots on checked-out browsers, for readiness checks.
+    ///
+    /// Always zero unless
+    /// [`tabs_per_browser`](BrowserPoolConfig::tabs_per_browser) is above 1.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub(crate) fn shared_tabs_free(&self) -> usize {
+        let limit = self.config.tabs_per_browser;
+        if limit <= 1 {
+            return 0;
+        }
+        self.get_active_browsers_snapshot()
+            .iter()
+            .map(|(_, tracked)| tracked.tabs_in_use())
+            .filter(|in_use| *in_use > 0)
+            .map(|in_use| limit.saturating_sub(in_use))
+            .sum()
+    }
+
+    /// Check out up to `n` browsers at once, within the pool's capacity.
+    ///
+    /// Capacity is `max_pool_size` minus the browsers already checked out,
+    /// and none while checkouts are waiting in the queue. Slots are
+    /// reserved up front, so concurrent callers never count the same
+    /// headroom. Healthy pooled browsers are claimed first and the rest
+    /// are created.
+    ///
+    /// # Returns
+    ///
+    /// Between 0 and `n` handles; fewer than `n` when the pool lacks
+    /// capacity or browser creation fails partway.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`BrowserPoolError::ShuttingDown`] if pool is shutting down or draining.
+    /// - Returns [`BrowserPoolError::BrowserCreation`] if capacity allowed
+    ///   at least one browser but none could be obtained.
+    /// - Returns [`BrowserPoolError::DiskFull`] if the temp volume is below
+    ///   [`min_free_disk_mb`](crate::BrowserPoolConfig::min_free_disk_mb).
+    pub(crate) fn get_many(self: &Arc<Self>, n: usize) -> Result<Vec<BrowserHandle<B>>> {
+        if self.is_shutting_down() || self.is_draining() {
+            return Err(BrowserPoolError::ShuttingDown);
+        }
+        self.disk_guard.check()?;
+
+        let mut slots = self.checkout_queue.reserve(n);
+        let count = slots.len();
+        log::debug!("Bulk checkout of {} browsers ({} requested)", count, n);
+
+        let mut browsers = Vec::with_capacity(count);
+        while browsers.len() < count {
+            match self.take_healthy_pooled() {
+                Some(tracked) => browsers.push(tracked),
+                None => break,
+            }
+        }
+        while browsers.len() < count {
+            match self.create_browser_direct() {
+                Ok(tracked) => browsers.push(tracked),
+                Err(e) if browsers.is_empty() => return Err(e),
+                Err(e) => {
+                    log::warn!(
+                        "⚠️ Bulk checkout stopping at {}/{} browsers: {}",
+                        browsers.len(),
+                        count,
+                        e
+                    );
+                    break;
+                }
+            }
+        }
+
+        // Unused slots are released as `slots` drops
+        Ok(browsers
+            .into_iter()
+            .zip(slots.drain(..))
+            .map(|(tracked, slot)| {
+                tracked.claim_tab();
+                tracked.record_use();
+                BrowserHandle::new(tracked, Arc::clone(self), slot)
+            })
+            .collect())
+    }
+
+    /// Verify a pooled browser before handing it out.
+    ///
+    /// By default this is a single CDP `Browser.getVersion` round-trip,
+    /// which proves the browser process and its connection are alive. The
+    /// keep-alive thread performs the deeper tab-level check in the
+    /// background. With [`deep_health_check`](BrowserPoolConfig::deep_health_check)
+    /// enabled, the full new_tab + navigate + close check runs instead.
+    ///
+    /// # Returns
+    ///
+    /// `Err` with a description of the failed step.
+    fn checkout_health_check(
+        &self,
+        tracked: &TrackedBrowser<B>,
+    ) -> std::result::Result<(), String> {
+        let failed_step = |e: BrowserPoolError| match e {
+            BrowserPoolError::HealthCheckFailed(step) => step,
+            e => e.to_string(),
+        };
+
+        if !self.config.deep_health_check {
+            tracked.browser().ping().map_err(failed_step)?;
+            log::debug!(
+                "✅ Browser {} passed version ping - ready for use",
+                tracked.id()
+            );
+            return Ok(());
+        }
+
+        tracked.browser().deep_check().map_err(failed_step)?;
+        log::debug!(
+            "✅ Browser {} passed full health check - ready for use",
+            tracked.id()
+        );
+
+        Ok(())
     }
 
     /// Return a browser to the pool (called by BrowserHandle::drop).
     ///
     /// # Critical Lock Ordering
     ///
-    /// Always acquires locks in order: active -> available.
+    /// Always acquires the browser's shard locks in order: active -> available.
     /// Both locks are held together to prevent race conditions.
     ///
     /// # Algorithm
     ///
-    /// 1. Acquire both locks (order: active, then available)
+    /// 0. Release the handle's tab slot; stop if other renders still use the browser
+    /// 1. Acquire both shard locks (order: active, then available)
     /// 2. Verify browser is in active tracking
-    /// 3. Check TTL - if expired, retire and trigger replacement
+    /// 3. Check TTL and use count - if expired or used up, retire and trigger replacement
     /// 4. If pool has space, add to available pool
     /// 5. If pool full, remove from active (browser gets dropped)
     ///
@@ -419,7 +725,7 @@ impl BrowserPoolInner {
     ///
     /// * `self_arc` - Arc reference to self (needed for spawning async tasks).
     /// * `tracked` - The browser being returned.
-    pub(crate) fn return_browser(self_arc: &Arc<Self>, tracked: TrackedBrowser) {
+    pub(crate) fn return_browser(self_arc: &Arc<Self>, tracked: TrackedBrowser<B>) {
         log::debug!(" Returning browser {} to pool...", tracked.id());
 
         // Early exit if shutting down (don't waste time managing pool)
@@ -432,12 +738,26 @@ impl BrowserPoolInner {
         }
 
         // CRITICAL: Always acquire in order: active -> pool
-        // Holding both locks prevents ALL race conditions:
+        // Holding both shard locks prevents ALL race conditions for this browser:
         // - Prevents concurrent modifications to browser state
         // - Prevents duplicate returns
-        // - Ensures pool size limits are respected
-        let mut active = self_arc.active.lock().unwrap();
-        let mut pool = self_arc.available.lock().unwrap();
+        // The pool size limit is enforced by reserving a slot in `available_total`
+        let shard = self_arc.shard_for(tracked.id());
+        let mut active = shard.active.lock().unwrap();
+        let mut pool = shard.available.lock().unwrap();
+
+        // A shared browser stays checked out until its last render is done.
+        // Released under the shard locks so a concurrent return of the
+        // other handle can't see a stale count.
+        let still_in_use = tracked.release_tab();
+        if still_in_use > 0 {
+            log::debug!(
+                " Browser {} still serving {} render(s), not returning to pool",
+                tracked.id(),
+                still_in_use
+            );
+            return;
+        }
 
         // Verify browser is actually tracked (sanity check)
         if !active.contains_key(&tracked.id()) {
@@ -448,26 +768,37 @@ impl BrowserPoolInner {
             return;
         }
 
-        // Check TTL before returning to pool
-        // Expired browsers should be retired to prevent memory leaks
-        if tracked.is_expired(self_arc.config.browser_ttl) {
-            log::info!(
-                "⏰ Browser {} expired (age: {}min, TTL: {}min), retiring instead of returning",
-                tracked.id(),
-                tracked.age_minutes(),
-                self_arc.config.browser_ttl.as_secs() / 60
-            );
+        // Check TTL and use count before returning to pool
+        // Expired or used-up browsers should be retired to prevent memory leaks
+        let expired = tracked.is_expired(self_arc.config.browser_ttl);
+        let used_up = tracked.is_used_up(self_arc.config.max_browser_uses);
+        if expired || used_up {
+            if expired {
+                log::info!(
+                    "⏰ Browser {} expired (age: {}min, TTL: {}min), retiring instead of returning",
+                    tracked.id(),
+                    tracked.age_minutes(),
+                    self_arc.config.browser_ttl.as_secs() / 60
+                );
+            } else {
+                log::info!(
+                    " Browser {} reached max uses ({}), retiring instead of returning",
+                    tracked.id(),
+                    tracked.uses()
+                );
+            }
 
             // Remove from active tracking
             active.remove(&tracked.id());
-            log::debug!(" Active browsers after TTL retirement: {}", active.len());
+            self_arc.lifetime.retired.fetch_add(1, Ordering::Relaxed);
+            log::debug!(" Active browsers after retirement: {}", active.len());
 
             // Release locks before spawning replacement task
             drop(active);
             drop(pool);
 
             // Trigger async replacement creation (non-blocking)
-            log::debug!(" Triggering replacement browser creation for expired browser");
+            log::debug!(" Triggering replacement browser creation for retired browser");
             Self::spawn_replacement_creation(Arc::clone(self_arc), 1);
             return;
         }
@@ -482,24 +813,25 @@ impl BrowserPoolInner {
         }
 
         // Check if pool has space for this browser
-        if pool.len() < self_arc.config.max_pool_size {
+        if let Some(pool_size) = self_arc.reserve_available_slot() {
             // Add to pool for reuse
             pool.push(tracked.clone());
             log::info!(
                 "♻️ Browser {} returned to pool (pool size: {}/{})",
                 tracked.id(),
-                pool.len(),
+                pool_size,
                 self_arc.config.max_pool_size
             );
         } else {
             // Pool is full, remove from tracking (browser will be dropped)
             log::debug!(
-                "️ Pool full ({}/{}), removing browser {} from system",
-                pool.len(),
+                "️ Pool full ({}/{}), removing browser {} from system",
+                self_arc.available_count(),
                 self_arc.config.max_pool_size,
                 tracked.id()
             );
             active.remove(&tracked.id());
+            self_arc.lifetime.retired.fetch_add(1, Ordering::Relaxed);
             log::debug!(" Active browsers after removal: {}", active.len());
         }
     }
@@ -543,11 +875,11 @@ impl BrowserPoolInner {
 
             // Check if pool has space BEFORE creating (avoid wasted work)
             let pool_has_space = {
-                let pool = inner.available.lock().unwrap();
-                let has_space = pool.len() < inner.config.max_pool_size;
+                let pool_size = inner.available_count();
+                let has_space = pool_size < inner.config.max_pool_size;
                 log::trace!(
                     " Pool space check: {}/{} (has space: {})",
-                    pool.len(),
+                    pool_size,
                     inner.config.max_pool_size,
                     has_space
                 );
@@ -567,20 +899,23 @@ impl BrowserPoolInner {
 
             // Use spawn_blocking for CPU-bound browser creation
             // This prevents blocking the async runtime
+            // The standby tab is opened here so the first request on this
+            // browser doesn't pay for it
             let inner_clone = Arc::clone(&inner);
-            let result =
-                tokio::task::spawn_blocking(move || inner_clone.create_browser_direct()).await;
+            let result = tokio::task::spawn_blocking(move || {
+                let tracked = inner_clone.create_browser_direct()?;
+                tracked.open_standby_tab();
+                Ok::<_, BrowserPoolError>(tracked)
+            })
+            .await;
 
             match result {
                 Ok(Ok(tracked)) => {
                     let id = tracked.id();
 
                     // Add to pool (with space check to handle race conditions)
-                    let mut pool = inner.available.lock().unwrap();
-
                     // Double-check space (another thread might have added browsers)
-                    if pool.len() < inner.config.max_pool_size {
-                        pool.push(tracked);
+                    if inner.push_available(tracked) {
                         created_count += 1;
                         log::info!(
                             "✅ Created replacement browser {} and added to pool ({}/{})",
@@ -618,8 +953,8 @@ impl BrowserPoolInner {
         }
 
         // Final status report
-        let pool_size = inner.available.lock().unwrap().len();
-        let active_size = inner.active.lock().unwrap().len();
+        let pool_size = inner.available_count();
+        let active_size = inner.active_count();
 
         log::info!(
             " Replacement creation completed: {}/{} created, {} failed. Pool: {}, Active: {}",
@@ -689,6 +1024,12 @@ impl BrowserPoolInner {
         &self.config
     }
 
+    /// Get the factory that creates the pool's browsers.
+    #[inline]
+    pub(crate) fn factory(&self) -> &dyn BrowserFactory<B> {
+        self.factory.as_ref()
+    }
+
     /// Check if the pool is shutting down.
     #[inline]
     pub(crate) fn is_shutting_down(&self) -> bool {
@@ -699,6 +1040,27 @@ impl BrowserPoolInner {
     #[inline]
     pub(crate) fn set_shutting_down(&self, value: bool) {
         self.shutting_down.store(value, Ordering::Release);
+        if value {
+            self.checkout_queue.close();
+        }
+    }
+
+    /// Check if the pool is draining.
+    #[inline]
+    pub(crate) fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    /// Stop serving checkouts and release waiting ones.
+    pub(crate) fn set_draining(&self) {
+        self.draining.store(true, Ordering::Release);
+        self.checkout_queue.close();
+    }
+
+    /// Get the checkout queue.
+    #[inline]
+    pub(crate) fn checkout_queue(&self) -> &Arc<CheckoutQueue> {
+        &self.checkout_queue
     }
 
     /// Get the shutdown signal for the keep-alive thread.
@@ -707,41 +1069,227 @@ impl BrowserPoolInner {
         &self.shutdown_signal
     }
 
+    /// Get the shard that owns the browser with the given ID.
+    ///
+    /// IDs are assigned sequentially, so a modulo spreads browsers evenly.
+    #[inline]
+    fn shard_for(&self, id: u64) -> &PoolShard<B> {
+        &self.shards[(id % self.shards.len() as u64) as usize]
+    }
+
+    /// Reserve one slot in the available pool, if below `max_pool_size`.
+    ///
+    /// Must be followed by a push while holding a shard's `available` lock.
+    ///
+    /// # Returns
+    ///
+    /// The pool size including the reserved slot, or `None` if full.
+    fn reserve_available_slot(&self) -> Option<usize> {
+        self.available_total
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < self.config.max_pool_size).then_some(n + 1)
+            })
+            .ok()
+            .map(|n| n + 1)
+    }
+
+    /// Pop one browser from the available pool.
+    ///
+    /// Starts at a rotating shard so concurrent checkouts spread across
+    /// shards, and holds only one shard lock at a time.
+    fn pop_available(&self) -> Option<TrackedBrowser<B>> {
+        let start = self.next_shard.fetch_add(1, Ordering::Relaxed);
+
+        for offset in 0..self.shards.len() {
+            let shard = &self.shards[(start + offset) % self.shards.len()];
+            let mut available = shard.available.lock().unwrap();
+            if let Some(tracked) = available.pop() {
+                self.available_total.fetch_sub(1, Ordering::AcqRel);
+                log::trace!(" Pool size after pop: {}", self.available_count());
+                return Some(tracked);
+            }
+        }
+
+        None
+    }
+
+    /// Add a browser to the available pool if it has space.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the browser was pooled, `false` if the pool was full.
+    fn push_available(&self, tracked: TrackedBrowser<B>) -> bool {
+        let mut available = self.shard_for(tracked.id()).available.lock().unwrap();
+        if self.reserve_available_slot().is_some() {
+            available.push(tracked);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Get the available browsers count.
     pub(crate) fn available_count(&self) -> usize {
-        self.available.lock().map(|g| g.len()).unwrap_or(0)
+        self.available_total.load(Ordering::Acquire)
     }
 
     /// Get the active browsers count.
     pub(crate) fn active_count(&self) -> usize {
-        self.active.lock().map(|g| g.len()).unwrap_or(0)
+        self.shards
+            .iter()
+            .map(|shard| shard.active.lock().map(|g| g.len()).unwrap_or(0))
+            .sum()
     }
 
     /// Get a snapshot of active browsers for health checking.
     ///
     /// Returns a cloned list to avoid holding locks during I/O.
-    pub(crate) fn get_active_browsers_snapshot(&self) -> Vec<(u64, TrackedBrowser)> {
-        let active = self.active.lock().unwrap();
-        active
-            .iter()
-            .map(|(id, tracked)| (*id, tracked.clone()))
-            .collect()
+    pub(crate) fn get_active_browsers_snapshot(&self) -> Vec<(u64, TrackedBrowser<B>)> {
+        let mut snapshot = Vec::new();
+        for shard in self.shards.iter() {
+            let active = shard.active.lock().unwrap();
+            snapshot.extend(active.iter().map(|(id, tracked)| (*id, tracked.clone())));
+        }
+        snapshot
     }
 
     /// Remove a browser from active tracking.
-    pub(crate) fn remove_from_active(&self, id: u64) -> Option<TrackedBrowser> {
-        let mut active = self.active.lock().unwrap();
+    pub(crate) fn remove_from_active(&self, id: u64) -> Option<TrackedBrowser<B>> {
+        let mut active = self.shard_for(id).active.lock().unwrap();
         active.remove(&id)
     }
 
     /// Remove browsers from the available pool by ID.
     pub(crate) fn remove_from_available(&self, ids: &[u64]) {
-        let mut pool = self.available.lock().unwrap();
-        let original_size = pool.len();
-        pool.retain(|b| !ids.contains(&b.id()));
-        let removed = original_size - pool.len();
+        let mut removed = 0;
+        for shard in self.shards.iter() {
+            let mut pool = shard.available.lock().unwrap();
+            let original_size = pool.len();
+            pool.retain(|b| !ids.contains(&b.id()));
+            let shard_removed = original_size - pool.len();
+            self.available_total
+                .fetch_sub(shard_removed, Ordering::AcqRel);
+            removed += shard_removed;
+        }
         if removed > 0 {
-            log::debug!("️ Removed {} browsers from available pool", removed);
+            log::debug!("️ Removed {} browsers from available pool", removed);
+        }
+    }
+
+    /// Get the IDs of the browsers in the available pool.
+    pub(crate) fn available_ids(&self) -> HashSet<u64> {
+        let mut ids = HashSet::new();
+        for shard in self.shards.iter() {
+            let pool = shard.available.lock().unwrap();
+            ids.extend(pool.iter().map(TrackedBrowser::id));
+        }
+        ids
+    }
+
+    /// Get the number of replacement tasks still running.
+    pub(crate) fn replacement_tasks_in_flight(&self) -> usize {
+        self.replacement_tasks
+            .lock()
+            .map(|tasks| tasks.iter().filter(|h| !h.is_finished()).count())
+            .unwrap_or(0)
+    }
+
+    /// Record that the keep-alive thread is waiting for its next cycle.
+    fn keep_alive_idle(&self) {
+        if let Ok(mut timing) = self.keep_alive_timing.lock() {
+            timing.idle_since = Some(Instant::now());
+        }
+    }
+
+    /// Record the start of a keep-alive cycle.
+    fn keep_alive_cycle_started(&self) {
+        if let Ok(mut timing) = self.keep_alive_timing.lock() {
+            timing.last_started = Some(Instant::now());
+            timing.idle_since = None;
+        }
+    }
+
+    /// Record the end of a keep-alive cycle and start waiting for the next.
+    fn keep_alive_cycle_finished(&self) {
+        if let Ok(mut timing) = self.keep_alive_timing.lock() {
+            timing.cycles += 1;
+            timing.last_duration = timing.last_started.map(|at| at.elapsed());
+            timing.idle_since = Some(Instant::now());
+        }
+    }
+
+    /// Get the keep-alive thread's timing.
+    pub(crate) fn keep_alive_status(&self) -> KeepAliveStatus {
+        let interval = self.config.ping_interval;
+        let timing = self
+            .keep_alive_timing
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        KeepAliveStatus {
+            interval,
+            cycles: timing.cycles,
+            last_cycle_started: timing.last_started.map(|at| at.elapsed()),
+            last_cycle_duration: timing.last_duration,
+            next_cycle_in: timing
+                .idle_since
+                .map(|at| interval.saturating_sub(at.elapsed())),
+            recycles: timing.recycles,
+            last_recycle: timing.last_recycle.map(|at| at.elapsed()),
+        }
+    }
+
+    /// Discard every browser and launch `warmup_count` fresh ones.
+    ///
+    /// Called by the keep-alive thread when every browser has failed its
+    /// health checks for `failed_cycles` cycles in a row. Pending
+    /// replacement tasks are aborted first so they cannot add browsers from
+    /// the failing state. Checked-out browsers are no longer tracked, so
+    /// they are dropped when returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `inner` - Arc reference to pool state.
+    /// * `failed_cycles` - Consecutive failed cycles that triggered this.
+    pub(crate) fn recycle(inner: &Arc<Self>, failed_cycles: u32) -> PoolRecycle {
+        let aborted = inner.abort_replacement_tasks();
+
+        let ids: Vec<u64> = inner
+            .get_active_browsers_snapshot()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        let discarded = ids
+            .iter()
+            .filter(|id| inner.remove_from_active(**id).is_some())
+            .count();
+        inner.remove_from_available(&ids);
+        inner
+            .lifetime
+            .failed
+            .fetch_add(discarded as u64, Ordering::Relaxed);
+
+        if let Ok(mut timing) = inner.keep_alive_timing.lock() {
+            timing.recycles += 1;
+            timing.last_recycle = Some(Instant::now());
+        }
+
+        let relaunching = inner.config.warmup_count;
+        log::error!(
+            " Recycling pool after {} failed health check cycles: discarded {} browsers, aborted {} replacement tasks, launching {} fresh browsers",
+            failed_cycles,
+            discarded,
+            aborted,
+            relaunching
+        );
+        if relaunching > 0 {
+            Self::spawn_replacement_creation(Arc::clone(inner), relaunching);
+        }
+
+        PoolRecycle {
+            discarded,
+            failed_cycles,
+            relaunching,
         }
     }
 
@@ -759,8 +1307,34 @@ impl BrowserPoolInner {
     }
 }
 
+/// Count of consecutive keep-alive cycles in which every browser failed,
+/// after a cycle that pinged `pinged` browsers of which `healthy` passed.
+///
+/// A cycle with no browsers to ping leaves the count unchanged, so an empty
+/// pool (e.g. right after a recycle) is never recycled again.
+fn next_failed_cycles(failed_cycles: u32, pinged: usize, healthy: usize) -> u32 {
+    match (pinged, healthy) {
+        (0, _) => failed_cycles,
+        (_, 0) => failed_cycles + 1,
+        _ => 0,
+    }
+}
+
+/// Browsers to launch so that at least `min_pool_size` are available,
+/// without the pool tracking more than `max_pool_size` in total.
+fn top_up_count(
+    min_pool_size: usize,
+    max_pool_size: usize,
+    available: usize,
+    total: usize,
+) -> usize {
+    min_pool_size
+        .saturating_sub(available)
+        .min(max_pool_size.saturating_sub(total))
+}
+
 // ============================================================================
-// BrowserPool
+// Pool
 // ============================================================================
 
 /// Main browser pool with lifecycle management.
@@ -768,12 +1342,20 @@ impl BrowserPoolInner {
 /// This is the public-facing API for the browser pool. It wraps the internal
 /// state and manages the keep-alive thread.
 ///
+/// The pooling, TTL, health-check and handle machinery is generic over the
+/// pooled resource `B`, a [`PooledBrowser`]. Chrome
+/// ([`headless_chrome::Browser`]) is the default, and [`BrowserPool`] names
+/// the pool by its usual name; another backend (a WebDriver session, a
+/// converter worker process) is pooled with
+/// [`BrowserPoolBuilder::with_backend`].
+///
 /// # Overview
 ///
-/// `BrowserPool` provides:
-/// - Browser checkout via [`get()`](Self::get)
+/// `Pool` provides:
+/// - Browser checkout via [`get()`](Self::get) and [`get_many()`](Self::get_many)
 /// - Pool warmup via [`warmup()`](Self::warmup)
 /// - Statistics via [`stats()`](Self::stats)
+/// - Diagnostics via [`debug_snapshot()`](Self::debug_snapshot)
 /// - Graceful shutdown via [`shutdown_async()`](Self::shutdown_async)
 ///
 /// # Example
@@ -816,17 +1398,80 @@ impl BrowserPoolInner {
 ///
 /// `BrowserPool` is `Send` and can be wrapped in `Arc<Mutex<>>` for sharing
 /// across threads. Use [`into_shared()`](Self::into_shared) for convenience.
-pub struct BrowserPool {
+pub struct Pool<B: PooledBrowser = Browser> {
     /// Shared internal state.
-    inner: Arc<BrowserPoolInner>,
+    inner: Arc<BrowserPoolInner<B>>,
 
     /// Handle to keep-alive monitoring thread.
     ///
     /// Option allows taking during shutdown. None means keep-alive disabled.
     keep_alive_handle: Option<JoinHandle<()>>,
+
+    /// Per-API-key usage recorded by the service layer.
+    usage: UsageTracker,
+
+    /// Responses replayed for repeated `Idempotency-Key` headers.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    idempotency: crate::service::IdempotencyCache,
+
+    /// Renders in flight, shared with identical concurrent requests.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    inflight: Arc<crate::service::InflightRenders>,
+
+    /// Recently rendered PDFs served to identical requests.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    response_cache: Arc<crate::service::ResponseCache>,
+
+    /// Running jobs and the kept results of finished ones.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    jobs: crate::service::JobRegistry,
+}
+
+/// The pool of Chrome browsers, and the name the crate's API uses.
+///
+/// An alias of [`Pool`] that keeps `BrowserPool` code working unchanged;
+/// `BrowserPool<MyBrowser>` works too.
+pub type BrowserPool<B = Browser> = Pool<B>;
+
+impl Pool {
+    /// Create a new builder for constructing a BrowserPool.
+    ///
+    /// This is the recommended way to create a pool. For a backend other
+    /// than Chrome, use [`BrowserPoolBuilder::with_backend`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let pool = BrowserPool::builder()
+    ///     .factory(Box::new(ChromeBrowserFactory::with_defaults()))
+    ///     .build()?;
+    /// ```
+    pub fn builder() -> BrowserPoolBuilder {
+        BrowserPoolBuilder::new()
+    }
 }
 
-impl BrowserPool {
+impl<B: PooledBrowser> Pool<B> {
     /// Convert pool into a shared `Arc<Mutex<>>` for use in web handlers.
     ///
     /// This is convenient for web frameworks that need shared state.
@@ -842,30 +1487,15 @@ impl BrowserPool {
     /// // Can now be cloned and shared across handlers
     /// let pool_clone = Arc::clone(&pool);
     /// ```
-    pub fn into_shared(self) -> Arc<Mutex<BrowserPool>> {
+    pub fn into_shared(self) -> Arc<Mutex<Pool<B>>> {
         log::debug!(" Converting BrowserPool into shared Arc<Mutex<>>");
         Arc::new(Mutex::new(self))
     }
 
-    /// Create a new builder for constructing a BrowserPool.
+    /// Get a browser from the pool (or create one if empty).
     ///
-    /// This is the recommended way to create a pool.
-    ///
-    /// # Example
-    ///
-    /// ```rust,ignore
-    /// let pool = BrowserPool::builder()
-    ///     .factory(Box::new(ChromeBrowserFactory::with_defaults()))
-    ///     .build()?;
-    /// ```
-    pub fn builder() -> BrowserPoolBuilder {
-        BrowserPoolBuilder::new()
-    }
-
-    /// Get a browser from the pool (or create one if empty).
-    ///
-    /// Returns a [`BrowserHandle`] that implements `Deref<Target=Browser>`,
-    /// allowing transparent access to browser methods.
+    /// Returns a [`BrowserHandle`] that implements `Deref<Target=Browser>`,
+    /// allowing transparent access to browser methods.
     ///
     /// # Automatic Return
     ///
@@ -874,9 +1504,13 @@ impl BrowserPool {
     ///
     /// # Errors
     ///
-    /// - Returns [`BrowserPoolError::ShuttingDown`] if pool is shutting down.
+    /// - Returns [`BrowserPoolError::ShuttingDown`] if pool is shutting down or draining.
     /// - Returns [`BrowserPoolError::BrowserCreation`] if new browser creation fails.
     /// - Returns [`BrowserPoolError::HealthCheckFailed`] if all pooled browsers are unhealthy.
+    /// - Returns [`BrowserPoolError::CheckoutTimeout`] if the checkout queue
+    ///   is enabled and no browser was returned in time.
+    /// - Returns [`BrowserPoolError::DiskFull`] if the temp volume is below
+    ///   [`min_free_disk_mb`](crate::BrowserPoolConfig::min_free_disk_mb).
     ///
     /// # Example
     ///
@@ -886,9 +1520,139 @@ impl BrowserPool {
     /// tab.navigate_to("https://example.com")?;
     /// // browser returned automatically when it goes out of scope
     /// ```
-    pub fn get(&self) -> Result<BrowserHandle> {
+    pub fn get(&self) -> Result<BrowserHandle<B>> {
         log::trace!(" BrowserPool::get() called");
-        self.inner.get_or_create_browser()
+        self.inner.get_or_create_browser(Priority::Normal)
+    }
+
+    /// Get a browser, with `priority` deciding its place in the checkout
+    /// queue.
+    ///
+    /// Identical to [`get()`](Self::get) (which uses
+    /// [`Priority::Normal`]) unless
+    /// [`checkout_queue_timeout`](crate::BrowserPoolConfig::checkout_queue_timeout)
+    /// is set and `max_pool_size` browsers are checked out. Then the call
+    /// waits for a browser to be returned, behind any waiting checkouts of
+    /// higher priority and earlier ones of the same priority.
+    ///
+    /// # Errors
+    ///
+    /// As for [`get()`](Self::get), plus
+    /// [`BrowserPoolError::CheckoutTimeout`] if no browser was returned
+    /// within the timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::Priority;
+    ///
+    /// // A user is waiting on this one
+    /// let browser = pool.get_with_priority(Priority::High)?;
+    /// ```
+    pub fn get_with_priority(&self, priority: Priority) -> Result<BrowserHandle<B>> {
+        log::trace!("BrowserPool::get_with_priority({}) called", priority);
+        self.inner.get_or_create_browser(priority)
+    }
+
+    /// Get a browser from async code without blocking a thread while
+    /// waiting for one.
+    ///
+    /// Behaves like [`get()`](Self::get), but waiting in the checkout
+    /// queue (with
+    /// [`checkout_queue_timeout`](crate::BrowserPoolConfig::checkout_queue_timeout)
+    /// set and the pool saturated) yields to the runtime instead of
+    /// parking a worker thread. Only the short checkout itself (health
+    /// ping, or launching a browser) runs on tokio's blocking pool.
+    ///
+    /// The returned future does not borrow the pool, so the lock around a
+    /// [`SharedBrowserPool`](crate::SharedBrowserPool) can be released
+    /// before awaiting it. Dropping the future while it waits gives up its
+    /// place in the queue.
+    ///
+    /// # Errors
+    ///
+    /// As for [`get_with_priority()`](Self::get_with_priority).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// async fn render(pool: &SharedBrowserPool) -> Result<Vec<u8>, BrowserPoolError> {
+    ///     let checkout = pool.lock().unwrap().get_async(); // lock released here
+    ///     let browser = checkout.await?;
+    ///     // ...
+    /// }
+    /// ```
+    pub fn get_async(&self) -> impl Future<Output = Result<BrowserHandle<B>>> + Send + 'static {
+        log::trace!("BrowserPool::get_async() called");
+        let inner = Arc::clone(&self.inner);
+        async move {
+            let slot = inner.checkout_queue().admit_async(Priority::Normal).await?;
+            // Health checks and browser launches are blocking CDP calls
+            tokio::task::spawn_blocking(move || inner.checkout(slot))
+                .await
+                .map_err(|e| {
+                    BrowserPoolError::BrowserCreation(format!("checkout task failed: {}", e))
+                })?
+        }
+    }
+
+    /// Get the checkout queue, to wait for a slot without holding the
+    /// lock around the pool.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub(crate) fn checkout_queue(&self) -> Arc<CheckoutQueue> {
+        Arc::clone(self.inner.checkout_queue())
+    }
+
+    /// Check out a browser into a slot reserved from
+    /// [`checkout_queue()`](Self::checkout_queue).
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub(crate) fn checkout(&self, slot: CheckoutSlot) -> Result<BrowserHandle<B>> {
+        self.inner.checkout(slot)
+    }
+
+    /// Check out up to `n` browsers in one call.
+    ///
+    /// For batch renderers that want to reserve capacity up front instead
+    /// of racing `n` separate [`get()`](Self::get) calls against other
+    /// traffic. The number returned is limited by the pool's capacity:
+    /// `max_pool_size` minus the browsers already checked out. Healthy
+    /// pooled browsers are handed out first; the rest are launched, which
+    /// takes 1-3 seconds per browser.
+    ///
+    /// Each handle returns its browser to the pool when dropped, exactly
+    /// like one from [`get()`](Self::get).
+    ///
+    /// # Returns
+    ///
+    /// Between 0 and `n` handles. An empty `Vec` means the pool is at
+    /// capacity; fewer than `n` means capacity or browser creation ran out.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`BrowserPoolError::ShuttingDown`] if pool is shutting down or draining.
+    /// - Returns [`BrowserPoolError::BrowserCreation`] if there was capacity
+    ///   but not a single browser could be obtained.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let browsers = pool.get_many(4)?;
+    /// println!("Rendering with {} browsers", browsers.len());
+    /// // all returned to the pool when `browsers` is dropped
+    /// ```
+    pub fn get_many(&self, n: usize) -> Result<Vec<BrowserHandle<B>>> {
+        log::trace!("BrowserPool::get_many({}) called", n);
+        self.inner.get_many(n)
     }
 
     /// Get pool statistics snapshot.
@@ -919,6 +1683,136 @@ impl BrowserPool {
         }
     }
 
+    /// Whether [`warmup()`](Self::warmup) has finished successfully.
+    ///
+    /// Used by readiness checks when
+    /// [`ready_require_warmup`](BrowserPoolConfig::ready_require_warmup)
+    /// is set.
+    pub fn is_warmed_up(&self) -> bool {
+        self.inner.warmed_up.load(Ordering::Acquire)
+    }
+
+    /// Whether [`start_drain()`](Self::start_drain) has been called.
+    ///
+    /// A draining pool hands out no browsers and reports itself not ready.
+    pub fn is_draining(&self) -> bool {
+        self.inner.is_draining()
+    }
+
+    /// Number of checkouts waiting for a browser in the checkout queue.
+    pub(crate) fn queued_checkouts(&self) -> usize {
+        self.inner.checkout_queue().waiting()
+    }
+
+    /// Get a detailed diagnostic snapshot of the pool.
+    ///
+    /// Reports each tracked browser's age, use count, open tabs, memory,
+    /// and last health check, plus replacement tasks in flight and
+    /// keep-alive timing. It reads every browser's process memory, so use
+    /// [`stats()`](Self::stats) for frequent polling.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let snapshot = pool.debug_snapshot();
+    /// for browser in &snapshot.browsers {
+    ///     if let Some(check) = &browser.last_health_check {
+    ///         println!("browser {}: healthy={}", browser.id, check.is_healthy());
+    ///     }
+    /// }
+    /// ```
+    pub fn debug_snapshot(&self) -> PoolDebugSnapshot {
+        let pooled = self.inner.available_ids();
+
+        let mut browsers: Vec<BrowserDebugInfo> = self
+            .inner
+            .get_active_browsers_snapshot()
+            .into_iter()
+            .map(|(id, tracked)| BrowserDebugInfo {
+                id,
+                age: tracked.age(),
+                uses: tracked.uses(),
+                checked_out: !pooled.contains(&id),
+                open_tabs: tracked.open_tab_count(),
+                memory_bytes: tracked.memory_bytes(),
+                last_health_check: tracked.last_health_check(),
+            })
+            .collect();
+        // IDs are sequential, so this is oldest first
+        browsers.sort_by_key(|browser| browser.id);
+
+        PoolDebugSnapshot {
+            stats: self.stats(),
+            max_pool_size: self.inner.config().max_pool_size,
+            browsers,
+            replacement_tasks: self.inner.replacement_tasks_in_flight(),
+            keep_alive: self
+                .keep_alive_handle
+                .as_ref()
+                .map(|_| self.inner.keep_alive_status()),
+            shutting_down: self.inner.is_shutting_down(),
+            queued_checkouts: self.queued_checkouts(),
+        }
+    }
+
+    /// Get per-browser statistics and the pool's lifetime counters.
+    ///
+    /// Reports each tracked browser's age, use count, time since its last
+    /// successful ping, consecutive ping failures, memory, and whether it is
+    /// idle or busy, plus how many browsers have been created, retired, and
+    /// removed as failed since the pool started. Like
+    /// [`debug_snapshot()`](Self::debug_snapshot) it reads every browser's
+    /// process memory, so use [`stats()`](Self::stats) for frequent polling.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::BrowserState;
+    ///
+    /// let stats = pool.detailed_stats();
+    /// let busy = stats
+    ///     .browsers
+    ///     .iter()
+    ///     .filter(|browser| browser.state == BrowserState::Busy)
+    ///     .count();
+    /// println!("{} busy, {} retired so far", busy, stats.total_retired);
+    /// ```
+    pub fn detailed_stats(&self) -> DetailedPoolStats {
+        let pooled = self.inner.available_ids();
+
+        let mut browsers: Vec<BrowserStats> = self
+            .inner
+            .get_active_browsers_snapshot()
+            .into_iter()
+            .map(|(id, tracked)| BrowserStats {
+                id,
+                age: tracked.age(),
+                uses: tracked.uses(),
+                last_ping: tracked
+                    .last_ping_time()
+                    .map_or_else(|| tracked.age(), |at| at.elapsed()),
+                ping_failures: tracked.ping_failures(),
+                memory_bytes: tracked.memory_bytes(),
+                state: if pooled.contains(&id) {
+                    BrowserState::Idle
+                } else {
+                    BrowserState::Busy
+                },
+            })
+            .collect();
+        // IDs are sequential, so this is oldest first
+        browsers.sort_by_key(|browser| browser.id);
+
+        let lifetime = &self.inner.lifetime;
+        DetailedPoolStats {
+            stats: self.stats(),
+            browsers,
+            total_created: lifetime.created.load(Ordering::Relaxed),
+            total_retired: lifetime.retired.load(Ordering::Relaxed),
+            total_failed: lifetime.failed.load(Ordering::Relaxed),
+        }
+    }
+
     /// Get a reference to the pool configuration.
     ///
     /// Returns the configuration that was used to create this pool.
@@ -951,6 +1845,75 @@ impl BrowserPool {
         self.inner.config()
     }
 
+    /// Get the pool's per-API-key usage counters.
+    ///
+    /// The pre-built handlers record every successful render here and
+    /// check it against the configured render quotas.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let usage = pool.usage().usage("team-a");
+    /// println!("Renders today: {}", usage.today.renders);
+    /// ```
+    #[inline]
+    pub fn usage(&self) -> &UsageTracker {
+        &self.usage
+    }
+
+    /// Get the pool's cache of idempotent responses.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    #[inline]
+    pub(crate) fn idempotency(&self) -> &crate::service::IdempotencyCache {
+        &self.idempotency
+    }
+
+    /// Get the registry of in-flight renders used for coalescing.
+    ///
+    /// Returned as an `Arc` so it can be used without holding the pool lock
+    /// for the length of a render.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    #[inline]
+    pub(crate) fn inflight(&self) -> Arc<crate::service::InflightRenders> {
+        Arc::clone(&self.inflight)
+    }
+
+    /// Get the cache of rendered PDFs.
+    ///
+    /// Returned as an `Arc` so it can be used without holding the pool lock.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    #[inline]
+    pub(crate) fn response_cache(&self) -> Arc<crate::service::ResponseCache> {
+        Arc::clone(&self.response_cache)
+    }
+
+    /// Get the registry of running and finished jobs.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    #[inline]
+    pub(crate) fn jobs(&self) -> &crate::service::JobRegistry {
+        &self.jobs
+    }
+
     /// Warmup the pool by pre-creating browsers.
     ///
     /// This is highly recommended to reduce first-request latency.
@@ -958,16 +1921,23 @@ impl BrowserPool {
     ///
     /// # Process
     ///
-    /// 1. Creates `warmup_count` browsers sequentially with staggered timing
-    /// 2. Tests each browser with navigation
+    /// 1. Creates `warmup_count` browsers (sequentially, or `warmup_concurrency` at a time)
+    /// 2. Tests each browser with navigation, keeping the test tab as a standby tab
     /// 3. Returns all browsers to pool
     /// 4. Entire process has timeout (configurable via `warmup_timeout`)
     ///
     /// # Staggered Creation
     ///
-    /// Browsers are created with a 30-second delay between them to ensure
-    /// their TTLs are offset. This prevents all browsers from expiring
-    /// at the same time.
+    /// With the default `warmup_concurrency` of 1, browsers are created with
+    /// a 30-second delay between them to ensure their TTLs are offset. This
+    /// prevents all browsers from expiring at the same time.
+    ///
+    /// # Concurrent Creation
+    ///
+    /// With `warmup_concurrency` above 1, browsers are launched in parallel
+    /// without the stagger, and [`ttl_jitter`](BrowserPoolConfig::ttl_jitter)
+    /// offsets their expiry instead. A 5-browser pool then warms up in
+    /// seconds rather than minutes.
     ///
     /// # Errors
     ///
@@ -1005,6 +1975,7 @@ impl BrowserPool {
                     stats.available,
                     stats.active
                 );
+                self.inner.warmed_up.store(true, Ordering::Release);
                 Ok(())
             }
             Ok(Err(e)) => {
@@ -1023,109 +1994,65 @@ impl BrowserPool {
 
     /// Internal warmup implementation (separated for cleaner timeout wrapping).
     ///
-    /// Creates browsers sequentially with a delay between them.
-    /// This ensures they don't all reach their TTL (expiration) at the exact same moment.
+    /// Dispatches to sequential (staggered) or concurrent creation based on
+    /// `warmup_concurrency`, then returns all created browsers to the pool.
     async fn warmup_internal(&self, count: usize) -> Result<()> {
-        log::debug!(" Starting internal warmup process for {} browsers", count);
+        let concurrency = self
+            .inner
+            .config()
+            .warmup_concurrency
+            .clamp(1, count.max(1));
 
-        // STAGGER CONFIGURATION
-        // We wait this long between creations to distribute expiration times
-        let stagger_interval = Duration::from_secs(30);
+        log::debug!(
+            " Starting internal warmup process for {} browsers (concurrency: {})",
+            count,
+            concurrency
+        );
 
-        let mut handles = Vec::new();
-        let mut created_count = 0;
-        let mut failed_count = 0;
+        let handles = if concurrency > 1 {
+            self.warmup_concurrent(count, concurrency).await
+        } else {
+            self.warmup_sequential(count).await
+        };
 
-        for i in 0..count {
-            log::debug!(" Creating startup browser instance {}/{}", i + 1, count);
-
-            // Per-browser timeout (15s per browser is reasonable)
-            // This prevents one slow browser from blocking entire warmup
-            let browser_result = tokio::time::timeout(
-                Duration::from_secs(15),
-                tokio::task::spawn_blocking({
-                    let inner = Arc::clone(&self.inner);
-                    move || inner.create_browser_direct()
-                }),
-            )
-            .await;
+        log::info!(
+            " Warmup creation phase: {} created, {} failed",
+            handles.len(),
+            count - handles.len()
+        );
 
-            match browser_result {
-                Ok(Ok(Ok(tracked))) => {
-                    log::debug!(
-                        "✅ Browser {} created, performing validation test...",
-                        tracked.id()
-                    );
+        // Return all browsers to pool by dropping handles
+        log::debug!(" Returning {} warmup browsers to pool...", handles.len());
+        drop(handles);
 
-                    // Test the browser with actual navigation
-                    match tracked.browser().new_tab() {
-                        Ok(tab) => {
-                            log::trace!("✅ Browser {} test: new_tab() successful", tracked.id());
+        // Small delay to ensure Drop handlers complete
+        tokio::time::sleep(Duration::from_millis(300)).await;
 
-                            // Navigate to test page
-                            let nav_result = tab.navigate_to(
-                                "data:text/html,<html><body>Warmup test</body></html>",
-                            );
-                            if let Err(e) = nav_result {
-                                log::warn!(
-                                    "⚠️ Browser {} test navigation failed: {}",
-                                    tracked.id(),
-                                    e
-                                );
-                            } else {
-                                log::trace!(
-                                    "✅ Browser {} test: navigation successful",
-                                    tracked.id()
-                                );
-                            }
+        let final_stats = self.stats();
+        log::info!(
+            " Warmup internal completed - Pool: {}, Active: {}",
+            final_stats.available,
+            final_stats.active
+        );
 
-                            // Clean up test tab
-                            let _ = tab.close(true);
+        Ok(())
+    }
+
+    /// Create warmup browsers one at a time with a delay between them.
+    ///
+    /// This ensures they don't all reach their TTL (expiration) at the exact same moment.
+    async fn warmup_sequential(&self, count: usize) -> Vec<BrowserHandle<B>> {
+        // STAGGER CONFIGURATION
+        // We wait this long between creations to distribute expiration times
+        let stagger_interval = Duration::from_secs(30);
 
-                            // Keep handle so browser stays alive
-                            handles.push(BrowserHandle::new(tracked, Arc::clone(&self.inner)));
+        let mut handles = Vec::new();
 
-                            created_count += 1;
-                            log::info!(
-                                "✅ Browser instance {}/{} ready and validated",
-                                i + 1,
-                                count
-                            );
-                        }
-                        Err(e) => {
-                            failed_count += 1;
-                            log::error!(
-                                "❌ Browser {} validation test failed: {}",
-                                tracked.id(),
-                                e
-                            );
-
-                            // Remove from active tracking since it's broken
-                            self.inner.remove_from_active(tracked.id());
-                        }
-                    }
-                }
-                Ok(Ok(Err(e))) => {
-                    failed_count += 1;
-                    log::error!("❌ Failed to create browser {}/{}: {}", i + 1, count, e);
-                }
-                Ok(Err(e)) => {
-                    failed_count += 1;
-                    log::error!(
-                        "❌ Browser {}/{} creation task panicked: {:?}",
-                        i + 1,
-                        count,
-                        e
-                    );
-                }
-                Err(_) => {
-                    failed_count += 1;
-                    log::error!(
-                        "❌ Browser {}/{} creation timed out (15s limit)",
-                        i + 1,
-                        count
-                    );
-                }
+        for i in 0..count {
+            if let Some(handle) =
+                Self::create_warmup_browser(Arc::clone(&self.inner), i, count).await
+            {
+                handles.push(handle);
             }
 
             // === STAGGER LOGIC ===
@@ -1140,26 +2067,143 @@ impl BrowserPool {
             }
         }
 
-        log::info!(
-            " Warmup creation phase: {} created, {} failed",
-            created_count,
-            failed_count
-        );
+        handles
+    }
 
-        // Return all browsers to pool by dropping handles
-        log::debug!(" Returning {} warmup browsers to pool...", handles.len());
-        drop(handles);
+    /// Create warmup browsers in parallel, at most `concurrency` at a time.
+    ///
+    /// No stagger is applied; TTL jitter de-synchronizes expiry instead.
+    async fn warmup_concurrent(&self, count: usize, concurrency: usize) -> Vec<BrowserHandle<B>> {
+        if self.inner.config().ttl_jitter.is_zero() {
+            log::warn!(
+                "⚠️ Concurrent warmup without ttl_jitter - warmup browsers will expire at the same time"
+            );
+        }
 
-        // Small delay to ensure Drop handlers complete
-        tokio::time::sleep(Duration::from_millis(300)).await;
+        let permits = Arc::new(Semaphore::new(concurrency));
+
+        let tasks: Vec<_> = (0..count)
+            .map(|i| {
+                let inner = Arc::clone(&self.inner);
+                let permits = Arc::clone(&permits);
+
+                tokio::spawn(async move {
+                    let _permit = permits.acquire_owned().await.ok()?;
+                    Self::create_warmup_browser(inner, i, count).await
+                })
+            })
+            .collect();
+
+        let mut handles = Vec::with_capacity(count);
+        for task in tasks {
+            match task.await {
+                Ok(Some(handle)) => handles.push(handle),
+                Ok(None) => {}
+                Err(e) => log::error!("❌ Warmup task panicked: {:?}", e),
+            }
+        }
 
-        let final_stats = self.stats();
-        log::info!(
-            " Warmup internal completed - Pool: {}, Active: {}",
-            final_stats.available,
-            final_stats.active
+        handles
+    }
+
+    /// Create and validate one warmup browser.
+    ///
+    /// Creation and the validation test run on the blocking thread pool with
+    /// a 15s limit. Returns `None` (after logging) if any step fails.
+    async fn create_warmup_browser(
+        inner: Arc<BrowserPoolInner<B>>,
+        i: usize,
+        count: usize,
+    ) -> Option<BrowserHandle<B>> {
+        log::debug!(" Creating startup browser instance {}/{}", i + 1, count);
+
+        // Per-browser timeout (15s per browser is reasonable)
+        // This prevents one slow browser from blocking entire warmup
+        let browser_result = tokio::time::timeout(
+            Duration::from_secs(15),
+            tokio::task::spawn_blocking({
+                let inner = Arc::clone(&inner);
+                move || {
+                    let tracked = inner.create_browser_direct()?;
+                    Ok::<_, BrowserPoolError>((Self::validate_warmup_browser(&tracked), tracked))
+                }
+            }),
+        )
+        .await;
+
+        match browser_result {
+            Ok(Ok(Ok((Ok(()), tracked)))) => {
+                log::info!(
+                    "✅ Browser instance {}/{} ready and validated",
+                    i + 1,
+                    count
+                );
+
+                // Keep handle so browser stays alive
+                let slot = inner.checkout_queue.claim();
+                Some(BrowserHandle::new(tracked, inner, slot))
+            }
+            Ok(Ok(Ok((Err(e), tracked)))) => {
+                log::error!("❌ Browser {} validation test failed: {}", tracked.id(), e);
+
+                // Remove from active tracking since it's broken
+                if inner.remove_from_active(tracked.id()).is_some() {
+                    inner.lifetime.failed.fetch_add(1, Ordering::Relaxed);
+                }
+                None
+            }
+            Ok(Ok(Err(e))) => {
+                log::error!("❌ Failed to create browser {}/{}: {}", i + 1, count, e);
+                None
+            }
+            Ok(Err(e)) => {
+                log::error!(
+                    "❌ Browser {}/{} creation task panicked: {:?}",
+                    i + 1,
+                    count,
+                    e
+                );
+                None
+            }
+            Err(_) => {
+                log::error!(
+                    "❌ Browser {}/{} creation timed out (15s limit)",
+                    i + 1,
+                    count
+                );
+                None
+            }
+        }
+    }
+
+    /// Test a freshly created warmup browser with actual navigation (blocking).
+    ///
+    /// Navigation failures are only logged; a browser that cannot open a tab
+    /// is rejected. A tab that navigated successfully is kept as the
+    /// browser's standby tab.
+    fn validate_warmup_browser(tracked: &TrackedBrowser<B>) -> std::result::Result<(), String> {
+        log::debug!(
+            "✅ Browser {} created, performing validation test...",
+            tracked.id()
         );
 
+        let tab = tracked.browser().new_tab().map_err(|e| e.to_string())?;
+        log::trace!("✅ Browser {} test: new_tab() successful", tracked.id());
+
+        // Navigate to test page
+        let nav_result = tab.navigate("data:text/html,<html><body>Warmup test</body></html>");
+        if let Err(e) = nav_result {
+            log::warn!("⚠️ Browser {} test navigation failed: {}", tracked.id(), e);
+
+            // Clean up test tab
+            let _ = tab.close();
+        } else {
+            log::trace!("✅ Browser {} test: navigation successful", tracked.id());
+
+            // Keep the warmed-up test tab as the standby tab for the first render
+            tracked.store_reusable_tab(tab);
+        }
+
         Ok(())
     }
 
@@ -1170,6 +2214,10 @@ impl BrowserPool {
     /// - Removes unresponsive browsers after max_ping_failures
     /// - Retires browsers that exceed TTL
     /// - Spawns replacement browsers as needed
+    /// - Keeps at least [`min_pool_size`](BrowserPoolConfig::min_pool_size)
+    ///   browsers available
+    /// - Recycles the whole pool when every browser keeps failing (see
+    ///   [`recycle_after_failed_cycles`](BrowserPoolConfig::recycle_after_failed_cycles))
     ///
     /// # Critical Design Notes
     ///
@@ -1180,14 +2228,20 @@ impl BrowserPool {
     /// # Parameters
     ///
     /// * `inner` - Arc reference to pool state.
+    /// * `recycle_hook` - Called after each full pool recycle.
     ///
     /// # Returns
     ///
     /// JoinHandle for the background thread.
-    fn start_keep_alive(inner: Arc<BrowserPoolInner>) -> JoinHandle<()> {
+    fn start_keep_alive(
+        inner: Arc<BrowserPoolInner<B>>,
+        recycle_hook: Option<RecycleHook>,
+    ) -> JoinHandle<()> {
         let ping_interval = inner.config().ping_interval;
         let max_failures = inner.config().max_ping_failures;
+        let recycle_after = inner.config().recycle_after_failed_cycles;
         let browser_ttl = inner.config().browser_ttl;
+        let prefetch_interval = inner.config().prefetch_interval;
         let shutdown_signal = Arc::clone(inner.shutdown_signal());
 
         log::info!(
@@ -1199,10 +2253,17 @@ impl BrowserPool {
 
         thread::spawn(move || {
             log::info!(" Keep-alive thread started successfully");
+            inner.keep_alive_idle();
 
             // Track consecutive failures per browser ID
             let mut failure_counts: HashMap<u64, u32> = HashMap::new();
 
+            // When the prefetch list was last loaded (None = never)
+            let mut last_prefetch: Option<Instant> = None;
+
+            // Consecutive cycles in which every pinged browser failed
+            let mut failed_cycles: u32 = 0;
+
             loop {
                 // Wait for next ping interval OR shutdown signal (whichever comes first)
                 // Using condvar instead of sleep allows immediate wake-up on shutdown
@@ -1234,6 +2295,7 @@ impl BrowserPool {
                 }
 
                 log::trace!(" Keep-alive ping cycle starting...");
+                inner.keep_alive_cycle_started();
 
                 // Collect browsers to ping WITHOUT holding locks
                 // This is critical: we clone the list and release the lock
@@ -1245,8 +2307,10 @@ impl BrowserPool {
                 );
 
                 // Now ping browsers without holding any locks
+                let mut pinged = 0;
                 let mut to_remove = Vec::new();
                 let mut expired_browsers = Vec::new();
+                let mut healthy_browsers = Vec::new();
 
                 for (id, tracked) in browsers_to_ping {
                     // Check shutdown during ping loop (allows early exit)
@@ -1269,12 +2333,17 @@ impl BrowserPool {
 
                     // Perform health check (this is I/O, no locks held)
                     use crate::traits::Healthcheck;
-                    match tracked.ping() {
+                    pinged += 1;
+                    let result = tracked.ping();
+                    tracked.record_health(result.as_ref().err().map(ToString::to_string));
+                    match result {
                         Ok(_) => {
                             // Reset failure count on success
+                            tracked.set_ping_failures(0);
                             if failure_counts.remove(&id).is_some() {
                                 log::debug!("Browser {} ping successful, failure count reset", id);
                             }
+                            healthy_browsers.push(tracked);
                         }
                         Err(e) => {
                             // Only process failures if NOT shutting down
@@ -1282,6 +2351,7 @@ impl BrowserPool {
                             if !inner.is_shutting_down() {
                                 let failures = failure_counts.entry(id).or_insert(0);
                                 *failures += 1;
+                                tracked.set_ping_failures(*failures);
 
                                 log::warn!(
                                     "Browser {} ping failed (attempt {}/{}): {}",
@@ -1311,6 +2381,20 @@ impl BrowserPool {
                     break;
                 }
 
+                // Every browser failing together points at a systemic problem
+                // that one-by-one replacement won't fix: recycle everything
+                failed_cycles = next_failed_cycles(failed_cycles, pinged, healthy_browsers.len());
+                if recycle_after > 0 && failed_cycles >= recycle_after {
+                    let recycle = BrowserPoolInner::recycle(&inner, failed_cycles);
+                    failure_counts.clear();
+                    failed_cycles = 0;
+                    if let Some(hook) = &recycle_hook {
+                        hook(&recycle);
+                    }
+                    inner.keep_alive_cycle_finished();
+                    continue;
+                }
+
                 // Handle TTL retirements first (they need replacement browsers)
                 if !expired_browsers.is_empty() {
                     log::info!("Processing {} TTL-expired browsers", expired_browsers.len());
@@ -1343,6 +2427,11 @@ impl BrowserPool {
 
                     log::debug!("Pool size after cleanup: {}", inner.available_count());
 
+                    inner
+                        .lifetime
+                        .failed
+                        .fetch_add(actual_removed_count as u64, Ordering::Relaxed);
+
                     // Trigger replacement for the browsers we just removed
                     if actual_removed_count > 0 {
                         log::info!(
@@ -1356,7 +2445,39 @@ impl BrowserPool {
                     }
                 }
 
+                // Keep warm capacity for bursts, unless replacements already
+                // launching will restore it
+                let min_pool_size = inner.config().min_pool_size;
+                if min_pool_size > 0
+                    && !inner.is_draining()
+                    && inner.replacement_tasks_in_flight() == 0
+                {
+                    let missing = top_up_count(
+                        min_pool_size,
+                        inner.config().max_pool_size,
+                        inner.available_count(),
+                        inner.active_count(),
+                    );
+                    if missing > 0 {
+                        log::info!(
+                            "Pool below min_pool_size ({}/{}), launching {} browsers",
+                            inner.available_count(),
+                            min_pool_size,
+                            missing
+                        );
+                        BrowserPoolInner::spawn_replacement_creation(Arc::clone(&inner), missing);
+                    }
+                }
+
+                // Refresh HTTP caches with the configured prefetch URLs when due
+                let prefetch_due = last_prefetch.is_none_or(|at| at.elapsed() >= prefetch_interval);
+                if !inner.config().prefetch_urls.is_empty() && prefetch_due {
+                    Self::prefetch_browsers(&inner, &healthy_browsers);
+                    last_prefetch = Some(Instant::now());
+                }
+
                 // Log keep-alive cycle summary
+                inner.keep_alive_cycle_finished();
                 log::debug!(
                     "Keep-alive cycle complete - Active: {}, Pooled: {}, Tracking {} failure states",
                     inner.active_count(),
@@ -1369,6 +2490,53 @@ impl BrowserPool {
         })
     }
 
+    /// Load the configured prefetch URLs in each healthy browser.
+    ///
+    /// Populates each browser's HTTP cache and warms the JIT for pages that
+    /// are rendered repeatedly. Each browser uses one temporary tab.
+    /// Failures are logged and otherwise ignored; they never count as
+    /// health check failures.
+    ///
+    /// # Parameters
+    ///
+    /// * `inner` - Arc reference to pool state.
+    /// * `browsers` - Browsers that passed this cycle's ping.
+    fn prefetch_browsers(inner: &Arc<BrowserPoolInner<B>>, browsers: &[TrackedBrowser<B>]) {
+        let urls = &inner.config().prefetch_urls;
+
+        log::debug!(
+            "Prefetching {} URLs in {} browsers",
+            urls.len(),
+            browsers.len()
+        );
+
+        for tracked in browsers {
+            if inner.is_shutting_down() {
+                log::info!("Shutdown detected during prefetch, stopping");
+                return;
+            }
+
+            let tab = match tracked.browser().new_tab() {
+                Ok(tab) => tab,
+                Err(e) => {
+                    log::warn!("Browser {} prefetch skipped (new_tab): {}", tracked.id(), e);
+                    continue;
+                }
+            };
+
+            for url in urls {
+                match tab.navigate(url) {
+                    Ok(_) => log::trace!("Browser {} prefetched {}", tracked.id(), url),
+                    Err(e) => {
+                        log::warn!("Browser {} failed to prefetch {}: {}", tracked.id(), url, e)
+                    }
+                }
+            }
+
+            let _ = tab.close();
+        }
+    }
+
     /// Handle browser retirement due to TTL expiration.
     ///
     /// This function:
@@ -1386,7 +2554,7 @@ impl BrowserPool {
     /// * `expired_ids` - List of browser IDs that have exceeded TTL.
     /// * `failure_counts` - Mutable map of failure counts (updated to remove retired browsers).
     fn handle_browser_retirement(
-        inner: &Arc<BrowserPoolInner>,
+        inner: &Arc<BrowserPoolInner<B>>,
         expired_ids: Vec<u64>,
         failure_counts: &mut HashMap<u64, u32>,
     ) {
@@ -1408,6 +2576,10 @@ impl BrowserPool {
 
         // Remove from pool as well
         inner.remove_from_available(&expired_ids);
+        inner
+            .lifetime
+            .retired
+            .fetch_add(retired_count as u64, Ordering::Relaxed);
 
         log::debug!(
             "After retirement - Active: {}, Pooled: {}",
@@ -1427,6 +2599,83 @@ impl BrowserPool {
         }
     }
 
+    /// Stop handing out browsers and wait for checked-out ones to return.
+    ///
+    /// From this call on, checkouts fail with
+    /// [`BrowserPoolError::ShuttingDown`], including those waiting in the
+    /// checkout queue. Renders already holding a browser run to completion.
+    /// The pool keeps its browsers and background tasks; shut it down once
+    /// the returned future resolves.
+    ///
+    /// The future does not borrow the pool, so the lock around a
+    /// [`SharedBrowserPool`](crate::SharedBrowserPool) can be released
+    /// before awaiting it.
+    ///
+    /// # Returns
+    ///
+    /// A future resolving to `true` once every [`BrowserHandle`] has been
+    /// dropped, or `false` if some were still out after `timeout`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // On SIGTERM: let in-flight PDFs finish, then shut down
+    /// let drained = pool.lock().unwrap().start_drain(Duration::from_secs(25));
+    /// if !drained.await {
+    ///     log::warn!("Shutting down with renders still in flight");
+    /// }
+    /// pool.lock().unwrap().shutdown();
+    /// ```
+    pub fn start_drain(&self, timeout: Duration) -> impl Future<Output = bool> + Send + use<B> {
+        let inner = Arc::clone(&self.inner);
+        if !inner.is_draining() {
+            log::info!(
+                "Draining browser pool ({} browsers checked out)...",
+                inner.checkout_queue().checked_out()
+            );
+            inner.set_draining();
+        }
+        async move {
+            let drained = tokio::time::timeout(timeout, inner.checkout_queue().wait_idle())
+                .await
+                .is_ok();
+            if drained {
+                log::info!("Browser pool drained, all browsers returned");
+            } else {
+                log::warn!(
+                    "Drain timed out after {:?} with {} browsers still checked out",
+                    timeout,
+                    inner.checkout_queue().checked_out()
+                );
+            }
+            drained
+        }
+    }
+
+    /// Drain the pool, then shut it down.
+    ///
+    /// Waits up to `timeout` for checked-out browsers as in
+    /// [`start_drain()`](Self::start_drain), then runs
+    /// [`shutdown_async()`](Self::shutdown_async) whether or not they all
+    /// came back. Use this for graceful termination (e.g. on SIGTERM in
+    /// Kubernetes) so in-flight PDFs finish before the process exits.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every browser was returned before shutdown.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Stay within the pod's terminationGracePeriodSeconds
+    /// pool.drain(Duration::from_secs(25)).await;
+    /// ```
+    pub async fn drain(&mut self, timeout: Duration) -> bool {
+        let drained = self.start_drain(timeout).await;
+        self.shutdown_async().await;
+        drained
+    }
+
     /// Asynchronously shutdown the pool (recommended method).
     ///
     /// This is the preferred shutdown method as it can properly await
@@ -1580,12 +2829,12 @@ impl BrowserPool {
     /// This is primarily for internal use and testing.
     #[doc(hidden)]
     #[allow(dead_code)]
-    pub(crate) fn inner(&self) -> &Arc<BrowserPoolInner> {
+    pub(crate) fn inner(&self) -> &Arc<BrowserPoolInner<B>> {
         &self.inner
     }
 }
 
-impl Drop for BrowserPool {
+impl<B: PooledBrowser> Drop for Pool<B> {
     /// Automatic cleanup when pool is dropped.
     ///
     /// This ensures resources are released even if shutdown wasn't called explicitly.
@@ -1630,15 +2879,36 @@ impl Drop for BrowserPool {
 ///     .enable_keep_alive(true)
 ///     .build()?;
 /// ```
-pub struct BrowserPoolBuilder {
+pub struct BrowserPoolBuilder<B: PooledBrowser = Browser> {
     /// Optional configuration (uses default if not provided).
     config: Option<BrowserPoolConfig>,
 
     /// Browser factory (required).
-    factory: Option<Box<dyn BrowserFactory>>,
+    factory: Option<Box<dyn BrowserFactory<B>>>,
 
     /// Whether to enable keep-alive thread (default: true).
     enable_keep_alive: bool,
+
+    /// Called after each full pool recycle (optional).
+    recycle_hook: Option<RecycleHook>,
+
+    /// Response cache backend (default: in memory).
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pdf_cache: Option<Box<dyn crate::service::PdfCache>>,
+
+    /// Job store backend (default: in memory).
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    job_store: Option<Box<dyn crate::service::JobStore>>,
 }
 
 impl BrowserPoolBuilder {
@@ -1650,10 +2920,43 @@ impl BrowserPoolBuilder {
     /// let builder = BrowserPoolBuilder::new();
     /// ```
     pub fn new() -> Self {
+        Self::with_backend()
+    }
+}
+
+impl<B: PooledBrowser> BrowserPoolBuilder<B> {
+    /// Create a builder for a pool of another [`PooledBrowser`] backend.
+    ///
+    /// The backend is named as the type parameter; its factory must
+    /// implement [`BrowserFactory<B>`](BrowserFactory).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let pool = BrowserPoolBuilder::<RemoteBrowser>::with_backend()
+    ///     .factory(Box::new(RemoteBrowserFactory::new(endpoints)))
+    ///     .build()?;
+    /// ```
+    pub fn with_backend() -> Self {
         Self {
             config: None,
             factory: None,
             enable_keep_alive: true,
+            recycle_hook: None,
+            #[cfg(any(
+                feature = "actix-integration",
+                feature = "rocket-integration",
+                feature = "axum-integration",
+                feature = "cli"
+            ))]
+            pdf_cache: None,
+            #[cfg(any(
+                feature = "actix-integration",
+                feature = "rocket-integration",
+                feature = "axum-integration",
+                feature = "cli"
+            ))]
+            job_store: None,
         }
     }
 
@@ -1698,11 +3001,46 @@ impl BrowserPoolBuilder {
     ///     .factory(Box::new(ChromeBrowserFactory::with_defaults()))
     ///     .build()?;
     /// ```
-    pub fn factory(mut self, factory: Box<dyn BrowserFactory>) -> Self {
+    pub fn factory(mut self, factory: Box<dyn BrowserFactory<B>>) -> Self {
         self.factory = Some(factory);
         self
     }
 
+    /// Set a closure as the browser factory.
+    ///
+    /// Shorthand for one-off launch logic (extra flags, per-environment
+    /// tweaks) that would otherwise need a type implementing
+    /// [`BrowserFactory`]. The closure is called for every browser the
+    /// pool launches, possibly from several threads at once. Replaces any
+    /// factory set earlier.
+    ///
+    /// # Parameters
+    ///
+    /// * `create` - Launches a browser, failing with
+    ///   [`BrowserPoolError::BrowserCreation`] if it can't.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use headless_chrome::Browser;
+    /// use html2pdf_api::{BrowserPool, BrowserPoolError, create_chrome_options};
+    ///
+    /// let pool = BrowserPool::builder()
+    ///     .factory_fn(|| {
+    ///         let mut options = create_chrome_options(None)
+    ///             .map_err(|e| BrowserPoolError::Configuration(e.to_string()))?;
+    ///         options.window_size = Some((1920, 1080));
+    ///         Browser::new(options).map_err(|e| BrowserPoolError::BrowserCreation(e.to_string()))
+    ///     })
+    ///     .build()?;
+    /// ```
+    pub fn factory_fn<F>(self, create: F) -> Self
+    where
+        F: Fn() -> Result<B> + Send + Sync + 'static,
+    {
+        self.factory(Box::new(FnBrowserFactory(create)))
+    }
+
     /// Enable or disable keep-alive thread.
     ///
     /// Keep-alive should be disabled only for testing.
@@ -1726,6 +3064,115 @@ impl BrowserPoolBuilder {
         self
     }
 
+    /// Set a hook called after each full pool recycle.
+    ///
+    /// Recycles happen when
+    /// [`recycle_after_failed_cycles`](BrowserPoolConfig::recycle_after_failed_cycles)
+    /// is set and every browser keeps failing its health checks. The hook
+    /// runs on the keep-alive thread, so it should return quickly (e.g.
+    /// bump a metric or send an alert).
+    ///
+    /// # Parameters
+    ///
+    /// * `hook` - Called with the details of each recycle.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let pool = BrowserPool::builder()
+    ///     .config(
+    ///         BrowserPoolConfigBuilder::new()
+    ///             .recycle_after_failed_cycles(3)
+    ///             .build()?
+    ///     )
+    ///     .factory(Box::new(ChromeBrowserFactory::with_defaults()))
+    ///     .on_recycle(|recycle| {
+    ///         log::error!("pool recycled, {} browsers discarded", recycle.discarded);
+    ///     })
+    ///     .build()?;
+    /// ```
+    pub fn on_recycle<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&PoolRecycle) + Send + Sync + 'static,
+    {
+        self.recycle_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Set where the response cache stores PDFs.
+    ///
+    /// Defaults to a [`MemoryPdfCache`](crate::service::MemoryPdfCache)
+    /// of [`response_cache_max_mb`](crate::BrowserPoolConfig::response_cache_max_mb).
+    /// Only used when
+    /// [`response_cache_ttl`](crate::BrowserPoolConfig::response_cache_ttl)
+    /// is set.
+    ///
+    /// # Parameters
+    ///
+    /// * `cache` - The backend, e.g. a shared
+    ///   [`RedisPdfCache`](crate::service::RedisPdfCache).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::service::RedisPdfCache;
+    ///
+    /// let pool = BrowserPool::builder()
+    ///     .config(
+    ///         BrowserPoolConfigBuilder::new()
+    ///             .response_cache_ttl(Duration::from_secs(300))
+    ///             .build()?
+    ///     )
+    ///     .factory(Box::new(ChromeBrowserFactory::with_defaults()))
+    ///     .pdf_cache(Box::new(RedisPdfCache::new("redis://cache:6379")?))
+    ///     .build()?;
+    /// ```
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub fn pdf_cache(mut self, cache: Box<dyn crate::service::PdfCache>) -> Self {
+        self.pdf_cache = Some(cache);
+        self
+    }
+
+    /// Set where background jobs are queued and their results kept.
+    ///
+    /// Defaults to a [`MemoryJobStore`](crate::service::MemoryJobStore),
+    /// which loses queued jobs and results on restart. With a store shared
+    /// between instances, also run
+    /// [`spawn_job_worker`](crate::service::spawn_job_worker) on each.
+    ///
+    /// # Parameters
+    ///
+    /// * `store` - The backend, e.g. a shared
+    ///   [`RedisJobStore`](crate::service::RedisJobStore).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use html2pdf_api::service::{RedisJobStore, spawn_job_worker};
+    ///
+    /// let pool = BrowserPool::builder()
+    ///     .factory(Box::new(ChromeBrowserFactory::with_defaults()))
+    ///     .job_store(Box::new(RedisJobStore::new("redis://jobs:6379")?))
+    ///     .build()?
+    ///     .into_shared();
+    /// spawn_job_worker(&pool)?;
+    /// ```
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub fn job_store(mut self, store: Box<dyn crate::service::JobStore>) -> Self {
+        self.job_store = Some(store);
+        self
+    }
+
     /// Build the browser pool.
     ///
     /// # Errors
@@ -1743,7 +3190,7 @@ impl BrowserPoolBuilder {
     ///     .factory(Box::new(ChromeBrowserFactory::with_defaults()))
     ///     .build()?;
     /// ```
-    pub fn build(self) -> Result<BrowserPool> {
+    pub fn build(self) -> Result<Pool<B>> {
         let config = self.config.unwrap_or_default();
         let factory = self.factory.ok_or_else(|| {
             BrowserPoolError::Configuration("No browser factory provided".to_string())
@@ -1757,7 +3204,10 @@ impl BrowserPoolBuilder {
         // Start keep-alive thread if enabled
         let keep_alive_handle = if self.enable_keep_alive {
             log::info!(" Starting keep-alive monitoring thread");
-            Some(BrowserPool::start_keep_alive(Arc::clone(&inner)))
+            Some(BrowserPool::<B>::start_keep_alive(
+                Arc::clone(&inner),
+                self.recycle_hook,
+            ))
         } else {
             log::warn!("⚠️ Keep-alive thread disabled (should only be used for testing)");
             None
@@ -1765,9 +3215,82 @@ impl BrowserPoolBuilder {
 
         log::info!("✅ Browser pool built successfully");
 
-        Ok(BrowserPool {
+        #[cfg(any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        ))]
+        let idempotency = crate::service::IdempotencyCache::new(inner.config().idempotency_window);
+        #[cfg(any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        ))]
+        let inflight = Arc::new(crate::service::InflightRenders::new(
+            inner.config().coalesce_requests,
+        ));
+        #[cfg(any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        ))]
+        let response_cache = Arc::new(crate::service::ResponseCache::new(
+            inner.config().response_cache_ttl,
+            self.pdf_cache.unwrap_or_else(|| {
+                Box::new(crate::service::MemoryPdfCache::new(
+                    inner.config().response_cache_max_mb,
+                ))
+            }),
+        ));
+        #[cfg(any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        ))]
+        let jobs = crate::service::JobRegistry::new(
+            inner.config().job_retention,
+            inner.config().max_pending_jobs,
+            inner.config().max_pool_size * inner.config().tabs_per_browser,
+            self.job_store
+                .unwrap_or_else(|| Box::new(crate::service::MemoryJobStore::new())),
+        );
+
+        Ok(Pool {
             inner,
             keep_alive_handle,
+            usage: UsageTracker::new(),
+            #[cfg(any(
+                feature = "actix-integration",
+                feature = "rocket-integration",
+                feature = "axum-integration",
+                feature = "cli"
+            ))]
+            idempotency,
+            #[cfg(any(
+                feature = "actix-integration",
+                feature = "rocket-integration",
+                feature = "axum-integration",
+                feature = "cli"
+            ))]
+            inflight,
+            #[cfg(any(
+                feature = "actix-integration",
+                feature = "rocket-integration",
+                feature = "axum-integration",
+                feature = "cli"
+            ))]
+            response_cache,
+            #[cfg(any(
+                feature = "actix-integration",
+                feature = "rocket-integration",
+                feature = "axum-integration",
+                feature = "cli"
+            ))]
+            jobs,
         })
     }
 }
@@ -1797,7 +3320,17 @@ impl Default for BrowserPoolBuilder {
 /// - `BROWSER_WARMUP_COUNT`: Warmup browser count (default: 3)
 /// - `BROWSER_TTL_SECONDS`: Browser TTL in seconds (default: 3600)
 /// - `BROWSER_WARMUP_TIMEOUT_SECONDS`: Warmup timeout (default: 60)
+/// - `CHROME_WS_URL`: Comma-separated DevTools WebSocket URLs of running
+///   browsers to connect to instead of launching Chrome (optional)
 /// - `CHROME_PATH`: Custom Chrome binary path (optional)
+/// - `CHROME_NO_SANDBOX`: Disable Chrome's sandbox (default: false)
+/// - `CHROME_CA_BUNDLE`: PEM file of extra CAs to trust (optional)
+/// - `CHROME_FONT_DIR`: Directory of extra fonts (optional)
+/// - `CHROME_STEALTH`: Hide automation signals (default: false)
+/// - `CHROME_USER_AGENT`: User agent sent in stealth mode (optional)
+/// - `CHROME_LANGUAGES`: Languages sent in stealth mode (default: en-US,en)
+/// - `PDF_SIGN_CERT`, `PDF_SIGN_KEY`: PEM certificate chain and key that
+///   sign every PDF (optional, `pdf-signing` feature)
 ///
 /// # Returns
 ///
@@ -1806,6 +3339,10 @@ impl Default for BrowserPoolBuilder {
 /// # Errors
 ///
 /// - Returns error if configuration is invalid.
+/// - Returns error if `CHROME_CA_BUNDLE` is set but can't be loaded.
+/// - Returns error if `CHROME_FONT_DIR` is set but holds no readable fonts.
+/// - Returns error if `PDF_SIGN_CERT` or `PDF_SIGN_KEY` is set but the
+///   signer can't be loaded.
 /// - Returns error if warmup fails.
 ///
 /// # Example
@@ -1825,15 +3362,23 @@ impl Default for BrowserPoolBuilder {
 #[cfg(feature = "env-config")]
 pub async fn init_browser_pool() -> Result<Arc<Mutex<BrowserPool>>> {
     use crate::config::env::{chrome_path_from_env, from_env};
-    use crate::factory::ChromeBrowserFactory;
+    use crate::factory::{
+        ChromeBrowserFactory, FontDirectory, RemoteCdpBrowserFactory, SandboxOptions,
+        StealthOptions, TrustedCa,
+    };
 
     log::info!("Initializing browser pool from environment...");
 
     // Load configuration from environment
     let config = from_env()?;
 
-    // Get optional Chrome path
+    // Get optional Chrome path, sandbox settings, extra CAs, fonts, and
+    // stealth settings
     let chrome_path = chrome_path_from_env();
+    let sandbox = SandboxOptions::from_env();
+    let trusted_ca = TrustedCa::from_env()?;
+    let fonts = FontDirectory::from_env()?;
+    let stealth = StealthOptions::from_env();
 
     log::info!("Pool configuration from environment:");
     log::info!("   - Max pool size: {}", config.max_pool_size);
@@ -1848,30 +3393,122 @@ pub async fn init_browser_pool() -> Result<Arc<Mutex<BrowserPool>>> {
         "   - Chrome path: {}",
         chrome_path.as_deref().unwrap_or("auto-detect")
     );
+    log::info!(
+        "   - Chrome sandbox: {}",
+        if sandbox.is_disabled() {
+            "disabled"
+        } else {
+            "enabled"
+        }
+    );
 
-    // Create factory based on whether custom path is provided
-    let factory: Box<dyn BrowserFactory> = match chrome_path {
-        Some(path) => {
-            log::info!("Using custom Chrome path: {}", path);
-            Box::new(ChromeBrowserFactory::with_path(path))
+    // Sign every PDF, if configured
+    #[cfg(all(
+        feature = "pdf-signing",
+        any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        )
+    ))]
+    let config = match crate::service::PdfSigner::from_env()? {
+        Some(signer) => {
+            log::info!("   - PDF signing: {:?}", signer);
+            let mut config = config;
+            config.post_processors.push(Arc::new(signer));
+            config
         }
-        None => {
-            log::info!("Using auto-detected Chrome browser");
-            Box::new(ChromeBrowserFactory::with_defaults())
+        None => config,
+    };
+
+    let factory: Box<dyn BrowserFactory> = match RemoteCdpBrowserFactory::from_env() {
+        Some(remote) => {
+            log::info!(
+                "   - Remote Chrome endpoints: {} (launch settings ignored)",
+                remote.endpoints().len()
+            );
+            match stealth {
+                Some(stealth) => Box::new(remote.stealth(stealth)),
+                None => Box::new(remote),
+            }
         }
+        None => Box::new(ChromeBrowserFactory::with_stealth(
+            chrome_path,
+            sandbox,
+            trusted_ca,
+            fonts,
+            stealth,
+        )),
     };
 
     // Create browser pool with Chrome factory
     log::debug!("Building browser pool...");
-    let pool = BrowserPool::builder()
+    let builder = BrowserPool::builder()
         .config(config.clone())
         .factory(factory)
-        .enable_keep_alive(true)
-        .build()
-        .map_err(|e| {
-            log::error!("❌ Failed to create browser pool: {}", e);
-            e
-        })?;
+        .enable_keep_alive(true);
+
+    // Share cached PDFs between instances through Redis, if configured
+    #[cfg(all(
+        feature = "redis-cache",
+        any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        )
+    ))]
+    let builder = match crate::service::RedisPdfCache::from_env()? {
+        Some(cache) => {
+            log::info!("   - Response cache backend: Redis");
+            builder.pdf_cache(Box::new(cache))
+        }
+        None => builder,
+    };
+
+    // Queue jobs and keep their results in Redis, if configured
+    #[cfg(all(
+        feature = "redis-jobs",
+        any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        )
+    ))]
+    let redis_jobs = crate::service::RedisJobStore::from_env()?;
+    #[cfg(all(
+        feature = "redis-jobs",
+        any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        )
+    ))]
+    let redis_jobs_configured = redis_jobs.is_some();
+    #[cfg(all(
+        feature = "redis-jobs",
+        any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        )
+    ))]
+    let builder = match redis_jobs {
+        Some(store) => {
+            log::info!("   - Job store backend: Redis");
+            builder.job_store(Box::new(store))
+        }
+        None => builder,
+    };
+
+    let pool = builder.build().map_err(|e| {
+        log::error!("❌ Failed to create browser pool: {}", e);
+        e
+    })?;
 
     log::info!("✅ Browser pool created successfully");
 
@@ -1893,7 +3530,25 @@ pub async fn init_browser_pool() -> Result<Arc<Mutex<BrowserPool>>> {
         stats.total
     );
 
-    Ok(pool.into_shared())
+    let pool = pool.into_shared();
+
+    // Render jobs queued by other instances or before a restart
+    #[cfg(all(
+        feature = "redis-jobs",
+        any(
+            feature = "actix-integration",
+            feature = "rocket-integration",
+            feature = "axum-integration",
+            feature = "cli"
+        )
+    ))]
+    if redis_jobs_configured {
+        crate::service::spawn_job_worker(&pool)
+            .map_err(|e| BrowserPoolError::Configuration(e.to_string()))?;
+        log::info!("✅ Job worker started");
+    }
+
+    Ok(pool)
 }
 
 // ============================================================================
@@ -1954,4 +3609,495 @@ mod tests {
         let builder = BrowserPoolBuilder::new().enable_keep_alive(false);
         assert!(!builder.enable_keep_alive);
     }
+
+    /// Verifies shard count is bounded and pool slots are capped across shards.
+    #[test]
+    fn test_sharded_available_slots() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let config = crate::config::BrowserPoolConfigBuilder::new()
+            .max_pool_size(2)
+            .warmup_count(0)
+            .build()
+            .unwrap();
+        let inner =
+            BrowserPoolInner::new(config, Box::new(MockBrowserFactory::always_fails("unused")));
+
+        assert_eq!(inner.shards.len(), 2);
+        assert!(std::ptr::eq(inner.shard_for(1), inner.shard_for(3)));
+
+        assert_eq!(inner.reserve_available_slot(), Some(1));
+        assert_eq!(inner.reserve_available_slot(), Some(2));
+        assert_eq!(inner.reserve_available_slot(), None, "Pool is full");
+        assert_eq!(inner.available_count(), 2);
+        assert_eq!(inner.active_count(), 0);
+    }
+
+    /// Verifies keep-alive cycle timing as reported by the debug snapshot.
+    #[test]
+    fn test_keep_alive_status() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let config = crate::config::BrowserPoolConfigBuilder::new()
+            .ping_interval(Duration::from_secs(15))
+            .build()
+            .unwrap();
+        let inner =
+            BrowserPoolInner::new(config, Box::new(MockBrowserFactory::always_fails("unused")));
+
+        let status = inner.keep_alive_status();
+        assert_eq!(status.cycles, 0);
+        assert_eq!(status.last_cycle_started, None);
+        assert_eq!(status.next_cycle_in, None);
+
+        inner.keep_alive_idle();
+        assert!(inner.keep_alive_status().next_cycle_in.unwrap() <= Duration::from_secs(15));
+
+        inner.keep_alive_cycle_started();
+        let status = inner.keep_alive_status();
+        assert!(status.last_cycle_started.is_some());
+        assert_eq!(status.next_cycle_in, None, "No next cycle mid-cycle");
+
+        inner.keep_alive_cycle_finished();
+        let status = inner.keep_alive_status();
+        assert_eq!(status.cycles, 1);
+        assert!(status.last_cycle_duration.is_some());
+        assert!(status.next_cycle_in.is_some());
+    }
+
+    /// Verifies the keep-alive thread fills the pool up to `min_pool_size`.
+    #[test]
+    fn test_min_pool_size_top_up() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let pool = BrowserPoolBuilder::<FakeBrowser>::with_backend()
+            .config(
+                crate::BrowserPoolConfigBuilder::new()
+                    .warmup_count(0)
+                    .min_pool_size(2)
+                    .ping_interval(Duration::from_millis(20))
+                    .build()
+                    .unwrap(),
+            )
+            .factory_fn(|| Ok(FakeBrowser::default()))
+            .build()
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while pool.stats().available < 2 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(pool.stats().available, 2);
+        assert_eq!(pool.stats().total, 2);
+    }
+
+    /// Verifies a browser is retired and replaced after `max_browser_uses`.
+    #[test]
+    fn test_max_browser_uses() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let pool = BrowserPoolBuilder::<FakeBrowser>::with_backend()
+            .config(
+                crate::BrowserPoolConfigBuilder::new()
+                    .warmup_count(0)
+                    .max_browser_uses(2)
+                    .build()
+                    .unwrap(),
+            )
+            .factory_fn(|| Ok(FakeBrowser::default()))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let first = pool.get().unwrap().id();
+        assert_eq!(pool.get().unwrap().id(), first, "Reused below the ceiling");
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while pool.stats().available == 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(pool.stats().available, 1);
+
+        let detailed = pool.detailed_stats();
+        assert_eq!(detailed.total_created, 2);
+        assert_eq!(detailed.total_retired, 1);
+        assert_eq!(detailed.total_failed, 0);
+        assert_eq!(detailed.browsers.len(), 1);
+        assert_eq!(detailed.browsers[0].state, BrowserState::Idle);
+        assert_eq!(detailed.browsers[0].uses, 0);
+
+        let handle = pool.get().unwrap();
+        assert_ne!(handle.id(), first, "Replaced at the ceiling");
+        assert_eq!(pool.detailed_stats().browsers[0].state, BrowserState::Busy);
+    }
+
+    /// Verifies draining refuses checkouts and waits for handles to return.
+    #[test]
+    fn test_drain() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let mut pool = BrowserPoolBuilder::<FakeBrowser>::with_backend()
+            .config(
+                crate::BrowserPoolConfigBuilder::new()
+                    .warmup_count(0)
+                    .build()
+                    .unwrap(),
+            )
+            .factory_fn(|| Ok(FakeBrowser::default()))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let handle = pool.get().unwrap();
+        let drained_early = runtime.block_on(pool.start_drain(Duration::from_millis(50)));
+        assert!(!drained_early, "Handle still out");
+        assert!(pool.is_draining());
+        assert!(matches!(pool.get(), Err(BrowserPoolError::ShuttingDown)));
+        assert!(matches!(
+            pool.get_many(1),
+            Err(BrowserPoolError::ShuttingDown)
+        ));
+
+        let drained = pool.start_drain(Duration::from_secs(5));
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(handle);
+        });
+        assert!(runtime.block_on(drained));
+        assert!(runtime.block_on(pool.drain(Duration::from_secs(1))));
+        assert!(pool.inner().is_shutting_down());
+    }
+
+    /// Verifies how many browsers are launched to reach `min_pool_size`.
+    #[test]
+    fn test_top_up_count() {
+        assert_eq!(top_up_count(0, 5, 0, 0), 0, "Disabled");
+        assert_eq!(top_up_count(3, 5, 1, 1), 2);
+        assert_eq!(top_up_count(3, 5, 0, 4), 1, "Capped by max_pool_size");
+        assert_eq!(top_up_count(2, 5, 3, 3), 0, "Already enough available");
+    }
+
+    /// Verifies a cycle only counts as failed when browsers were pinged and
+    /// none passed.
+    #[test]
+    fn test_next_failed_cycles() {
+        assert_eq!(next_failed_cycles(0, 3, 0), 1);
+        assert_eq!(next_failed_cycles(2, 1, 0), 3);
+        assert_eq!(next_failed_cycles(2, 3, 1), 0, "One healthy browser resets");
+        assert_eq!(
+            next_failed_cycles(2, 0, 0),
+            2,
+            "Empty cycle keeps the count"
+        );
+    }
+
+    /// Verifies a recycle is counted and relaunches `warmup_count` browsers.
+    #[test]
+    fn test_recycle() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let config = crate::config::BrowserPoolConfigBuilder::new()
+            .warmup_count(2)
+            .recycle_after_failed_cycles(3)
+            .build()
+            .unwrap();
+        let inner = BrowserPoolInner::new(
+            config,
+            Box::new(MockBrowserFactory::always_fails("no chrome")),
+        );
+        assert_eq!(inner.keep_alive_status().recycles, 0);
+
+        let recycle = BrowserPoolInner::recycle(&inner, 3);
+        assert_eq!(
+            recycle,
+            PoolRecycle {
+                discarded: 0,
+                failed_cycles: 3,
+                relaunching: 2,
+            }
+        );
+        let status = inner.keep_alive_status();
+        assert_eq!(status.recycles, 1);
+        assert!(status.last_recycle.is_some());
+        inner.abort_replacement_tasks();
+    }
+
+    /// Verifies the debug snapshot of an empty pool.
+    #[test]
+    fn test_debug_snapshot_empty_pool() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let pool = BrowserPool::builder()
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let snapshot = pool.debug_snapshot();
+        assert!(snapshot.browsers.is_empty());
+        assert_eq!(snapshot.max_pool_size, pool.config().max_pool_size);
+        assert_eq!(snapshot.replacement_tasks, 0);
+        assert!(snapshot.keep_alive.is_none(), "Keep-alive disabled");
+        assert!(!snapshot.shutting_down);
+    }
+
+    /// Verifies the pool reports warmup only once it has finished.
+    #[test]
+    fn test_is_warmed_up() {
+        use crate::BrowserPoolConfigBuilder;
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let pool = BrowserPool::builder()
+            .config(
+                BrowserPoolConfigBuilder::new()
+                    .warmup_count(0)
+                    .build()
+                    .unwrap(),
+            )
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        assert!(!pool.is_warmed_up());
+        runtime.block_on(pool.warmup()).unwrap();
+        assert!(pool.is_warmed_up());
+    }
+
+    /// Verifies a closure can stand in for a factory type.
+    #[test]
+    fn test_builder_factory_fn() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&calls);
+        let pool = BrowserPool::builder()
+            .factory_fn(move || {
+                counted.fetch_add(1, Ordering::SeqCst);
+                Err(BrowserPoolError::BrowserCreation("no chrome".to_string()))
+            })
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        match pool.get() {
+            Err(BrowserPoolError::BrowserCreation(msg)) => assert_eq!(msg, "no chrome"),
+            other => panic!("expected BrowserCreation, got {:?}", other.err()),
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    /// A browser backend that never touches Chrome.
+    #[derive(Default)]
+    struct FakeBrowser {
+        tabs: AtomicUsize,
+    }
+
+    #[derive(Default)]
+    struct FakeTab {
+        url: Mutex<String>,
+    }
+
+    impl PooledBrowser for FakeBrowser {
+        type Tab = FakeTab;
+
+        fn new_tab(&self) -> Result<Arc<FakeTab>> {
+            self.tabs.fetch_add(1, Ordering::SeqCst);
+            Ok(Arc::new(FakeTab::default()))
+        }
+
+        fn validate(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn ping(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn deep_check(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl BrowserTab for FakeTab {
+        fn navigate(&self, url: &str) -> Result<()> {
+            *self.url.lock().unwrap() = url.to_string();
+            Ok(())
+        }
+
+        fn print_to_pdf(
+            &self,
+            _options: Option<headless_chrome::types::PrintToPdfOptions>,
+        ) -> Result<Vec<u8>> {
+            Ok(format!("%PDF {}", self.url.lock().unwrap()).into_bytes())
+        }
+
+        fn screenshot(&self) -> Result<Vec<u8>> {
+            Ok(Vec::new())
+        }
+
+        fn close(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Verifies that the pool runs a non-Chrome backend end to end.
+    #[test]
+    fn test_custom_backend() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let pool = BrowserPoolBuilder::<FakeBrowser>::with_backend()
+            .factory_fn(|| Ok(FakeBrowser::default()))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let handle = pool.get().unwrap();
+        let tab = handle.checkout_tab().unwrap();
+        tab.navigate("https://example.com").unwrap();
+        assert_eq!(tab.print_to_pdf(None).unwrap(), b"%PDF https://example.com");
+        handle.release_tab(tab);
+        assert_eq!(handle.tabs.load(Ordering::SeqCst), 1);
+    }
+
+    /// Verifies async checkouts reach the factory and report its failure.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_async() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let factory = MockBrowserFactory::always_fails("no chrome");
+        let created = factory.counter();
+        let pool = BrowserPool::builder()
+            .factory(Box::new(factory))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let checkout = pool.get_async();
+        assert!(matches!(
+            checkout.await,
+            Err(BrowserPoolError::BrowserCreation(_))
+        ));
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.inner.checkout_queue().waiting(), 0);
+    }
+
+    /// Verifies checkout capacity counts every tab slot of every browser.
+    #[test]
+    fn test_tabs_per_browser_capacity() {
+        use crate::BrowserPoolConfigBuilder;
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let pool = BrowserPool::builder()
+            .config(
+                BrowserPoolConfigBuilder::new()
+                    .max_pool_size(2)
+                    .tabs_per_browser(3)
+                    .warmup_count(0)
+                    .build()
+                    .unwrap(),
+            )
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let slots = pool.inner.checkout_queue().reserve(10);
+        assert_eq!(slots.len(), 6);
+    }
+
+    /// Verifies bulk checkout handles zero requests and reports creation
+    /// failure when there was capacity.
+    #[test]
+    fn test_get_many() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let factory = MockBrowserFactory::always_fails("no chrome");
+        let created = factory.counter();
+        let mut pool = BrowserPool::builder()
+            .factory(Box::new(factory))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        assert!(pool.get_many(0).unwrap().is_empty());
+        assert_eq!(created.load(Ordering::SeqCst), 0);
+
+        assert!(matches!(
+            pool.get_many(3),
+            Err(BrowserPoolError::BrowserCreation(_))
+        ));
+        assert_eq!(created.load(Ordering::SeqCst), 1, "Stops at first failure");
+
+        pool.shutdown();
+        assert!(matches!(
+            pool.get_many(1),
+            Err(BrowserPoolError::ShuttingDown)
+        ));
+    }
+
+    /// Verifies a checkout in a saturated pool waits and then times out
+    /// when the checkout queue is enabled.
+    #[test]
+    fn test_get_with_priority_queue_timeout() {
+        use crate::BrowserPoolConfigBuilder;
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+
+        let pool = BrowserPool::builder()
+            .config(
+                BrowserPoolConfigBuilder::new()
+                    .max_pool_size(1)
+                    .warmup_count(0)
+                    .checkout_queue_timeout(Duration::from_millis(50))
+                    .build()
+                    .unwrap(),
+            )
+            .factory(Box::new(MockBrowserFactory::always_fails("no chrome")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let held = pool.inner.checkout_queue().reserve(1);
+        assert!(matches!(
+            pool.get_with_priority(Priority::High),
+            Err(BrowserPoolError::CheckoutTimeout(_))
+        ));
+        assert!(pool.get_many(1).unwrap().is_empty(), "No capacity left");
+
+        drop(held);
+        assert!(matches!(
+            pool.get_with_priority(Priority::Low),
+            Err(BrowserPoolError::BrowserCreation(_))
+        ));
+        assert_eq!(pool.debug_snapshot().queued_checkouts, 0);
+    }
 }
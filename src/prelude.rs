@@ -26,6 +26,7 @@
 //! | [`Result`] | Type alias for `Result<T, BrowserPoolError>` |
 //! | [`BrowserHandle`] | RAII handle for checked-out browsers |
 //! | [`PoolStats`] | Real-time pool statistics |
+//! | [`Priority`] | Checkout priority when the pool is saturated |
 //! | [`BrowserFactory`] | Trait for browser creation strategies |
 //! | [`ChromeBrowserFactory`] | Default Chrome/Chromium factory |
 //! | [`Healthcheck`] | Trait for browser health checking |
@@ -82,6 +83,7 @@
 //! | [`PdfServiceError`] | Service-level errors |
 //! | [`ErrorResponse`] | JSON error response format |
 //! | [`PoolStatsResponse`] | Pool statistics response |
+//! | [`PoolDebugResponse`] | Pool diagnostics response |
 //! | [`HealthResponse`] | Health check response |
 //!
 //! # Usage Examples
@@ -318,6 +320,16 @@ pub use crate::handle::BrowserHandle;
 /// See [`crate::stats::PoolStats`] for full documentation.
 pub use crate::stats::PoolStats;
 
+/// How urgently a checkout needs a browser in a saturated pool.
+///
+/// See [`crate::queue::Priority`] for full documentation.
+pub use crate::queue::Priority;
+
+/// Shared flag for cancelling a queued or running render.
+///
+/// See [`crate::cancel::CancelHandle`] for full documentation.
+pub use crate::cancel::CancelHandle;
+
 /// Trait for browser creation strategies.
 ///
 /// Implement this trait to customize how browsers are created.
@@ -413,7 +425,35 @@ pub use std::sync::Mutex;
 /// | `BROWSER_WARMUP_TIMEOUT_SECONDS` | u64 | 60 | Warmup timeout |
 /// | `BROWSER_PING_INTERVAL_SECONDS` | u64 | 15 | Health check interval |
 /// | `BROWSER_MAX_PING_FAILURES` | u32 | 3 | Max failures before removal |
+/// | `BROWSER_WARMUP_CONCURRENCY` | usize | 1 | Parallel warmup launches |
+/// | `BROWSER_TTL_JITTER_SECONDS` | u64 | 0 | Max random TTL reduction |
+/// | `BROWSER_REUSE_TABS` | bool | false | Reuse one tab per browser |
+/// | `BROWSER_TABS_PER_BROWSER` | usize | 1 | Concurrent renders per browser |
+/// | `BROWSER_DEEP_HEALTH_CHECK` | bool | false | Full tab check on checkout |
+/// | `BROWSER_PREFETCH_URLS` | String | empty | Comma-separated URLs to prefetch |
+/// | `BROWSER_PREFETCH_INTERVAL_SECONDS` | u64 | 600 | Prefetch interval |
+/// | `BROWSER_BLOCK_URLS` | String | empty | Comma-separated domains/URL patterns to block |
+/// | `BROWSER_MAX_REDIRECTS` | u32 | 20 | Redirects allowed per URL navigation |
+/// | `BROWSER_ALLOW_CROSS_ORIGIN_REDIRECTS` | bool | true | Allow redirects to other origins |
+/// | `BROWSER_ALLOWED_FILE_DIRS` | String | empty | Directories `file://` URLs may be rendered from, separated like `PATH` |
+/// | `BROWSER_DENY_PRIVATE_NETWORKS` | bool | false | Reject URLs on private networks and `localhost` |
+/// | `BROWSER_ALLOWED_HOSTS` | String | empty | Comma-separated hosts URL renders are limited to (with subdomains) |
+/// | `BROWSER_DENIED_HOSTS` | String | empty | Comma-separated hosts URL renders may never reach (with subdomains) |
+/// | `BROWSER_TRACE_DIR` | String | none | Directory for `trace` request performance traces |
+/// | `BROWSER_DAILY_RENDER_QUOTA` | u64 | 0 | Renders per API key per UTC day (0 = unlimited) |
+/// | `BROWSER_MONTHLY_RENDER_QUOTA` | u64 | 0 | Renders per API key per UTC month (0 = unlimited) |
+/// | `BROWSER_IDEMPOTENCY_WINDOW_SECONDS` | u64 | 300 | `Idempotency-Key` replay window (0 = disabled) |
+/// | `BROWSER_COALESCE_REQUESTS` | bool | false | Share renders among identical concurrent requests |
+/// | `BROWSER_CHECKOUT_QUEUE_TIMEOUT_SECONDS` | u64 | 0 | How long checkouts wait for a browser when the pool is saturated (0 = never wait) |
+/// | `BROWSER_JOB_RETENTION_SECONDS` | u64 | 3600 | How long async job results are kept (0 = async jobs disabled) |
+/// | `BROWSER_READY_MIN_AVAILABLE` | usize | 1 | Browsers a checkout must be able to get for the pool to be ready |
+/// | `BROWSER_READY_MAX_QUEUE_DEPTH` | usize | none | Queued checkouts above which the pool is not ready |
+/// | `BROWSER_READY_REQUIRE_WARMUP` | bool | false | Pool is not ready until warmup has finished |
+/// | `BROWSER_RECYCLE_AFTER_FAILED_CYCLES` | u32 | 0 | Keep-alive cycles with every browser failing before the pool recycles itself (0 = never) |
+/// | `BROWSER_MIN_FREE_DISK_MB` | u64 | 0 | Free space the temp volume needs for checkouts (0 = not checked) |
 /// | `CHROME_PATH` | String | auto | Custom Chrome path |
+/// | `CHROME_NO_SANDBOX` | bool | false | Disable Chrome's sandbox |
+/// | `CHROME_CA_BUNDLE` | String | none | PEM file of extra CAs to trust |
 ///
 /// # Example
 ///
@@ -540,6 +580,16 @@ pub use crate::service::ErrorResponse;
 ))]
 pub use crate::service::PoolStatsResponse;
 
+/// Pool diagnostics response for API endpoints.
+///
+/// See [`crate::service::PoolDebugResponse`] for full documentation.
+#[cfg(any(
+    feature = "actix-integration",
+    feature = "rocket-integration",
+    feature = "axum-integration"
+))]
+pub use crate::service::PoolDebugResponse;
+
 /// Health check response for API endpoints.
 ///
 /// See [`crate::service::HealthResponse`] for full documentation.
@@ -588,6 +638,7 @@ pub use crate::service::HealthResponse;
 /// | `pdf_from_url` | Handler for URL-to-PDF |
 /// | `pdf_from_html` | Handler for HTML-to-PDF |
 /// | `pool_stats` | Handler for pool statistics |
+/// | `usage_stats` | Handler for API key usage |
 /// | `health_check` | Handler for health check |
 /// | `readiness_check` | Handler for readiness check |
 /// | `SharedPool` | Type alias for `Arc<Mutex<BrowserPool>>` |
@@ -628,6 +679,7 @@ pub mod actix {
 /// | `pdf_from_url` | Handler for URL-to-PDF |
 /// | `pdf_from_html` | Handler for HTML-to-PDF |
 /// | `pool_stats` | Handler for pool statistics |
+/// | `usage_stats` | Handler for API key usage |
 /// | `health_check` | Handler for health check |
 /// | `readiness_check` | Handler for readiness check |
 /// | `SharedPool` | Type alias for `Arc<Mutex<BrowserPool>>` |
@@ -669,6 +721,7 @@ pub mod rocket {
 /// | `pdf_from_url` | Handler for URL-to-PDF |
 /// | `pdf_from_html` | Handler for HTML-to-PDF |
 /// | `pool_stats` | Handler for pool statistics |
+/// | `usage_stats` | Handler for API key usage |
 /// | `health_check` | Handler for health check |
 /// | `readiness_check` | Handler for readiness check |
 /// | `SharedPool` | Type alias for `Arc<Mutex<BrowserPool>>` |
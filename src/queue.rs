@@ -0,0 +1,540 @@
+//! Priority-ordered waiting for browsers when the pool is saturated.
+//!
+//! By default a checkout never waits: once `max_pool_size` browsers are
+//! checked out, [`BrowserPool::get()`](crate::BrowserPool::get) launches
+//! another one. With
+//! [`checkout_queue_timeout`](crate::BrowserPoolConfig::checkout_queue_timeout)
+//! set, a checkout beyond `max_pool_size` instead waits for a browser to be
+//! returned. Waiting checkouts are served by [`Priority`] first and arrival
+//! order second, so interactive renders jump ahead of queued batch jobs.
+//!
+//! ```text
+//!  checked out: 5/5           waiting
+//!  ┌──────────────┐    ┌──────┬──────┬──────┬──────┐
+//!  │ ■ ■ ■ ■ ■    │ ◀─ │ high │normal│ low  │ low  │
+//!  └──────────────┘    └──────┴──────┴──────┴──────┘
+//!         │ browser returned     ▲ served first
+//!         └──────────────────────┘
+//! ```
+//!
+//! Async callers wait with
+//! [`BrowserPool::get_async()`](crate::BrowserPool::get_async), which
+//! queues the same way but yields to the runtime instead of blocking a
+//! thread.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::pin::pin;
+use std::str::FromStr;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Notify;
+
+use crate::cancel::CancelHandle;
+use crate::error::{BrowserPoolError, Result};
+
+/// How often a waiting checkout with a [`CancelHandle`] checks it.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How urgently a checkout needs a browser.
+///
+/// Only matters while checkouts are waiting in the queue; an unsaturated
+/// pool serves every priority immediately.
+///
+/// # Example
+///
+/// ```rust
+/// use html2pdf_api::Priority;
+///
+/// let priority: Priority = "HIGH".parse().unwrap();
+/// assert_eq!(priority, Priority::High);
+/// assert_eq!(Priority::default(), Priority::Normal);
+/// assert!("urgent".parse::<Priority>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Interactive, user-facing work; served before everything else.
+    High,
+    /// Ordinary requests.
+    #[default]
+    Normal,
+    /// Background and batch work; served when nothing else is waiting.
+    Low,
+}
+
+impl Priority {
+    /// The priority's lowercase name, as accepted by [`FromStr`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Normal => "normal",
+            Self::Low => "low",
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    /// Parse `high`, `normal`, or `low` (case-insensitive).
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "high" => Ok(Self::High),
+            "normal" => Ok(Self::Normal),
+            "low" => Ok(Self::Low),
+            _ => Err(format!(
+                "invalid priority {:?}: expected high, normal, or low",
+                s
+            )),
+        }
+    }
+}
+
+/// Counts checked-out browsers and orders checkouts waiting for one.
+#[derive(Debug)]
+pub(crate) struct CheckoutQueue {
+    /// Browsers that may be checked out before checkouts wait.
+    capacity: usize,
+
+    /// How long a checkout waits; zero disables waiting.
+    timeout: Duration,
+
+    state: Mutex<QueueState>,
+
+    /// Signalled when a slot is released or the head of the queue changes.
+    turn: Condvar,
+
+    /// Async counterpart of `turn`, for [`admit_async()`](Self::admit_async).
+    notify: Notify,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    /// Checkouts currently holding a [`CheckoutSlot`].
+    checked_out: usize,
+
+    /// Waiting checkouts, in the order they are served.
+    waiting: BTreeSet<(Priority, u64)>,
+
+    /// Arrival number of the next waiting checkout.
+    next_ticket: u64,
+
+    /// Set on shutdown; waiting checkouts give up.
+    closed: bool,
+}
+
+impl CheckoutQueue {
+    /// Create a queue holding checkouts beyond `capacity` for up to
+    /// `timeout` (zero never waits).
+    pub(crate) fn new(capacity: usize, timeout: Duration) -> Self {
+        Self {
+            capacity,
+            timeout,
+            state: Mutex::new(QueueState::default()),
+            turn: Condvar::new(),
+            notify: Notify::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Reserve a slot for one checkout, waiting in priority order while
+    /// the pool is saturated.
+    ///
+    /// # Errors
+    ///
+    /// - [`BrowserPoolError::CheckoutTimeout`] if no slot frees up in time.
+    /// - [`BrowserPoolError::ShuttingDown`] if the pool shuts down while
+    ///   waiting.
+    /// - [`BrowserPoolError::Cancelled`] if `cancel` is cancelled while
+    ///   waiting.
+    pub(crate) fn admit(
+        self: &Arc<Self>,
+        priority: Priority,
+        cancel: Option<&CancelHandle>,
+    ) -> Result<CheckoutSlot> {
+        if cancel.is_some_and(CancelHandle::is_cancelled) {
+            return Err(BrowserPoolError::Cancelled);
+        }
+
+        let mut state = self.lock();
+        if self.timeout.is_zero() || (state.waiting.is_empty() && state.checked_out < self.capacity)
+        {
+            state.checked_out += 1;
+            return Ok(self.slot());
+        }
+
+        let ticket = (priority, state.next_ticket);
+        state.next_ticket += 1;
+        state.waiting.insert(ticket);
+        log::debug!(
+            "Pool saturated, {} priority checkout waiting ({} in queue)",
+            priority,
+            state.waiting.len()
+        );
+
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            if state.closed {
+                state.waiting.remove(&ticket);
+                return Err(BrowserPoolError::ShuttingDown);
+            }
+            if cancel.is_some_and(CancelHandle::is_cancelled) {
+                state.waiting.remove(&ticket);
+                self.wake();
+                return Err(BrowserPoolError::Cancelled);
+            }
+            if state.checked_out < self.capacity && state.waiting.first() == Some(&ticket) {
+                state.waiting.remove(&ticket);
+                state.checked_out += 1;
+                // The next in line may fit too
+                self.wake();
+                return Ok(self.slot());
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                state.waiting.remove(&ticket);
+                self.wake();
+                return Err(BrowserPoolError::CheckoutTimeout(self.timeout));
+            }
+            // Nothing signals a cancel, so wake up to check for one
+            let wait = match cancel {
+                Some(_) => (deadline - now).min(CANCEL_POLL_INTERVAL),
+                None => deadline - now,
+            };
+            state = self
+                .turn
+                .wait_timeout(state, wait)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Reserve a slot for one checkout like [`admit()`](Self::admit), but
+    /// wait without blocking the thread.
+    ///
+    /// Dropping the future while it waits leaves the queue.
+    ///
+    /// # Errors
+    ///
+    /// - [`BrowserPoolError::CheckoutTimeout`] if no slot frees up in time.
+    /// - [`BrowserPoolError::ShuttingDown`] if the pool shuts down while
+    ///   waiting.
+    pub(crate) async fn admit_async(self: &Arc<Self>, priority: Priority) -> Result<CheckoutSlot> {
+        let ticket = {
+            let mut state = self.lock();
+            if self.timeout.is_zero()
+                || (state.waiting.is_empty() && state.checked_out < self.capacity)
+            {
+                state.checked_out += 1;
+                return Ok(self.slot());
+            }
+
+            let ticket = (priority, state.next_ticket);
+            state.next_ticket += 1;
+            state.waiting.insert(ticket);
+            log::debug!(
+                "Pool saturated, {} priority checkout waiting ({} in queue)",
+                priority,
+                state.waiting.len()
+            );
+            ticket
+        };
+        let mut waiter = Waiter {
+            queue: self,
+            ticket: Some(ticket),
+        };
+
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        loop {
+            // Register before checking, so a release in between is not missed
+            let mut notified = pin!(self.notify.notified());
+            notified.as_mut().enable();
+
+            {
+                let mut state = self.lock();
+                if state.closed {
+                    return Err(BrowserPoolError::ShuttingDown);
+                }
+                if state.checked_out < self.capacity && state.waiting.first() == Some(&ticket) {
+                    state.waiting.remove(&ticket);
+                    state.checked_out += 1;
+                    waiter.ticket = None;
+                    // The next in line may fit too
+                    self.wake();
+                    return Ok(self.slot());
+                }
+            }
+
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return Err(BrowserPoolError::CheckoutTimeout(self.timeout));
+            }
+        }
+    }
+
+    /// Reserve up to `n` slots without waiting, within capacity and only
+    /// when nothing is queued.
+    pub(crate) fn reserve(self: &Arc<Self>, n: usize) -> Vec<CheckoutSlot> {
+        let mut state = self.lock();
+        let free = if state.waiting.is_empty() {
+            self.capacity.saturating_sub(state.checked_out)
+        } else {
+            0
+        };
+        let count = n.min(free);
+        state.checked_out += count;
+        (0..count).map(|_| self.slot()).collect()
+    }
+
+    /// Take a slot regardless of capacity, for browsers held by the pool
+    /// itself (warmup).
+    pub(crate) fn claim(self: &Arc<Self>) -> CheckoutSlot {
+        self.lock().checked_out += 1;
+        self.slot()
+    }
+
+    /// Number of checkouts waiting for a browser.
+    pub(crate) fn waiting(&self) -> usize {
+        self.lock().waiting.len()
+    }
+
+    /// Number of slots currently held.
+    pub(crate) fn checked_out(&self) -> usize {
+        self.lock().checked_out
+    }
+
+    /// Wait until every slot has been released.
+    pub(crate) async fn wait_idle(&self) {
+        loop {
+            // Register before checking, so a release in between is not missed
+            let mut notified = pin!(self.notify.notified());
+            notified.as_mut().enable();
+
+            if self.lock().checked_out == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Release all waiting checkouts with [`BrowserPoolError::ShuttingDown`].
+    pub(crate) fn close(&self) {
+        self.lock().closed = true;
+        self.wake();
+    }
+
+    /// Wake waiting checkouts, blocking and async alike.
+    fn wake(&self) {
+        self.turn.notify_all();
+        self.notify.notify_waiters();
+    }
+
+    fn slot(self: &Arc<Self>) -> CheckoutSlot {
+        CheckoutSlot {
+            queue: Arc::clone(self),
+        }
+    }
+
+    fn release(&self) {
+        let mut state = self.lock();
+        state.checked_out = state.checked_out.saturating_sub(1);
+        self.wake();
+    }
+}
+
+/// One reserved checkout; the slot is released when this is dropped.
+///
+/// Owned by the [`BrowserHandle`](crate::BrowserHandle), so the slot frees
+/// up only after the browser is back in the pool.
+#[derive(Debug)]
+pub(crate) struct CheckoutSlot {
+    queue: Arc<CheckoutQueue>,
+}
+
+impl Drop for CheckoutSlot {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+/// Place of an async checkout in the queue; leaves it when dropped
+/// unless the ticket was served.
+struct Waiter<'a> {
+    queue: &'a CheckoutQueue,
+    ticket: Option<(Priority, u64)>,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        if let Some(ticket) = self.ticket.take() {
+            self.queue.lock().waiting.remove(&ticket);
+            self.queue.wake();
+        }
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies priorities parse case-insensitively and display lowercase.
+    #[test]
+    fn test_priority_parse() {
+        assert_eq!(" Low ".parse::<Priority>().unwrap(), Priority::Low);
+        assert_eq!("normal".parse::<Priority>().unwrap(), Priority::Normal);
+        assert_eq!(Priority::High.to_string(), "high");
+        assert!("".parse::<Priority>().is_err());
+        assert!(Priority::High < Priority::Normal && Priority::Normal < Priority::Low);
+    }
+
+    /// Verifies a disabled queue never waits and slots are released on drop.
+    #[test]
+    fn test_disabled_queue_never_waits() {
+        let queue = Arc::new(CheckoutQueue::new(1, Duration::ZERO));
+        let first = queue.admit(Priority::Low, None).unwrap();
+        let second = queue.admit(Priority::Low, None).unwrap();
+        assert_eq!(queue.lock().checked_out, 2);
+
+        drop((first, second));
+        assert_eq!(queue.lock().checked_out, 0);
+    }
+
+    /// Verifies a saturated queue times out and reserves nothing in bulk.
+    #[test]
+    fn test_saturated_queue_times_out() {
+        let queue = Arc::new(CheckoutQueue::new(1, Duration::from_millis(50)));
+        let held = queue.reserve(3);
+        assert_eq!(held.len(), 1, "Reserve is limited by capacity");
+        assert!(queue.reserve(1).is_empty());
+
+        assert!(matches!(
+            queue.admit(Priority::High, None),
+            Err(BrowserPoolError::CheckoutTimeout(_))
+        ));
+        assert_eq!(queue.waiting(), 0, "Timed-out checkout leaves the queue");
+    }
+
+    /// Verifies waiting checkouts are served by priority, then arrival.
+    #[test]
+    fn test_queue_serves_by_priority() {
+        let queue = Arc::new(CheckoutQueue::new(1, Duration::from_secs(10)));
+        let held = queue.admit(Priority::Normal, None).unwrap();
+        let served = Mutex::new(Vec::new());
+
+        std::thread::scope(|scope| {
+            for (queued, (name, priority)) in [
+                ("low-1", Priority::Low),
+                ("normal", Priority::Normal),
+                ("low-2", Priority::Low),
+                ("high", Priority::High),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let (queue, served) = (&queue, &served);
+                scope.spawn(move || {
+                    let _slot = queue.admit(priority, None).unwrap();
+                    served.lock().unwrap().push(name);
+                    std::thread::sleep(Duration::from_millis(10));
+                });
+                // Let each one join the queue before the next
+                while queue.waiting() <= queued {
+                    std::thread::yield_now();
+                }
+            }
+            drop(held);
+        });
+
+        assert_eq!(
+            served.into_inner().unwrap(),
+            ["high", "normal", "low-1", "low-2"]
+        );
+    }
+
+    /// Verifies async checkouts wait for a release, time out, and leave
+    /// the queue when dropped.
+    #[tokio::test]
+    async fn test_admit_async() {
+        let queue = Arc::new(CheckoutQueue::new(1, Duration::from_millis(200)));
+        let held = queue.admit_async(Priority::Normal).await.unwrap();
+
+        let waiter = tokio::spawn({
+            let queue = Arc::clone(&queue);
+            async move { queue.admit_async(Priority::Normal).await.map(drop) }
+        });
+        while queue.waiting() == 0 {
+            tokio::task::yield_now().await;
+        }
+        drop(held);
+        waiter.await.unwrap().unwrap();
+
+        let held = queue.admit(Priority::Normal, None).unwrap();
+        assert!(matches!(
+            queue.admit_async(Priority::High).await,
+            Err(BrowserPoolError::CheckoutTimeout(_))
+        ));
+        assert_eq!(queue.waiting(), 0, "Timed-out checkout leaves the queue");
+
+        let abandoned =
+            tokio::time::timeout(Duration::from_millis(20), queue.admit_async(Priority::Low)).await;
+        assert!(abandoned.is_err());
+        assert_eq!(queue.waiting(), 0, "Dropped checkout leaves the queue");
+        drop(held);
+    }
+
+    /// Verifies closing the queue releases waiting checkouts.
+    #[test]
+    fn test_close_releases_waiters() {
+        let queue = Arc::new(CheckoutQueue::new(0, Duration::from_secs(10)));
+        std::thread::scope(|scope| {
+            let waiter = scope.spawn(|| queue.admit(Priority::Normal, None));
+            while queue.waiting() == 0 {
+                std::thread::yield_now();
+            }
+            queue.close();
+            assert!(matches!(
+                waiter.join().unwrap(),
+                Err(BrowserPoolError::ShuttingDown)
+            ));
+        });
+    }
+
+    /// Verifies a cancelled checkout leaves the queue.
+    #[test]
+    fn test_cancel_releases_waiter() {
+        let queue = Arc::new(CheckoutQueue::new(0, Duration::from_secs(10)));
+        let cancel = CancelHandle::new();
+        std::thread::scope(|scope| {
+            let waiter = scope.spawn(|| queue.admit(Priority::Normal, Some(&cancel)));
+            while queue.waiting() == 0 {
+                std::thread::yield_now();
+            }
+            cancel.cancel();
+            assert!(matches!(
+                waiter.join().unwrap(),
+                Err(BrowserPoolError::Cancelled)
+            ));
+        });
+        assert_eq!(queue.waiting(), 0);
+
+        // Already cancelled: never joins the queue
+        assert!(matches!(
+            queue.admit(Priority::High, Some(&cancel)),
+            Err(BrowserPoolError::Cancelled)
+        ));
+    }
+}
@@ -0,0 +1,473 @@
+//! HTML uploaded together with its assets.
+//!
+//! `POST /pdf/html` takes a single document, so its images, stylesheets,
+//! and fonts must be inlined as data URLs or hosted somewhere the browser
+//! can reach. `POST /pdf/html/assets` takes the whole bundle as
+//! `multipart/form-data` instead, and the tab loads it from a private
+//! origin whose requests are answered from the upload (`Fetch`
+//! interception, as for `mock_responses`):
+//!
+//! ```text
+//! index.html    ──▶ https://assets.html2pdf.invalid/index.html
+//! css/site.css  ──▶ https://assets.html2pdf.invalid/css/site.css
+//! img/logo.png  ──▶ https://assets.html2pdf.invalid/img/logo.png
+//! anything else ──▶ 404
+//! ```
+//!
+//! Relative URLs therefore resolve against the uploaded files, while
+//! absolute URLs elsewhere load as they would for `POST /pdf/html`.
+
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+
+use crate::service::types::{PdfFromAssetsRequest, PdfFromHtmlRequest, PdfServiceError};
+
+/// Origin an upload is served from; `.invalid` never resolves, so no real
+/// server can answer in its place.
+pub(crate) const ASSETS_ORIGIN: &str = "https://assets.html2pdf.invalid";
+
+/// Path of the entry document in an upload.
+pub(crate) const INDEX_PATH: &str = "index.html";
+
+/// Name of the part holding the render options as JSON.
+const OPTIONS_PART: &str = "options";
+
+/// Most files accepted in one upload, `index.html` included.
+const MAX_ASSETS: usize = 256;
+
+/// Longest accepted asset path, in bytes.
+const MAX_PATH_LEN: usize = 255;
+
+impl PdfFromAssetsRequest {
+    /// Parse a `multipart/form-data` upload.
+    ///
+    /// Every part with a filename is a file, stored under that filename,
+    /// which may include directories (`css/site.css`); one of them must be
+    /// `index.html`. A part named `options` without a filename holds the
+    /// fields of a [`PdfFromHtmlRequest`] as JSON, minus `html`. Content
+    /// types are taken from file extensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - The request's `Content-Type` header, carrying the
+    ///   boundary
+    /// * `body` - The raw request body
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfServiceError::InvalidRequest`] if the body is not
+    /// well-formed multipart, `index.html` is missing or not UTF-8, a path
+    /// is absolute or leaves the upload (`..`), a file is uploaded twice,
+    /// there are more than 256 files, or `options` is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use html2pdf_api::service::{Bytes, PdfFromAssetsRequest};
+    ///
+    /// let body = concat!(
+    ///     "--XyZ\r\n",
+    ///     "Content-Disposition: form-data; name=\"file\"; filename=\"index.html\"\r\n\r\n",
+    ///     "<img src=\"logo.svg\">\r\n",
+    ///     "--XyZ\r\n",
+    ///     "Content-Disposition: form-data; name=\"file\"; filename=\"logo.svg\"\r\n\r\n",
+    ///     "<svg xmlns=\"http://www.w3.org/2000/svg\"/>\r\n",
+    ///     "--XyZ--\r\n",
+    /// );
+    /// let request = PdfFromAssetsRequest::from_multipart(
+    ///     "multipart/form-data; boundary=XyZ",
+    ///     Bytes::from_static(body.as_bytes()),
+    /// )?;
+    /// assert_eq!(request.document.html, "<img src=\"logo.svg\">");
+    /// assert!(request.assets.contains_key("logo.svg"));
+    /// # Ok::<(), html2pdf_api::service::PdfServiceError>(())
+    /// ```
+    pub fn from_multipart(content_type: &str, body: Bytes) -> Result<Self, PdfServiceError> {
+        let boundary = multipart_boundary(content_type)?;
+
+        let mut html = None;
+        let mut options = None;
+        let mut assets = BTreeMap::new();
+        for part in parse_multipart(&body, &boundary)? {
+            let Some(filename) = part.filename else {
+                if part.name != OPTIONS_PART {
+                    return Err(invalid(format!(
+                        "part {:?} is neither a file nor options",
+                        part.name
+                    )));
+                }
+                if options.replace(part.data).is_some() {
+                    return Err(invalid("options given more than once".to_string()));
+                }
+                continue;
+            };
+
+            let path = asset_path(&filename)?;
+            let duplicate = if path == INDEX_PATH {
+                html.replace(part.data).is_some()
+            } else {
+                assets.insert(path.clone(), part.data).is_some()
+            };
+            if duplicate {
+                return Err(invalid(format!("{} uploaded more than once", path)));
+            }
+            if assets.len() >= MAX_ASSETS {
+                return Err(invalid(format!(
+                    "at most {} files can be uploaded",
+                    MAX_ASSETS
+                )));
+            }
+        }
+
+        let html = html.ok_or_else(|| invalid("index.html is missing".to_string()))?;
+        let html = String::from_utf8(html.to_vec())
+            .map_err(|_| invalid("index.html must be UTF-8".to_string()))?;
+        Ok(Self {
+            document: parse_options(options.as_deref(), html)?,
+            assets,
+        })
+    }
+}
+
+/// URL an uploaded file is served at.
+pub(crate) fn asset_url(path: &str) -> String {
+    let url = format!("{}/{}", ASSETS_ORIGIN, path);
+    // Percent-encode as Chrome will when requesting it
+    url::Url::parse(&url).map_or(url, String::from)
+}
+
+/// Content type of an uploaded file, from its extension.
+pub(crate) fn content_type(path: &str) -> &'static str {
+    let extension = path
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "txt" => "text/plain; charset=utf-8",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// One part of a `multipart/form-data` body.
+#[derive(Debug)]
+struct Part {
+    name: String,
+    filename: Option<String>,
+    data: Bytes,
+}
+
+/// Error for a malformed upload.
+fn invalid(message: String) -> PdfServiceError {
+    PdfServiceError::InvalidRequest(message)
+}
+
+/// The boundary of a `multipart/form-data` content type.
+fn multipart_boundary(content_type: &str) -> Result<String, PdfServiceError> {
+    let mut params = content_type.split(';');
+    let media_type = params.next().unwrap_or_default().trim();
+    if !media_type.eq_ignore_ascii_case("multipart/form-data") {
+        return Err(invalid(format!(
+            "Content-Type must be multipart/form-data, got {:?}",
+            media_type
+        )));
+    }
+    params
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+        .filter(|boundary| (1..=70).contains(&boundary.len()))
+        .ok_or_else(|| invalid("multipart Content-Type has no boundary".to_string()))
+}
+
+/// Split a `multipart/form-data` body into its parts.
+fn parse_multipart(body: &Bytes, boundary: &str) -> Result<Vec<Part>, PdfServiceError> {
+    let malformed = |reason: &str| invalid(format!("malformed multipart body: {}", reason));
+    let delimiter = format!("\r\n--{}", boundary);
+    let delimiter = delimiter.as_bytes();
+
+    // The first delimiter may start the body, without the leading CRLF
+    let mut pos = if body.starts_with(&delimiter[2..]) {
+        delimiter.len() - 2
+    } else {
+        find(body, delimiter, 0).ok_or_else(|| malformed("no boundary found"))? + delimiter.len()
+    };
+
+    let mut parts = Vec::new();
+    loop {
+        if body[pos..].starts_with(b"--") {
+            return Ok(parts);
+        }
+        let line_end = find(body, b"\r\n", pos).ok_or_else(|| malformed("truncated part"))?;
+        let headers_end =
+            find(body, b"\r\n\r\n", line_end).ok_or_else(|| malformed("truncated headers"))?;
+        let content_start = headers_end + 4;
+        let content_end = find(body, delimiter, content_start)
+            .ok_or_else(|| malformed("missing closing boundary"))?;
+
+        let headers = String::from_utf8_lossy(&body[line_end..headers_end]);
+        let disposition = headers
+            .split("\r\n")
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-disposition"))
+            .map(|(_, value)| disposition_params(value))
+            .ok_or_else(|| malformed("part without Content-Disposition"))?;
+        let param = |key: &str| {
+            disposition
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.clone())
+        };
+
+        parts.push(Part {
+            name: param("name").unwrap_or_default(),
+            filename: param("filename").filter(|filename| !filename.is_empty()),
+            data: body.slice(content_start..content_end),
+        });
+        pos = content_end + delimiter.len();
+    }
+}
+
+/// The parameters of a `Content-Disposition` value, names lowercased.
+///
+/// Quoted values end at the next quote: browsers percent-encode quotes in
+/// filenames rather than escaping them.
+fn disposition_params(value: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = value.split_once(';').map_or("", |(_, rest)| rest);
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ';']);
+        let Some((name, after)) = rest.split_once('=') else {
+            return params;
+        };
+        let after = after.trim_start();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => after.split_once(';').unwrap_or((after, "")),
+        };
+        params.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        rest = remaining;
+    }
+}
+
+/// Validate an uploaded file's path, relative to `index.html`.
+fn asset_path(filename: &str) -> Result<String, PdfServiceError> {
+    let path = filename.trim();
+    let path = path.strip_prefix("./").unwrap_or(path);
+    let valid = path.len() <= MAX_PATH_LEN
+        && !path.contains(|c: char| matches!(c, '\\' | '?' | '#' | '*') || c.is_control())
+        && path
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+    if !valid {
+        return Err(invalid(format!("invalid file path: {:?}", filename)));
+    }
+    Ok(path.to_string())
+}
+
+/// Build the entry document's request from the `options` part.
+fn parse_options(
+    options: Option<&[u8]>,
+    html: String,
+) -> Result<PdfFromHtmlRequest, PdfServiceError> {
+    let mut value = match options {
+        Some(options) => serde_json::from_slice(options)
+            .map_err(|e| invalid(format!("invalid options: {}", e)))?,
+        None => serde_json::Value::Object(Default::default()),
+    };
+    let Some(fields) = value.as_object_mut() else {
+        return Err(invalid("options must be a JSON object".to_string()));
+    };
+    if fields.contains_key("html") {
+        return Err(invalid(
+            "options must not contain html; upload index.html instead".to_string(),
+        ));
+    }
+    if fields.contains_key("base_url") {
+        return Err(invalid(
+            "base_url cannot be combined with uploaded assets".to_string(),
+        ));
+    }
+    fields.insert("html".to_string(), serde_json::Value::String(html));
+
+    let document: PdfFromHtmlRequest =
+        serde_json::from_value(value).map_err(|e| invalid(format!("invalid options: {}", e)))?;
+    if document.stream() {
+        return Err(invalid(
+            "stream cannot be combined with uploaded assets".to_string(),
+        ));
+    }
+    Ok(document)
+}
+
+/// Index of the first `needle` in `haystack` at or after `from`.
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|index| index + from)
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT_TYPE: &str = "multipart/form-data; boundary=\"----bound\"";
+
+    /// Build a body from `(name, filename, content)` parts.
+    fn body(parts: &[(&str, Option<&str>, &str)]) -> Bytes {
+        let mut body = String::from("preamble\r\n");
+        for (name, filename, content) in parts {
+            body.push_str("------bound\r\nContent-Disposition: form-data; name=\"");
+            body.push_str(name);
+            body.push('"');
+            if let Some(filename) = filename {
+                body.push_str("; filename=\"");
+                body.push_str(filename);
+                body.push('"');
+            }
+            body.push_str("\r\nContent-Type: application/octet-stream\r\n\r\n");
+            body.push_str(content);
+            body.push_str("\r\n");
+        }
+        body.push_str("------bound--\r\n");
+        Bytes::from(body)
+    }
+
+    /// Verifies files, nested paths, and options are read from an upload.
+    #[test]
+    fn test_from_multipart() {
+        let request = PdfFromAssetsRequest::from_multipart(
+            CONTENT_TYPE,
+            body(&[
+                ("file", Some("./index.html"), "<h1>Report</h1>"),
+                ("file", Some("css/site.css"), "h1 {\r\n}"),
+                ("file", Some("empty.txt"), ""),
+                (
+                    "options",
+                    None,
+                    r#"{"filename": "report.pdf", "landscape": true}"#,
+                ),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(request.document.html, "<h1>Report</h1>");
+        assert_eq!(request.document.filename_or_default(), "report.pdf");
+        assert!(request.document.is_landscape());
+        assert_eq!(request.assets.len(), 2);
+        assert_eq!(request.assets["css/site.css"], "h1 {\r\n}");
+        assert_eq!(request.assets["empty.txt"], "");
+    }
+
+    /// Verifies malformed uploads are rejected.
+    #[test]
+    fn test_from_multipart_rejects() {
+        let index = ("file", Some("index.html"), "<h1>Report</h1>");
+        let rejected = |content_type: &str, body: Bytes| {
+            matches!(
+                PdfFromAssetsRequest::from_multipart(content_type, body),
+                Err(PdfServiceError::InvalidRequest(_))
+            )
+        };
+
+        assert!(rejected("application/json", body(&[index])));
+        assert!(rejected("multipart/form-data", body(&[index])));
+        assert!(rejected(CONTENT_TYPE, Bytes::from_static(b"no parts")));
+        assert!(rejected(CONTENT_TYPE, body(&[("file", Some("a.css"), "")])));
+        assert!(rejected(CONTENT_TYPE, body(&[index, index])));
+        assert!(rejected(CONTENT_TYPE, body(&[index, ("extra", None, "")])));
+        assert!(rejected(
+            CONTENT_TYPE,
+            body(&[index, ("file", Some("../secret"), "")])
+        ));
+        assert!(rejected(
+            CONTENT_TYPE,
+            body(&[index, ("options", None, r#"{"html": "<p>"}"#)])
+        ));
+        assert!(rejected(
+            CONTENT_TYPE,
+            body(&[index, ("options", None, r#"{"stream": true}"#)])
+        ));
+        assert!(rejected(
+            CONTENT_TYPE,
+            body(&[
+                index,
+                ("options", None, r#"{"base_url": "https://x.test/"}"#)
+            ])
+        ));
+
+        let truncated = body(&[index]).slice(..40);
+        assert!(rejected(CONTENT_TYPE, truncated));
+    }
+
+    /// Verifies paths that could escape the upload are rejected.
+    #[test]
+    fn test_asset_path() {
+        assert_eq!(asset_path("img/logo.png").unwrap(), "img/logo.png");
+        assert_eq!(asset_path("./logo.png").unwrap(), "logo.png");
+        for path in [
+            "",
+            "/etc/passwd",
+            "a/../b",
+            "a//b",
+            "dir/",
+            "a\\b",
+            "a?b",
+            "*.css",
+        ] {
+            assert!(asset_path(path).is_err(), "{:?}", path);
+        }
+    }
+
+    /// Verifies `Content-Disposition` parameters, quoted or not.
+    #[test]
+    fn test_disposition_params() {
+        assert_eq!(
+            disposition_params(r#"form-data; name="file"; filename="a; b.png""#),
+            [
+                ("name".to_string(), "file".to_string()),
+                ("filename".to_string(), "a; b.png".to_string())
+            ]
+        );
+        assert_eq!(
+            disposition_params("form-data; NAME=options"),
+            [("name".to_string(), "options".to_string())]
+        );
+    }
+
+    /// Verifies asset URLs are encoded like Chrome requests them, and
+    /// content types follow extensions.
+    #[test]
+    fn test_asset_url_and_content_type() {
+        assert_eq!(
+            asset_url("img/my logo.png"),
+            "https://assets.html2pdf.invalid/img/my%20logo.png"
+        );
+        assert_eq!(content_type("fonts/Inter.WOFF2"), "font/woff2");
+        assert_eq!(content_type("index.html"), "text/html; charset=utf-8");
+        assert_eq!(content_type("LICENSE"), "application/octet-stream");
+    }
+}
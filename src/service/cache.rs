@@ -0,0 +1,410 @@
+//! Cache of rendered PDFs for identical requests.
+//!
+//! Dashboards and report links often request the same document over and
+//! over. With
+//! [`response_cache_ttl`](crate::BrowserPoolConfig::response_cache_ttl)
+//! set, a successful PDF is cached and identical requests within the TTL
+//! get it without touching a browser.
+//!
+//! Requests are identical when their serialized form is, exactly as for
+//! [`render_coalesced`](crate::service::render_coalesced): the URL or HTML
+//! and every render option must match. Failures are never cached.
+//!
+//! PDFs are stored in a [`PdfCache`] backend. The default,
+//! [`MemoryPdfCache`], keeps them in process memory and evicts the oldest
+//! once they exceed
+//! [`response_cache_max_mb`](crate::BrowserPoolConfig::response_cache_max_mb).
+//! [`RedisPdfCache`](crate::service::RedisPdfCache) (feature
+//! `redis-cache`) shares them between instances.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::pool::BrowserPool;
+use crate::service::idempotency::fingerprint;
+use crate::service::types::{PdfResponse, PdfServiceError};
+
+/// Storage backend for rendered PDFs, consulted by [`render_cached`].
+///
+/// The pool uses a [`MemoryPdfCache`] unless another backend is set with
+/// [`BrowserPoolBuilder::pdf_cache`](crate::BrowserPoolBuilder::pdf_cache).
+/// A shared backend such as
+/// [`RedisPdfCache`](crate::service::RedisPdfCache) (feature `redis-cache`)
+/// lets several instances serve each other's PDFs.
+///
+/// Keys are hex SHA-256 fingerprints of the request. A cache is an
+/// optimization, so implementations log backend failures and carry on:
+/// a failed `get` is a miss and a failed `put` caches nothing.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{PdfCache, PdfResponse};
+///
+/// struct NoCache;
+///
+/// impl PdfCache for NoCache {
+///     fn get(&self, _key: &str) -> Option<PdfResponse> { None }
+///     fn put(&self, _key: &str, _response: &PdfResponse, _ttl: Duration) {}
+///     fn evict(&self, _key: &str) {}
+/// }
+/// ```
+pub trait PdfCache: Send + Sync {
+    /// Get the PDF cached under `key`, unless it has expired.
+    fn get(&self, key: &str) -> Option<PdfResponse>;
+
+    /// Cache `response` under `key` for `ttl`, replacing any previous PDF.
+    fn put(&self, key: &str, response: &PdfResponse, ttl: Duration);
+
+    /// Remove the PDF cached under `key`, if any.
+    fn evict(&self, key: &str);
+}
+
+/// [`PdfCache`] keeping PDFs in process memory, up to a size limit.
+///
+/// The default backend. Once the cached PDFs exceed the limit, the oldest
+/// are evicted; PDFs larger than the whole cache are not cached.
+#[derive(Debug)]
+pub struct MemoryPdfCache {
+    max_bytes: usize,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, Entry>,
+
+    /// Size of all cached PDFs.
+    bytes: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    response: PdfResponse,
+
+    /// When the PDF was cached.
+    at: Instant,
+
+    /// When the PDF stops being served.
+    expires: Instant,
+}
+
+impl MemoryPdfCache {
+    /// Create a cache holding up to `max_mb` megabytes of PDFs.
+    pub fn new(max_mb: usize) -> Self {
+        Self {
+            max_bytes: max_mb.saturating_mul(1024 * 1024),
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl PdfCache for MemoryPdfCache {
+    fn get(&self, key: &str) -> Option<PdfResponse> {
+        let mut state = self.lock();
+        match state.entries.get(key) {
+            Some(entry) if Instant::now() < entry.expires => Some(entry.response.clone()),
+            Some(_) => {
+                state.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache a PDF, evicting expired and then the oldest entries to make
+    /// room.
+    fn put(&self, key: &str, response: &PdfResponse, ttl: Duration) {
+        let size = response.size();
+        if size > self.max_bytes {
+            log::debug!("PDF of {} bytes is larger than the response cache", size);
+            return;
+        }
+
+        let mut state = self.lock();
+        let now = Instant::now();
+        state.remove(key);
+        let expired: Vec<String> = state
+            .entries
+            .iter()
+            .filter(|(_, entry)| now >= entry.expires)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            state.remove(&key);
+        }
+        while state.bytes + size > self.max_bytes {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.at)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(oldest) => state.remove(&oldest),
+                None => break,
+            }
+        }
+
+        state.bytes += size;
+        state.entries.insert(
+            key.to_string(),
+            Entry {
+                response: response.clone(),
+                at: now,
+                expires: now + ttl,
+            },
+        );
+    }
+
+    fn evict(&self, key: &str) {
+        self.lock().remove(key);
+    }
+}
+
+impl CacheState {
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.bytes -= entry.response.size();
+        }
+    }
+}
+
+/// The pool's response cache: a [`PdfCache`] backend plus the TTL and
+/// hit/miss counters.
+///
+/// Owned by the [`BrowserPool`]; use [`render_cached`] rather than this
+/// type directly.
+pub(crate) struct ResponseCache {
+    ttl: Duration,
+    backend: Box<dyn PdfCache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    /// Create a cache keeping PDFs in `backend` for `ttl` (zero disables
+    /// it).
+    pub(crate) fn new(ttl: Duration, backend: Box<dyn PdfCache>) -> Self {
+        Self {
+            ttl,
+            backend,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Requests served from the cache.
+    pub(crate) fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Requests that had to render.
+    pub(crate) fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Whether PDFs are cached at all.
+    fn enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    /// Look up a PDF, counting the hit or miss.
+    fn get(&self, key: &str) -> Option<PdfResponse> {
+        let response = self.backend.get(key);
+        match &response {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        response
+    }
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttl", &self.ttl)
+            .field("hits", &self.hits)
+            .field("misses", &self.misses)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Hex form of the request fingerprint, used as the cache key.
+fn cache_key<R: Serialize>(request: &R) -> Result<String, PdfServiceError> {
+    let digest = fingerprint(request)?;
+    let mut key = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(key, "{:02x}", byte);
+    }
+    Ok(key)
+}
+
+/// Run a render, or serve an identical request's PDF from memory.
+///
+/// With the pool's
+/// [`response_cache_ttl`](crate::BrowserPoolConfig::response_cache_ttl)
+/// zero (the default) this just calls `render`. Otherwise a cached PDF for
+/// an identical `request` is returned without calling `render`, and a
+/// successful render is cached; see the [module documentation](self).
+///
+/// Call it inside [`render_metered`](crate::service::render_metered), so
+/// cached PDFs still count toward the caller's usage, and around
+/// [`render_coalesced`](crate::service::render_coalesced), so a cache miss
+/// during a burst still renders only once.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `request` - The request, identifying identical renders
+/// * `render` - The render to run on a cache miss
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{generate_pdf_from_url, render_cached, render_coalesced};
+///
+/// let response = render_cached(&pool, &request, || {
+///     render_coalesced(&pool, &request, || generate_pdf_from_url(&pool, &request))
+/// })?;
+/// ```
+pub fn render_cached<R, F>(
+    pool: &Mutex<BrowserPool>,
+    request: &R,
+    render: F,
+) -> Result<PdfResponse, PdfServiceError>
+where
+    R: Serialize,
+    F: FnOnce() -> Result<PdfResponse, PdfServiceError>,
+{
+    let cache = {
+        let pool_guard = pool.lock().map_err(|e| {
+            log::error!("Failed to lock browser pool for response cache: {}", e);
+            PdfServiceError::PoolLockFailed(e.to_string())
+        })?;
+        pool_guard.response_cache()
+    };
+
+    if !cache.enabled() {
+        return render();
+    }
+
+    let key = cache_key(request)?;
+    if let Some(response) = cache.get(&key) {
+        log::debug!("Serving cached PDF ({} bytes)", response.size());
+        return Ok(response);
+    }
+
+    let result = render();
+    if let Ok(response) = &result {
+        cache.backend.put(&key, response, cache.ttl);
+    }
+    result
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BrowserPoolConfigBuilder;
+    use crate::factory::mock::MockBrowserFactory;
+    use crate::service::{PdfFromHtmlRequest, PdfFromUrlRequest};
+
+    fn pdf(body: &[u8]) -> Result<PdfResponse, PdfServiceError> {
+        Ok(PdfResponse::new(
+            body.to_vec(),
+            "doc.pdf".to_string(),
+            false,
+        ))
+    }
+
+    /// Verifies identical requests are served from the cache, failures
+    /// are not cached, and hits and misses are counted.
+    #[test]
+    fn test_render_cached() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .config(
+                BrowserPoolConfigBuilder::new()
+                    .response_cache_ttl(Duration::from_secs(60))
+                    .build()
+                    .unwrap(),
+            )
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+        let pool = Mutex::new(pool);
+        let request = PdfFromUrlRequest {
+            url: "https://example.com/report".to_string(),
+            ..Default::default()
+        };
+
+        let first = render_cached(&pool, &request, || pdf(b"first"));
+        let repeat = render_cached(&pool, &request, || pdf(b"second"));
+        assert_eq!(&first.unwrap().data[..], b"first");
+        assert_eq!(&repeat.unwrap().data[..], b"first");
+
+        // Different options are a different request
+        let landscape = PdfFromUrlRequest {
+            landscape: Some(true),
+            ..request.clone()
+        };
+        let result = render_cached(&pool, &landscape, || pdf(b"landscape"));
+        assert_eq!(&result.unwrap().data[..], b"landscape");
+
+        let html = PdfFromHtmlRequest {
+            html: "<h1>Broken</h1>".to_string(),
+            ..Default::default()
+        };
+        let failed = render_cached(&pool, &html, || {
+            Err(PdfServiceError::Timeout("slow".to_string()))
+        });
+        assert!(failed.is_err());
+        let retried = render_cached(&pool, &html, || pdf(b"retry"));
+        assert_eq!(&retried.unwrap().data[..], b"retry");
+
+        let cache = pool.lock().unwrap().response_cache();
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 4);
+    }
+
+    /// Verifies entries expire after their TTL and the oldest are evicted
+    /// once the cache is full.
+    #[test]
+    fn test_memory_cache_eviction() {
+        let cache = MemoryPdfCache::new(1);
+        let ttl = Duration::from_secs(60);
+        let half = PdfResponse::new(vec![0; 600 * 1024], "a.pdf".to_string(), false);
+
+        cache.put("a", &half, Duration::from_millis(50));
+        assert!(cache.get("a").is_some());
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(cache.get("a").is_none(), "Expired");
+
+        cache.put("a", &half, ttl);
+        cache.put("b", &half, ttl);
+        assert!(cache.get("a").is_none(), "Evicted for b");
+        assert!(cache.get("b").is_some());
+        assert_eq!(cache.lock().bytes, half.size());
+
+        cache.evict("b");
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.lock().bytes, 0);
+
+        let huge = PdfResponse::new(vec![0; 2 * 1024 * 1024], "b.pdf".to_string(), false);
+        cache.put("c", &huge, ttl);
+        assert!(cache.get("c").is_none(), "Larger than the cache");
+    }
+}
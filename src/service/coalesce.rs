@@ -0,0 +1,344 @@
+//! Coalescing of identical concurrent renders.
+//!
+//! During traffic spikes the same popular report URL is often requested
+//! many times at once. With
+//! [`coalesce_requests`](crate::BrowserPoolConfig::coalesce_requests)
+//! enabled, the first request renders and identical requests that arrive
+//! while it is still in flight wait for its result instead of each taking
+//! a browser ("single-flight").
+//!
+//! Requests are identical when their serialized form is, so every field
+//! (filename, wait time, blocked resources, ...) must match. Only renders
+//! that overlap are shared; nothing is cached afterward. Failures are
+//! shared too, so a burst of requests for a broken page fails together.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+
+use crate::pool::BrowserPool;
+use crate::service::idempotency::fingerprint;
+use crate::service::types::{PdfResponse, PdfServiceError};
+
+type RenderResult = Result<PdfResponse, PdfServiceError>;
+
+/// Renders currently in flight, by request fingerprint.
+///
+/// Owned by the [`BrowserPool`]; use [`render_coalesced`] rather than this
+/// type directly.
+#[derive(Debug)]
+pub(crate) struct InflightRenders {
+    enabled: bool,
+    flights: Mutex<HashMap<Vec<u8>, Arc<Flight>>>,
+    /// Callers currently blocked on another caller's render.
+    waiting: AtomicUsize,
+}
+
+/// One in-flight render and the result its waiters receive.
+#[derive(Debug, Default)]
+struct Flight {
+    result: Mutex<Option<RenderResult>>,
+    done: Condvar,
+}
+
+impl Flight {
+    /// Block until the render finishes and return a copy of its result.
+    fn wait(&self) -> RenderResult {
+        let mut result = self.result.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(result) = result.as_ref() {
+                return result.clone();
+            }
+            result = self
+                .done
+                .wait(result)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+impl InflightRenders {
+    /// Create the registry; when `enabled` is false every render runs.
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            flights: Mutex::new(HashMap::new()),
+            waiting: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of callers waiting for an identical render to finish.
+    pub(crate) fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::Relaxed)
+    }
+
+    /// Join the render for `key`, or start one. Returns the flight and
+    /// whether the caller leads it.
+    fn join(&self, key: &[u8]) -> (Arc<Flight>, bool) {
+        let mut flights = self.flights.lock().unwrap_or_else(PoisonError::into_inner);
+        match flights.get(key) {
+            Some(flight) => (Arc::clone(flight), false),
+            None => {
+                let flight = Arc::new(Flight::default());
+                flights.insert(key.to_vec(), Arc::clone(&flight));
+                (flight, true)
+            }
+        }
+    }
+
+    /// Publish the leader's result and let new requests render again.
+    fn complete(&self, key: &[u8], flight: &Flight, result: RenderResult) {
+        self.flights
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key);
+        *flight.result.lock().unwrap_or_else(PoisonError::into_inner) = Some(result);
+        flight.done.notify_all();
+    }
+}
+
+/// Completes a flight even if the leader's render panics, so waiters are
+/// never stranded.
+struct Leader<'a> {
+    renders: &'a InflightRenders,
+    key: &'a [u8],
+    flight: Arc<Flight>,
+    completed: bool,
+}
+
+impl Leader<'_> {
+    fn complete(mut self, result: &RenderResult) {
+        self.renders
+            .complete(self.key, &self.flight, result.clone());
+        self.completed = true;
+    }
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.renders.complete(
+                self.key,
+                &self.flight,
+                Err(PdfServiceError::Internal(
+                    "coalesced render did not complete".to_string(),
+                )),
+            );
+        }
+    }
+}
+
+/// Run a render, sharing it with identical renders already in flight.
+///
+/// With the pool's
+/// [`coalesce_requests`](crate::BrowserPoolConfig::coalesce_requests)
+/// disabled (the default) this just calls `render`. Enabled, the first
+/// caller for a request runs `render` and later callers with an identical
+/// `request` block until it finishes and receive a copy of its result; see
+/// the [module documentation](self).
+///
+/// Call it inside [`render_metered`](crate::service::render_metered), so
+/// every caller's usage is still recorded.
+///
+/// # Blocking Behavior
+///
+/// Blocks for as long as the shared render does. Call it inside the same
+/// blocking task as the render.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `request` - The request, identifying identical renders
+/// * `render` - The render to run or join
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{generate_pdf_from_url, render_coalesced, render_metered};
+///
+/// let response = render_metered(&pool, api_key, || {
+///     render_coalesced(&pool, &request, || generate_pdf_from_url(&pool, &request))
+/// })?;
+/// ```
+pub fn render_coalesced<R, F>(
+    pool: &Mutex<BrowserPool>,
+    request: &R,
+    render: F,
+) -> Result<PdfResponse, PdfServiceError>
+where
+    R: Serialize,
+    F: FnOnce() -> Result<PdfResponse, PdfServiceError>,
+{
+    let renders = {
+        let pool_guard = pool.lock().map_err(|e| {
+            log::error!("Failed to lock browser pool for coalescing: {}", e);
+            PdfServiceError::PoolLockFailed(e.to_string())
+        })?;
+        pool_guard.inflight()
+    };
+
+    if !renders.enabled {
+        return render();
+    }
+
+    let key = fingerprint(request)?;
+    let (flight, leads) = renders.join(&key);
+
+    if !leads {
+        log::debug!("Waiting for an identical render already in flight");
+        renders.waiting.fetch_add(1, Ordering::Relaxed);
+        let result = flight.wait();
+        renders.waiting.fetch_sub(1, Ordering::Relaxed);
+        return result;
+    }
+
+    let leader = Leader {
+        renders: &renders,
+        key: &key,
+        flight,
+        completed: false,
+    };
+    let result = render();
+    leader.complete(&result);
+    result
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BrowserPoolConfigBuilder;
+    use crate::factory::mock::MockBrowserFactory;
+    use crate::service::{PdfFromHtmlRequest, PdfFromUrlRequest};
+    use std::sync::Barrier;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn pool(coalesce: bool) -> Mutex<BrowserPool> {
+        let pool = BrowserPool::builder()
+            .config(
+                BrowserPoolConfigBuilder::new()
+                    .coalesce_requests(coalesce)
+                    .build()
+                    .unwrap(),
+            )
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+        Mutex::new(pool)
+    }
+
+    /// Render `request` from `callers` threads at once, returning how many
+    /// renders ran and the results.
+    fn render_concurrently(
+        pool: &Mutex<BrowserPool>,
+        request: &PdfFromUrlRequest,
+        callers: usize,
+        outcome: RenderResult,
+    ) -> (usize, Vec<RenderResult>) {
+        let renders = AtomicUsize::new(0);
+        let barrier = Barrier::new(callers);
+
+        let results = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..callers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        barrier.wait();
+                        render_coalesced(pool, request, || {
+                            renders.fetch_add(1, Ordering::SeqCst);
+                            std::thread::sleep(Duration::from_millis(300));
+                            outcome.clone()
+                        })
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        (renders.load(Ordering::SeqCst), results)
+    }
+
+    /// Verifies concurrent identical requests share one render and its
+    /// failure, and later requests render again.
+    #[test]
+    fn test_render_coalesced() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = pool(true);
+        let request = PdfFromUrlRequest {
+            url: "https://example.com/report".to_string(),
+            ..Default::default()
+        };
+
+        let pdf = PdfResponse::new(b"%PDF-shared".to_vec(), "report.pdf".to_string(), false);
+        let (renders, results) = render_concurrently(&pool, &request, 4, Ok(pdf));
+        assert_eq!(renders, 1);
+        for result in results {
+            assert_eq!(&result.unwrap().data[..], b"%PDF-shared");
+        }
+        assert_eq!(pool.lock().unwrap().inflight().waiting(), 0);
+
+        let failure = Err(PdfServiceError::Timeout("slow".to_string()));
+        let (renders, results) = render_concurrently(&pool, &request, 3, failure);
+        assert_eq!(renders, 1);
+        assert!(
+            results
+                .iter()
+                .all(|result| matches!(result, Err(PdfServiceError::Timeout(_))))
+        );
+
+        // Different requests never share
+        let other = PdfFromHtmlRequest {
+            html: "<h1>Report</h1>".to_string(),
+            ..Default::default()
+        };
+        let result = render_coalesced(&pool, &other, || {
+            Ok(PdfResponse::new(
+                b"%PDF-other".to_vec(),
+                "a.pdf".to_string(),
+                false,
+            ))
+        });
+        assert_eq!(&result.unwrap().data[..], b"%PDF-other");
+    }
+
+    /// Verifies every caller renders when coalescing is disabled.
+    #[test]
+    fn test_render_coalesced_disabled() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = pool(false);
+        let request = PdfFromUrlRequest::default();
+
+        let pdf = PdfResponse::new(b"%PDF-".to_vec(), "a.pdf".to_string(), false);
+        let (renders, _) = render_concurrently(&pool, &request, 3, Ok(pdf));
+        assert_eq!(renders, 3);
+    }
+
+    /// Verifies waiters are released if the leading render panics.
+    #[test]
+    fn test_leader_panic_releases_waiters() {
+        let renders = InflightRenders::new(true);
+        let (flight, leads) = renders.join(b"key");
+        assert!(leads);
+        let (waiter, leads) = renders.join(b"key");
+        assert!(!leads);
+
+        drop(Leader {
+            renders: &renders,
+            key: b"key",
+            flight,
+            completed: false,
+        });
+        assert!(matches!(waiter.wait(), Err(PdfServiceError::Internal(_))));
+        assert!(renders.join(b"key").1);
+    }
+}
@@ -0,0 +1,415 @@
+//! Debug reports of individual renders.
+//!
+//! A request with `debug` set gets a JSON [`RenderReport`] instead of the
+//! PDF, built from the DevTools events of its tab: main-frame navigations
+//! and lifecycle events, console output, uncaught exceptions, browser log
+//! entries, and failed requests. The service adds its own steps (actions,
+//! going offline, the readiness wait, printing) to the timeline.
+//!
+//! Recording enables the `Runtime`, `Log`, and `Network` domains, which a
+//! reused tab must not keep, so a debugged render's tab is closed rather
+//! than returned to the pool.
+
+use headless_chrome::browser::tab::EventListener;
+use headless_chrome::protocol::cdp::types::Event;
+use headless_chrome::protocol::cdp::{Log, Network, Runtime};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::Instant;
+
+use crate::service::types::{
+    ConsoleMessage, FailedRequest, ReadyOutcome, RenderReport, TimelineEvent,
+};
+
+/// Console messages and failed requests kept per report.
+const MAX_ENTRIES: usize = 200;
+
+/// Longest console message or URL kept, in bytes.
+const MAX_TEXT_LEN: usize = 2000;
+
+/// Lifecycle events of the main frame worth a timeline entry.
+const LIFECYCLE_EVENTS: &[&str] = &[
+    "DOMContentLoaded",
+    "load",
+    "firstContentfulPaint",
+    "networkAlmostIdle",
+    "networkIdle",
+];
+
+/// What has been recorded so far.
+#[derive(Debug, Default)]
+struct RenderLog {
+    timeline: Vec<TimelineEvent>,
+    console: Vec<ConsoleMessage>,
+    failed_requests: Vec<FailedRequest>,
+    ready: ReadyOutcome,
+    /// ID of the main frame, once it has navigated.
+    main_frame: Option<String>,
+    /// URL of every request seen, by request ID, for naming failures.
+    requests: HashMap<String, String>,
+}
+
+/// A debug report being recorded in one tab.
+pub(crate) struct DebugRecorder {
+    start: Instant,
+    log: Arc<Mutex<RenderLog>>,
+    listener: Weak<dyn EventListener<Event> + Send + Sync>,
+}
+
+impl DebugRecorder {
+    /// Start recording in `tab`; times in the report count from `start`.
+    pub(crate) fn start(tab: &headless_chrome::Tab, start: Instant) -> Result<Self, String> {
+        let log: Arc<Mutex<RenderLog>> = Arc::default();
+        let listener = {
+            let log = Arc::clone(&log);
+            Arc::new(move |event: &Event| {
+                let at_ms = start.elapsed().as_millis() as u64;
+                log.lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .record(at_ms, event);
+            })
+        };
+        let listener = tab
+            .add_event_listener(listener)
+            .map_err(|e| e.to_string())?;
+
+        let enabled = tab
+            .call_method(Runtime::Enable(None))
+            .and_then(|_| tab.call_method(Log::Enable(None)))
+            .and_then(|_| {
+                tab.call_method(Network::Enable {
+                    max_total_buffer_size: None,
+                    max_resource_buffer_size: None,
+                    max_post_data_size: None,
+                })
+            });
+        if let Err(e) = enabled {
+            let _ = tab.remove_event_listener(&listener);
+            return Err(e.to_string());
+        }
+
+        log::debug!("Recording debug report");
+        Ok(Self {
+            start,
+            log,
+            listener,
+        })
+    }
+
+    /// Add a service step to the timeline.
+    pub(crate) fn mark(&self, event: &str, detail: Option<String>) {
+        let at_ms = self.start.elapsed().as_millis() as u64;
+        self.lock().timeline.push(TimelineEvent {
+            at_ms,
+            event: event.to_string(),
+            detail,
+        });
+    }
+
+    /// Record how the readiness wait ended.
+    pub(crate) fn ready(&self, outcome: ReadyOutcome, expression: &str) {
+        self.lock().ready = outcome;
+        let event = match outcome {
+            ReadyOutcome::Signaled => "ready_signaled",
+            ReadyOutcome::TimedOut => "ready_timed_out",
+            ReadyOutcome::Cancelled => "ready_cancelled",
+            ReadyOutcome::Skipped => return,
+        };
+        self.mark(event, Some(expression.to_string()));
+    }
+
+    /// Stop recording and build the report.
+    pub(crate) fn finish(
+        self,
+        tab: &headless_chrome::Tab,
+        pdf_size: usize,
+        options: serde_json::Value,
+    ) -> RenderReport {
+        let _ = tab.remove_event_listener(&self.listener);
+        let log = std::mem::take(&mut *self.lock());
+        RenderReport {
+            duration_ms: self.start.elapsed().as_millis() as u64,
+            pdf_size,
+            ready: log.ready,
+            timeline: log.timeline,
+            console: log.console,
+            failed_requests: log.failed_requests,
+            options,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RenderLog> {
+        self.log.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RenderLog {
+    /// Add whatever `event` contributes to the report.
+    fn record(&mut self, at_ms: u64, event: &Event) {
+        match event {
+            Event::PageFrameNavigated(navigated) => {
+                let frame = &navigated.params.frame;
+                if frame.parent_id.is_none() {
+                    self.main_frame = Some(frame.id.clone());
+                    self.timeline.push(TimelineEvent {
+                        at_ms,
+                        event: "navigated".to_string(),
+                        detail: Some(truncate(&frame.url)),
+                    });
+                }
+            }
+            Event::PageLifecycleEvent(lifecycle) => {
+                let params = &lifecycle.params;
+                if self.main_frame.as_ref() == Some(&params.frame_id)
+                    && LIFECYCLE_EVENTS.contains(&params.name.as_str())
+                {
+                    self.timeline.push(TimelineEvent {
+                        at_ms,
+                        event: params.name.clone(),
+                        detail: None,
+                    });
+                }
+            }
+            Event::RuntimeConsoleAPICalled(called) => {
+                let params = &called.params;
+                let text = params
+                    .args
+                    .iter()
+                    .map(describe_value)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let url = params
+                    .stack_trace
+                    .as_ref()
+                    .and_then(|stack| stack.call_frames.first())
+                    .map(|frame| frame.url.clone())
+                    .filter(|url| !url.is_empty());
+                let level = cdp_name(&params.Type).unwrap_or_else(|| "log".to_string());
+                self.push_console(at_ms, level, text, url);
+            }
+            Event::RuntimeExceptionThrown(thrown) => {
+                let details = &thrown.params.exception_details;
+                let text = details
+                    .exception
+                    .as_ref()
+                    .and_then(|exception| exception.description.clone())
+                    .unwrap_or_else(|| details.text.clone());
+                self.push_console(at_ms, "exception".to_string(), text, details.url.clone());
+            }
+            Event::LogEntryAdded(added) => {
+                let entry = &added.params.entry;
+                let level = cdp_name(&entry.level).unwrap_or_else(|| "info".to_string());
+                self.push_console(at_ms, level, entry.text.clone(), entry.url.clone());
+            }
+            Event::NetworkRequestWillBeSent(sent) => {
+                let params = &sent.params;
+                self.requests
+                    .insert(params.request_id.clone(), truncate(&params.request.url));
+            }
+            Event::NetworkResponseReceived(received) => {
+                let params = &received.params;
+                let response = &params.response;
+                if response.status >= 400 {
+                    self.push_failure(
+                        truncate(&response.url),
+                        cdp_name(&params.Type),
+                        format!("HTTP {} {}", response.status, response.status_text)
+                            .trim_end()
+                            .to_string(),
+                    );
+                }
+            }
+            Event::NetworkLoadingFailed(failed) => {
+                let params = &failed.params;
+                let error = match &params.blocked_reason {
+                    Some(reason) => format!(
+                        "blocked: {}",
+                        cdp_name(reason).unwrap_or_else(|| format!("{:?}", reason))
+                    ),
+                    None => params.error_text.clone(),
+                };
+                let url = self
+                    .requests
+                    .get(&params.request_id)
+                    .cloned()
+                    .unwrap_or_default();
+                self.push_failure(url, cdp_name(&params.Type), error);
+            }
+            _ => {}
+        }
+    }
+
+    fn push_console(&mut self, at_ms: u64, level: String, text: String, url: Option<String>) {
+        if self.console.len() < MAX_ENTRIES {
+            self.console.push(ConsoleMessage {
+                at_ms,
+                level,
+                text: truncate(&text),
+                url: url.map(|url| truncate(&url)),
+            });
+        }
+    }
+
+    fn push_failure(&mut self, url: String, resource_type: Option<String>, error: String) {
+        if self.failed_requests.len() < MAX_ENTRIES {
+            self.failed_requests.push(FailedRequest {
+                url,
+                resource_type,
+                error,
+            });
+        }
+    }
+}
+
+/// Text of one console argument, as DevTools would print it.
+fn describe_value(value: &Runtime::RemoteObject) -> String {
+    match &value.value {
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+        None => value
+            .unserializable_value
+            .clone()
+            .or_else(|| value.description.clone())
+            .unwrap_or_else(|| cdp_name(&value.Type).unwrap_or_default()),
+    }
+}
+
+/// The protocol name of a CDP enum value, e.g. `error` or `Stylesheet`.
+fn cdp_name(value: &impl Serialize) -> Option<String> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => Some(name),
+        _ => None,
+    }
+}
+
+/// Cut `text` to [`MAX_TEXT_LEN`] bytes, on a character boundary.
+fn truncate(text: &str) -> String {
+    if text.len() <= MAX_TEXT_LEN {
+        return text.to_string();
+    }
+    let mut end = MAX_TEXT_LEN;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...", &text[..end])
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(method: &str, params: serde_json::Value) -> Event {
+        serde_json::from_value(serde_json::json!({ "method": method, "params": params })).unwrap()
+    }
+
+    /// Verifies navigations, console output, and failed requests land in
+    /// the right part of the report.
+    #[test]
+    fn test_record_events() {
+        let mut log = RenderLog::default();
+        let frame = serde_json::json!({
+            "id": "main", "loaderId": "l1", "url": "https://example.com/",
+            "domainAndRegistry": "example.com", "securityOrigin": "https://example.com",
+            "mimeType": "text/html", "secureContextType": "Secure",
+            "crossOriginIsolatedContextType": "NotIsolated", "gatedAPIFeatures": []
+        });
+        log.record(
+            5,
+            &event(
+                "Page.frameNavigated",
+                serde_json::json!({ "frame": frame, "type": "Navigation" }),
+            ),
+        );
+        for (frame_id, name) in [("main", "load"), ("child", "load"), ("main", "init")] {
+            log.record(
+                9,
+                &event(
+                    "Page.lifecycleEvent",
+                    serde_json::json!({ "frameId": frame_id, "loaderId": "l1", "name": name, "timestamp": 1.0 }),
+                ),
+            );
+        }
+        log.record(
+            12,
+            &event(
+                "Runtime.consoleAPICalled",
+                serde_json::json!({
+                    "type": "error", "executionContextId": 1, "timestamp": 1.0,
+                    "args": [{ "type": "string", "value": "no data" }, { "type": "number", "value": 42 }]
+                }),
+            ),
+        );
+        log.record(
+            13,
+            &event(
+                "Network.responseReceived",
+                serde_json::json!({
+                    "requestId": "r1", "loaderId": "l1", "timestamp": 1.0, "type": "XHR",
+                    "hasExtraInfo": false,
+                    "response": {
+                        "url": "https://api.example.com/data", "status": 401,
+                        "statusText": "Unauthorized", "headers": {}, "mimeType": "application/json",
+                        "charset": "", "connectionReused": false, "connectionId": 0.0,
+                        "encodedDataLength": 0.0, "securityState": "secure"
+                    }
+                }),
+            ),
+        );
+        log.requests.insert(
+            "r2".to_string(),
+            "https://cdn.example.com/logo.png".to_string(),
+        );
+        log.record(
+            14,
+            &event(
+                "Network.loadingFailed",
+                serde_json::json!({
+                    "requestId": "r2", "timestamp": 1.0, "type": "Image",
+                    "errorText": "net::ERR_NAME_NOT_RESOLVED"
+                }),
+            ),
+        );
+
+        let events: Vec<_> = log.timeline.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(events, ["navigated", "load"]);
+        assert_eq!(
+            log.timeline[0].detail.as_deref(),
+            Some("https://example.com/")
+        );
+
+        assert_eq!(log.console.len(), 1);
+        assert_eq!(log.console[0].level, "error");
+        assert_eq!(log.console[0].text, "no data 42");
+
+        assert_eq!(
+            log.failed_requests,
+            [
+                FailedRequest {
+                    url: "https://api.example.com/data".to_string(),
+                    resource_type: Some("XHR".to_string()),
+                    error: "HTTP 401 Unauthorized".to_string(),
+                },
+                FailedRequest {
+                    url: "https://cdn.example.com/logo.png".to_string(),
+                    resource_type: Some("Image".to_string()),
+                    error: "net::ERR_NAME_NOT_RESOLVED".to_string(),
+                },
+            ]
+        );
+    }
+
+    /// Verifies long text is cut on a character boundary.
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short"), "short");
+        let long = "é".repeat(MAX_TEXT_LEN);
+        let cut = truncate(&long);
+        assert!(cut.ends_with("..."));
+        assert!(cut.len() <= MAX_TEXT_LEN + 3);
+    }
+}
@@ -0,0 +1,296 @@
+//! Placeholder PDFs returned instead of JSON errors.
+//!
+//! PDF links embedded in emails or web pages are opened by people, not API
+//! clients, and a JSON error body shows up as a broken download. Requests
+//! with `fallback_pdf` set get a one-page "generation failed" document
+//! instead, showing a request ID and a short error summary that support
+//! can match against the server log.
+//!
+//! The placeholder is written directly, without Chrome, so it is available
+//! even when the failure was an exhausted or unhealthy browser pool.
+
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::service::types::{PdfResponse, PdfServiceError};
+
+/// Request header whose value is used as the request ID, when present.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Longest caller-supplied request ID accepted.
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// Characters per line of the error summary.
+const LINE_WIDTH: usize = 80;
+
+/// Lines of error summary shown before it is cut off.
+const MAX_SUMMARY_LINES: usize = 8;
+
+/// Placeholder document returned in place of an error response.
+///
+/// Build one before rendering (so the request ID can be logged with the
+/// render) and call [`render`](Self::render) if the render fails.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{FallbackPdf, PdfServiceError};
+///
+/// let fallback = FallbackPdf::new(None, "invoice.pdf".to_string(), false);
+/// let error = PdfServiceError::Timeout("Operation timed out after 60 seconds".to_string());
+/// let placeholder = fallback.render(&error);
+/// assert!(placeholder.data.starts_with(b"%PDF-"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FallbackPdf {
+    /// ID shown on the placeholder and logged with the failure.
+    pub request_id: String,
+
+    /// Filename of the placeholder, normally the requested filename.
+    pub filename: String,
+
+    /// Whether the placeholder is sent as an attachment.
+    pub force_download: bool,
+}
+
+impl FallbackPdf {
+    /// Create a placeholder for one request.
+    ///
+    /// `request_id` is the caller's [`REQUEST_ID_HEADER`] value; it is used
+    /// if it is at most 64 printable ASCII characters, and a random ID is
+    /// generated otherwise.
+    pub fn new(request_id: Option<&str>, filename: String, force_download: bool) -> Self {
+        let request_id = request_id
+            .map(str::trim)
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= MAX_REQUEST_ID_LEN
+                    && id.bytes().all(|b| b.is_ascii_graphic())
+            })
+            .map(String::from)
+            .unwrap_or_else(generate_request_id);
+
+        Self {
+            request_id,
+            filename,
+            force_download,
+        }
+    }
+
+    /// Build the placeholder PDF for `error`.
+    ///
+    /// Logs the failure with the request ID, since the client only sees
+    /// a successful PDF download.
+    pub fn render(&self, error: &PdfServiceError) -> PdfResponse {
+        log::warn!(
+            "Returning fallback PDF for request {}: {} ({})",
+            self.request_id,
+            error,
+            error.error_code()
+        );
+
+        let mut lines = vec![
+            "The requested document could not be generated.".to_string(),
+            "Please try again later. If the problem persists, contact support".to_string(),
+            "and quote the request ID below.".to_string(),
+            String::new(),
+            format!("Request ID: {}", self.request_id),
+            format!("Error code: {}", error.error_code()),
+            String::new(),
+        ];
+        lines.extend(wrap(&error.to_string(), LINE_WIDTH, MAX_SUMMARY_LINES));
+
+        PdfResponse::new(
+            write_pdf("PDF generation failed", &lines),
+            self.filename.clone(),
+            self.force_download,
+        )
+    }
+}
+
+/// Random 16-hex-digit request ID.
+pub(crate) fn generate_request_id() -> String {
+    let mut bytes = [0u8; 8];
+    if SystemRandom::new().fill(&mut bytes).is_err() {
+        // Only reachable if the OS RNG is unavailable; fall back to the clock.
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        bytes = nanos.to_be_bytes();
+    }
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Split `text` into lines of at most `width` characters, breaking at
+/// spaces, and cut it off after `max_lines`.
+fn wrap(text: &str, width: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        while word.chars().count() > width {
+            let rest = word.split_off(word.char_indices().nth(width).map_or(0, |(i, _)| i));
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(word);
+            word = rest;
+        }
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            last.push_str(" ...");
+        }
+    }
+    lines
+}
+
+/// Escape text for a PDF string literal, replacing characters the
+/// standard Helvetica encoding cannot show.
+fn pdf_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+/// Write a one-page A4 PDF with a bold title and plain text lines.
+fn write_pdf(title: &str, lines: &[String]) -> Vec<u8> {
+    let mut content = format!("BT /F2 18 Tf 56 770 Td ({}) Tj ET\n", pdf_string(title));
+    for (i, line) in lines.iter().enumerate() {
+        content.push_str(&format!(
+            "BT /F1 11 Tf 56 {} Td ({}) Tj ET\n",
+            735 - 16 * i,
+            pdf_string(line)
+        ));
+    }
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] \
+         /Resources << /Font << /F1 4 0 R /F2 5 0 R >> >> /Contents 6 0 R >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold >>".to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies the placeholder is a well-formed PDF carrying the request
+    /// ID and error summary.
+    #[test]
+    fn test_fallback_pdf_render() {
+        let fallback = FallbackPdf::new(Some("req-42"), "invoice.pdf".to_string(), true);
+        let response = fallback.render(&PdfServiceError::NavigationFailed(
+            "net::ERR_NAME_NOT_RESOLVED (https://example.invalid)".to_string(),
+        ));
+
+        assert_eq!(response.filename, "invoice.pdf");
+        assert!(response.force_download);
+
+        let pdf = String::from_utf8(response.data.to_vec()).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("(Request ID: req-42)"));
+        assert!(pdf.contains("(Error code: NAVIGATION_FAILED)"));
+        assert!(pdf.contains("\\(https://example.invalid\\)"));
+
+        // The cross-reference table must point at the objects
+        let startxref: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(pdf[startxref..].starts_with("xref\n0 7\n"));
+        let first_offset: usize = pdf[startxref..].lines().nth(3).unwrap()[..10]
+            .parse()
+            .unwrap();
+        assert!(pdf[first_offset..].starts_with("1 0 obj"));
+    }
+
+    /// Verifies caller request IDs are kept only when safe to print.
+    #[test]
+    fn test_fallback_request_id() {
+        let keep = |id: &str| FallbackPdf::new(Some(id), String::new(), false).request_id;
+
+        assert_eq!(keep(" abc-123 "), "abc-123");
+        assert_ne!(keep("has space"), "has space");
+        assert_ne!(keep("ünïcode"), "ünïcode");
+        assert_eq!(keep(&"x".repeat(65)).len(), 16);
+        assert_eq!(
+            FallbackPdf::new(None, String::new(), false)
+                .request_id
+                .len(),
+            16
+        );
+    }
+
+    /// Verifies long summaries wrap at spaces and are cut off.
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("a bb ccc", 4, 10), vec!["a bb", "ccc"]);
+        assert_eq!(wrap("abcdefghij", 4, 10), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("a b c d", 1, 2), vec!["a", "b ..."]);
+        assert!(wrap("", 10, 10).is_empty());
+    }
+}
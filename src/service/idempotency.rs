@@ -0,0 +1,375 @@
+//! `Idempotency-Key` support for render endpoints.
+//!
+//! A client that loses the connection while a PDF is rendering cannot tell
+//! whether the render happened. By sending an `Idempotency-Key` header, it
+//! can retry safely: within the pool's
+//! [`idempotency_window`](crate::BrowserPoolConfig::idempotency_window) a
+//! repeat of the same key returns the first PDF instead of rendering (and
+//! billing) the document again.
+//!
+//! # Semantics
+//!
+//! | Situation | Result |
+//! |-----------|--------|
+//! | First request with a key | Renders; a successful PDF is cached |
+//! | Repeat after success | Cached PDF, no render, no usage recorded |
+//! | Repeat while the first is rendering | `IDEMPOTENCY_CONFLICT` (409) |
+//! | Repeat after a failure | Renders again (failures are not cached) |
+//! | Same key, different request | `INVALID_REQUEST` (400) |
+//!
+//! Keys are scoped to the API key (`X-API-Key`), so one client can never
+//! receive another client's PDF by reusing its idempotency key.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::pool::BrowserPool;
+use crate::service::types::{PdfResponse, PdfServiceError};
+use crate::usage::ANONYMOUS_KEY;
+
+/// Request header carrying the client's idempotency key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Longest idempotency key accepted.
+const MAX_KEY_LEN: usize = 255;
+
+/// Most responses kept at once; the oldest is evicted beyond this.
+const MAX_ENTRIES: usize = 256;
+
+/// Responses to recently seen idempotency keys.
+///
+/// Owned by the [`BrowserPool`]; use [`render_idempotent`] rather than
+/// this type directly.
+#[derive(Debug)]
+pub(crate) struct IdempotencyCache {
+    window: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    /// SHA-256 of the serialized request the key was first used with.
+    fingerprint: Vec<u8>,
+
+    /// When the render started, or finished once `response` is set.
+    at: Instant,
+
+    /// The PDF, or `None` while the first request is still rendering.
+    response: Option<PdfResponse>,
+}
+
+impl IdempotencyCache {
+    /// Create a cache replaying responses for `window` (zero disables it).
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim `key` for a new render, or return its cached response.
+    fn begin(&self, key: &str, fingerprint: &[u8]) -> Result<Option<PdfResponse>, PdfServiceError> {
+        if self.window.is_zero() {
+            return Ok(None);
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        entries.retain(|_, entry| now.duration_since(entry.at) < self.window);
+
+        match entries.get(key) {
+            Some(entry) if entry.fingerprint != fingerprint => {
+                Err(PdfServiceError::InvalidRequest(
+                    "Idempotency-Key was already used with a different request".to_string(),
+                ))
+            }
+            Some(Entry {
+                response: Some(response),
+                ..
+            }) => Ok(Some(response.clone())),
+            Some(_) => Err(PdfServiceError::IdempotencyConflict(
+                "a request with this Idempotency-Key is still rendering".to_string(),
+            )),
+            None => {
+                if entries.len() >= MAX_ENTRIES {
+                    let oldest = entries
+                        .iter()
+                        .min_by_key(|(_, entry)| entry.at)
+                        .map(|(key, _)| key.clone());
+                    if let Some(oldest) = oldest {
+                        entries.remove(&oldest);
+                    }
+                }
+                entries.insert(
+                    key.to_string(),
+                    Entry {
+                        fingerprint: fingerprint.to_vec(),
+                        at: now,
+                        response: None,
+                    },
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Cache a successful render for `key`, or release the key after a failure.
+    fn finish(&self, key: &str, result: &Result<PdfResponse, PdfServiceError>) {
+        if self.window.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        match result {
+            Ok(response) => {
+                if let Some(entry) = entries.get_mut(key) {
+                    entry.at = Instant::now();
+                    entry.response = Some(response.clone());
+                }
+            }
+            Err(_) => {
+                entries.remove(key);
+            }
+        }
+    }
+
+    /// Forget `key` without caching anything, so it can be used again.
+    fn release(&self, key: &str) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key);
+    }
+}
+
+/// Releases an idempotency key when dropped before its render finished,
+/// e.g. because the render panicked, so retries are not rejected as still
+/// rendering.
+struct PendingKey<'a> {
+    pool: &'a Mutex<BrowserPool>,
+    key: &'a str,
+    finished: bool,
+}
+
+impl Drop for PendingKey<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.pool
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .idempotency()
+                .release(self.key);
+        }
+    }
+}
+
+/// Run a render at most once per idempotency key.
+///
+/// Without an idempotency key this just calls `render`. With one, a repeat
+/// inside the pool's
+/// [`idempotency_window`](crate::BrowserPoolConfig::idempotency_window)
+/// returns the first successful PDF without calling `render`; see the
+/// [module documentation](self) for the full rules.
+///
+/// Wrap [`render_metered`](crate::service::render_metered) with this, not
+/// the other way around, so replays are not counted as new renders.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `api_key` - Key from the `X-API-Key` header, scoping the idempotency key
+/// * `idempotency_key` - Key from the [`IDEMPOTENCY_KEY_HEADER`] header
+/// * `request` - The request, used to detect a key reused for different input
+/// * `render` - The render to run once
+///
+/// # Errors
+///
+/// - [`PdfServiceError::InvalidRequest`] if the key is longer than 255
+///   characters or was used with a different request
+/// - [`PdfServiceError::IdempotencyConflict`] if the first request with the
+///   key is still rendering
+/// - Any error returned by `render`
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{generate_pdf_from_html, render_idempotent, render_metered};
+///
+/// let response = render_idempotent(&pool, api_key, Some("order-1234"), &request, || {
+///     render_metered(&pool, api_key, || generate_pdf_from_html(&pool, &request))
+/// })?;
+/// ```
+pub fn render_idempotent<R, F>(
+    pool: &Mutex<BrowserPool>,
+    api_key: Option<&str>,
+    idempotency_key: Option<&str>,
+    request: &R,
+    render: F,
+) -> Result<PdfResponse, PdfServiceError>
+where
+    R: Serialize,
+    F: FnOnce() -> Result<PdfResponse, PdfServiceError>,
+{
+    let Some(idempotency_key) = idempotency_key else {
+        return render();
+    };
+
+    if idempotency_key.len() > MAX_KEY_LEN {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "Idempotency-Key must be at most {} characters",
+            MAX_KEY_LEN
+        )));
+    }
+
+    let fingerprint = fingerprint(request)?;
+    let key = format!("{}\n{}", api_key.unwrap_or(ANONYMOUS_KEY), idempotency_key);
+
+    if let Some(response) = with_cache(pool, |cache| cache.begin(&key, &fingerprint))?? {
+        log::debug!("Replaying response for a repeated Idempotency-Key");
+        return Ok(response);
+    }
+
+    let mut pending = PendingKey {
+        pool,
+        key: &key,
+        finished: false,
+    };
+    let result = render();
+    pending.finished = true;
+
+    if let Err(e) = with_cache(pool, |cache| cache.finish(&key, &result)) {
+        log::error!("Failed to store idempotent response: {}", e);
+    }
+
+    result
+}
+
+/// Run `f` on the pool's idempotency cache.
+fn with_cache<T>(
+    pool: &Mutex<BrowserPool>,
+    f: impl FnOnce(&IdempotencyCache) -> T,
+) -> Result<T, PdfServiceError> {
+    let pool_guard = pool.lock().map_err(|e| {
+        log::error!("Failed to lock browser pool for idempotency: {}", e);
+        PdfServiceError::PoolLockFailed(e.to_string())
+    })?;
+
+    Ok(f(pool_guard.idempotency()))
+}
+
+/// SHA-256 of the request's JSON form.
+pub(crate) fn fingerprint<R: Serialize>(request: &R) -> Result<Vec<u8>, PdfServiceError> {
+    let json = serde_json::to_vec(request).map_err(|e| PdfServiceError::Internal(e.to_string()))?;
+    Ok(ring::digest::digest(&ring::digest::SHA256, &json)
+        .as_ref()
+        .to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BrowserPoolConfigBuilder;
+    use crate::factory::mock::MockBrowserFactory;
+    use crate::service::PdfFromHtmlRequest;
+
+    fn pdf(body: &[u8]) -> Result<PdfResponse, PdfServiceError> {
+        Ok(PdfResponse::new(
+            body.to_vec(),
+            "doc.pdf".to_string(),
+            false,
+        ))
+    }
+
+    /// Verifies repeats replay the first PDF and failures are not cached.
+    #[test]
+    fn test_render_idempotent() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .config(BrowserPoolConfigBuilder::new().build().unwrap())
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+        let pool = Mutex::new(pool);
+        let request = PdfFromHtmlRequest {
+            html: "<h1>Invoice</h1>".to_string(),
+            ..Default::default()
+        };
+
+        let first = render_idempotent(&pool, None, Some("k1"), &request, || pdf(b"first"));
+        let repeat = render_idempotent(&pool, None, Some("k1"), &request, || pdf(b"second"));
+        assert_eq!(&first.unwrap().data[..], b"first");
+        assert_eq!(&repeat.unwrap().data[..], b"first");
+
+        // Scoped per API key
+        let other = render_idempotent(&pool, Some("team-b"), Some("k1"), &request, || {
+            pdf(b"other")
+        });
+        assert_eq!(&other.unwrap().data[..], b"other");
+
+        // Reused for a different request
+        let changed = PdfFromHtmlRequest {
+            html: "<h1>Changed</h1>".to_string(),
+            ..Default::default()
+        };
+        let result = render_idempotent(&pool, None, Some("k1"), &changed, || pdf(b"x"));
+        assert!(matches!(result, Err(PdfServiceError::InvalidRequest(_))));
+
+        // Failures release the key
+        let failed = render_idempotent(&pool, None, Some("k2"), &request, || {
+            Err(PdfServiceError::Timeout("slow".to_string()))
+        });
+        assert!(failed.is_err());
+        let retried = render_idempotent(&pool, None, Some("k2"), &request, || pdf(b"retry"));
+        assert_eq!(&retried.unwrap().data[..], b"retry");
+    }
+
+    /// Verifies a render that panics releases its key, so a retry renders
+    /// instead of conflicting forever.
+    #[test]
+    fn test_render_idempotent_panic() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .config(BrowserPoolConfigBuilder::new().build().unwrap())
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+        let pool = Mutex::new(pool);
+        let request = PdfFromHtmlRequest {
+            html: "<h1>Invoice</h1>".to_string(),
+            ..Default::default()
+        };
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            render_idempotent(&pool, None, Some("k1"), &request, || {
+                panic!("render panicked")
+            })
+        }));
+        assert!(panicked.is_err());
+
+        let retried = render_idempotent(&pool, None, Some("k1"), &request, || pdf(b"retry"));
+        assert_eq!(&retried.unwrap().data[..], b"retry");
+    }
+
+    /// Verifies a repeat during the first render conflicts, and a zero
+    /// window disables caching.
+    #[test]
+    fn test_idempotency_cache_states() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        assert!(cache.begin("k", b"fp").unwrap().is_none());
+        assert!(matches!(
+            cache.begin("k", b"fp"),
+            Err(PdfServiceError::IdempotencyConflict(_))
+        ));
+
+        let disabled = IdempotencyCache::new(Duration::ZERO);
+        assert!(disabled.begin("k", b"fp").unwrap().is_none());
+        disabled.finish("k", &pdf(b"a"));
+        assert!(disabled.begin("k", b"fp").unwrap().is_none());
+    }
+}
@@ -0,0 +1,471 @@
+//! Incremental updates to rendered PDFs.
+//!
+//! Metadata stamping and signing both edit a finished PDF by appending to
+//! it, as any PDF editor saving in place does: the changed and new objects,
+//! a cross-reference table listing only them, and a trailer chaining back
+//! to the previous table.
+//!
+//! ```text
+//! %PDF-1.4 ... xref ... trailer << /Size 42 ... >> startxref ... %%EOF   (as Chrome wrote it)
+//! 42 0 obj << ... >> endobj
+//! xref 42 1 ... trailer << /Size 43 ... /Prev ... >> startxref ... %%EOF
+//! ```
+//!
+//! The original bytes are untouched, so the pages render exactly as before.
+//! Only classic cross-reference tables are read; Chrome writes those, but
+//! files rewritten with cross-reference streams (PDF 1.5) are refused.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Most cross-reference sections followed looking for an object.
+const MAX_XREF_SECTIONS: usize = 32;
+
+/// How far from the end of the file `startxref` is looked for.
+const STARTXREF_WINDOW: usize = 1024;
+
+/// An incremental update being built for a PDF.
+///
+/// Errors are descriptions of what could not be parsed; callers wrap them
+/// in their own error.
+pub(crate) struct Update<'a> {
+    pdf: &'a [u8],
+    /// Offset of the cross-reference table the update chains to.
+    last_xref: usize,
+    section: XrefSection,
+    /// Next free object number.
+    size: u32,
+    /// Objects written so far, with their source between `obj` and `endobj`.
+    objects: Vec<(u32, String)>,
+}
+
+impl<'a> Update<'a> {
+    /// Read `pdf`'s last cross-reference table and trailer.
+    pub(crate) fn open(pdf: &'a [u8]) -> Result<Self, String> {
+        let last_xref = find_startxref(pdf).ok_or("no startxref")?;
+        let section = XrefSection::parse(pdf, last_xref)?;
+        let size = dict_get(&section.trailer, "Size")
+            .and_then(|size| size.parse().ok())
+            .ok_or("trailer has no /Size")?;
+        Ok(Self {
+            pdf,
+            last_xref,
+            section,
+            size,
+            objects: Vec::new(),
+        })
+    }
+
+    /// A value in the current trailer, as PDF source.
+    pub(crate) fn trailer(&self, key: &str) -> Option<&str> {
+        dict_get(&self.section.trailer, key)
+    }
+
+    /// The dictionary of the object a reference such as `5 0 R` points to.
+    pub(crate) fn read_dict(&self, reference: &str) -> Result<Vec<(String, String)>, String> {
+        let number = parse_reference(reference)
+            .ok_or_else(|| format!("malformed reference {}", reference))?;
+        let offset = find_object(self.pdf, &self.section, number)?;
+        read_object_dict(self.pdf, offset, number)
+    }
+
+    /// Allocate a new object number.
+    pub(crate) fn new_object(&mut self) -> u32 {
+        let number = self.size;
+        self.size += 1;
+        number
+    }
+
+    /// Write object `number`, new or replacing an existing one, as the
+    /// dictionary `entries`.
+    pub(crate) fn write_dict(&mut self, number: u32, entries: &[(String, String)]) {
+        self.write_object(number, dict_source(entries));
+    }
+
+    /// Write object `number` with the given source.
+    pub(crate) fn write_object(&mut self, number: u32, source: impl Into<String>) {
+        self.objects.push((number, source.into()));
+    }
+
+    /// The PDF with the update appended; `trailer` entries are added to or
+    /// replace those of the current trailer.
+    pub(crate) fn finish(mut self, trailer: &[(&str, String)]) -> Vec<u8> {
+        let mut update = String::new();
+        if !self.pdf.ends_with(b"\n") {
+            update.push('\n');
+        }
+
+        self.objects.sort_by_key(|(number, _)| *number);
+        let mut offsets = Vec::with_capacity(self.objects.len());
+        for (number, source) in &self.objects {
+            offsets.push((*number, self.pdf.len() + update.len()));
+            let _ = write!(update, "{} 0 obj\n{}\nendobj\n", number, source);
+        }
+
+        let xref_offset = self.pdf.len() + update.len();
+        update.push_str("xref\n");
+        for (number, offset) in offsets {
+            let _ = write!(update, "{} 1\n{:010} 00000 n \n", number, offset);
+        }
+        update.push_str("trailer\n<<");
+        for (key, value) in &self.section.trailer {
+            let replaced = trailer.iter().any(|(name, _)| name == key);
+            if !replaced && !matches!(key.as_str(), "Size" | "Prev") {
+                let _ = write!(update, " /{} {}", key, value);
+            }
+        }
+        let _ = write!(update, " /Size {}", self.size);
+        for (key, value) in trailer {
+            let _ = write!(update, " /{} {}", key, value);
+        }
+        let _ = write!(
+            update,
+            " /Prev {} >>\nstartxref\n{}\n%%EOF\n",
+            self.last_xref, xref_offset
+        );
+
+        let mut updated = Vec::with_capacity(self.pdf.len() + update.len());
+        updated.extend_from_slice(self.pdf);
+        updated.extend_from_slice(update.as_bytes());
+        updated
+    }
+}
+
+/// One classic cross-reference section and the trailer after it.
+struct XrefSection {
+    /// Byte offset of each in-use object in this section.
+    offsets: HashMap<u32, usize>,
+    trailer: Vec<(String, String)>,
+}
+
+impl XrefSection {
+    /// Parse the `xref` table at `offset` and its trailer.
+    ///
+    /// Cross-reference streams (PDF 1.5) are not supported; Chrome writes
+    /// classic tables.
+    fn parse(pdf: &[u8], offset: usize) -> Result<Self, String> {
+        let rest = pdf.get(offset..).ok_or("startxref points past the end")?;
+        let rest = rest.strip_prefix(b"xref").ok_or(
+            "no classic cross-reference table (cross-reference streams are not supported)",
+        )?;
+        let trailer_at = find(rest, b"trailer").ok_or("no trailer")?;
+        let table = std::str::from_utf8(&rest[..trailer_at]).map_err(|_| "malformed xref")?;
+
+        let mut offsets = HashMap::new();
+        let mut tokens = table.split_ascii_whitespace();
+        while let Some(first) = tokens.next() {
+            let first: u32 = first.parse().map_err(|_| "malformed xref")?;
+            let count: u32 = tokens
+                .next()
+                .and_then(|count| count.parse().ok())
+                .ok_or("malformed xref")?;
+            for number in first..first.saturating_add(count) {
+                let (Some(position), Some(_), Some(kind)) =
+                    (tokens.next(), tokens.next(), tokens.next())
+                else {
+                    return Err("truncated xref".to_string());
+                };
+                if kind == "n" {
+                    offsets.insert(number, position.parse().map_err(|_| "malformed xref")?);
+                }
+            }
+        }
+
+        let trailer = &rest[trailer_at + b"trailer".len()..];
+        let start = find(trailer, b"<<").ok_or("no trailer dictionary")?;
+        let (trailer, _) = parse_dict(&trailer[start..]).ok_or("malformed trailer")?;
+        Ok(Self { offsets, trailer })
+    }
+}
+
+/// Offset of object `number`, looked up in `section` and the sections
+/// before it.
+fn find_object(pdf: &[u8], section: &XrefSection, number: u32) -> Result<usize, String> {
+    let mut earlier;
+    let mut section = section;
+    for _ in 0..MAX_XREF_SECTIONS {
+        if let Some(offset) = section.offsets.get(&number) {
+            return Ok(*offset);
+        }
+        let prev = dict_get(&section.trailer, "Prev")
+            .and_then(|prev| prev.parse().ok())
+            .ok_or_else(|| format!("object {} not in xref", number))?;
+        earlier = XrefSection::parse(pdf, prev)?;
+        section = &earlier;
+    }
+    Err("too many xref sections".to_string())
+}
+
+/// The dictionary of the object `number` starting at `offset`.
+fn read_object_dict(
+    pdf: &[u8],
+    offset: usize,
+    number: u32,
+) -> Result<Vec<(String, String)>, String> {
+    let object = pdf.get(offset..).ok_or("xref offset past the end")?;
+    let header = format!("{} ", number);
+    let body = find(object, b"obj")
+        .filter(|_| object.starts_with(header.as_bytes()))
+        .ok_or_else(|| format!("no object {} at offset {}", number, offset))?;
+    let object = &object[body + b"obj".len()..];
+    let start = skip_whitespace(object, 0);
+    if !object[start..].starts_with(b"<<") {
+        return Err(format!("object {} is not a dictionary", number));
+    }
+    parse_dict(&object[start..])
+        .map(|(entries, _)| entries)
+        .ok_or_else(|| format!("malformed object {}", number))
+}
+
+/// Offset given by the last `startxref` in the file.
+fn find_startxref(pdf: &[u8]) -> Option<usize> {
+    let tail_start = pdf.len().saturating_sub(STARTXREF_WINDOW);
+    let tail = &pdf[tail_start..];
+    let at = tail.windows(9).rposition(|window| window == b"startxref")?;
+    let number = tail[at + 9..]
+        .iter()
+        .skip_while(|b| b.is_ascii_whitespace())
+        .take_while(|b| b.is_ascii_digit())
+        .map(|b| char::from(*b))
+        .collect::<String>();
+    number.parse().ok()
+}
+
+/// Parse the dictionary at the start of `bytes` (which begin with `<<`).
+///
+/// Returns its entries, each value as the raw PDF source, and the number of
+/// bytes consumed.
+fn parse_dict(bytes: &[u8]) -> Option<(Vec<(String, String)>, usize)> {
+    let mut i = 2;
+    let mut entries = Vec::new();
+    loop {
+        i = skip_whitespace(bytes, i);
+        if bytes.get(i..i + 2) == Some(b">>") {
+            return Some((entries, i + 2));
+        }
+        if bytes.get(i) != Some(&b'/') {
+            return None;
+        }
+        let key_end = name_end(bytes, i + 1);
+        let key = String::from_utf8_lossy(&bytes[i + 1..key_end]).into_owned();
+
+        let start = skip_whitespace(bytes, key_end);
+        let end = value_end(bytes, start)?;
+        let value = String::from_utf8_lossy(&bytes[start..end])
+            .trim()
+            .to_string();
+        entries.push((key, value));
+        i = end;
+    }
+}
+
+/// End of the value starting at `start`: one name, or everything up to
+/// the next key or the end of the enclosing dictionary, which covers
+/// numbers, references (`5 0 R`), strings, arrays, and nested dictionaries.
+fn value_end(bytes: &[u8], start: usize) -> Option<usize> {
+    if bytes.get(start) == Some(&b'/') {
+        return Some(name_end(bytes, start + 1));
+    }
+    let mut i = start;
+    let mut depth = 0usize;
+    loop {
+        match *bytes.get(i)? {
+            b'(' => i = string_end(bytes, i)?,
+            b'<' if bytes.get(i + 1) == Some(&b'<') => {
+                depth += 1;
+                i += 2;
+            }
+            b'<' => i = bytes[i..].iter().position(|b| *b == b'>')? + i + 1,
+            b'>' if bytes.get(i + 1) == Some(&b'>') => {
+                if depth == 0 {
+                    return Some(i);
+                }
+                depth -= 1;
+                i += 2;
+            }
+            b'[' => {
+                depth += 1;
+                i += 1;
+            }
+            b']' => {
+                depth = depth.checked_sub(1)?;
+                i += 1;
+            }
+            b'/' if depth == 0 && i > start => return Some(i),
+            _ => i += 1,
+        }
+    }
+}
+
+/// End of the literal string starting at `start` (an opening `(`), which
+/// may hold balanced parentheses and backslash escapes.
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = start;
+    loop {
+        match *bytes.get(i)? {
+            b'\\' => i += 1,
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// End of the name whose characters start at `start`.
+fn name_end(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|b| b.is_ascii_whitespace() || b"/<>[]()%".contains(b))
+        .map_or(bytes.len(), |end| start + end)
+}
+
+fn skip_whitespace(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .map_or(bytes.len(), |end| start + end)
+}
+
+/// The object number of a reference such as `5 0 R`.
+pub(crate) fn parse_reference(value: &str) -> Option<u32> {
+    let mut parts = value.split_ascii_whitespace();
+    let number = parts.next()?.parse().ok()?;
+    parts.next()?.parse::<u32>().ok()?;
+    (parts.next()? == "R").then_some(number)
+}
+
+/// The value of `key` among a dictionary's entries.
+pub(crate) fn dict_get<'a>(entries: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    entries
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// The source of a dictionary with the given entries.
+fn dict_source(entries: &[(String, String)]) -> String {
+    let mut source = String::from("<<");
+    for (key, value) in entries {
+        let _ = write!(source, " /{} {}", key, value);
+    }
+    source.push_str(" >>");
+    source
+}
+
+pub(crate) fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Encode `text` as a PDF text string: a literal string for printable
+/// ASCII, UTF-16BE with a byte order mark otherwise.
+pub(crate) fn text_string(text: &str) -> String {
+    if text.bytes().all(|b| (0x20..0x7f).contains(&b)) {
+        let mut literal = String::with_capacity(text.len() + 2);
+        literal.push('(');
+        for c in text.chars() {
+            if matches!(c, '\\' | '(' | ')') {
+                literal.push('\\');
+            }
+            literal.push(c);
+        }
+        literal.push(')');
+        literal
+    } else {
+        let mut hex = String::from("<FEFF");
+        for unit in text.encode_utf16() {
+            let _ = write!(hex, "{:04X}", unit);
+        }
+        hex.push('>');
+        hex
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A minimal PDF laid out the way Chrome writes one, with its offsets
+    /// computed.
+    pub(crate) fn chrome_like_pdf() -> Vec<u8> {
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [] /Count 0 >>",
+            "<< /Title (Report \\(draft\\)) /Creator (Chromium) /Producer <FEFF0053006B00690061> >>",
+        ];
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+        }
+        let xref = pdf.len();
+        pdf.extend_from_slice(b"xref\n0 4\n0000000000 65535 f \n");
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size 4 /Root 1 0 R /Info 3 0 R >>\nstartxref\n{}\n%%EOF",
+                xref
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+
+    /// Verifies replaced and new objects are listed at their offsets, and a
+    /// later update reads the replacements.
+    #[test]
+    fn test_update() {
+        let pdf = chrome_like_pdf();
+        let mut update = Update::open(&pdf).unwrap();
+        let mut catalog = update.read_dict(update.trailer("Root").unwrap()).unwrap();
+        assert_eq!(dict_get(&catalog, "Pages"), Some("2 0 R"));
+
+        let page_labels = update.new_object();
+        assert_eq!(page_labels, 4);
+        update.write_object(page_labels, "<< /Nums [0 << /S /r >>] >>");
+        catalog.push(("PageLabels".to_string(), "4 0 R".to_string()));
+        update.write_dict(1, &catalog);
+        let updated = update.finish(&[]);
+
+        assert!(updated.starts_with(&pdf));
+        let appended = String::from_utf8(updated[pdf.len()..].to_vec()).unwrap();
+        for number in [1, 4] {
+            let object = appended.find(&format!("{} 0 obj", number)).unwrap() + pdf.len();
+            assert!(appended.contains(&format!("{} 1\n{:010} 00000 n ", number, object)));
+        }
+        assert!(appended.contains("/Root 1 0 R /Info 3 0 R /Size 5 /Prev "));
+
+        let update = Update::open(&updated).unwrap();
+        let catalog = update.read_dict("1 0 R").unwrap();
+        assert_eq!(dict_get(&catalog, "PageLabels"), Some("4 0 R"));
+        let labels = update.read_dict("4 0 R").unwrap();
+        assert_eq!(dict_get(&labels, "Nums"), Some("[0 << /S /r >>]"));
+        assert!(update.read_dict("9 0 R").is_err());
+    }
+
+    /// Verifies files without a classic cross-reference table are refused.
+    #[test]
+    fn test_update_unreadable() {
+        for pdf in [
+            &b"not a pdf"[..],
+            b"%PDF-1.5\n1 0 obj\n<< /Type /XRef >>\nendobj\nstartxref\n9\n%%EOF",
+        ] {
+            assert!(Update::open(pdf).is_err());
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Page count and size of generated PDFs.
+//!
+//! Billing and pagination UIs need the page count of every render, and
+//! parsing it client-side means shipping a PDF library. Chrome writes page
+//! dictionaries as plain objects (never inside compressed object streams),
+//! so a scan for `/Type /Page` and the first `/MediaBox` is enough; no
+//! PDF library is needed.
+//!
+//! The pre-built endpoints send the results as [`PDF_PAGES_HEADER`] and
+//! [`PDF_PAGE_SIZE_HEADER`], and job status responses include them.
+
+use crate::service::types::PageInfo;
+
+/// Response header carrying the PDF's page count.
+pub const PDF_PAGES_HEADER: &str = "X-Pdf-Pages";
+
+/// Response header carrying the first page's size in points, as
+/// `{width}x{height}` (e.g. `612x792` for US Letter).
+pub const PDF_PAGE_SIZE_HEADER: &str = "X-Pdf-Page-Size";
+
+/// Count the pages of `pdf` and measure the first one.
+///
+/// Returns `None` when no page dictionary is found, e.g. for PDFs whose
+/// objects are all compressed.
+pub(crate) fn page_info(pdf: &[u8]) -> Option<PageInfo> {
+    let count = page_count(pdf);
+    if count == 0 {
+        return None;
+    }
+    let (width, height) = media_box(pdf).unwrap_or_default();
+    Some(PageInfo {
+        count,
+        width,
+        height,
+    })
+}
+
+/// Number of `/Type /Page` dictionaries, not counting `/Type /Pages`.
+fn page_count(pdf: &[u8]) -> u32 {
+    let mut count = 0;
+    let mut rest = pdf;
+    while let Some(at) = find(rest, b"/Type") {
+        rest = skip_whitespace(&rest[at + b"/Type".len()..]);
+        if let Some(after) = rest.strip_prefix(b"/Page") {
+            if !after.first().is_some_and(|b| is_name_char(*b)) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Width and height of the first `/MediaBox [x0 y0 x1 y1]`, in points.
+fn media_box(pdf: &[u8]) -> Option<(f64, f64)> {
+    let at = find(pdf, b"/MediaBox")?;
+    let rest = skip_whitespace(&pdf[at + b"/MediaBox".len()..]).strip_prefix(b"[")?;
+    let end = rest.iter().position(|b| *b == b']')?;
+    let numbers: Vec<f64> = std::str::from_utf8(&rest[..end])
+        .ok()?
+        .split_ascii_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match numbers[..] {
+        [x0, y0, x1, y1] => Some(((x1 - x0).abs(), (y1 - y0).abs())),
+        _ => None,
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn skip_whitespace(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+/// Whether `b` continues a PDF name (anything but whitespace and
+/// delimiters).
+fn is_name_char(b: u8) -> bool {
+    !b.is_ascii_whitespace() && !b"()<>[]{}/%".contains(&b)
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::{FallbackPdf, PdfServiceError};
+
+    /// Verifies pages are counted without the page tree node, and the
+    /// first page's size is read.
+    #[test]
+    fn test_page_info() {
+        let pdf = b"%PDF-1.4\n\
+            1 0 obj\n<</Type /Pages\n/Count 2\n/Kids [2 0 R 3 0 R]>>\nendobj\n\
+            2 0 obj\n<</Type /Page\n/MediaBox [0 0 595.28 841.89]\n/Parent 1 0 R>>\nendobj\n\
+            3 0 obj\n<</Type/Page/MediaBox [0 0 842 595]/Parent 1 0 R>>\nendobj\n";
+        let info = page_info(pdf).unwrap();
+        assert_eq!(info.count, 2);
+        assert_eq!((info.width, info.height), (595.28, 841.89));
+        assert_eq!(info.size_header(), "595.28x841.89");
+
+        assert_eq!(page_info(b"%PDF-1.4\n<</Type /Pages /Count 0>>"), None);
+        assert_eq!(page_info(b""), None);
+    }
+
+    /// Verifies the placeholder PDF, written without Chrome, is read.
+    #[test]
+    fn test_page_info_fallback_pdf() {
+        let fallback = FallbackPdf::new(Some("req-1"), "doc.pdf".to_string(), false);
+        let response = fallback.render(&PdfServiceError::EmptyHtml);
+        let info = page_info(&response.data).unwrap();
+        assert_eq!(info.count, 1);
+        assert_eq!(info.size_header(), "595x842");
+    }
+}
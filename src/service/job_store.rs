@@ -0,0 +1,336 @@
+//! Where background jobs wait and where their results are kept.
+//!
+//! `POST /jobs` puts a job into the pool's [`JobStore`]; workers take jobs
+//! out in order, render them, and put the result back for
+//! `GET /jobs/{id}/result`. The store decides how far that state reaches:
+//!
+//! | Store | Queued jobs and results |
+//! |-------|-------------------------|
+//! | [`MemoryJobStore`] (default) | This process only; lost on restart |
+//! | [`RedisJobStore`](crate::service::RedisJobStore) (feature `redis-jobs`) | Shared by every instance using the server; survive restarts |
+//!
+//! With a shared store, any instance can answer for any job, and a job
+//! queued on one instance may be rendered by another. Each instance needs
+//! [`spawn_job_worker`](crate::service::spawn_job_worker) running to pick
+//! up jobs it did not queue itself.
+//!
+//! Other backends, such as a database table, plug in by implementing
+//! [`JobStore`] and passing it to
+//! [`BrowserPoolBuilder::job_store`](crate::BrowserPoolBuilder::job_store).
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::service::types::{PdfResponse, PdfServiceError, RenderJob};
+
+/// Most finished jobs [`MemoryJobStore`] keeps; the oldest is dropped
+/// beyond this.
+const MAX_FINISHED_JOBS: usize = 1024;
+
+/// A job waiting in a [`JobStore`] for a worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedJob {
+    /// The job ID, as the client knows it.
+    pub id: String,
+
+    /// API key the render is accounted to.
+    pub api_key: Option<String>,
+
+    /// The render to run.
+    pub job: RenderJob,
+
+    /// URL the result is POSTed to once the job finishes.
+    pub callback_url: Option<String>,
+}
+
+/// What a [`JobStore`] holds for a job.
+#[derive(Debug, Clone)]
+pub enum JobState {
+    /// Waiting for a worker.
+    Queued,
+
+    /// Claimed by a worker, here or on another instance.
+    Running,
+
+    /// Finished; the result is kept for `expires_in`.
+    Finished {
+        /// The PDF, or the error the render failed with.
+        result: Box<Result<PdfResponse, PdfServiceError>>,
+        /// Time left until the result is dropped.
+        expires_in: Duration,
+    },
+}
+
+/// Storage for background jobs: a queue of waiting jobs plus the kept
+/// results of finished ones.
+///
+/// Jobs are addressed by a key the service builds from the API key and job
+/// ID; stores treat it as an opaque string. Calls are blocking and made
+/// without the pool lock held. A store that cannot be reached should log
+/// and behave as if it held nothing, except in [`push`](Self::push), which
+/// reports the failure to the client.
+pub trait JobStore: Send + Sync {
+    /// Queue `job` under `key`, replacing a finished job's result.
+    ///
+    /// # Errors
+    ///
+    /// - [`PdfServiceError::InvalidRequest`] if a job under `key` is queued
+    ///   or running
+    /// - [`PdfServiceError::Internal`] if the store cannot be written
+    fn push(&self, key: &str, job: &QueuedJob) -> Result<(), PdfServiceError>;
+
+    /// Take the oldest queued job, marking it running.
+    ///
+    /// A job still running after `lease` was claimed by a worker that
+    /// stopped; a store that outlives its workers should queue it again.
+    fn claim(&self, lease: Duration) -> Option<(String, QueuedJob)>;
+
+    /// Keep the `result` of the job under `key` for `retention`.
+    fn finish(&self, key: &str, result: &Result<PdfResponse, PdfServiceError>, retention: Duration);
+
+    /// What the store holds under `key`.
+    fn get(&self, key: &str) -> Option<JobState>;
+
+    /// Remove a queued job or a finished result.
+    ///
+    /// Returns what was held under `key`. A running job is left in place
+    /// and reported as [`JobState::Running`].
+    fn remove(&self, key: &str) -> Option<JobState>;
+
+    /// Number of jobs queued or running.
+    fn pending(&self) -> usize;
+}
+
+/// [`JobStore`] keeping jobs in this process's memory.
+///
+/// Keeps at most 1024 finished results, dropping the oldest beyond that.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::MemoryJobStore;
+///
+/// let pool = BrowserPool::builder()
+///     .factory(Box::new(ChromeBrowserFactory::with_defaults()))
+///     .job_store(Box::new(MemoryJobStore::new()))
+///     .build()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryJobStore {
+    jobs: Mutex<MemoryJobs>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryJobs {
+    /// Keys of queued jobs, oldest first; may hold keys removed since.
+    queue: VecDeque<String>,
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Debug)]
+enum Entry {
+    Queued(Box<QueuedJob>),
+    Running,
+    Finished {
+        result: Result<PdfResponse, PdfServiceError>,
+        expires_at: Instant,
+    },
+}
+
+impl MemoryJobStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lock the jobs, dropping expired results first.
+    fn jobs(&self) -> MutexGuard<'_, MemoryJobs> {
+        let mut jobs = self.jobs.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        jobs.entries.retain(|_, entry| match entry {
+            Entry::Finished { expires_at, .. } => *expires_at > now,
+            _ => true,
+        });
+        jobs
+    }
+}
+
+impl Entry {
+    fn state(&self) -> JobState {
+        match self {
+            Self::Queued(_) => JobState::Queued,
+            Self::Running => JobState::Running,
+            Self::Finished { result, expires_at } => JobState::Finished {
+                result: Box::new(result.clone()),
+                expires_in: expires_at.saturating_duration_since(Instant::now()),
+            },
+        }
+    }
+}
+
+impl JobStore for MemoryJobStore {
+    fn push(&self, key: &str, job: &QueuedJob) -> Result<(), PdfServiceError> {
+        let mut jobs = self.jobs();
+        if let Some(Entry::Queued(_) | Entry::Running) = jobs.entries.get(key) {
+            return Err(job_in_use());
+        }
+        jobs.entries
+            .insert(key.to_string(), Entry::Queued(Box::new(job.clone())));
+        jobs.queue.push_back(key.to_string());
+        Ok(())
+    }
+
+    fn claim(&self, _lease: Duration) -> Option<(String, QueuedJob)> {
+        // Jobs only run in this process, so none can be orphaned
+        let mut jobs = self.jobs();
+        while let Some(key) = jobs.queue.pop_front() {
+            let Some(entry) = jobs.entries.get_mut(&key) else {
+                continue;
+            };
+            if !matches!(entry, Entry::Queued(_)) {
+                continue;
+            }
+            if let Entry::Queued(job) = std::mem::replace(entry, Entry::Running) {
+                return Some((key, *job));
+            }
+        }
+        None
+    }
+
+    fn finish(
+        &self,
+        key: &str,
+        result: &Result<PdfResponse, PdfServiceError>,
+        retention: Duration,
+    ) {
+        let mut jobs = self.jobs();
+        let finished = jobs
+            .entries
+            .values()
+            .filter(|entry| matches!(entry, Entry::Finished { .. }))
+            .count();
+        if finished >= MAX_FINISHED_JOBS {
+            let oldest = jobs
+                .entries
+                .iter()
+                .filter_map(|(key, entry)| match entry {
+                    Entry::Finished { expires_at, .. } => Some((key, *expires_at)),
+                    _ => None,
+                })
+                .min_by_key(|(_, expires_at)| *expires_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                jobs.entries.remove(&oldest);
+            }
+        }
+        jobs.entries.insert(
+            key.to_string(),
+            Entry::Finished {
+                result: result.clone(),
+                expires_at: Instant::now() + retention,
+            },
+        );
+    }
+
+    fn get(&self, key: &str) -> Option<JobState> {
+        self.jobs().entries.get(key).map(Entry::state)
+    }
+
+    fn remove(&self, key: &str) -> Option<JobState> {
+        let mut jobs = self.jobs();
+        match jobs.entries.get(key)? {
+            Entry::Running => Some(JobState::Running),
+            // A removed queued job's key stays in the queue until claimed
+            _ => jobs.entries.remove(key).map(|entry| entry.state()),
+        }
+    }
+
+    fn pending(&self) -> usize {
+        self.jobs()
+            .entries
+            .values()
+            .filter(|entry| matches!(entry, Entry::Queued(_) | Entry::Running))
+            .count()
+    }
+}
+
+/// Error for pushing a job whose key is already queued or running.
+pub(crate) fn job_in_use() -> PdfServiceError {
+    PdfServiceError::InvalidRequest("X-Job-Id is already in use by a running render".to_string())
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queued(id: &str) -> QueuedJob {
+        QueuedJob {
+            id: id.to_string(),
+            api_key: None,
+            job: crate::service::PdfFromHtmlRequest {
+                html: "<h1>Report</h1>".to_string(),
+                ..Default::default()
+            }
+            .into(),
+            callback_url: None,
+        }
+    }
+
+    /// Verifies jobs are claimed oldest first, each once, and removed
+    /// queued jobs are skipped.
+    #[test]
+    fn test_memory_store_queue() {
+        let store = MemoryJobStore::new();
+        store.push("a", &queued("a")).unwrap();
+        store.push("b", &queued("b")).unwrap();
+        store.push("c", &queued("c")).unwrap();
+        assert!(matches!(
+            store.push("a", &queued("a")),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+        assert_eq!(store.pending(), 3);
+
+        assert!(matches!(store.remove("b"), Some(JobState::Queued)));
+        let (key, job) = store.claim(Duration::from_secs(60)).unwrap();
+        assert_eq!((key.as_str(), job.id.as_str()), ("a", "a"));
+        assert!(matches!(store.remove("a"), Some(JobState::Running)));
+        assert_eq!(store.claim(Duration::from_secs(60)).unwrap().0, "c");
+        assert!(store.claim(Duration::from_secs(60)).is_none());
+        assert_eq!(store.pending(), 2);
+    }
+
+    /// Verifies finished results are kept until they expire, and a new
+    /// job may reuse a finished job's key.
+    #[test]
+    fn test_memory_store_retention() {
+        let store = MemoryJobStore::new();
+        store.push("k", &queued("k")).unwrap();
+        store.claim(Duration::from_secs(60)).unwrap();
+
+        let pdf = PdfResponse::new(b"%PDF-".to_vec(), "doc.pdf".to_string(), false);
+        store.finish("k", &Ok(pdf), Duration::from_millis(50));
+        assert!(matches!(
+            store.get("k"),
+            Some(JobState::Finished { result, .. }) if result.is_ok()
+        ));
+        assert_eq!(store.pending(), 0);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(store.get("k").is_none());
+
+        store.finish(
+            "k",
+            &Err(PdfServiceError::Timeout("slow".to_string())),
+            Duration::from_secs(60),
+        );
+        store.push("k", &queued("k")).unwrap();
+        assert!(matches!(store.get("k"), Some(JobState::Queued)));
+    }
+}
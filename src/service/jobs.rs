@@ -0,0 +1,1200 @@
+//! Jobs: renders addressed by ID.
+//!
+//! A render gets a job ID in one of two ways:
+//!
+//! | How | Result |
+//! |-----|--------|
+//! | `X-Job-Id` header on `GET /pdf` / `POST /pdf/html` | Returned on the same request; the ID only allows cancelling |
+//! | `POST /jobs` ([`submit_job`]) | Rendered in the background; fetched later from `GET /jobs/{id}/result` |
+//!
+//! Either way `DELETE /jobs/{id}` stops a running render: it leaves the
+//! checkout queue or has its tab closed, and its browser goes back to the
+//! pool. Without this, a render whose client has gone away runs to
+//! completion and holds a browser the whole time.
+//!
+//! ```text
+//! POST /jobs {"url": "..."}         ──▶ 202 {"id": "3f9c...", "status": "running"}
+//! GET /jobs/3f9c...                 ──▶ 200 {"status": "succeeded", "expires_in_seconds": 3598}
+//! GET /jobs/3f9c.../result          ──▶ 200 application/pdf
+//! ```
+//!
+//! Background jobs wait in the pool's [`JobStore`] until one of
+//! `max_pool_size` × `tabs_per_browser` worker slots is free, and their
+//! results are kept there for
+//! [`job_retention`](crate::BrowserPoolConfig::job_retention) after they
+//! finish. The default store is in memory, so neither survives a restart;
+//! see [`JobStore`] for shared, persistent stores. Job IDs are scoped to the API key (`X-API-Key`), so
+//! one client cannot see or cancel another's jobs. An ID can be reused once
+//! its render has finished.
+//!
+//! At most [`max_pending_jobs`](crate::BrowserPoolConfig::max_pending_jobs)
+//! background jobs are queued or running at once; `POST /jobs` fails with
+//! `JOB_QUEUE_FULL` beyond that.
+//!
+//! # Callbacks
+//!
+//! Instead of polling, a client can pass a `callback_url` with the job. Once
+//! the job finishes, its result is POSTed there with an `X-Job-Id` header:
+//!
+//! | Outcome | Body |
+//! |---------|------|
+//! | Succeeded | The PDF (or other requested output), as `GET /jobs/{id}/result` returns it |
+//! | Failed or cancelled | The job's [`JobResponse`] as JSON, with its `error` |
+//!
+//! A delivery that fails or gets a non-2xx response is retried twice, after
+//! 2 and 4 seconds; redirects are not followed. The result stays available
+//! from `GET /jobs/{id}/result` either way.
+//!
+//! Callbacks obey the pool's
+//! [`url_policy`](crate::BrowserPoolConfig::url_policy). Each attempt
+//! resolves the host, checks the addresses, and connects to exactly those,
+//! so a name that resolves to a private address is refused.
+//!
+//! Library code can skip job IDs entirely and cancel through the request's
+//! [`CancelHandle`] directly.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use ureq::unversioned::resolver::{ResolvedSocketAddrs, Resolver};
+use ureq::unversioned::transport::{DefaultConnector, NextTimeout};
+
+use crate::cancel::CancelHandle;
+use crate::pool::BrowserPool;
+use crate::service::fallback::generate_request_id;
+use crate::service::job_store::{JobState, JobStore, QueuedJob, job_in_use};
+use crate::service::pdf::{DEFAULT_TIMEOUT_SECS, check_url_policy, render_job, render_metered};
+use crate::service::types::{
+    ErrorResponse, JobRequest, JobResponse, JobStatus, PdfResponse, PdfServiceError, ResponseBody,
+};
+use crate::url_policy::UrlPolicy;
+use crate::usage::ANONYMOUS_KEY;
+
+/// Request header carrying the client's ID for a render.
+pub const JOB_ID_HEADER: &str = "X-Job-Id";
+
+/// Longest job ID accepted.
+const MAX_JOB_ID_LEN: usize = 128;
+
+/// How long a claimed job may run before a shared store hands it to
+/// another worker: the render timeout plus time to store the result.
+const JOB_LEASE: Duration = Duration::from_secs(DEFAULT_TIMEOUT_SECS + 30);
+
+/// How often [`spawn_job_worker`] checks an empty store for new jobs.
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Attempts made to deliver a job's result to its callback URL.
+const CALLBACK_ATTEMPTS: u32 = 3;
+
+/// How long one callback attempt may take.
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The pool's job store, and the renders running in this process.
+///
+/// Owned by the [`BrowserPool`]; use the functions in this module rather
+/// than this type directly.
+pub(crate) struct JobRegistry {
+    retention: Duration,
+    max_pending: usize,
+    store: Arc<dyn JobStore>,
+
+    /// One permit per background job rendering in this process.
+    workers: Arc<Semaphore>,
+
+    /// Cancel handles of renders running in this process.
+    running: Mutex<HashMap<String, CancelHandle>>,
+}
+
+/// What is known about a job.
+enum Lookup {
+    Running,
+    Finished {
+        result: Box<Result<PdfResponse, PdfServiceError>>,
+        expires_in: Duration,
+    },
+    Unknown,
+}
+
+impl JobRegistry {
+    /// Create a registry over `store`, keeping results for `retention`,
+    /// admitting up to `max_pending` background jobs, and rendering up to
+    /// `workers` of them at once.
+    pub(crate) fn new(
+        retention: Duration,
+        max_pending: usize,
+        workers: usize,
+        store: Box<dyn JobStore>,
+    ) -> Self {
+        Self {
+            retention,
+            max_pending,
+            store: Arc::from(store),
+            workers: Arc::new(Semaphore::new(workers)),
+            running: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn running(&self) -> MutexGuard<'_, HashMap<String, CancelHandle>> {
+        self.running.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Register a render running in this process under `key`.
+    fn register(&self, key: &str, cancel: &CancelHandle) -> Result<(), PdfServiceError> {
+        let mut running = self.running();
+        if running.contains_key(key) {
+            return Err(job_in_use());
+        }
+        running.insert(key.to_string(), cancel.clone());
+        Ok(())
+    }
+
+    /// Remove `key` once its render has finished, if it is still `cancel`'s.
+    fn unregister(&self, key: &str, cancel: &CancelHandle) {
+        let mut running = self.running();
+        if running
+            .get(key)
+            .is_some_and(|running| running.is_same(cancel))
+        {
+            running.remove(key);
+        }
+    }
+
+    /// Cancel the render running in this process under `key`, if any.
+    fn cancel(&self, key: &str) -> bool {
+        match self.running().get(key) {
+            Some(cancel) => {
+                cancel.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn is_running(&self, key: &str) -> bool {
+        self.running().contains_key(key)
+    }
+}
+
+impl std::fmt::Debug for JobRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobRegistry")
+            .field("retention", &self.retention)
+            .field("max_pending", &self.max_pending)
+            .field("running", &self.running().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Run a render that [`cancel_job`] can stop by its job ID.
+///
+/// Without a job ID this just calls `render`. With one, the ID is
+/// registered for the length of the render; `cancel` must be the handle
+/// set on the request being rendered (its `cancel` field), since that is
+/// what [`cancel_job`] cancels. The result is returned, not kept; use
+/// [`submit_job`] for renders fetched later.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `api_key` - Key from the `X-API-Key` header, scoping the job ID
+/// * `job_id` - ID from the [`JOB_ID_HEADER`] header
+/// * `cancel` - The request's cancel handle
+/// * `render` - The render to run
+///
+/// # Errors
+///
+/// - [`PdfServiceError::InvalidRequest`] if the job ID is longer than 128
+///   characters or a render with the same ID is still running
+/// - Any error returned by `render`, including
+///   [`PdfServiceError::Cancelled`] after a cancel
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::CancelHandle;
+/// use html2pdf_api::service::{generate_pdf_from_url, render_cancellable};
+///
+/// let cancel = CancelHandle::new();
+/// request.cancel = Some(cancel.clone());
+/// let response = render_cancellable(&pool, api_key, Some("report-42"), &cancel, || {
+///     generate_pdf_from_url(&pool, &request)
+/// })?;
+/// ```
+pub fn render_cancellable<F>(
+    pool: &Mutex<BrowserPool>,
+    api_key: Option<&str>,
+    job_id: Option<&str>,
+    cancel: &CancelHandle,
+    render: F,
+) -> Result<PdfResponse, PdfServiceError>
+where
+    F: FnOnce() -> Result<PdfResponse, PdfServiceError>,
+{
+    let Some(job_id) = job_id else {
+        return render();
+    };
+
+    let key = job_key(api_key, job_id)?;
+    with_registry(pool, |jobs| jobs.register(&key, cancel))??;
+    log::debug!("Render registered as job '{}'", job_id);
+
+    let result = render();
+
+    if let Err(e) = with_registry(pool, |jobs| jobs.unregister(&key, cancel)) {
+        log::error!("Failed to unregister job '{}': {}", job_id, e);
+    }
+
+    result
+}
+
+/// Queue `job` to render in the background.
+///
+/// Returns at once with the job's ID: `job_id` if given, otherwise a
+/// generated one. The job waits in the pool's [`JobStore`] for a worker
+/// slot; its render is accounted to `api_key` like any other, and cancelled
+/// if it takes longer than [`DEFAULT_TIMEOUT_SECS`]. Once it finishes,
+/// [`job_result`] returns its PDF or error until
+/// [`job_retention`](crate::BrowserPoolConfig::job_retention) has passed,
+/// and the result is POSTed to the job's `callback_url`, if any (see
+/// [Callbacks](self#callbacks)).
+///
+/// Must be called from within a Tokio runtime.
+///
+/// # Arguments
+///
+/// * `pool` - The shared browser pool
+/// * `api_key` - Key from the `X-API-Key` header, scoping the job ID
+/// * `job_id` - ID from the [`JOB_ID_HEADER`] header, or `None` to generate one
+/// * `job` - The render to run, and where to deliver its result
+///
+/// # Errors
+///
+/// - [`PdfServiceError::InvalidRequest`] if async jobs are disabled
+///   (`job_retention` is zero), the job ID is longer than 128 characters,
+///   or a job with the same ID is still queued or running
+/// - [`PdfServiceError::InvalidUrl`] if `callback_url` is not an `http://`
+///   or `https://` URL, or the pool's
+///   [`url_policy`](crate::BrowserPoolConfig::url_policy) forbids it
+/// - [`PdfServiceError::JobQueueFull`] if
+///   [`max_pending_jobs`](crate::BrowserPoolConfig::max_pending_jobs) jobs
+///   are already pending
+/// - [`PdfServiceError::Internal`] if the job store cannot be written
+/// - [`PdfServiceError::PoolLockFailed`] if the pool lock is poisoned
+///
+/// Render errors are not returned here; they are kept as the job's result.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{JobStatus, PdfFromUrlRequest, submit_job};
+///
+/// let request = PdfFromUrlRequest {
+///     url: "https://example.com/report".to_string(),
+///     ..Default::default()
+/// };
+/// let job = submit_job(&pool, Some("team-a"), None, request.into())?;
+/// assert_eq!(job.status, JobStatus::Running);
+/// ```
+pub fn submit_job(
+    pool: &Arc<Mutex<BrowserPool>>,
+    api_key: Option<&str>,
+    job_id: Option<&str>,
+    job: JobRequest,
+) -> Result<JobResponse, PdfServiceError> {
+    let retention = pool
+        .lock()
+        .map_err(|e| PdfServiceError::PoolLockFailed(e.to_string()))?
+        .config()
+        .job_retention;
+    if retention.is_zero() {
+        return Err(PdfServiceError::InvalidRequest(
+            "async jobs are disabled (job_retention is zero)".to_string(),
+        ));
+    }
+
+    let callback_url = job
+        .callback_url
+        .as_deref()
+        .map(|url| validate_callback_url(pool, url))
+        .transpose()?;
+
+    let id = job_id.map_or_else(generate_request_id, String::from);
+    let key = job_key(api_key, &id)?;
+    let (store, max_pending) =
+        with_registry(pool, |jobs| (Arc::clone(&jobs.store), jobs.max_pending))?;
+    if store.pending() >= max_pending {
+        return Err(PdfServiceError::JobQueueFull(format!(
+            "{} jobs are already pending",
+            max_pending
+        )));
+    }
+
+    let queued = QueuedJob {
+        id: id.clone(),
+        api_key: api_key.map(String::from),
+        job: job.job,
+        callback_url,
+    };
+    store.push(&key, &queued)?;
+    log::info!("Job '{}' queued", id);
+    tokio::spawn(work_one(Arc::clone(pool)));
+
+    Ok(JobResponse::new(&id, JobStatus::Running))
+}
+
+/// Keep taking jobs from the pool's [`JobStore`] and rendering them.
+///
+/// Jobs queued by [`submit_job`] in this process are picked up without
+/// this. Run it when the store is shared with other instances or outlives
+/// the process, so this instance also renders jobs queued elsewhere or
+/// before a restart. Renders share the worker slots of [`submit_job`]. An
+/// empty store is checked again every second; the worker stops once the
+/// pool starts draining.
+///
+/// Must be called from within a Tokio runtime.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::PoolLockFailed`] if the pool lock is poisoned.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::spawn_job_worker;
+///
+/// let worker = spawn_job_worker(&pool)?;
+/// // ...
+/// worker.abort();
+/// ```
+pub fn spawn_job_worker(pool: &Arc<Mutex<BrowserPool>>) -> Result<JoinHandle<()>, PdfServiceError> {
+    let (store, workers) = with_registry(pool, |jobs| {
+        (Arc::clone(&jobs.store), Arc::clone(&jobs.workers))
+    })?;
+    let pool = Arc::clone(pool);
+
+    Ok(tokio::spawn(async move {
+        while !is_draining(&pool) {
+            let Ok(slot) = Arc::clone(&workers).acquire_owned().await else {
+                break;
+            };
+            match claim(&store).await {
+                Some((key, job)) => {
+                    let pool = Arc::clone(&pool);
+                    tokio::spawn(async move {
+                        run_claimed(pool, key, job).await;
+                        drop(slot);
+                    });
+                }
+                None => {
+                    drop(slot);
+                    tokio::time::sleep(JOB_POLL_INTERVAL).await;
+                }
+            }
+        }
+        log::info!("Job worker stopped");
+    }))
+}
+
+/// Wait for a free worker slot, then take one queued job and render it.
+///
+/// [`submit_job`] starts one of these per job, so every job queued here
+/// has a worker; which job it takes is up to the store.
+async fn work_one(pool: Arc<Mutex<BrowserPool>>) {
+    let Ok((store, workers)) = with_registry(&pool, |jobs| {
+        (Arc::clone(&jobs.store), Arc::clone(&jobs.workers))
+    }) else {
+        return;
+    };
+    let Ok(_slot) = workers.acquire_owned().await else {
+        return;
+    };
+    if is_draining(&pool) {
+        // Leave the job for after the restart, if the store keeps it
+        return;
+    }
+    if let Some((key, job)) = claim(&store).await {
+        run_claimed(pool, key, job).await;
+    }
+}
+
+/// Take the next queued job from `store`.
+async fn claim(store: &Arc<dyn JobStore>) -> Option<(String, QueuedJob)> {
+    let store = Arc::clone(store);
+    tokio::task::spawn_blocking(move || store.claim(JOB_LEASE))
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Render a job claimed from the store.
+async fn run_claimed(pool: Arc<Mutex<BrowserPool>>, key: String, job: QueuedJob) {
+    let cancel = CancelHandle::new();
+    match with_registry(&pool, |jobs| jobs.register(&key, &cancel)) {
+        Ok(Ok(())) => {
+            let _ = run_job(pool, key, job, cancel).await;
+        }
+        Ok(Err(e)) | Err(e) => {
+            log::warn!("Job '{}' failed: {}", job.id, e);
+            store_result(&pool, &key, &job.id, &Err(e)).await;
+        }
+    }
+}
+
+/// Render a scheduled run of `job` under `job_id` now, bypassing the
+/// queue, on a Tokio task.
+///
+/// The task keeps the result in the store, delivers it to the job's
+/// callback like [`submit_job`] does, and also returns it, for callers
+/// that want to wait for it.
+pub(crate) fn start_job(
+    pool: &Arc<Mutex<BrowserPool>>,
+    api_key: Option<&str>,
+    job_id: &str,
+    job: JobRequest,
+) -> Result<JoinHandle<Result<PdfResponse, PdfServiceError>>, PdfServiceError> {
+    let callback_url = job
+        .callback_url
+        .as_deref()
+        .map(|url| validate_callback_url(pool, url))
+        .transpose()?;
+    let key = job_key(api_key, job_id)?;
+    let store = with_registry(pool, |jobs| Arc::clone(&jobs.store))?;
+    if let Some(JobState::Queued | JobState::Running) = store.get(&key) {
+        return Err(job_in_use());
+    }
+    let cancel = CancelHandle::new();
+    with_registry(pool, |jobs| jobs.register(&key, &cancel))??;
+    log::info!("Job '{}' submitted", job_id);
+
+    let job = QueuedJob {
+        id: job_id.to_string(),
+        api_key: api_key.map(String::from),
+        job: job.job,
+        callback_url,
+    };
+    Ok(tokio::spawn(run_job(Arc::clone(pool), key, job, cancel)))
+}
+
+/// Render `job`, registered here under `key` with `cancel`, keep its
+/// result in the store, and send it to the job's callback.
+async fn run_job(
+    pool: Arc<Mutex<BrowserPool>>,
+    key: String,
+    job: QueuedJob,
+    cancel: CancelHandle,
+) -> Result<PdfResponse, PdfServiceError> {
+    let QueuedJob {
+        id,
+        api_key,
+        job: mut render,
+        callback_url,
+    } = job;
+    render.set_cancel(cancel.clone());
+
+    let task = tokio::task::spawn_blocking({
+        let pool = Arc::clone(&pool);
+        move || render_metered(&pool, api_key.as_deref(), || render_job(&pool, &render))
+    });
+
+    let result = match tokio::time::timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS), task).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(join_err)) => Err(PdfServiceError::Internal(join_err.to_string())),
+        Err(_timeout) => {
+            cancel.cancel();
+            Err(PdfServiceError::Timeout(format!(
+                "Operation timed out after {} seconds",
+                DEFAULT_TIMEOUT_SECS
+            )))
+        }
+    };
+
+    match &result {
+        Ok(response) => log::info!("Job '{}' finished: {} bytes", id, response.size()),
+        Err(e) => log::warn!("Job '{}' failed: {}", id, e),
+    }
+    // Stored before unregistering, so lookups never miss the job
+    store_result(&pool, &key, &id, &result).await;
+    if let Err(e) = with_registry(&pool, |jobs| jobs.unregister(&key, &cancel)) {
+        log::error!("Failed to unregister job '{}': {}", id, e);
+    }
+
+    if let Some(url) = callback_url {
+        let policy = pool
+            .lock()
+            .map(|pool| pool.config().url_policy.clone())
+            .unwrap_or_else(|_| UrlPolicy::public());
+        tokio::spawn(deliver_callback(id, url, policy, result.clone()));
+    }
+    result
+}
+
+/// Keep a finished job's result in the store.
+async fn store_result(
+    pool: &Mutex<BrowserPool>,
+    key: &str,
+    job_id: &str,
+    result: &Result<PdfResponse, PdfServiceError>,
+) {
+    let stored = match with_registry(pool, |jobs| (Arc::clone(&jobs.store), jobs.retention)) {
+        Ok((store, retention)) => {
+            let key = key.to_string();
+            let result = result.clone();
+            tokio::task::spawn_blocking(move || store.finish(&key, &result, retention))
+                .await
+                .map_err(|e| e.to_string())
+        }
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = stored {
+        log::error!("Failed to store result of job '{}': {}", job_id, e);
+    }
+}
+
+/// Get the status of a job.
+///
+/// # Errors
+///
+/// - [`PdfServiceError::JobNotFound`] if no job with this ID is running or
+///   kept for the API key
+/// - [`PdfServiceError::PoolLockFailed`] if the pool lock is poisoned
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{JobStatus, job_status};
+///
+/// let job = job_status(&pool, Some("team-a"), "report-42")?;
+/// if job.status == JobStatus::Succeeded {
+///     // fetch it with job_result()
+/// }
+/// ```
+pub fn job_status(
+    pool: &Mutex<BrowserPool>,
+    api_key: Option<&str>,
+    job_id: &str,
+) -> Result<JobResponse, PdfServiceError> {
+    let key = job_key(api_key, job_id)?;
+
+    match lookup(pool, &key)? {
+        Lookup::Running => Ok(JobResponse::new(job_id, JobStatus::Running)),
+        Lookup::Finished { result, expires_in } => Ok(JobResponse {
+            expires_in_seconds: Some(expires_in.as_secs()),
+            ..finished_job(job_id, *result)
+        }),
+        Lookup::Unknown => Err(not_found(job_id)),
+    }
+}
+
+/// Status of a job that finished with `result`.
+fn finished_job(job_id: &str, result: Result<PdfResponse, PdfServiceError>) -> JobResponse {
+    let (status, error, pages) = match result {
+        Ok(response) => (JobStatus::Succeeded, None, response.page_info()),
+        Err(e @ PdfServiceError::Cancelled(_)) => {
+            (JobStatus::Cancelled, Some(ErrorResponse::from(e)), None)
+        }
+        Err(e) => (JobStatus::Failed, Some(ErrorResponse::from(e)), None),
+    };
+    JobResponse {
+        id: job_id.to_string(),
+        status,
+        error,
+        expires_in_seconds: None,
+        pages,
+    }
+}
+
+/// Get the result of a finished job.
+///
+/// Returns the job's PDF, or the error its render failed with. Results
+/// can be fetched any number of times until they expire.
+///
+/// # Errors
+///
+/// - [`PdfServiceError::JobPending`] if the job is still running
+/// - [`PdfServiceError::JobNotFound`] if no job with this ID is running or
+///   kept for the API key
+/// - The job's own error if its render failed
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::job_result;
+///
+/// let pdf = job_result(&pool, Some("team-a"), "report-42")?;
+/// std::fs::write(&pdf.filename, &pdf.data)?;
+/// ```
+pub fn job_result(
+    pool: &Mutex<BrowserPool>,
+    api_key: Option<&str>,
+    job_id: &str,
+) -> Result<PdfResponse, PdfServiceError> {
+    let key = job_key(api_key, job_id)?;
+
+    match lookup(pool, &key)? {
+        Lookup::Running => Err(PdfServiceError::JobPending(format!(
+            "job '{}' is still running",
+            job_id
+        ))),
+        Lookup::Finished { result, .. } => *result,
+        Lookup::Unknown => Err(not_found(job_id)),
+    }
+}
+
+/// Cancel a queued or running job, or delete a finished job's result.
+///
+/// A running render stops wherever it is and fails with
+/// [`PdfServiceError::Cancelled`]; see [`CancelHandle::cancel`]. A job
+/// still queued is taken out of the queue and kept as cancelled. Only the
+/// API key that started the job can cancel it.
+///
+/// # Errors
+///
+/// - [`PdfServiceError::JobNotFound`] if no job with this ID is running or
+///   kept for the API key
+/// - [`PdfServiceError::JobPending`] if the job is rendering on another
+///   instance sharing the job store
+/// - [`PdfServiceError::PoolLockFailed`] if the pool lock is poisoned
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{JobStatus, cancel_job};
+///
+/// let response = cancel_job(&pool, Some("team-a"), "report-42")?;
+/// assert_eq!(response.status, JobStatus::Cancelled);
+/// ```
+pub fn cancel_job(
+    pool: &Mutex<BrowserPool>,
+    api_key: Option<&str>,
+    job_id: &str,
+) -> Result<JobResponse, PdfServiceError> {
+    let key = job_key(api_key, job_id)?;
+
+    let (cancelled, store, retention) = with_registry(pool, |jobs| {
+        (jobs.cancel(&key), Arc::clone(&jobs.store), jobs.retention)
+    })?;
+    let status = if cancelled {
+        JobStatus::Cancelled
+    } else {
+        match store.remove(&key) {
+            Some(JobState::Queued) => {
+                let cancelled = Err(PdfServiceError::Cancelled(
+                    "job was cancelled before it started".to_string(),
+                ));
+                store.finish(&key, &cancelled, retention);
+                JobStatus::Cancelled
+            }
+            Some(JobState::Running) => {
+                return Err(PdfServiceError::JobPending(format!(
+                    "job '{}' is running on another instance and cannot be cancelled from here",
+                    job_id
+                )));
+            }
+            Some(JobState::Finished { .. }) => JobStatus::Deleted,
+            None => return Err(not_found(job_id)),
+        }
+    };
+    log::info!("Job '{}': {:?}", job_id, status);
+    Ok(JobResponse::new(job_id, status))
+}
+
+/// Find a job, among renders running here first, then in the store.
+fn lookup(pool: &Mutex<BrowserPool>, key: &str) -> Result<Lookup, PdfServiceError> {
+    let (running, store) =
+        with_registry(pool, |jobs| (jobs.is_running(key), Arc::clone(&jobs.store)))?;
+    if running {
+        return Ok(Lookup::Running);
+    }
+    Ok(match store.get(key) {
+        Some(JobState::Queued | JobState::Running) => Lookup::Running,
+        Some(JobState::Finished { result, expires_in }) => Lookup::Finished { result, expires_in },
+        None => Lookup::Unknown,
+    })
+}
+
+/// Check a job's callback URL before accepting the job.
+///
+/// Callbacks go to the same places a render may load, so the pool's
+/// `url_policy` applies to them too.
+pub(crate) fn validate_callback_url(
+    pool: &Mutex<BrowserPool>,
+    url: &str,
+) -> Result<String, PdfServiceError> {
+    let parsed = url::Url::parse(url)
+        .map_err(|e| PdfServiceError::InvalidUrl(format!("callback_url: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(PdfServiceError::InvalidUrl(
+            "callback_url must be an http:// or https:// URL".to_string(),
+        ));
+    }
+    check_url_policy(pool, parsed.as_str())?;
+    Ok(parsed.into())
+}
+
+/// POST a finished job's `result` to `url`, retrying on failure.
+///
+/// Each attempt resolves the host again and checks the addresses against
+/// `policy` before connecting to them.
+async fn deliver_callback(
+    job_id: String,
+    url: String,
+    policy: UrlPolicy,
+    result: Result<PdfResponse, PdfServiceError>,
+) {
+    let body = match result {
+        Ok(response) => response.body(),
+        Err(e) => ResponseBody {
+            content_type: "application/json".to_string(),
+            filename: None,
+            content_disposition: None,
+            data: serde_json::to_vec(&finished_job(&job_id, Err(e)))
+                .unwrap_or_default()
+                .into(),
+        },
+    };
+    let body = Arc::new(body);
+
+    for attempt in 1..=CALLBACK_ATTEMPTS {
+        let post = tokio::task::spawn_blocking({
+            let url = url.clone();
+            let policy = policy.clone();
+            let job_id = job_id.clone();
+            let body = Arc::clone(&body);
+            move || {
+                let addrs = resolve_callback(&policy, &url)?;
+                post_callback(&url, addrs, &job_id, &body)
+            }
+        });
+        let error = match post.await {
+            Ok(Ok(())) => {
+                log::info!("Job '{}' delivered to its callback", job_id);
+                return;
+            }
+            Ok(Err(e)) => e,
+            Err(e) => e.to_string(),
+        };
+        log::warn!(
+            "Callback for job '{}' failed (attempt {}/{}): {}",
+            job_id,
+            attempt,
+            CALLBACK_ATTEMPTS,
+            error
+        );
+        if attempt < CALLBACK_ATTEMPTS {
+            tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+        }
+    }
+    log::error!("Giving up on the callback for job '{}'", job_id);
+}
+
+/// Resolve a callback URL's host and check the addresses against `policy`.
+///
+/// [`validate_callback_url`] only looks at the URL, so a public name that
+/// resolves to a private address is caught here.
+fn resolve_callback(policy: &UrlPolicy, url: &str) -> Result<Vec<SocketAddr>, String> {
+    let parsed = url::Url::parse(url).map_err(|e| e.to_string())?;
+    let addrs = parsed
+        .socket_addrs(|| None)
+        .map_err(|e| format!("cannot resolve callback host: {}", e))?;
+    if addrs.is_empty() {
+        return Err("callback host has no addresses".to_string());
+    }
+    policy.check_addrs(&parsed, &addrs)?;
+    Ok(addrs)
+}
+
+/// POST `body` to a callback URL once, connecting only to `addrs`.
+fn post_callback(
+    url: &str,
+    addrs: Vec<SocketAddr>,
+    job_id: &str,
+    body: &ResponseBody,
+) -> Result<(), String> {
+    let config = ureq::Agent::config_builder()
+        .timeout_global(Some(CALLBACK_TIMEOUT))
+        .max_redirects(0)
+        .http_status_as_error(false)
+        // A proxy would be resolved to the pinned addresses
+        .proxy(None)
+        .build();
+    let agent = ureq::Agent::with_parts(config, DefaultConnector::default(), PinnedResolver(addrs));
+
+    let mut request = agent
+        .post(url)
+        .header("Content-Type", &body.content_type)
+        .header(JOB_ID_HEADER, job_id);
+    if let Some(disposition) = &body.content_disposition {
+        request = request.header("Content-Disposition", disposition);
+    }
+
+    let response = request.send(&body.data[..]).map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Resolves every host to the addresses already checked by
+/// [`resolve_callback`].
+#[derive(Debug)]
+struct PinnedResolver(Vec<SocketAddr>);
+
+impl Resolver for PinnedResolver {
+    fn resolve(
+        &self,
+        _uri: &ureq::http::Uri,
+        _config: &ureq::config::Config,
+        _timeout: NextTimeout,
+    ) -> Result<ResolvedSocketAddrs, ureq::Error> {
+        let mut addrs = self.empty();
+        // ureq keeps at most 16 addresses
+        for addr in self.0.iter().take(16) {
+            addrs.push(*addr);
+        }
+        if addrs.is_empty() {
+            return Err(ureq::Error::HostNotFound);
+        }
+        Ok(addrs)
+    }
+}
+
+/// Whether the pool has stopped taking new work.
+fn is_draining(pool: &Mutex<BrowserPool>) -> bool {
+    pool.lock().map(|pool| pool.is_draining()).unwrap_or(true)
+}
+
+/// Registry key for a job ID, scoped to the API key.
+fn job_key(api_key: Option<&str>, job_id: &str) -> Result<String, PdfServiceError> {
+    if job_id.len() > MAX_JOB_ID_LEN {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "X-Job-Id must be at most {} characters",
+            MAX_JOB_ID_LEN
+        )));
+    }
+    Ok(format!("{}\n{}", api_key.unwrap_or(ANONYMOUS_KEY), job_id))
+}
+
+fn not_found(job_id: &str) -> PdfServiceError {
+    PdfServiceError::JobNotFound(format!("no job with ID '{}'", job_id))
+}
+
+/// Run `f` on the pool's job registry.
+fn with_registry<T>(
+    pool: &Mutex<BrowserPool>,
+    f: impl FnOnce(&JobRegistry) -> T,
+) -> Result<T, PdfServiceError> {
+    let pool_guard = pool.lock().map_err(|e| {
+        log::error!("Failed to lock browser pool for jobs: {}", e);
+        PdfServiceError::PoolLockFailed(e.to_string())
+    })?;
+
+    Ok(f(pool_guard.jobs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BrowserPoolConfigBuilder;
+    use crate::factory::mock::MockBrowserFactory;
+
+    fn test_pool() -> Mutex<BrowserPool> {
+        let pool = BrowserPool::builder()
+            .config(BrowserPoolConfigBuilder::new().build().unwrap())
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+        Mutex::new(pool)
+    }
+
+    /// Verifies a running job can be cancelled only by its API key, and
+    /// is forgotten once it finishes.
+    #[test]
+    fn test_cancel_running_job() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = test_pool();
+        let cancel = CancelHandle::new();
+
+        let result = render_cancellable(&pool, Some("team-a"), Some("job-1"), &cancel, || {
+            assert!(matches!(
+                cancel_job(&pool, Some("team-b"), "job-1"),
+                Err(PdfServiceError::JobNotFound(_))
+            ));
+            assert!(matches!(
+                render_cancellable(&pool, Some("team-a"), Some("job-1"), &cancel, || {
+                    unreachable!()
+                }),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+
+            let response = cancel_job(&pool, Some("team-a"), "job-1").unwrap();
+            assert_eq!(response.status, JobStatus::Cancelled);
+            assert!(cancel.is_cancelled());
+            Err(PdfServiceError::Cancelled("test".to_string()))
+        });
+        assert!(matches!(result, Err(PdfServiceError::Cancelled(_))));
+
+        assert!(matches!(
+            cancel_job(&pool, Some("team-a"), "job-1"),
+            Err(PdfServiceError::JobNotFound(_))
+        ));
+    }
+
+    /// Verifies a background job's failure is kept and reported.
+    #[test]
+    fn test_submitted_job_result() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = Arc::new(test_pool());
+        let request = crate::service::PdfFromHtmlRequest {
+            html: "<h1>Report</h1>".to_string(),
+            ..Default::default()
+        };
+
+        let job = submit_job(&pool, None, Some("report"), request.into()).unwrap();
+        assert_eq!(job.status, JobStatus::Running);
+
+        let status = runtime.block_on(async {
+            loop {
+                let status = job_status(&pool, None, "report").unwrap();
+                if status.status != JobStatus::Running {
+                    break status;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+        assert_eq!(status.status, JobStatus::Failed);
+        assert_eq!(status.error.unwrap().code, "BROWSER_UNAVAILABLE");
+        assert!(matches!(
+            job_result(&pool, None, "report"),
+            Err(PdfServiceError::BrowserUnavailable(_))
+        ));
+        assert!(matches!(
+            job_status(&pool, Some("team-a"), "report"),
+            Err(PdfServiceError::JobNotFound(_))
+        ));
+
+        assert_eq!(
+            cancel_job(&pool, None, "report").unwrap().status,
+            JobStatus::Deleted
+        );
+        assert!(matches!(
+            job_result(&pool, None, "report"),
+            Err(PdfServiceError::JobNotFound(_))
+        ));
+    }
+
+    /// Verifies a render replaced by a newer one with the same ID does not
+    /// unregister it when finishing.
+    #[test]
+    fn test_replaced_job_not_unregistered() {
+        let registry = JobRegistry::new(
+            Duration::from_secs(60),
+            16,
+            4,
+            Box::new(crate::service::MemoryJobStore::new()),
+        );
+        let old = CancelHandle::new();
+        registry.register("k", &old).unwrap();
+        assert!(matches!(
+            registry.register("k", &CancelHandle::new()),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+        registry.unregister("k", &old);
+        assert!(!registry.is_running("k"));
+
+        let new = CancelHandle::new();
+        registry.register("k", &new).unwrap();
+        registry.unregister("k", &old);
+        assert!(registry.is_running("k"));
+
+        assert!(registry.cancel("k"));
+        assert!(new.is_cancelled());
+        assert!(!old.is_cancelled());
+    }
+
+    /// Verifies background jobs beyond `max_pending_jobs` are rejected, and
+    /// a queued job can be cancelled before it starts.
+    #[test]
+    fn test_job_queue_bound() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .config(
+                BrowserPoolConfigBuilder::new()
+                    .max_pending_jobs(1)
+                    .build()
+                    .unwrap(),
+            )
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+        let pool = Arc::new(Mutex::new(pool));
+        let request = || -> JobRequest {
+            crate::service::PdfFromHtmlRequest {
+                html: "<h1>Report</h1>".to_string(),
+                ..Default::default()
+            }
+            .into()
+        };
+
+        // Queued without a worker, so it stays queued
+        let store = with_registry(&pool, |jobs| Arc::clone(&jobs.store)).unwrap();
+        let key = job_key(None, "a").unwrap();
+        store
+            .push(
+                &key,
+                &QueuedJob {
+                    id: "a".to_string(),
+                    api_key: None,
+                    job: request().job,
+                    callback_url: None,
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            submit_job(&pool, None, Some("b"), request()),
+            Err(PdfServiceError::JobQueueFull(_))
+        ));
+        assert_eq!(
+            job_status(&pool, None, "a").unwrap().status,
+            JobStatus::Running
+        );
+
+        assert_eq!(
+            cancel_job(&pool, None, "a").unwrap().status,
+            JobStatus::Cancelled
+        );
+        assert_eq!(
+            job_status(&pool, None, "a").unwrap().status,
+            JobStatus::Cancelled
+        );
+        assert!(matches!(
+            job_result(&pool, None, "a"),
+            Err(PdfServiceError::Cancelled(_))
+        ));
+        submit_job(&pool, None, Some("b"), request()).unwrap();
+    }
+
+    /// Verifies a failed job's status is POSTed to its callback URL, and
+    /// callback URLs are checked up front.
+    #[test]
+    fn test_job_callback() {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = Arc::new(test_pool());
+
+        let request = |callback: &str| JobRequest {
+            job: crate::service::PdfFromHtmlRequest {
+                html: "<h1>Report</h1>".to_string(),
+                ..Default::default()
+            }
+            .into(),
+            callback_url: Some(callback.to_string()),
+        };
+        assert!(matches!(
+            submit_job(&pool, None, None, request("ftp://example.com/hook")),
+            Err(PdfServiceError::InvalidUrl(_))
+        ));
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let callback = format!("http://{}/hook", listener.local_addr().unwrap());
+        submit_job(&pool, None, Some("report"), request(&callback)).unwrap();
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            headers.push(line.trim().to_ascii_lowercase());
+        }
+        let length: usize = headers
+            .iter()
+            .find_map(|h| h.strip_prefix("content-length: "))
+            .unwrap()
+            .parse()
+            .unwrap();
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+            .unwrap();
+
+        assert!(headers[0].starts_with("post /hook "));
+        assert!(headers.contains(&"x-job-id: report".to_string()));
+        assert!(headers.contains(&"content-type: application/json".to_string()));
+        let job: JobResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.error.unwrap().code, "BROWSER_UNAVAILABLE");
+    }
+
+    /// Verifies callbacks connect to the checked addresses rather than
+    /// resolving the host again, and private addresses are refused.
+    #[test]
+    fn test_callback_pinned() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let policy = UrlPolicy::public();
+        assert!(resolve_callback(&policy, "http://127.0.0.1:1/hook").is_err());
+        assert!(resolve_callback(&UrlPolicy::default(), "http://127.0.0.1:1/hook").is_ok());
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                headers.push(line.trim().to_ascii_lowercase());
+            }
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            headers
+        });
+
+        // `.invalid` never resolves, so the request can only reach the pin
+        let url = format!("http://callback.invalid:{}/hook", addr.port());
+        let body = ResponseBody {
+            content_type: "application/json".to_string(),
+            filename: None,
+            content_disposition: None,
+            data: b"{}".to_vec().into(),
+        };
+        post_callback(&url, vec![addr], "report", &body).unwrap();
+
+        let headers = server.join().unwrap();
+        assert!(headers[0].starts_with("post /hook "));
+        assert!(headers.contains(&format!("host: callback.invalid:{}", addr.port())));
+    }
+
+    /// Verifies overlong job IDs are rejected.
+    #[test]
+    fn test_job_id_length() {
+        let long = "x".repeat(MAX_JOB_ID_LEN + 1);
+        assert!(matches!(
+            job_key(None, &long),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+        assert!(job_key(None, "report-42").is_ok());
+    }
+}
@@ -0,0 +1,176 @@
+//! Linearized ("fast web view") PDF output.
+//!
+//! Chrome writes PDFs with the page tree and cross-reference table at the
+//! end of the file, so a viewer must download the whole document before it
+//! can show anything. A linearized PDF puts the first page's objects and a
+//! hint table up front; browsers fetching it with HTTP range requests can
+//! show page 1 of a large report while the rest is still downloading.
+//!
+//! Linearization is done by [qpdf](https://qpdf.readthedocs.io), which must
+//! be installed on the server. It is looked up on `PATH`, or at the path in
+//! the [`QPDF_PATH_ENV`] environment variable. The helpers for running it
+//! are shared with [`split`](super::split) and [`merge`](super::merge).
+
+use ring::rand::{SecureRandom, SystemRandom};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+
+use crate::service::types::PdfServiceError;
+
+/// Environment variable overriding the qpdf binary used for linearization,
+/// page splitting, and merging.
+pub const QPDF_PATH_ENV: &str = "QPDF_PATH";
+
+/// qpdf binary used when [`QPDF_PATH_ENV`] is not set.
+const DEFAULT_QPDF: &str = "qpdf";
+
+/// qpdf exit code for "succeeded, with warnings"; the output is usable.
+const QPDF_EXIT_WARNINGS: i32 = 3;
+
+/// Rewrite a PDF in linearized form.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::PdfGenerationFailed`] if qpdf is not
+/// installed or rejects the document.
+pub(crate) fn linearize_pdf(pdf: &[u8]) -> Result<Vec<u8>, PdfServiceError> {
+    let start = Instant::now();
+
+    let linearized = run_qpdf(&qpdf_binary(), pdf).map_err(|e| {
+        log::error!("❌ Failed to linearize PDF: {}", e);
+        PdfServiceError::PdfGenerationFailed(format!("failed to linearize PDF: {}", e))
+    })?;
+
+    log::debug!(
+        "PDF linearized in {:?} ({} -> {} bytes)",
+        start.elapsed(),
+        pdf.len(),
+        linearized.len()
+    );
+
+    Ok(linearized)
+}
+
+/// The qpdf binary: [`QPDF_PATH_ENV`] if set, else `qpdf` on `PATH`.
+pub(crate) fn qpdf_binary() -> OsString {
+    std::env::var_os(QPDF_PATH_ENV).unwrap_or_else(|| OsString::from(DEFAULT_QPDF))
+}
+
+/// Run `qpdf --linearize` on `pdf` in a private temporary directory.
+fn run_qpdf(qpdf: &OsStr, pdf: &[u8]) -> Result<Vec<u8>, String> {
+    let dir = TempDir::create()?;
+    let input = dir.path().join("input.pdf");
+    let output = dir.path().join("linearized.pdf");
+
+    fs::write(&input, pdf).map_err(|e| format!("cannot write temporary file: {}", e))?;
+    qpdf_command(
+        qpdf,
+        [
+            OsStr::new("--linearize"),
+            input.as_os_str(),
+            output.as_os_str(),
+        ],
+    )?;
+
+    fs::read(&output).map_err(|e| format!("cannot read qpdf output: {}", e))
+}
+
+/// Run qpdf with `args`, treating "succeeded with warnings" as success.
+pub(crate) fn qpdf_command<'a>(
+    qpdf: &OsStr,
+    args: impl IntoIterator<Item = &'a OsStr>,
+) -> Result<(), String> {
+    let result = Command::new(qpdf).args(args).output().map_err(|e| {
+        format!(
+            "cannot run {} ({}); install qpdf or set {}",
+            qpdf.to_string_lossy(),
+            e,
+            QPDF_PATH_ENV
+        )
+    })?;
+
+    if !result.status.success() && result.status.code() != Some(QPDF_EXIT_WARNINGS) {
+        return Err(format!(
+            "qpdf exited with {}: {}",
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Temporary directory removed on drop.
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create a new, randomly named directory only this user can access.
+    pub(crate) fn create() -> Result<Self, String> {
+        let mut bytes = [0u8; 8];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| "cannot generate temporary directory name".to_string())?;
+        let name: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        let path = std::env::temp_dir().join(format!("html2pdf-qpdf-{}", name));
+
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder
+            .create(&path)
+            .map_err(|e| format!("cannot create temporary directory: {}", e))?;
+
+        Ok(Self(path))
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.0) {
+            log::warn!("Failed to remove {}: {}", self.0.display(), e);
+        }
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies a missing qpdf is reported with a hint.
+    #[test]
+    fn test_missing_qpdf() {
+        let error = run_qpdf(OsStr::new("/nonexistent/qpdf"), b"%PDF-1.4\n").unwrap_err();
+
+        assert!(error.contains("/nonexistent/qpdf"));
+        assert!(error.contains(QPDF_PATH_ENV));
+    }
+
+    /// Verifies a failing qpdf run is reported with its exit status.
+    #[cfg(unix)]
+    #[test]
+    fn test_qpdf_failure() {
+        let error = run_qpdf(OsStr::new("false"), b"%PDF-1.4\n").unwrap_err();
+        assert!(error.starts_with("qpdf exited with"));
+    }
+
+    /// Verifies the temporary directory is removed with its contents.
+    #[test]
+    fn test_temp_dir_cleanup() {
+        let dir = TempDir::create().unwrap();
+        let path = dir.path().to_path_buf();
+        fs::write(path.join("input.pdf"), b"%PDF-1.4\n").unwrap();
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+}
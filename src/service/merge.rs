@@ -0,0 +1,186 @@
+//! Merged PDF output.
+//!
+//! Reports are often assembled from parts that are easiest to render
+//! separately: a cover page from an HTML template, the body from the live
+//! application, an appendix in landscape. [`merge_pdfs`] renders each part
+//! as its own document, in parallel across the pool, and joins the results
+//! with [qpdf](https://qpdf.readthedocs.io) (see
+//! [`QPDF_PATH_ENV`](crate::service::QPDF_PATH_ENV)):
+//!
+//! ```text
+//! documents[0] ──▶ cover.pdf    ┐
+//! documents[1] ──▶ body.pdf     ├──▶ qpdf --empty --pages ... ──▶ merged.pdf
+//! documents[2] ──▶ appendix.pdf ┘
+//! ```
+
+use std::ffi::OsStr;
+use std::fs;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::pool::BrowserPool;
+use crate::service::linearize::{TempDir, qpdf_binary, qpdf_command};
+use crate::service::pdf::{render_parallel, run_post_processors};
+use crate::service::types::{PdfMergeRequest, PdfResponse, PdfServiceError};
+
+/// Most documents accepted in one merge.
+const MAX_MERGE_DOCUMENTS: usize = 32;
+
+/// Render several documents and join them into one PDF, in order.
+///
+/// Documents are rendered like [`render_parallel`] does, at most
+/// [`max_pool_size`](crate::BrowserPoolConfig::max_pool_size) at once. If
+/// any of them fails, nothing is merged and its error is returned. The
+/// pool's [`post_processors`](crate::BrowserPoolConfig::post_processors)
+/// run on each part as it renders and again on the merged PDF.
+///
+/// # Blocking Behavior
+///
+/// **This function blocks the calling thread** until every document has
+/// been rendered and merged. In async code, call it from `spawn_blocking`.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `request` - The documents and the merged PDF's filename
+///
+/// # Errors
+///
+/// - [`PdfServiceError::InvalidRequest`] if `documents` is empty or holds
+///   more than 32 documents
+/// - The first error of a document that failed to render
+/// - [`PdfServiceError::PdfGenerationFailed`] if qpdf is not installed or
+///   rejects a document, or a post-processor fails
+/// - [`PdfServiceError::PoolLockFailed`] if the pool lock is poisoned
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{PdfFromHtmlRequest, PdfFromUrlRequest, PdfMergeRequest, merge_pdfs};
+///
+/// let request = PdfMergeRequest {
+///     documents: vec![
+///         PdfFromHtmlRequest { html: cover_html, ..Default::default() }.into(),
+///         PdfFromUrlRequest { url: report_url, ..Default::default() }.into(),
+///     ],
+///     filename: Some("report.pdf".to_string()),
+///     ..Default::default()
+/// };
+/// let merged = merge_pdfs(&pool, &request)?;
+/// ```
+pub fn merge_pdfs(
+    pool: &Mutex<BrowserPool>,
+    request: &PdfMergeRequest,
+) -> Result<PdfResponse, PdfServiceError> {
+    validate_documents(request.documents.len())?;
+
+    let (max_concurrency, post_processors) = {
+        let pool = pool
+            .lock()
+            .map_err(|e| PdfServiceError::PoolLockFailed(e.to_string()))?;
+        let config = pool.config();
+        (config.max_pool_size, config.post_processors.clone())
+    };
+
+    log::info!("Merging {} documents", request.documents.len());
+    let mut parts = Vec::with_capacity(request.documents.len());
+    for (index, result) in render_parallel(pool, &request.documents, max_concurrency)
+        .into_iter()
+        .enumerate()
+    {
+        match result {
+            Ok(response) => parts.push(response.data),
+            Err(e) => {
+                log::warn!("Merge document {} failed: {}", index + 1, e);
+                return Err(e);
+            }
+        }
+    }
+
+    let start = Instant::now();
+    let merged = merge_documents(&qpdf_binary(), &parts).map_err(|e| {
+        log::error!("❌ Failed to merge PDFs: {}", e);
+        PdfServiceError::PdfGenerationFailed(format!("failed to merge PDFs: {}", e))
+    })?;
+
+    log::debug!(
+        "{} PDFs merged in {:?} ({} bytes)",
+        parts.len(),
+        start.elapsed(),
+        merged.len()
+    );
+    let merged = run_post_processors(merged, &post_processors)?;
+
+    Ok(PdfResponse::new(
+        merged,
+        request.filename_or_default(),
+        request.is_download(),
+    ))
+}
+
+/// Check the number of documents to merge.
+fn validate_documents(count: usize) -> Result<(), PdfServiceError> {
+    if count == 0 {
+        return Err(PdfServiceError::InvalidRequest(
+            "documents must not be empty".to_string(),
+        ));
+    }
+    if count > MAX_MERGE_DOCUMENTS {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "at most {} documents can be merged",
+            MAX_MERGE_DOCUMENTS
+        )));
+    }
+    Ok(())
+}
+
+/// Run `qpdf --empty --pages` on `documents`; returns the merged PDF.
+fn merge_documents(qpdf: &OsStr, documents: &[impl AsRef<[u8]>]) -> Result<Vec<u8>, String> {
+    let dir = TempDir::create()?;
+    let output = dir.path().join("merged.pdf");
+
+    let mut inputs = Vec::with_capacity(documents.len());
+    for (index, document) in documents.iter().enumerate() {
+        let input = dir.path().join(format!("document-{}.pdf", index + 1));
+        fs::write(&input, document).map_err(|e| format!("cannot write temporary file: {}", e))?;
+        inputs.push(input);
+    }
+
+    let mut args = vec![OsStr::new("--empty"), OsStr::new("--pages")];
+    args.extend(inputs.iter().map(|input| input.as_os_str()));
+    args.extend([OsStr::new("--"), output.as_os_str()]);
+    qpdf_command(qpdf, args)?;
+
+    fs::read(&output).map_err(|e| format!("cannot read qpdf output: {}", e))
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies empty and oversized merges are rejected.
+    #[test]
+    fn test_validate_documents() {
+        assert!(matches!(
+            validate_documents(0),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+        assert!(validate_documents(1).is_ok());
+        assert!(validate_documents(MAX_MERGE_DOCUMENTS).is_ok());
+        assert!(matches!(
+            validate_documents(MAX_MERGE_DOCUMENTS + 1),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+    }
+
+    /// Verifies a missing qpdf is reported.
+    #[test]
+    fn test_missing_qpdf() {
+        let error = merge_documents(OsStr::new("/nonexistent/qpdf"), &[b"%PDF-1.4\n"]).unwrap_err();
+        assert!(error.contains("/nonexistent/qpdf"));
+    }
+}
@@ -0,0 +1,385 @@
+//! Localized error messages.
+//!
+//! An [`ErrorResponse`](crate::service::ErrorResponse) carries three
+//! things: the stable machine `code`, the `error` detail meant for
+//! developers and logs (always English), and a `message` end users can be
+//! shown. The message comes from a catalog keyed by code, in the language
+//! the client's `Accept-Language` header prefers:
+//!
+//! ```json
+//! {
+//!     "error": "Navigation failed: net::ERR_NAME_NOT_RESOLVED",
+//!     "code": "NAVIGATION_FAILED",
+//!     "message": "Die Seite konnte nicht geladen werden."
+//! }
+//! ```
+//!
+//! | Locale | Language |
+//! |--------|----------|
+//! | `en` | English (default) |
+//! | `de` | German |
+//! | `es` | Spanish |
+//! | `fr` | French |
+//!
+//! The pre-built endpoints name the chosen language in a
+//! `Content-Language` header.
+
+/// Locale used when the client accepts none of [`SUPPORTED_LOCALES`].
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Locales the message catalog covers, in the column order of `CATALOG`.
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "de", "es", "fr"];
+
+/// Message for each error code, one column per [`SUPPORTED_LOCALES`]
+/// entry.
+///
+/// Messages say what happened in terms an end user understands; the
+/// technical cause stays in `error`.
+const CATALOG: &[(&str, [&str; 4])] = &[
+    (
+        "INVALID_URL",
+        [
+            "The address of the page is not valid.",
+            "Die Adresse der Seite ist ungültig.",
+            "La dirección de la página no es válida.",
+            "L'adresse de la page n'est pas valide.",
+        ],
+    ),
+    (
+        "EMPTY_HTML",
+        [
+            "There is no content to convert.",
+            "Es gibt keinen Inhalt zum Umwandeln.",
+            "No hay contenido para convertir.",
+            "Il n'y a aucun contenu à convertir.",
+        ],
+    ),
+    (
+        "INVALID_REQUEST",
+        [
+            "One of the document options is not valid.",
+            "Eine der Dokumentoptionen ist ungültig.",
+            "Una de las opciones del documento no es válida.",
+            "L'une des options du document n'est pas valide.",
+        ],
+    ),
+    (
+        "IDEMPOTENCY_CONFLICT",
+        [
+            "This document is already being created. Please wait a moment.",
+            "Dieses Dokument wird bereits erstellt. Bitte warten Sie einen Moment.",
+            "Este documento ya se está generando. Espere un momento.",
+            "Ce document est déjà en cours de création. Veuillez patienter un instant.",
+        ],
+    ),
+    (
+        "QUOTA_EXCEEDED",
+        [
+            "The document limit for this account has been reached.",
+            "Das Dokumentenlimit für dieses Konto ist erreicht.",
+            "Se ha alcanzado el límite de documentos de esta cuenta.",
+            "La limite de documents de ce compte est atteinte.",
+        ],
+    ),
+    (
+        "JOB_NOT_FOUND",
+        [
+            "The document could not be found. It may have expired.",
+            "Das Dokument wurde nicht gefunden. Möglicherweise ist es abgelaufen.",
+            "No se encontró el documento. Es posible que haya caducado.",
+            "Le document est introuvable. Il a peut-être expiré.",
+        ],
+    ),
+    (
+        "JOB_PENDING",
+        [
+            "The document is not ready yet. Please try again shortly.",
+            "Das Dokument ist noch nicht fertig. Bitte versuchen Sie es gleich noch einmal.",
+            "El documento aún no está listo. Vuelva a intentarlo en breve.",
+            "Le document n'est pas encore prêt. Veuillez réessayer dans un instant.",
+        ],
+    ),
+    (
+        "JOB_QUEUE_FULL",
+        [
+            "Too many documents are waiting to be created. Please try again later.",
+            "Zu viele Dokumente warten auf ihre Erstellung. Bitte versuchen Sie es später erneut.",
+            "Hay demasiados documentos en espera. Vuelva a intentarlo más tarde.",
+            "Trop de documents sont en attente. Veuillez réessayer plus tard.",
+        ],
+    ),
+    (
+        "CANCELLED",
+        [
+            "Creating the document was cancelled.",
+            "Die Erstellung des Dokuments wurde abgebrochen.",
+            "Se canceló la generación del documento.",
+            "La création du document a été annulée.",
+        ],
+    ),
+    (
+        "POOL_LOCK_FAILED",
+        [
+            "Something went wrong on our side. Please try again later.",
+            "Bei uns ist ein Fehler aufgetreten. Bitte versuchen Sie es später erneut.",
+            "Se produjo un error en nuestro sistema. Vuelva a intentarlo más tarde.",
+            "Une erreur s'est produite de notre côté. Veuillez réessayer plus tard.",
+        ],
+    ),
+    (
+        "BROWSER_UNAVAILABLE",
+        [
+            "The service is busy. Please try again in a moment.",
+            "Der Dienst ist ausgelastet. Bitte versuchen Sie es gleich noch einmal.",
+            "El servicio está ocupado. Vuelva a intentarlo en un momento.",
+            "Le service est occupé. Veuillez réessayer dans un instant.",
+        ],
+    ),
+    (
+        "DISK_FULL",
+        [
+            "The service is temporarily out of storage. Please try again later.",
+            "Dem Dienst steht vorübergehend kein Speicherplatz zur Verfügung. Bitte versuchen Sie es später erneut.",
+            "El servicio no tiene espacio de almacenamiento por el momento. Vuelva a intentarlo más tarde.",
+            "Le service manque temporairement d'espace de stockage. Veuillez réessayer plus tard.",
+        ],
+    ),
+    (
+        "TAB_CREATION_FAILED",
+        [
+            "Something went wrong on our side. Please try again later.",
+            "Bei uns ist ein Fehler aufgetreten. Bitte versuchen Sie es später erneut.",
+            "Se produjo un error en nuestro sistema. Vuelva a intentarlo más tarde.",
+            "Une erreur s'est produite de notre côté. Veuillez réessayer plus tard.",
+        ],
+    ),
+    (
+        "NAVIGATION_FAILED",
+        [
+            "The page could not be loaded.",
+            "Die Seite konnte nicht geladen werden.",
+            "No se pudo cargar la página.",
+            "La page n'a pas pu être chargée.",
+        ],
+    ),
+    (
+        "NAVIGATION_TIMEOUT",
+        [
+            "The page took too long to load.",
+            "Das Laden der Seite hat zu lange gedauert.",
+            "La página tardó demasiado en cargarse.",
+            "Le chargement de la page a pris trop de temps.",
+        ],
+    ),
+    (
+        "REDIRECT_REJECTED",
+        [
+            "The page redirected to an address that is not allowed.",
+            "Die Seite hat auf eine nicht zulässige Adresse weitergeleitet.",
+            "La página redirigió a una dirección no permitida.",
+            "La page a redirigé vers une adresse non autorisée.",
+        ],
+    ),
+    (
+        "ACTION_FAILED",
+        [
+            "An interaction with the page could not be completed.",
+            "Eine Interaktion mit der Seite konnte nicht ausgeführt werden.",
+            "No se pudo completar una interacción con la página.",
+            "Une interaction avec la page n'a pas pu être effectuée.",
+        ],
+    ),
+    (
+        "PDF_GENERATION_FAILED",
+        [
+            "The PDF could not be created.",
+            "Das PDF konnte nicht erstellt werden.",
+            "No se pudo crear el PDF.",
+            "Le PDF n'a pas pu être créé.",
+        ],
+    ),
+    (
+        "TIMEOUT",
+        [
+            "Creating the document took too long.",
+            "Die Erstellung des Dokuments hat zu lange gedauert.",
+            "La generación del documento tardó demasiado.",
+            "La création du document a pris trop de temps.",
+        ],
+    ),
+    (
+        "POOL_SHUTTING_DOWN",
+        [
+            "The service is restarting. Please try again in a moment.",
+            "Der Dienst wird neu gestartet. Bitte versuchen Sie es gleich noch einmal.",
+            "El servicio se está reiniciando. Vuelva a intentarlo en un momento.",
+            "Le service redémarre. Veuillez réessayer dans un instant.",
+        ],
+    ),
+    (
+        "INTERNAL_ERROR",
+        [
+            "Something went wrong on our side. Please try again later.",
+            "Bei uns ist ein Fehler aufgetreten. Bitte versuchen Sie es später erneut.",
+            "Se produjo un error en nuestro sistema. Vuelva a intentarlo más tarde.",
+            "Une erreur s'est produite de notre côté. Veuillez réessayer plus tard.",
+        ],
+    ),
+    (
+        "NOT_READY",
+        [
+            "The service is busy. Please try again in a moment.",
+            "Der Dienst ist ausgelastet. Bitte versuchen Sie es gleich noch einmal.",
+            "El servicio está ocupado. Vuelva a intentarlo en un momento.",
+            "Le service est occupé. Veuillez réessayer dans un instant.",
+        ],
+    ),
+];
+
+/// Pick the supported locale an `Accept-Language` header prefers.
+///
+/// Languages are matched on their primary subtag (`de-CH` selects `de`)
+/// and ranked by `q` weight, earlier entries winning ties; `q=0` rules a
+/// language out. Falls back to [`DEFAULT_LOCALE`].
+///
+/// # Examples
+///
+/// ```rust
+/// use html2pdf_api::service::negotiate_locale;
+///
+/// assert_eq!(negotiate_locale(Some("fr-CA, fr;q=0.9, en;q=0.8")), "fr");
+/// assert_eq!(negotiate_locale(Some("ja, de;q=0.5")), "de");
+/// assert_eq!(negotiate_locale(Some("ja")), "en");
+/// assert_eq!(negotiate_locale(None), "en");
+/// ```
+pub fn negotiate_locale(accept_language: Option<&str>) -> &'static str {
+    let mut best: Option<(&'static str, f32)> = None;
+    for entry in accept_language.unwrap_or_default().split(',') {
+        let mut params = entry.split(';');
+        let tag = params.next().unwrap_or_default().trim();
+        let quality = params
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if quality <= 0.0 {
+            continue;
+        }
+
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        let locale = if primary == "*" {
+            Some(DEFAULT_LOCALE)
+        } else {
+            SUPPORTED_LOCALES
+                .iter()
+                .copied()
+                .find(|locale| locale.eq_ignore_ascii_case(primary))
+        };
+        if let Some(locale) = locale {
+            if best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((locale, quality));
+            }
+        }
+    }
+    best.map_or(DEFAULT_LOCALE, |(locale, _)| locale)
+}
+
+/// The end-user message for an error `code` in `locale`.
+///
+/// Unsupported locales get the [`DEFAULT_LOCALE`] message; `None` for a
+/// code the catalog doesn't know.
+///
+/// # Examples
+///
+/// ```rust
+/// use html2pdf_api::service::error_message;
+///
+/// assert_eq!(
+///     error_message("NAVIGATION_TIMEOUT", "es"),
+///     Some("La página tardó demasiado en cargarse.")
+/// );
+/// assert_eq!(error_message("NO_SUCH_CODE", "en"), None);
+/// ```
+pub fn error_message(code: &str, locale: &str) -> Option<&'static str> {
+    let column = SUPPORTED_LOCALES
+        .iter()
+        .position(|supported| supported.eq_ignore_ascii_case(locale))
+        .unwrap_or(0);
+    CATALOG
+        .iter()
+        .find(|(entry, _)| *entry == code)
+        .map(|(_, messages)| messages[column])
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::PdfServiceError;
+
+    /// Verifies weights, subtags, wildcards, and exclusions are honoured.
+    #[test]
+    fn test_negotiate_locale() {
+        let cases = [
+            (Some("de"), "de"),
+            (Some("de-AT"), "de"),
+            (Some("ES"), "es"),
+            (Some("en;q=0.5, fr;q=0.8"), "fr"),
+            (Some("fr;q=0.8, es;q=0.8"), "fr"),
+            (Some("ja, zh;q=0.9, *;q=0.1"), "en"),
+            (Some("de;q=0, fr;q=0.1"), "fr"),
+            (Some("de;q=abc"), "de"),
+            (Some(""), "en"),
+            (Some("ja"), "en"),
+            (None, "en"),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(negotiate_locale(header), expected, "{:?}", header);
+        }
+    }
+
+    /// Verifies every error code has a message in every locale.
+    #[test]
+    fn test_catalog_covers_error_codes() {
+        let errors = [
+            PdfServiceError::InvalidUrl(String::new()),
+            PdfServiceError::EmptyHtml,
+            PdfServiceError::InvalidRequest(String::new()),
+            PdfServiceError::IdempotencyConflict(String::new()),
+            PdfServiceError::QuotaExceeded(String::new()),
+            PdfServiceError::JobNotFound(String::new()),
+            PdfServiceError::JobPending(String::new()),
+            PdfServiceError::JobQueueFull(String::new()),
+            PdfServiceError::Cancelled(String::new()),
+            PdfServiceError::PoolLockFailed(String::new()),
+            PdfServiceError::BrowserUnavailable(String::new()),
+            PdfServiceError::DiskFull(String::new()),
+            PdfServiceError::TabCreationFailed(String::new()),
+            PdfServiceError::NavigationFailed(String::new()),
+            PdfServiceError::NavigationTimeout(String::new()),
+            PdfServiceError::RedirectRejected(String::new()),
+            PdfServiceError::ActionFailed(String::new()),
+            PdfServiceError::PdfGenerationFailed(String::new()),
+            PdfServiceError::Timeout(String::new()),
+            PdfServiceError::PoolShuttingDown,
+            PdfServiceError::Internal(String::new()),
+        ];
+        for error in errors {
+            for locale in SUPPORTED_LOCALES {
+                let message = error_message(error.error_code(), locale);
+                assert!(
+                    message.is_some_and(|m| !m.is_empty()),
+                    "{} in {}",
+                    error.error_code(),
+                    locale
+                );
+            }
+        }
+        assert_eq!(
+            error_message("TIMEOUT", "pt"),
+            error_message("TIMEOUT", DEFAULT_LOCALE)
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! PDF document metadata.
+//!
+//! Chrome fills the PDF's document information dictionary with little more
+//! than the page's `<title>` and its own producer string. A request's
+//! [`metadata`](crate::service::PdfFromUrlRequest::metadata) replaces those
+//! properties by appending an incremental update to the PDF, as any PDF
+//! editor saving in place does:
+//!
+//! ```text
+//! %PDF-1.4 ... xref ... trailer << /Info 5 0 R >> startxref ... %%EOF   (as Chrome wrote it)
+//! 42 0 obj << /Title (Q2 Report) /Author (Finance) ... >> endobj
+//! xref 42 1 ... trailer << /Info 42 0 R /Prev ... >> startxref ... %%EOF
+//! ```
+//!
+//! The original bytes are untouched, so the pages render exactly as before.
+//! The update is written straight after rendering, before
+//! [`pdf_a`](crate::service::PdfFromUrlRequest::pdf_a) or
+//! [`linearize`](crate::service::PdfFromUrlRequest::linearize) rewrite the
+//! file; both carry the properties over.
+
+use crate::service::incremental::{Update, text_string};
+use crate::service::types::{PdfMetadata, PdfServiceError};
+
+/// Longest title, author, subject, or keyword list accepted, in bytes.
+const MAX_METADATA_LEN: usize = 1024;
+
+/// A request's `metadata`, checked and encoded for the PDF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DocumentInfo {
+    /// Document information keys and their PDF-encoded values.
+    entries: Vec<(&'static str, String)>,
+}
+
+impl DocumentInfo {
+    /// Check a request's `metadata`.
+    ///
+    /// Returns `None` when it sets nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfServiceError::InvalidRequest`] for a value over
+    /// [`MAX_METADATA_LEN`] bytes or a malformed `creation_date`.
+    pub(crate) fn new(metadata: Option<&PdfMetadata>) -> Result<Option<Self>, PdfServiceError> {
+        let Some(metadata) = metadata else {
+            return Ok(None);
+        };
+
+        let keywords = metadata
+            .keywords
+            .as_ref()
+            .map(|keywords| keywords.join(", "));
+        let texts = [
+            ("title", "Title", metadata.title.as_deref()),
+            ("author", "Author", metadata.author.as_deref()),
+            ("subject", "Subject", metadata.subject.as_deref()),
+            ("keywords", "Keywords", keywords.as_deref()),
+        ];
+
+        let mut entries = Vec::new();
+        for (name, key, value) in texts {
+            let Some(value) = value else { continue };
+            if value.len() > MAX_METADATA_LEN {
+                return Err(PdfServiceError::InvalidRequest(format!(
+                    "metadata.{} must be at most {} bytes",
+                    name, MAX_METADATA_LEN
+                )));
+            }
+            entries.push((key, text_string(value)));
+        }
+        if let Some(date) = metadata.creation_date.as_deref() {
+            let date = pdf_date(date).ok_or_else(|| {
+                PdfServiceError::InvalidRequest(format!(
+                    "invalid metadata.creation_date {:?}: expected RFC 3339 \
+                     (2024-05-01T09:30:00Z) or YYYY-MM-DD",
+                    date
+                ))
+            })?;
+            entries.push(("CreationDate", format!("({})", date)));
+        }
+
+        Ok((!entries.is_empty()).then_some(Self { entries }))
+    }
+
+    /// Names of the properties set, for logs and debug reports.
+    pub(crate) fn keys(&self) -> Vec<&'static str> {
+        self.entries.iter().map(|(key, _)| *key).collect()
+    }
+}
+
+/// Write `info` into `pdf`'s document information with an incremental
+/// update.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::PdfGenerationFailed`] if the PDF's
+/// cross-reference table cannot be read.
+pub(crate) fn stamp_metadata(pdf: &[u8], info: &DocumentInfo) -> Result<Vec<u8>, PdfServiceError> {
+    append_info(pdf, info).map_err(|e| {
+        log::error!("❌ Failed to write PDF metadata: {}", e);
+        PdfServiceError::PdfGenerationFailed(format!("failed to write PDF metadata: {}", e))
+    })
+}
+
+/// Append the update; errors describe what could not be parsed.
+fn append_info(pdf: &[u8], info: &DocumentInfo) -> Result<Vec<u8>, String> {
+    let mut update = Update::open(pdf)?;
+
+    // Start from the current properties, so the ones not set survive
+    let mut entries = match update.trailer("Info") {
+        Some(reference) => update.read_dict(reference)?,
+        None => Vec::new(),
+    };
+    for (key, value) in &info.entries {
+        match entries.iter_mut().find(|(name, _)| name == key) {
+            Some(entry) => entry.1.clone_from(value),
+            None => entries.push((key.to_string(), value.clone())),
+        }
+    }
+
+    let number = update.new_object();
+    update.write_dict(number, &entries);
+    Ok(update.finish(&[("Info", format!("{} 0 R", number))]))
+}
+
+/// Convert an RFC 3339 timestamp or `YYYY-MM-DD` date to a PDF date
+/// (`D:YYYYMMDDHHmmSS+HH'mm'`).
+fn pdf_date(date: &str) -> Option<String> {
+    let date = date.trim();
+    let (day, time) = match date.split_once(['T', 't', ' ']) {
+        Some((day, time)) => (day, Some(time)),
+        None => (date, None),
+    };
+
+    let [year, month, day_of_month] = split_numbers(day, '-', [4, 2, 2])?;
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day_of_month) {
+        return None;
+    }
+
+    let (clock, zone) = match time {
+        None => ([0, 0, 0], "Z".to_string()),
+        Some(time) => {
+            let zone_at = time.find(['Z', 'z', '+', '-'])?;
+            let (clock, zone) = time.split_at(zone_at);
+            // Fractional seconds are below what a PDF date can hold
+            let clock = clock.split('.').next()?;
+            let clock = split_numbers(clock, ':', [2, 2, 2])?;
+            if clock[0] > 23 || clock[1] > 59 || clock[2] > 59 {
+                return None;
+            }
+            let zone = if zone.eq_ignore_ascii_case("z") {
+                "Z".to_string()
+            } else {
+                let (sign, offset) = zone.split_at(1);
+                let [hours, minutes] = split_numbers(offset, ':', [2, 2])?;
+                if hours > 23 || minutes > 59 {
+                    return None;
+                }
+                format!("{}{:02}'{:02}'", sign, hours, minutes)
+            };
+            (clock, zone)
+        }
+    };
+
+    Some(format!(
+        "D:{:04}{:02}{:02}{:02}{:02}{:02}{}",
+        year, month, day_of_month, clock[0], clock[1], clock[2], zone
+    ))
+}
+
+/// Split `text` at `separator` into numbers of exactly the given widths.
+fn split_numbers<const N: usize>(
+    text: &str,
+    separator: char,
+    widths: [usize; N],
+) -> Option<[u32; N]> {
+    let mut numbers = [0; N];
+    let mut parts = text.split(separator);
+    for (number, width) in numbers.iter_mut().zip(widths) {
+        let part = parts.next()?;
+        if part.len() != width || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        *number = part.parse().ok()?;
+    }
+    parts.next().is_none().then_some(numbers)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::incremental::tests::chrome_like_pdf;
+
+    /// Verifies the update keeps the original bytes, replaces only the
+    /// properties set, and chains to the previous cross-reference table.
+    #[test]
+    fn test_stamp_metadata() {
+        let pdf = chrome_like_pdf();
+        let info = DocumentInfo::new(Some(&PdfMetadata {
+            title: Some("Q2 Report".to_string()),
+            author: Some("Zoë".to_string()),
+            keywords: Some(vec!["quarterly".to_string(), "revenue".to_string()]),
+            creation_date: Some("2024-07-01".to_string()),
+            ..Default::default()
+        }))
+        .unwrap()
+        .unwrap();
+
+        let stamped = stamp_metadata(&pdf, &info).unwrap();
+        assert!(stamped.starts_with(&pdf));
+        let update = String::from_utf8(stamped[pdf.len()..].to_vec()).unwrap();
+        assert!(update.contains("/Title (Q2 Report)"));
+        assert!(update.contains("/Creator (Chromium)"));
+        assert!(update.contains("/Producer <FEFF0053006B00690061>"));
+        assert!(update.contains("/Author <FEFF005A006F00EB>"));
+        assert!(update.contains("/Keywords (quarterly, revenue)"));
+        assert!(update.contains("/CreationDate (D:20240701000000Z)"));
+        assert!(update.contains("/Root 1 0 R /Size 5 /Info 4 0 R /Prev "));
+
+        // The new object sits where the new table says
+        let object = update.find("4 0 obj").unwrap() + pdf.len();
+        assert!(update.contains(&format!("4 1\n{:010} 00000 n ", object)));
+
+        // A second stamp follows the chain back to the first
+        let info = DocumentInfo::new(Some(&PdfMetadata {
+            subject: Some("Revenue".to_string()),
+            ..Default::default()
+        }))
+        .unwrap()
+        .unwrap();
+        let restamped = stamp_metadata(&stamped, &info).unwrap();
+        let update = String::from_utf8(restamped[stamped.len()..].to_vec()).unwrap();
+        assert!(update.contains("/Title (Q2 Report)"));
+        assert!(update.contains("/Subject (Revenue)"));
+        assert!(update.contains("/Size 6 /Info 5 0 R"));
+    }
+
+    /// Verifies unreadable PDFs fail instead of being corrupted.
+    #[test]
+    fn test_stamp_metadata_unreadable() {
+        let info = DocumentInfo::new(Some(&PdfMetadata {
+            title: Some("x".to_string()),
+            ..Default::default()
+        }))
+        .unwrap()
+        .unwrap();
+        for pdf in [
+            &b"not a pdf"[..],
+            b"%PDF-1.5\n1 0 obj\n<< /Type /XRef >>\nendobj\nstartxref\n9\n%%EOF",
+        ] {
+            assert!(matches!(
+                stamp_metadata(pdf, &info),
+                Err(PdfServiceError::PdfGenerationFailed(_))
+            ));
+        }
+    }
+
+    /// Verifies request metadata is checked and encoded.
+    #[test]
+    fn test_document_info() {
+        assert_eq!(DocumentInfo::new(None).unwrap(), None);
+        assert_eq!(
+            DocumentInfo::new(Some(&PdfMetadata::default())).unwrap(),
+            None
+        );
+
+        let info = DocumentInfo::new(Some(&PdfMetadata {
+            title: Some("a (b) \\ c".to_string()),
+            ..Default::default()
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(info.entries, [("Title", "(a \\(b\\) \\\\ c)".to_string())]);
+        assert_eq!(info.keys(), ["Title"]);
+
+        for metadata in [
+            PdfMetadata {
+                subject: Some("x".repeat(MAX_METADATA_LEN + 1)),
+                ..Default::default()
+            },
+            PdfMetadata {
+                creation_date: Some("yesterday".to_string()),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                DocumentInfo::new(Some(&metadata)),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+    }
+
+    /// Verifies RFC 3339 timestamps and plain dates become PDF dates.
+    #[test]
+    fn test_pdf_date() {
+        assert_eq!(pdf_date("2024-02-29").unwrap(), "D:20240229000000Z");
+        assert_eq!(
+            pdf_date("2024-05-01T09:30:15.250+02:00").unwrap(),
+            "D:20240501093015+02'00'"
+        );
+        assert_eq!(
+            pdf_date("2024-05-01t23:59:59-05:30").unwrap(),
+            "D:20240501235959-05'30'"
+        );
+        assert_eq!(
+            pdf_date("2024-05-01T00:00:00Z").unwrap(),
+            "D:20240501000000Z"
+        );
+
+        for invalid in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024-5-1",
+            "2024-05-01T24:00:00Z",
+            "2024-05-01T09:30:00",
+            "2024-05-01T09:30:00+2",
+            "2024-05-01Z",
+        ] {
+            assert_eq!(pdf_date(invalid), None, "{}", invalid);
+        }
+    }
+}
@@ -62,21 +62,47 @@
 //! |------|---------|---------|
 //! | `PdfFromUrlRequest` | Parameters for URL → PDF conversion | `GET /pdf` |
 //! | `PdfFromHtmlRequest` | Parameters for HTML → PDF conversion | `POST /pdf/html` |
+//! | `PdfFromAssetsRequest` | HTML uploaded with its images, stylesheets, and fonts | `POST /pdf/html/assets` |
+//! | `PdfMergeRequest` | Documents to render and join into one PDF | `POST /pdf/merge` |
 //!
 //! ## Response Types
 //!
 //! | Type | Purpose | Used By |
 //! |------|---------|---------|
 //! | `PdfResponse` | Successful PDF generation result | PDF endpoints |
+//! | `PdfStream` | PDF read from Chrome in chunks while it is sent | PDF endpoints with `stream` |
 //! | `PoolStatsResponse` | Browser pool statistics | `GET /pool/stats` |
+//! | `PoolDebugResponse` | Detailed pool diagnostics | `GET /pool/debug` |
+//! | `PoolDetailedStatsResponse` | Per-browser statistics and lifetime counters | `GET /pool/stats/detailed` |
+//! | `UsageResponse` | Per-API-key usage and quotas | `GET /usage` |
+//! | `JobResponse` | State of a job | `/jobs` endpoints |
+//! | `RenderReport` | Timeline, console, and failed requests of a `debug` render | PDF endpoints |
 //! | `HealthResponse` | Health check response | `GET /health` |
 //! | `ErrorResponse` | JSON error response | All endpoints (on error) |
 //!
+//! ## Response Cache
+//!
+//! | Type | Purpose |
+//! |------|---------|
+//! | `PdfCache` | Storage backend for `render_cached` |
+//! | `MemoryPdfCache` | In-process backend (default) |
+//! | `RedisPdfCache` | Backend shared between instances (feature `redis-cache`) |
+//!
+//! ## Job Store
+//!
+//! | Type | Purpose |
+//! |------|---------|
+//! | `JobStore` | Where `POST /jobs` queues jobs and keeps their results |
+//! | `MemoryJobStore` | In-process backend (default) |
+//! | `RedisJobStore` | Backend shared between instances, surviving restarts (feature `redis-jobs`) |
+//!
 //! ## Error Types
 //!
 //! | Type | Purpose |
 //! |------|---------|
 //! | `PdfServiceError` | All possible service errors with HTTP status mapping |
+//! | `FallbackPdf` | Placeholder PDF returned instead of an error |
+//! | `ByteRange` | Part of a kept result a `Range` header asks for |
 //!
 //! ## Core Functions
 //!
@@ -84,7 +110,33 @@
 //! |----------|---------|-----------|
 //! | `generate_pdf_from_url` | Convert URL to PDF | ⚠️ Yes |
 //! | `generate_pdf_from_html` | Convert HTML to PDF | ⚠️ Yes |
+//! | `generate_pdf_from_assets` | Convert HTML with uploaded assets to PDF | ⚠️ Yes |
+//! | `generate_pdf_from_url_async` | Convert URL to PDF | ✅ No (async) |
+//! | `generate_pdf_from_html_async` | Convert HTML to PDF | ✅ No (async) |
+//! | `stream_pdf_from_url` | Convert URL to PDF, streamed out in chunks | ⚠️ Yes |
+//! | `stream_pdf_from_html` | Convert HTML to PDF, streamed out in chunks | ⚠️ Yes |
+//! | `render_parallel` | Convert many URLs/HTML documents | ⚠️ Yes |
+//! | `render_parallel_with` | Convert many URLs/HTML documents, handling each as it finishes | ⚠️ Yes |
+//! | `merge_pdfs` | Convert many URLs/HTML documents into one PDF | ⚠️ Yes |
+//! | `prewarm_url` | Load a URL ahead of time to warm caches | ⚠️ Yes |
+//! | `render_metered` | Enforce and record an API key's usage around a render | ⚠️ Yes |
+//! | `stream_metered` | Enforce and record an API key's usage around a streamed render | ⚠️ Yes |
+//! | `render_idempotent` | Replay a render's result for a repeated `Idempotency-Key` | ⚠️ Yes |
+//! | `render_coalesced` | Share one render among identical concurrent requests | ⚠️ Yes |
+//! | `render_cached` | Serve identical requests a recently rendered PDF | ⚠️ Yes |
+//! | `render_cancellable` | Let `DELETE /jobs/{id}` cancel a render by its `X-Job-Id` | ⚠️ Yes |
+//! | `submit_job` | Queue a render in the background | ✅ Fast (spawns) |
+//! | `spawn_job_worker` | Render jobs queued by other instances in a shared `JobStore` | ✅ Fast (spawns) |
+//! | `job_status` | Get a job's status | ✅ Fast |
+//! | `job_result` | Get a finished job's PDF or error | ✅ Fast |
+//! | `cancel_job` | Cancel a running job or delete a kept result | ✅ Fast |
+//! | `negotiate_locale` | Pick the error message language from `Accept-Language` | ✅ Fast |
+//! | `error_message` | Get the localized message for an error code | ✅ Fast |
+//! | `spawn_scheduler` | Run `ScheduledRender`s on their cron schedules | ✅ Fast (spawns) |
+//! | `get_usage` | Get an API key's usage | ✅ Fast |
 //! | `get_pool_stats` | Get pool statistics | ✅ Fast |
+//! | `get_pool_debug` | Get a detailed pool diagnostic snapshot | ✅ Fast |
+//! | `get_pool_detailed_stats` | Get per-browser statistics and lifetime counters | ✅ Fast |
 //! | `is_pool_ready` | Check pool readiness | ✅ Fast |
 //!
 //! ## Constants
@@ -93,6 +145,20 @@
 //! |----------|-------|---------|
 //! | `DEFAULT_TIMEOUT_SECS` | 60 | Overall operation timeout |
 //! | `DEFAULT_WAIT_SECS` | 5 | JavaScript wait time |
+//! | `DEFAULT_THUMBNAIL_WIDTH` | 256 | First-page thumbnail width in pixels |
+//! | `DEFAULT_WAIT_EXPRESSION` | `window.isPageDone === true` | Readiness check polled during the wait |
+//! | `READY_FUNCTION` | `html2pdfReady` | Function pages call to end the wait at once |
+//! | `API_KEY_HEADER` | `X-API-Key` | Header naming the API key usage is accounted to |
+//! | `IDEMPOTENCY_KEY_HEADER` | `Idempotency-Key` | Header making a render safe to retry |
+//! | `JOB_ID_HEADER` | `X-Job-Id` | Header naming a render so it can be cancelled |
+//! | `REQUEST_ID_HEADER` | `X-Request-Id` | Header naming the request ID shown on fallback PDFs |
+//! | `QPDF_PATH_ENV` | `QPDF_PATH` | Environment variable naming the qpdf binary used by `linearize` and `split_pages` |
+//! | `GHOSTSCRIPT_PATH_ENV` | `GHOSTSCRIPT_PATH` | Environment variable naming the Ghostscript binary used by `pdf_a` |
+//! | `TRACE_ID_HEADER` | `X-Trace-Id` | Response header naming a render's saved performance trace |
+//! | `PDF_PAGES_HEADER` | `X-Pdf-Pages` | Response header with the PDF's page count |
+//! | `PDF_PAGE_SIZE_HEADER` | `X-Pdf-Page-Size` | Response header with the first page's size in points |
+//! | `DEFAULT_LOCALE` | `en` | Error message language when `Accept-Language` names no supported one |
+//! | `SUPPORTED_LOCALES` | `en`, `de`, `es`, `fr` | Languages error messages are translated into |
 //!
 //! # Usage Patterns
 //!
@@ -176,11 +242,11 @@
 //! ```rust,ignore
 //! use html2pdf_api::service::{
 //!     generate_pdf_from_url, generate_pdf_from_html,
-//!     PdfFromUrlRequest, PdfFromHtmlRequest,
+//!     Bytes, PdfFromUrlRequest, PdfFromHtmlRequest, PdfServiceError,
 //! };
 //! use std::sync::Mutex;
 //!
-//! fn batch_convert(pool: &Mutex<BrowserPool>, urls: Vec<String>) -> Vec<Result<Vec<u8>, PdfServiceError>> {
+//! fn batch_convert(pool: &Mutex<BrowserPool>, urls: Vec<String>) -> Vec<Result<Bytes, PdfServiceError>> {
 //!     urls.into_iter()
 //!         .map(|url| {
 //!             let request = PdfFromUrlRequest {
@@ -228,6 +294,9 @@
 //!
 //! // ✅ Synchronous context: Call directly
 //! let result = generate_pdf_from_url(&pool, &request);
+//!
+//! // ✅ Tokio: Use the async variant (no thread held during waits)
+//! let result = generate_pdf_from_url_async(&pool, &request).await;
 //! ```
 //!
 //! ## Incorrect Usage
@@ -316,36 +385,173 @@
 //! - [`crate::integrations`] - Framework-specific handlers
 //! - [`crate::prelude`] - Convenient re-exports
 
+mod assets;
+mod cache;
+mod coalesce;
+mod debug;
+mod fallback;
+mod idempotency;
+mod incremental;
+mod inspect;
+mod job_store;
+mod jobs;
+mod linearize;
+mod merge;
+mod messages;
+mod metadata;
 mod pdf;
+mod pdfa;
+mod range;
+mod ready;
+#[cfg(feature = "redis-cache")]
+mod redis_cache;
+#[cfg(any(feature = "redis-cache", feature = "redis-jobs"))]
+mod redis_conn;
+#[cfg(feature = "redis-jobs")]
+mod redis_jobs;
+mod schedule;
+#[cfg(feature = "pdf-signing")]
+mod sign;
+mod split;
+mod stream;
+mod trace;
 mod types;
+mod watermark;
 
 // ============================================================================
 // Re-exports: Types
 // ============================================================================
 
+pub use cache::MemoryPdfCache;
+pub use cache::PdfCache;
+pub use fallback::FallbackPdf;
+pub use job_store::JobState;
+pub use job_store::JobStore;
+pub use job_store::MemoryJobStore;
+pub use job_store::QueuedJob;
+pub use range::ByteRange;
+#[cfg(feature = "redis-cache")]
+pub use redis_cache::RedisPdfCache;
+#[cfg(feature = "redis-jobs")]
+pub use redis_jobs::RedisJobStore;
+pub use schedule::CronSchedule;
+pub use schedule::ScheduledRender;
+#[cfg(feature = "pdf-signing")]
+pub use sign::PdfSigner;
+#[cfg(any(
+    feature = "actix-integration",
+    feature = "rocket-integration",
+    feature = "axum-integration"
+))]
+pub use stream::PdfChunks;
+pub use stream::PdfStream;
+pub use types::AuthSpec;
+pub use types::BrowserDebugResponse;
+pub use types::BrowserStateResponse;
+pub use types::BrowserStatsResponse;
+pub use types::ColorScheme;
+pub use types::ConsoleMessage;
+pub use types::CookieSpec;
 pub use types::ErrorResponse;
+pub use types::FailedRequest;
+pub use types::Geolocation;
+pub use types::HealthCheckResponse;
 pub use types::HealthResponse;
+pub use types::ImageFormat;
+pub use types::JobRequest;
+pub use types::JobResponse;
+pub use types::JobStatus;
+pub use types::KeepAliveResponse;
+pub use types::MediaType;
+pub use types::MockResponse;
+pub use types::Outputs;
+pub use types::PageAction;
+pub use types::PageInfo;
+pub use types::PaperFormat;
+pub use types::PdfFromAssetsRequest;
 pub use types::PdfFromHtmlRequest;
 pub use types::PdfFromUrlRequest;
+pub use types::PdfMergeRequest;
+pub use types::PdfMetadata;
 pub use types::PdfResponse;
 pub use types::PdfServiceError;
+pub use types::PoolDebugResponse;
+pub use types::PoolDetailedStatsResponse;
 pub use types::PoolStatsResponse;
+pub use types::ReadyOutcome;
+pub use types::RenderJob;
+pub use types::RenderReport;
+pub use types::ResponseBody;
+pub use types::TimelineEvent;
+pub use types::UsageResponse;
+pub use types::UsageTotals;
+pub use types::Watermark;
+pub use types::WebStorage;
+
+/// Re-exported so callers can name [`PdfResponse::data`] without adding
+/// `bytes` as a direct dependency.
+pub use bytes::Bytes;
 
 // ============================================================================
 // Re-exports: Functions
 // ============================================================================
 
+pub use cache::render_cached;
+pub use coalesce::render_coalesced;
+pub use idempotency::render_idempotent;
+pub use jobs::cancel_job;
+pub use jobs::job_result;
+pub use jobs::job_status;
+pub use jobs::render_cancellable;
+pub use jobs::spawn_job_worker;
+pub use jobs::submit_job;
+pub use merge::merge_pdfs;
+pub use messages::error_message;
+pub use messages::negotiate_locale;
+pub use pdf::generate_pdf_from_assets;
 pub use pdf::generate_pdf_from_html;
+pub use pdf::generate_pdf_from_html_async;
 pub use pdf::generate_pdf_from_url;
+pub use pdf::generate_pdf_from_url_async;
+pub use pdf::get_pool_debug;
+pub use pdf::get_pool_detailed_stats;
 pub use pdf::get_pool_stats;
+pub use pdf::get_usage;
 pub use pdf::is_pool_ready;
+pub use pdf::prewarm_url;
+pub use pdf::render_metered;
+pub use pdf::render_parallel;
+pub use pdf::render_parallel_with;
+pub use pdf::stream_metered;
+pub use pdf::stream_pdf_from_html;
+pub use pdf::stream_pdf_from_url;
+pub use schedule::spawn_scheduler;
+
+pub(crate) use cache::ResponseCache;
+pub(crate) use coalesce::InflightRenders;
+pub(crate) use idempotency::IdempotencyCache;
+pub(crate) use jobs::JobRegistry;
 
 // ============================================================================
 // Re-exports: Constants
 // ============================================================================
 
+pub use fallback::REQUEST_ID_HEADER;
+pub use idempotency::IDEMPOTENCY_KEY_HEADER;
+pub use inspect::PDF_PAGE_SIZE_HEADER;
+pub use inspect::PDF_PAGES_HEADER;
+pub use jobs::JOB_ID_HEADER;
+pub use linearize::QPDF_PATH_ENV;
+pub use messages::DEFAULT_LOCALE;
+pub use messages::SUPPORTED_LOCALES;
+pub use pdf::API_KEY_HEADER;
+pub use pdf::DEFAULT_THUMBNAIL_WIDTH;
 pub use pdf::DEFAULT_TIMEOUT_SECS;
+pub use pdf::DEFAULT_WAIT_EXPRESSION;
 pub use pdf::DEFAULT_WAIT_SECS;
+pub use pdfa::GHOSTSCRIPT_PATH_ENV;
+pub use ready::READY_FUNCTION;
+pub use trace::TRACE_ID_HEADER;
 
 // ============================================================================
 // Module-level tests
@@ -368,11 +574,14 @@ mod tests {
             available: 0,
             active: 0,
             total: 0,
+            cache_hits: 0,
+            cache_misses: 0,
         };
         let _: HealthResponse = HealthResponse::default();
         let _: ErrorResponse = ErrorResponse {
             error: "test".to_string(),
             code: "TEST".to_string(),
+            message: "test".to_string(),
         };
 
         // Error types
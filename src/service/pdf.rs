@@ -134,7 +134,8 @@
 //! | Page navigation | 100ms - 10s | Depends on target page |
 //! | JavaScript wait | 0 - 15s | Configurable via `waitsecs` |
 //! | PDF generation | 100ms - 5s | Depends on page complexity |
-//! | Tab cleanup | < 100ms | Best effort, non-blocking |
+//! | Tab creation | ~100ms | Skipped for a browser's first render and when `reuse_tabs` is enabled |
+//! | Tab cleanup | < 100ms | Best effort; `about:blank` reset when reusing tabs |
 //!
 //! # Error Handling
 //!
@@ -144,13 +145,47 @@
 //!
 //! [`PdfServiceError`]: crate::service::PdfServiceError
 
+use base64::Engine;
+use headless_chrome::browser::tab::RequestPausedDecision;
+use headless_chrome::protocol::cdp::Fetch::events::RequestPausedEvent;
+use headless_chrome::protocol::cdp::Fetch::{
+    ContinueRequest, FailRequest, FulfillRequest, HeaderEntry, RequestPattern, RequestStage,
+};
+use headless_chrome::protocol::cdp::Network::{ErrorReason, ResourceType};
+use headless_chrome::protocol::cdp::types::Event;
+use headless_chrome::protocol::cdp::{
+    Browser, CSS, DOM, Emulation, IO, Network, Page, Runtime, Security, Storage,
+};
 use headless_chrome::types::PrintToPdfOptions;
-use std::sync::Mutex;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 
+use crate::SharedBrowserPool;
+use crate::cancel::CancelHandle;
+use crate::error::BrowserPoolError;
 use crate::handle::BrowserHandle;
 use crate::pool::BrowserPool;
+use crate::queue::Priority;
+use crate::service::assets::{ASSETS_ORIGIN, INDEX_PATH, asset_url, content_type};
+use crate::service::debug::DebugRecorder;
+use crate::service::linearize::linearize_pdf;
+use crate::service::metadata::{DocumentInfo, stamp_metadata};
+use crate::service::pdfa::convert_to_pdf_a;
+use crate::service::ready::ReadySignal;
+use crate::service::split::split_pdf;
+use crate::service::stream::PdfStream;
+use crate::service::trace::{TraceRecorder, check_trace_dir};
 use crate::service::types::*;
+use crate::service::watermark::watermark_css;
+use crate::traits::PdfPostProcessor;
+use crate::url_policy::UrlPolicy;
+use crate::usage::ANONYMOUS_KEY;
 
 // ============================================================================
 // Constants
@@ -220,6 +255,106 @@ pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
 /// | Real-time data loading | 10-20 seconds |
 pub const DEFAULT_WAIT_SECS: u64 = 5;
 
+/// Request header carrying the API key that renders are accounted to.
+///
+/// Read by the pre-built handlers and passed to [`render_metered`] and
+/// [`get_usage`]. Requests without it share the
+/// [`ANONYMOUS_KEY`](crate::usage::ANONYMOUS_KEY) account.
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Default width of first-page thumbnails in pixels.
+///
+/// Used when a request sets `thumbnail` without `thumbnail_width`.
+pub const DEFAULT_THUMBNAIL_WIDTH: u32 = 256;
+
+/// Readiness expression used when a request sets no `wait_for_expression`.
+///
+/// Pages signal that they are ready to print by setting this flag.
+pub const DEFAULT_WAIT_EXPRESSION: &str = "window.isPageDone === true";
+
+/// Longest `wait_for_expression` accepted, in bytes.
+const MAX_WAIT_EXPRESSION_LEN: usize = 1024;
+
+/// Writes web storage entries on a new document of the requested origin.
+///
+/// `__ORIGIN__`, `__LOCAL__`, and `__SESSION__` are replaced with JSON by
+/// `StorageSeed::new`. Errors (storage disabled, quota) are swallowed so
+/// the page still loads.
+const STORAGE_SCRIPT: &str = r#"(() => {
+    if (location.origin !== __ORIGIN__) return;
+    const write = (storage, entries) => {
+        for (const [key, value] of Object.entries(entries)) storage.setItem(key, value);
+    };
+    try {
+        write(window.localStorage, __LOCAL__);
+        write(window.sessionStorage, __SESSION__);
+    } catch (e) {}
+})();"#;
+
+/// `Content-Type` of a `POST` navigation's body when the request names none.
+const DEFAULT_POST_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Longest `timezone` accepted, in bytes. IANA names are at most ~30.
+const MAX_TIMEZONE_LEN: usize = 64;
+
+/// Most extra headers a request may send.
+const MAX_HEADERS: usize = 50;
+
+/// Most cookies a request may set.
+const MAX_COOKIES: usize = 50;
+
+/// Largest paper width or height a request may ask for, in inches.
+const MAX_PAPER_INCHES: f64 = 100.0;
+
+/// Longest `header_template` or `footer_template` accepted, in bytes.
+const MAX_TEMPLATE_LEN: usize = 64 * 1024;
+
+/// Printed in place of a template the request leaves out, so Chrome's
+/// default date and title don't appear opposite the one it set.
+const EMPTY_TEMPLATE: &str = "<span></span>";
+
+/// Most bytes of a printed PDF read from Chrome at once.
+///
+/// Bounds how much of a streamed PDF the service holds; see
+/// [`stream_pdf_from_url`].
+const PDF_CHUNK_SIZE: u32 = 1024 * 1024;
+
+/// Most `actions` steps one request may run.
+const MAX_ACTIONS: usize = 20;
+
+/// How long an `actions` step waits for its selector to match, in seconds.
+const ACTION_TIMEOUT_SECS: u64 = 10;
+
+/// Longest `inject_css` or `inject_js` accepted, in bytes.
+const MAX_INJECT_LEN: usize = 64 * 1024;
+
+/// Accepted range of `viewport_width` and `viewport_height` values, in
+/// CSS pixels.
+const VIEWPORT_RANGE: std::ops::RangeInclusive<u32> = 1..=16384;
+
+/// Largest `device_scale_factor` accepted.
+const MAX_DEVICE_SCALE_FACTOR: f64 = 4.0;
+
+/// Longest `user_agent` accepted, in bytes.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// Accepted range of `thumbnail_width` values, in pixels.
+const THUMBNAIL_WIDTH_RANGE: std::ops::RangeInclusive<u32> = 16..=2048;
+
+/// Accepted range of `thumbnail_quality` values.
+const THUMBNAIL_QUALITY_RANGE: std::ops::RangeInclusive<u32> = 1..=100;
+
+/// Serializes the rendered DOM for the `html` output.
+const CAPTURE_HTML_SCRIPT: &str = "(document.doctype ? new XMLSerializer().serializeToString(document.doctype) + '\\n' : '') + document.documentElement.outerHTML";
+
+/// Chrome's default PDF paper size (US Letter) in CSS pixels at 96 DPI,
+/// portrait. Thumbnails are laid out at this size unless the pool's
+/// print options set a paper size.
+const PAGE_SIZE_PX: (u32, u32) = (816, 1056);
+
+/// CSS pixels per inch, for converting print option paper sizes.
+const CSS_PX_PER_INCH: f64 = 96.0;
+
 /// Polling interval for JavaScript completion check in milliseconds.
 ///
 /// When waiting for JavaScript to complete, the service checks for
@@ -235,6 +370,68 @@ pub const DEFAULT_WAIT_SECS: u64 = 5;
 /// `200` milliseconds - provides good responsiveness without excessive polling.
 const JS_POLL_INTERVAL_MS: u64 = 200;
 
+/// Maximum time to wait for HTML content to finish loading, in seconds.
+///
+/// After `Page.setDocumentContent`, subresources (stylesheets, images,
+/// fonts) load asynchronously. The service waits until
+/// `document.readyState` is `"complete"` or this limit is reached, which
+/// matches the navigation timeout applied to URL requests.
+const HTML_LOAD_TIMEOUT_SECS: u64 = 20;
+
+// The HTML load timeout must leave room for JS wait and printing
+const _: () = assert!(HTML_LOAD_TIMEOUT_SECS > 0 && HTML_LOAD_TIMEOUT_SECS < DEFAULT_TIMEOUT_SECS);
+
+/// Chrome's own redirect limit; lower `max_redirects` values are enforced
+/// by the service.
+const CHROME_MAX_REDIRECTS: u32 = 20;
+
+/// Resource type names accepted in `block_resources`.
+///
+/// The main document itself can never be blocked.
+pub const BLOCKABLE_RESOURCES: &[&str] = &[
+    "image",
+    "media",
+    "font",
+    "stylesheet",
+    "script",
+    "xhr",
+    "fetch",
+    "websocket",
+    "trackers",
+];
+
+/// Analytics, advertising, and session-recording domains blocked by the
+/// `trackers` entry of `block_resources`, with their subdomains.
+const TRACKER_DOMAINS: &[&str] = &[
+    "google-analytics.com",
+    "googletagmanager.com",
+    "googlesyndication.com",
+    "googleadservices.com",
+    "doubleclick.net",
+    "connect.facebook.net",
+    "analytics.tiktok.com",
+    "bat.bing.com",
+    "clarity.ms",
+    "hotjar.com",
+    "fullstory.com",
+    "mixpanel.com",
+    "segment.com",
+    "segment.io",
+    "amplitude.com",
+    "heap.io",
+    "scorecardresearch.com",
+    "quantserve.com",
+    "hs-analytics.net",
+    "plausible.io",
+];
+
+/// Maximum time the async functions wait for a navigation to settle, in
+/// seconds.
+///
+/// Matches the default tab timeout that `wait_until_navigated` applies to
+/// the blocking functions.
+const NAVIGATION_TIMEOUT_SECS: u64 = 20;
+
 // ============================================================================
 // Public API - Core PDF Generation Functions
 // ============================================================================
@@ -311,6 +508,7 @@ const JS_POLL_INTERVAL_MS: u64 = 200;
 ///     waitsecs: Some(10),         // Complex charts
 ///     download: Some(true),       // Force download
 ///     print_background: Some(true),
+///     ..Default::default()
 /// };
 ///
 /// let response = generate_pdf_from_url(&pool, &request)?;
@@ -363,8 +561,10 @@ pub fn generate_pdf_from_url(
     pool: &Mutex<BrowserPool>,
     request: &PdfFromUrlRequest,
 ) -> Result<PdfResponse, PdfServiceError> {
-    // Validate URL before acquiring browser
+    // Validate URL and options before acquiring browser
     let url = validate_url(&request.url)?;
+    check_url_policy(pool, &url)?;
+    let options = RenderOptions::try_from(request)?;
 
     log::debug!(
         "Generating PDF from URL: {} (landscape={}, wait={}s)",
@@ -374,34 +574,24 @@ pub fn generate_pdf_from_url(
     );
 
     // Acquire browser from pool (lock held briefly)
-    let browser = acquire_browser(pool)?;
+    let browser = acquire_browser(pool, options.priority, options.cancel.as_ref())?;
 
     // Generate PDF (lock released, browser returned via RAII on completion/error)
-    let pdf_data = generate_pdf_internal(
-        &browser,
-        &url,
-        request.wait_duration(),
-        request.is_landscape(),
-        request.print_background(),
-    )?;
+    let rendered = generate_pdf_internal(&browser, PageSource::Url(&url), &options)?;
 
     log::info!(
         "✅ PDF generated successfully from URL: {} ({} bytes)",
         url,
-        pdf_data.len()
+        rendered.pdf.len()
     );
 
-    Ok(PdfResponse::new(
-        pdf_data,
-        request.filename_or_default(),
-        request.is_download(),
-    ))
+    Ok(rendered.into_response(request.filename_or_default(), request.is_download()))
 }
 
 /// Generate a PDF from HTML content.
 ///
-/// Loads the provided HTML content into a browser tab using a data URL,
-/// waits for any JavaScript execution, and generates a PDF.
+/// Loads the provided HTML content into a blank browser tab, waits for any
+/// JavaScript execution, and generates a PDF.
 ///
 /// # Thread Safety
 ///
@@ -415,14 +605,15 @@ pub fn generate_pdf_from_url(
 ///
 /// # How It Works
 ///
-/// The HTML content is converted to a data URL:
+/// The tab is navigated to `about:blank` and the HTML is written into its
+/// main frame with the CDP `Page.setDocumentContent` command:
 ///
 /// ```text
-/// data:text/html;charset=utf-8,<encoded-html-content>
+/// about:blank ──→ Page.setDocumentContent(html) ──→ document.readyState == "complete"
 /// ```
 ///
-/// This allows loading HTML directly without a web server. The browser
-/// renders the HTML as if it were loaded from a regular URL.
+/// This allows loading HTML directly without a web server, with no
+/// percent-encoding step and no data URL size limit.
 ///
 /// # Arguments
 ///
@@ -439,25 +630,33 @@ pub fn generate_pdf_from_url(
 /// | Error | Cause | Resolution |
 /// |-------|-------|------------|
 /// | [`EmptyHtml`] | HTML content is empty/whitespace | Provide HTML content |
+/// | [`InvalidUrl`] | `base_url` is not http(s) or the URL policy forbids it | Fix `base_url` |
 /// | [`PoolLockFailed`] | Mutex poisoned | Restart service |
 /// | [`BrowserUnavailable`] | Pool exhausted | Retry or increase pool size |
-/// | [`NavigationFailed`] | HTML parsing issue | Check HTML validity |
+/// | [`NavigationFailed`] | Content could not be loaded | Check HTML validity |
+/// | [`NavigationTimeout`] | Subresources never finished loading | Check external URLs |
 /// | [`PdfGenerationFailed`] | Rendering issue | Simplify HTML |
 ///
 /// [`EmptyHtml`]: PdfServiceError::EmptyHtml
+/// [`InvalidUrl`]: PdfServiceError::InvalidUrl
 /// [`PoolLockFailed`]: PdfServiceError::PoolLockFailed
 /// [`BrowserUnavailable`]: PdfServiceError::BrowserUnavailable
 /// [`NavigationFailed`]: PdfServiceError::NavigationFailed
+/// [`NavigationTimeout`]: PdfServiceError::NavigationTimeout
 /// [`PdfGenerationFailed`]: PdfServiceError::PdfGenerationFailed
 ///
 /// # Limitations
 ///
 /// ## External Resources
 ///
-/// Since HTML is loaded via data URL, relative URLs don't work:
+/// Without [`base_url`](PdfFromHtmlRequest::base_url), the document is
+/// loaded at `about:blank`, so relative URLs don't resolve. With it, the
+/// document is served at that URL and they resolve against it (checked
+/// against the pool's URL policy like a URL render); to upload the assets
+/// themselves, see [`generate_pdf_from_assets`].
 ///
 /// ```html
-/// <!-- ❌ Won't work - relative URL -->
+/// <!-- ❌ Won't work without base_url - relative URL -->
 /// <img src="/images/logo.png">
 ///
 /// <!-- ✅ Works - absolute URL -->
@@ -469,11 +668,9 @@ pub fn generate_pdf_from_url(
 ///
 /// ## Size Limits
 ///
-/// Data URLs have browser-specific size limits. For very large HTML documents
-/// (> 1MB), consider:
-/// - Hosting the HTML on a temporary server
-/// - Using [`generate_pdf_from_url`] instead
-/// - Splitting into multiple PDFs
+/// There is no URL length limit, but the whole document is sent over the
+/// DevTools connection in one message. Multi-megabyte documents work; for
+/// very large ones prefer [`generate_pdf_from_url`] or splitting the PDF.
 ///
 /// # Examples
 ///
@@ -579,6 +776,10 @@ pub fn generate_pdf_from_html(
         log::warn!("Empty HTML content provided");
         return Err(PdfServiceError::EmptyHtml);
     }
+    let options = RenderOptions::try_from(request)?;
+    if let Some(base_url) = &options.base_url {
+        check_url_policy(pool, base_url)?;
+    }
 
     log::debug!(
         "Generating PDF from HTML ({} bytes, landscape={}, wait={}s)",
@@ -588,43 +789,330 @@ pub fn generate_pdf_from_html(
     );
 
     // Acquire browser from pool
-    let browser = acquire_browser(pool)?;
+    let browser = acquire_browser(pool, options.priority, options.cancel.as_ref())?;
+
+    // Generate PDF (HTML is written straight into the tab's document)
+    let rendered = generate_pdf_internal(&browser, PageSource::Html(&request.html), &options)?;
 
-    // Convert HTML to data URL
-    // Using percent-encoding to handle special characters
-    let data_url = format!(
-        "data:text/html;charset=utf-8,{}",
-        urlencoding::encode(&request.html)
+    log::info!(
+        "✅ PDF generated successfully from HTML ({} bytes input → {} bytes output)",
+        request.html.len(),
+        rendered.pdf.len()
     );
 
-    log::trace!("Data URL length: {} bytes", data_url.len());
+    Ok(rendered.into_response(request.filename_or_default(), request.is_download()))
+}
 
-    // Generate PDF
-    let pdf_data = generate_pdf_internal(
-        &browser,
-        &data_url,
-        request.wait_duration(),
-        request.is_landscape(),
-        request.print_background(),
-    )?;
+/// Generate a PDF from uploaded HTML and the files it refers to.
+///
+/// Like [`generate_pdf_from_html`], but the document is loaded from
+/// `https://assets.html2pdf.invalid/index.html`, a private origin whose
+/// requests are answered from `request.assets`, so relative URLs for
+/// images, stylesheets, and fonts resolve against the upload. URLs on the
+/// origin that were not uploaded get a 404. With `offline`, only the
+/// uploaded files load.
+///
+/// # Thread Safety
+///
+/// Same as [`generate_pdf_from_url`].
+///
+/// # Blocking Behavior
+///
+/// **This function blocks the calling thread.** See [`generate_pdf_from_url`].
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `request` - The document, its options, and its assets; usually parsed
+///   with [`PdfFromAssetsRequest::from_multipart`]
+///
+/// # Errors
+///
+/// Same as [`generate_pdf_from_html`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{generate_pdf_from_assets, PdfFromAssetsRequest};
+///
+/// let request = PdfFromAssetsRequest::from_multipart(&content_type, body)?;
+/// let response = generate_pdf_from_assets(&pool, &request)?;
+/// ```
+pub fn generate_pdf_from_assets(
+    pool: &Mutex<BrowserPool>,
+    request: &PdfFromAssetsRequest,
+) -> Result<PdfResponse, PdfServiceError> {
+    let document = &request.document;
+    if document.html.trim().is_empty() {
+        log::warn!("Empty HTML content provided");
+        return Err(PdfServiceError::EmptyHtml);
+    }
+    let mut options = RenderOptions::try_from(document)?;
+    options.mocks = asset_mocks(request, &options.mocks);
+    options.isolated = options.offline;
+
+    log::debug!(
+        "Generating PDF from HTML with {} assets ({} bytes, landscape={}, wait={}s)",
+        request.assets.len(),
+        document.html.len(),
+        document.is_landscape(),
+        document.wait_duration().as_secs()
+    );
+
+    let browser = acquire_browser(pool, options.priority, options.cancel.as_ref())?;
+
+    let index_url = asset_url(INDEX_PATH);
+    let rendered = generate_pdf_internal(&browser, PageSource::Url(&index_url), &options)?;
 
     log::info!(
-        "✅ PDF generated successfully from HTML ({} bytes input → {} bytes output)",
-        request.html.len(),
-        pdf_data.len()
+        "✅ PDF generated successfully from HTML with {} assets ({} bytes output)",
+        request.assets.len(),
+        rendered.pdf.len()
     );
 
-    Ok(PdfResponse::new(
-        pdf_data,
-        request.filename_or_default(),
-        request.is_download(),
-    ))
+    Ok(rendered.into_response(document.filename_or_default(), document.is_download()))
+}
+
+/// Render many jobs concurrently across the pool.
+///
+/// Fans `jobs` out over at most `max_concurrency` worker threads, each of
+/// which checks out its own browser per job, and returns one result per
+/// job **in input order**. A failing job does not affect the others.
+///
+/// # Thread Safety
+///
+/// Workers are scoped threads that share `pool`; the pool lock is only
+/// held while checking out a browser, exactly as in
+/// [`generate_pdf_from_url`].
+///
+/// # Blocking Behavior
+///
+/// **This function blocks the calling thread** until every job finished.
+/// In async code, call it from `spawn_blocking`.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `jobs` - URL and/or HTML conversions. See [`RenderJob`].
+/// * `max_concurrency` - Maximum jobs rendered at once (clamped to `1..=jobs.len()`)
+///
+/// # Concurrency and Pool Size
+///
+/// The pool creates a browser whenever none is idle, so `max_concurrency`
+/// is what bounds the number of simultaneous browsers. Keep it at or below
+/// [`max_pool_size`](crate::BrowserPoolConfig::max_pool_size) so browsers
+/// are returned to the pool instead of being dropped afterward.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::{render_parallel, PdfFromUrlRequest, RenderJob};
+///
+/// let jobs: Vec<RenderJob> = ["https://example.com", "https://example.org"]
+///     .into_iter()
+///     .map(|url| PdfFromUrlRequest { url: url.to_string(), ..Default::default() }.into())
+///     .collect();
+///
+/// for (job, result) in jobs.iter().zip(render_parallel(&pool, &jobs, 4)) {
+///     match result {
+///         Ok(pdf) => println!("{:?}: {} bytes", job, pdf.size()),
+///         Err(e) => eprintln!("{:?}: {}", job, e),
+///     }
+/// }
+/// ```
+pub fn render_parallel(
+    pool: &Mutex<BrowserPool>,
+    jobs: &[RenderJob],
+    max_concurrency: usize,
+) -> Vec<Result<PdfResponse, PdfServiceError>> {
+    let slots: Vec<OnceLock<Result<PdfResponse, PdfServiceError>>> =
+        jobs.iter().map(|_| OnceLock::new()).collect();
+
+    render_parallel_with(pool, jobs, max_concurrency, |index, result, _elapsed| {
+        let _ = slots[index].set(result);
+    });
+
+    slots
+        .into_iter()
+        .map(|slot| {
+            slot.into_inner()
+                .expect("render_parallel_with reports every job")
+        })
+        .collect()
+}
+
+/// Render many jobs concurrently, handing each result over as it finishes.
+///
+/// Works like [`render_parallel`], but instead of collecting the results
+/// calls `on_done(index, result, elapsed)` on the worker thread as soon as
+/// job `index` finishes, with the time its render took. Results arrive in
+/// completion order, so a caller can write each one out while the rest
+/// are still rendering.
+///
+/// A job whose worker panicked is reported once all workers stopped, as a
+/// [`PdfServiceError::Internal`] with a zero duration.
+///
+/// # Blocking Behavior
+///
+/// **This function blocks the calling thread** until every job finished.
+/// In async code, call it from `spawn_blocking`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::render_parallel_with;
+///
+/// render_parallel_with(&pool, &jobs, 4, |index, result, elapsed| {
+///     match result {
+///         Ok(pdf) => println!("#{}: {} bytes in {:?}", index, pdf.size(), elapsed),
+///         Err(e) => eprintln!("#{}: {}", index, e),
+///     }
+/// });
+/// ```
+pub fn render_parallel_with<F>(
+    pool: &Mutex<BrowserPool>,
+    jobs: &[RenderJob],
+    max_concurrency: usize,
+    on_done: F,
+) where
+    F: Fn(usize, Result<PdfResponse, PdfServiceError>, Duration) + Sync,
+{
+    if jobs.is_empty() {
+        return;
+    }
+
+    let workers = max_concurrency.clamp(1, jobs.len());
+    let next = AtomicUsize::new(0);
+    let reported: Vec<AtomicBool> = jobs.iter().map(|_| AtomicBool::new(false)).collect();
+
+    log::debug!(
+        "Rendering {} jobs with {} parallel workers",
+        jobs.len(),
+        workers
+    );
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    // Work stealing: each worker claims the next unclaimed job
+                    while let Some((index, job)) = {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        jobs.get(index).map(|job| (index, job))
+                    } {
+                        let start = Instant::now();
+                        let result = render_job(pool, job);
+                        reported[index].store(true, Ordering::Relaxed);
+                        on_done(index, result, start.elapsed());
+                    }
+                })
+            })
+            .collect();
+
+        // Join explicitly so a panicking worker doesn't propagate the panic;
+        // its unfinished job is reported as an Internal error below.
+        for handle in handles {
+            if handle.join().is_err() {
+                log::error!("❌ Parallel render worker panicked");
+            }
+        }
+    });
+
+    for (index, reported) in reported.iter().enumerate() {
+        if !reported.load(Ordering::Relaxed) {
+            on_done(
+                index,
+                Err(PdfServiceError::Internal(
+                    "render worker panicked before finishing this job".to_string(),
+                )),
+                Duration::ZERO,
+            );
+        }
+    }
+}
+
+/// Dispatch a single [`RenderJob`] to the matching service function.
+pub(crate) fn render_job(
+    pool: &Mutex<BrowserPool>,
+    job: &RenderJob,
+) -> Result<PdfResponse, PdfServiceError> {
+    match job {
+        RenderJob::Url(request) => generate_pdf_from_url(pool, request),
+        RenderJob::Html(request) => generate_pdf_from_html(pool, request),
+    }
+}
+
+/// Load a URL in a pooled browser without rendering it.
+///
+/// Navigates one browser to `url` and waits for the load to finish,
+/// populating that browser's HTTP cache and warming the JavaScript JIT so a
+/// later [`generate_pdf_from_url`] for the same page (or pages sharing its
+/// assets) renders faster. Call it right before a known burst of requests,
+/// e.g. when a scheduled report run starts.
+///
+/// Each browser has its own cache, so this only helps the browser that was
+/// checked out. To keep every browser warm, set
+/// [`prefetch_urls`](crate::BrowserPoolConfig::prefetch_urls) instead.
+///
+/// # Blocking Behavior
+///
+/// **This function blocks the calling thread** while the page loads.
+/// In async code, call it from `spawn_blocking`.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `url` - Absolute URL to load
+///
+/// # Errors
+///
+/// Returns the same URL validation, pool, and navigation errors as
+/// [`generate_pdf_from_url`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use html2pdf_api::service::prewarm_url;
+///
+/// tokio::task::spawn_blocking(move || {
+///     if let Err(e) = prewarm_url(&pool, "https://reports.internal/daily") {
+///         log::warn!("Prewarm failed: {}", e);
+///     }
+/// });
+/// ```
+pub fn prewarm_url(pool: &Mutex<BrowserPool>, url: &str) -> Result<(), PdfServiceError> {
+    let url = validate_url(url)?;
+    check_url_policy(pool, &url)?;
+    let start_time = Instant::now();
+
+    let browser = acquire_browser(pool, Priority::Low, None)?;
+    let url = resolve_file_url(&url, &browser.pool_config().allowed_file_dirs)?;
+    let tab = browser.checkout_tab().map_err(|e| {
+        log::error!("❌ {}", e);
+        PdfServiceError::TabCreationFailed(e.to_string())
+    })?;
+
+    let result = navigate(&tab, &url);
+    match result {
+        Ok(()) => browser.release_tab(tab),
+        Err(_) => browser.discard_tab(tab),
+    }
+
+    log::debug!(
+        "Prewarmed {} in browser {} ({:?})",
+        truncate_url(&url, 100),
+        browser.id(),
+        start_time.elapsed()
+    );
+
+    result
 }
 
 /// Get current browser pool statistics.
 ///
 /// Returns real-time metrics about the browser pool state including
-/// available browsers, active browsers, and total count.
+/// available browsers, active browsers, and total count, plus the response
+/// cache's hits and misses (see [`render_cached`](crate::service::render_cached)).
 ///
 /// # Thread Safety
 ///
@@ -704,27 +1192,106 @@ pub fn get_pool_stats(pool: &Mutex<BrowserPool>) -> Result<PoolStatsResponse, Pd
     })?;
 
     let stats = pool_guard.stats();
+    let cache = pool_guard.response_cache();
 
     Ok(PoolStatsResponse {
         available: stats.available,
         active: stats.active,
         total: stats.total,
+        cache_hits: cache.hits(),
+        cache_misses: cache.misses(),
     })
 }
 
+/// Get a detailed diagnostic snapshot of the browser pool.
+///
+/// Returns everything support usually asks for when the pool misbehaves:
+/// each browser's age, uses, memory, open tabs, and last health check,
+/// plus waiting requests, replacement tasks in flight, and keep-alive
+/// timing. See [`BrowserPool::debug_snapshot()`].
+///
+/// # Blocking Behavior
+///
+/// Holds the pool lock while reading each browser's process memory from
+/// `/proc`, typically a few milliseconds. Fine for an occasional debug
+/// request; use [`get_pool_stats`] for frequent polling.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+///
+/// # Returns
+///
+/// * `Ok(PoolDebugResponse)` - The diagnostic snapshot
+/// * `Err(PdfServiceError::PoolLockFailed)` - If mutex is poisoned
+pub fn get_pool_debug(pool: &Mutex<BrowserPool>) -> Result<PoolDebugResponse, PdfServiceError> {
+    let pool_guard = pool.lock().map_err(|e| {
+        log::error!("Failed to lock browser pool for debug snapshot: {}", e);
+        PdfServiceError::PoolLockFailed(e.to_string())
+    })?;
+
+    let snapshot = pool_guard.debug_snapshot();
+    let waiting = pool_guard.inflight().waiting();
+
+    Ok(PoolDebugResponse::new(snapshot, waiting))
+}
+
+/// Get per-browser statistics and the pool's lifetime counters.
+///
+/// Reports each browser's age, uses, time since its last ping, consecutive
+/// ping failures, memory, and idle/busy state, plus how many browsers have
+/// been created, retired, and removed as failed. See
+/// [`BrowserPool::detailed_stats()`].
+///
+/// # Blocking Behavior
+///
+/// Like [`get_pool_debug`], holds the pool lock while reading each
+/// browser's process memory. Scrape it every few seconds at most; use
+/// [`get_pool_stats`] for frequent polling.
+///
+/// # Arguments
+///
+/// * `pool` - Reference to the mutex-wrapped browser pool
+///
+/// # Returns
+///
+/// * `Ok(PoolDetailedStatsResponse)` - The statistics
+/// * `Err(PdfServiceError::PoolLockFailed)` - If mutex is poisoned
+pub fn get_pool_detailed_stats(
+    pool: &Mutex<BrowserPool>,
+) -> Result<PoolDetailedStatsResponse, PdfServiceError> {
+    let pool_guard = pool.lock().map_err(|e| {
+        log::error!("Failed to lock browser pool for detailed stats: {}", e);
+        PdfServiceError::PoolLockFailed(e.to_string())
+    })?;
+
+    Ok(pool_guard.detailed_stats().into())
+}
+
 /// Check if the browser pool is ready to handle requests.
 ///
-/// Returns `true` if the pool has available browsers or capacity to create
-/// new ones. This is useful for readiness probes in container orchestration.
+/// Returns `true` if the pool meets the readiness thresholds of its
+/// [`BrowserPoolConfig`](crate::BrowserPoolConfig). This is useful for
+/// readiness probes in container orchestration.
 ///
 /// # Readiness Criteria
 ///
-/// The pool is considered "ready" if either:
-/// - There are idle browsers available (`available > 0`), OR
-/// - There is capacity to create new browsers (`active < max_pool_size`)
-///
-/// The pool is "not ready" only when:
-/// - All browsers are in use AND the pool is at maximum capacity
+/// The pool is considered "ready" when all of these hold:
+/// - At least [`ready_min_available`](crate::BrowserPoolConfig::ready_min_available)
+///   browsers can be checked out without waiting: idle ones
+///   (`available`) plus those that can still be launched
+///   (`max_pool_size - active`)
+/// - No more than [`ready_max_queue_depth`](crate::BrowserPoolConfig::ready_max_queue_depth)
+///   checkouts are waiting for a browser, if set
+/// - [`warmup()`](crate::BrowserPool::warmup) has finished, if
+///   [`ready_require_warmup`](crate::BrowserPoolConfig::ready_require_warmup)
+///   is set
+/// - The pool is not draining (see
+///   [`start_drain()`](crate::BrowserPool::start_drain)), so load balancers
+///   stop routing to an instance that is shutting down
+///
+/// With the defaults the pool is "not ready" only when all browsers are in
+/// use AND the pool is at maximum capacity, or while it drains.
 ///
 /// # Arguments
 ///
@@ -733,7 +1300,7 @@ pub fn get_pool_stats(pool: &Mutex<BrowserPool>) -> Result<PoolStatsResponse, Pd
 /// # Returns
 ///
 /// * `Ok(true)` - Pool can accept new requests
-/// * `Ok(false)` - Pool is at capacity, requests will queue
+/// * `Ok(false)` - Pool is at (or near) capacity, or not warmed up
 /// * `Err(PdfServiceError::PoolLockFailed)` - If mutex is poisoned
 ///
 /// # Use Cases
@@ -789,667 +1356,6785 @@ pub fn is_pool_ready(pool: &Mutex<BrowserPool>) -> Result<bool, PdfServiceError>
 
     let stats = pool_guard.stats();
     let config = pool_guard.config();
+    let queued = pool_guard.queued_checkouts();
+    let warmed_up = pool_guard.is_warmed_up();
+    let shared_free = pool_guard.inner().shared_tabs_free();
 
-    // Ready if we have available browsers OR we can create more
-    let is_ready = stats.available > 0 || stats.active < config.max_pool_size;
+    let is_ready = !pool_guard.is_draining()
+        && meets_readiness(config, &stats, shared_free, queued, warmed_up);
 
     log::trace!(
-        "Pool readiness check: available={}, active={}, max={}, ready={}",
+        "Pool readiness check: available={}, active={}, max={}, queued={}, warmed_up={}, ready={}",
         stats.available,
         stats.active,
         config.max_pool_size,
+        queued,
+        warmed_up,
         is_ready
     );
 
     Ok(is_ready)
 }
 
+/// Whether a pool in this state meets `config`'s readiness thresholds; see
+/// [`is_pool_ready`].
+fn meets_readiness(
+    config: &crate::BrowserPoolConfig,
+    stats: &crate::PoolStats,
+    shared_free: usize,
+    queued: usize,
+    warmed_up: bool,
+) -> bool {
+    // Tabs of idle browsers and of those we can still create, plus free
+    // tabs of browsers already serving renders
+    let obtainable = (stats.available + config.max_pool_size.saturating_sub(stats.active))
+        * config.tabs_per_browser
+        + shared_free;
+
+    obtainable >= config.ready_min_available
+        && config
+            .ready_max_queue_depth
+            .is_none_or(|max_depth| queued <= max_depth)
+        && (warmed_up || !config.ready_require_warmup)
+}
+
 // ============================================================================
-// Internal Helper Functions
+// Public API - Usage Accounting
 // ============================================================================
 
-/// Validate and normalize a URL string.
-///
-/// Parses the URL using the `url` crate and returns the normalized form.
-/// This catches malformed URLs early, before acquiring a browser.
+/// Run a render on behalf of an API key, enforcing and recording its usage.
+///
+/// Rejects the render with [`PdfServiceError::QuotaExceeded`] if the key
+/// has reached the pool's
+/// [`daily_render_quota`](crate::BrowserPoolConfig::daily_render_quota) or
+/// [`monthly_render_quota`](crate::BrowserPoolConfig::monthly_render_quota).
+/// Otherwise counts the render in [`BrowserPool::usage()`] before running
+/// `render`, so concurrent renders cannot overrun a quota, and adds the
+/// PDF size and browser time once it succeeds. The browser time runs from
+/// checkout, leaving out any wait for a free browser. Failed or panicking
+/// renders are taken back and not counted.
 ///
-/// # Validation Rules
+/// # Blocking Behavior
 ///
-/// - URL must not be empty
-/// - URL must be parseable by the `url` crate
-/// - Scheme must be present (http/https/file/data)
+/// Blocks for as long as `render` does. Call it inside the same blocking
+/// task as the render.
 ///
 /// # Arguments
 ///
-/// * `url` - The URL string to validate
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `api_key` - Key from the [`API_KEY_HEADER`] header, or `None`
+/// * `render` - The render to account, usually a call to
+///   [`generate_pdf_from_url`] or [`generate_pdf_from_html`]
 ///
-/// # Returns
+/// # Examples
 ///
-/// * `Ok(String)` - The normalized URL
-/// * `Err(PdfServiceError::InvalidUrl)` - If validation fails
+/// ```rust,ignore
+/// use html2pdf_api::service::{generate_pdf_from_url, render_metered};
+///
+/// let response = render_metered(&pool, Some("team-a"), || {
+///     generate_pdf_from_url(&pool, &request)
+/// })?;
+/// ```
+pub fn render_metered<F>(
+    pool: &Mutex<BrowserPool>,
+    api_key: Option<&str>,
+    render: F,
+) -> Result<PdfResponse, PdfServiceError>
+where
+    F: FnOnce() -> Result<PdfResponse, PdfServiceError>,
+{
+    let api_key = api_key.unwrap_or(ANONYMOUS_KEY);
+    // Refunded when dropped, if the render fails or panics
+    let reservation = QuotaReservation::new(pool, api_key)?;
+
+    let start_time = Instant::now();
+    let waited = checkout_wait();
+    let response = render()?;
+    let browser_time = start_time
+        .elapsed()
+        .saturating_sub(checkout_wait() - waited);
+
+    reservation.complete(response.size() as u64, browser_time);
+    Ok(response)
+}
+
+/// Open a streamed render on behalf of an API key, enforcing and recording
+/// its usage like [`render_metered`].
+///
+/// The render is counted when the stream is opened, and its size and time
+/// are added once the stream has been read to the end: the number of bytes
+/// streamed and the time from checkout to the last chunk. Streams dropped
+/// early are taken back and not counted.
+///
+/// # Arguments
+///
+/// * `pool` - The shared browser pool, kept by the stream to record usage
+/// * `api_key` - Key from the [`API_KEY_HEADER`] header, or `None`
+/// * `open` - Opens the stream, usually a call to [`stream_pdf_from_url`]
+///   or [`stream_pdf_from_html`]
 ///
 /// # Examples
 ///
 /// ```rust,ignore
-/// assert!(validate_url("https://example.com").is_ok());
-/// assert!(validate_url("").is_err());
-/// assert!(validate_url("not-a-url").is_err());
+/// use html2pdf_api::service::{stream_metered, stream_pdf_from_url};
+///
+/// let stream = stream_metered(&pool, Some("team-a"), || {
+///     stream_pdf_from_url(&pool, &request)
+/// })?;
 /// ```
-fn validate_url(url: &str) -> Result<String, PdfServiceError> {
-    // Check for empty URL first (better error message)
-    if url.trim().is_empty() {
-        log::debug!("URL validation failed: empty URL");
-        return Err(PdfServiceError::InvalidUrl("URL is required".to_string()));
-    }
+pub fn stream_metered<F>(
+    pool: &SharedBrowserPool,
+    api_key: Option<&str>,
+    open: F,
+) -> Result<PdfStream, PdfServiceError>
+where
+    F: FnOnce() -> Result<PdfStream, PdfServiceError>,
+{
+    let api_key = api_key.unwrap_or(ANONYMOUS_KEY);
+    // Refunded when dropped: if opening fails, or the stream is abandoned
+    let reservation = QuotaReservation::new(Arc::clone(pool), api_key)?;
 
-    // Parse and normalize the URL
-    match url::Url::parse(url) {
-        Ok(parsed) => {
-            log::trace!("URL validated successfully: {}", parsed);
-            Ok(parsed.to_string())
-        }
-        Err(e) => {
-            log::debug!("URL validation failed for '{}': {}", url, e);
-            Err(PdfServiceError::InvalidUrl(e.to_string()))
-        }
-    }
+    let start_time = Instant::now();
+    let waited = checkout_wait();
+    let stream = open()?;
+    let waited = checkout_wait() - waited;
+
+    Ok(stream.on_finish(move |size| {
+        reservation.complete(size, start_time.elapsed().saturating_sub(waited));
+    }))
 }
 
-/// Acquire a browser from the pool.
-///
-/// Locks the pool mutex, retrieves a browser, and returns it. The lock is
-/// released immediately after checkout, not held during PDF generation.
-///
-/// # Browser Lifecycle
-///
-/// The returned `BrowserHandle` uses RAII to automatically return the
-/// browser to the pool when dropped:
-///
-/// ```text
-/// ┌─────────────────┐     ┌─────────────────┐     ┌─────────────────┐
-/// │  acquire_browser │ ──▶ │  BrowserHandle  │ ──▶ │  PDF Generation │
-/// │  (lock, get)     │     │  (RAII guard)   │     │  (uses browser) │
-/// └─────────────────┘     └─────────────────┘     └────────┬────────┘
-///                                                          │
-///                                                          ▼
-///                         ┌─────────────────┐     ┌─────────────────┐
-///                         │  Back to Pool   │ ◀── │  Drop Handle    │
-///                         │  (automatic)    │     │  (RAII cleanup) │
-///                         └─────────────────┘     └─────────────────┘
-/// ```
+/// Get the usage of one API key.
 ///
 /// # Arguments
 ///
 /// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `api_key` - Key from the [`API_KEY_HEADER`] header, or `None` for the
+///   anonymous account
 ///
 /// # Returns
 ///
-/// * `Ok(BrowserHandle)` - A browser ready for use
-/// * `Err(PdfServiceError)` - If pool lock or browser acquisition fails
-fn acquire_browser(pool: &Mutex<BrowserPool>) -> Result<BrowserHandle, PdfServiceError> {
-    // Acquire lock on the pool
+/// * `Ok(UsageResponse)` - The key's usage and the configured quotas
+/// * `Err(PdfServiceError::PoolLockFailed)` - If mutex is poisoned
+pub fn get_usage(
+    pool: &Mutex<BrowserPool>,
+    api_key: Option<&str>,
+) -> Result<UsageResponse, PdfServiceError> {
     let pool_guard = pool.lock().map_err(|e| {
-        log::error!("❌ Failed to lock browser pool: {}", e);
+        log::error!("Failed to lock browser pool for usage: {}", e);
         PdfServiceError::PoolLockFailed(e.to_string())
     })?;
 
-    // Get a browser from the pool
-    let browser = pool_guard.get().map_err(|e| {
-        log::error!("❌ Failed to get browser from pool: {}", e);
-        PdfServiceError::BrowserUnavailable(e.to_string())
-    })?;
+    let usage = pool_guard.usage().usage(api_key.unwrap_or(ANONYMOUS_KEY));
+    let config = pool_guard.config();
 
-    log::debug!("Acquired browser {} from pool", browser.id());
+    Ok(UsageResponse {
+        today: usage.today.into(),
+        this_month: usage.this_month.into(),
+        total: usage.total.into(),
+        daily_quota: Some(config.daily_render_quota).filter(|&quota| quota > 0),
+        monthly_quota: Some(config.monthly_render_quota).filter(|&quota| quota > 0),
+    })
+}
 
-    Ok(browser)
-    // pool_guard (MutexGuard) is dropped here, releasing the lock
+/// A render counted against an API key's quotas before it runs.
+///
+/// Counting up front, under the same lock as the quota check, keeps
+/// concurrent renders from overrunning a quota. Dropped without
+/// [`complete`](Self::complete) - the render failed or panicked, or its
+/// stream was abandoned - the render is taken back.
+struct QuotaReservation<P: Deref<Target = Mutex<BrowserPool>>> {
+    pool: P,
+    api_key: String,
+    day: u64,
+    completed: bool,
 }
 
-/// Core PDF generation logic.
+impl<P: Deref<Target = Mutex<BrowserPool>>> QuotaReservation<P> {
+    /// Count a render for `api_key`, failing with
+    /// [`PdfServiceError::QuotaExceeded`] if it is at a quota.
+    fn new(pool: P, api_key: &str) -> Result<Self, PdfServiceError> {
+        let day = {
+            let pool_guard = pool.lock().map_err(|e| {
+                log::error!("Failed to lock browser pool for quota check: {}", e);
+                PdfServiceError::PoolLockFailed(e.to_string())
+            })?;
+            let config = pool_guard.config();
+            pool_guard
+                .usage()
+                .reserve(
+                    api_key,
+                    config.daily_render_quota,
+                    config.monthly_render_quota,
+                )
+                .map_err(PdfServiceError::QuotaExceeded)?
+        };
+
+        Ok(Self {
+            pool,
+            api_key: api_key.to_string(),
+            day,
+            completed: false,
+        })
+    }
+
+    /// Record the finished render's size and browser time.
+    fn complete(mut self, bytes: u64, browser_time: Duration) {
+        self.completed = true;
+        // The PDF is already rendered; a poisoned lock only loses the record
+        match self.pool.lock() {
+            Ok(pool_guard) => pool_guard
+                .usage()
+                .complete(&self.api_key, bytes, browser_time),
+            Err(e) => log::error!("Failed to lock browser pool to record usage: {}", e),
+        }
+    }
+}
+
+impl<P: Deref<Target = Mutex<BrowserPool>>> Drop for QuotaReservation<P> {
+    fn drop(&mut self) {
+        if self.completed {
+            return;
+        }
+        match self.pool.lock() {
+            Ok(pool_guard) => pool_guard.usage().refund(&self.api_key, self.day),
+            Err(e) => log::error!("Failed to lock browser pool to refund usage: {}", e),
+        }
+    }
+}
+
+// ============================================================================
+// Public API - Streamed PDF Generation Functions
+// ============================================================================
+
+/// Generate a PDF from a URL and stream it out as Chrome hands it over.
 ///
-/// This function performs the actual work of:
-/// 1. Creating a new browser tab
-/// 2. Navigating to the URL
-/// 3. Waiting for JavaScript completion
-/// 4. Generating the PDF
-/// 5. Cleaning up the tab
+/// Renders like [`generate_pdf_from_url`], but the PDF stays in Chrome
+/// once printed: the returned [`PdfStream`] reads it in chunks of at most
+/// 1 MiB as it is consumed, so the service never holds the whole document.
+/// The tab stays checked out until the stream is read to the end or
+/// dropped.
 ///
-/// # Arguments
+/// Options that work on the whole PDF - `thumbnail`, `outputs`,
+/// `metadata`, `linearize`, `pdf_a`, `split_pages`, `trace`, and `debug` -
+/// cannot be streamed, and neither can anything while the pool has
+/// [`post_processors`](crate::BrowserPoolConfig::post_processors).
 ///
-/// * `browser` - Browser handle from the pool
-/// * `url` - URL to navigate to (can be http/https or data: URL)
-/// * `wait_duration` - How long to wait for JavaScript
-/// * `landscape` - Whether to use landscape orientation
-/// * `print_background` - Whether to include background graphics
+/// # Blocking Behavior
 ///
-/// # Returns
+/// **This function blocks the calling thread** until the page is printed,
+/// and reading the stream blocks too. In async code, call it from
+/// `spawn_blocking` and read the PDF through [`PdfStream::into_chunks`].
 ///
-/// * `Ok(Vec<u8>)` - The raw PDF binary data
-/// * `Err(PdfServiceError)` - If any step fails
+/// # Errors
 ///
-/// # Tab Lifecycle
+/// Same as [`generate_pdf_from_url`], plus
+/// [`PdfServiceError::InvalidRequest`] if the request asks for an option
+/// above or the pool post-processes PDFs. Failures reading the PDF are returned by the stream.
 ///
-/// A new tab is created for each PDF generation and closed afterward.
-/// This ensures clean state and prevents memory leaks from accumulating
-/// page resources.
+/// # Examples
 ///
-/// ```text
-/// Browser Instance
-/// ├── Tab 1 (new) ◀── Created for this request
-/// │   ├── Navigate to URL
-/// │   ├── Wait for JS
-/// │   ├── Generate PDF
-/// │   └── Close tab ◀── Cleanup
-/// └── (available for next request)
+/// ```rust,ignore
+/// use std::io::Write;
+/// use html2pdf_api::service::{stream_pdf_from_url, PdfFromUrlRequest};
+///
+/// let request = PdfFromUrlRequest {
+///     url: "https://example.com/ledger".to_string(),
+///     ..Default::default()
+/// };
+///
+/// let mut file = std::fs::File::create("ledger.pdf")?;
+/// for chunk in stream_pdf_from_url(&pool, &request)? {
+///     file.write_all(&chunk?)?;
+/// }
 /// ```
-fn generate_pdf_internal(
-    browser: &BrowserHandle,
-    url: &str,
-    wait_duration: Duration,
-    landscape: bool,
-    print_background: bool,
-) -> Result<Vec<u8>, PdfServiceError> {
-    let start_time = Instant::now();
-
-    // Create new tab
-    log::trace!("Creating new browser tab");
-    let tab = browser.new_tab().map_err(|e| {
-        log::error!("❌ Failed to create tab: {}", e);
-        PdfServiceError::TabCreationFailed(e.to_string())
-    })?;
-
-    // Configure PDF options
-    let print_options = build_print_options(landscape, print_background);
-
-    // Navigate to URL
-    log::trace!("Navigating to URL: {}", truncate_url(url, 100));
-    let nav_start = Instant::now();
+pub fn stream_pdf_from_url(
+    pool: &Mutex<BrowserPool>,
+    request: &PdfFromUrlRequest,
+) -> Result<PdfStream, PdfServiceError> {
+    // Validate URL and options before acquiring browser
+    let url = validate_url(&request.url)?;
+    check_url_policy(pool, &url)?;
+    let options = RenderOptions::try_from(request)?;
+    check_streamable(&options)?;
 
-    let page = tab
-        .navigate_to(url)
-        .map_err(|e| {
-            log::error!("❌ Failed to navigate to URL: {}", e);
-            PdfServiceError::NavigationFailed(e.to_string())
-        })?
-        .wait_until_navigated()
-        .map_err(|e| {
-            log::error!("❌ Navigation timeout: {}", e);
-            PdfServiceError::NavigationTimeout(e.to_string())
-        })?;
+    log::debug!(
+        "Streaming PDF from URL: {} (landscape={}, wait={}s)",
+        url,
+        request.is_landscape(),
+        request.wait_duration().as_secs()
+    );
 
-    log::debug!("Navigation completed in {:?}", nav_start.elapsed());
+    let browser = acquire_browser(pool, options.priority, options.cancel.as_ref())?;
+    let stream = open_pdf_stream(browser, PageSource::Url(&url), &options)?;
 
-    // Wait for JavaScript execution
-    wait_for_page_ready(&tab, wait_duration);
+    log::info!("✅ PDF printed from URL: {}, streaming", url);
 
-    // Generate PDF
-    log::trace!("Generating PDF");
-    let pdf_start = Instant::now();
+    Ok(PdfStream::new(
+        stream,
+        request.filename_or_default(),
+        request.is_download(),
+    ))
+}
 
-    let pdf_data = page.print_to_pdf(print_options).map_err(|e| {
-        log::error!("❌ Failed to generate PDF: {}", e);
-        PdfServiceError::PdfGenerationFailed(e.to_string())
-    })?;
+/// Generate a PDF from HTML content and stream it out as Chrome hands it
+/// over.
+///
+/// Streamed counterpart of [`generate_pdf_from_html`]; see
+/// [`stream_pdf_from_url`] for how the stream behaves and which options
+/// cannot be streamed.
+///
+/// # Errors
+///
+/// Same as [`generate_pdf_from_html`], plus
+/// [`PdfServiceError::InvalidRequest`] for options that cannot be
+/// streamed.
+pub fn stream_pdf_from_html(
+    pool: &Mutex<BrowserPool>,
+    request: &PdfFromHtmlRequest,
+) -> Result<PdfStream, PdfServiceError> {
+    // Validate HTML content
+    if request.html.trim().is_empty() {
+        log::warn!("Empty HTML content provided");
+        return Err(PdfServiceError::EmptyHtml);
+    }
+    let options = RenderOptions::try_from(request)?;
+    if let Some(base_url) = &options.base_url {
+        check_url_policy(pool, base_url)?;
+    }
+    check_streamable(&options)?;
 
     log::debug!(
-        "PDF generated in {:?} ({} bytes)",
-        pdf_start.elapsed(),
-        pdf_data.len()
+        "Streaming PDF from HTML ({} bytes, landscape={}, wait={}s)",
+        request.html.len(),
+        request.is_landscape(),
+        request.wait_duration().as_secs()
     );
 
-    // Close tab (best effort - don't fail if this doesn't work)
-    close_tab_safely(&tab);
+    let browser = acquire_browser(pool, options.priority, options.cancel.as_ref())?;
+    let stream = open_pdf_stream(browser, PageSource::Html(&request.html), &options)?;
 
-    log::debug!("Total PDF generation time: {:?}", start_time.elapsed());
+    log::info!(
+        "✅ PDF printed from HTML ({} bytes input), streaming",
+        request.html.len()
+    );
 
-    Ok(pdf_data)
+    Ok(PdfStream::new(
+        stream,
+        request.filename_or_default(),
+        request.is_download(),
+    ))
 }
 
-/// Build PDF print options.
-///
-/// Creates the `PrintToPdfOptions` struct with the specified settings
-/// and sensible defaults for margins and other options.
-///
-/// # Default Settings
-///
-/// - **Margins**: All set to 0 (full page)
-/// - **Header/Footer**: Disabled
-/// - **Background**: Configurable (default: true)
-/// - **Scale**: 1.0 (100%)
-fn build_print_options(landscape: bool, print_background: bool) -> Option<PrintToPdfOptions> {
-    Some(PrintToPdfOptions {
-        landscape: Some(landscape),
-        display_header_footer: Some(false),
-        print_background: Some(print_background),
-        // Zero margins for full-page output
-        margin_top: Some(0.0),
-        margin_bottom: Some(0.0),
-        margin_left: Some(0.0),
-        margin_right: Some(0.0),
-        // Use defaults for everything else
-        ..Default::default()
-    })
+/// Reject options that need the whole PDF at once.
+fn check_streamable(options: &RenderOptions) -> Result<(), PdfServiceError> {
+    let conflicts = [
+        ("thumbnail", options.thumbnail_width.is_some()),
+        ("outputs", options.outputs.is_some()),
+        ("metadata", options.metadata.is_some()),
+        ("linearize", options.linearize),
+        ("pdf_a", options.pdf_a),
+        ("split_pages", options.split_pages),
+        ("trace", options.trace),
+        ("debug", options.debug),
+    ];
+    match conflicts.iter().find(|(_, set)| *set) {
+        Some((name, _)) => Err(PdfServiceError::InvalidRequest(format!(
+            "stream cannot be combined with {}",
+            name
+        ))),
+        None => Ok(()),
+    }
 }
 
-/// Wait for the page to signal it's ready for PDF generation.
-///
-/// This function implements a polling loop that checks for `window.isPageDone === true`.
-/// This allows JavaScript-heavy pages to signal when they've finished rendering,
-/// enabling early PDF generation without waiting the full timeout.
+// ============================================================================
+// Public API - Async PDF Generation Functions
+// ============================================================================
+
+/// Generate a PDF from a URL without pinning a thread for the whole render.
 ///
-/// # Behavior Summary
+/// Async counterpart of [`generate_pdf_from_url`], with the same request
+/// handling, errors, and response. Navigation completes on the tab's
+/// lifecycle events and the JavaScript wait sleeps on the Tokio timer, so a
+/// request with `waitsecs: Some(10)` spends those ten seconds as a suspended
+/// task instead of holding a blocking-pool thread. Individual Chrome calls
+/// (browser checkout, each readiness check, printing) still run on
+/// `spawn_blocking`, but only for as long as the call itself takes.
 ///
-/// | Page State | Result |
-/// |------------|--------|
-/// | `window.isPageDone = true` | Returns **immediately** (early exit) |
-/// | `window.isPageDone = false` | Waits **full duration** |
-/// | `window.isPageDone` not defined | Waits **full duration** |
-/// | JavaScript error during check | Waits **full duration** |
+/// # Runtime
 ///
-/// # Default Behavior (No Flag Set)
+/// Must be called from within a Tokio runtime.
 ///
-/// **Important:** If the page does not set `window.isPageDone = true`, this function
-/// waits the **full `max_wait` duration** before returning. This is intentional -
-/// it gives JavaScript-heavy pages time to render even without explicit signaling.
+/// # Cancellation
 ///
-/// For example, with the default `waitsecs = 5`:
-/// - A page **with** the flag set immediately: ~0ms wait
-/// - A page **without** the flag: full 5000ms wait
+/// The render runs in its own task. Dropping the returned future stops
+/// waiting for the result, but the render runs to completion so the tab and
+/// browser are always handed back to the pool.
 ///
-/// # How It Works
+/// # Errors
 ///
-/// ```text
-/// ┌─────────────────────────────────────────────────────────────────┐
-/// │                    wait_for_page_ready                          │
-/// │                                                                 │
-/// │   ┌─────────┐     ┌──────────────┐     ┌─────────────────────┐  │
-/// │   │  Start  │────▶│ Check flag   │────▶│ window.isPageDone?  │  │
-/// │   └─────────┘     └──────────────┘     └──────────┬──────────┘  │
-/// │                                                   │             │
-/// │                          ┌────────────────────────┼─────────┐   │
-/// │                          │                        │         │   │
-/// │                          ▼                        ▼         │   │
-/// │                   ┌────────────┐           ┌───────────┐    │   │
-/// │                   │   true     │           │  false /  │    │   │
-/// │                   │ (ready!)   │           │ undefined │    │   │
-/// │                   └─────┬──────┘           └─────┬─────┘    │   │
-/// │                         │                        │          │   │
-/// │                         ▼                        ▼          │   │
-/// │                   ┌───────────┐           ┌───────────┐     │   │
-/// │                   │  Return   │           │ Sleep     │     │   │
-/// │                   │  early    │           │ 200ms     │─────┘   │
-/// │                   └───────────┘           └───────────┘         │
-/// │                                                  │              │
-/// │                                                  ▼              │
-/// │                                           ┌───────────┐         │
-/// │                                           │ Timeout?  │         │
-/// │                                           └─────┬─────┘         │
-/// │                                                 │               │
-/// │                                    ┌────────────┴────────────┐  │
-/// │                                    ▼                         ▼  │
-/// │                             ┌───────────┐              ┌──────┐ │
-/// │                             │   Yes     │              │  No  │ │
-/// │                             │ (proceed) │              │(loop)│ │
-/// │                             └───────────┘              └──────┘ │
-/// └─────────────────────────────────────────────────────────────────┘
-/// ```
+/// Same as [`generate_pdf_from_url`]. A render task that panics is reported
+/// as [`PdfServiceError::Internal`].
 ///
-/// # Polling Timeline
+/// # Examples
 ///
-/// The function polls every 200ms (see `JS_POLL_INTERVAL_MS`):
+/// ```rust,ignore
+/// use html2pdf_api::service::{generate_pdf_from_url_async, PdfFromUrlRequest};
 ///
-/// ```text
-/// Time:   0ms    200ms   400ms   600ms   800ms  ...  5000ms
-///          │       │       │       │       │           │
-///          ▼       ▼       ▼       ▼       ▼           ▼
-///        Poll    Poll    Poll    Poll    Poll  ...   Timeout
-///          │       │       │       │       │           │
-///          └───────┴───────┴───────┴───────┴───────────┤
-///                                                      ▼
-///                                              Proceed to PDF
+/// let request = PdfFromUrlRequest {
+///     url: "https://example.com/dashboard".to_string(),
+///     waitsecs: Some(10), // No thread is blocked while waiting
+///     ..Default::default()
+/// };
 ///
-/// If window.isPageDone = true at any poll → Exit immediately
+/// let response = generate_pdf_from_url_async(&pool, &request).await?;
 /// ```
+pub async fn generate_pdf_from_url_async(
+    pool: &SharedBrowserPool,
+    request: &PdfFromUrlRequest,
+) -> Result<PdfResponse, PdfServiceError> {
+    // Validate URL and options before acquiring browser
+    let url = validate_url(&request.url)?;
+    check_url_policy(pool, &url)?;
+    let options = RenderOptions::try_from(request)?;
+
+    log::debug!(
+        "Generating PDF from URL (async): {} (landscape={}, wait={}s)",
+        url,
+        request.is_landscape(),
+        request.wait_duration().as_secs()
+    );
+
+    let rendered = spawn_render(pool, OwnedPageSource::Url(url.clone()), options).await?;
+
+    log::info!(
+        "✅ PDF generated successfully from URL: {} ({} bytes)",
+        url,
+        rendered.pdf.len()
+    );
+
+    Ok(rendered.into_response(request.filename_or_default(), request.is_download()))
+}
+
+/// Generate a PDF from HTML content without pinning a thread for the whole
+/// render.
 ///
-/// Each poll executes this JavaScript:
+/// Async counterpart of [`generate_pdf_from_html`]. Waiting for the
+/// document's subresources and for `window.isPageDone` uses the Tokio
+/// timer; see [`generate_pdf_from_url_async`] for the runtime and
+/// cancellation behavior.
 ///
-/// ```javascript
-/// window.isPageDone === true  // Returns true, false, or undefined
-/// ```
+/// # Errors
 ///
-/// - `true` → Function returns immediately
-/// - `false` / `undefined` / error → Continue polling until timeout
+/// Same as [`generate_pdf_from_html`]. A render task that panics is
+/// reported as [`PdfServiceError::Internal`].
 ///
-/// # Page-Side Implementation (Optional)
+/// # Examples
 ///
-/// To enable early completion and avoid unnecessary waiting, add this to your
-/// page's JavaScript **after** all content is rendered:
+/// ```rust,ignore
+/// use html2pdf_api::service::{generate_pdf_from_html_async, PdfFromHtmlRequest};
 ///
-/// ```javascript
-/// // Signal that the page is ready for PDF generation
-/// window.isPageDone = true;
+/// let request = PdfFromHtmlRequest {
+///     html: "<h1>Invoice #1234</h1>".to_string(),
+///     filename: Some("invoice.pdf".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let response = generate_pdf_from_html_async(&pool, &request).await?;
 /// ```
+pub async fn generate_pdf_from_html_async(
+    pool: &SharedBrowserPool,
+    request: &PdfFromHtmlRequest,
+) -> Result<PdfResponse, PdfServiceError> {
+    // Validate HTML content
+    if request.html.trim().is_empty() {
+        log::warn!("Empty HTML content provided");
+        return Err(PdfServiceError::EmptyHtml);
+    }
+    let options = RenderOptions::try_from(request)?;
+    if let Some(base_url) = &options.base_url {
+        check_url_policy(pool, base_url)?;
+    }
+
+    log::debug!(
+        "Generating PDF from HTML (async) ({} bytes, landscape={}, wait={}s)",
+        request.html.len(),
+        request.is_landscape(),
+        request.wait_duration().as_secs()
+    );
+
+    let rendered = spawn_render(pool, OwnedPageSource::Html(request.html.clone()), options).await?;
+
+    log::info!(
+        "✅ PDF generated successfully from HTML ({} bytes input → {} bytes output)",
+        request.html.len(),
+        rendered.pdf.len()
+    );
+
+    Ok(rendered.into_response(request.filename_or_default(), request.is_download()))
+}
+
+// ============================================================================
+// Internal Helper Functions
+// ============================================================================
+
+/// Validate and normalize a URL string.
 ///
-/// ## Framework Examples
+/// Parses the URL using the `url` crate and returns the normalized form.
+/// This catches malformed URLs early, before acquiring a browser.
 ///
-/// **React:**
-/// ```javascript
-/// useEffect(() => {
-///     fetchData().then((result) => {
-///         setData(result);
-///         // Signal ready after state update and re-render
-///         setTimeout(() => { window.isPageDone = true; }, 0);
-///     });
-/// }, []);
-/// ```
+/// # Validation Rules
 ///
-/// **Vue:**
-/// ```javascript
-/// mounted() {
-///     this.loadData().then(() => {
-///         this.$nextTick(() => {
-///             window.isPageDone = true;
-///         });
-///     });
-/// }
-/// ```
+/// - URL must not be empty
+/// - URL must be parseable by the `url` crate
+/// - Scheme must be present (http/https/file/data)
 ///
-/// **Vanilla JavaScript:**
-/// ```javascript
-/// document.addEventListener('DOMContentLoaded', async () => {
-///     await loadDynamicContent();
-///     await renderCharts();
-///     window.isPageDone = true;  // All done!
-/// });
+/// # Arguments
+///
+/// * `url` - The URL string to validate
+///
+/// # Returns
+///
+/// * `Ok(String)` - The normalized URL
+/// * `Err(PdfServiceError::InvalidUrl)` - If validation fails
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// assert!(validate_url("https://example.com").is_ok());
+/// assert!(validate_url("").is_err());
+/// assert!(validate_url("not-a-url").is_err());
 /// ```
+fn validate_url(url: &str) -> Result<String, PdfServiceError> {
+    // Check for empty URL first (better error message)
+    if url.trim().is_empty() {
+        log::debug!("URL validation failed: empty URL");
+        return Err(PdfServiceError::InvalidUrl("URL is required".to_string()));
+    }
+
+    // Parse and normalize the URL
+    match url::Url::parse(url) {
+        Ok(parsed) => {
+            log::trace!("URL validated successfully: {}", parsed);
+            Ok(parsed.to_string())
+        }
+        Err(e) => {
+            log::debug!("URL validation failed for '{}': {}", url, e);
+            Err(PdfServiceError::InvalidUrl(e.to_string()))
+        }
+    }
+}
+
+/// Check a URL against the pool's
+/// [`url_policy`](crate::BrowserPoolConfig::url_policy) before a browser is
+/// checked out.
 ///
-/// # When to Increase `waitsecs`
+/// Only the URL itself is checked here; the addresses its host resolves
+/// to are checked when the browser requests it (see [`RedirectGuard`]).
 ///
-/// If you cannot modify the target page to set `window.isPageDone`, increase
-/// `waitsecs` based on the page complexity:
+/// # Errors
 ///
-/// | Page Type | Recommended `waitsecs` |
-/// |-----------|------------------------|
-/// | Static HTML (no JS) | 1 |
-/// | Light JS (form validation, simple DOM) | 2-3 |
-/// | Moderate JS (API calls, dynamic content) | 5 (default) |
-/// | Heavy SPA (React, Vue, Angular) | 5-10 |
-/// | Complex visualizations (D3, charts, maps) | 10-15 |
-/// | Pages loading external resources | 10-20 |
+/// Returns [`PdfServiceError::InvalidUrl`] if the policy forbids the URL,
+/// or [`PdfServiceError::PoolLockFailed`] if the pool lock is poisoned.
+pub(crate) fn check_url_policy(
+    pool: &Mutex<BrowserPool>,
+    url: &str,
+) -> Result<(), PdfServiceError> {
+    let policy = {
+        let pool = pool.lock().map_err(|e| {
+            log::error!("❌ Failed to lock browser pool: {}", e);
+            PdfServiceError::PoolLockFailed(e.to_string())
+        })?;
+        let policy = &pool.config().url_policy;
+        if !policy.is_restricted() {
+            return Ok(());
+        }
+        policy.clone()
+    };
+
+    let parsed = url::Url::parse(url).map_err(|e| PdfServiceError::InvalidUrl(e.to_string()))?;
+    policy.check(&parsed).map_err(|reason| {
+        log::warn!("⚠️ Rejected URL {}: {}", truncate_url(url, 100), reason);
+        PdfServiceError::InvalidUrl(reason)
+    })
+}
+
+/// Check a `file://` URL against the pool's
+/// [`allowed_file_dirs`](crate::BrowserPoolConfig::allowed_file_dirs).
 ///
-/// # Performance Optimization
+/// Other URLs are returned unchanged. A file URL is resolved to its
+/// canonical path, so `..` segments and symlinks can't reach outside the
+/// allowed directories, and the canonical form is returned for loading.
 ///
-/// For high-throughput scenarios, implementing `window.isPageDone` on your
-/// pages can significantly improve performance:
+/// # Errors
 ///
-/// ```text
-/// Without flag (5s default wait):
-///     Request 1: ████████████████████ 5.2s
-///     Request 2: ████████████████████ 5.1s
-///     Request 3: ████████████████████ 5.3s
-///     Average: 5.2s per PDF
+/// Returns [`PdfServiceError::InvalidUrl`] if no directories are allowed,
+/// the file does not exist, or it lies outside every allowed directory.
+fn resolve_file_url(url: &str, allowed_dirs: &[PathBuf]) -> Result<String, PdfServiceError> {
+    let mut parsed = match url::Url::parse(url) {
+        Ok(parsed) if parsed.scheme() == "file" => parsed,
+        _ => return Ok(url.to_string()),
+    };
+
+    if allowed_dirs.is_empty() {
+        return Err(PdfServiceError::InvalidUrl(
+            "file:// URLs are disabled; set allowed_file_dirs (BROWSER_ALLOWED_FILE_DIRS) to enable them"
+                .to_string(),
+        ));
+    }
+
+    let path = parsed
+        .to_file_path()
+        .map_err(|()| PdfServiceError::InvalidUrl(format!("not a local file URL: {}", url)))?
+        .canonicalize()
+        .map_err(|e| PdfServiceError::InvalidUrl(format!("cannot open {}: {}", url, e)))?;
+
+    let allowed = allowed_dirs
+        .iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .any(|dir| path.starts_with(dir));
+    if !allowed {
+        log::warn!(
+            "Rejected file URL outside the allowed directories: {}",
+            path.display()
+        );
+        return Err(PdfServiceError::InvalidUrl(format!(
+            "{} is outside the allowed file directories",
+            url
+        )));
+    }
+
+    let query = parsed.query().map(String::from);
+    let fragment = parsed.fragment().map(String::from);
+    parsed = url::Url::from_file_path(&path)
+        .map_err(|()| PdfServiceError::InvalidUrl(format!("not a local file URL: {}", url)))?;
+    parsed.set_query(query.as_deref());
+    parsed.set_fragment(fragment.as_deref());
+
+    Ok(parsed.to_string())
+}
+
+thread_local! {
+    /// Time this thread has spent in [`acquire_browser`], so metered
+    /// renders can leave the wait for a browser out of their browser time.
+    static CHECKOUT_WAIT: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// Total time this thread has waited for browsers so far.
+fn checkout_wait() -> Duration {
+    CHECKOUT_WAIT.with(Cell::get)
+}
+
+/// Acquire a browser from the pool.
 ///
-/// With flag (page ready in 800ms):
-///     Request 1: ████ 0.9s
-///     Request 2: ████ 0.8s
-///     Request 3: ████ 0.9s
-///     Average: 0.87s per PDF (6x faster!)
-/// ```
+/// Locks the pool mutex, retrieves a browser, and returns it. The lock is
+/// released immediately after checkout, not held during PDF generation.
 ///
-/// # Arguments
+/// # Browser Lifecycle
 ///
-/// * `tab` - The browser tab to check. Must have completed navigation.
-/// * `max_wait` - Maximum time to wait before proceeding with PDF generation.
-///   This is the upper bound; the function may return earlier if the page
-///   signals readiness.
+/// The returned `BrowserHandle` uses RAII to automatically return the
+/// browser to the pool when dropped:
 ///
-/// # Returns
+/// ```text
+/// ┌─────────────────┐     ┌─────────────────┐     ┌─────────────────┐
+/// │  acquire_browser │ ──▶ │  BrowserHandle  │ ──▶ │  PDF Generation │
+/// │  (lock, get)     │     │  (RAII guard)   │     │  (uses browser) │
+/// └─────────────────┘     └─────────────────┘     └────────┬────────┘
+///                                                          │
+///                                                          ▼
+///                         ┌─────────────────┐     ┌─────────────────┐
+///                         │  Back to Pool   │ ◀── │  Drop Handle    │
+///                         │  (automatic)    │     │  (RAII cleanup) │
+///                         └─────────────────┘     └─────────────────┘
+/// ```
 ///
-/// This function returns `()` (unit). It either:
-/// - Returns early when `window.isPageDone === true` is detected
-/// - Returns after `max_wait` duration has elapsed (timeout)
+/// When the pool's checkout queue is enabled and the pool is saturated,
+/// the wait for a free slot happens before the lock is taken, so waiting
+/// renders don't block stats or other endpoints.
 ///
-/// In both cases, PDF generation proceeds afterward. This function never fails -
-/// timeout is a normal completion path, not an error.
+/// # Arguments
 ///
-/// # Thread Blocking
+/// * `pool` - Reference to the mutex-wrapped browser pool
+/// * `priority` - Place in the checkout queue
+/// * `cancel` - Handle that abandons the wait in the queue when cancelled
 ///
-/// This function blocks the calling thread with `std::thread::sleep()`.
-/// Always call from within a blocking context (e.g., `spawn_blocking`).
+/// # Returns
 ///
-/// # Example
+/// * `Ok(BrowserHandle)` - A browser ready for use
+/// * `Err(PdfServiceError)` - If pool lock or browser acquisition fails,
+///   or the render was cancelled while waiting
+fn acquire_browser(
+    pool: &Mutex<BrowserPool>,
+    priority: Priority,
+    cancel: Option<&CancelHandle>,
+) -> Result<BrowserHandle, PdfServiceError> {
+    let waiting = Instant::now();
+    let lock = || {
+        pool.lock().map_err(|e| {
+            log::error!("❌ Failed to lock browser pool: {}", e);
+            PdfServiceError::PoolLockFailed(e.to_string())
+        })
+    };
+
+    // Wait for a checkout slot (lock not held)
+    let queue = lock()?.checkout_queue();
+    let slot = queue.admit(priority, cancel).map_err(|e| match e {
+        BrowserPoolError::Cancelled => {
+            log::debug!("Render cancelled while waiting for a browser");
+            PdfServiceError::Cancelled("render cancelled while waiting for a browser".to_string())
+        }
+        e => {
+            log::warn!("⚠️ No browser for {} priority render: {}", priority, e);
+            PdfServiceError::BrowserUnavailable(e.to_string())
+        }
+    })?;
+
+    // Acquire lock on the pool
+    let pool_guard = lock()?;
+
+    // Get a browser from the pool
+    let browser = pool_guard.checkout(slot).map_err(|e| match e {
+        BrowserPoolError::DiskFull(msg) => {
+            log::error!("❌ Refusing render, not enough disk space: {}", msg);
+            PdfServiceError::DiskFull(msg)
+        }
+        e => {
+            log::error!("❌ Failed to get browser from pool: {}", e);
+            PdfServiceError::BrowserUnavailable(e.to_string())
+        }
+    })?;
+
+    log::debug!("Acquired browser {} from pool", browser.id());
+    CHECKOUT_WAIT.with(|wait| wait.set(wait.get() + waiting.elapsed()));
+
+    Ok(browser)
+    // pool_guard (MutexGuard) is dropped here, releasing the lock
+}
+
+/// What to load into the tab before printing.
+#[derive(Debug, Clone, Copy)]
+enum PageSource<'a> {
+    /// Navigate to a validated URL.
+    Url(&'a str),
+    /// Write HTML directly into a blank document.
+    Html(&'a str),
+}
+
+/// Per-request rendering settings shared by the URL and HTML paths.
 ///
-/// ```rust,ignore
-/// // Navigate to page first
-/// let page = tab.navigate_to(url)?.wait_until_navigated()?;
+/// Built with `try_from` so option values are validated before a browser
+/// is checked out.
+#[derive(Debug, Clone)]
+struct RenderOptions {
+    /// How long to wait for the page to signal readiness.
+    wait_duration: Duration,
+    /// Expression polled until truthy, already wrapped by
+    /// `validate_wait_expression`.
+    wait_expression: Arc<str>,
+    /// Landscape orientation requested; `None` uses the pool's print options.
+    landscape: Option<bool>,
+    /// Background graphics requested; `None` uses the pool's print options.
+    print_background: Option<bool>,
+    /// Paper size, margins, scale and page ranges requested.
+    page: PageSetup,
+    /// CSS media type emulated from before the page loads; `None` loads
+    /// as `screen` and prints as `print`.
+    media: Option<MediaType>,
+    /// `prefers-color-scheme` emulated from before the page loads.
+    color_scheme: Option<ColorScheme>,
+    /// The pool's base print options, set by `with_pool_policy`.
+    print_defaults: Arc<PrintToPdfOptions>,
+    /// Whether the document's own scripts may run.
+    javascript_enabled: bool,
+    /// Whether invalid TLS certificates are accepted.
+    ignore_tls_errors: bool,
+    /// Whether the browser's HTTP cache is ignored.
+    bypass_cache: bool,
+    /// Whether the tab goes offline: before loading HTML, or once a URL
+    /// has loaded.
+    offline: bool,
+    /// Whether every request no mock answers is failed, for offline HTML
+    /// that mocks serve: the tab itself must stay online to load it.
+    isolated: bool,
+    /// Position reported to the page's Geolocation API.
+    geolocation: Option<Geolocation>,
+    /// IANA timezone the page runs in; `None` for the server's.
+    timezone: Option<String>,
+    /// Emulated screen; `None` keeps the browser's.
+    viewport: Option<Viewport>,
+    /// `User-Agent` replacing the browser's for this render.
+    user_agent: Option<String>,
+    /// Web storage written on the target origin before the page boots.
+    storage: Option<Arc<StorageSeed>>,
+    /// Extra headers sent to the target origin.
+    headers: Option<Arc<ExtraHeaders>>,
+    /// Cookies set before navigating; the cookie jar is cleared afterwards.
+    cookies: Vec<Network::CookieParam>,
+    /// Body of a `POST` navigation; `None` for `GET`.
+    post: Option<Arc<PostNavigation>>,
+    /// Resource types whose requests are failed before they are sent.
+    blocked_resources: Vec<ResourceType>,
+    /// URL patterns passed to `Network.setBlockedURLs`.
+    blocked_urls: Vec<String>,
+    /// Canned responses, most specific pattern first.
+    mocks: Arc<[ResourceMock]>,
+    /// URL an HTML document is served at, by a mock, so its relative URLs
+    /// resolve against it; `None` writes the HTML into a blank document.
+    base_url: Option<String>,
+    /// Steps run in the page before the wait for readiness.
+    actions: Arc<[PageAction]>,
+    /// Stylesheet drawing the watermark, added before `inject_css`.
+    watermark: Option<Arc<str>>,
+    /// Stylesheet added once the page is ready, before printing.
+    inject_css: Option<Arc<str>>,
+    /// Script run once the page is ready, after `inject_css`.
+    inject_js: Option<Arc<str>>,
+    /// Redirect policy for the main navigation; `None` when unrestricted.
+    redirects: Option<Arc<RedirectGuard>>,
+    /// Width of the first-page thumbnail; `None` when not requested.
+    thumbnail_width: Option<u32>,
+    /// Image format of the thumbnail.
+    thumbnail_format: ImageFormat,
+    /// Compression quality of a lossy thumbnail; `None` for Chrome's default.
+    thumbnail_quality: Option<u32>,
+    /// Artifacts the request listed in `outputs`; `None` when unset.
+    outputs: Option<Outputs>,
+    /// Document properties written into the PDF after rendering.
+    metadata: Option<Arc<DocumentInfo>>,
+    /// Whether the PDF is linearized after rendering.
+    linearize: bool,
+    /// Whether the PDF is converted toward PDF/A after rendering.
+    pdf_a: bool,
+    /// Whether the PDF is split into a ZIP of per-page PDFs after
+    /// rendering.
+    split_pages: bool,
+    /// Steps applied to the finished PDF; set by `with_pool_policy`.
+    post_processors: Vec<Arc<dyn PdfPostProcessor>>,
+    /// Whether a performance trace was requested.
+    trace: bool,
+    /// Where the trace is saved; set by `with_pool_policy` for traced
+    /// renders when the pool has a trace directory.
+    trace_dir: Option<Arc<Path>>,
+    /// Whether a debug report replaces the PDF.
+    debug: bool,
+    /// Place in the checkout queue when the pool is saturated.
+    priority: Priority,
+    /// Handle that aborts the render; `None` when it cannot be cancelled.
+    cancel: Option<CancelHandle>,
+}
+
+/// What one render produced.
+#[derive(Debug)]
+struct Rendered {
+    /// The PDF.
+    pdf: Vec<u8>,
+    /// Image of the first page, when requested.
+    thumbnail: Option<Vec<u8>>,
+    /// Format of `thumbnail`.
+    thumbnail_format: ImageFormat,
+    /// The rendered DOM, when `outputs` asked for it.
+    html: Option<String>,
+    /// ZIP of per-page PDFs, when `split_pages` asked for it.
+    pages: Option<Vec<u8>>,
+    /// Artifacts the request listed in `outputs`.
+    outputs: Option<Outputs>,
+    /// ID of the saved performance trace, when one was recorded.
+    trace_id: Option<String>,
+    /// What happened during the render, when `debug` asked for it.
+    report: Option<RenderReport>,
+}
+
+impl Rendered {
+    /// Write the metadata, convert the PDF to PDF/A, linearize it, run
+    /// the pool's post-processors, and split it into pages, as far as the
+    /// render asked for each.
+    ///
+    /// Runs after the tab is released, since none of these need the
+    /// browser.
+    fn post_processed(self, options: &RenderOptions) -> Result<Self, PdfServiceError> {
+        let pdf = match &options.metadata {
+            Some(info) => stamp_metadata(&self.pdf, info)?,
+            None => self.pdf,
+        };
+        let pdf = if options.pdf_a {
+            convert_to_pdf_a(&pdf)?
+        } else {
+            pdf
+        };
+        let pdf = if options.linearize {
+            linearize_pdf(&pdf)?
+        } else {
+            pdf
+        };
+        let pdf = run_post_processors(pdf, &options.post_processors)?;
+        let pages = if options.split_pages {
+            Some(split_pdf(&pdf)?)
+        } else {
+            None
+        };
+        Ok(Self { pdf, pages, ..self })
+    }
+
+    /// Whether [`post_processed`](Self::post_processed) has work to do.
+    fn needs_post_processing(options: &RenderOptions) -> bool {
+        options.metadata.is_some()
+            || options.pdf_a
+            || options.linearize
+            || options.split_pages
+            || !options.post_processors.is_empty()
+    }
+
+    fn into_response(self, filename: String, force_download: bool) -> PdfResponse {
+        let mut response = PdfResponse::new(self.pdf, filename, force_download);
+        if let Some(image) = self.thumbnail {
+            response = response.with_thumbnail_as(image, self.thumbnail_format);
+        }
+        if let Some(html) = self.html {
+            response = response.with_html(html);
+        }
+        if let Some(zip) = self.pages {
+            response = response.with_pages(zip);
+        }
+        if let Some(outputs) = self.outputs {
+            response = response.with_outputs(outputs);
+        }
+        if let Some(trace_id) = self.trace_id {
+            response = response.with_trace_id(trace_id);
+        }
+        if let Some(report) = self.report {
+            response = response.with_report(report);
+        }
+        response
+    }
+}
+
+/// Run `processors` on a finished PDF, in order.
 ///
-/// // Wait up to 10 seconds for JavaScript
-/// wait_for_page_ready(&tab, Duration::from_secs(10));
+/// # Errors
 ///
-/// // Now generate PDF - page is either ready or we've waited long enough
-/// let pdf_data = page.print_to_pdf(options)?;
-/// ```
-fn wait_for_page_ready(tab: &headless_chrome::Tab, max_wait: Duration) {
-    let start = Instant::now();
-    let poll_interval = Duration::from_millis(JS_POLL_INTERVAL_MS);
+/// Returns [`PdfServiceError::PdfGenerationFailed`] naming the first
+/// processor that fails.
+pub(crate) fn run_post_processors(
+    mut pdf: Vec<u8>,
+    processors: &[Arc<dyn PdfPostProcessor>],
+) -> Result<Vec<u8>, PdfServiceError> {
+    for processor in processors {
+        let start = Instant::now();
+        pdf = processor.process(pdf).map_err(|e| {
+            log::error!("❌ PDF post-processor {} failed: {}", processor.name(), e);
+            PdfServiceError::PdfGenerationFailed(format!(
+                "post-processor {} failed: {}",
+                processor.name(),
+                e
+            ))
+        })?;
+        log::debug!(
+            "PDF post-processor {} took {:?}",
+            processor.name(),
+            start.elapsed()
+        );
+    }
+    Ok(pdf)
+}
+
+impl RenderOptions {
+    /// Add the pool-wide policy to this render's own settings: the
+    /// [`block_urls`](crate::BrowserPoolConfig::block_urls) blocklist,
+    /// the redirect limits and [`url_policy`](crate::BrowserPoolConfig::url_policy), the base
+    /// [`print_options`](crate::BrowserPoolConfig::print_options), the
+    /// [`trace_dir`](crate::BrowserPoolConfig::trace_dir), and the
+    /// [`post_processors`](crate::BrowserPoolConfig::post_processors).
+    fn with_pool_policy(mut self, config: &crate::BrowserPoolConfig) -> Self {
+        self.print_defaults = Arc::clone(&config.print_options);
+        self.post_processors.clone_from(&config.post_processors);
+        if self.trace {
+            self.trace_dir = config.trace_dir.as_deref().map(Arc::from);
+        }
+        for pattern in config
+            .block_urls
+            .iter()
+            .flat_map(|entry| url_block_patterns(entry))
+        {
+            if !self.blocked_urls.contains(&pattern) {
+                self.blocked_urls.push(pattern);
+            }
+        }
+        if config.max_redirects < CHROME_MAX_REDIRECTS
+            || !config.allow_cross_origin_redirects
+            || config.url_policy.is_restricted()
+        {
+            self.redirects = Some(Arc::new(RedirectGuard::new(
+                config.max_redirects,
+                config.allow_cross_origin_redirects,
+                Some(config.url_policy.clone()).filter(UrlPolicy::is_restricted),
+            )));
+        }
+        self
+    }
+
+    /// Print options for this render: the pool's base options with the
+    /// request's fields applied.
+    fn print_options(&self) -> PrintToPdfOptions {
+        build_print_options(
+            &self.print_defaults,
+            self.landscape,
+            self.print_background,
+            &self.page,
+        )
+    }
+
+    /// The media type the PDF is printed with.
+    fn print_media(&self) -> MediaType {
+        self.media.unwrap_or(MediaType::Print)
+    }
+
+    /// The wait expression as the request gave it, without the wrapping
+    /// added by `validate_wait_expression`.
+    fn wait_expression_source(&self) -> &str {
+        self.wait_expression
+            .strip_prefix("!!(\n")
+            .and_then(|expression| expression.strip_suffix("\n)"))
+            .unwrap_or(&self.wait_expression)
+    }
+
+    /// The settings a debug report lists, leaving out secrets: web storage,
+    /// header and cookie values, `POST` bodies, and mock response bodies.
+    fn summary(&self) -> serde_json::Value {
+        let names = |values: &[ResourceType]| -> Vec<serde_json::Value> {
+            values
+                .iter()
+                .filter_map(|value| serde_json::to_value(value).ok())
+                .collect()
+        };
+        serde_json::json!({
+            "wait_secs": self.wait_duration.as_secs_f64(),
+            "wait_for_expression": self.wait_expression_source(),
+            "emulate_media": self.media.map(|media| media.as_str()),
+            "color_scheme": self.color_scheme.map(|scheme| scheme.as_str()),
+            "javascript_enabled": self.javascript_enabled,
+            "ignore_tls_errors": self.ignore_tls_errors,
+            "bypass_cache": self.bypass_cache,
+            "offline": self.offline,
+            "isolated": self.isolated,
+            "geolocation": self.geolocation,
+            "timezone": self.timezone,
+            "viewport": self.viewport.map(|viewport| serde_json::json!({
+                "width": viewport.width,
+                "height": viewport.height,
+                "device_scale_factor": viewport.device_scale_factor,
+                "mobile": viewport.mobile,
+            })),
+            "user_agent": self.user_agent,
+            "storage_origin": self.storage.as_ref().map(|storage| &storage.origin),
+            "headers": self.headers.as_ref().map(|headers| headers.names()),
+            "auth": self.headers.as_ref().and_then(|headers| headers.auth_scheme),
+            "cookies": self.cookies.iter().map(|cookie| &cookie.name).collect::<Vec<_>>(),
+            "post_content_type": self.post.as_ref().map(|post| &post.content_type),
+            "blocked_resources": names(&self.blocked_resources),
+            "blocked_urls": self.blocked_urls,
+            "mock_responses": self.mocks.iter().map(|mock| &mock.pattern).collect::<Vec<_>>(),
+            "base_url": self.base_url,
+            "actions": self.actions.iter().map(describe_action).collect::<Vec<_>>(),
+            "watermark": self.watermark.is_some(),
+            "inject_css": self.inject_css.is_some(),
+            "inject_js": self.inject_js.is_some(),
+            "redirects": self.redirects.as_ref().map(|guard| serde_json::json!({
+                "max_redirects": guard.max_redirects,
+                "allow_cross_origin": guard.allow_cross_origin,
+                "url_policy": guard.policy.as_ref().map(|policy| serde_json::json!({
+                    "deny_private_networks": policy.deny_private_networks,
+                    "allowed_hosts": policy.allowed_hosts,
+                    "denied_hosts": policy.denied_hosts,
+                })),
+            })),
+            "print_options": serde_json::to_value(self.print_options()).unwrap_or_default(),
+            "thumbnail_width": self.thumbnail_width,
+            "metadata": self.metadata.as_ref().map(|info| info.keys()),
+            "linearize": self.linearize,
+            "pdf_a": self.pdf_a,
+            "split_pages": self.split_pages,
+            "post_processors": self
+                .post_processors
+                .iter()
+                .map(|processor| processor.name())
+                .collect::<Vec<_>>(),
+            "trace": self.trace,
+            "priority": self.priority.as_str(),
+        })
+    }
+
+    /// Whether the rendered HTML is captured.
+    fn captures_html(&self) -> bool {
+        self.outputs.is_some_and(|outputs| outputs.html)
+    }
+
+    /// Paper size in CSS pixels (96 DPI), oriented as the PDF will be.
+    fn page_size_px(&self) -> (u32, u32) {
+        let options = self.print_options();
+        let to_px = |inches: f64| (inches * CSS_PX_PER_INCH).round() as u32;
+        let width = options.paper_width.map_or(PAGE_SIZE_PX.0, to_px);
+        let height = options.paper_height.map_or(PAGE_SIZE_PX.1, to_px);
+        if options.landscape == Some(true) {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+}
+
+/// Web storage to write before a page's scripts run, for one origin.
+#[derive(Debug)]
+struct StorageSeed {
+    /// Origin the entries belong to, e.g. `https://app.example.com`.
+    origin: String,
+    /// Script run on every new document; writes the entries when the
+    /// document is on `origin`.
+    script: String,
+}
+
+impl StorageSeed {
+    /// Prepare `storage` for the origin of `url`.
+    ///
+    /// Returns `None` when there is nothing to write, or when `url` does
+    /// not parse (URL validation reports that).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfServiceError::InvalidRequest`] for a URL without an
+    /// origin of its own, such as `file://`.
+    fn new(url: &str, storage: Option<&WebStorage>) -> Result<Option<Arc<Self>>, PdfServiceError> {
+        let Some(storage) = storage.filter(|storage| !storage.is_empty()) else {
+            return Ok(None);
+        };
+        let Ok(url) = url::Url::parse(url.trim()) else {
+            return Ok(None);
+        };
+        let origin = url.origin();
+        if !origin.is_tuple() {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "storage needs an http or https URL, got a {} URL",
+                url.scheme()
+            )));
+        }
+        let origin = origin.ascii_serialization();
+        let script = STORAGE_SCRIPT
+            .replace(
+                "__ORIGIN__",
+                &serde_json::Value::from(origin.as_str()).to_string(),
+            )
+            .replace(
+                "__LOCAL__",
+                &serde_json::to_string(&storage.local).unwrap_or_default(),
+            )
+            .replace(
+                "__SESSION__",
+                &serde_json::to_string(&storage.session).unwrap_or_default(),
+            );
+        Ok(Some(Arc::new(Self { origin, script })))
+    }
+}
+
+/// Extra HTTP headers for requests to one origin.
+#[derive(Debug)]
+struct ExtraHeaders {
+    /// Origin the headers are sent to, e.g. `https://app.example.com`.
+    origin: String,
+    /// Header names and values, in request order.
+    headers: Vec<HeaderEntry>,
+    /// Scheme of the request's `auth`, whose `Authorization` header is
+    /// among `headers`.
+    auth_scheme: Option<&'static str>,
+}
+
+impl ExtraHeaders {
+    /// Prepare `headers`, and an `Authorization` header for `auth`, for
+    /// the origin of `url`.
+    ///
+    /// Returns `None` when there is nothing to send, or when `url` does not
+    /// parse (URL validation reports that).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfServiceError::InvalidRequest`] for a URL without an
+    /// origin of its own, a name that isn't an HTTP token, a value with a
+    /// line break, invalid credentials, or `auth` alongside an
+    /// `Authorization` header.
+    fn new(
+        url: &str,
+        headers: Option<&BTreeMap<String, String>>,
+        auth: Option<&AuthSpec>,
+    ) -> Result<Option<Arc<Self>>, PdfServiceError> {
+        let empty = BTreeMap::new();
+        let headers = headers.unwrap_or(&empty);
+        if headers.is_empty() && auth.is_none() {
+            return Ok(None);
+        }
+        let Ok(url) = url::Url::parse(url.trim()) else {
+            return Ok(None);
+        };
+        let origin = url.origin();
+        if !origin.is_tuple() {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "headers and auth need an http or https URL, got a {} URL",
+                url.scheme()
+            )));
+        }
+        if headers.len() > MAX_HEADERS {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "too many headers: {} (maximum {})",
+                headers.len(),
+                MAX_HEADERS
+            )));
+        }
+
+        let mut entries = headers
+            .iter()
+            .map(|(name, value)| {
+                let name = name.trim();
+                if !is_http_token(name) {
+                    return Err(PdfServiceError::InvalidRequest(format!(
+                        "invalid header name {:?}",
+                        name
+                    )));
+                }
+                if value.contains(['\r', '\n', '\0']) {
+                    return Err(PdfServiceError::InvalidRequest(format!(
+                        "header {} must be a single line",
+                        name
+                    )));
+                }
+                Ok(HeaderEntry {
+                    name: name.to_string(),
+                    value: value.trim().to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(auth) = auth {
+            auth.validate()?;
+            if entries
+                .iter()
+                .any(|header| header.name.eq_ignore_ascii_case("authorization"))
+            {
+                return Err(PdfServiceError::InvalidRequest(
+                    "auth and an Authorization header can't both be set".to_string(),
+                ));
+            }
+            entries.push(HeaderEntry {
+                name: "Authorization".to_string(),
+                value: auth.authorization(),
+            });
+        }
+        Ok(Some(Arc::new(Self {
+            origin: origin.ascii_serialization(),
+            headers: entries,
+            auth_scheme: auth.map(AuthSpec::scheme),
+        })))
+    }
+
+    /// Header names, for the debug report.
+    fn names(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .map(|header| header.name.as_str())
+            .collect()
+    }
+
+    /// Whether `url` is on the origin the headers are for.
+    fn applies_to(&self, url: &str) -> bool {
+        url::Url::parse(url).is_ok_and(|url| url.origin().ascii_serialization() == self.origin)
+    }
+
+    /// Add the headers to `request`, replacing ones of the same name.
+    ///
+    /// `rewritten` is a continuation already decided for the request (a
+    /// `POST` rewrite); otherwise one is made from the request's headers.
+    fn apply(
+        &self,
+        rewritten: Option<ContinueRequest>,
+        request_id: &str,
+        request: &Network::Request,
+    ) -> ContinueRequest {
+        let mut continued = rewritten.unwrap_or_else(|| ContinueRequest {
+            request_id: request_id.to_string(),
+            url: None,
+            method: None,
+            post_data: None,
+            headers: None,
+            intercept_response: None,
+        });
+        let mut headers = continued
+            .headers
+            .take()
+            .unwrap_or_else(|| header_entries(request));
+        headers.retain(|header| {
+            !self
+                .headers
+                .iter()
+                .any(|extra| extra.name.eq_ignore_ascii_case(&header.name))
+        });
+        headers.extend(self.headers.iter().cloned());
+        continued.headers = Some(headers);
+        continued
+    }
+}
+
+/// Whether `name` is a valid HTTP header name (an RFC 9110 token).
+fn is_http_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// A paused request's headers, as `Fetch.continueRequest` takes them.
+fn header_entries(request: &Network::Request) -> Vec<HeaderEntry> {
+    request
+        .headers
+        .0
+        .as_ref()
+        .and_then(|headers| headers.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, value)| {
+            Some(HeaderEntry {
+                name: name.clone(),
+                value: value.as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Turn a request's `cookies` into CDP cookies for the site of `url`.
+///
+/// Returns no cookies when `url` does not parse (URL validation reports
+/// that).
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::InvalidRequest`] for a URL that isn't
+/// `http` or `https`, a malformed name, value, or path, or a domain that
+/// doesn't cover the URL's host.
+fn parse_cookies(
+    url: &str,
+    cookies: Option<&[CookieSpec]>,
+) -> Result<Vec<Network::CookieParam>, PdfServiceError> {
+    let Some(cookies) = cookies.filter(|cookies| !cookies.is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let Ok(url) = url::Url::parse(url.trim()) else {
+        return Ok(Vec::new());
+    };
+    let host = match url.host_str() {
+        Some(host) if matches!(url.scheme(), "http" | "https") => host.to_ascii_lowercase(),
+        _ => {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "cookies need an http or https URL, got a {} URL",
+                url.scheme()
+            )));
+        }
+    };
+    if cookies.len() > MAX_COOKIES {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "too many cookies: {} (maximum {})",
+            cookies.len(),
+            MAX_COOKIES
+        )));
+    }
+
+    let invalid = |name: &str, reason: &str| {
+        PdfServiceError::InvalidRequest(format!("invalid cookie {:?}: {}", name, reason))
+    };
+    cookies
+        .iter()
+        .map(|cookie| {
+            let name = cookie.name.trim();
+            if !is_http_token(name) {
+                return Err(invalid(name, "name must be a token"));
+            }
+            if cookie
+                .value
+                .chars()
+                .any(|c| c.is_control() || c == ';' || c == ',')
+            {
+                return Err(invalid(
+                    name,
+                    "value contains ';', ',' or control characters",
+                ));
+            }
+            let domain = match cookie.domain.as_deref().map(str::trim) {
+                None | Some("") => None,
+                Some(domain) => {
+                    let bare = domain.trim_start_matches('.').to_ascii_lowercase();
+                    let covers = host == bare
+                        || host
+                            .strip_suffix(bare.as_str())
+                            .is_some_and(|prefix| prefix.ends_with('.'));
+                    if bare.is_empty() || !covers {
+                        return Err(invalid(
+                            name,
+                            &format!("domain {} doesn't cover {}", domain, host),
+                        ));
+                    }
+                    Some(domain.to_string())
+                }
+            };
+            let path = cookie.path.as_deref().map(str::trim).unwrap_or("/");
+            if !path.starts_with('/') || path.contains(|c: char| c.is_control() || c == ';') {
+                return Err(invalid(name, "path must start with '/'"));
+            }
+            Ok(Network::CookieParam {
+                name: name.to_string(),
+                value: cookie.value.clone(),
+                url: Some(url.to_string()),
+                domain,
+                path: Some(path.to_string()),
+                secure: cookie.secure,
+                http_only: cookie.http_only,
+                same_site: None,
+                expires: None,
+                priority: None,
+                same_party: None,
+                source_scheme: None,
+                source_port: None,
+                partition_key: None,
+            })
+        })
+        .collect()
+}
+
+/// Turns the render's first main-frame request into a form-style `POST`.
+#[derive(Debug)]
+struct PostNavigation {
+    /// Request body, base64-encoded as `Fetch.continueRequest` expects.
+    body: String,
+    /// `Content-Type` sent with the body.
+    content_type: String,
+    /// Set once the navigation request has been rewritten; redirects and
+    /// later navigations go out unchanged.
+    sent: AtomicBool,
+}
+
+impl PostNavigation {
+    /// Read a request's `method`, `post_data`, and `content_type`.
+    ///
+    /// Returns `None` for a `GET` navigation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfServiceError::InvalidRequest`] for a method other than
+    /// `GET` or `POST`, or a body or content type without `POST`.
+    fn new(request: &PdfFromUrlRequest) -> Result<Option<Arc<Self>>, PdfServiceError> {
+        let method = request.method.as_deref().map(str::trim).unwrap_or("GET");
+        if method.eq_ignore_ascii_case("GET") {
+            if request.post_data.is_some() || request.content_type.is_some() {
+                return Err(PdfServiceError::InvalidRequest(
+                    "post_data and content_type need method POST".to_string(),
+                ));
+            }
+            return Ok(None);
+        }
+        if !method.eq_ignore_ascii_case("POST") {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "invalid method {:?}: expected GET or POST",
+                method
+            )));
+        }
+
+        let content_type = request
+            .content_type
+            .as_deref()
+            .map(str::trim)
+            .filter(|content_type| !content_type.is_empty())
+            .unwrap_or(DEFAULT_POST_CONTENT_TYPE);
+        if content_type.contains(['\r', '\n']) {
+            return Err(PdfServiceError::InvalidRequest(
+                "content_type must be a single header value".to_string(),
+            ));
+        }
+        Ok(Some(Arc::new(Self {
+            body: base64::engine::general_purpose::STANDARD
+                .encode(request.post_data.as_deref().unwrap_or_default()),
+            content_type: content_type.to_string(),
+            sent: AtomicBool::new(false),
+        })))
+    }
+
+    /// Rewrite `request` into the `POST`, if it is the navigation's first.
+    fn rewrite(&self, request_id: &str, request: &Network::Request) -> Option<ContinueRequest> {
+        if self.sent.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        let mut headers = header_entries(request);
+        headers.retain(|header| !header.name.eq_ignore_ascii_case("content-type"));
+        headers.push(HeaderEntry {
+            name: "Content-Type".to_string(),
+            value: self.content_type.clone(),
+        });
+
+        log::trace!("Sending navigation to {} as POST", request.url);
+        Some(ContinueRequest {
+            request_id: request_id.to_string(),
+            url: None,
+            method: Some("POST".to_string()),
+            post_data: Some(self.body.clone()),
+            headers: Some(headers),
+            intercept_response: None,
+        })
+    }
+}
+
+/// Enforces the redirect policy on one render's main-frame navigation,
+/// and the URL policy on every document it loads.
+///
+/// Fed every document request by the tab's `Fetch` interceptor. The first
+/// violation is remembered so the render can report it instead of the
+/// generic navigation error Chrome produces.
+#[derive(Debug)]
+struct RedirectGuard {
+    /// Redirect hops allowed before the navigation is failed.
+    max_redirects: u32,
+    /// Whether a hop may leave the origin of the requested URL.
+    allow_cross_origin: bool,
+    /// Hosts documents may be loaded from; `None` when unrestricted.
+    policy: Option<UrlPolicy>,
+    /// Progress of the current navigation.
+    state: Mutex<RedirectState>,
+}
+
+/// Mutable part of [`RedirectGuard`].
+#[derive(Debug, Default)]
+struct RedirectState {
+    /// Redirects followed so far.
+    hops: u32,
+    /// Origin of the navigation's first request.
+    origin: Option<url::Origin>,
+    /// Why the navigation was stopped, if it was.
+    violation: Option<PdfServiceError>,
+}
+
+impl RedirectGuard {
+    fn new(max_redirects: u32, allow_cross_origin: bool, policy: Option<UrlPolicy>) -> Self {
+        Self {
+            max_redirects,
+            allow_cross_origin,
+            policy,
+            state: Mutex::new(RedirectState::default()),
+        }
+    }
+
+    /// Decide whether a main-frame document request may proceed.
+    ///
+    /// `redirected` is `true` when the request follows a redirect response.
+    fn allow(&self, url: &str, redirected: bool) -> bool {
+        let parsed = url::Url::parse(url).ok();
+        let origin = parsed.as_ref().map(|u| u.origin());
+        let denied = self.denied(parsed.as_ref());
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let violation = if !redirected {
+            state.hops = 0;
+            state.origin = origin;
+            denied.map(PdfServiceError::InvalidUrl)
+        } else {
+            state.hops += 1;
+            let reason = if state.hops > self.max_redirects {
+                Some(format!(
+                    "exceeded the limit of {} redirects at {}",
+                    self.max_redirects,
+                    truncate_url(url, 100)
+                ))
+            } else if !self.allow_cross_origin && origin != state.origin {
+                Some(format!(
+                    "cross-origin redirect to {} is not allowed",
+                    truncate_url(url, 100)
+                ))
+            } else {
+                denied.map(|reason| {
+                    format!(
+                        "redirect to {} is not allowed: {}",
+                        truncate_url(url, 100),
+                        reason
+                    )
+                })
+            };
+            reason.map(PdfServiceError::RedirectRejected)
+        };
+
+        match violation {
+            Some(error) => {
+                log::warn!("⚠️ Stopped navigation: {}", error);
+                state.violation.get_or_insert(error);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Decide whether a document request of another frame (an iframe) may
+    /// proceed. Only the URL policy applies.
+    fn allow_frame(&self, url: &str) -> bool {
+        match self.denied(url::Url::parse(url).ok().as_ref()) {
+            Some(reason) => {
+                log::warn!("⚠️ Blocked frame {}: {}", truncate_url(url, 100), reason);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Why the URL policy forbids `url`, if it does.
+    fn denied(&self, url: Option<&url::Url>) -> Option<String> {
+        let policy = self.policy.as_ref()?;
+        match url {
+            Some(url) => policy.check_resolved(url).err(),
+            None => Some("URL cannot be parsed".to_string()),
+        }
+    }
+
+    /// The first policy violation seen, if any.
+    fn violation(&self) -> Option<PdfServiceError> {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .violation
+            .clone()
+    }
+}
+
+impl TryFrom<&PdfFromUrlRequest> for RenderOptions {
+    type Error = PdfServiceError;
+
+    fn try_from(request: &PdfFromUrlRequest) -> Result<Self, Self::Error> {
+        let outputs = request.outputs()?;
+        let thumbnail_format = thumbnail_format(outputs, request.thumbnail_format()?);
+        Ok(Self {
+            wait_duration: request.wait_duration(),
+            wait_expression: validate_wait_expression(request.wait_for_expression())?,
+            landscape: request.landscape,
+            print_background: request.print_background,
+            page: PageSetup::new(
+                request.paper_format()?,
+                [request.paper_width, request.paper_height],
+                [
+                    request.margin_top,
+                    request.margin_bottom,
+                    request.margin_left,
+                    request.margin_right,
+                ],
+                request.scale,
+                request.page_ranges.as_deref(),
+                request.display_header_footer,
+                [
+                    request.header_template.as_deref(),
+                    request.footer_template.as_deref(),
+                ],
+            )?
+            .with_structure(
+                request.generate_tagged_pdf,
+                request.generate_document_outline,
+            ),
+            media: request.emulate_media()?,
+            color_scheme: request.color_scheme()?,
+            print_defaults: Arc::default(),
+            javascript_enabled: request.javascript_enabled(),
+            ignore_tls_errors: request.ignore_tls_errors(),
+            bypass_cache: request.bypass_cache(),
+            offline: request.offline(),
+            isolated: false,
+            geolocation: request.geolocation()?,
+            timezone: validate_timezone(request.timezone.as_deref())?,
+            viewport: Viewport::new(
+                [request.viewport_width, request.viewport_height],
+                request.device_scale_factor,
+                request.mobile,
+            )?,
+            user_agent: validate_user_agent(request.user_agent.as_deref())?,
+            storage: StorageSeed::new(&request.url, request.storage.as_ref())?,
+            headers: ExtraHeaders::new(
+                &request.url,
+                request.headers.as_ref(),
+                request.auth.as_ref(),
+            )?,
+            cookies: parse_cookies(&request.url, request.cookies.as_deref())?,
+            post: PostNavigation::new(request)?,
+            blocked_resources: parse_resource_types(request.block_resources.as_deref())?,
+            blocked_urls: blocked_url_patterns(
+                request.block_resources.as_deref(),
+                request.block_urls.as_deref(),
+            )?,
+            mocks: parse_mock_responses(request.mock_responses.as_ref())?,
+            base_url: None,
+            actions: parse_actions(request.actions.as_deref())?,
+            watermark: watermark_css(request.watermark.as_ref())?,
+            inject_css: validate_injection("inject_css", request.inject_css.as_deref())?,
+            inject_js: validate_inject_js(
+                request.inject_js.as_deref(),
+                request.javascript_enabled(),
+            )?,
+            redirects: None,
+            thumbnail_width: validate_thumbnail_width(thumbnail_width(
+                outputs,
+                request.thumbnail_width(),
+                request.thumbnail_width,
+            ))?,
+            thumbnail_format,
+            thumbnail_quality: validate_thumbnail_quality(
+                thumbnail_format,
+                request.thumbnail_quality,
+            )?,
+            outputs,
+            metadata: DocumentInfo::new(request.metadata.as_ref())?.map(Arc::new),
+            linearize: request.linearize(),
+            pdf_a: validate_pdf_a(
+                request.pdf_a(),
+                request.generate_tagged_pdf,
+                request.generate_document_outline,
+            )?,
+            split_pages: request.split_pages(),
+            post_processors: Vec::new(),
+            trace: request.trace(),
+            debug: request.debug(),
+            trace_dir: None,
+            priority: request.priority()?,
+            cancel: request.cancel.clone(),
+        })
+    }
+}
+
+impl TryFrom<&PdfFromHtmlRequest> for RenderOptions {
+    type Error = PdfServiceError;
+
+    fn try_from(request: &PdfFromHtmlRequest) -> Result<Self, Self::Error> {
+        let outputs = request.outputs()?;
+        let thumbnail_format = thumbnail_format(outputs, request.thumbnail_format()?);
+        let base_url = validate_base_url(request.base_url.as_deref())?;
+        let mocks = parse_mock_responses(request.mock_responses.as_ref())?;
+        Ok(Self {
+            wait_duration: request.wait_duration(),
+            wait_expression: validate_wait_expression(request.wait_for_expression())?,
+            landscape: request.landscape,
+            print_background: request.print_background,
+            page: PageSetup::new(
+                request.paper_format()?,
+                [request.paper_width, request.paper_height],
+                [
+                    request.margin_top,
+                    request.margin_bottom,
+                    request.margin_left,
+                    request.margin_right,
+                ],
+                request.scale,
+                request.page_ranges.as_deref(),
+                request.display_header_footer,
+                [
+                    request.header_template.as_deref(),
+                    request.footer_template.as_deref(),
+                ],
+            )?
+            .with_structure(
+                request.generate_tagged_pdf,
+                request.generate_document_outline,
+            ),
+            media: request.emulate_media()?,
+            color_scheme: request.color_scheme()?,
+            print_defaults: Arc::default(),
+            javascript_enabled: request.javascript_enabled(),
+            ignore_tls_errors: request.ignore_tls_errors(),
+            bypass_cache: request.bypass_cache(),
+            offline: request.offline(),
+            isolated: request.offline() && base_url.is_some(),
+            geolocation: request.geolocation()?,
+            timezone: validate_timezone(request.timezone.as_deref())?,
+            viewport: Viewport::new(
+                [request.viewport_width, request.viewport_height],
+                request.device_scale_factor,
+                request.mobile,
+            )?,
+            user_agent: validate_user_agent(request.user_agent.as_deref())?,
+            storage: None,
+            headers: None,
+            cookies: Vec::new(),
+            post: None,
+            blocked_resources: parse_resource_types(request.block_resources.as_deref())?,
+            blocked_urls: blocked_url_patterns(
+                request.block_resources.as_deref(),
+                request.block_urls.as_deref(),
+            )?,
+            mocks: match &base_url {
+                Some(base_url) => with_document_mock(&mocks, base_url, &request.html),
+                None => mocks,
+            },
+            base_url,
+            actions: parse_actions(request.actions.as_deref())?,
+            watermark: watermark_css(request.watermark.as_ref())?,
+            inject_css: validate_injection("inject_css", request.inject_css.as_deref())?,
+            inject_js: validate_inject_js(
+                request.inject_js.as_deref(),
+                request.javascript_enabled(),
+            )?,
+            redirects: None,
+            thumbnail_width: validate_thumbnail_width(thumbnail_width(
+                outputs,
+                request.thumbnail_width(),
+                request.thumbnail_width,
+            ))?,
+            thumbnail_format,
+            thumbnail_quality: validate_thumbnail_quality(
+                thumbnail_format,
+                request.thumbnail_quality,
+            )?,
+            outputs,
+            metadata: DocumentInfo::new(request.metadata.as_ref())?.map(Arc::new),
+            linearize: request.linearize(),
+            pdf_a: validate_pdf_a(
+                request.pdf_a(),
+                request.generate_tagged_pdf,
+                request.generate_document_outline,
+            )?,
+            split_pages: request.split_pages(),
+            post_processors: Vec::new(),
+            trace: request.trace(),
+            debug: request.debug(),
+            trace_dir: None,
+            priority: request.priority()?,
+            cancel: request.cancel.clone(),
+        })
+    }
+}
+
+/// Page layout requested for a render, in inches; `None` fields use the
+/// pool's print options.
+#[derive(Debug, Clone, Default)]
+struct PageSetup {
+    display_header_footer: Option<bool>,
+    header_template: Option<String>,
+    footer_template: Option<String>,
+    paper_width: Option<f64>,
+    paper_height: Option<f64>,
+    margin_top: Option<f64>,
+    margin_bottom: Option<f64>,
+    margin_left: Option<f64>,
+    margin_right: Option<f64>,
+    scale: Option<f64>,
+    page_ranges: Option<String>,
+    generate_tagged_pdf: Option<bool>,
+    generate_document_outline: Option<bool>,
+}
+
+impl PageSetup {
+    /// Check a request's page fields. The paper format sets both
+    /// dimensions; an explicit width or height overrides it. Setting a
+    /// template turns the header and footer on unless
+    /// `display_header_footer` says otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfServiceError::InvalidRequest`] for a paper dimension
+    /// that is not positive or over [`MAX_PAPER_INCHES`], a negative
+    /// margin, a scale outside 0.1-2.0, a malformed page range, or a
+    /// template over [`MAX_TEMPLATE_LEN`].
+    fn new(
+        format: Option<PaperFormat>,
+        [width, height]: [Option<f64>; 2],
+        [top, bottom, left, right]: [Option<f64>; 4],
+        scale: Option<f64>,
+        page_ranges: Option<&str>,
+        display_header_footer: Option<bool>,
+        [header, footer]: [Option<&str>; 2],
+    ) -> Result<Self, PdfServiceError> {
+        for (name, template) in [("header_template", header), ("footer_template", footer)] {
+            if template.is_some_and(|template| template.len() > MAX_TEMPLATE_LEN) {
+                return Err(PdfServiceError::InvalidRequest(format!(
+                    "{} is longer than {} bytes",
+                    name, MAX_TEMPLATE_LEN
+                )));
+            }
+        }
+        let has_template = header.is_some() || footer.is_some();
+
+        let size = format.map(|format| format.size_inches());
+        let paper_width = width.or(size.map(|(width, _)| width));
+        let paper_height = height.or(size.map(|(_, height)| height));
+        for (name, value) in [("paper_width", paper_width), ("paper_height", paper_height)] {
+            if let Some(value) = value {
+                if !(value > 0.0 && value <= MAX_PAPER_INCHES) {
+                    return Err(PdfServiceError::InvalidRequest(format!(
+                        "{} must be greater than 0 and at most {} inches, got {}",
+                        name, MAX_PAPER_INCHES, value
+                    )));
+                }
+            }
+        }
+
+        let margins = [
+            ("margin_top", top),
+            ("margin_bottom", bottom),
+            ("margin_left", left),
+            ("margin_right", right),
+        ];
+        for (name, value) in margins {
+            if let Some(value) = value {
+                if !(0.0..MAX_PAPER_INCHES).contains(&value) {
+                    return Err(PdfServiceError::InvalidRequest(format!(
+                        "{} must be 0 or more inches, got {}",
+                        name, value
+                    )));
+                }
+            }
+        }
+
+        if let Some(scale) = scale {
+            if !(0.1..=2.0).contains(&scale) {
+                return Err(PdfServiceError::InvalidRequest(format!(
+                    "scale must be between 0.1 and 2.0, got {}",
+                    scale
+                )));
+            }
+        }
+
+        let page_ranges = match page_ranges.map(str::trim) {
+            Some(ranges)
+                if ranges.is_empty()
+                    || !ranges.contains(|c: char| c.is_ascii_digit())
+                    || !ranges
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || matches!(c, '-' | ',' | ' ')) =>
+            {
+                return Err(PdfServiceError::InvalidRequest(format!(
+                    "invalid page_ranges {:?}: expected page numbers and ranges like \"1-5, 8\"",
+                    ranges
+                )));
+            }
+            ranges => ranges.map(str::to_string),
+        };
+
+        Ok(Self {
+            display_header_footer: display_header_footer.or(has_template.then_some(true)),
+            // A lone template gets an empty partner rather than Chrome's
+            // default date and title
+            header_template: header
+                .or(has_template.then_some(EMPTY_TEMPLATE))
+                .map(str::to_string),
+            footer_template: footer
+                .or(has_template.then_some(EMPTY_TEMPLATE))
+                .map(str::to_string),
+            paper_width,
+            paper_height,
+            margin_top: top,
+            margin_bottom: bottom,
+            margin_left: left,
+            margin_right: right,
+            scale,
+            page_ranges,
+            generate_tagged_pdf: None,
+            generate_document_outline: None,
+        })
+    }
+
+    /// Set the request's tagged PDF and document outline fields.
+    fn with_structure(mut self, tagged: Option<bool>, outline: Option<bool>) -> Self {
+        self.generate_tagged_pdf = tagged;
+        self.generate_document_outline = outline;
+        self
+    }
+}
+
+/// Check a readiness expression and wrap it so any truthy value counts.
+///
+/// The expression goes on its own line inside the wrapper, so a trailing
+/// `//` comment cannot swallow the closing parenthesis.
+fn validate_wait_expression(expression: &str) -> Result<Arc<str>, PdfServiceError> {
+    if expression.trim().is_empty() {
+        return Err(PdfServiceError::InvalidRequest(
+            "wait_for_expression must not be empty".to_string(),
+        ));
+    }
+    if expression.len() > MAX_WAIT_EXPRESSION_LEN {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "wait_for_expression must be at most {} bytes",
+            MAX_WAIT_EXPRESSION_LEN
+        )));
+    }
+    Ok(Arc::from(format!("!!(\n{}\n)", expression)))
+}
+
+/// Check a requested timezone looks like an IANA name.
+///
+/// Chrome has the final say on whether the zone exists; this catches
+/// values that cannot be one before a browser is checked out.
+fn validate_timezone(timezone: Option<&str>) -> Result<Option<String>, PdfServiceError> {
+    let Some(timezone) = timezone.map(str::trim).filter(|tz| !tz.is_empty()) else {
+        return Ok(None);
+    };
+    let valid = timezone.len() <= MAX_TIMEZONE_LEN
+        && timezone
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '+'));
+    if !valid {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "invalid timezone {:?}: expected an IANA name such as \"Europe/Paris\"",
+            timezone
+        )));
+    }
+    Ok(Some(timezone.to_string()))
+}
+
+/// Screen emulated with `Emulation.setDeviceMetricsOverride`.
+///
+/// Zero width, height, or scale keeps the browser's own value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Viewport {
+    width: u32,
+    height: u32,
+    device_scale_factor: f64,
+    mobile: bool,
+}
+
+impl Viewport {
+    /// Check a request's viewport fields; `None` if it sets none of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfServiceError::InvalidRequest`] for a dimension outside
+    /// [`VIEWPORT_RANGE`] or a scale outside 0.1 to
+    /// [`MAX_DEVICE_SCALE_FACTOR`].
+    fn new(
+        [width, height]: [Option<u32>; 2],
+        device_scale_factor: Option<f64>,
+        mobile: Option<bool>,
+    ) -> Result<Option<Self>, PdfServiceError> {
+        if width.is_none() && height.is_none() && device_scale_factor.is_none() && mobile.is_none()
+        {
+            return Ok(None);
+        }
+        for (name, value) in [("viewport_width", width), ("viewport_height", height)] {
+            if let Some(value) = value.filter(|value| !VIEWPORT_RANGE.contains(value)) {
+                return Err(PdfServiceError::InvalidRequest(format!(
+                    "{} must be between {} and {} pixels, got {}",
+                    name,
+                    VIEWPORT_RANGE.start(),
+                    VIEWPORT_RANGE.end(),
+                    value
+                )));
+            }
+        }
+        if let Some(factor) = device_scale_factor {
+            if !(0.1..=MAX_DEVICE_SCALE_FACTOR).contains(&factor) {
+                return Err(PdfServiceError::InvalidRequest(format!(
+                    "device_scale_factor must be between 0.1 and {}, got {}",
+                    MAX_DEVICE_SCALE_FACTOR, factor
+                )));
+            }
+        }
+        Ok(Some(Self {
+            width: width.unwrap_or(0),
+            height: height.unwrap_or(0),
+            device_scale_factor: device_scale_factor.unwrap_or(0.0),
+            mobile: mobile.unwrap_or(false),
+        }))
+    }
+}
+
+/// Check a requested user agent, which goes into a request header.
+fn validate_user_agent(user_agent: Option<&str>) -> Result<Option<String>, PdfServiceError> {
+    let Some(user_agent) = user_agent.map(str::trim).filter(|ua| !ua.is_empty()) else {
+        return Ok(None);
+    };
+    if user_agent.len() > MAX_USER_AGENT_LEN {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "user_agent must be at most {} bytes",
+            MAX_USER_AGENT_LEN
+        )));
+    }
+    if user_agent.chars().any(char::is_control) {
+        return Err(PdfServiceError::InvalidRequest(
+            "user_agent must not contain control characters".to_string(),
+        ));
+    }
+    Ok(Some(user_agent.to_string()))
+}
+
+/// Width of the thumbnail to capture: requested by an image in `outputs`
+/// when they are set, otherwise by the `thumbnail` flag.
+fn thumbnail_width(
+    outputs: Option<Outputs>,
+    flagged_width: Option<u32>,
+    width: Option<u32>,
+) -> Option<u32> {
+    match outputs {
+        Some(outputs) => outputs
+            .image
+            .map(|_| width.unwrap_or(DEFAULT_THUMBNAIL_WIDTH)),
+        None => flagged_width,
+    }
+}
+
+/// Format of the thumbnail: an image listed in `outputs` wins over
+/// `thumbnail_format`.
+fn thumbnail_format(outputs: Option<Outputs>, format: ImageFormat) -> ImageFormat {
+    outputs.and_then(|o| o.image).unwrap_or(format)
+}
+
+/// Check a requested thumbnail width against [`THUMBNAIL_WIDTH_RANGE`].
+fn validate_thumbnail_width(width: Option<u32>) -> Result<Option<u32>, PdfServiceError> {
+    match width {
+        Some(width) if !THUMBNAIL_WIDTH_RANGE.contains(&width) => {
+            Err(PdfServiceError::InvalidRequest(format!(
+                "thumbnail_width must be between {} and {} pixels, got {}",
+                THUMBNAIL_WIDTH_RANGE.start(),
+                THUMBNAIL_WIDTH_RANGE.end(),
+                width
+            )))
+        }
+        _ => Ok(width),
+    }
+}
+
+/// Check a requested thumbnail quality against [`THUMBNAIL_QUALITY_RANGE`]
+/// and the format, since PNG has no quality setting.
+fn validate_thumbnail_quality(
+    format: ImageFormat,
+    quality: Option<u32>,
+) -> Result<Option<u32>, PdfServiceError> {
+    match quality {
+        Some(_) if !format.is_lossy() => Err(PdfServiceError::InvalidRequest(format!(
+            "thumbnail_quality applies to jpeg and webp thumbnails, not {}",
+            format
+        ))),
+        Some(quality) if !THUMBNAIL_QUALITY_RANGE.contains(&quality) => {
+            Err(PdfServiceError::InvalidRequest(format!(
+                "thumbnail_quality must be between {} and {}, got {}",
+                THUMBNAIL_QUALITY_RANGE.start(),
+                THUMBNAIL_QUALITY_RANGE.end(),
+                quality
+            )))
+        }
+        _ => Ok(quality),
+    }
+}
+
+/// Validate `block_urls` entries and expand them to CDP URL patterns.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::InvalidRequest`] for blank entries or entries
+/// containing whitespace.
+fn parse_url_patterns(entries: Option<&[String]>) -> Result<Vec<String>, PdfServiceError> {
+    let mut patterns = Vec::new();
+    for entry in entries.unwrap_or_default() {
+        let entry = entry.trim();
+        if entry.is_empty() || entry.contains(char::is_whitespace) {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "invalid block_urls pattern: {:?}",
+                entry
+            )));
+        }
+        for pattern in url_block_patterns(entry) {
+            if !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+    }
+    Ok(patterns)
+}
+
+/// All URL patterns a request blocks: its `block_urls`, plus
+/// [`TRACKER_DOMAINS`] when `block_resources` names `trackers`.
+///
+/// # Errors
+///
+/// Same as [`parse_url_patterns`].
+fn blocked_url_patterns(
+    resources: Option<&[String]>,
+    entries: Option<&[String]>,
+) -> Result<Vec<String>, PdfServiceError> {
+    let mut patterns = parse_url_patterns(entries)?;
+    let trackers = resources
+        .unwrap_or_default()
+        .iter()
+        .any(|name| name.trim().eq_ignore_ascii_case("trackers"));
+    if trackers {
+        for pattern in TRACKER_DOMAINS
+            .iter()
+            .flat_map(|domain| url_block_patterns(domain))
+        {
+            if !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+    }
+    Ok(patterns)
+}
+
+/// Expand one blocklist entry into `Network.setBlockedURLs` patterns.
+///
+/// A bare domain (no `*`, `/` or `:`) blocks the domain and all of its
+/// subdomains over any scheme; anything else is passed through as a
+/// wildcard pattern matched against the full request URL.
+fn url_block_patterns(entry: &str) -> Vec<String> {
+    if entry.contains(['*', '/', ':']) {
+        vec![entry.to_string()]
+    } else {
+        vec![format!("*://{}/*", entry), format!("*://*.{}/*", entry)]
+    }
+}
+
+/// A canned response for requests matching one URL pattern.
+#[derive(Debug, Clone)]
+struct ResourceMock {
+    /// Wildcard pattern matched against the full request URL.
+    pattern: String,
+    status: u16,
+    headers: Vec<HeaderEntry>,
+    /// Body, base64-encoded as `Fetch.fulfillRequest` expects.
+    body: Arc<str>,
+}
+
+impl ResourceMock {
+    /// Answer the paused request with this response.
+    fn fulfill(&self, request_id: String) -> FulfillRequest {
+        FulfillRequest {
+            request_id,
+            response_code: u32::from(self.status),
+            response_headers: Some(self.headers.clone()),
+            binary_response_headers: None,
+            body: Some(self.body.to_string()),
+            response_phrase: None,
+        }
+    }
+}
+
+/// Validate `mock_responses` and expand their patterns like `block_urls`.
+///
+/// Longer patterns sort first, so the most specific match wins.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::InvalidRequest`] for a blank pattern, one
+/// containing whitespace, or a status outside 100-599.
+fn parse_mock_responses(
+    mocks: Option<&BTreeMap<String, MockResponse>>,
+) -> Result<Arc<[ResourceMock]>, PdfServiceError> {
+    let mut parsed = Vec::new();
+    for (entry, mock) in mocks.into_iter().flatten() {
+        let entry = entry.trim();
+        if entry.is_empty() || entry.contains(char::is_whitespace) {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "invalid mock_responses pattern: {:?}",
+                entry
+            )));
+        }
+        if !(100..=599).contains(&mock.status()) {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "mock_responses status for {:?} must be between 100 and 599, got {}",
+                entry,
+                mock.status()
+            )));
+        }
+        let headers: Vec<HeaderEntry> = mock
+            .headers
+            .iter()
+            .map(|(name, value)| HeaderEntry {
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        let body: Arc<str> = base64::engine::general_purpose::STANDARD
+            .encode(&mock.body)
+            .into();
+        for pattern in url_block_patterns(entry) {
+            parsed.push(ResourceMock {
+                pattern,
+                status: mock.status(),
+                headers: headers.clone(),
+                body: Arc::clone(&body),
+            });
+        }
+    }
+    parsed.sort_by_key(|mock| std::cmp::Reverse(mock.pattern.len()));
+    Ok(parsed.into())
+}
+
+/// Validate an HTML request's `base_url`.
+///
+/// The fragment is dropped, as Chrome does when requesting the URL.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::InvalidUrl`] if the URL cannot be parsed or
+/// is not `http`/`https`.
+fn validate_base_url(base_url: Option<&str>) -> Result<Option<String>, PdfServiceError> {
+    let Some(base_url) = base_url.map(str::trim).filter(|url| !url.is_empty()) else {
+        return Ok(None);
+    };
+    let mut parsed = url::Url::parse(base_url)
+        .map_err(|e| PdfServiceError::InvalidUrl(format!("invalid base_url: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(PdfServiceError::InvalidUrl(format!(
+            "base_url must be an http or https URL, got {}",
+            truncate_url(base_url, 100)
+        )));
+    }
+    parsed.set_fragment(None);
+    Ok(Some(parsed.into()))
+}
+
+/// Add a mock serving `html` as the document at `url` to `mocks`.
+fn with_document_mock(mocks: &[ResourceMock], url: &str, html: &str) -> Arc<[ResourceMock]> {
+    let mut mocks = mocks.to_vec();
+    mocks.push(ResourceMock {
+        pattern: url.to_string(),
+        status: 200,
+        headers: vec![HeaderEntry {
+            name: "Content-Type".to_string(),
+            value: "text/html; charset=utf-8".to_string(),
+        }],
+        body: base64::engine::general_purpose::STANDARD
+            .encode(html)
+            .into(),
+    });
+    mocks.sort_by_key(|mock| std::cmp::Reverse(mock.pattern.len()));
+    mocks.into()
+}
+
+/// Serve an upload's files from [`ASSETS_ORIGIN`], alongside the request's
+/// own `mock_responses`.
+///
+/// Each file also answers its URL with a query string appended; any other
+/// URL on the origin gets a 404.
+fn asset_mocks(request: &PdfFromAssetsRequest, mocks: &[ResourceMock]) -> Arc<[ResourceMock]> {
+    let files = std::iter::once((INDEX_PATH, request.document.html.as_bytes())).chain(
+        request
+            .assets
+            .iter()
+            .map(|(path, data)| (path.as_str(), data.as_ref())),
+    );
+
+    let mut parsed = Vec::with_capacity(request.assets.len() * 2 + mocks.len() + 3);
+    for (path, data) in files {
+        let url = asset_url(path);
+        let headers = vec![HeaderEntry {
+            name: "Content-Type".to_string(),
+            value: content_type(path).to_string(),
+        }];
+        let body: Arc<str> = base64::engine::general_purpose::STANDARD
+            .encode(data)
+            .into();
+        for pattern in [format!("{}?*", url), url] {
+            parsed.push(ResourceMock {
+                pattern,
+                status: 200,
+                headers: headers.clone(),
+                body: Arc::clone(&body),
+            });
+        }
+    }
+    parsed.push(ResourceMock {
+        pattern: format!("{}/*", ASSETS_ORIGIN),
+        status: 404,
+        headers: Vec::new(),
+        body: Arc::from(""),
+    });
+    parsed.extend(mocks.iter().cloned());
+    parsed.sort_by_key(|mock| std::cmp::Reverse(mock.pattern.len()));
+    parsed.into()
+}
+
+/// Validate a request's `actions`.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::InvalidRequest`] for more than
+/// [`MAX_ACTIONS`] steps, a blank selector, or a `navigate` step whose URL
+/// is not `http`/`https` (file URLs would bypass `allowed_file_dirs`).
+fn parse_actions(actions: Option<&[PageAction]>) -> Result<Arc<[PageAction]>, PdfServiceError> {
+    let actions = actions.unwrap_or_default();
+    if actions.len() > MAX_ACTIONS {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "too many actions: {} (at most {})",
+            actions.len(),
+            MAX_ACTIONS
+        )));
+    }
+    actions
+        .iter()
+        .enumerate()
+        .map(|(index, action)| {
+            let invalid = |reason: &str| {
+                PdfServiceError::InvalidRequest(format!("action {}: {}", index + 1, reason))
+            };
+            match action {
+                PageAction::Click(selector)
+                | PageAction::WaitFor(selector)
+                | PageAction::Type { selector, .. }
+                    if selector.trim().is_empty() =>
+                {
+                    Err(invalid("selector is empty"))
+                }
+                PageAction::Navigate(url) => match url::Url::parse(url.trim()) {
+                    Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+                        Ok(PageAction::Navigate(parsed.to_string()))
+                    }
+                    Ok(_) => Err(invalid("navigate needs an http or https URL")),
+                    Err(e) => Err(invalid(&format!("invalid navigate URL: {}", e))),
+                },
+                action => Ok(action.clone()),
+            }
+        })
+        .collect()
+}
+
+/// Check CSS or JavaScript a request injects before printing.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::InvalidRequest`] if `source` is blank or
+/// longer than [`MAX_INJECT_LEN`] bytes.
+fn validate_injection(
+    name: &str,
+    source: Option<&str>,
+) -> Result<Option<Arc<str>>, PdfServiceError> {
+    let Some(source) = source else {
+        return Ok(None);
+    };
+    if source.trim().is_empty() {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "{} must not be empty",
+            name
+        )));
+    }
+    if source.len() > MAX_INJECT_LEN {
+        return Err(PdfServiceError::InvalidRequest(format!(
+            "{} must be at most {} bytes",
+            name, MAX_INJECT_LEN
+        )));
+    }
+    Ok(Some(Arc::from(source)))
+}
+
+/// Check a request's `inject_js`, which needs scripts enabled.
+fn validate_inject_js(
+    source: Option<&str>,
+    javascript_enabled: bool,
+) -> Result<Option<Arc<str>>, PdfServiceError> {
+    let script = validate_injection("inject_js", source)?;
+    if script.is_some() && !javascript_enabled {
+        return Err(PdfServiceError::InvalidRequest(
+            "inject_js needs javascript_enabled".to_string(),
+        ));
+    }
+    Ok(script)
+}
+
+/// Check a request's `pdf_a`, which Ghostscript cannot apply without
+/// dropping the tags of a tagged PDF.
+fn validate_pdf_a(
+    pdf_a: bool,
+    tagged: Option<bool>,
+    outline: Option<bool>,
+) -> Result<bool, PdfServiceError> {
+    let conflict = [
+        ("generate_tagged_pdf", tagged),
+        ("generate_document_outline", outline),
+    ]
+    .into_iter()
+    .find(|(_, set)| *set == Some(true));
+    match conflict {
+        Some((name, _)) if pdf_a => Err(PdfServiceError::InvalidRequest(format!(
+            "pdf_a cannot be combined with {}",
+            name
+        ))),
+        _ => Ok(pdf_a),
+    }
+}
+
+/// A step as named in logs and errors; typed text is left out, since it
+/// is often a password.
+fn describe_action(action: &PageAction) -> String {
+    match action {
+        PageAction::Click(selector) => format!("click {:?}", selector),
+        PageAction::Type { selector, .. } => format!("type into {:?}", selector),
+        PageAction::WaitFor(selector) => format!("wait_for {:?}", selector),
+        PageAction::Navigate(url) => format!("navigate {}", truncate_url(url, 100)),
+    }
+}
+
+/// Whether `url` matches a pattern in which `*` stands for any run of
+/// characters, as in `Network.setBlockedURLs`.
+fn matches_url_pattern(pattern: &str, url: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = url.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No wildcard: the whole URL must match
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Escape a wildcard pattern for `Fetch.enable`, where `?` and `\` are
+/// special too.
+fn fetch_url_pattern(pattern: &str) -> String {
+    pattern.replace('\\', "\\\\").replace('?', "\\?")
+}
+
+/// Map `block_resources` names to CDP resource types.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::InvalidRequest`] for names outside
+/// [`BLOCKABLE_RESOURCES`].
+fn parse_resource_types(names: Option<&[String]>) -> Result<Vec<ResourceType>, PdfServiceError> {
+    let mut types = Vec::new();
+    for name in names.unwrap_or_default() {
+        let resource_type = match name.trim().to_ascii_lowercase().as_str() {
+            "image" => ResourceType::Image,
+            "media" => ResourceType::Media,
+            "font" => ResourceType::Font,
+            "stylesheet" => ResourceType::Stylesheet,
+            "script" => ResourceType::Script,
+            "xhr" => ResourceType::Xhr,
+            "fetch" => ResourceType::Fetch,
+            "websocket" => ResourceType::WebSocket,
+            // Blocked by domain; see `blocked_url_patterns`
+            "trackers" => continue,
+            _ => {
+                return Err(PdfServiceError::InvalidRequest(format!(
+                    "unknown resource type '{}' in block_resources (expected one of: {})",
+                    name,
+                    BLOCKABLE_RESOURCES.join(", ")
+                )));
+            }
+        };
+        if !types.contains(&resource_type) {
+            types.push(resource_type);
+        }
+    }
+    Ok(types)
+}
+
+/// Core PDF generation logic.
+///
+/// This function performs the actual work of:
+/// 1. Checking out a browser tab
+/// 2. Navigating to the URL or loading the HTML
+/// 3. Waiting for JavaScript completion
+/// 4. Generating the PDF
+/// 5. Releasing the tab
+///
+/// # Arguments
+///
+/// * `browser` - Browser handle from the pool
+/// * `source` - URL to navigate to, or HTML content to load
+/// * `options` - Wait time, page layout, and JavaScript settings
+///
+/// # Returns
+///
+/// * `Ok(Rendered)` - The raw PDF binary data, and the thumbnail if requested
+/// * `Err(PdfServiceError)` - If any step fails
+///
+/// # Tab Lifecycle
+///
+/// By default a new tab is created for each PDF generation and closed
+/// afterward. This ensures clean state and prevents memory leaks from
+/// accumulating page resources.
+///
+/// ```text
+/// Browser Instance
+/// ├── Tab 1 (new) ◀── Created for this request
+/// │   ├── Navigate to URL / load HTML
+/// │   ├── Wait for JS
+/// │   ├── Generate PDF
+/// │   └── Close tab ◀── Cleanup
+/// └── (available for next request)
+/// ```
+///
+/// With [`reuse_tabs`](crate::BrowserPoolConfig::reuse_tabs) enabled, the
+/// browser's persistent tab is used instead and reset to `about:blank`
+/// afterward. A tab that fails mid-render, or whose per-request settings
+/// cannot be undone, is always closed.
+fn generate_pdf_internal(
+    browser: &BrowserHandle,
+    source: PageSource<'_>,
+    options: &RenderOptions,
+) -> Result<Rendered, PdfServiceError> {
+    let options = &options.clone().with_pool_policy(browser.pool_config());
+    let start_time = Instant::now();
+
+    let resolved;
+    let source = match source {
+        PageSource::Url(url) => {
+            resolved = resolve_file_url(url, &browser.pool_config().allowed_file_dirs)?;
+            PageSource::Url(&resolved)
+        }
+        source => source,
+    };
+    require_trace_dir(options.trace, browser.pool_config())?;
+
+    log::trace!("Checking out browser tab");
+    let tab = browser.checkout_tab().map_err(|e| {
+        log::error!("❌ {}", e);
+        PdfServiceError::TabCreationFailed(e.to_string())
+    })?;
+    if let Some(cancel) = &options.cancel {
+        cancel.attach_tab(&tab);
+    }
+
+    let result = apply_tab_settings(&tab, options).and_then(|()| {
+        let trace = start_trace(&tab, options);
+        let debug = start_debug(&tab, options, start_time);
+        let result = render_in_tab(&tab, source, options, debug.as_ref());
+        let result = finish_debug(&tab, debug, options, result);
+        finish_trace(&tab, trace, options, result)
+    });
+    let result = finish_cancellable(options, result);
+    return_tab(browser, tab, options, result.is_ok());
+
+    let result = result.and_then(|rendered| rendered.post_processed(options));
+
+    log::debug!("Total PDF generation time: {:?}", start_time.elapsed());
+
+    result
+}
+
+/// Load `source` into a tab of `browser` and print it to a Chrome-side
+/// stream, handing browser and tab to the returned [`PrintStream`].
+///
+/// Follows [`generate_pdf_internal`], minus the tracing and debugging that
+/// [`check_streamable`] rules out.
+fn open_pdf_stream(
+    browser: BrowserHandle,
+    source: PageSource<'_>,
+    options: &RenderOptions,
+) -> Result<PrintStream, PdfServiceError> {
+    let options = options.clone().with_pool_policy(browser.pool_config());
+    if !options.post_processors.is_empty() {
+        return Err(PdfServiceError::InvalidRequest(
+            "stream cannot be used while the server post-processes PDFs".to_string(),
+        ));
+    }
+
+    let resolved;
+    let source = match source {
+        PageSource::Url(url) => {
+            resolved = resolve_file_url(url, &browser.pool_config().allowed_file_dirs)?;
+            PageSource::Url(&resolved)
+        }
+        source => source,
+    };
+
+    log::trace!("Checking out browser tab");
+    let tab = browser.checkout_tab().map_err(|e| {
+        log::error!("❌ {}", e);
+        PdfServiceError::TabCreationFailed(e.to_string())
+    })?;
+    if let Some(cancel) = &options.cancel {
+        cancel.attach_tab(&tab);
+    }
+
+    let result = apply_tab_settings(&tab, &options).and_then(|()| {
+        load_page(&tab, source, &options, None)?;
+        log::trace!("Printing PDF to a stream");
+        open_print_stream(&tab, options.print_options())
+    });
+
+    match finish_cancellable(&options, result) {
+        Ok(handle) => Ok(PrintStream {
+            browser,
+            tab: Some(tab),
+            handle,
+            options,
+            finished: false,
+        }),
+        Err(e) => {
+            return_tab(&browser, tab, &options, false);
+            Err(e)
+        }
+    }
+}
+
+/// A PDF printed to a Chrome-side stream, read by [`PdfStream`].
+///
+/// Holds the render's tab until dropped. Dropping closes the stream and
+/// hands the tab back, parked for reuse only if the whole PDF was read.
+pub(crate) struct PrintStream {
+    browser: BrowserHandle,
+    /// The render's tab; taken when dropped.
+    tab: Option<Arc<headless_chrome::Tab>>,
+    /// Handle of the stream in Chrome.
+    handle: String,
+    options: RenderOptions,
+    /// Whether the whole PDF has been read.
+    finished: bool,
+}
+
+impl PrintStream {
+    /// Read the next chunk of the PDF; `None` once all of it has been read.
+    pub(crate) fn read_chunk(&mut self) -> Result<Option<Vec<u8>>, PdfServiceError> {
+        let Some(tab) = self.tab.as_ref().filter(|_| !self.finished) else {
+            return Ok(None);
+        };
+        let (chunk, eof) = read_print_stream(tab, &self.handle)?;
+        self.finished = eof;
+        if chunk.is_empty() && eof {
+            Ok(None)
+        } else {
+            Ok(Some(chunk))
+        }
+    }
+}
+
+impl Drop for PrintStream {
+    fn drop(&mut self) {
+        if let Some(tab) = self.tab.take() {
+            close_print_stream(&tab, &self.handle);
+            return_tab(&self.browser, tab, &self.options, self.finished);
+        }
+    }
+}
+
+/// Hand a render's tab back to its browser: clear what the render left
+/// behind, then park the tab for reuse if `succeeded`, or close it.
+///
+/// Best effort - never fails the request.
+fn return_tab(
+    browser: &BrowserHandle,
+    tab: Arc<headless_chrome::Tab>,
+    options: &RenderOptions,
+    succeeded: bool,
+) {
+    clear_storage(&tab, options);
+    clear_cookies(&tab, options);
+
+    if succeeded && reset_tab_settings(&tab, options) {
+        browser.release_tab(tab);
+    } else {
+        browser.discard_tab(tab);
+    }
+}
+
+/// Apply per-request settings to a freshly checked-out tab.
+///
+/// Fails closed: if scripts were requested off or resources blocked but the
+/// tab cannot be configured that way, the render does not happen.
+fn apply_tab_settings(
+    tab: &headless_chrome::Tab,
+    options: &RenderOptions,
+) -> Result<(), PdfServiceError> {
+    if !options.javascript_enabled {
+        log::trace!("Disabling JavaScript for this render");
+        set_script_execution_disabled(tab, true).map_err(|e| {
+            log::error!("❌ Failed to disable JavaScript: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to disable JavaScript: {}", e))
+        })?;
+    }
+    if options.ignore_tls_errors {
+        log::warn!(
+            "⚠️ TLS certificate errors are IGNORED for this render (ignore_tls_errors=true); \
+             the connection can be intercepted"
+        );
+        set_ignore_certificate_errors(tab, true).map_err(|e| {
+            log::error!("❌ Failed to ignore certificate errors: {}", e);
+            PdfServiceError::TabCreationFailed(format!(
+                "failed to ignore certificate errors: {}",
+                e
+            ))
+        })?;
+    }
+    if options.bypass_cache {
+        log::trace!("Bypassing the HTTP cache for this render");
+        set_cache_disabled(tab, true).map_err(|e| {
+            log::error!("❌ Failed to disable the HTTP cache: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to disable the HTTP cache: {}", e))
+        })?;
+    }
+    if options.media.is_some() || options.color_scheme.is_some() {
+        log::trace!(
+            "Emulating media {:?}, color scheme {:?}",
+            options.media,
+            options.color_scheme
+        );
+        let media = options.media.map_or("", |media| media.as_str());
+        set_emulated_media(tab, media, options.color_scheme).map_err(|e| {
+            log::error!("❌ Failed to emulate media: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to emulate media: {}", e))
+        })?;
+    }
+    if let Some(timezone) = &options.timezone {
+        log::trace!("Emulating timezone {}", timezone);
+        set_timezone(tab, timezone).map_err(|e| {
+            log::warn!("⚠️ Chrome rejected timezone {:?}: {}", timezone, e);
+            PdfServiceError::InvalidRequest(format!("unknown timezone {:?}", timezone))
+        })?;
+    }
+    if let Some(viewport) = options.viewport {
+        log::trace!("Emulating viewport {:?}", viewport);
+        set_viewport(tab, Some(viewport)).map_err(|e| {
+            log::error!("❌ Failed to emulate viewport: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to emulate viewport: {}", e))
+        })?;
+    }
+    if let Some(user_agent) = &options.user_agent {
+        log::trace!("Overriding user agent: {}", user_agent);
+        tab.call_method(Emulation::SetUserAgentOverride {
+            user_agent: user_agent.clone(),
+            accept_language: None,
+            platform: None,
+            user_agent_metadata: None,
+        })
+        .map_err(|e| {
+            log::error!("❌ Failed to override user agent: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to override user agent: {}", e))
+        })?;
+    }
+    if let Some(storage) = &options.storage {
+        log::trace!("Seeding web storage for {}", storage.origin);
+        tab.call_method(Page::AddScriptToEvaluateOnNewDocument {
+            source: storage.script.clone(),
+            world_name: None,
+            include_command_line_api: None,
+            run_immediately: None,
+        })
+        .map_err(|e| {
+            log::error!("❌ Failed to seed web storage: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to seed web storage: {}", e))
+        })?;
+    }
+    if !options.cookies.is_empty() {
+        log::trace!("Setting {} cookies", options.cookies.len());
+        tab.call_method(Network::SetCookies {
+            cookies: options.cookies.clone(),
+        })
+        .map_err(|e| {
+            log::error!("❌ Failed to set cookies: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to set cookies: {}", e))
+        })?;
+    }
+    if let Some(geolocation) = options.geolocation {
+        log::trace!("Emulating geolocation {:?}", geolocation);
+        set_geolocation(tab, Some(geolocation)).map_err(|e| {
+            log::error!("❌ Failed to emulate geolocation: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to emulate geolocation: {}", e))
+        })?;
+    }
+    if !options.blocked_urls.is_empty() {
+        log::trace!("Blocking URL patterns: {:?}", options.blocked_urls);
+        set_blocked_urls(tab, options.blocked_urls.clone()).map_err(|e| {
+            log::error!("❌ Failed to set blocked URLs: {}", e);
+            PdfServiceError::TabCreationFailed(format!("failed to set blocked URLs: {}", e))
+        })?;
+    }
+    if uses_interception(options) {
+        log::trace!(
+            "Intercepting requests (blocked types: {:?}, redirect policy: {})",
+            options.blocked_resources,
+            options.redirects.is_some()
+        );
+        enable_interception(tab, options).map_err(|e| {
+            log::error!("❌ Failed to enable request interception: {}", e);
+            PdfServiceError::TabCreationFailed(format!(
+                "failed to enable request interception: {}",
+                e
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+/// Stop tracking the render's tab for cancellation, and report a
+/// cancelled render as [`PdfServiceError::Cancelled`] in place of whatever
+/// failure closing its tab caused.
+///
+/// A failed result means the tab is discarded rather than reused.
+fn finish_cancellable<T>(
+    options: &RenderOptions,
+    result: Result<T, PdfServiceError>,
+) -> Result<T, PdfServiceError> {
+    let Some(cancel) = &options.cancel else {
+        return result;
+    };
+
+    cancel.detach_tab();
+    if cancel.is_cancelled() {
+        log::debug!("Render cancelled, returning browser to the pool");
+        return Err(PdfServiceError::Cancelled(
+            "render cancelled while in progress".to_string(),
+        ));
+    }
+    result
+}
+
+/// Whether the render has been cancelled.
+fn is_cancelled(cancel: Option<&CancelHandle>) -> bool {
+    cancel.is_some_and(CancelHandle::is_cancelled)
+}
+
+/// Reject a traced render when the pool has nowhere to save traces.
+fn require_trace_dir(
+    trace: bool,
+    config: &crate::BrowserPoolConfig,
+) -> Result<(), PdfServiceError> {
+    if trace && config.trace_dir.is_none() {
+        return Err(PdfServiceError::InvalidRequest(
+            "trace is not enabled on this server (set trace_dir / BROWSER_TRACE_DIR)".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Start recording the render's performance trace, if it asked for one.
+///
+/// Tracing is best effort: on failure the render proceeds untraced.
+fn start_trace(tab: &headless_chrome::Tab, options: &RenderOptions) -> Option<TraceRecorder> {
+    let dir = options.trace_dir.as_deref()?;
+    match check_trace_dir(dir).and_then(|()| TraceRecorder::start(tab)) {
+        Ok(recorder) => Some(recorder),
+        Err(e) => {
+            log::warn!("⚠️ Failed to start performance trace: {}", e);
+            None
+        }
+    }
+}
+
+/// Save the render's trace and attach its ID to a successful result.
+///
+/// A failed render keeps its error; the trace ID is logged with it instead.
+fn finish_trace(
+    tab: &headless_chrome::Tab,
+    recorder: Option<TraceRecorder>,
+    options: &RenderOptions,
+    result: Result<Rendered, PdfServiceError>,
+) -> Result<Rendered, PdfServiceError> {
+    let (Some(recorder), Some(dir)) = (recorder, options.trace_dir.as_deref()) else {
+        return result;
+    };
+
+    match recorder.finish(tab, dir) {
+        Ok(trace_id) => {
+            log::info!("Performance trace {} saved in {}", trace_id, dir.display());
+            match result {
+                Ok(rendered) => Ok(Rendered {
+                    trace_id: Some(trace_id),
+                    ..rendered
+                }),
+                Err(e) => {
+                    log::warn!("Render failed ({}); see performance trace {}", e, trace_id);
+                    Err(e)
+                }
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠️ Failed to save performance trace: {}", e);
+            result
+        }
+    }
+}
+
+/// Start recording the render's debug report, if it asked for one.
+///
+/// Fails open like tracing: on failure the render proceeds and returns
+/// the PDF.
+fn start_debug(
+    tab: &headless_chrome::Tab,
+    options: &RenderOptions,
+    start: Instant,
+) -> Option<DebugRecorder> {
+    if !options.debug {
+        return None;
+    }
+    match DebugRecorder::start(tab, start) {
+        Ok(recorder) => Some(recorder),
+        Err(e) => {
+            log::warn!("⚠️ Failed to start debug report: {}", e);
+            None
+        }
+    }
+}
+
+/// Attach the render's debug report to a successful result.
+///
+/// A failed render keeps its error; the report is only logged.
+fn finish_debug(
+    tab: &headless_chrome::Tab,
+    recorder: Option<DebugRecorder>,
+    options: &RenderOptions,
+    result: Result<Rendered, PdfServiceError>,
+) -> Result<Rendered, PdfServiceError> {
+    let Some(recorder) = recorder else {
+        return result;
+    };
+
+    let pdf_size = result.as_ref().map_or(0, |rendered| rendered.pdf.len());
+    let report = recorder.finish(tab, pdf_size, options.summary());
+    match result {
+        Ok(rendered) => Ok(Rendered {
+            report: Some(report),
+            ..rendered
+        }),
+        Err(e) => {
+            log::debug!(
+                "Render failed ({}); debug report: {}",
+                e,
+                serde_json::to_string(&report).unwrap_or_default()
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Whether the render needs CDP `Fetch` interception.
+fn uses_interception(options: &RenderOptions) -> bool {
+    !options.blocked_resources.is_empty()
+        || options.redirects.is_some()
+        || options.post.is_some()
+        || options.headers.is_some()
+        || !options.mocks.is_empty()
+        || options.isolated
+}
+
+/// Undo [`apply_tab_settings`] so the tab can serve the next request.
+///
+/// Returns `false` if the tab must be closed instead of reused.
+fn reset_tab_settings(tab: &headless_chrome::Tab, options: &RenderOptions) -> bool {
+    if options.storage.is_some() {
+        // The seeding script and sessionStorage live as long as the tab
+        log::trace!("Closing tab that had web storage seeded");
+        return false;
+    }
+    if options.debug {
+        // Recording enabled domains that would keep reporting to nobody
+        log::trace!("Closing tab that recorded a debug report");
+        return false;
+    }
+    if options.user_agent.is_some() {
+        // The browser's own override (stealth) isn't known here to restore
+        log::trace!("Closing tab that had its user agent overridden");
+        return false;
+    }
+    if !options.javascript_enabled {
+        if let Err(e) = set_script_execution_disabled(tab, false) {
+            log::warn!("⚠️ Failed to re-enable JavaScript, closing tab: {}", e);
+            return false;
+        }
+    }
+    if options.ignore_tls_errors {
+        if let Err(e) = set_ignore_certificate_errors(tab, false) {
+            log::warn!(
+                "⚠️ Failed to restore certificate checks, closing tab: {}",
+                e
+            );
+            return false;
+        }
+    }
+    if options.bypass_cache {
+        if let Err(e) = set_cache_disabled(tab, false) {
+            log::warn!("⚠️ Failed to re-enable the HTTP cache, closing tab: {}", e);
+            return false;
+        }
+    }
+    if options.media.is_some() || options.color_scheme.is_some() {
+        if let Err(e) = set_emulated_media(tab, "", None) {
+            log::warn!("⚠️ Failed to clear emulated media, closing tab: {}", e);
+            return false;
+        }
+    }
+    if options.timezone.is_some() {
+        if let Err(e) = set_timezone(tab, "") {
+            log::warn!("⚠️ Failed to clear timezone, closing tab: {}", e);
+            return false;
+        }
+    }
+    if options.viewport.is_some() {
+        if let Err(e) = set_viewport(tab, None) {
+            log::warn!("⚠️ Failed to clear viewport, closing tab: {}", e);
+            return false;
+        }
+    }
+    if options.geolocation.is_some() {
+        if let Err(e) = set_geolocation(tab, None) {
+            log::warn!("⚠️ Failed to clear geolocation, closing tab: {}", e);
+            return false;
+        }
+    }
+    if !options.blocked_urls.is_empty() {
+        if let Err(e) = set_blocked_urls(tab, Vec::new()) {
+            log::warn!("⚠️ Failed to clear blocked URLs, closing tab: {}", e);
+            return false;
+        }
+    }
+    if options.offline {
+        if let Err(e) = set_offline(tab, false) {
+            log::warn!("⚠️ Failed to restore network access, closing tab: {}", e);
+            return false;
+        }
+    }
+    if uses_interception(options) {
+        if let Err(e) = tab.disable_fetch() {
+            log::warn!(
+                "⚠️ Failed to disable request interception, closing tab: {}",
+                e
+            );
+            return false;
+        }
+    }
+    if options.thumbnail_width.is_some() {
+        if let Err(e) = clear_print_layout(tab) {
+            log::warn!("⚠️ Failed to restore screen layout, closing tab: {}", e);
+            return false;
+        }
+    }
+    true
+}
+
+/// Replace the tab's `Network.setBlockedURLs` list.
+///
+/// The network domain is only needed while a list is active, so it is
+/// enabled for a non-empty list and disabled again when clearing.
+fn set_blocked_urls(tab: &headless_chrome::Tab, urls: Vec<String>) -> Result<(), String> {
+    let clearing = urls.is_empty();
+    if !clearing {
+        tab.call_method(Network::Enable {
+            max_total_buffer_size: None,
+            max_resource_buffer_size: None,
+            max_post_data_size: None,
+        })
+        .map_err(|e| e.to_string())?;
+    }
+    tab.call_method(Network::SetBlockedURLs { urls })
+        .map_err(|e| e.to_string())?;
+    if clearing {
+        tab.call_method(Network::Disable(None))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Pause requests with CDP `Fetch` to enforce resource blocking and the
+/// redirect policy, and to add extra headers.
+///
+/// Only blocked resource types, requests to the origin extra headers are
+/// for, and, with a redirect or URL policy, document requests are paused.
+/// Blocked types are failed with `BlockedByClient`; documents go through
+/// the [`RedirectGuard`]; requests to the headers' origin get them;
+/// everything else continues untouched.
+fn enable_interception(tab: &headless_chrome::Tab, options: &RenderOptions) -> Result<(), String> {
+    let mut patterns: Vec<RequestPattern> = options
+        .blocked_resources
+        .iter()
+        .map(|resource_type| RequestPattern {
+            url_pattern: Some("*".to_string()),
+            resource_Type: Some(resource_type.clone()),
+            request_stage: Some(RequestStage::Request),
+        })
+        .collect();
+    if options.redirects.is_some() || options.post.is_some() {
+        patterns.push(RequestPattern {
+            url_pattern: Some("*".to_string()),
+            resource_Type: Some(ResourceType::Document),
+            request_stage: Some(RequestStage::Request),
+        });
+    }
+
+    if let Some(headers) = &options.headers {
+        patterns.push(RequestPattern {
+            url_pattern: Some(format!("{}/*", headers.origin)),
+            resource_Type: None,
+            request_stage: Some(RequestStage::Request),
+        });
+    }
+
+    patterns.extend(options.mocks.iter().map(|mock| RequestPattern {
+        url_pattern: Some(fetch_url_pattern(&mock.pattern)),
+        resource_Type: None,
+        request_stage: Some(RequestStage::Request),
+    }));
+    if options.isolated {
+        patterns.push(RequestPattern {
+            url_pattern: Some("*".to_string()),
+            resource_Type: None,
+            request_stage: Some(RequestStage::Request),
+        });
+    }
+
+    let blocked = options.blocked_resources.clone();
+    let redirects = options.redirects.clone();
+    let post = options.post.clone();
+    let extra_headers = options.headers.clone();
+    let mocks = Arc::clone(&options.mocks);
+    let isolated = options.isolated;
+    // A page target's main frame shares the target's ID
+    let main_frame = tab.get_target_id().clone();
+
+    tab.enable_request_interception(Arc::new(
+        move |_transport, _session_id, event: RequestPausedEvent| {
+            let params = event.params;
+            if let Some(mock) = mocks
+                .iter()
+                .find(|mock| matches_url_pattern(&mock.pattern, &params.request.url))
+            {
+                log::trace!("Mocked {} request: {}", mock.status, params.request.url);
+                return RequestPausedDecision::Fulfill(mock.fulfill(params.request_id));
+            }
+            if isolated {
+                log::trace!("Blocked offline request: {}", params.request.url);
+                return RequestPausedDecision::Fail(FailRequest {
+                    request_id: params.request_id,
+                    error_reason: ErrorReason::InternetDisconnected,
+                });
+            }
+            let fail = if blocked.contains(&params.resource_Type) {
+                log::trace!(
+                    "Blocked {:?} request: {}",
+                    params.resource_Type,
+                    params.request.url
+                );
+                true
+            } else if let Some(guard) = redirects
+                .as_ref()
+                .filter(|_| params.resource_Type == ResourceType::Document)
+            {
+                if params.frame_id == main_frame {
+                    !guard.allow(&params.request.url, params.redirected_request_id.is_some())
+                } else {
+                    !guard.allow_frame(&params.request.url)
+                }
+            } else {
+                false
+            };
+
+            if fail {
+                RequestPausedDecision::Fail(FailRequest {
+                    request_id: params.request_id,
+                    error_reason: ErrorReason::BlockedByClient,
+                })
+            } else {
+                let rewritten = post.as_ref().filter(|_| {
+                    params.frame_id == main_frame
+                        && params.resource_Type == ResourceType::Document
+                        && params.redirected_request_id.is_none()
+                });
+                let rewritten =
+                    rewritten.and_then(|post| post.rewrite(&params.request_id, &params.request));
+                match extra_headers
+                    .as_ref()
+                    .filter(|headers| headers.applies_to(&params.request.url))
+                {
+                    Some(headers) => RequestPausedDecision::Continue(Some(headers.apply(
+                        rewritten,
+                        &params.request_id,
+                        &params.request,
+                    ))),
+                    None => RequestPausedDecision::Continue(rewritten),
+                }
+            }
+        },
+    ))
+    .map_err(|e| e.to_string())?;
+
+    tab.enable_fetch(Some(&patterns), None)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Report a redirect or URL policy violation in place of the navigation
+/// result.
+///
+/// Chrome may either fail the navigation or settle on its error page when
+/// a redirect is blocked, so the guard is consulted in both cases.
+fn check_redirects(
+    options: &RenderOptions,
+    result: Result<(), PdfServiceError>,
+) -> Result<(), PdfServiceError> {
+    match options
+        .redirects
+        .as_ref()
+        .and_then(|guard| guard.violation())
+    {
+        Some(error) => Err(error),
+        None => result,
+    }
+}
+
+/// Take the tab offline for an `offline` render.
+///
+/// Fails closed: a render that asked to be offline does not continue with
+/// network access.
+fn go_offline(tab: &headless_chrome::Tab, offline: bool) -> Result<(), PdfServiceError> {
+    if !offline {
+        return Ok(());
+    }
+    log::trace!("Taking the tab offline");
+    set_offline(tab, true).map_err(|e| {
+        log::error!("❌ Failed to take the tab offline: {}", e);
+        PdfServiceError::TabCreationFailed(format!("failed to take the tab offline: {}", e))
+    })
+}
+
+/// Cut or restore the tab's network access (CDP
+/// `Network.emulateNetworkConditions`).
+///
+/// Like [`set_blocked_urls`], enables the network domain while offline and
+/// disables it again when restoring.
+fn set_offline(tab: &headless_chrome::Tab, offline: bool) -> Result<(), String> {
+    if offline {
+        tab.call_method(Network::Enable {
+            max_total_buffer_size: None,
+            max_resource_buffer_size: None,
+            max_post_data_size: None,
+        })
+        .map_err(|e| e.to_string())?;
+    }
+    tab.call_method(Network::EmulateNetworkConditions {
+        offline,
+        latency: 0.0,
+        download_throughput: -1.0,
+        upload_throughput: -1.0,
+        connection_Type: None,
+        packet_loss: None,
+        packet_queue_length: None,
+        packet_reordering: None,
+    })
+    .map_err(|e| e.to_string())?;
+    if !offline {
+        tab.call_method(Network::Disable(None))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Toggle the HTTP cache (CDP `Network.setCacheDisabled`).
+///
+/// The `Network` domain is enabled while the cache is off, as with
+/// [`set_offline`].
+fn set_cache_disabled(tab: &headless_chrome::Tab, disabled: bool) -> Result<(), String> {
+    if disabled {
+        tab.call_method(Network::Enable {
+            max_total_buffer_size: None,
+            max_resource_buffer_size: None,
+            max_post_data_size: None,
+        })
+        .map_err(|e| e.to_string())?;
+    }
+    tab.call_method(Network::SetCacheDisabled {
+        cache_disabled: disabled,
+    })
+    .map_err(|e| e.to_string())?;
+    if !disabled {
+        tab.call_method(Network::Disable(None))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Toggle certificate verification (CDP `Security.setIgnoreCertificateErrors`).
+fn set_ignore_certificate_errors(tab: &headless_chrome::Tab, ignore: bool) -> Result<(), String> {
+    tab.call_method(Security::SetIgnoreCertificateErrors { ignore })
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Clear everything the seeded origin stored in the browser profile, so
+/// the next render on it - from any API key - does not inherit a
+/// logged-in app's state.
+///
+/// The app booted with the seeded token may have kept it, or tokens it
+/// got in exchange, anywhere: `localStorage`, IndexedDB, cookies, cache
+/// storage, or a service worker. All of them are cleared, not just the
+/// seeded `localStorage`.
+///
+/// Runs whether or not the render succeeded. Best effort: a failure is
+/// logged, since the render's own result stands either way.
+fn clear_storage(tab: &headless_chrome::Tab, options: &RenderOptions) {
+    let Some(storage) = &options.storage else {
+        return;
+    };
+    if let Err(e) = tab.call_method(Storage::ClearDataForOrigin {
+        origin: storage.origin.clone(),
+        storage_Types: "all".to_string(),
+    }) {
+        log::error!("❌ Failed to clear storage for {}: {}", storage.origin, e);
+    }
+}
+
+/// Empty the browser's cookie jar after a render that sent credentials, so
+/// later renders - from any API key - send neither the render's cookies
+/// nor those the site set in response (a rotated session ID, a CSRF token).
+///
+/// Pooled tabs share the browser's cookie jar, so deleting only the
+/// injected cookies is not enough. With
+/// [`tabs_per_browser`](crate::BrowserPoolConfig::tabs_per_browser) above 1
+/// this also clears the cookies of renders running in the browser's other
+/// tabs. Like [`clear_storage`], runs whether or not the render succeeded
+/// and only logs failures.
+fn clear_cookies(tab: &headless_chrome::Tab, options: &RenderOptions) {
+    if !sends_credentials(options) {
+        return;
+    }
+    if let Err(e) = tab.call_method(Network::ClearBrowserCookies(None)) {
+        log::error!("❌ Failed to clear browser cookies: {}", e);
+    }
+}
+
+/// Whether a render sends credentials: cookies, headers (including
+/// `auth`), or seeded storage, which the site or the app's API may answer
+/// with session cookies.
+fn sends_credentials(options: &RenderOptions) -> bool {
+    !options.cookies.is_empty() || options.headers.is_some() || options.storage.is_some()
+}
+
+/// Render the page with CSS media type `media` and, if given,
+/// `color_scheme` (CDP `Emulation.setEmulatedMedia`); an empty type and no
+/// scheme restore the defaults.
+fn set_emulated_media(
+    tab: &headless_chrome::Tab,
+    media: &str,
+    color_scheme: Option<ColorScheme>,
+) -> Result<(), String> {
+    tab.call_method(emulated_media(media, color_scheme))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// The `Emulation.setEmulatedMedia` call for [`set_emulated_media`].
+///
+/// Each call replaces every emulated media feature, so the color scheme
+/// has to be repeated whenever the media type changes.
+fn emulated_media(media: &str, color_scheme: Option<ColorScheme>) -> Emulation::SetEmulatedMedia {
+    Emulation::SetEmulatedMedia {
+        media: Some(media.to_string()),
+        features: Some(
+            color_scheme
+                .map(|scheme| Emulation::MediaFeature {
+                    name: "prefers-color-scheme".to_string(),
+                    value: scheme.as_str().to_string(),
+                })
+                .into_iter()
+                .collect(),
+        ),
+    }
+}
+
+/// Run the page in `timezone` (CDP `Emulation.setTimezoneOverride`); an
+/// empty name restores the system timezone.
+fn set_timezone(tab: &headless_chrome::Tab, timezone: &str) -> Result<(), String> {
+    tab.call_method(Emulation::SetTimezoneOverride {
+        timezone_id: timezone.to_string(),
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Report `geolocation` to the page and grant it the permission to read
+/// it, or with `None`, clear the override and revoke the permission.
+///
+/// The permission is granted for every origin, since redirects can move
+/// the page to one other than the requested URL's.
+fn set_geolocation(
+    tab: &headless_chrome::Tab,
+    geolocation: Option<Geolocation>,
+) -> Result<(), String> {
+    let Some(geolocation) = geolocation else {
+        tab.call_method(Emulation::ClearGeolocationOverride(None))
+            .map_err(|e| e.to_string())?;
+        return tab
+            .call_method(Browser::ResetPermissions {
+                browser_context_id: None,
+            })
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+    };
+
+    tab.call_method(Browser::GrantPermissions {
+        permissions: vec![Browser::PermissionType::Geolocation],
+        origin: None,
+        browser_context_id: None,
+    })
+    .map_err(|e| e.to_string())?;
+    tab.call_method(Emulation::SetGeolocationOverride {
+        latitude: Some(geolocation.lat),
+        longitude: Some(geolocation.lon),
+        accuracy: Some(geolocation.accuracy.unwrap_or(0.0)),
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Emulate `viewport`, or with `None`, go back to the browser window's
+/// (CDP `Emulation.setDeviceMetricsOverride`).
+fn set_viewport(tab: &headless_chrome::Tab, viewport: Option<Viewport>) -> Result<(), String> {
+    let Some(viewport) = viewport else {
+        return tab
+            .call_method(Emulation::ClearDeviceMetricsOverride(None))
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+    };
+    tab.call_method(Emulation::SetDeviceMetricsOverride {
+        width: viewport.width,
+        height: viewport.height,
+        device_scale_factor: viewport.device_scale_factor,
+        mobile: viewport.mobile,
+        scale: None,
+        screen_width: None,
+        screen_height: None,
+        position_x: None,
+        position_y: None,
+        dont_set_visible_size: None,
+        screen_orientation: None,
+        viewport: None,
+        display_feature: None,
+        device_posture: None,
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Toggle the tab's script execution (CDP `Emulation.setScriptExecutionDisabled`).
+fn set_script_execution_disabled(tab: &headless_chrome::Tab, disabled: bool) -> Result<(), String> {
+    tab.call_method(Emulation::SetScriptExecutionDisabled { value: disabled })
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Install [`READY_FUNCTION`](crate::service::READY_FUNCTION) for a render
+/// with JavaScript enabled.
+///
+/// Best effort: without it the wait still polls the readiness expression.
+fn ready_signal(tab: &headless_chrome::Tab, javascript_enabled: bool) -> Option<Arc<ReadySignal>> {
+    if !javascript_enabled {
+        return None;
+    }
+    ReadySignal::for_tab(tab)
+        .inspect_err(|e| log::warn!("⚠️ Failed to install readiness callback: {}", e))
+        .ok()
+}
+
+/// Add the start of loading `source` to a debugged render's timeline.
+fn mark_source(debug: Option<&DebugRecorder>, source: PageSource<'_>) {
+    let Some(debug) = debug else { return };
+    match source {
+        PageSource::Url(url) => debug.mark("navigation_started", Some(truncate_url(url, 200))),
+        PageSource::Html(html) => debug.mark("html_loading", Some(format!("{} bytes", html.len()))),
+    }
+}
+
+/// Add the end of the render's actions to a debugged render's timeline.
+fn mark_actions(debug: Option<&DebugRecorder>, options: &RenderOptions) {
+    if let Some(debug) = debug.filter(|_| !options.actions.is_empty()) {
+        debug.mark("actions_completed", Some(options.actions.len().to_string()));
+    }
+}
+
+/// Add the network being cut to a debugged render's timeline.
+fn mark_offline(debug: Option<&DebugRecorder>, options: &RenderOptions) {
+    if let Some(debug) = debug.filter(|_| options.offline) {
+        debug.mark("offline", None);
+    }
+}
+
+/// Add the finished PDF to a debugged render's timeline.
+fn mark_printed(debug: Option<&DebugRecorder>, size: usize) {
+    if let Some(debug) = debug {
+        debug.mark("printed", Some(format!("{} bytes", size)));
+    }
+}
+
+/// Load the page, wait for JavaScript, and print a single tab.
+///
+/// Steps are added to `debug`'s timeline when the render is debugged.
+fn render_in_tab(
+    tab: &headless_chrome::Tab,
+    source: PageSource<'_>,
+    options: &RenderOptions,
+    debug: Option<&DebugRecorder>,
+) -> Result<Rendered, PdfServiceError> {
+    load_page(tab, source, options, debug)?;
+
+    // Take the DOM before printing, which runs `beforeprint` handlers
+    let html = if options.captures_html() {
+        Some(capture_html(tab)?)
+    } else {
+        None
+    };
+
+    // Generate PDF
+    log::trace!("Generating PDF");
+    let pdf_start = Instant::now();
+
+    let pdf_data = print_pdf(tab, options.print_options())?;
+
+    log::debug!(
+        "PDF generated in {:?} ({} bytes)",
+        pdf_start.elapsed(),
+        pdf_data.len()
+    );
+    mark_printed(debug, pdf_data.len());
+
+    let thumbnail = match options.thumbnail_width {
+        Some(width) => Some(capture_thumbnail(
+            tab,
+            options.page_size_px(),
+            width,
+            options.thumbnail_format,
+            options.thumbnail_quality,
+            (options.print_media(), options.color_scheme),
+        )?),
+        None => None,
+    };
+
+    Ok(Rendered {
+        pdf: pdf_data,
+        thumbnail,
+        thumbnail_format: options.thumbnail_format,
+        html,
+        pages: None,
+        outputs: options.outputs,
+        trace_id: None,
+        report: None,
+    })
+}
+
+/// Navigate to or load the page, run its actions, and wait for it to be
+/// ready to print.
+fn load_page(
+    tab: &headless_chrome::Tab,
+    source: PageSource<'_>,
+    options: &RenderOptions,
+    debug: Option<&DebugRecorder>,
+) -> Result<(), PdfServiceError> {
+    let ready = ready_signal(tab, options.javascript_enabled);
+
+    // HTML with a base URL is served there by a mock and loads like a URL
+    let source = match (source, options.base_url.as_deref()) {
+        (PageSource::Html(_), Some(base_url)) => PageSource::Url(base_url),
+        (source, _) => source,
+    };
+
+    let nav_start = Instant::now();
+    mark_source(debug, source);
+    match source {
+        PageSource::Url(url) => {
+            check_redirects(options, navigate(tab, url))?;
+            run_actions(tab, options)?;
+            mark_actions(debug, options);
+            go_offline(tab, options.offline)?;
+            mark_offline(debug, options);
+        }
+        PageSource::Html(html) => {
+            go_offline(tab, options.offline)?;
+            mark_offline(debug, options);
+            load_html(tab, html, options.cancel.as_ref())?;
+            run_actions(tab, options)?;
+            mark_actions(debug, options);
+        }
+    }
+    log::debug!("Navigation completed in {:?}", nav_start.elapsed());
+
+    // Wait for JavaScript execution (nothing can signal readiness without it)
+    if options.javascript_enabled {
+        let outcome = wait_for_page_ready(
+            tab,
+            &options.wait_expression,
+            ready.as_deref(),
+            options.wait_duration,
+            options.cancel.as_ref(),
+        );
+        if let Some(debug) = debug {
+            debug.ready(outcome, options.wait_expression_source());
+        }
+    }
+    inject(tab, options)
+}
+
+/// Print the tab to PDF, reading the PDF back from Chrome in chunks.
+///
+/// Returning the PDF inline would carry it base64-encoded in a single
+/// DevTools message, which for a large document costs several times its
+/// size in memory; reading it in chunks costs the PDF plus one chunk.
+fn print_pdf(
+    tab: &headless_chrome::Tab,
+    options: PrintToPdfOptions,
+) -> Result<Vec<u8>, PdfServiceError> {
+    let handle = open_print_stream(tab, options)?;
+    let mut pdf = Vec::new();
+    let result = loop {
+        match read_print_stream(tab, &handle) {
+            Ok((chunk, eof)) => {
+                pdf.extend_from_slice(&chunk);
+                if eof {
+                    break Ok(pdf);
+                }
+            }
+            Err(e) => break Err(e),
+        }
+    };
+    close_print_stream(tab, &handle);
+    result
+}
+
+/// Print the tab to PDF, leaving the PDF in a Chrome-side stream.
+///
+/// Returns the stream's handle for [`read_print_stream`]; the stream must
+/// be closed with [`close_print_stream`].
+fn open_print_stream(
+    tab: &headless_chrome::Tab,
+    options: PrintToPdfOptions,
+) -> Result<String, PdfServiceError> {
+    let returned = tab
+        .call_method(Page::PrintToPDF {
+            landscape: options.landscape,
+            display_header_footer: options.display_header_footer,
+            print_background: options.print_background,
+            scale: options.scale,
+            paper_width: options.paper_width,
+            paper_height: options.paper_height,
+            margin_top: options.margin_top,
+            margin_bottom: options.margin_bottom,
+            margin_left: options.margin_left,
+            margin_right: options.margin_right,
+            page_ranges: options.page_ranges,
+            generate_tagged_pdf: options.generate_tagged_pdf,
+            generate_document_outline: options.generate_document_outline,
+            header_template: options.header_template,
+            footer_template: options.footer_template,
+            prefer_css_page_size: options.prefer_css_page_size,
+            transfer_mode: Some(Page::PrintToPDFTransfer_modeOption::ReturnAsStream),
+        })
+        .map_err(|e| {
+            log::error!("❌ Failed to generate PDF: {}", e);
+            PdfServiceError::PdfGenerationFailed(e.to_string())
+        })?;
+
+    returned.stream.ok_or_else(|| {
+        log::error!("❌ Chrome returned no PDF stream");
+        PdfServiceError::PdfGenerationFailed("Chrome returned no PDF stream".to_string())
+    })
+}
+
+/// Read the next chunk of at most [`PDF_CHUNK_SIZE`] bytes from a print
+/// stream; the flag is set once the whole PDF has been read.
+fn read_print_stream(
+    tab: &headless_chrome::Tab,
+    handle: &str,
+) -> Result<(Vec<u8>, bool), PdfServiceError> {
+    let read = tab
+        .call_method(IO::Read {
+            handle: handle.to_string(),
+            offset: None,
+            size: Some(PDF_CHUNK_SIZE),
+        })
+        .map_err(|e| {
+            log::error!("❌ Failed to read PDF from Chrome: {}", e);
+            PdfServiceError::PdfGenerationFailed(format!("failed to read PDF: {}", e))
+        })?;
+
+    let chunk = if read.base_64_encoded == Some(true) {
+        base64::engine::general_purpose::STANDARD
+            .decode(&read.data)
+            .map_err(|e| {
+                log::error!("❌ Chrome sent a malformed PDF chunk: {}", e);
+                PdfServiceError::PdfGenerationFailed(format!("failed to read PDF: {}", e))
+            })?
+    } else {
+        read.data.into_bytes()
+    };
+    Ok((chunk, read.eof))
+}
+
+/// Close a print stream, freeing the PDF Chrome still holds.
+///
+/// Best effort: Chrome frees the stream with the tab anyway.
+fn close_print_stream(tab: &headless_chrome::Tab, handle: &str) {
+    if let Err(e) = tab.call_method(IO::Close {
+        handle: handle.to_string(),
+    }) {
+        log::debug!("Failed to close PDF stream {}: {}", handle, e);
+    }
+}
+
+/// Screenshot the first page as it is laid out for print, scaled to `width`
+/// pixels wide and encoded as `format`.
+///
+/// Lays the document out at the PDF's paper size with the PDF's media
+/// type emulated; [`reset_tab_settings`] restores the screen layout
+/// afterward.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::PdfGenerationFailed`] if Chrome cannot lay
+/// out or capture the page.
+fn capture_thumbnail(
+    tab: &headless_chrome::Tab,
+    (page_width, page_height): (u32, u32),
+    width: u32,
+    format: ImageFormat,
+    quality: Option<u32>,
+    (media, color_scheme): (MediaType, Option<ColorScheme>),
+) -> Result<Vec<u8>, PdfServiceError> {
+    let start = Instant::now();
+
+    let result = tab
+        .call_method(emulated_media(media.as_str(), color_scheme))
+        .and_then(|_| {
+            tab.call_method(Emulation::SetDeviceMetricsOverride {
+                width: page_width,
+                height: page_height,
+                device_scale_factor: 1.0,
+                mobile: false,
+                scale: None,
+                screen_width: None,
+                screen_height: None,
+                position_x: None,
+                position_y: None,
+                dont_set_visible_size: None,
+                screen_orientation: None,
+                viewport: None,
+                display_feature: None,
+                device_posture: None,
+            })
+        })
+        .and_then(|_| {
+            tab.capture_screenshot(
+                match format {
+                    ImageFormat::Png => Page::CaptureScreenshotFormatOption::Png,
+                    ImageFormat::Jpeg => Page::CaptureScreenshotFormatOption::Jpeg,
+                    ImageFormat::Webp => Page::CaptureScreenshotFormatOption::Webp,
+                },
+                quality,
+                Some(Page::Viewport {
+                    x: 0.0,
+                    y: 0.0,
+                    width: page_width as f64,
+                    height: page_height as f64,
+                    scale: width as f64 / page_width as f64,
+                }),
+                true,
+            )
+        });
+
+    match result {
+        Ok(image) => {
+            log::debug!(
+                "{} thumbnail captured in {:?} ({} bytes)",
+                format,
+                start.elapsed(),
+                image.len()
+            );
+            Ok(image)
+        }
+        Err(e) => {
+            log::error!("❌ Failed to capture thumbnail: {}", e);
+            Err(PdfServiceError::PdfGenerationFailed(format!(
+                "failed to capture thumbnail: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Serialize the page's current DOM, with its doctype, as HTML.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::PdfGenerationFailed`] if the page cannot be
+/// read.
+fn capture_html(tab: &headless_chrome::Tab) -> Result<String, PdfServiceError> {
+    let result = tab
+        .evaluate(CAPTURE_HTML_SCRIPT, false)
+        .map_err(|e| e.to_string())
+        .and_then(|result| match result.value {
+            Some(serde_json::Value::String(html)) => Ok(html),
+            _ => Err("page returned no HTML".to_string()),
+        });
+
+    match result {
+        Ok(html) => {
+            log::debug!("HTML captured ({} bytes)", html.len());
+            Ok(html)
+        }
+        Err(e) => {
+            log::error!("❌ Failed to capture HTML: {}", e);
+            Err(PdfServiceError::PdfGenerationFailed(format!(
+                "failed to capture HTML: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Undo the print layout applied by [`capture_thumbnail`].
+fn clear_print_layout(tab: &headless_chrome::Tab) -> Result<(), String> {
+    tab.call_method(Emulation::ClearDeviceMetricsOverride(None))
+        .and_then(|_| {
+            tab.call_method(Emulation::SetEmulatedMedia {
+                media: Some(String::new()),
+                features: None,
+            })
+        })
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Build a [`PdfServiceError::NavigationFailed`], explaining certificate
+/// errors, which Chrome reports only as a bare `net::ERR_CERT_*` code.
+fn navigation_failed(message: String) -> PdfServiceError {
+    if message.contains("ERR_CERT_") {
+        PdfServiceError::NavigationFailed(format!(
+            "{} (TLS certificate rejected; trust the issuing CA with CHROME_CA_BUNDLE, \
+             or set ignore_tls_errors for this request)",
+            message
+        ))
+    } else {
+        PdfServiceError::NavigationFailed(message)
+    }
+}
+
+/// Navigate the tab to a URL and wait for the load to finish.
+fn navigate(tab: &headless_chrome::Tab, url: &str) -> Result<(), PdfServiceError> {
+    log::trace!("Navigating to URL: {}", truncate_url(url, 100));
+
+    tab.navigate_to(url)
+        .map_err(|e| {
+            log::error!("❌ Failed to navigate to URL: {}", e);
+            navigation_failed(e.to_string())
+        })?
+        .wait_until_navigated()
+        .map_err(|e| {
+            log::error!("❌ Navigation timeout: {}", e);
+            PdfServiceError::NavigationTimeout(e.to_string())
+        })?;
+
+    Ok(())
+}
+
+/// Run the request's [`PageAction`]s in order.
+///
+/// Stops early once the render is cancelled; the caller reports that.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::ActionFailed`] naming the first step that
+/// failed, or the navigation error of a failed `navigate` step.
+fn run_actions(tab: &headless_chrome::Tab, options: &RenderOptions) -> Result<(), PdfServiceError> {
+    let timeout = Duration::from_secs(ACTION_TIMEOUT_SECS);
+    for (index, action) in options.actions.iter().enumerate() {
+        if is_cancelled(options.cancel.as_ref()) {
+            return Ok(());
+        }
+        let step = format!("step {} ({})", index + 1, describe_action(action));
+        log::trace!("Running action {}", step);
+        let failed = |reason: String| {
+            log::warn!("⚠️ Action {} failed: {}", step, reason);
+            PdfServiceError::ActionFailed(format!("{}: {}", step, reason))
+        };
+        let find = |selector: &str| {
+            tab.wait_for_element_with_custom_timeout(selector, timeout)
+                .map_err(|_| {
+                    failed(format!(
+                        "no element matched within {}s",
+                        ACTION_TIMEOUT_SECS
+                    ))
+                })
+        };
+
+        match action {
+            PageAction::Click(selector) => {
+                find(selector)?.click().map_err(|e| failed(e.to_string()))?;
+            }
+            PageAction::Type { selector, text } => {
+                find(selector)?
+                    .type_into(text)
+                    .map_err(|e| failed(e.to_string()))?;
+            }
+            PageAction::WaitFor(selector) => {
+                find(selector)?;
+            }
+            PageAction::Navigate(url) => check_redirects(options, navigate(tab, url))?,
+        }
+    }
+    Ok(())
+}
+
+/// Async version of [`run_actions`].
+async fn run_actions_async(
+    tab: &Arc<headless_chrome::Tab>,
+    options: &RenderOptions,
+) -> Result<(), PdfServiceError> {
+    if options.actions.is_empty() {
+        return Ok(());
+    }
+    let tab = Arc::clone(tab);
+    let options = options.clone();
+    run_blocking(move || run_actions(&tab, &options)).await
+}
+
+/// Apply the request's `watermark`, `inject_css`, and `inject_js` to the
+/// ready page.
+///
+/// The stylesheets go in through the CSS domain, so they apply with
+/// scripts disabled too. A script returning a promise is awaited.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::ActionFailed`] if a stylesheet cannot be
+/// added or the script throws.
+fn inject(tab: &headless_chrome::Tab, options: &RenderOptions) -> Result<(), PdfServiceError> {
+    let failed = |name: &str, reason: String| {
+        log::warn!("⚠️ {} failed: {}", name, reason);
+        PdfServiceError::ActionFailed(format!("{}: {}", name, reason))
+    };
+    if let Some(css) = &options.watermark {
+        log::trace!("Adding watermark");
+        add_style_sheet(tab, css).map_err(|e| failed("watermark", e))?;
+    }
+    if let Some(css) = &options.inject_css {
+        log::trace!("Injecting {} bytes of CSS", css.len());
+        add_style_sheet(tab, css).map_err(|e| failed("inject_css", e))?;
+    }
+    if let Some(script) = &options.inject_js {
+        log::trace!("Injecting {} bytes of JavaScript", script.len());
+        run_script(tab, script).map_err(|e| failed("inject_js", e))?;
+    }
+    Ok(())
+}
+
+/// Async version of [`inject`].
+async fn inject_async(
+    tab: &Arc<headless_chrome::Tab>,
+    options: &RenderOptions,
+) -> Result<(), PdfServiceError> {
+    if options.watermark.is_none() && options.inject_css.is_none() && options.inject_js.is_none() {
+        return Ok(());
+    }
+    let tab = Arc::clone(tab);
+    let options = options.clone();
+    run_blocking(move || inject(&tab, &options)).await
+}
+
+/// Add a stylesheet to the tab's main frame (CDP `CSS.createStyleSheet`).
+fn add_style_sheet(tab: &headless_chrome::Tab, css: &str) -> Result<(), String> {
+    let frame_id = tab
+        .call_method(Page::GetFrameTree(None))
+        .map_err(|e| e.to_string())?
+        .frame_tree
+        .frame
+        .id;
+    tab.call_method(DOM::Enable {
+        include_whitespace: None,
+    })
+    .and_then(|_| tab.call_method(CSS::Enable(None)))
+    .and_then(|_| tab.call_method(CSS::CreateStyleSheet { frame_id }))
+    .and_then(|sheet| {
+        tab.call_method(CSS::SetStyleSheetText {
+            style_sheet_id: sheet.style_sheet_id,
+            text: css.to_string(),
+        })
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Evaluate a script in the page, awaiting the promise it returns, if any.
+///
+/// Returns the exception's description if the script throws or the
+/// promise rejects.
+fn run_script(tab: &headless_chrome::Tab, script: &str) -> Result<(), String> {
+    let returned = tab
+        .call_method(Runtime::Evaluate {
+            expression: script.to_string(),
+            object_group: None,
+            include_command_line_api: None,
+            silent: None,
+            context_id: None,
+            return_by_value: None,
+            generate_preview: None,
+            user_gesture: None,
+            await_promise: Some(true),
+            throw_on_side_effect: None,
+            timeout: None,
+            disable_breaks: None,
+            repl_mode: None,
+            allow_unsafe_eval_blocked_by_csp: None,
+            unique_context_id: None,
+            serialization_options: None,
+        })
+        .map_err(|e| e.to_string())?;
+    match returned.exception_details {
+        Some(details) => Err(details
+            .exception
+            .and_then(|exception| exception.description)
+            .unwrap_or(details.text)),
+        None => Ok(()),
+    }
+}
+
+/// Write HTML into a blank document via `Page.setDocumentContent`.
+///
+/// Avoids building a percent-encoded data URL, which copies the document
+/// twice and fails for documents above a few megabytes.
+fn load_html(
+    tab: &headless_chrome::Tab,
+    html: &str,
+    cancel: Option<&CancelHandle>,
+) -> Result<(), PdfServiceError> {
+    log::trace!(
+        "Loading {} bytes of HTML via setDocumentContent",
+        html.len()
+    );
+
+    navigate(tab, "about:blank")?;
+
+    let frame_id = tab
+        .call_method(Page::GetFrameTree(None))
+        .map_err(|e| {
+            log::error!("❌ Failed to get frame tree: {}", e);
+            PdfServiceError::NavigationFailed(e.to_string())
+        })?
+        .frame_tree
+        .frame
+        .id;
+
+    tab.call_method(Page::SetDocumentContent {
+        frame_id,
+        html: html.to_string(),
+    })
+    .map_err(|e| {
+        log::error!("❌ Failed to set document content: {}", e);
+        PdfServiceError::NavigationFailed(e.to_string())
+    })?;
+
+    wait_for_document_complete(tab, Duration::from_secs(HTML_LOAD_TIMEOUT_SECS), cancel)
+}
+
+/// Poll until `document.readyState` is `"complete"`.
+///
+/// Stops early once `cancel` is cancelled; the caller reports that.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::NavigationTimeout`] if subresources are still
+/// loading after `max_wait`.
+fn wait_for_document_complete(
+    tab: &headless_chrome::Tab,
+    max_wait: Duration,
+    cancel: Option<&CancelHandle>,
+) -> Result<(), PdfServiceError> {
+    let start = Instant::now();
+    let poll_interval = Duration::from_millis(JS_POLL_INTERVAL_MS);
+
+    loop {
+        let is_complete = evaluate_flag(tab, "document.readyState === 'complete'");
+
+        if is_complete || is_cancelled(cancel) {
+            return Ok(());
+        }
+
+        if start.elapsed() >= max_wait {
+            log::error!("❌ HTML content still loading after {:?}", max_wait);
+            return Err(PdfServiceError::NavigationTimeout(format!(
+                "HTML content did not finish loading within {:?}",
+                max_wait
+            )));
+        }
+
+        std::thread::sleep(poll_interval);
+    }
+}
+
+/// Build PDF print options.
+///
+/// Copies the pool's base
+/// [`print_options`](crate::BrowserPoolConfig::print_options) and applies
+/// the request's fields on top.
+///
+/// # Precedence
+///
+/// | Setting | Request Field | Base Option | Fallback |
+/// |---------|---------------|-------------|----------|
+/// | Orientation | `landscape` | `landscape` | Portrait |
+/// | Backgrounds | `print_background` | `print_background` | Printed |
+/// | Paper size | `paper_format`, `paper_width`, `paper_height` | `paper_width`, `paper_height` | US Letter |
+/// | Margins | `margin_*` | `margin_*` | Chrome's default |
+/// | Scale | `scale` | `scale` | 1.0 |
+/// | Pages | `page_ranges` | `page_ranges` | All pages |
+/// | Header/footer | `display_header_footer`, `*_template` | `display_header_footer`, `*_template` | None |
+/// | Tags | `generate_tagged_pdf` | `generate_tagged_pdf` | Chrome's default |
+/// | Outline | `generate_document_outline` | `generate_document_outline` | None; turns tags on |
+/// | Everything else | - | As configured | Chrome's default |
+///
+/// `transfer_mode` is never copied: the PDF must come back inline.
+fn build_print_options(
+    base: &PrintToPdfOptions,
+    landscape: Option<bool>,
+    print_background: Option<bool>,
+    page: &PageSetup,
+) -> PrintToPdfOptions {
+    let outline = page
+        .generate_document_outline
+        .or(base.generate_document_outline);
+    PrintToPdfOptions {
+        landscape: Some(landscape.or(base.landscape).unwrap_or(false)),
+        display_header_footer: page.display_header_footer.or(base.display_header_footer),
+        print_background: Some(print_background.or(base.print_background).unwrap_or(true)),
+        scale: page.scale.or(base.scale),
+        paper_width: page.paper_width.or(base.paper_width),
+        paper_height: page.paper_height.or(base.paper_height),
+        margin_top: page.margin_top.or(base.margin_top),
+        margin_bottom: page.margin_bottom.or(base.margin_bottom),
+        margin_left: page.margin_left.or(base.margin_left),
+        margin_right: page.margin_right.or(base.margin_right),
+        page_ranges: page
+            .page_ranges
+            .clone()
+            .or_else(|| base.page_ranges.clone()),
+        ignore_invalid_page_ranges: base.ignore_invalid_page_ranges,
+        header_template: page
+            .header_template
+            .clone()
+            .or_else(|| base.header_template.clone()),
+        footer_template: page
+            .footer_template
+            .clone()
+            .or_else(|| base.footer_template.clone()),
+        prefer_css_page_size: base.prefer_css_page_size,
+        transfer_mode: None,
+        generate_document_outline: outline,
+        // Chrome builds the outline from the tags
+        generate_tagged_pdf: if outline == Some(true) {
+            Some(true)
+        } else {
+            page.generate_tagged_pdf.or(base.generate_tagged_pdf)
+        },
+    }
+}
+
+/// Wait for the page to signal it's ready for PDF generation.
+///
+/// This function implements a polling loop that checks for `window.isPageDone === true`.
+/// This allows JavaScript-heavy pages to signal when they've finished rendering,
+/// enabling early PDF generation without waiting the full timeout.
+///
+/// Requests can replace the check with their own `wait_for_expression`
+/// (e.g. `window.__APP_READY__`) for pages that expose a different flag;
+/// the table below applies to whichever expression is polled.
+///
+/// # Behavior Summary
+///
+/// | Page State | Result |
+/// |------------|--------|
+/// | `window.isPageDone = true` | Returns **immediately** (early exit) |
+/// | `window.isPageDone = false` | Waits **full duration** |
+/// | `window.isPageDone` not defined | Waits **full duration** |
+/// | JavaScript error during check | Waits **full duration** |
+///
+/// # Default Behavior (No Flag Set)
+///
+/// **Important:** If the page does not set `window.isPageDone = true`, this function
+/// waits the **full `max_wait` duration** before returning. This is intentional -
+/// it gives JavaScript-heavy pages time to render even without explicit signaling.
+///
+/// For example, with the default `waitsecs = 5`:
+/// - A page **with** the flag set immediately: ~0ms wait
+/// - A page **without** the flag: full 5000ms wait
+///
+/// # How It Works
+///
+/// ```text
+/// ┌─────────────────────────────────────────────────────────────────┐
+/// │                    wait_for_page_ready                          │
+/// │                                                                 │
+/// │   ┌─────────┐     ┌──────────────┐     ┌─────────────────────┐  │
+/// │   │  Start  │────▶│ Check flag   │────▶│ window.isPageDone?  │  │
+/// │   └─────────┘     └──────────────┘     └──────────┬──────────┘  │
+/// │                                                   │             │
+/// │                          ┌────────────────────────┼─────────┐   │
+/// │                          │                        │         │   │
+/// │                          ▼                        ▼         │   │
+/// │                   ┌────────────┐           ┌───────────┐    │   │
+/// │                   │   true     │           │  false /  │    │   │
+/// │                   │ (ready!)   │           │ undefined │    │   │
+/// │                   └─────┬──────┘           └─────┬─────┘    │   │
+/// │                         │                        │          │   │
+/// │                         ▼                        ▼          │   │
+/// │                   ┌───────────┐           ┌───────────┐     │   │
+/// │                   │  Return   │           │ Sleep     │     │   │
+/// │                   │  early    │           │ 200ms     │─────┘   │
+/// │                   └───────────┘           └───────────┘         │
+/// │                                                  │              │
+/// │                                                  ▼              │
+/// │                                           ┌───────────┐         │
+/// │                                           │ Timeout?  │         │
+/// │                                           └─────┬─────┘         │
+/// │                                                 │               │
+/// │                                    ┌────────────┴────────────┐  │
+/// │                                    ▼                         ▼  │
+/// │                             ┌───────────┐              ┌──────┐ │
+/// │                             │   Yes     │              │  No  │ │
+/// │                             │ (proceed) │              │(loop)│ │
+/// │                             └───────────┘              └──────┘ │
+/// └─────────────────────────────────────────────────────────────────┘
+/// ```
+///
+/// # Polling Timeline
+///
+/// The function polls every 200ms (see `JS_POLL_INTERVAL_MS`):
+///
+/// ```text
+/// Time:   0ms    200ms   400ms   600ms   800ms  ...  5000ms
+///          │       │       │       │       │           │
+///          ▼       ▼       ▼       ▼       ▼           ▼
+///        Poll    Poll    Poll    Poll    Poll  ...   Timeout
+///          │       │       │       │       │           │
+///          └───────┴───────┴───────┴───────┴───────────┤
+///                                                      ▼
+///                                              Proceed to PDF
+///
+/// If window.isPageDone = true at any poll → Exit immediately
+/// ```
+///
+/// Each poll executes this JavaScript:
+///
+/// ```javascript
+/// window.isPageDone === true  // Returns true, false, or undefined
+/// ```
+///
+/// - `true` → Function returns immediately
+/// - `false` / `undefined` / error → Continue polling until timeout
+///
+/// # Page-Side Implementation (Optional)
+///
+/// To enable early completion and avoid unnecessary waiting, add this to your
+/// page's JavaScript **after** all content is rendered:
+///
+/// ```javascript
+/// // Signal that the page is ready for PDF generation
+/// window.isPageDone = true;
+/// ```
+///
+/// ## Framework Examples
+///
+/// **React:**
+/// ```javascript
+/// useEffect(() => {
+///     fetchData().then((result) => {
+///         setData(result);
+///         // Signal ready after state update and re-render
+///         setTimeout(() => { window.isPageDone = true; }, 0);
+///     });
+/// }, []);
+/// ```
+///
+/// **Vue:**
+/// ```javascript
+/// mounted() {
+///     this.loadData().then(() => {
+///         this.$nextTick(() => {
+///             window.isPageDone = true;
+///         });
+///     });
+/// }
+/// ```
+///
+/// **Vanilla JavaScript:**
+/// ```javascript
+/// document.addEventListener('DOMContentLoaded', async () => {
+///     await loadDynamicContent();
+///     await renderCharts();
+///     window.isPageDone = true;  // All done!
+/// });
+/// ```
+///
+/// # When to Increase `waitsecs`
+///
+/// If you cannot modify the target page to set `window.isPageDone`, increase
+/// `waitsecs` based on the page complexity:
+///
+/// | Page Type | Recommended `waitsecs` |
+/// |-----------|------------------------|
+/// | Static HTML (no JS) | 1 |
+/// | Light JS (form validation, simple DOM) | 2-3 |
+/// | Moderate JS (API calls, dynamic content) | 5 (default) |
+/// | Heavy SPA (React, Vue, Angular) | 5-10 |
+/// | Complex visualizations (D3, charts, maps) | 10-15 |
+/// | Pages loading external resources | 10-20 |
+///
+/// # Performance Optimization
+///
+/// For high-throughput scenarios, implementing `window.isPageDone` on your
+/// pages can significantly improve performance:
+///
+/// ```text
+/// Without flag (5s default wait):
+///     Request 1: ████████████████████ 5.2s
+///     Request 2: ████████████████████ 5.1s
+///     Request 3: ████████████████████ 5.3s
+///     Average: 5.2s per PDF
+///
+/// With flag (page ready in 800ms):
+///     Request 1: ████ 0.9s
+///     Request 2: ████ 0.8s
+///     Request 3: ████ 0.9s
+///     Average: 0.87s per PDF (6x faster!)
+/// ```
+///
+/// # Arguments
+///
+/// * `tab` - The browser tab to check. Must have completed navigation.
+/// * `expression` - Readiness check from `validate_wait_expression`.
+/// * `ready` - The tab's [`READY_FUNCTION`](crate::service::READY_FUNCTION)
+///   callback; a call ends the wait without waiting for the next poll.
+/// * `max_wait` - Maximum time to wait before proceeding with PDF generation.
+///   This is the upper bound; the function may return earlier if the page
+///   signals readiness.
+/// * `cancel` - Handle that ends the wait early when the render is cancelled.
+///
+/// # Returns
+///
+/// This function returns `()` (unit). It either:
+/// - Returns early when `window.isPageDone === true` is detected, or the
+///   page calls `window.html2pdfReady()`
+/// - Returns after `max_wait` duration has elapsed (timeout)
+///
+/// In both cases, PDF generation proceeds afterward. This function never fails -
+/// timeout is a normal completion path, not an error.
+///
+/// # Thread Blocking
+///
+/// This function blocks the calling thread with `std::thread::sleep()`.
+/// Always call from within a blocking context (e.g., `spawn_blocking`).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // Navigate to page first
+/// let page = tab.navigate_to(url)?.wait_until_navigated()?;
+///
+/// // Wait up to 10 seconds for JavaScript
+/// wait_for_page_ready(&tab, &options.wait_expression, None, Duration::from_secs(10), None);
+///
+/// // Now generate PDF - page is either ready or we've waited long enough
+/// let pdf_data = page.print_to_pdf(options)?;
+/// ```
+fn wait_for_page_ready(
+    tab: &headless_chrome::Tab,
+    expression: &str,
+    ready: Option<&ReadySignal>,
+    max_wait: Duration,
+    cancel: Option<&CancelHandle>,
+) -> ReadyOutcome {
+    let start = Instant::now();
+    let poll_interval = Duration::from_millis(JS_POLL_INTERVAL_MS);
+
+    log::trace!(
+        "Waiting up to {:?} for page to be ready (polling every {:?})",
+        max_wait,
+        poll_interval
+    );
+
+    while start.elapsed() < max_wait && !is_cancelled(cancel) {
+        // Check if page signals completion
+        let is_done = ready.is_some_and(ReadySignal::is_fired) || evaluate_flag(tab, expression);
+
+        if is_done {
+            log::debug!("Page signaled ready after {:?}", start.elapsed());
+            return ReadyOutcome::Signaled;
+        }
+
+        // Sleep before next poll, waking early if the page calls back
+        match ready {
+            Some(ready) => {
+                if ready.wait_timeout(poll_interval) {
+                    log::debug!("Page called back ready after {:?}", start.elapsed());
+                    return ReadyOutcome::Signaled;
+                }
+            }
+            None => std::thread::sleep(poll_interval),
+        }
+    }
+
+    if is_cancelled(cancel) {
+        return ReadyOutcome::Cancelled;
+    }
+    log::debug!(
+        "Page wait completed after {:?} (timeout, proceeding anyway)",
+        start.elapsed()
+    );
+    ReadyOutcome::TimedOut
+}
+
+/// Owned counterpart of [`PageSource`] for renders that run in a spawned task.
+#[derive(Debug)]
+enum OwnedPageSource {
+    /// Navigate to a validated URL.
+    Url(String),
+    /// Write HTML directly into a blank document.
+    Html(String),
+}
+
+/// Run [`render_async`] in its own task.
+///
+/// Detaching the render from the caller's future means a cancelled request
+/// still releases its tab and browser instead of dropping them mid-render.
+async fn spawn_render(
+    pool: &SharedBrowserPool,
+    source: OwnedPageSource,
+    options: RenderOptions,
+) -> Result<Rendered, PdfServiceError> {
+    let pool = Arc::clone(pool);
+
+    tokio::spawn(render_async(pool, source, options))
+        .await
+        .unwrap_or_else(|e| {
+            log::error!("❌ Render task failed: {}", e);
+            Err(PdfServiceError::Internal(e.to_string()))
+        })
+}
+
+/// Async version of [`generate_pdf_internal`].
+///
+/// Browser checkout, navigation commands, readiness checks, and printing
+/// each run on `spawn_blocking`; everything in between is timer-driven.
+async fn render_async(
+    pool: SharedBrowserPool,
+    source: OwnedPageSource,
+    options: RenderOptions,
+) -> Result<Rendered, PdfServiceError> {
+    let start_time = Instant::now();
+    let trace = options.trace;
+    let priority = options.priority;
+    let cancel = options.cancel.clone();
+
+    let (browser, tab, source) = run_blocking(move || {
+        let browser = acquire_browser(&pool, priority, cancel.as_ref())?;
+        let source = match source {
+            OwnedPageSource::Url(url) => OwnedPageSource::Url(resolve_file_url(
+                &url,
+                &browser.pool_config().allowed_file_dirs,
+            )?),
+            source => source,
+        };
+        require_trace_dir(trace, browser.pool_config())?;
+
+        log::trace!("Checking out browser tab");
+        let tab = browser.checkout_tab().map_err(|e| {
+            log::error!("❌ {}", e);
+            PdfServiceError::TabCreationFailed(e.to_string())
+        })?;
+
+        Ok((browser, tab, source))
+    })
+    .await?;
+
+    let options = Arc::new(options.with_pool_policy(browser.pool_config()));
+    if let Some(cancel) = &options.cancel {
+        cancel.attach_tab(&tab);
+    }
+    let settings_tab = Arc::clone(&tab);
+    let settings = Arc::clone(&options);
+    let result = match run_blocking(move || apply_tab_settings(&settings_tab, &settings)).await {
+        Ok(()) => {
+            let trace_tab = Arc::clone(&tab);
+            let trace_options = Arc::clone(&options);
+            let trace =
+                tokio::task::spawn_blocking(move || start_trace(&trace_tab, &trace_options))
+                    .await
+                    .ok()
+                    .flatten();
+            let debug_tab = Arc::clone(&tab);
+            let debug_options = Arc::clone(&options);
+            let debug = tokio::task::spawn_blocking(move || {
+                start_debug(&debug_tab, &debug_options, start_time)
+            })
+            .await
+            .ok()
+            .flatten();
+
+            let result = until_cancelled(
+                options.cancel.as_ref(),
+                render_in_tab_async(&tab, source, &options, debug.as_ref()),
+            )
+            .await;
+
+            let trace_tab = Arc::clone(&tab);
+            let trace_options = Arc::clone(&options);
+            run_blocking(move || {
+                let result = finish_debug(&trace_tab, debug, &trace_options, result);
+                finish_trace(&trace_tab, trace, &trace_options, result)
+            })
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    let result = finish_cancellable(&options, result);
+
+    // Reset or close the tab and return the browser (best effort)
+    let succeeded = result.is_ok();
+    let reset_options = Arc::clone(&options);
+    let _ = tokio::task::spawn_blocking(move || {
+        clear_storage(&tab, &reset_options);
+        clear_cookies(&tab, &reset_options);
+        if succeeded && reset_tab_settings(&tab, &reset_options) {
+            browser.release_tab(tab);
+        } else {
+            browser.discard_tab(tab);
+        }
+    })
+    .await;
+
+    let result = match result {
+        Ok(rendered) if Rendered::needs_post_processing(&options) => {
+            run_blocking(move || rendered.post_processed(&options)).await
+        }
+        other => other,
+    };
+
+    log::debug!("Total PDF generation time: {:?}", start_time.elapsed());
+
+    result
+}
+
+/// Run `work`, abandoning it as soon as `cancel` is cancelled.
+///
+/// The tab is closed by the cancel itself; dropping `work` just stops
+/// waiting on it.
+async fn until_cancelled<T>(
+    cancel: Option<&CancelHandle>,
+    work: impl std::future::Future<Output = Result<T, PdfServiceError>>,
+) -> Result<T, PdfServiceError> {
+    let Some(cancel) = cancel else {
+        return work.await;
+    };
+
+    tokio::select! {
+        result = work => result,
+        () = cancel.cancelled() => Err(PdfServiceError::Cancelled(
+            "render cancelled while in progress".to_string(),
+        )),
+    }
+}
+
+/// Async version of [`render_in_tab`].
+async fn render_in_tab_async(
+    tab: &Arc<headless_chrome::Tab>,
+    source: OwnedPageSource,
+    options: &RenderOptions,
+    debug: Option<&DebugRecorder>,
+) -> Result<Rendered, PdfServiceError> {
+    let ready_tab = Arc::clone(tab);
+    let javascript_enabled = options.javascript_enabled;
+    let ready = tokio::task::spawn_blocking(move || ready_signal(&ready_tab, javascript_enabled))
+        .await
+        .ok()
+        .flatten();
+
+    let nav_start = Instant::now();
+    let offline_tab = Arc::clone(tab);
+    let offline = options.offline;
+    let take_offline = run_blocking(move || go_offline(&offline_tab, offline));
+    // HTML with a base URL is served there by a mock and loads like a URL
+    let source = match (source, &options.base_url) {
+        (OwnedPageSource::Html(_), Some(base_url)) => OwnedPageSource::Url(base_url.clone()),
+        (source, _) => source,
+    };
+    match source {
+        OwnedPageSource::Url(url) => {
+            mark_source(debug, PageSource::Url(&url));
+            check_redirects(options, navigate_async(tab, url).await)?;
+            run_actions_async(tab, options).await?;
+            mark_actions(debug, options);
+            take_offline.await?;
+            mark_offline(debug, options);
+        }
+        OwnedPageSource::Html(html) => {
+            mark_source(debug, PageSource::Html(&html));
+            take_offline.await?;
+            mark_offline(debug, options);
+            load_html_async(tab, html).await?;
+            run_actions_async(tab, options).await?;
+            mark_actions(debug, options);
+        }
+    }
+    log::debug!("Navigation completed in {:?}", nav_start.elapsed());
+
+    // Wait for JavaScript execution (nothing can signal readiness without it)
+    if options.javascript_enabled {
+        let outcome = wait_for_page_ready_async(
+            tab,
+            Arc::clone(&options.wait_expression),
+            ready,
+            options.wait_duration,
+        )
+        .await;
+        if let Some(debug) = debug {
+            debug.ready(outcome, options.wait_expression_source());
+        }
+    }
+    inject_async(tab, options).await?;
+
+    // Take the DOM before printing, which runs `beforeprint` handlers
+    let html = if options.captures_html() {
+        let html_tab = Arc::clone(tab);
+        Some(run_blocking(move || capture_html(&html_tab)).await?)
+    } else {
+        None
+    };
+
+    // Generate PDF
+    log::trace!("Generating PDF");
+    let pdf_start = Instant::now();
+
+    let print_tab = Arc::clone(tab);
+    let print_options = options.print_options();
+    let pdf_data = run_blocking(move || print_pdf(&print_tab, print_options)).await?;
+
+    log::debug!(
+        "PDF generated in {:?} ({} bytes)",
+        pdf_start.elapsed(),
+        pdf_data.len()
+    );
+    mark_printed(debug, pdf_data.len());
+
+    let thumbnail = match options.thumbnail_width {
+        Some(width) => {
+            let thumbnail_tab = Arc::clone(tab);
+            let page_size = options.page_size_px();
+            let (format, quality) = (options.thumbnail_format, options.thumbnail_quality);
+            let media = (options.print_media(), options.color_scheme);
+            Some(
+                run_blocking(move || {
+                    capture_thumbnail(&thumbnail_tab, page_size, width, format, quality, media)
+                })
+                .await?,
+            )
+        }
+        None => None,
+    };
+
+    Ok(Rendered {
+        pdf: pdf_data,
+        thumbnail,
+        thumbnail_format: options.thumbnail_format,
+        html,
+        pages: None,
+        outputs: options.outputs,
+        trace_id: None,
+        report: None,
+    })
+}
+
+/// Navigate the tab to a URL and wait for the load without blocking.
+///
+/// Issues `Page.navigate` directly to learn the navigation's loader ID,
+/// then waits for that loader's `networkAlmostIdle` lifecycle event - the
+/// same signal `wait_until_navigated` polls for - via a [`Notify`] instead
+/// of sleeping on a thread.
+///
+/// # Errors
+///
+/// - [`PdfServiceError::NavigationFailed`] if Chrome rejects the navigation
+/// - [`PdfServiceError::NavigationTimeout`] if the page has not settled
+///   within [`NAVIGATION_TIMEOUT_SECS`]
+async fn navigate_async(
+    tab: &Arc<headless_chrome::Tab>,
+    url: String,
+) -> Result<(), PdfServiceError> {
+    log::trace!("Navigating to URL: {}", truncate_url(&url, 100));
+
+    // Record idle loaders before navigating so a fast load can't be missed
+    let idle_loaders: Arc<Mutex<HashSet<String>>> = Arc::default();
+    let notify = Arc::new(Notify::new());
+    let listener = {
+        let idle_loaders = Arc::clone(&idle_loaders);
+        let notify = Arc::clone(&notify);
+        Arc::new(move |event: &Event| {
+            if let Event::PageLifecycleEvent(lifecycle) = event {
+                if lifecycle.params.name == "networkAlmostIdle" {
+                    if let Ok(mut idle) = idle_loaders.lock() {
+                        idle.insert(lifecycle.params.loader_id.clone());
+                    }
+                    notify.notify_one();
+                }
+            }
+        })
+    };
+    let listener = tab
+        .add_event_listener(listener)
+        .map_err(|e| PdfServiceError::NavigationFailed(e.to_string()))?;
+
+    let result = wait_for_navigation(tab, url, &idle_loaders, &notify).await;
+
+    let _ = tab.remove_event_listener(&listener);
+    result
+}
+
+/// Issue the navigation and wait for its loader to go idle.
+async fn wait_for_navigation(
+    tab: &Arc<headless_chrome::Tab>,
+    url: String,
+    idle_loaders: &Mutex<HashSet<String>>,
+    notify: &Notify,
+) -> Result<(), PdfServiceError> {
+    let nav_tab = Arc::clone(tab);
+    let navigated = run_blocking(move || {
+        nav_tab
+            .call_method(Page::Navigate {
+                url,
+                referrer: None,
+                transition_Type: None,
+                frame_id: None,
+                referrer_policy: None,
+            })
+            .map_err(|e| {
+                log::error!("❌ Failed to navigate to URL: {}", e);
+                PdfServiceError::NavigationFailed(e.to_string())
+            })
+    })
+    .await?;
+
+    if let Some(error_text) = navigated.error_text {
+        log::error!("❌ Failed to navigate to URL: {}", error_text);
+        return Err(navigation_failed(error_text));
+    }
+
+    // Same-document navigations have no loader and nothing to wait for
+    let Some(loader_id) = navigated.loader_id else {
+        return Ok(());
+    };
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(NAVIGATION_TIMEOUT_SECS);
+    loop {
+        let is_idle = idle_loaders
+            .lock()
+            .map(|idle| idle.contains(&loader_id))
+            .unwrap_or(false);
+        if is_idle {
+            return Ok(());
+        }
+
+        if tokio::time::timeout_at(deadline, notify.notified())
+            .await
+            .is_err()
+        {
+            log::error!("❌ Navigation timeout after {}s", NAVIGATION_TIMEOUT_SECS);
+            return Err(PdfServiceError::NavigationTimeout(format!(
+                "Page did not finish loading within {}s",
+                NAVIGATION_TIMEOUT_SECS
+            )));
+        }
+    }
+}
+
+/// Async version of [`load_html`].
+async fn load_html_async(
+    tab: &Arc<headless_chrome::Tab>,
+    html: String,
+) -> Result<(), PdfServiceError> {
+    log::trace!(
+        "Loading {} bytes of HTML via setDocumentContent",
+        html.len()
+    );
+
+    navigate_async(tab, "about:blank".to_string()).await?;
+
+    let content_tab = Arc::clone(tab);
+    run_blocking(move || {
+        let frame_id = content_tab
+            .call_method(Page::GetFrameTree(None))
+            .map_err(|e| {
+                log::error!("❌ Failed to get frame tree: {}", e);
+                PdfServiceError::NavigationFailed(e.to_string())
+            })?
+            .frame_tree
+            .frame
+            .id;
+
+        content_tab
+            .call_method(Page::SetDocumentContent { frame_id, html })
+            .map_err(|e| {
+                log::error!("❌ Failed to set document content: {}", e);
+                PdfServiceError::NavigationFailed(e.to_string())
+            })?;
+
+        Ok(())
+    })
+    .await?;
+
+    let max_wait = Duration::from_secs(HTML_LOAD_TIMEOUT_SECS);
+    let expression = Arc::from("document.readyState === 'complete'");
+    if poll_flag_async(tab, expression, max_wait).await {
+        Ok(())
+    } else {
+        log::error!("❌ HTML content still loading after {:?}", max_wait);
+        Err(PdfServiceError::NavigationTimeout(format!(
+            "HTML content did not finish loading within {:?}",
+            max_wait
+        )))
+    }
+}
+
+/// Async version of [`wait_for_page_ready`].
+///
+/// Sleeps on the Tokio timer between checks, so the wait costs no thread.
+async fn wait_for_page_ready_async(
+    tab: &Arc<headless_chrome::Tab>,
+    expression: Arc<str>,
+    ready: Option<Arc<ReadySignal>>,
+    max_wait: Duration,
+) -> ReadyOutcome {
+    let start = Instant::now();
+
+    log::trace!(
+        "Waiting up to {:?} for page to be ready (polling every {}ms)",
+        max_wait,
+        JS_POLL_INTERVAL_MS
+    );
+
+    let called_back = async {
+        match &ready {
+            Some(ready) => ready.fired().await,
+            None => std::future::pending().await,
+        }
+    };
+    let signaled = tokio::select! {
+        signaled = poll_flag_async(tab, expression, max_wait) => signaled,
+        () = called_back => true,
+    };
+    if signaled {
+        log::debug!("Page signaled ready after {:?}", start.elapsed());
+        ReadyOutcome::Signaled
+    } else {
+        log::debug!(
+            "Page wait completed after {:?} (timeout, proceeding anyway)",
+            start.elapsed()
+        );
+        ReadyOutcome::TimedOut
+    }
+}
+
+/// Poll a boolean expression every [`JS_POLL_INTERVAL_MS`] until it is
+/// `true` or `max_wait` elapses.
+///
+/// Returns whether the expression became `true`.
+async fn poll_flag_async(
+    tab: &Arc<headless_chrome::Tab>,
+    expression: Arc<str>,
+    max_wait: Duration,
+) -> bool {
+    let start = Instant::now();
+    let poll_interval = Duration::from_millis(JS_POLL_INTERVAL_MS);
+
+    loop {
+        let eval_tab = Arc::clone(tab);
+        let eval_expression = Arc::clone(&expression);
+        let is_true =
+            tokio::task::spawn_blocking(move || evaluate_flag(&eval_tab, &eval_expression))
+                .await
+                .unwrap_or(false);
+
+        if is_true {
+            return true;
+        }
+
+        if start.elapsed() >= max_wait {
+            return false;
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Run a fallible Chrome call on the blocking pool.
+async fn run_blocking<T, F>(f: F) -> Result<T, PdfServiceError>
+where
+    F: FnOnce() -> Result<T, PdfServiceError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|e| Err(PdfServiceError::Internal(e.to_string())))
+}
+
+/// Evaluate a boolean expression in the page.
+///
+/// Evaluation errors and non-boolean results count as `false`, so a page
+/// that is mid-navigation simply reads as "not yet".
+fn evaluate_flag(tab: &headless_chrome::Tab, expression: &str) -> bool {
+    tab.evaluate(expression, false)
+        .map(|result| result.value.and_then(|v| v.as_bool()).unwrap_or(false))
+        .unwrap_or(false)
+}
+
+/// Truncate a URL for logging purposes.
+///
+/// Data URLs can be extremely long (containing entire HTML documents).
+/// This function truncates them for readable log output.
+///
+/// # Arguments
+///
+/// * `url` - The URL to truncate
+/// * `max_len` - Maximum length before truncation
+///
+/// # Returns
+///
+/// The URL, truncated with "..." if longer than `max_len`.
+fn truncate_url(url: &str, max_len: usize) -> String {
+    if url.len() <= max_len {
+        url.to_string()
+    } else {
+        format!("{}...", &url[..max_len])
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    // URL Validation Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_validate_url_valid_https() {
+        let result = validate_url("https://example.com");
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "https://example.com/");
+    }
+
+    #[test]
+    fn test_validate_url_valid_http() {
+        let result = validate_url("http://example.com/path?query=value");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validate_url_valid_with_port() {
+        let result = validate_url("http://localhost:3000/api");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validate_url_empty() {
+        let result = validate_url("");
+        assert!(matches!(result, Err(PdfServiceError::InvalidUrl(_))));
+    }
+
+    #[test]
+    fn test_validate_url_whitespace_only() {
+        let result = validate_url("   ");
+        assert!(matches!(result, Err(PdfServiceError::InvalidUrl(_))));
+    }
+
+    #[test]
+    fn test_validate_url_no_scheme() {
+        let result = validate_url("example.com");
+        assert!(matches!(result, Err(PdfServiceError::InvalidUrl(_))));
+    }
+
+    #[test]
+    fn test_validate_url_relative() {
+        let result = validate_url("/path/to/page");
+        assert!(matches!(result, Err(PdfServiceError::InvalidUrl(_))));
+    }
+
+    #[test]
+    fn test_validate_url_data_url() {
+        let result = validate_url("data:text/html,<h1>Hello</h1>");
+        assert!(result.is_ok());
+    }
+
+    // -------------------------------------------------------------------------
+    // Helper Function Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_truncate_url_short() {
+        let url = "https://example.com";
+        assert_eq!(truncate_url(url, 50), url);
+    }
+
+    #[test]
+    fn test_truncate_url_long() {
+        let url = "https://example.com/very/long/path/that/exceeds/the/maximum/length";
+        let truncated = truncate_url(url, 30);
+        assert_eq!(truncated.len(), 33); // 30 + "..."
+        assert!(truncated.ends_with("..."));
+    }
+
+    #[test]
+    fn test_truncate_url_exact_length() {
+        let url = "https://example.com";
+        assert_eq!(truncate_url(url, url.len()), url);
+    }
+
+    #[test]
+    fn test_build_print_options_landscape() {
+        let options = build_print_options(
+            &default_print_options(),
+            Some(true),
+            Some(true),
+            &PageSetup::default(),
+        );
+        assert_eq!(options.landscape, Some(true));
+        assert_eq!(options.print_background, Some(true));
+    }
+
+    #[test]
+    fn test_build_print_options_portrait() {
+        let options = build_print_options(
+            &default_print_options(),
+            Some(false),
+            Some(false),
+            &PageSetup::default(),
+        );
+        assert_eq!(options.landscape, Some(false));
+        assert_eq!(options.print_background, Some(false));
+    }
+
+    #[test]
+    fn test_build_print_options_zero_margins() {
+        let options =
+            build_print_options(&default_print_options(), None, None, &PageSetup::default());
+        assert_eq!(options.margin_top, Some(0.0));
+        assert_eq!(options.margin_bottom, Some(0.0));
+        assert_eq!(options.margin_left, Some(0.0));
+        assert_eq!(options.margin_right, Some(0.0));
+    }
+
+    #[test]
+    fn test_build_print_options_no_header_footer() {
+        let options =
+            build_print_options(&default_print_options(), None, None, &PageSetup::default());
+        assert_eq!(options.display_header_footer, Some(false));
+    }
+
+    /// Verifies request fields override the pool's base print options,
+    /// which apply otherwise.
+    #[test]
+    fn test_build_print_options_precedence() {
+        let base = PrintToPdfOptions {
+            landscape: Some(true),
+            print_background: Some(false),
+            paper_width: Some(8.27),
+            paper_height: Some(11.69),
+            footer_template: Some("<span class=\"pageNumber\"></span>".to_string()),
+            ..Default::default()
+        };
+
+        let options = build_print_options(&base, None, None, &PageSetup::default());
+        assert_eq!(options.landscape, Some(true));
+        assert_eq!(options.print_background, Some(false));
+        assert_eq!(options.paper_width, Some(8.27));
+        assert_eq!(options.footer_template, base.footer_template);
+        assert_eq!(options.margin_top, None);
+
+        let options = build_print_options(&base, Some(false), Some(true), &PageSetup::default());
+        assert_eq!(options.landscape, Some(false));
+        assert_eq!(options.print_background, Some(true));
+    }
+
+    /// Verifies request page fields override the base print options, and
+    /// a paper format yields its size unless a dimension is given.
+    #[test]
+    fn test_build_print_options_page_setup() {
+        let page = PageSetup::new(
+            Some(PaperFormat::A4),
+            [None, Some(12.0)],
+            [Some(0.5), None, Some(0.25), None],
+            Some(0.8),
+            Some(" 1-3, 5 "),
+            None,
+            [None; 2],
+        )
+        .unwrap();
+        let options = build_print_options(&default_print_options(), None, None, &page);
+        assert_eq!(options.paper_width, Some(8.27));
+        assert_eq!(options.paper_height, Some(12.0));
+        assert_eq!(options.margin_top, Some(0.5));
+        assert_eq!(options.margin_bottom, Some(0.0));
+        assert_eq!(options.margin_left, Some(0.25));
+        assert_eq!(options.scale, Some(0.8));
+        assert_eq!(options.page_ranges.as_deref(), Some("1-3, 5"));
+
+        for (size, margins, scale, ranges) in [
+            ([Some(0.0), None], [None; 4], None, None),
+            ([None, Some(f64::NAN)], [None; 4], None, None),
+            ([Some(500.0), None], [None; 4], None, None),
+            ([None; 2], [None, Some(-0.1), None, None], None, None),
+            ([None; 2], [None; 4], Some(3.0), None),
+            ([None; 2], [None; 4], None, Some("")),
+            ([None; 2], [None; 4], None, Some("1-3; 7")),
+        ] {
+            assert!(matches!(
+                PageSetup::new(None, size, margins, scale, ranges, None, [None; 2]),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+    }
+
+    /// Verifies the tagged PDF and outline fields override the base print
+    /// options, and an outline turns tags on.
+    #[test]
+    fn test_build_print_options_structure() {
+        let base = PrintToPdfOptions {
+            generate_tagged_pdf: Some(true),
+            ..Default::default()
+        };
+        let options = build_print_options(&base, None, None, &PageSetup::default());
+        assert_eq!(options.generate_tagged_pdf, Some(true));
+        assert_eq!(options.generate_document_outline, None);
+
+        let page = PageSetup::default().with_structure(Some(false), None);
+        let options = build_print_options(&base, None, None, &page);
+        assert_eq!(options.generate_tagged_pdf, Some(false));
+
+        let page = PageSetup::default().with_structure(Some(false), Some(true));
+        let options = build_print_options(&default_print_options(), None, None, &page);
+        assert_eq!(options.generate_tagged_pdf, Some(true));
+        assert_eq!(options.generate_document_outline, Some(true));
+    }
+
+    /// Verifies `pdf_a` is rejected alongside tags, which Ghostscript drops.
+    #[test]
+    fn test_validate_pdf_a() {
+        assert!(!validate_pdf_a(false, Some(true), Some(true)).unwrap());
+        assert!(validate_pdf_a(true, Some(false), None).unwrap());
+        for (tagged, outline) in [(Some(true), None), (None, Some(true))] {
+            assert!(matches!(
+                validate_pdf_a(true, tagged, outline),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+    }
+
+    /// Verifies a request template turns the header and footer on, with
+    /// an empty partner, and overrides the pool's templates.
+    #[test]
+    fn test_build_print_options_templates() {
+        let base = PrintToPdfOptions {
+            header_template: Some("<span class=\"title\"></span>".to_string()),
+            display_header_footer: Some(false),
+            ..Default::default()
+        };
+        let footer = "<div style=\"font-size: 9px\"><span class=\"pageNumber\"></span></div>";
+        let page = PageSetup::new(
+            None,
+            [None; 2],
+            [None; 4],
+            None,
+            None,
+            None,
+            [None, Some(footer)],
+        )
+        .unwrap();
+        let options = build_print_options(&base, None, None, &page);
+        assert_eq!(options.display_header_footer, Some(true));
+        assert_eq!(options.header_template.as_deref(), Some(EMPTY_TEMPLATE));
+        assert_eq!(options.footer_template.as_deref(), Some(footer));
+
+        let page = PageSetup::new(
+            None,
+            [None; 2],
+            [None; 4],
+            None,
+            None,
+            Some(false),
+            [None, Some(footer)],
+        )
+        .unwrap();
+        let options = build_print_options(&base, None, None, &page);
+        assert_eq!(options.display_header_footer, Some(false));
+
+        let options = build_print_options(&base, None, None, &PageSetup::default());
+        assert_eq!(options.display_header_footer, Some(false));
+        assert_eq!(options.header_template, base.header_template);
+
+        let long = "x".repeat(MAX_TEMPLATE_LEN + 1);
+        assert!(matches!(
+            PageSetup::new(
+                None,
+                [None; 2],
+                [None; 4],
+                None,
+                None,
+                None,
+                [Some(&long), None]
+            ),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+    }
+
+    /// Verifies thumbnails follow the configured paper size and orientation.
+    #[test]
+    fn test_page_size_px() {
+        let mut options = RenderOptions::try_from(&PdfFromUrlRequest::default()).unwrap();
+        assert_eq!(options.page_size_px(), PAGE_SIZE_PX);
+
+        options.landscape = Some(true);
+        options.print_defaults = Arc::new(PrintToPdfOptions {
+            paper_width: Some(8.27),
+            paper_height: Some(11.69),
+            ..Default::default()
+        });
+        assert_eq!(options.page_size_px(), (1122, 794));
+    }
+
+    fn default_print_options() -> Arc<PrintToPdfOptions> {
+        crate::BrowserPoolConfig::default().print_options
+    }
+
+    // -------------------------------------------------------------------------
+    // Constants Tests
+    // -------------------------------------------------------------------------
+
+    /// Verifies the default thresholds keep the old criterion and custom
+    /// ones take an instance out of rotation early.
+    #[test]
+    fn test_meets_readiness() {
+        use crate::{BrowserPoolConfigBuilder, PoolStats};
+
+        let stats = |available, active| PoolStats {
+            available,
+            active,
+            total: active,
+        };
+        let config = BrowserPoolConfigBuilder::new()
+            .max_pool_size(3)
+            .warmup_count(0)
+            .build()
+            .unwrap();
+        assert!(meets_readiness(&config, &stats(0, 2), 0, 0, false));
+        assert!(meets_readiness(&config, &stats(1, 3), 0, 5, false));
+        assert!(!meets_readiness(&config, &stats(0, 3), 0, 0, true));
+
+        let config = BrowserPoolConfigBuilder::new()
+            .max_pool_size(3)
+            .warmup_count(0)
+            .ready_min_available(2)
+            .ready_max_queue_depth(1)
+            .ready_require_warmup(true)
+            .build()
+            .unwrap();
+        assert!(meets_readiness(&config, &stats(1, 2), 0, 1, true));
+        assert!(!meets_readiness(&config, &stats(0, 2), 0, 0, true));
+        assert!(!meets_readiness(&config, &stats(1, 2), 0, 2, true));
+        assert!(!meets_readiness(&config, &stats(1, 2), 0, 0, false));
+
+        // With several tabs per browser, busy browsers' free tabs count
+        let config = BrowserPoolConfigBuilder::new()
+            .max_pool_size(2)
+            .tabs_per_browser(3)
+            .warmup_count(0)
+            .ready_min_available(2)
+            .build()
+            .unwrap();
+        assert!(meets_readiness(&config, &stats(0, 2), 2, 0, false));
+        assert!(!meets_readiness(&config, &stats(0, 2), 1, 0, false));
+        assert!(meets_readiness(&config, &stats(0, 1), 0, 0, false));
+    }
+
+    #[test]
+    fn test_default_timeout_reasonable() {
+        // Timeout should be at least 30 seconds for complex pages
+        assert!(DEFAULT_TIMEOUT_SECS >= 30);
+        // But not more than 5 minutes (would be too long)
+        assert!(DEFAULT_TIMEOUT_SECS <= 300);
+    }
+
+    #[test]
+    fn test_default_wait_reasonable() {
+        // Wait should be at least 1 second for any JS
+        assert!(DEFAULT_WAIT_SECS >= 1);
+        // But not more than 30 seconds by default
+        assert!(DEFAULT_WAIT_SECS <= 30);
+    }
+
+    #[test]
+    fn test_poll_interval_reasonable() {
+        // Poll interval should be at least 100ms (not too aggressive)
+        assert!(JS_POLL_INTERVAL_MS >= 100);
+        // But not more than 1 second (responsive enough)
+        assert!(JS_POLL_INTERVAL_MS <= 1000);
+    }
+
+    /// Verifies an empty batch returns immediately without touching the pool.
+    #[test]
+    fn test_render_parallel_empty() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let results = render_parallel(&Mutex::new(pool), &[], 4);
+        assert!(results.is_empty());
+    }
+
+    /// Verifies failures are reported per job and in input order.
+    #[test]
+    fn test_render_parallel_preserves_order() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .factory(Box::new(MockBrowserFactory::always_fails("no chrome")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let jobs: Vec<RenderJob> = vec![
+            PdfFromUrlRequest {
+                url: "not a url".to_string(),
+                ..Default::default()
+            }
+            .into(),
+            PdfFromHtmlRequest::default().into(),
+            PdfFromUrlRequest {
+                url: "https://example.com".to_string(),
+                ..Default::default()
+            }
+            .into(),
+        ];
+
+        let results = render_parallel(&Mutex::new(pool), &jobs, 8);
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], Err(PdfServiceError::InvalidUrl(_))));
+        assert!(matches!(results[1], Err(PdfServiceError::EmptyHtml)));
+        assert!(matches!(
+            results[2],
+            Err(PdfServiceError::BrowserUnavailable(_))
+        ));
+    }
+
+    /// Verifies every job is handed to the callback exactly once, with its
+    /// index.
+    #[test]
+    fn test_render_parallel_with_reports_each_job() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .factory(Box::new(MockBrowserFactory::always_fails("no chrome")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let jobs: Vec<RenderJob> = vec![
+            PdfFromHtmlRequest::default().into(),
+            PdfFromUrlRequest {
+                url: "https://example.com".to_string(),
+                ..Default::default()
+            }
+            .into(),
+        ];
+
+        let reported = Mutex::new(Vec::new());
+        render_parallel_with(&Mutex::new(pool), &jobs, 2, |index, result, _elapsed| {
+            reported.lock().unwrap().push((index, result));
+        });
+
+        let mut reported = reported.into_inner().unwrap();
+        reported.sort_by_key(|(index, _)| *index);
+        assert_eq!(reported.len(), 2);
+        assert!(matches!(reported[0], (0, Err(PdfServiceError::EmptyHtml))));
+        assert!(matches!(
+            reported[1],
+            (1, Err(PdfServiceError::BrowserUnavailable(_)))
+        ));
+    }
+
+    /// Verifies metered renders are recorded per key and stop at the quota.
+    #[test]
+    fn test_render_metered_quota() {
+        use crate::BrowserPoolConfigBuilder;
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .config(
+                BrowserPoolConfigBuilder::new()
+                    .daily_render_quota(2)
+                    .build()
+                    .unwrap(),
+            )
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+        let pool = Mutex::new(pool);
+        let render = || Ok(PdfResponse::new(vec![0; 100], "a.pdf".to_string(), false));
+
+        assert!(render_metered(&pool, Some("team-a"), render).is_ok());
+        assert!(render_metered(&pool, Some("team-a"), render).is_ok());
+        assert!(matches!(
+            render_metered(&pool, Some("team-a"), render),
+            Err(PdfServiceError::QuotaExceeded(_))
+        ));
+
+        // Failures are not counted, and other keys have their own quota
+        let failed = render_metered(&pool, Some("team-b"), || Err(PdfServiceError::EmptyHtml));
+        assert!(matches!(failed, Err(PdfServiceError::EmptyHtml)));
+        assert!(render_metered(&pool, None, render).is_ok());
+
+        let usage = get_usage(&pool, Some("team-a")).unwrap();
+        assert_eq!(usage.today.renders, 2);
+        assert_eq!(usage.today.bytes, 200);
+        assert_eq!(usage.daily_quota, Some(2));
+        assert_eq!(usage.monthly_quota, None);
+        assert_eq!(get_usage(&pool, Some("team-b")).unwrap().total.renders, 0);
+        assert_eq!(get_usage(&pool, None).unwrap().total.renders, 1);
+    }
+
+    /// Verifies a render in progress already counts toward the quota, and
+    /// a panicking render is taken back.
+    #[test]
+    fn test_render_metered_reserves_quota() {
+        use crate::BrowserPoolConfigBuilder;
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .config(
+                BrowserPoolConfigBuilder::new()
+                    .daily_render_quota(1)
+                    .build()
+                    .unwrap(),
+            )
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+        let pool = Mutex::new(pool);
+        let render = || Ok(PdfResponse::new(vec![0; 100], "a.pdf".to_string(), false));
+
+        let outer = render_metered(&pool, Some("team-a"), || {
+            assert!(matches!(
+                render_metered(&pool, Some("team-a"), render),
+                Err(PdfServiceError::QuotaExceeded(_))
+            ));
+            render()
+        });
+        assert!(outer.is_ok());
+        assert_eq!(get_usage(&pool, Some("team-a")).unwrap().today.renders, 1);
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            render_metered(&pool, Some("team-b"), || panic!("render panicked"))
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(get_usage(&pool, Some("team-b")).unwrap().total.renders, 0);
+        assert!(render_metered(&pool, Some("team-b"), render).is_ok());
+    }
+
+    /// Verifies prewarming validates the URL before checking out a browser.
+    #[test]
+    fn test_prewarm_url_errors() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = Mutex::new(
+            BrowserPool::builder()
+                .factory(Box::new(MockBrowserFactory::always_fails("no chrome")))
+                .enable_keep_alive(false)
+                .build()
+                .unwrap(),
+        );
+
+        assert!(matches!(
+            prewarm_url(&pool, "not a url"),
+            Err(PdfServiceError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            prewarm_url(&pool, "https://example.com"),
+            Err(PdfServiceError::BrowserUnavailable(_))
+        ));
+    }
+
+    /// Verifies the async functions report the same errors as the blocking ones.
+    #[test]
+    fn test_async_generation_errors() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool: SharedBrowserPool = Arc::new(Mutex::new(
+            BrowserPool::builder()
+                .factory(Box::new(MockBrowserFactory::always_fails("no chrome")))
+                .enable_keep_alive(false)
+                .build()
+                .unwrap(),
+        ));
 
-    log::trace!(
-        "Waiting up to {:?} for page to be ready (polling every {:?})",
-        max_wait,
-        poll_interval
-    );
+        runtime.block_on(async {
+            let invalid = PdfFromUrlRequest {
+                url: "not a url".to_string(),
+                ..Default::default()
+            };
+            assert!(matches!(
+                generate_pdf_from_url_async(&pool, &invalid).await,
+                Err(PdfServiceError::InvalidUrl(_))
+            ));
 
-    while start.elapsed() < max_wait {
-        // Check if page signals completion
-        let is_done = tab
-            .evaluate("window.isPageDone === true", false)
-            .map(|result| result.value.and_then(|v| v.as_bool()).unwrap_or(false))
-            .unwrap_or(false);
+            assert!(matches!(
+                generate_pdf_from_html_async(&pool, &PdfFromHtmlRequest::default()).await,
+                Err(PdfServiceError::EmptyHtml)
+            ));
 
-        if is_done {
-            log::debug!("Page signaled ready after {:?}", start.elapsed());
-            return;
+            let valid = PdfFromUrlRequest {
+                url: "https://example.com".to_string(),
+                ..Default::default()
+            };
+            assert!(matches!(
+                generate_pdf_from_url_async(&pool, &valid).await,
+                Err(PdfServiceError::BrowserUnavailable(_))
+            ));
+        });
+    }
+
+    /// Verifies `block_resources` names are mapped and validated before any
+    /// browser is checked out.
+    #[test]
+    fn test_block_resources_validation() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let request = PdfFromHtmlRequest {
+            html: "<p>x</p>".to_string(),
+            block_resources: Some(vec![
+                "Image".to_string(),
+                " font ".to_string(),
+                "image".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(
+            options.blocked_resources,
+            vec![ResourceType::Image, ResourceType::Font]
+        );
+        assert!(options.blocked_urls.is_empty());
+
+        let request = PdfFromHtmlRequest {
+            html: "<p>x</p>".to_string(),
+            block_resources: Some(vec!["Trackers".to_string()]),
+            block_urls: Some(vec!["doubleclick.net".to_string()]),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert!(options.blocked_resources.is_empty());
+        assert_eq!(options.blocked_urls.len(), TRACKER_DOMAINS.len() * 2);
+        assert!(
+            options
+                .blocked_urls
+                .contains(&"*://*.google-analytics.com/*".to_string())
+        );
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = Mutex::new(
+            BrowserPool::builder()
+                .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+                .enable_keep_alive(false)
+                .build()
+                .unwrap(),
+        );
+        let invalid = PdfFromUrlRequest {
+            url: "https://example.com".to_string(),
+            block_resources: Some(vec!["document".to_string()]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate_pdf_from_url(&pool, &invalid),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+    }
+
+    /// Verifies thumbnail widths default when requested and are range-checked.
+    #[test]
+    fn test_thumbnail_width_validation() {
+        let options = RenderOptions::try_from(&PdfFromUrlRequest::default()).unwrap();
+        assert_eq!(options.thumbnail_width, None);
+
+        let mut request = PdfFromHtmlRequest {
+            html: "<p>x</p>".to_string(),
+            thumbnail: Some(true),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(options.thumbnail_width, Some(DEFAULT_THUMBNAIL_WIDTH));
+
+        // Width alone does not request a thumbnail
+        request.thumbnail = None;
+        request.thumbnail_width = Some(5000);
+        assert!(RenderOptions::try_from(&request).is_ok());
+
+        request.thumbnail = Some(true);
+        assert!(matches!(
+            RenderOptions::try_from(&request),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+        request.thumbnail_width = Some(8);
+        assert!(RenderOptions::try_from(&request).is_err());
+    }
+
+    /// Verifies thumbnail formats parse and quality is checked against them.
+    #[test]
+    fn test_thumbnail_format_validation() {
+        let options = RenderOptions::try_from(&PdfFromUrlRequest::default()).unwrap();
+        assert_eq!(options.thumbnail_format, ImageFormat::Png);
+        assert_eq!(options.thumbnail_quality, None);
+
+        let mut request = PdfFromUrlRequest {
+            thumbnail: Some(true),
+            thumbnail_format: Some("webp".to_string()),
+            thumbnail_quality: Some(60),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(options.thumbnail_format, ImageFormat::Webp);
+        assert_eq!(options.thumbnail_quality, Some(60));
+
+        for quality in [0, 101] {
+            request.thumbnail_quality = Some(quality);
+            assert!(matches!(
+                RenderOptions::try_from(&request),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
         }
 
-        // Sleep before next poll
-        std::thread::sleep(poll_interval);
+        // PNG is lossless and takes no quality
+        request.thumbnail_format = Some("png".to_string());
+        request.thumbnail_quality = Some(60);
+        assert!(RenderOptions::try_from(&request).is_err());
+
+        request.thumbnail_format = Some("avif".to_string());
+        request.thumbnail_quality = None;
+        match RenderOptions::try_from(&request) {
+            Err(PdfServiceError::InvalidRequest(message)) => assert!(message.contains("webp")),
+            other => panic!("expected InvalidRequest, got {:?}", other.map(|_| ())),
+        }
     }
 
-    log::debug!(
-        "Page wait completed after {:?} (timeout, proceeding anyway)",
-        start.elapsed()
-    );
-}
+    /// Verifies storage is bound to the URL's origin and safely embedded.
+    #[test]
+    fn test_storage_seed() {
+        let storage = WebStorage {
+            local: [("token".to_string(), "a\"b</script>".to_string())].into(),
+            ..Default::default()
+        };
 
-/// Safely close a browser tab, ignoring errors.
-///
-/// Tab cleanup is best-effort. If it fails, we log a warning but don't
-/// propagate the error since the PDF generation already succeeded.
-///
-/// # Why Best-Effort?
-///
-/// - The PDF data is already captured
-/// - Tab resources will be cleaned up when the browser is recycled
-/// - Failing here would discard a valid PDF
-/// - Some errors (e.g., browser already closed) are expected
-///
-/// # Arguments
-///
-/// * `tab` - The browser tab to close
-fn close_tab_safely(tab: &headless_chrome::Tab) {
-    log::trace!("Closing browser tab");
+        let seed = StorageSeed::new("https://app.example.com:8443/report?x=1", Some(&storage))
+            .unwrap()
+            .unwrap();
+        assert_eq!(seed.origin, "https://app.example.com:8443");
+        assert!(
+            seed.script
+                .contains(r#"location.origin !== "https://app.example.com:8443""#)
+        );
+        assert!(seed.script.contains(r#"{"token":"a\"b</script>"}"#));
 
-    if let Err(e) = tab.close(true) {
-        // Log but don't fail - PDF generation already succeeded
-        log::warn!(
-            "Failed to close tab (continuing anyway, resources will be cleaned up): {}",
-            e
+        assert!(
+            StorageSeed::new("https://example.com", Some(&WebStorage::default()))
+                .unwrap()
+                .is_none()
         );
-    } else {
-        log::trace!("Tab closed successfully");
+        assert!(
+            StorageSeed::new("https://example.com", None)
+                .unwrap()
+                .is_none()
+        );
+        assert!(matches!(
+            StorageSeed::new("file:///srv/report.html", Some(&storage)),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
     }
-}
 
-/// Truncate a URL for logging purposes.
-///
-/// Data URLs can be extremely long (containing entire HTML documents).
-/// This function truncates them for readable log output.
-///
-/// # Arguments
-///
-/// * `url` - The URL to truncate
-/// * `max_len` - Maximum length before truncation
-///
-/// # Returns
-///
-/// The URL, truncated with "..." if longer than `max_len`.
-fn truncate_url(url: &str, max_len: usize) -> String {
-    if url.len() <= max_len {
-        url.to_string()
-    } else {
-        format!("{}...", &url[..max_len])
+    /// Verifies `*` wildcards match like Chrome's URL patterns.
+    #[test]
+    fn test_matches_url_pattern() {
+        let url = "https://cdn.example.com/widgets/chat.js?v=3";
+        for pattern in [
+            "*",
+            "*://cdn.example.com/*",
+            "*://*.example.com/*",
+            "https://cdn.example.com/widgets/*.js*",
+            url,
+        ] {
+            assert!(matches_url_pattern(pattern, url), "{}", pattern);
+        }
+        for pattern in [
+            "*://example.com/*",
+            "https://cdn.example.com/widgets/chat.js",
+            "*.css",
+            "http://*",
+        ] {
+            assert!(!matches_url_pattern(pattern, url), "{}", pattern);
+        }
+        assert_eq!(
+            fetch_url_pattern("https://x.test/a?b=1"),
+            "https://x.test/a\\?b=1"
+        );
     }
-}
 
-// ============================================================================
-// Unit Tests
-// ============================================================================
+    /// Verifies mocks are validated and the longest pattern is tried first.
+    #[test]
+    fn test_parse_mock_responses() {
+        let mocks: BTreeMap<String, MockResponse> = [
+            ("example.com".to_string(), MockResponse::default()),
+            (
+                "https://api.example.com/prices/*".to_string(),
+                MockResponse {
+                    status: Some(503),
+                    body: "{}".to_string(),
+                    ..Default::default()
+                },
+            ),
+        ]
+        .into();
+        let parsed = parse_mock_responses(Some(&mocks)).unwrap();
+        assert_eq!(parsed.len(), 3);
+        let url = "https://api.example.com/prices/today";
+        let mock = parsed
+            .iter()
+            .find(|mock| matches_url_pattern(&mock.pattern, url))
+            .unwrap();
+        assert_eq!(mock.status, 503);
+        assert_eq!(mock.fulfill("r1".to_string()).body.as_deref(), Some("e30="));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        for (pattern, status) in [(" ", None), ("a b", None), ("example.com", Some(99))] {
+            let mocks: BTreeMap<String, MockResponse> = [(
+                pattern.to_string(),
+                MockResponse {
+                    status,
+                    ..Default::default()
+                },
+            )]
+            .into();
+            assert!(matches!(
+                parse_mock_responses(Some(&mocks)),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+    }
 
-    // -------------------------------------------------------------------------
-    // URL Validation Tests
-    // -------------------------------------------------------------------------
+    /// Verifies `base_url` is validated and the HTML served at it.
+    #[test]
+    fn test_html_base_url() {
+        assert_eq!(validate_base_url(None).unwrap(), None);
+        assert_eq!(validate_base_url(Some(" ")).unwrap(), None);
+        assert_eq!(
+            validate_base_url(Some("https://example.com#top")).unwrap(),
+            Some("https://example.com/".to_string())
+        );
+        for url in ["example.com", "file:///tmp/", "javascript:alert(1)"] {
+            assert!(matches!(
+                validate_base_url(Some(url)),
+                Err(PdfServiceError::InvalidUrl(_))
+            ));
+        }
 
+        let request = PdfFromHtmlRequest {
+            html: "<img src=\"logo.png\">".to_string(),
+            base_url: Some("https://example.com/reports/".to_string()),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(
+            options.base_url.as_deref(),
+            Some("https://example.com/reports/")
+        );
+        assert!(uses_interception(&options));
+        assert!(!options.isolated);
+        let document = options
+            .mocks
+            .iter()
+            .find(|mock| matches_url_pattern(&mock.pattern, "https://example.com/reports/"))
+            .unwrap();
+        assert_eq!(
+            document.fulfill("r1".to_string()).body.as_deref(),
+            Some("PGltZyBzcmM9ImxvZ28ucG5nIj4=")
+        );
+        assert!(!options.mocks.iter().any(|mock| matches_url_pattern(
+            &mock.pattern,
+            "https://example.com/reports/logo.png"
+        )));
+
+        // Offline, the tab must stay online to load the document, so
+        // everything else is failed by interception instead
+        let request = PdfFromHtmlRequest {
+            offline: Some(true),
+            ..request
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert!(options.offline);
+        assert!(options.isolated);
+        let request = PdfFromHtmlRequest {
+            base_url: None,
+            ..request
+        };
+        assert!(!RenderOptions::try_from(&request).unwrap().isolated);
+    }
+
+    /// Verifies uploaded files are served from the assets origin, with or
+    /// without a query string, and other URLs there get a 404.
     #[test]
-    fn test_validate_url_valid_https() {
-        let result = validate_url("https://example.com");
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "https://example.com/");
+    fn test_asset_mocks() {
+        let mut request = PdfFromAssetsRequest {
+            document: PdfFromHtmlRequest {
+                html: "<h1>Report</h1>".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        request
+            .assets
+            .insert("css/site.css".to_string(), bytes::Bytes::from_static(b"{}"));
+        let mocks = asset_mocks(&request, &[]);
+
+        let served = |url: &str| {
+            let mock = mocks
+                .iter()
+                .find(|mock| matches_url_pattern(&mock.pattern, url))
+                .unwrap();
+            (mock.status, mock.fulfill("r1".to_string()))
+        };
+        let (status, index) = served("https://assets.html2pdf.invalid/index.html");
+        assert_eq!(status, 200);
+        assert_eq!(
+            index.response_headers.unwrap()[0].value,
+            "text/html; charset=utf-8"
+        );
+        let (status, css) = served("https://assets.html2pdf.invalid/css/site.css?v=2");
+        assert_eq!(status, 200);
+        assert_eq!(css.body.as_deref(), Some("e30="));
+        assert_eq!(
+            served("https://assets.html2pdf.invalid/css/other.css").0,
+            404
+        );
+        assert!(
+            !mocks
+                .iter()
+                .any(|mock| matches_url_pattern(&mock.pattern, "https://example.com/"))
+        );
     }
 
+    /// Verifies actions are validated and navigate URLs normalized.
     #[test]
-    fn test_validate_url_valid_http() {
-        let result = validate_url("http://example.com/path?query=value");
-        assert!(result.is_ok());
+    fn test_parse_actions() {
+        let actions = [
+            PageAction::Click("#accept".to_string()),
+            PageAction::Navigate(" https://example.com ".to_string()),
+        ];
+        let parsed = parse_actions(Some(&actions)).unwrap();
+        assert_eq!(parsed[0], actions[0]);
+        assert_eq!(
+            parsed[1],
+            PageAction::Navigate("https://example.com/".to_string())
+        );
+        assert!(parse_actions(None).unwrap().is_empty());
+
+        let typed = PageAction::Type {
+            selector: "#password".to_string(),
+            text: "hunter2".to_string(),
+        };
+        assert!(!describe_action(&typed).contains("hunter2"));
+
+        for invalid in [
+            PageAction::WaitFor(" ".to_string()),
+            PageAction::Type {
+                selector: String::new(),
+                text: "x".to_string(),
+            },
+            PageAction::Navigate("file:///etc/passwd".to_string()),
+            PageAction::Navigate("not a url".to_string()),
+        ] {
+            assert!(
+                matches!(
+                    parse_actions(Some(std::slice::from_ref(&invalid))),
+                    Err(PdfServiceError::InvalidRequest(_))
+                ),
+                "{:?}",
+                invalid
+            );
+        }
+        let too_many = vec![PageAction::WaitFor("body".to_string()); MAX_ACTIONS + 1];
+        assert!(parse_actions(Some(&too_many)).is_err());
     }
 
+    /// Verifies the navigation method options are checked.
     #[test]
-    fn test_validate_url_valid_with_port() {
-        let result = validate_url("http://localhost:3000/api");
-        assert!(result.is_ok());
+    fn test_post_navigation_validation() {
+        let request = |method: Option<&str>, post_data: Option<&str>| PdfFromUrlRequest {
+            method: method.map(String::from),
+            post_data: post_data.map(String::from),
+            ..Default::default()
+        };
+
+        assert!(PostNavigation::new(&request(None, None)).unwrap().is_none());
+        assert!(
+            PostNavigation::new(&request(Some("get"), None))
+                .unwrap()
+                .is_none()
+        );
+        let post = PostNavigation::new(&request(Some("post"), Some("a=1")))
+            .unwrap()
+            .unwrap();
+        assert_eq!(post.body, "YT0x");
+        assert_eq!(post.content_type, DEFAULT_POST_CONTENT_TYPE);
+
+        for (method, post_data) in [(Some("PUT"), None), (None, Some("a=1"))] {
+            assert!(matches!(
+                PostNavigation::new(&request(method, post_data)),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
     }
 
+    /// Verifies only the first request is rewritten, keeping its headers.
     #[test]
-    fn test_validate_url_empty() {
-        let result = validate_url("");
-        assert!(matches!(result, Err(PdfServiceError::InvalidUrl(_))));
+    fn test_post_navigation_rewrite() {
+        let post = PostNavigation::new(&PdfFromUrlRequest {
+            method: Some("POST".to_string()),
+            post_data: Some("{}".to_string()),
+            content_type: Some("application/json".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+        .unwrap();
+        let request: Network::Request = serde_json::from_value(serde_json::json!({
+            "url": "https://reports.example.com/run",
+            "method": "GET",
+            "headers": {"Accept": "text/html", "content-type": "text/plain"},
+            "initialPriority": "VeryHigh",
+            "referrerPolicy": "no-referrer",
+        }))
+        .unwrap();
+
+        let rewritten = post.rewrite("req-1", &request).unwrap();
+        assert_eq!(rewritten.method.as_deref(), Some("POST"));
+        assert_eq!(rewritten.post_data.as_deref(), Some("e30="));
+        let headers = rewritten.headers.unwrap();
+        assert_eq!(headers.len(), 2);
+        assert!(
+            headers
+                .iter()
+                .any(|h| h.name == "Content-Type" && h.value == "application/json")
+        );
+
+        assert!(post.rewrite("req-2", &request).is_none());
     }
 
+    /// Verifies extra headers are scoped to the URL's origin, replace
+    /// same-named headers, and combine with a `POST` rewrite.
     #[test]
-    fn test_validate_url_whitespace_only() {
-        let result = validate_url("   ");
-        assert!(matches!(result, Err(PdfServiceError::InvalidUrl(_))));
+    fn test_extra_headers() {
+        let headers = BTreeMap::from([
+            ("Authorization".to_string(), "Bearer t0ken".to_string()),
+            ("X-Tenant".to_string(), "acme".to_string()),
+        ]);
+        let extra = ExtraHeaders::new("https://app.example.com/report", Some(&headers), None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(extra.origin, "https://app.example.com");
+        assert!(extra.applies_to("https://app.example.com/api/data"));
+        assert!(!extra.applies_to("https://cdn.example.com/app.js"));
+        assert!(!extra.applies_to("http://app.example.com/"));
+        assert!(!extra.applies_to("https://app.example.com:8443/"));
+
+        let request: Network::Request = serde_json::from_value(serde_json::json!({
+            "url": "https://app.example.com/report",
+            "method": "GET",
+            "headers": {"Accept": "text/html", "authorization": "Basic old"},
+            "initialPriority": "VeryHigh",
+            "referrerPolicy": "no-referrer",
+        }))
+        .unwrap();
+        let continued = extra.apply(None, "req-1", &request);
+        assert!(continued.method.is_none());
+        let sent = continued.headers.unwrap();
+        assert_eq!(sent.len(), 3);
+        assert!(
+            sent.iter()
+                .any(|h| h.name == "Authorization" && h.value == "Bearer t0ken")
+        );
+        assert!(!sent.iter().any(|h| h.value == "Basic old"));
+
+        let post = PostNavigation::new(&PdfFromUrlRequest {
+            method: Some("POST".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+        .unwrap();
+        let continued = extra.apply(post.rewrite("req-2", &request), "req-2", &request);
+        assert_eq!(continued.method.as_deref(), Some("POST"));
+        assert_eq!(continued.headers.unwrap().len(), 4);
+
+        assert!(
+            ExtraHeaders::new("https://a.test/", None, None)
+                .unwrap()
+                .is_none()
+        );
+        for (name, value) in [("Bad Name", "x"), ("X-Ok", "a\r\nInjected: 1"), ("", "x")] {
+            let headers = BTreeMap::from([(name.to_string(), value.to_string())]);
+            assert!(matches!(
+                ExtraHeaders::new("https://a.test/", Some(&headers), None),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+        assert!(ExtraHeaders::new("file:///srv/a.html", Some(&headers), None).is_err());
     }
 
+    /// Verifies `auth` becomes an origin-scoped `Authorization` header.
     #[test]
-    fn test_validate_url_no_scheme() {
-        let result = validate_url("example.com");
-        assert!(matches!(result, Err(PdfServiceError::InvalidUrl(_))));
+    fn test_auth_header() {
+        let basic = AuthSpec::Basic {
+            username: "viewer".to_string(),
+            password: "s3cret".to_string(),
+        };
+        let extra = ExtraHeaders::new("https://grafana.internal/d/ops", None, Some(&basic))
+            .unwrap()
+            .unwrap();
+        assert_eq!(extra.origin, "https://grafana.internal");
+        assert_eq!(extra.auth_scheme, Some("basic"));
+        assert_eq!(extra.headers.len(), 1);
+        assert_eq!(extra.headers[0].name, "Authorization");
+        assert_eq!(extra.headers[0].value, "Basic dmlld2VyOnMzY3JldA==");
+
+        let bearer = AuthSpec::Bearer {
+            token: " t0ken ".to_string(),
+        };
+        let extra = ExtraHeaders::new("https://api.example.com/", None, Some(&bearer))
+            .unwrap()
+            .unwrap();
+        assert_eq!(extra.headers[0].value, "Bearer t0ken");
+
+        let headers = BTreeMap::from([("authorization".to_string(), "x".to_string())]);
+        assert!(ExtraHeaders::new("https://a.test/", Some(&headers), Some(&bearer)).is_err());
+        for auth in [
+            AuthSpec::Basic {
+                username: "a:b".to_string(),
+                password: String::new(),
+            },
+            AuthSpec::Basic {
+                username: "a".to_string(),
+                password: "x\r\nInjected: 1".to_string(),
+            },
+            AuthSpec::Bearer {
+                token: "two words".to_string(),
+            },
+        ] {
+            assert!(matches!(
+                ExtraHeaders::new("https://a.test/", None, Some(&auth)),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+        assert!(ExtraHeaders::new("file:///srv/a.html", None, Some(&basic)).is_err());
     }
 
+    /// Verifies renders with cookies, headers, auth, or storage have the
+    /// cookie jar cleared afterwards.
     #[test]
-    fn test_validate_url_relative() {
-        let result = validate_url("/path/to/page");
-        assert!(matches!(result, Err(PdfServiceError::InvalidUrl(_))));
+    fn test_sends_credentials() {
+        let url = "https://app.example.com/r".to_string();
+        let plain = PdfFromUrlRequest {
+            url: url.clone(),
+            ..Default::default()
+        };
+        assert!(!sends_credentials(
+            &RenderOptions::try_from(&plain).unwrap()
+        ));
+
+        for request in [
+            PdfFromUrlRequest {
+                cookies: Some(vec!["session=abc".parse().unwrap()]),
+                ..plain.clone()
+            },
+            PdfFromUrlRequest {
+                headers: Some([("X-Tenant".to_string(), "acme".to_string())].into()),
+                ..plain.clone()
+            },
+            PdfFromUrlRequest {
+                auth: Some(AuthSpec::Bearer {
+                    token: "t0ken".to_string(),
+                }),
+                ..plain.clone()
+            },
+            PdfFromUrlRequest {
+                storage: Some(WebStorage {
+                    local: [("auth_token".to_string(), "eyJ".to_string())].into(),
+                    ..Default::default()
+                }),
+                ..plain.clone()
+            },
+        ] {
+            assert!(sends_credentials(
+                &RenderOptions::try_from(&request).unwrap()
+            ));
+        }
     }
 
+    /// Verifies cookies are bound to the URL's site and validated.
     #[test]
-    fn test_validate_url_data_url() {
-        let result = validate_url("data:text/html,<h1>Hello</h1>");
-        assert!(result.is_ok());
+    fn test_parse_cookies() {
+        let cookies = vec![
+            CookieSpec {
+                name: "session".to_string(),
+                value: "abc".to_string(),
+                http_only: Some(true),
+                ..Default::default()
+            },
+            CookieSpec {
+                name: "tenant".to_string(),
+                value: "acme".to_string(),
+                domain: Some(".example.com".to_string()),
+                path: Some("/reports".to_string()),
+                ..Default::default()
+            },
+        ];
+        let params = parse_cookies("https://app.example.com/r?id=1", Some(&cookies)).unwrap();
+        assert_eq!(params.len(), 2);
+        assert_eq!(
+            params[0].url.as_deref(),
+            Some("https://app.example.com/r?id=1")
+        );
+        assert_eq!(params[0].domain, None);
+        assert_eq!(params[0].path.as_deref(), Some("/"));
+        assert_eq!(params[0].http_only, Some(true));
+        assert_eq!(params[1].domain.as_deref(), Some(".example.com"));
+        assert_eq!(params[1].path.as_deref(), Some("/reports"));
+
+        assert!(parse_cookies("https://a.test/", None).unwrap().is_empty());
+        let bad = |cookie: CookieSpec| parse_cookies("https://app.example.com/", Some(&[cookie]));
+        for cookie in [
+            CookieSpec {
+                name: "a b".to_string(),
+                ..Default::default()
+            },
+            CookieSpec {
+                name: "a".to_string(),
+                value: "x; Path=/".to_string(),
+                ..Default::default()
+            },
+            CookieSpec {
+                name: "a".to_string(),
+                domain: Some("evil.test".to_string()),
+                ..Default::default()
+            },
+            CookieSpec {
+                name: "a".to_string(),
+                domain: Some("ample.com".to_string()),
+                ..Default::default()
+            },
+            CookieSpec {
+                name: "a".to_string(),
+                path: Some("reports".to_string()),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                bad(cookie),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+        assert!(parse_cookies("file:///srv/a.html", Some(&cookies)).is_err());
     }
 
-    // -------------------------------------------------------------------------
-    // Helper Function Tests
-    // -------------------------------------------------------------------------
+    /// Verifies timezone names are trimmed and obviously bad ones rejected.
+    #[test]
+    fn test_validate_timezone() {
+        assert_eq!(validate_timezone(None).unwrap(), None);
+        assert_eq!(validate_timezone(Some("  ")).unwrap(), None);
+        assert_eq!(
+            validate_timezone(Some(" America/Argentina/Buenos_Aires ")).unwrap(),
+            Some("America/Argentina/Buenos_Aires".to_string())
+        );
+        assert_eq!(
+            validate_timezone(Some("Etc/GMT+5")).unwrap(),
+            Some("Etc/GMT+5".to_string())
+        );
+        for bad in ["Europe/Paris; DROP", "../../etc/passwd", &"A".repeat(65)] {
+            assert!(matches!(
+                validate_timezone(Some(bad)),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+    }
 
+    /// Verifies an image in `outputs` implies a thumbnail in that format.
     #[test]
-    fn test_truncate_url_short() {
-        let url = "https://example.com";
-        assert_eq!(truncate_url(url, 50), url);
+    fn test_outputs_options() {
+        let request = PdfFromUrlRequest {
+            outputs: Some(vec!["pdf".to_string(), "jpeg".to_string()]),
+            thumbnail_format: Some("webp".to_string()),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(options.thumbnail_width, Some(DEFAULT_THUMBNAIL_WIDTH));
+        assert_eq!(options.thumbnail_format, ImageFormat::Jpeg);
+        assert!(!options.captures_html());
+
+        let request = PdfFromHtmlRequest {
+            html: "<p>hi</p>".to_string(),
+            thumbnail: Some(true),
+            outputs: Some(vec!["pdf".to_string(), "html".to_string()]),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(options.thumbnail_width, None);
+        assert!(options.captures_html());
+
+        let request = PdfFromUrlRequest {
+            outputs: Some(vec!["svg".to_string()]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            RenderOptions::try_from(&request),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
     }
 
+    /// Verifies `file://` URLs are rejected unless they resolve inside an
+    /// allowed directory.
     #[test]
-    fn test_truncate_url_long() {
-        let url = "https://example.com/very/long/path/that/exceeds/the/maximum/length";
-        let truncated = truncate_url(url, 30);
-        assert_eq!(truncated.len(), 33); // 30 + "..."
-        assert!(truncated.ends_with("..."));
+    fn test_resolve_file_url() {
+        let root = std::env::temp_dir().join(format!("html2pdf-file-url-{}", std::process::id()));
+        let allowed = root.join("allowed");
+        std::fs::create_dir_all(&allowed).unwrap();
+        std::fs::write(allowed.join("report.html"), "<h1>Report</h1>").unwrap();
+        std::fs::write(root.join("secret.html"), "secret").unwrap();
+        let dirs = vec![allowed.clone()];
+        let file_url = |path: PathBuf| url::Url::from_file_path(path).unwrap().to_string();
+
+        // Other schemes are untouched
+        assert_eq!(
+            resolve_file_url("https://example.com/", &[]).unwrap(),
+            "https://example.com/"
+        );
+
+        // Disabled by default
+        let report = file_url(allowed.join("report.html"));
+        assert!(matches!(
+            resolve_file_url(&report, &[]),
+            Err(PdfServiceError::InvalidUrl(_))
+        ));
+
+        let resolved = resolve_file_url(&format!("{}#page=2", report), &dirs).unwrap();
+        assert!(resolved.ends_with("/allowed/report.html#page=2"));
+
+        assert!(resolve_file_url(&file_url(root.join("secret.html")), &dirs).is_err());
+        assert!(
+            resolve_file_url(
+                &format!("{}/../secret.html", file_url(allowed.clone())),
+                &dirs
+            )
+            .is_err()
+        );
+        assert!(resolve_file_url(&file_url(allowed.join("missing.html")), &dirs).is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.join("secret.html"), allowed.join("link.html"))
+                .unwrap();
+            assert!(resolve_file_url(&file_url(allowed.join("link.html")), &dirs).is_err());
+        }
+
+        std::fs::remove_dir_all(&root).unwrap();
     }
 
+    /// Verifies readiness expressions default to `window.isPageDone`, are
+    /// wrapped for truthiness, and are length-checked.
     #[test]
-    fn test_truncate_url_exact_length() {
-        let url = "https://example.com";
-        assert_eq!(truncate_url(url, url.len()), url);
+    fn test_wait_expression_validation() {
+        let options = RenderOptions::try_from(&PdfFromUrlRequest::default()).unwrap();
+        assert_eq!(
+            &*options.wait_expression,
+            "!!(\nwindow.isPageDone === true\n)"
+        );
+
+        let mut request = PdfFromHtmlRequest {
+            html: "<p>x</p>".to_string(),
+            wait_for_expression: Some("window.__APP_READY__ // set by the app".to_string()),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(
+            &*options.wait_expression,
+            "!!(\nwindow.__APP_READY__ // set by the app\n)"
+        );
+
+        request.wait_for_expression = Some("  ".to_string());
+        assert!(matches!(
+            RenderOptions::try_from(&request),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+        request.wait_for_expression = Some("x".repeat(MAX_WAIT_EXPRESSION_LEN + 1));
+        assert!(RenderOptions::try_from(&request).is_err());
     }
 
+    /// Verifies the color scheme is sent with every media type change,
+    /// and that clearing sends no features.
     #[test]
-    fn test_build_print_options_landscape() {
-        let options = build_print_options(true, true).unwrap();
-        assert_eq!(options.landscape, Some(true));
-        assert_eq!(options.print_background, Some(true));
+    fn test_emulated_media() {
+        let call = emulated_media("print", Some(ColorScheme::Dark));
+        assert_eq!(call.media.as_deref(), Some("print"));
+        let features = call.features.unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].name, "prefers-color-scheme");
+        assert_eq!(features[0].value, "dark");
+
+        let call = emulated_media("", None);
+        assert_eq!(call.features, Some(Vec::new()));
+
+        let request = PdfFromHtmlRequest {
+            html: "<p>x</p>".to_string(),
+            color_scheme: Some("DARK".to_string()),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(options.color_scheme, Some(ColorScheme::Dark));
+        assert_eq!(options.print_media(), MediaType::Print);
     }
 
+    /// Verifies viewport fields are range-checked, and unset dimensions
+    /// keep the browser's.
     #[test]
-    fn test_build_print_options_portrait() {
-        let options = build_print_options(false, false).unwrap();
-        assert_eq!(options.landscape, Some(false));
-        assert_eq!(options.print_background, Some(false));
+    fn test_viewport_validation() {
+        assert_eq!(Viewport::new([None, None], None, None).unwrap(), None);
+        assert_eq!(
+            Viewport::new([Some(390), None], Some(3.0), Some(true)).unwrap(),
+            Some(Viewport {
+                width: 390,
+                height: 0,
+                device_scale_factor: 3.0,
+                mobile: true,
+            })
+        );
+        for (size, factor) in [
+            ([Some(0), None], None),
+            ([None, Some(VIEWPORT_RANGE.end() + 1)], None),
+            ([None, None], Some(0.0)),
+            ([None, None], Some(MAX_DEVICE_SCALE_FACTOR + 1.0)),
+        ] {
+            assert!(matches!(
+                Viewport::new(size, factor, None),
+                Err(PdfServiceError::InvalidRequest(_))
+            ));
+        }
+
+        assert_eq!(validate_user_agent(Some("  ")).unwrap(), None);
+        assert_eq!(
+            validate_user_agent(Some(" ReportBot/1.0 "))
+                .unwrap()
+                .as_deref(),
+            Some("ReportBot/1.0")
+        );
+        assert!(validate_user_agent(Some("a\r\nX-Injected: 1")).is_err());
+        assert!(validate_user_agent(Some(&"a".repeat(MAX_USER_AGENT_LEN + 1))).is_err());
     }
 
+    /// Verifies `inject_css` and `inject_js` are validated, and scripts
+    /// are refused when the request disables JavaScript.
     #[test]
-    fn test_build_print_options_zero_margins() {
-        let options = build_print_options(false, true).unwrap();
-        assert_eq!(options.margin_top, Some(0.0));
-        assert_eq!(options.margin_bottom, Some(0.0));
-        assert_eq!(options.margin_left, Some(0.0));
-        assert_eq!(options.margin_right, Some(0.0));
+    fn test_injection_validation() {
+        let mut request = PdfFromUrlRequest {
+            url: "https://example.com".to_string(),
+            inject_css: Some("#cookie-banner { display: none }".to_string()),
+            inject_js: Some("document.querySelector('details').open = true".to_string()),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert_eq!(
+            options.inject_css.as_deref(),
+            Some("#cookie-banner { display: none }")
+        );
+        assert!(options.inject_js.is_some());
+
+        request.javascript_enabled = Some(false);
+        assert!(matches!(
+            RenderOptions::try_from(&request),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+        request.inject_js = None;
+        assert!(RenderOptions::try_from(&request).is_ok());
+
+        request.inject_css = Some(" ".to_string());
+        assert!(RenderOptions::try_from(&request).is_err());
+        request.inject_css = Some("x".repeat(MAX_INJECT_LEN + 1));
+        assert!(RenderOptions::try_from(&request).is_err());
     }
 
+    /// Verifies a watermark is validated and kept as a stylesheet that
+    /// does not need JavaScript.
     #[test]
-    fn test_build_print_options_no_header_footer() {
-        let options = build_print_options(false, true).unwrap();
-        assert_eq!(options.display_header_footer, Some(false));
+    fn test_watermark_options() {
+        let mut request = PdfFromHtmlRequest {
+            html: "<h1>Contract</h1>".to_string(),
+            javascript_enabled: Some(false),
+            watermark: Some(Watermark::text("DRAFT")),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert!(options.watermark.unwrap().contains("content: \"DRAFT\";"));
+
+        request.watermark = Some(Watermark::default());
+        assert!(matches!(
+            RenderOptions::try_from(&request),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
     }
 
-    // -------------------------------------------------------------------------
-    // Constants Tests
-    // -------------------------------------------------------------------------
+    /// Verifies the pool's post-processors are copied into each render and
+    /// run in order, with failures naming the processor.
+    #[test]
+    fn test_post_processors() {
+        #[derive(Debug)]
+        struct Append(&'static str);
 
+        impl PdfPostProcessor for Append {
+            fn name(&self) -> &str {
+                self.0
+            }
+
+            fn process(&self, mut pdf: Vec<u8>) -> Result<Vec<u8>, crate::PostProcessError> {
+                if self.0 == "broken" {
+                    return Err("out of ink".into());
+                }
+                pdf.extend_from_slice(self.0.as_bytes());
+                Ok(pdf)
+            }
+        }
+
+        let request = PdfFromHtmlRequest {
+            html: "<h1>Invoice</h1>".to_string(),
+            ..Default::default()
+        };
+        let options = RenderOptions::try_from(&request).unwrap();
+        assert!(!Rendered::needs_post_processing(&options));
+
+        let config = crate::BrowserPoolConfigBuilder::new()
+            .post_processor(Append("-a"))
+            .post_processor(Append("-b"))
+            .build()
+            .unwrap();
+        let options = options.with_pool_policy(&config);
+        assert!(Rendered::needs_post_processing(&options));
+        assert_eq!(
+            options.summary()["post_processors"],
+            serde_json::json!(["-a", "-b"])
+        );
+        assert_eq!(
+            run_post_processors(b"%PDF".to_vec(), &options.post_processors).unwrap(),
+            b"%PDF-a-b"
+        );
+
+        let broken: Vec<Arc<dyn PdfPostProcessor>> = vec![Arc::new(Append("broken"))];
+        match run_post_processors(b"%PDF".to_vec(), &broken) {
+            Err(PdfServiceError::PdfGenerationFailed(message)) => {
+                assert_eq!(message, "post-processor broken failed: out of ink");
+            }
+            other => panic!("expected a generation failure, got {:?}", other),
+        }
+    }
+
+    /// Verifies `block_urls` entries are validated and bare domains also
+    /// cover subdomains, with pool-wide patterns appended.
     #[test]
-    fn test_default_timeout_reasonable() {
-        // Timeout should be at least 30 seconds for complex pages
-        assert!(DEFAULT_TIMEOUT_SECS >= 30);
-        // But not more than 5 minutes (would be too long)
-        assert!(DEFAULT_TIMEOUT_SECS <= 300);
+    fn test_block_urls_patterns() {
+        let request = PdfFromUrlRequest {
+            url: "https://example.com".to_string(),
+            block_urls: Some(vec![
+                "tracker.example".to_string(),
+                "*://cdn.example/ads/*".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let config = crate::BrowserPoolConfigBuilder::new()
+            .block_urls(["tracker.example", "analytics.example"])
+            .build()
+            .unwrap();
+        let options = RenderOptions::try_from(&request)
+            .unwrap()
+            .with_pool_policy(&config);
+        assert_eq!(
+            options.blocked_urls,
+            vec![
+                "*://tracker.example/*",
+                "*://*.tracker.example/*",
+                "*://cdn.example/ads/*",
+                "*://analytics.example/*",
+                "*://*.analytics.example/*",
+            ]
+        );
+
+        let invalid = PdfFromUrlRequest {
+            url: "https://example.com".to_string(),
+            block_urls: Some(vec!["  ".to_string()]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            RenderOptions::try_from(&invalid),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
     }
 
+    /// Verifies the redirect guard counts hops and enforces same-origin
+    /// redirects, remembering the first violation.
     #[test]
-    fn test_default_wait_reasonable() {
-        // Wait should be at least 1 second for any JS
-        assert!(DEFAULT_WAIT_SECS >= 1);
-        // But not more than 30 seconds by default
-        assert!(DEFAULT_WAIT_SECS <= 30);
+    fn test_redirect_guard() {
+        let guard = RedirectGuard::new(2, true, None);
+        assert!(guard.allow("https://a.example/", false));
+        assert!(guard.allow("https://b.example/", true));
+        assert!(guard.allow("https://c.example/", true));
+        assert!(guard.violation().is_none());
+        assert!(!guard.allow("https://d.example/", true));
+        assert!(
+            guard
+                .violation()
+                .unwrap()
+                .to_string()
+                .contains("2 redirects")
+        );
+
+        let guard = RedirectGuard::new(5, false, None);
+        assert!(guard.allow("https://a.example/start", false));
+        assert!(guard.allow("https://a.example/next", true));
+        assert!(!guard.allow("https://evil.example/", true));
+        assert!(
+            guard
+                .violation()
+                .unwrap()
+                .to_string()
+                .contains("cross-origin")
+        );
+
+        // Default pool config leaves redirects to Chrome
+        let options = RenderOptions::try_from(&PdfFromUrlRequest::default())
+            .unwrap()
+            .with_pool_policy(&crate::BrowserPoolConfig::default());
+        assert!(options.redirects.is_none());
+        assert!(!uses_interception(&options));
     }
 
+    /// Verifies the URL policy is checked before checkout, and on every
+    /// document request, including redirects and iframes.
     #[test]
-    fn test_poll_interval_reasonable() {
-        // Poll interval should be at least 100ms (not too aggressive)
-        assert!(JS_POLL_INTERVAL_MS >= 100);
-        // But not more than 1 second (responsive enough)
-        assert!(JS_POLL_INTERVAL_MS <= 1000);
+    fn test_url_policy() {
+        use crate::factory::mock::MockBrowserFactory;
+
+        let config = crate::BrowserPoolConfigBuilder::new()
+            .url_policy(UrlPolicy::public())
+            .build()
+            .unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = Mutex::new(
+            BrowserPool::builder()
+                .config(config.clone())
+                .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+                .enable_keep_alive(false)
+                .build()
+                .unwrap(),
+        );
+        assert!(check_url_policy(&pool, "https://example.com/").is_ok());
+        assert!(matches!(
+            check_url_policy(&pool, "http://169.254.169.254/latest/meta-data/"),
+            Err(PdfServiceError::InvalidUrl(_))
+        ));
+
+        let options = RenderOptions::try_from(&PdfFromUrlRequest::default())
+            .unwrap()
+            .with_pool_policy(&config);
+        assert!(uses_interception(&options));
+        let guard = options.redirects.unwrap();
+        assert!(guard.allow("https://example.com/", false));
+        assert!(!guard.allow_frame("http://10.0.0.1/"));
+        assert!(guard.violation().is_none());
+        assert!(!guard.allow("http://127.0.0.1/admin", true));
+        assert!(matches!(
+            guard.violation(),
+            Some(PdfServiceError::RedirectRejected(_))
+        ));
+
+        let guard = RedirectGuard::new(20, true, Some(UrlPolicy::public()));
+        assert!(!guard.allow("http://localhost/", false));
+        assert!(matches!(
+            guard.violation(),
+            Some(PdfServiceError::InvalidUrl(_))
+        ));
+    }
+
+    /// Verifies options that need the whole PDF cannot be streamed, and
+    /// are rejected before a browser is checked out.
+    #[test]
+    fn test_check_streamable() {
+        let request = PdfFromUrlRequest {
+            url: "https://example.com".to_string(),
+            stream: Some(true),
+            ..Default::default()
+        };
+        assert!(check_streamable(&RenderOptions::try_from(&request).unwrap()).is_ok());
+
+        let request = PdfFromUrlRequest {
+            linearize: Some(true),
+            ..request
+        };
+        let error = check_streamable(&RenderOptions::try_from(&request).unwrap()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid request: stream cannot be combined with linearize"
+        );
+
+        use crate::factory::mock::MockBrowserFactory;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = BrowserPool::builder()
+            .factory(Box::new(MockBrowserFactory::always_fails("unused")))
+            .enable_keep_alive(false)
+            .build()
+            .unwrap();
+
+        let request = PdfFromHtmlRequest {
+            html: "<h1>Report</h1>".to_string(),
+            thumbnail: Some(true),
+            stream: Some(true),
+            ..Default::default()
+        };
+        assert!(matches!(
+            stream_pdf_from_html(&Mutex::new(pool), &request),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+    }
+
+    /// Verifies certificate failures get an actionable message.
+    #[test]
+    fn test_navigation_failed_certificate_hint() {
+        let error = navigation_failed("net::ERR_CERT_AUTHORITY_INVALID".to_string());
+        assert!(error.to_string().contains("CHROME_CA_BUNDLE"));
+
+        let error = navigation_failed("net::ERR_NAME_NOT_RESOLVED".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Navigation failed: net::ERR_NAME_NOT_RESOLVED"
+        );
     }
 }
@@ -0,0 +1,136 @@
+//! PDF/A output for archiving.
+//!
+//! Chrome writes ordinary PDFs: transparency groups, device-dependent
+//! colors, and no PDF/A identification. Archives and records systems that
+//! require PDF/A get a copy rewritten by
+//! [Ghostscript](https://ghostscript.com)'s `pdfwrite` device in PDF/A-2b
+//! mode, which embeds every font, converts colors to RGB, flattens
+//! transparency, and adds the PDF/A metadata.
+//!
+//! Ghostscript must be installed on the server. It is looked up on `PATH`
+//! as `gs`, or at the path in the [`GHOSTSCRIPT_PATH_ENV`] environment
+//! variable. Temporary files go through the same private directory helper
+//! as [`linearize`](super::linearize).
+
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::process::Command;
+use std::time::Instant;
+
+use crate::service::linearize::TempDir;
+use crate::service::types::PdfServiceError;
+
+/// Environment variable overriding the Ghostscript binary used for
+/// `pdf_a`.
+pub const GHOSTSCRIPT_PATH_ENV: &str = "GHOSTSCRIPT_PATH";
+
+/// Ghostscript binary used when [`GHOSTSCRIPT_PATH_ENV`] is not set.
+const DEFAULT_GHOSTSCRIPT: &str = "gs";
+
+/// `pdfwrite` settings for PDF/A-2b.
+///
+/// `PDFACompatibilityPolicy=1` drops features PDF/A forbids instead of
+/// aborting, so a page with, say, an embedded file still renders.
+const PDF_A_ARGS: &[&str] = &[
+    "-dPDFA=2",
+    "-dPDFACompatibilityPolicy=1",
+    "-sColorConversionStrategy=RGB",
+    "-sProcessColorModel=DeviceRGB",
+    "-sDEVICE=pdfwrite",
+    "-dBATCH",
+    "-dNOPAUSE",
+    "-dQUIET",
+    "-dSAFER",
+];
+
+/// Rewrite a PDF toward PDF/A-2b.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::PdfGenerationFailed`] if Ghostscript is not
+/// installed or rejects the document.
+pub(crate) fn convert_to_pdf_a(pdf: &[u8]) -> Result<Vec<u8>, PdfServiceError> {
+    let start = Instant::now();
+
+    let converted = run_ghostscript(&ghostscript_binary(), pdf).map_err(|e| {
+        log::error!("❌ Failed to convert PDF to PDF/A: {}", e);
+        PdfServiceError::PdfGenerationFailed(format!("failed to convert PDF to PDF/A: {}", e))
+    })?;
+
+    log::debug!(
+        "PDF converted to PDF/A in {:?} ({} -> {} bytes)",
+        start.elapsed(),
+        pdf.len(),
+        converted.len()
+    );
+
+    Ok(converted)
+}
+
+/// The Ghostscript binary: [`GHOSTSCRIPT_PATH_ENV`] if set, else `gs` on
+/// `PATH`.
+fn ghostscript_binary() -> OsString {
+    std::env::var_os(GHOSTSCRIPT_PATH_ENV).unwrap_or_else(|| OsString::from(DEFAULT_GHOSTSCRIPT))
+}
+
+/// Run Ghostscript's PDF/A conversion on `pdf` in a private temporary
+/// directory.
+fn run_ghostscript(gs: &OsStr, pdf: &[u8]) -> Result<Vec<u8>, String> {
+    let dir = TempDir::create()?;
+    let input = dir.path().join("input.pdf");
+    let output = dir.path().join("pdfa.pdf");
+
+    fs::write(&input, pdf).map_err(|e| format!("cannot write temporary file: {}", e))?;
+
+    let mut output_arg = OsString::from("-sOutputFile=");
+    output_arg.push(&output);
+    let result = Command::new(gs)
+        .args(PDF_A_ARGS)
+        .arg(&output_arg)
+        .arg(&input)
+        .output()
+        .map_err(|e| {
+            format!(
+                "cannot run {} ({}); install Ghostscript or set {}",
+                gs.to_string_lossy(),
+                e,
+                GHOSTSCRIPT_PATH_ENV
+            )
+        })?;
+
+    if !result.status.success() {
+        return Err(format!(
+            "Ghostscript exited with {}: {}",
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+
+    fs::read(&output).map_err(|e| format!("cannot read Ghostscript output: {}", e))
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies a missing Ghostscript is reported with a hint.
+    #[test]
+    fn test_missing_ghostscript() {
+        let error = run_ghostscript(OsStr::new("/nonexistent/gs"), b"%PDF-1.4\n").unwrap_err();
+
+        assert!(error.contains("/nonexistent/gs"));
+        assert!(error.contains(GHOSTSCRIPT_PATH_ENV));
+    }
+
+    /// Verifies a failing Ghostscript run is reported with its exit status.
+    #[cfg(unix)]
+    #[test]
+    fn test_ghostscript_failure() {
+        let error = run_ghostscript(OsStr::new("false"), b"%PDF-1.4\n").unwrap_err();
+        assert!(error.starts_with("Ghostscript exited with"));
+    }
+}
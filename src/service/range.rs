@@ -0,0 +1,223 @@
+//! HTTP range requests for kept job results.
+//!
+//! A PDF viewer that finds `Accept-Ranges: bytes` on a download can fetch a
+//! large document piece by piece, and with a
+//! [linearized](crate::service::PdfFromUrlRequest::linearize) PDF show the
+//! first page before the rest has arrived. The `GET /jobs/{id}/result`
+//! routes answer a `Range` header with [`ByteRange::from_header`]:
+//!
+//! | `Range` | Response |
+//! |---------|----------|
+//! | none, malformed, or several ranges | 200 with the whole body |
+//! | `bytes=0-1023`, `bytes=1024-`, `bytes=-512` | 206 with `Content-Range: bytes 0-1023/<size>` |
+//! | starting past the end | 416 with `Content-Range: bytes */<size>` |
+
+use bytes::Bytes;
+
+/// The part of a body a `Range` request header asks for.
+///
+/// Only a single `bytes` range is honoured. Requests for several ranges
+/// get the whole body, which HTTP allows a server to send instead.
+///
+/// # Example
+///
+/// ```rust
+/// use html2pdf_api::service::ByteRange;
+///
+/// let range = ByteRange::from_header(Some("bytes=0-99"), 1000);
+/// assert_eq!(range, ByteRange::Partial { start: 0, end: 99, total: 1000 });
+/// assert_eq!(range.content_range().as_deref(), Some("bytes 0-99/1000"));
+///
+/// assert_eq!(ByteRange::from_header(None, 1000), ByteRange::Full);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// Send the whole body with 200 OK.
+    Full,
+
+    /// Send bytes `start..=end` of `total` with 206 Partial Content.
+    Partial {
+        /// First byte sent.
+        start: usize,
+        /// Last byte sent (inclusive).
+        end: usize,
+        /// Size of the whole body.
+        total: usize,
+    },
+
+    /// The range lies past the end; respond 416 Range Not Satisfiable.
+    Unsatisfiable {
+        /// Size of the whole body.
+        total: usize,
+    },
+}
+
+impl ByteRange {
+    /// Resolve a `Range` header against a body of `total` bytes.
+    ///
+    /// Ranges ending past the body are shortened to it. A header that
+    /// can't be parsed is ignored, giving [`ByteRange::Full`].
+    pub fn from_header(range: Option<&str>, total: usize) -> Self {
+        let Some(spec) = range.and_then(|r| r.trim().strip_prefix("bytes=")) else {
+            return Self::Full;
+        };
+        if spec.contains(',') {
+            return Self::Full;
+        }
+        let Some((first, last)) = spec.trim().split_once('-') else {
+            return Self::Full;
+        };
+
+        match (first.parse::<usize>(), last.parse::<usize>()) {
+            // bytes=-500: the last 500 bytes
+            (Err(_), Ok(suffix)) if first.is_empty() => {
+                if suffix == 0 || total == 0 {
+                    return Self::Unsatisfiable { total };
+                }
+                Self::Partial {
+                    start: total.saturating_sub(suffix),
+                    end: total - 1,
+                    total,
+                }
+            }
+            // bytes=500-
+            (Ok(start), Err(_)) if last.is_empty() => Self::from_bounds(start, usize::MAX, total),
+            (Ok(start), Ok(end)) if start <= end => Self::from_bounds(start, end, total),
+            _ => Self::Full,
+        }
+    }
+
+    fn from_bounds(start: usize, end: usize, total: usize) -> Self {
+        if start >= total {
+            return Self::Unsatisfiable { total };
+        }
+        Self::Partial {
+            start,
+            end: end.min(total - 1),
+            total,
+        }
+    }
+
+    /// Value of the `Content-Range` response header, if one is sent.
+    pub fn content_range(&self) -> Option<String> {
+        match self {
+            Self::Full => None,
+            Self::Partial { start, end, total } => {
+                Some(format!("bytes {}-{}/{}", start, end, total))
+            }
+            Self::Unsatisfiable { total } => Some(format!("bytes */{}", total)),
+        }
+    }
+
+    /// The bytes of `body` to send. Shares `body`'s buffer.
+    pub fn slice(&self, body: &Bytes) -> Bytes {
+        match *self {
+            Self::Full => body.clone(),
+            Self::Partial { start, end, .. } => body.slice(start..=end),
+            Self::Unsatisfiable { .. } => Bytes::new(),
+        }
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies the three single-range forms, including clamping to the end.
+    #[test]
+    fn test_single_ranges() {
+        assert_eq!(
+            ByteRange::from_header(Some("bytes=100-199"), 1000),
+            ByteRange::Partial {
+                start: 100,
+                end: 199,
+                total: 1000
+            }
+        );
+        assert_eq!(
+            ByteRange::from_header(Some("bytes=900-"), 1000),
+            ByteRange::Partial {
+                start: 900,
+                end: 999,
+                total: 1000
+            }
+        );
+        assert_eq!(
+            ByteRange::from_header(Some("bytes=-100"), 1000),
+            ByteRange::Partial {
+                start: 900,
+                end: 999,
+                total: 1000
+            }
+        );
+        assert_eq!(
+            ByteRange::from_header(Some("bytes=-5000"), 1000),
+            ByteRange::Partial {
+                start: 0,
+                end: 999,
+                total: 1000
+            }
+        );
+        assert_eq!(
+            ByteRange::from_header(Some("bytes=500-5000"), 1000),
+            ByteRange::Partial {
+                start: 500,
+                end: 999,
+                total: 1000
+            }
+        );
+    }
+
+    /// Verifies ranges past the end are unsatisfiable.
+    #[test]
+    fn test_unsatisfiable_ranges() {
+        for header in ["bytes=1000-", "bytes=1000-2000", "bytes=-0"] {
+            let range = ByteRange::from_header(Some(header), 1000);
+            assert_eq!(
+                range,
+                ByteRange::Unsatisfiable { total: 1000 },
+                "{}",
+                header
+            );
+            assert_eq!(range.content_range().as_deref(), Some("bytes */1000"));
+        }
+        assert_eq!(
+            ByteRange::from_header(Some("bytes=-10"), 0),
+            ByteRange::Unsatisfiable { total: 0 }
+        );
+    }
+
+    /// Verifies unusable headers fall back to the whole body.
+    #[test]
+    fn test_ignored_headers() {
+        for header in [
+            "",
+            "bytes=",
+            "bytes=abc",
+            "bytes=10-5",
+            "bytes=0-1,5-9",
+            "items=0-10",
+            "bytes=-",
+        ] {
+            assert_eq!(
+                ByteRange::from_header(Some(header), 1000),
+                ByteRange::Full,
+                "{}",
+                header
+            );
+        }
+    }
+
+    /// Verifies slicing returns exactly the requested bytes.
+    #[test]
+    fn test_slice() {
+        let body = Bytes::from_static(b"%PDF-1.4 hello");
+        let range = ByteRange::from_header(Some("bytes=0-4"), body.len());
+        assert_eq!(&range.slice(&body)[..], b"%PDF-");
+        assert_eq!(ByteRange::Full.slice(&body), body);
+    }
+}
@@ -0,0 +1,160 @@
+//! Readiness callback exposed to pages.
+//!
+//! Besides setting a flag that the service polls (see
+//! [`DEFAULT_WAIT_EXPRESSION`](crate::service::DEFAULT_WAIT_EXPRESSION)),
+//! a page can call `window.html2pdfReady()` once it has rendered. The call
+//! reaches the service through a CDP binding and ends the wait at once,
+//! instead of at the next poll up to 200ms later:
+//!
+//! ```js
+//! await loadReport();
+//! if (window.html2pdfReady) window.html2pdfReady();
+//! ```
+//!
+//! The function is installed on every render with JavaScript enabled,
+//! before the page loads, so it can also be called during load. The polled
+//! expression keeps working alongside it; whichever signals first wins.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, OnceLock, PoisonError, Weak};
+use std::time::Duration;
+
+use headless_chrome::Tab;
+use tokio::sync::Notify;
+
+/// Name of the function pages call to signal they are ready to print.
+pub const READY_FUNCTION: &str = "html2pdfReady";
+
+/// Signals of tabs that already have [`READY_FUNCTION`] installed, by
+/// target ID.
+///
+/// Chrome keeps a binding for the life of the tab, so a reused tab is
+/// installed once. The tab's binding holds the only strong reference;
+/// entries for closed tabs are pruned as new ones are added.
+static SIGNALS: OnceLock<Mutex<HashMap<String, Weak<ReadySignal>>>> = OnceLock::new();
+
+/// Whether the page in one tab has called [`READY_FUNCTION`].
+#[derive(Debug, Default)]
+pub(crate) struct ReadySignal {
+    fired: Mutex<bool>,
+    /// Wakes blocking renders waiting in [`ReadySignal::wait_timeout`].
+    condvar: Condvar,
+    /// Wakes async renders waiting in [`ReadySignal::fired`].
+    notify: Notify,
+}
+
+impl ReadySignal {
+    /// The tab's signal, reset for a new render.
+    ///
+    /// Installs [`READY_FUNCTION`] the first time a tab is seen. Call it
+    /// before navigating, so the page can signal during load.
+    pub(crate) fn for_tab(tab: &Tab) -> Result<Arc<Self>, String> {
+        let mut signals = SIGNALS
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let target_id = tab.get_target_id().clone();
+        if let Some(signal) = signals.get(&target_id).and_then(Weak::upgrade) {
+            signal.reset();
+            return Ok(signal);
+        }
+
+        let signal = Arc::new(Self::default());
+        tab.enable_runtime().map_err(|e| e.to_string())?;
+        let fire = Arc::clone(&signal);
+        tab.expose_function(READY_FUNCTION, Arc::new(move |_| fire.fire()))
+            .map_err(|e| e.to_string())?;
+
+        signals.retain(|_, signal| signal.strong_count() > 0);
+        signals.insert(target_id, Arc::downgrade(&signal));
+        Ok(signal)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, bool> {
+        self.fired.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn reset(&self) {
+        *self.lock() = false;
+    }
+
+    /// Record the page's call and wake waiters.
+    fn fire(&self) {
+        *self.lock() = true;
+        self.condvar.notify_all();
+        self.notify.notify_waiters();
+    }
+
+    /// Whether the page has called [`READY_FUNCTION`] since the reset.
+    pub(crate) fn is_fired(&self) -> bool {
+        *self.lock()
+    }
+
+    /// Block for up to `timeout` until the page calls; returns whether it
+    /// did.
+    pub(crate) fn wait_timeout(&self, timeout: Duration) -> bool {
+        let fired = self.lock();
+        let (fired, _) = self
+            .condvar
+            .wait_timeout_while(fired, timeout, |fired| !*fired)
+            .unwrap_or_else(PoisonError::into_inner);
+        *fired
+    }
+
+    /// Wait until the page calls.
+    pub(crate) async fn fired(&self) {
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            // Register before checking, so a call in between is not missed
+            notified.as_mut().enable();
+            if self.is_fired() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies blocking waiters see a call and time out without one.
+    #[test]
+    fn test_wait_timeout() {
+        let signal = Arc::new(ReadySignal::default());
+        assert!(!signal.wait_timeout(Duration::from_millis(10)));
+
+        let firing = Arc::clone(&signal);
+        let caller = std::thread::spawn(move || firing.fire());
+        assert!(signal.wait_timeout(Duration::from_secs(5)));
+        caller.join().unwrap();
+
+        signal.reset();
+        assert!(!signal.is_fired());
+    }
+
+    /// Verifies async waiters wake on a call, including ones arriving late.
+    #[tokio::test]
+    async fn test_fired_wakes_waiters() {
+        let signal = Arc::new(ReadySignal::default());
+        let waiter = tokio::spawn({
+            let signal = Arc::clone(&signal);
+            async move { signal.fired().await }
+        });
+        tokio::task::yield_now().await;
+
+        signal.fire();
+        tokio::time::timeout(Duration::from_secs(5), waiter)
+            .await
+            .expect("Waiter should wake")
+            .unwrap();
+        signal.fired().await;
+    }
+}
@@ -0,0 +1,85 @@
+//! Connection handling shared by the Redis backends.
+//!
+//! [`RedisPdfCache`](crate::service::RedisPdfCache) and
+//! [`RedisJobStore`](crate::service::RedisJobStore) each keep one
+//! synchronous connection, opened on first use and reopened after an
+//! error. Every call is bounded by [`IO_TIMEOUT`], so an unreachable server
+//! slows requests down by at most that much instead of hanging them.
+
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::error::{BrowserPoolError, Result};
+
+/// How long connecting, reading, or writing may take before the call
+/// gives up.
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A lazily opened connection to one Redis server.
+pub(crate) struct RedisConnection {
+    client: redis::Client,
+
+    /// What the connection is for, in log messages.
+    purpose: &'static str,
+
+    /// Connection reused between calls; dropped after an error.
+    connection: Mutex<Option<redis::Connection>>,
+}
+
+impl RedisConnection {
+    /// Prepare a connection to the server at `url`, without connecting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::Configuration`] if `url` is invalid.
+    pub(crate) fn open(url: &str, purpose: &'static str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| BrowserPoolError::Configuration(format!("invalid Redis URL: {}", e)))?;
+        Ok(Self {
+            client,
+            purpose,
+            connection: Mutex::new(None),
+        })
+    }
+
+    /// Run `f` on the connection, connecting first if needed.
+    ///
+    /// Errors are logged and the connection dropped, so the next call
+    /// reconnects.
+    pub(crate) fn run<T>(
+        &self,
+        f: impl FnOnce(&mut redis::Connection) -> redis::RedisResult<T>,
+    ) -> Option<T> {
+        let mut connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if connection.is_none() {
+            match self.connect() {
+                Ok(conn) => *connection = Some(conn),
+                Err(e) => {
+                    log::warn!("⚠️ Redis {} unavailable: {}", self.purpose, e);
+                    return None;
+                }
+            }
+        }
+
+        let result = f(connection.as_mut()?);
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                log::warn!("⚠️ Redis {} error: {}", self.purpose, e);
+                *connection = None;
+                None
+            }
+        }
+    }
+
+    fn connect(&self) -> redis::RedisResult<redis::Connection> {
+        let conn = self.client.get_connection_with_timeout(IO_TIMEOUT)?;
+        conn.set_read_timeout(Some(IO_TIMEOUT))?;
+        conn.set_write_timeout(Some(IO_TIMEOUT))?;
+        Ok(conn)
+    }
+}
@@ -0,0 +1,756 @@
+//! Recurring renders on a cron schedule.
+//!
+//! [`spawn_scheduler`] runs each [`ScheduledRender`] whenever its cron
+//! expression matches, so nightly reports need no external cron wrapper
+//! around the HTTP API. Every run is a job named after the schedule:
+//!
+//! | Where | What |
+//! |-------|------|
+//! | `GET /jobs/{name}` / `GET /jobs/{name}/result` | Latest run, kept for [`job_retention`](crate::BrowserPoolConfig::job_retention) |
+//! | `output_dir` (optional) | Every successful run, as `{name}-{YYYYMMDDTHHMMZ}.pdf` |
+//! | `callback_url` (optional) | Every run's result, POSTed as for `POST /jobs` |
+//!
+//! A run still going when the next one is due makes that next run skip,
+//! with a warning.
+//!
+//! # Cron Expressions
+//!
+//! Five fields, evaluated in UTC:
+//!
+//! ```text
+//! ┌───────── minute (0-59)
+//! │ ┌─────── hour (0-23)
+//! │ │ ┌───── day of month (1-31)
+//! │ │ │ ┌─── month (1-12 or JAN-DEC)
+//! │ │ │ │ ┌─ day of week (0-7 or SUN-SAT; 0 and 7 are Sunday)
+//! │ │ │ │ │
+//! 0 2 * * *      every day at 02:00
+//! */15 * * * *   every 15 minutes
+//! 0 6 * * MON-FRI
+//! ```
+//!
+//! Fields accept `*`, numbers, ranges (`1-5`), steps (`*/15`, `0-30/10`),
+//! and lists (`1,15`). As in classic cron, when both day fields are
+//! restricted a day matching either one runs. A day field starting with
+//! `*` (such as `*/2`) makes both apply instead, so `0 0 */2 * MON` runs
+//! on Mondays that fall on an odd day. `@hourly`, `@daily`,
+//! `@weekly`, `@monthly`, and `@yearly` are accepted as shorthands.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use html2pdf_api::service::{PdfFromUrlRequest, ScheduledRender, spawn_scheduler};
+//!
+//! let nightly = ScheduledRender {
+//!     name: "nightly-report".to_string(),
+//!     cron: "0 2 * * *".parse()?,
+//!     api_key: None,
+//!     output_dir: Some("reports".into()),
+//!     job: PdfFromUrlRequest {
+//!         url: "https://example.com/report".to_string(),
+//!         ..Default::default()
+//!     }
+//!     .into(),
+//! };
+//!
+//! let scheduler = spawn_scheduler(&pool, vec![nightly])?;
+//! // ...
+//! scheduler.abort(); // stops every schedule
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use tokio::task::{JoinHandle, JoinSet};
+
+use crate::pool::BrowserPool;
+use crate::service::jobs::{start_job, validate_callback_url};
+use crate::service::types::{JobRequest, PdfResponse, PdfServiceError};
+use crate::usage::civil_from_days;
+
+const MINUTES_PER_DAY: u64 = 24 * 60;
+
+/// Days searched for the next match before a schedule is taken to never
+/// fire, e.g. `0 0 30 2 *`. Eight years always includes a February 29th.
+const MAX_DAYS_SEARCHED: u64 = 8 * 366;
+
+// ============================================================================
+// Cron Expressions
+// ============================================================================
+
+/// A parsed five-field cron expression, evaluated in UTC.
+///
+/// See the [module documentation](self) for the syntax.
+///
+/// # Examples
+///
+/// ```rust
+/// use html2pdf_api::service::CronSchedule;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let daily: CronSchedule = "30 2 * * *".parse().unwrap();
+///
+/// // 2024-01-01T00:00:00Z -> 2024-01-01T02:30:00Z
+/// let start = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+/// assert_eq!(
+///     daily.next_after(start),
+///     Some(start + Duration::from_secs(2 * 3600 + 30 * 60))
+/// );
+///
+/// assert!("61 * * * *".parse::<CronSchedule>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Day of month started with `*`, so days must match both fields
+    /// rather than either.
+    any_day: bool,
+    /// Day of week started with `*`, likewise.
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    /// The first matching minute strictly after `time`.
+    ///
+    /// Returns `None` if the expression can never match (such as
+    /// February 30th) or `time` is before the Unix epoch.
+    pub fn next_after(&self, time: SystemTime) -> Option<SystemTime> {
+        let start = time.duration_since(UNIX_EPOCH).ok()?.as_secs() / 60 + 1;
+        let first_day = start / MINUTES_PER_DAY;
+
+        (first_day..first_day + MAX_DAYS_SEARCHED)
+            .filter(|&day| self.matches_day(day))
+            .find_map(|day| {
+                let from = if day == first_day {
+                    start % MINUTES_PER_DAY
+                } else {
+                    0
+                };
+                (from..MINUTES_PER_DAY)
+                    .find(|minute| has(self.hours, minute / 60) && has(self.minutes, minute % 60))
+                    .map(|minute| {
+                        UNIX_EPOCH + Duration::from_secs((day * MINUTES_PER_DAY + minute) * 60)
+                    })
+            })
+    }
+
+    /// Whether the schedule runs on a day since the Unix epoch.
+    fn matches_day(&self, day: u64) -> bool {
+        let (_, month, day_of_month) = civil_from_days(day);
+        // 1970-01-01 was a Thursday
+        let weekday = (day + 4) % 7;
+
+        if !has(self.months, month) {
+            return false;
+        }
+        let day_matches = has(self.days, day_of_month);
+        let weekday_matches = has(self.weekdays, weekday);
+        if self.any_day || self.any_weekday {
+            day_matches && weekday_matches
+        } else {
+            day_matches || weekday_matches
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    /// Parse a five-field expression or an `@daily`-style shorthand.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.trim();
+        let expanded = match expression.to_ascii_lowercase().as_str() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            _ => expression,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "invalid cron expression {:?}: expected 5 fields, got {}",
+                expression,
+                fields.len()
+            ));
+        };
+
+        let parse = |field, name, min, max, names| {
+            parse_field(field, min, max, names).map_err(|e| {
+                format!(
+                    "invalid cron expression {:?}: {} field {:?}: {}",
+                    expression, name, field, e
+                )
+            })
+        };
+
+        let mut weekdays = parse(weekday, "day of week", 0, 7, WEEKDAY_NAMES)?;
+        if has(weekdays, 7) {
+            weekdays |= 1;
+        }
+
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: parse(minute, "minute", 0, 59, &[])?,
+            hours: parse(hour, "hour", 0, 23, &[])?,
+            days: parse(day, "day of month", 1, 31, &[])?,
+            months: parse(month, "month", 1, 12, MONTH_NAMES)?,
+            weekdays,
+            // As in Vixie cron, a field starting with `*` (`*/2` too)
+            // leaves days unrestricted
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+}
+
+impl TryFrom<String> for CronSchedule {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+/// Month names, in order from 1.
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Day names, in order from 0 (Sunday).
+const WEEKDAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Parse one field into a bit set of the values it matches.
+///
+/// `names` are accepted in place of numbers, the first meaning `min`.
+fn parse_field(field: &str, min: u64, max: u64, names: &[&str]) -> Result<u64, String> {
+    let value = |s: &str| -> Result<u64, String> {
+        let n = match names.iter().position(|name| name.eq_ignore_ascii_case(s)) {
+            Some(index) => index as u64 + min,
+            None => s.parse().map_err(|_| format!("{:?} is not a number", s))?,
+        };
+        if (min..=max).contains(&n) {
+            Ok(n)
+        } else {
+            Err(format!("{} is outside {}-{}", n, min, max))
+        }
+    };
+
+    let mut bits = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u64>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(format!("step {:?} is not a positive number", step)),
+            },
+            None => (item, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else {
+            let start = value(range)?;
+            // `5/15` means from 5 to the end, stepping by 15
+            (start, if item.contains('/') { max } else { start })
+        };
+        if start > end {
+            return Err(format!("range {:?} is backwards", range));
+        }
+
+        bits |= (start..=end)
+            .step_by(step as usize)
+            .fold(0, |bits, n| bits | 1 << n);
+    }
+    Ok(bits)
+}
+
+/// Whether `bits` contains `n`.
+fn has(bits: u64, n: u64) -> bool {
+    bits & (1 << n) != 0
+}
+
+// ============================================================================
+// Scheduler
+// ============================================================================
+
+/// A render repeated on a cron schedule.
+///
+/// Deserializes from the schedule fields next to the fields of a
+/// [`PdfFromUrlRequest`](crate::service::PdfFromUrlRequest) or
+/// [`PdfFromHtmlRequest`](crate::service::PdfFromHtmlRequest):
+///
+/// ```yaml
+/// name: nightly-report
+/// cron: "0 2 * * *"
+/// output_dir: reports
+/// url: https://example.com/report
+/// landscape: true
+/// ```
+///
+/// | Field | Required | Description |
+/// |-------|----------|-------------|
+/// | `name` | **Yes** | Job ID of every run; unique among schedules |
+/// | `cron` | **Yes** | When to run (see [`CronSchedule`]) |
+/// | `api_key` | No | Key the runs are accounted to and their job ID is scoped to |
+/// | `output_dir` | No | Directory successful runs are also written to |
+/// | `callback_url` | No | URL every run's result is POSTed to, as for `POST /jobs` |
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledRender {
+    /// Name of the schedule, used as the job ID of every run.
+    pub name: String,
+
+    /// When the render runs.
+    pub cron: CronSchedule,
+
+    /// API key the runs are accounted to, as if sent in `X-API-Key`.
+    ///
+    /// Results are fetched from `GET /jobs/{name}/result` with the same
+    /// key.
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// Directory each successful run's PDF is written to, created if
+    /// missing.
+    #[serde(default)]
+    pub output_dir: Option<PathBuf>,
+
+    /// The render to run, and optionally the callback each run's result
+    /// is delivered to (see [`submit_job`](crate::service::submit_job)).
+    #[serde(flatten)]
+    pub job: JobRequest,
+}
+
+/// Start running `schedules` in the background.
+///
+/// Each schedule waits for its next match, then renders its job under
+/// its name as the job ID (see [`submit_job`](crate::service::submit_job)),
+/// writing a successful PDF to its `output_dir` as well. Aborting the
+/// returned handle stops every schedule; runs in progress finish on their
+/// own.
+///
+/// Must be called from within a Tokio runtime.
+///
+/// # Errors
+///
+/// - [`PdfServiceError::InvalidRequest`] if a name is empty, longer than
+///   128 characters, or used by two schedules
+/// - [`PdfServiceError::InvalidUrl`] if a `callback_url` is not an
+///   `http://` or `https://` URL, or the pool's
+///   [`url_policy`](crate::BrowserPoolConfig::url_policy) forbids it
+pub fn spawn_scheduler(
+    pool: &Arc<Mutex<BrowserPool>>,
+    schedules: Vec<ScheduledRender>,
+) -> Result<JoinHandle<()>, PdfServiceError> {
+    let mut names = HashSet::new();
+    for schedule in &schedules {
+        if schedule.name.trim().is_empty() || schedule.name.len() > 128 {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "schedule name {:?} must be 1-128 characters",
+                schedule.name
+            )));
+        }
+        if !names.insert(schedule.name.as_str()) {
+            return Err(PdfServiceError::InvalidRequest(format!(
+                "schedule name {:?} is used more than once",
+                schedule.name
+            )));
+        }
+        if let Some(url) = &schedule.job.callback_url {
+            validate_callback_url(pool, url)?;
+        }
+    }
+
+    let mut tasks = JoinSet::new();
+    for schedule in schedules {
+        log::info!("Scheduled '{}' at '{}' (UTC)", schedule.name, schedule.cron);
+        tasks.spawn(run_schedule(Arc::clone(pool), schedule));
+    }
+
+    // Dropping the set when this task is aborted aborts every schedule
+    Ok(tokio::spawn(async move {
+        while tasks.join_next().await.is_some() {}
+    }))
+}
+
+/// Run one schedule until it can no longer fire.
+async fn run_schedule(pool: Arc<Mutex<BrowserPool>>, schedule: ScheduledRender) {
+    let schedule = Arc::new(schedule);
+    let mut in_flight = None;
+    while let Some(next) = schedule.cron.next_after(SystemTime::now()) {
+        let wait = next
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO);
+        tokio::time::sleep(wait).await;
+        start_run(&pool, &schedule, next, &mut in_flight);
+    }
+    log::warn!(
+        "Schedule '{}' at '{}' never matches again, stopping it",
+        schedule.name,
+        schedule.cron
+    );
+}
+
+/// Spawn the run of `schedule` due at `due`, unless the run in
+/// `in_flight` is still going.
+///
+/// Returns whether the run was started.
+fn start_run(
+    pool: &Arc<Mutex<BrowserPool>>,
+    schedule: &Arc<ScheduledRender>,
+    due: SystemTime,
+    in_flight: &mut Option<JoinHandle<()>>,
+) -> bool {
+    if in_flight.as_ref().is_some_and(|run| !run.is_finished()) {
+        log::warn!(
+            "⚠️ Skipping scheduled run of '{}': the previous run is still in progress",
+            schedule.name
+        );
+        return false;
+    }
+
+    let pool = Arc::clone(pool);
+    let schedule = Arc::clone(schedule);
+    *in_flight = Some(tokio::spawn(async move {
+        run_once(&pool, &schedule, due).await;
+    }));
+    true
+}
+
+/// Render one run of `schedule`, due at `due`.
+async fn run_once(pool: &Arc<Mutex<BrowserPool>>, schedule: &ScheduledRender, due: SystemTime) {
+    let handle = match start_job(
+        pool,
+        schedule.api_key.as_deref(),
+        &schedule.name,
+        schedule.job.clone(),
+    ) {
+        Ok(handle) => handle,
+        Err(e) => {
+            log::warn!("Skipping scheduled run of '{}': {}", schedule.name, e);
+            return;
+        }
+    };
+
+    let Ok(Ok(response)) = handle.await else {
+        // Failures are logged and kept by the job itself
+        return;
+    };
+    if let Some(dir) = &schedule.output_dir {
+        let path = dir.join(output_filename(&schedule.name, due));
+        let written = tokio::task::spawn_blocking({
+            let path = path.clone();
+            let dir = dir.clone();
+            move || write_output(&dir, &path, &response)
+        })
+        .await;
+        match written {
+            Ok(Ok(())) => log::info!(
+                "Scheduled run of '{}' saved to {}",
+                schedule.name,
+                path.display()
+            ),
+            Ok(Err(e)) => log::error!("Failed to write {}: {}", path.display(), e),
+            Err(e) => log::error!("Failed to write {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Write a run's PDF, creating its directory if needed.
+fn write_output(dir: &Path, path: &Path, response: &PdfResponse) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(path, &response.data)
+}
+
+/// File name for the run of `name` due at `due`, e.g.
+/// `nightly-report-20240101T0200Z.pdf`.
+fn output_filename(name: &str, due: SystemTime) -> String {
+    let minutes = due.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / 60;
+    let (year, month, day) = civil_from_days(minutes / MINUTES_PER_DAY);
+    let minute_of_day = minutes % MINUTES_PER_DAY;
+    let safe_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!(
+        "{}-{:04}{:02}{:02}T{:02}{:02}Z.pdf",
+        safe_name,
+        year,
+        month,
+        day,
+        minute_of_day / 60,
+        minute_of_day % 60
+    )
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BrowserPoolConfigBuilder;
+    use crate::factory::mock::MockBrowserFactory;
+    use crate::service::{JobStatus, RenderJob, job_status};
+
+    /// 2024-01-01T00:00:00Z, a Monday.
+    const JAN_1_2024: u64 = 1_704_067_200;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn next(expression: &str, from: u64) -> Option<u64> {
+        let cron: CronSchedule = expression.parse().unwrap();
+        cron.next_after(at(from))
+            .map(|time| time.duration_since(UNIX_EPOCH).unwrap().as_secs())
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        // Strictly after, from the next whole minute
+        assert_eq!(next("* * * * *", JAN_1_2024), Some(JAN_1_2024 + 60));
+        assert_eq!(next("* * * * *", JAN_1_2024 + 59), Some(JAN_1_2024 + 60));
+
+        assert_eq!(
+            next("*/15 * * * *", JAN_1_2024 + 60),
+            Some(JAN_1_2024 + 900)
+        );
+        assert_eq!(
+            next("0 2 * * *", JAN_1_2024 + 3 * 3600),
+            Some(JAN_1_2024 + 26 * 3600)
+        );
+        assert_eq!(next("@daily", JAN_1_2024), Some(JAN_1_2024 + 86_400));
+
+        // Saturday 2024-01-06
+        assert_eq!(
+            next("0 0 * * SAT", JAN_1_2024),
+            Some(JAN_1_2024 + 5 * 86_400)
+        );
+        // Sunday as 7
+        assert_eq!(next("0 0 * * 7", JAN_1_2024), Some(JAN_1_2024 + 6 * 86_400));
+        // 2024-02-29
+        assert_eq!(
+            next("0 0 29 FEB *", JAN_1_2024),
+            Some(JAN_1_2024 + 59 * 86_400)
+        );
+        // Either day field matches: the 15th or a Wednesday (2024-01-03)
+        assert_eq!(
+            next("0 0 15 * WED", JAN_1_2024),
+            Some(JAN_1_2024 + 2 * 86_400)
+        );
+
+        // A field starting with `*` makes both fields apply: an odd day
+        // that is a Monday (2024-01-15), not an odd day or any Monday
+        assert_eq!(
+            next("0 0 */2 * MON", JAN_1_2024),
+            Some(JAN_1_2024 + 14 * 86_400)
+        );
+        // ... and a 15th on an even weekday, Thursday 2024-02-15
+        assert_eq!(
+            next("0 0 15 * */2", JAN_1_2024),
+            Some(JAN_1_2024 + 45 * 86_400)
+        );
+
+        assert_eq!(next("0 0 30 2 *", JAN_1_2024), None);
+    }
+
+    #[test]
+    fn test_cron_parse_errors() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "x * * * *",
+        ] {
+            assert!(
+                expression.parse::<CronSchedule>().is_err(),
+                "{:?} should be rejected",
+                expression
+            );
+        }
+        assert_eq!(
+            "0,30 9-17 * * mon-fri"
+                .parse::<CronSchedule>()
+                .unwrap()
+                .to_string(),
+            "0,30 9-17 * * mon-fri"
+        );
+    }
+
+    #[test]
+    fn test_output_filename() {
+        assert_eq!(
+            output_filename("nightly report", at(JAN_1_2024 + 2 * 3600 + 5 * 60)),
+            "nightly_report-20240101T0205Z.pdf"
+        );
+    }
+
+    #[test]
+    fn test_scheduled_render_deserialization() {
+        let schedule: ScheduledRender = serde_json::from_str(
+            r#"{"name": "nightly", "cron": "0 2 * * *", "url": "https://example.com", "landscape": true}"#,
+        )
+        .unwrap();
+        assert_eq!(schedule.name, "nightly");
+        assert!(matches!(schedule.job.job, RenderJob::Url(ref r) if r.landscape == Some(true)));
+        assert!(schedule.job.callback_url.is_none());
+        assert!(schedule.output_dir.is_none());
+
+        let result = serde_json::from_str::<ScheduledRender>(
+            r#"{"name": "bad", "cron": "every day", "html": "<p>x</p>"}"#,
+        );
+        assert!(result.is_err());
+    }
+
+    /// Verifies a run is kept under the schedule's name, and duplicate
+    /// names are rejected.
+    #[tokio::test]
+    async fn test_scheduled_run() {
+        let pool = Arc::new(Mutex::new(
+            BrowserPool::builder()
+                .config(BrowserPoolConfigBuilder::new().build().unwrap())
+                .factory(Box::new(MockBrowserFactory::always_fails("no chrome")))
+                .enable_keep_alive(false)
+                .build()
+                .unwrap(),
+        ));
+        let schedule: ScheduledRender = serde_json::from_str(
+            r#"{"name": "nightly", "cron": "0 2 * * *", "url": "https://example.com"}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            spawn_scheduler(&pool, vec![schedule.clone(), schedule.clone()]),
+            Err(PdfServiceError::InvalidRequest(_))
+        ));
+
+        run_once(&pool, &schedule, at(JAN_1_2024)).await;
+        let job = job_status(&pool, None, "nightly").unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+
+        let scheduler = spawn_scheduler(&pool, vec![schedule]).unwrap();
+        scheduler.abort();
+    }
+
+    /// Verifies a run is skipped while the previous one is in flight.
+    #[tokio::test]
+    async fn test_overlapping_run_skipped() {
+        let pool = Arc::new(Mutex::new(
+            BrowserPool::builder()
+                .config(BrowserPoolConfigBuilder::new().build().unwrap())
+                .factory(Box::new(MockBrowserFactory::always_fails("no chrome")))
+                .enable_keep_alive(false)
+                .build()
+                .unwrap(),
+        ));
+        let schedule: Arc<ScheduledRender> = Arc::new(
+            serde_json::from_str(
+                r#"{"name": "nightly", "cron": "0 2 * * *", "url": "https://example.com"}"#,
+            )
+            .unwrap(),
+        );
+
+        let mut in_flight = Some(tokio::spawn(std::future::pending::<()>()));
+        assert!(!start_run(&pool, &schedule, at(JAN_1_2024), &mut in_flight));
+        assert!(job_status(&pool, None, "nightly").is_err());
+
+        in_flight.as_ref().unwrap().abort();
+        while !in_flight.as_ref().unwrap().is_finished() {
+            tokio::task::yield_now().await;
+        }
+        assert!(start_run(&pool, &schedule, at(JAN_1_2024), &mut in_flight));
+        in_flight.take().unwrap().await.unwrap();
+        let job = job_status(&pool, None, "nightly").unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+    }
+
+    /// Verifies a run's result is POSTed to the schedule's callback URL,
+    /// and invalid callback URLs are rejected up front.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_scheduled_callback() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let pool = Arc::new(Mutex::new(
+            BrowserPool::builder()
+                .config(BrowserPoolConfigBuilder::new().build().unwrap())
+                .factory(Box::new(MockBrowserFactory::always_fails("no chrome")))
+                .enable_keep_alive(false)
+                .build()
+                .unwrap(),
+        ));
+        let schedule = |callback: &str| -> ScheduledRender {
+            serde_json::from_value(serde_json::json!({
+                "name": "nightly",
+                "cron": "0 2 * * *",
+                "url": "https://example.com",
+                "callback_url": callback,
+            }))
+            .unwrap()
+        };
+        assert!(matches!(
+            spawn_scheduler(&pool, vec![schedule("ftp://example.com/hook")]),
+            Err(PdfServiceError::InvalidUrl(_))
+        ));
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let callback = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            request_line
+        });
+
+        run_once(&pool, &schedule(&callback), at(JAN_1_2024)).await;
+        let request_line = tokio::task::spawn_blocking(move || server.join().unwrap())
+            .await
+            .unwrap();
+        assert!(request_line.starts_with("POST /hook "));
+    }
+}
@@ -0,0 +1,142 @@
+//! Per-page PDF output.
+//!
+//! Some print and e-signature systems take one document per page. With
+//! [`split_pages`](crate::service::PdfFromUrlRequest::split_pages) the
+//! rendered PDF is split with [qpdf](https://qpdf.readthedocs.io) (see
+//! [`QPDF_PATH_ENV`](crate::service::QPDF_PATH_ENV)) and the pages are
+//! returned as a ZIP archive:
+//!
+//! ```text
+//! report.zip
+//! ├── page-01.pdf
+//! ├── page-02.pdf
+//! └── ...
+//! ```
+//!
+//! Page numbers are zero-padded to the width of the page count, so the
+//! entries sort in page order.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{Cursor, Write};
+use std::time::Instant;
+
+use zip::CompressionMethod;
+use zip::write::{SimpleFileOptions, ZipWriter};
+
+use crate::service::linearize::{TempDir, qpdf_binary, qpdf_command};
+use crate::service::types::PdfServiceError;
+
+/// Split a PDF into one PDF per page and pack them into a ZIP archive.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::PdfGenerationFailed`] if qpdf is not
+/// installed or rejects the document.
+pub(crate) fn split_pdf(pdf: &[u8]) -> Result<Vec<u8>, PdfServiceError> {
+    let start = Instant::now();
+
+    let archive = split_pages(&qpdf_binary(), pdf)
+        .and_then(|pages| zip_pages(&pages))
+        .map_err(|e| {
+            log::error!("❌ Failed to split PDF into pages: {}", e);
+            PdfServiceError::PdfGenerationFailed(format!("failed to split PDF into pages: {}", e))
+        })?;
+
+    log::debug!(
+        "PDF split into pages in {:?} ({} -> {} bytes)",
+        start.elapsed(),
+        pdf.len(),
+        archive.len()
+    );
+
+    Ok(archive)
+}
+
+/// Run `qpdf --split-pages` on `pdf`; returns the pages in order.
+fn split_pages(qpdf: &OsStr, pdf: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    let dir = TempDir::create()?;
+    let input = dir.path().join("input.pdf");
+    let pages_dir = dir.path().join("pages");
+
+    fs::write(&input, pdf).map_err(|e| format!("cannot write temporary file: {}", e))?;
+    fs::create_dir(&pages_dir).map_err(|e| format!("cannot create temporary directory: {}", e))?;
+    // qpdf zero-pads the page numbers it puts in place of %d
+    let output = pages_dir.join("page-%d.pdf");
+    qpdf_command(
+        qpdf,
+        [
+            OsStr::new("--split-pages"),
+            input.as_os_str(),
+            output.as_os_str(),
+        ],
+    )?;
+
+    let mut files: Vec<_> = fs::read_dir(&pages_dir)
+        .and_then(|entries| entries.map(|entry| entry.map(|e| e.path())).collect())
+        .map_err(|e| format!("cannot list qpdf output: {}", e))?;
+    files.sort();
+    if files.is_empty() {
+        return Err("qpdf produced no pages".to_string());
+    }
+
+    files
+        .iter()
+        .map(|file| fs::read(file).map_err(|e| format!("cannot read qpdf output: {}", e)))
+        .collect()
+}
+
+/// Pack `pages` into a ZIP archive as `page-<n>.pdf`.
+fn zip_pages(pages: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+    let width = pages.len().to_string().len().max(2);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (index, page) in pages.iter().enumerate() {
+        zip.start_file(format!("page-{:0width$}.pdf", index + 1), options)
+            .and_then(|()| zip.write_all(page).map_err(Into::into))
+            .map_err(|e| format!("cannot write archive: {}", e))?;
+    }
+    zip.finish()
+        .map(Cursor::into_inner)
+        .map_err(|e| format!("cannot write archive: {}", e))
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    /// Verifies pages are stored in order under zero-padded names.
+    #[test]
+    fn test_zip_pages() {
+        let pages: Vec<Vec<u8>> = (1..=12)
+            .map(|n| format!("%PDF-1.4 page {}", n).into_bytes())
+            .collect();
+        let archive = zip_pages(&pages).unwrap();
+
+        let mut zip = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        assert_eq!(zip.len(), 12);
+        let names: Vec<_> = zip.file_names().collect();
+        assert!(names.contains(&"page-01.pdf"));
+        assert!(names.contains(&"page-12.pdf"));
+
+        let mut page = String::new();
+        zip.by_name("page-03.pdf")
+            .unwrap()
+            .read_to_string(&mut page)
+            .unwrap();
+        assert_eq!(page, "%PDF-1.4 page 3");
+    }
+
+    /// Verifies a missing qpdf is reported.
+    #[test]
+    fn test_missing_qpdf() {
+        let error = split_pages(OsStr::new("/nonexistent/qpdf"), b"%PDF-1.4\n").unwrap_err();
+        assert!(error.contains("/nonexistent/qpdf"));
+    }
+}
@@ -0,0 +1,179 @@
+//! Streamed PDF output.
+//!
+//! By default a render hands back the whole PDF in a
+//! [`PdfResponse`](crate::service::PdfResponse), so a 200 MB report costs
+//! 200 MB in the service before the first byte goes out. With
+//! [`stream`](crate::service::PdfFromUrlRequest::stream) set, Chrome keeps
+//! the printed PDF (`Page.printToPDF` with `transferMode: ReturnAsStream`)
+//! and a [`PdfStream`] reads it in chunks while the response is sent:
+//!
+//! ```text
+//! Chrome ──IO.read (≤ 1 MiB)──▶ PdfStream ──chunk──▶ response body ──▶ client
+//!    ▲                                                                   │
+//!    └────────────── next chunk once the client has taken this one ◀─────┘
+//! ```
+//!
+//! The render's tab stays checked out until the client has read the whole
+//! PDF; a client that disconnects early closes the tab.
+
+use std::fmt;
+
+use bytes::Bytes;
+
+use crate::service::pdf::PrintStream;
+use crate::service::types::PdfServiceError;
+
+/// A rendered PDF read from Chrome chunk by chunk.
+///
+/// Returned by [`stream_pdf_from_url`](crate::service::stream_pdf_from_url)
+/// and [`stream_pdf_from_html`](crate::service::stream_pdf_from_html).
+/// Iterating yields the PDF's bytes in order, blocking while each chunk is
+/// read; after an error the iterator ends. Dropping the stream before the
+/// end abandons the PDF and closes the tab.
+pub struct PdfStream {
+    /// Where the chunks come from; `None` once finished or failed.
+    source: Option<PrintStream>,
+
+    filename: String,
+    force_download: bool,
+
+    /// Bytes yielded so far.
+    sent: u64,
+
+    /// Called with [`sent`](Self::sent) once the whole PDF has been read.
+    on_finish: Option<Box<dyn FnOnce(u64) + Send>>,
+}
+
+impl PdfStream {
+    pub(crate) fn new(source: PrintStream, filename: String, force_download: bool) -> Self {
+        Self {
+            source: Some(source),
+            filename,
+            force_download,
+            sent: 0,
+            on_finish: None,
+        }
+    }
+
+    /// Call `f` with the PDF's size once the whole PDF has been read.
+    pub(crate) fn on_finish(mut self, f: impl FnOnce(u64) + Send + 'static) -> Self {
+        self.on_finish = Some(Box::new(f));
+        self
+    }
+
+    /// The suggested filename for the download.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Whether the browser should download the PDF rather than display it.
+    pub fn is_download(&self) -> bool {
+        self.force_download
+    }
+
+    /// Generates the `Content-Disposition` header value, like
+    /// [`PdfResponse::content_disposition`](crate::service::PdfResponse::content_disposition).
+    pub fn content_disposition(&self) -> String {
+        let disposition_type = if self.force_download {
+            "attachment"
+        } else {
+            "inline"
+        };
+        format!("{}; filename=\"{}\"", disposition_type, self.filename)
+    }
+
+    /// Read the stream on a blocking thread, yielding its chunks as an
+    /// async [`Stream`](futures_core::Stream) for a response body.
+    ///
+    /// Chrome is read at most two chunks ahead of the consumer. Dropping
+    /// the returned stream stops reading and closes the tab.
+    ///
+    /// # Runtime
+    ///
+    /// Must be called from within a Tokio runtime.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration"
+    ))]
+    pub fn into_chunks(self) -> PdfChunks {
+        let (sender, receiver) = tokio::sync::mpsc::channel(2);
+        tokio::task::spawn_blocking(move || {
+            for chunk in self {
+                if sender.blocking_send(chunk).is_err() {
+                    log::debug!("PDF stream abandoned by the client");
+                    break;
+                }
+            }
+        });
+        PdfChunks { receiver }
+    }
+}
+
+impl Iterator for PdfStream {
+    type Item = Result<Bytes, PdfServiceError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let source = self.source.as_mut()?;
+            match source.read_chunk() {
+                Ok(Some(chunk)) if chunk.is_empty() => continue,
+                Ok(Some(chunk)) => {
+                    self.sent += chunk.len() as u64;
+                    return Some(Ok(chunk.into()));
+                }
+                Ok(None) => {
+                    // Return the tab before the caller moves on
+                    self.source = None;
+                    log::debug!("PDF streamed ({} bytes)", self.sent);
+                    if let Some(on_finish) = self.on_finish.take() {
+                        on_finish(self.sent);
+                    }
+                    return None;
+                }
+                Err(e) => {
+                    self.source = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for PdfStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PdfStream")
+            .field("filename", &self.filename)
+            .field("force_download", &self.force_download)
+            .field("sent", &self.sent)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The chunks of a [`PdfStream`], as an async
+/// [`Stream`](futures_core::Stream); see [`PdfStream::into_chunks`].
+#[cfg(any(
+    feature = "actix-integration",
+    feature = "rocket-integration",
+    feature = "axum-integration"
+))]
+#[derive(Debug)]
+pub struct PdfChunks {
+    receiver: tokio::sync::mpsc::Receiver<Result<Bytes, PdfServiceError>>,
+}
+
+#[cfg(any(
+    feature = "actix-integration",
+    feature = "rocket-integration",
+    feature = "axum-integration"
+))]
+impl futures_core::Stream for PdfChunks {
+    type Item = Result<Bytes, PdfServiceError>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
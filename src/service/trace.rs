@@ -0,0 +1,254 @@
+//! DevTools performance traces of individual renders.
+//!
+//! A request with `trace` set records a Chrome trace (the same data the
+//! DevTools Performance panel shows) from navigation through printing and
+//! saves it as `<trace_id>.json` in the pool's
+//! [`trace_dir`](crate::BrowserPoolConfig::trace_dir). Open the file in
+//! `chrome://tracing` or the Performance panel to see why a page is slow
+//! without reproducing the render locally.
+//!
+//! Tracing is best effort: a trace that cannot be started or saved is
+//! logged and the render proceeds without it. Renders that fail still save
+//! their trace, and the trace ID is logged with the error.
+
+use base64::Engine;
+use headless_chrome::protocol::cdp::types::Event;
+use headless_chrome::protocol::cdp::{IO, Tracing};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::service::fallback::generate_request_id;
+
+/// Response header carrying the ID of a render's saved trace.
+pub const TRACE_ID_HEADER: &str = "X-Trace-Id";
+
+/// Categories recorded, matching the DevTools Performance panel.
+const TRACE_CATEGORIES: &[&str] = &[
+    "-*",
+    "devtools.timeline",
+    "disabled-by-default-devtools.timeline",
+    "disabled-by-default-devtools.timeline.frame",
+    "disabled-by-default-devtools.timeline.stack",
+    "disabled-by-default-v8.cpu_profiler",
+    "v8.execute",
+    "blink.user_timing",
+    "loading",
+    "latencyInfo",
+    "toplevel",
+];
+
+/// How long to wait for Chrome to finish flushing a trace.
+const TRACE_COMPLETE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Bytes requested per `IO.read` of the trace stream.
+const READ_CHUNK_SIZE: u32 = 1 << 20;
+
+/// Stream handle from `Tracing.tracingComplete`; `Some(None)` if Chrome
+/// completed without one.
+type Completion = Arc<(Mutex<Option<Option<String>>>, Condvar)>;
+
+/// A trace being recorded in one tab.
+pub(crate) struct TraceRecorder {
+    completion: Completion,
+    listener:
+        std::sync::Weak<dyn headless_chrome::browser::tab::EventListener<Event> + Send + Sync>,
+}
+
+impl TraceRecorder {
+    /// Start recording a trace in `tab`.
+    pub(crate) fn start(tab: &headless_chrome::Tab) -> Result<Self, String> {
+        let completion: Completion = Arc::default();
+        let listener = {
+            let completion = Arc::clone(&completion);
+            Arc::new(move |event: &Event| {
+                if let Event::TracingComplete(complete) = event {
+                    let (stream, done) = &*completion;
+                    *stream.lock().unwrap_or_else(PoisonError::into_inner) =
+                        Some(complete.params.stream.clone());
+                    done.notify_all();
+                }
+            })
+        };
+        let listener = tab
+            .add_event_listener(listener)
+            .map_err(|e| e.to_string())?;
+
+        let started = tab.call_method(Tracing::Start {
+            categories: None,
+            options: None,
+            buffer_usage_reporting_interval: None,
+            transfer_mode: Some(Tracing::StartTransfer_modeOption::ReturnAsStream),
+            stream_format: Some(Tracing::StreamFormat::Json),
+            stream_compression: None,
+            trace_config: Some(Tracing::TraceConfig {
+                record_mode: None,
+                trace_buffer_size_in_kb: None,
+                enable_sampling: None,
+                enable_systrace: None,
+                enable_argument_filter: None,
+                included_categories: Some(TRACE_CATEGORIES.iter().map(|c| c.to_string()).collect()),
+                excluded_categories: None,
+                synthetic_delays: None,
+                memory_dump_config: None,
+            }),
+            perfetto_config: None,
+            tracing_backend: None,
+        });
+        if let Err(e) = started {
+            let _ = tab.remove_event_listener(&listener);
+            return Err(e.to_string());
+        }
+
+        log::debug!("Recording performance trace");
+        Ok(Self {
+            completion,
+            listener,
+        })
+    }
+
+    /// Stop recording and save the trace in `dir`, returning its ID.
+    ///
+    /// Blocks until Chrome has flushed the trace, up to 10 seconds.
+    pub(crate) fn finish(self, tab: &headless_chrome::Tab, dir: &Path) -> Result<String, String> {
+        let result = self.save(tab, dir);
+        let _ = tab.remove_event_listener(&self.listener);
+        result
+    }
+
+    fn save(&self, tab: &headless_chrome::Tab, dir: &Path) -> Result<String, String> {
+        tab.call_method(Tracing::End(None))
+            .map_err(|e| e.to_string())?;
+
+        let stream = {
+            let (stream, done) = &*self.completion;
+            let (stream, _) = done
+                .wait_timeout_while(
+                    stream.lock().unwrap_or_else(PoisonError::into_inner),
+                    TRACE_COMPLETE_TIMEOUT,
+                    |stream| stream.is_none(),
+                )
+                .unwrap_or_else(PoisonError::into_inner);
+            match stream.clone() {
+                Some(Some(handle)) => handle,
+                Some(None) => return Err("Chrome returned no trace stream".to_string()),
+                None => {
+                    return Err(format!(
+                        "trace not completed within {:?}",
+                        TRACE_COMPLETE_TIMEOUT
+                    ));
+                }
+            }
+        };
+
+        let trace_id = generate_request_id();
+        let path = trace_path(dir, &trace_id);
+        let written = File::create_new(&path)
+            .map_err(|e| format!("cannot create {}: {}", path.display(), e))
+            .and_then(|mut file| copy_stream(tab, &stream, &mut file));
+        let _ = tab.call_method(IO::Close { handle: stream });
+
+        if let Err(e) = written {
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+        Ok(trace_id)
+    }
+}
+
+/// Path of the trace file for `trace_id`.
+fn trace_path(dir: &Path, trace_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", trace_id))
+}
+
+/// Copy a DevTools stream into `out` until Chrome reports the end.
+fn copy_stream(
+    tab: &headless_chrome::Tab,
+    handle: &str,
+    out: &mut impl Write,
+) -> Result<(), String> {
+    loop {
+        let chunk = tab
+            .call_method(IO::Read {
+                handle: handle.to_string(),
+                offset: None,
+                size: Some(READ_CHUNK_SIZE),
+            })
+            .map_err(|e| format!("cannot read trace: {}", e))?;
+        out.write_all(&decode_chunk(&chunk)?)
+            .map_err(|e| format!("cannot write trace: {}", e))?;
+        if chunk.eof {
+            return Ok(());
+        }
+    }
+}
+
+/// The bytes of one `IO.read` result.
+fn decode_chunk(chunk: &IO::ReadReturnObject) -> Result<Vec<u8>, String> {
+    if chunk.base_64_encoded == Some(true) {
+        base64::engine::general_purpose::STANDARD
+            .decode(&chunk.data)
+            .map_err(|e| format!("invalid trace chunk: {}", e))
+    } else {
+        Ok(chunk.data.clone().into_bytes())
+    }
+}
+
+/// Check that the trace directory exists and is writable.
+///
+/// Called when a traced render starts, so a misconfigured directory is
+/// reported before the render rather than after it.
+pub(crate) fn check_trace_dir(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(format!(".probe-{}", generate_request_id()));
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|e| format!("trace directory {} is not writable: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies stream chunks are decoded whether or not Chrome
+    /// base64-encoded them.
+    #[test]
+    fn test_decode_chunk() {
+        let chunk = |data: &str, base_64_encoded| IO::ReadReturnObject {
+            base_64_encoded,
+            data: data.to_string(),
+            eof: false,
+        };
+
+        assert_eq!(
+            decode_chunk(&chunk("{\"a\":1}", None)).unwrap(),
+            b"{\"a\":1}"
+        );
+        assert_eq!(decode_chunk(&chunk("e30=", Some(true))).unwrap(), b"{}");
+        assert!(decode_chunk(&chunk("not base64!", Some(true))).is_err());
+    }
+
+    /// Verifies the trace directory check reports missing directories and
+    /// leaves no probe file behind.
+    #[test]
+    fn test_check_trace_dir() {
+        let dir = std::env::temp_dir().join(format!("html2pdf-traces-{}", std::process::id()));
+        assert!(check_trace_dir(&dir).is_err());
+
+        std::fs::create_dir_all(&dir).unwrap();
+        check_trace_dir(&dir).unwrap();
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        assert!(trace_path(&dir, "abc").ends_with("abc.json"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,355 @@
+//! Watermarks stamped on every page.
+//!
+//! A request's [`watermark`](crate::service::PdfFromUrlRequest::watermark)
+//! becomes a stylesheet added to the ready page along with `inject_css`.
+//! It draws a `position: fixed` pseudo-element on the root element, which
+//! Chrome repeats on every printed page:
+//!
+//! ```text
+//! html::after { content: "DRAFT"; position: fixed; top: 50%; left: 50%;
+//!               transform: translate(-50%, -50%) rotate(-45deg); opacity: 0.2; ... }
+//! ```
+//!
+//! Going through CSS rather than an inserted element leaves the page's DOM
+//! alone and works with scripts disabled. User-supplied text and URLs are
+//! written as escaped CSS strings, so they cannot break out of the rule.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::service::types::{PdfServiceError, Watermark};
+
+/// Longest watermark text accepted, in bytes.
+const MAX_TEXT_LEN: usize = 256;
+
+/// Longest watermark image URL accepted, in bytes; room for a `data:` URL
+/// of a logo.
+const MAX_IMAGE_LEN: usize = 2 * 1024 * 1024;
+
+/// Opacity used when the request sets none.
+const DEFAULT_OPACITY: f64 = 0.2;
+
+/// Rotation of a text watermark when the request sets none, in degrees.
+const DEFAULT_TEXT_ROTATION: f64 = -45.0;
+
+/// Text size used when the request sets none, in CSS pixels.
+const DEFAULT_FONT_SIZE: f64 = 96.0;
+
+/// Text color used when the request sets none.
+const DEFAULT_COLOR: &str = "gray";
+
+/// Where on the page a watermark sits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Position {
+    /// Parse a request's `position`, case-insensitively.
+    fn parse(value: &str) -> Option<Self> {
+        Some(match value.trim().to_ascii_lowercase().as_str() {
+            "center" => Self::Center,
+            "top" => Self::Top,
+            "bottom" => Self::Bottom,
+            "left" => Self::Left,
+            "right" => Self::Right,
+            "top-left" => Self::TopLeft,
+            "top-right" => Self::TopRight,
+            "bottom-left" => Self::BottomLeft,
+            "bottom-right" => Self::BottomRight,
+            _ => return None,
+        })
+    }
+
+    /// Offsets placing the box, and the translation centering it on them.
+    fn placement(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Center => ("top: 50%; left: 50%;", "translate(-50%, -50%)"),
+            Self::Top => ("top: 0; left: 50%;", "translate(-50%, 0)"),
+            Self::Bottom => ("bottom: 0; left: 50%;", "translate(-50%, 0)"),
+            Self::Left => ("top: 50%; left: 0;", "translate(0, -50%)"),
+            Self::Right => ("top: 50%; right: 0;", "translate(0, -50%)"),
+            Self::TopLeft => ("top: 0; left: 0;", "translate(0, 0)"),
+            Self::TopRight => ("top: 0; right: 0;", "translate(0, 0)"),
+            Self::BottomLeft => ("bottom: 0; left: 0;", "translate(0, 0)"),
+            Self::BottomRight => ("bottom: 0; right: 0;", "translate(0, 0)"),
+        }
+    }
+
+    /// Where an image sits within its box.
+    fn background_position(self) -> &'static str {
+        match self {
+            Self::Center => "center",
+            Self::Top => "center top",
+            Self::Bottom => "center bottom",
+            Self::Left => "left center",
+            Self::Right => "right center",
+            Self::TopLeft => "left top",
+            Self::TopRight => "right top",
+            Self::BottomLeft => "left bottom",
+            Self::BottomRight => "right bottom",
+        }
+    }
+}
+
+/// Check a request's `watermark` and build the stylesheet drawing it.
+///
+/// # Errors
+///
+/// Returns [`PdfServiceError::InvalidRequest`] unless exactly one of `text`
+/// and `image` is set, or when a setting is out of range.
+pub(crate) fn watermark_css(
+    watermark: Option<&Watermark>,
+) -> Result<Option<Arc<str>>, PdfServiceError> {
+    let Some(watermark) = watermark else {
+        return Ok(None);
+    };
+    let invalid = |message: String| PdfServiceError::InvalidRequest(message);
+
+    let opacity = watermark.opacity.unwrap_or(DEFAULT_OPACITY);
+    if !(0.0..=1.0).contains(&opacity) {
+        return Err(invalid(format!(
+            "watermark.opacity must be between 0.0 and 1.0, got {}",
+            opacity
+        )));
+    }
+    let position = match watermark.position.as_deref() {
+        Some(value) => Position::parse(value).ok_or_else(|| {
+            invalid(format!(
+                "invalid watermark.position {:?}: expected center, top, bottom, left, \
+                 right, top-left, top-right, bottom-left, or bottom-right",
+                value
+            ))
+        })?,
+        None => Position::Center,
+    };
+    let rotation = watermark.rotation.unwrap_or(match watermark.text {
+        Some(_) => DEFAULT_TEXT_ROTATION,
+        None => 0.0,
+    });
+    if !(-360.0..=360.0).contains(&rotation) {
+        return Err(invalid(format!(
+            "watermark.rotation must be between -360 and 360 degrees, got {}",
+            rotation
+        )));
+    }
+
+    let (offsets, translate) = position.placement();
+    let mut css = format!(
+        "html::after {{ position: fixed; {} transform: {} rotate({}deg); \
+         z-index: 2147483647; pointer-events: none; opacity: {}; \
+         -webkit-print-color-adjust: exact; print-color-adjust: exact; ",
+        offsets, translate, rotation, opacity
+    );
+
+    match (&watermark.text, &watermark.image) {
+        (Some(text), None) => {
+            if text.trim().is_empty() || text.len() > MAX_TEXT_LEN {
+                return Err(invalid(format!(
+                    "watermark.text must be 1 to {} bytes",
+                    MAX_TEXT_LEN
+                )));
+            }
+            let font_size = watermark.font_size.unwrap_or(DEFAULT_FONT_SIZE);
+            if !(1.0..=1000.0).contains(&font_size) {
+                return Err(invalid(format!(
+                    "watermark.font_size must be between 1 and 1000, got {}",
+                    font_size
+                )));
+            }
+            let color = watermark.color.as_deref().unwrap_or(DEFAULT_COLOR);
+            if !is_color(color) {
+                return Err(invalid(format!(
+                    "invalid watermark.color {:?}: expected a color name or #rgb/#rrggbb",
+                    color
+                )));
+            }
+            let _ = write!(
+                css,
+                "content: {}; white-space: pre; text-align: center; \
+                 font: bold {}px sans-serif; line-height: 1.2; color: {}; }}",
+                css_string(text),
+                font_size,
+                color
+            );
+        }
+        (None, Some(image)) => {
+            if image.len() > MAX_IMAGE_LEN {
+                return Err(invalid(format!(
+                    "watermark.image must be at most {} bytes",
+                    MAX_IMAGE_LEN
+                )));
+            }
+            let image = image.trim();
+            if !["http://", "https://", "data:image/"].iter().any(|scheme| {
+                image
+                    .get(..scheme.len())
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+            }) {
+                return Err(invalid(
+                    "watermark.image must be an http(s) or data:image/ URL".to_string(),
+                ));
+            }
+            let _ = write!(
+                css,
+                "content: \"\"; width: 50vw; height: 50vh; \
+                 background: url({}) {} / contain no-repeat; }}",
+                css_string(image),
+                position.background_position()
+            );
+        }
+        _ => {
+            return Err(invalid(
+                "watermark needs exactly one of text or image".to_string(),
+            ));
+        }
+    }
+
+    Ok(Some(Arc::from(css)))
+}
+
+/// Whether `value` is a CSS color name or a `#rgb`/`#rrggbb` hex color.
+fn is_color(value: &str) -> bool {
+    match value.strip_prefix('#') {
+        Some(hex) => matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => {
+            !value.is_empty() && value.len() <= 32 && value.chars().all(|c| c.is_ascii_alphabetic())
+        }
+    }
+}
+
+/// `value` as a quoted CSS string, with everything but letters, digits, and
+/// spaces written as hex escapes.
+fn css_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() || c == ' ' {
+            quoted.push(c);
+        } else {
+            // The trailing space ends the escape and is not part of the text
+            let _ = write!(quoted, "\\{:x} ", c as u32);
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies a text watermark gets the default look.
+    #[test]
+    fn test_text_watermark() {
+        let css = watermark_css(Some(&Watermark::text("DRAFT")))
+            .unwrap()
+            .unwrap();
+
+        assert!(css.starts_with("html::after {"));
+        assert!(css.contains("content: \"DRAFT\";"));
+        assert!(css.contains("top: 50%; left: 50%;"));
+        assert!(css.contains("translate(-50%, -50%) rotate(-45deg)"));
+        assert!(css.contains("opacity: 0.2;"));
+        assert!(css.contains("font: bold 96px sans-serif;"));
+        assert!(css.contains("color: gray;"));
+
+        assert_eq!(watermark_css(None).unwrap(), None);
+    }
+
+    /// Verifies an image watermark is scaled into a box at its position.
+    #[test]
+    fn test_image_watermark() {
+        let watermark = Watermark {
+            image: Some("https://example.com/logo.png".to_string()),
+            opacity: Some(0.5),
+            position: Some("Bottom-Right".to_string()),
+            ..Default::default()
+        };
+        let css = watermark_css(Some(&watermark)).unwrap().unwrap();
+
+        assert!(css.contains("bottom: 0; right: 0;"));
+        assert!(css.contains("rotate(0deg)"));
+        assert!(css.contains("opacity: 0.5;"));
+        assert!(css.contains(
+            "url(\"https\\3a \\2f \\2f example\\2e com\\2f logo\\2e png\") right bottom / contain"
+        ));
+    }
+
+    /// Verifies text cannot close the CSS string or rule.
+    #[test]
+    fn test_text_is_escaped() {
+        let css = watermark_css(Some(&Watermark::text("a\"; } body { display: none")))
+            .unwrap()
+            .unwrap();
+
+        assert!(css.contains("content: \"a\\22 \\3b  \\7d  body \\7b  display\\3a  none\";"));
+        assert_eq!(css.matches('}').count(), 1);
+        assert_eq!(css_string("x\ny"), "\"x\\a y\"");
+    }
+
+    /// Verifies out-of-range and conflicting settings are rejected.
+    #[test]
+    fn test_invalid_watermark() {
+        let invalid = [
+            Watermark::default(),
+            Watermark {
+                image: Some("https://example.com/logo.png".to_string()),
+                ..Watermark::text("DRAFT")
+            },
+            Watermark::text(" "),
+            Watermark::text("x".repeat(MAX_TEXT_LEN + 1)),
+            Watermark {
+                opacity: Some(1.5),
+                ..Watermark::text("DRAFT")
+            },
+            Watermark {
+                rotation: Some(720.0),
+                ..Watermark::text("DRAFT")
+            },
+            Watermark {
+                position: Some("middle".to_string()),
+                ..Watermark::text("DRAFT")
+            },
+            Watermark {
+                font_size: Some(0.0),
+                ..Watermark::text("DRAFT")
+            },
+            Watermark {
+                color: Some("red; background: url(x)".to_string()),
+                ..Watermark::text("DRAFT")
+            },
+            Watermark {
+                image: Some("file:///etc/passwd".to_string()),
+                ..Default::default()
+            },
+        ];
+        for watermark in invalid {
+            assert!(
+                matches!(
+                    watermark_css(Some(&watermark)),
+                    Err(PdfServiceError::InvalidRequest(_))
+                ),
+                "{:?} should be rejected",
+                watermark
+            );
+        }
+
+        assert!(is_color("#c00"));
+        assert!(is_color("#CC0000"));
+        assert!(is_color("crimson"));
+        assert!(!is_color("#cc00"));
+    }
+}
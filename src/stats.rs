@@ -15,6 +15,10 @@
 //! let stats = pool.stats();
 //! println!("Available: {}, Active: {}", stats.available, stats.active);
 //! ```
+//!
+//! For troubleshooting, [`PoolDebugSnapshot`] adds per-browser detail.
+
+use std::time::Duration;
 
 /// Snapshot of pool statistics at a point in time.
 ///
@@ -183,6 +187,219 @@ impl std::fmt::Display for PoolStats {
     }
 }
 
+// ============================================================================
+// Debug Snapshot
+// ============================================================================
+
+/// Detailed diagnostic snapshot of the pool, for troubleshooting.
+///
+/// Unlike [`PoolStats`], which is cheap enough to poll for metrics, this
+/// walks every tracked browser and reads its process memory. Get it from
+/// [`BrowserPool::debug_snapshot()`](crate::BrowserPool::debug_snapshot).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let snapshot = pool.debug_snapshot();
+/// for browser in &snapshot.browsers {
+///     println!("browser {}: {} uses, {:?} old", browser.id, browser.uses, browser.age);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PoolDebugSnapshot {
+    /// Counts at the time of the snapshot.
+    pub stats: PoolStats,
+
+    /// Configured maximum number of pooled browsers.
+    pub max_pool_size: usize,
+
+    /// Every tracked browser (pooled and checked out), oldest first.
+    pub browsers: Vec<BrowserDebugInfo>,
+
+    /// Background tasks still creating replacement browsers.
+    pub replacement_tasks: usize,
+
+    /// Keep-alive thread timing, or `None` when keep-alive is disabled.
+    pub keep_alive: Option<KeepAliveStatus>,
+
+    /// Whether shutdown has started.
+    pub shutting_down: bool,
+
+    /// Checkouts waiting for a browser in the checkout queue (see
+    /// [`checkout_queue_timeout`](crate::BrowserPoolConfig::checkout_queue_timeout)).
+    pub queued_checkouts: usize,
+}
+
+/// Diagnostic details of one browser in a [`PoolDebugSnapshot`].
+#[derive(Debug, Clone)]
+pub struct BrowserDebugInfo {
+    /// Browser ID, as used in log messages.
+    pub id: u64,
+
+    /// Time since the browser was created.
+    pub age: Duration,
+
+    /// Times the browser has been checked out.
+    pub uses: u64,
+
+    /// Whether the browser is out of the available pool: checked out, or
+    /// skipped near its TTL and waiting for the keep-alive to retire it.
+    pub checked_out: bool,
+
+    /// Open tabs, including a parked standby or reusable tab.
+    pub open_tabs: usize,
+
+    /// Resident memory of the browser process and its children, in bytes.
+    ///
+    /// `None` when the process is not local or the platform does not
+    /// expose it (only Linux is supported).
+    pub memory_bytes: Option<u64>,
+
+    /// Most recent health check, or `None` if none has run yet.
+    pub last_health_check: Option<HealthCheckResult>,
+}
+
+// ============================================================================
+// Detailed Stats
+// ============================================================================
+
+/// Per-browser statistics plus lifetime counters of the pool.
+///
+/// Meant for dashboards and capacity planning: where [`PoolStats`] only
+/// counts browsers, this reports what each one is doing and how many
+/// browsers the pool has gone through. Like [`PoolDebugSnapshot`] it reads
+/// every browser's process memory. Get it from
+/// [`BrowserPool::detailed_stats()`](crate::BrowserPool::detailed_stats).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let stats = pool.detailed_stats();
+/// println!(
+///     "created {}, retired {}, failed {}",
+///     stats.total_created, stats.total_retired, stats.total_failed
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct DetailedPoolStats {
+    /// Counts at the time of the snapshot.
+    pub stats: PoolStats,
+
+    /// Every tracked browser (idle and busy), oldest first.
+    pub browsers: Vec<BrowserStats>,
+
+    /// Browsers launched since the pool started.
+    pub total_created: u64,
+
+    /// Healthy browsers removed since the pool started: past their TTL,
+    /// at [`max_browser_uses`](crate::BrowserPoolConfig::max_browser_uses),
+    /// or returned to a full pool.
+    pub total_retired: u64,
+
+    /// Browsers removed after failing health checks since the pool
+    /// started, including those discarded by a pool recycle.
+    pub total_failed: u64,
+}
+
+/// Statistics of one browser in [`DetailedPoolStats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrowserStats {
+    /// Browser ID, as used in log messages.
+    pub id: u64,
+
+    /// Time since the browser was created.
+    pub age: Duration,
+
+    /// Times the browser has been checked out.
+    pub uses: u64,
+
+    /// Time since the browser last answered a ping (or was created).
+    pub last_ping: Duration,
+
+    /// Consecutive failed keep-alive pings; reset by a successful one.
+    pub ping_failures: u32,
+
+    /// Resident memory of the browser process and its children, in bytes
+    /// (Linux only).
+    pub memory_bytes: Option<u64>,
+
+    /// Whether the browser is idle in the pool or serving a render.
+    pub state: BrowserState,
+}
+
+/// What a browser in [`BrowserStats`] is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserState {
+    /// Waiting in the available pool.
+    Idle,
+
+    /// Checked out, or skipped near its TTL and waiting to be retired.
+    Busy,
+}
+
+/// Outcome of a browser's most recent health check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthCheckResult {
+    /// Time since the check ran.
+    pub age: Duration,
+
+    /// The check's error, or `None` if the browser was healthy.
+    pub error: Option<String>,
+}
+
+impl HealthCheckResult {
+    /// Whether the browser passed the check.
+    #[inline]
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Timing of the keep-alive thread in a [`PoolDebugSnapshot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeepAliveStatus {
+    /// Configured interval between cycles.
+    pub interval: Duration,
+
+    /// Cycles completed since the pool started.
+    pub cycles: u64,
+
+    /// Time since the last cycle started, or `None` before the first.
+    pub last_cycle_started: Option<Duration>,
+
+    /// How long the last completed cycle took.
+    pub last_cycle_duration: Option<Duration>,
+
+    /// Time until the next cycle, or `None` while a cycle is running.
+    pub next_cycle_in: Option<Duration>,
+
+    /// Full pool recycles since the pool started (see
+    /// [`recycle_after_failed_cycles`](crate::BrowserPoolConfig::recycle_after_failed_cycles)).
+    pub recycles: u64,
+
+    /// Time since the last recycle, or `None` if there has been none.
+    pub last_recycle: Option<Duration>,
+}
+
+/// A full pool recycle, passed to the hook set with
+/// [`BrowserPoolBuilder::on_recycle`](crate::BrowserPoolBuilder::on_recycle).
+///
+/// The keep-alive thread recycles the pool after
+/// [`recycle_after_failed_cycles`](crate::BrowserPoolConfig::recycle_after_failed_cycles)
+/// cycles in a row in which every browser failed its health check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolRecycle {
+    /// Browsers discarded, including checked-out ones that are dropped
+    /// when returned.
+    pub discarded: usize,
+
+    /// Consecutive failed cycles that triggered the recycle.
+    pub failed_cycles: u32,
+
+    /// Fresh browsers being launched to replace them (`warmup_count`).
+    pub relaunching: usize,
+}
+
 // ============================================================================
 // Unit Tests
 // ============================================================================
@@ -1,22 +1,33 @@
 //! Tracked browser with metadata for pool management.
 //!
-//! This module provides [`TrackedBrowser`], which wraps a [`Browser`] instance
-//! with tracking information for lifecycle management.
+//! This module provides [`TrackedBrowser`], which wraps a browser instance
+//! ([`Browser`] unless another [`PooledBrowser`] backend is used) with
+//! tracking information for lifecycle management.
 //!
 //! # Overview
 //!
 //! Each browser in the pool is wrapped in a `TrackedBrowser` that tracks:
 //! - **Unique ID**: For identification in logs and debugging
 //! - **Creation time**: For TTL (time-to-live) enforcement
+//! - **TTL jitter**: Random per-browser TTL reduction to spread out retirements
 //! - **Last ping time**: For health monitoring
+//! - **Use count and last health check**: For the pool's debug snapshot
+//! - **Tabs in use**: Renders currently running, for sharing one browser between renders
+//! - **Reusable tab**: Standby tab opened at creation, and the persistent tab when tab reuse is enabled
 //!
 //! # Architecture
 //!
 //! ```text
 //! TrackedBrowser
 //! ├── id: u64 (unique identifier)
-//! ├── browser: Arc<Browser> (shared ownership)
+//! ├── browser: Arc<B> (shared ownership)
 //! ├── last_ping: Arc<Mutex<Instant>> (health tracking)
+//! ├── last_health: Arc<Mutex<Option<HealthRecord>>> (latest health check)
+//! ├── uses: Arc<AtomicU64> (checkout count)
+//! ├── ping_failures: Arc<AtomicU32> (consecutive keep-alive failures)
+//! ├── tabs_in_use: Arc<AtomicUsize> (concurrent renders)
+//! ├── reusable_tab: Arc<Mutex<Option<Arc<B::Tab>>>> (standby / reused tab)
+//! ├── ttl_jitter: Duration (per-browser TTL offset)
 //! └── created_at: Instant (TTL calculation)
 //! ```
 //!
@@ -26,14 +37,17 @@
 //! with browsers through [`BrowserHandle`](crate::BrowserHandle), which
 //! provides transparent access via `Deref`.
 
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use headless_chrome::Browser;
 
 use crate::error::{BrowserPoolError, Result};
-use crate::traits::Healthcheck;
+use crate::stats::HealthCheckResult;
+use crate::traits::{BrowserTab, Healthcheck, PooledBrowser};
 
 /// A browser instance with metadata for pool management.
 ///
@@ -64,8 +78,7 @@ use crate::traits::Healthcheck;
 ///       │
 ///       └──→ TTL Expired ──→ Retired & Replaced
 /// ```
-#[derive(Clone)]
-pub(crate) struct TrackedBrowser {
+pub(crate) struct TrackedBrowser<B: PooledBrowser = Browser> {
     /// Globally unique identifier for this browser instance.
     ///
     /// Assigned sequentially using an atomic counter. Useful for:
@@ -74,13 +87,13 @@ pub(crate) struct TrackedBrowser {
     /// - Tracking browser lifecycle
     id: u64,
 
-    /// The actual headless_chrome Browser instance (ref-counted).
+    /// The actual browser instance (ref-counted).
     ///
     /// Wrapped in [`Arc`] to allow shared ownership between:
     /// - The pool's available list
     /// - The pool's active tracking map
     /// - Any [`BrowserHandle`](crate::BrowserHandle) using it
-    browser: Arc<Browser>,
+    browser: Arc<B>,
 
     /// Timestamp of last successful health check (protected by mutex).
     ///
@@ -88,6 +101,45 @@ pub(crate) struct TrackedBrowser {
     /// Used for monitoring browser responsiveness.
     last_ping: Arc<Mutex<Instant>>,
 
+    /// Latest health check result.
+    ///
+    /// Set by [`record_health()`](Self::record_health) from the keep-alive
+    /// and checkout checks; reported in the pool's debug snapshot.
+    last_health: Arc<Mutex<Option<HealthRecord>>>,
+
+    /// Number of times this browser has been checked out.
+    uses: Arc<AtomicU64>,
+
+    /// Consecutive failed keep-alive pings.
+    ///
+    /// Mirrors the keep-alive thread's own count so the pool's detailed
+    /// stats can report it.
+    ping_failures: Arc<AtomicU32>,
+
+    /// Number of handles currently rendering in this browser.
+    ///
+    /// At most one unless
+    /// [`tabs_per_browser`](crate::BrowserPoolConfig::tabs_per_browser) is
+    /// raised. The browser only goes back to the available list once this
+    /// drops to zero.
+    tabs_in_use: Arc<AtomicUsize>,
+
+    /// Tab parked for the next render on this browser.
+    ///
+    /// Holds the standby tab opened by [`open_standby_tab()`](Self::open_standby_tab)
+    /// during warmup/replacement, and in tab reuse mode the persistent tab
+    /// between renders. `None` once taken, or after a tab was discarded
+    /// because it failed. Shared between clones so the tab follows the
+    /// browser through the available list and active map.
+    reusable_tab: Arc<Mutex<Option<Arc<B::Tab>>>>,
+
+    /// Amount subtracted from the pool TTL for this browser.
+    ///
+    /// Drawn once by [`with_ttl_jitter()`](Self::with_ttl_jitter) so browsers
+    /// created at the same moment (e.g. concurrent warmup) retire at
+    /// different times. Zero unless jitter is configured.
+    ttl_jitter: Duration,
+
     /// Creation timestamp (immutable, used for TTL calculation).
     ///
     /// Set once during construction and never modified.
@@ -95,7 +147,31 @@ pub(crate) struct TrackedBrowser {
     created_at: Instant,
 }
 
-impl TrackedBrowser {
+/// When a health check ran and its error, if it failed.
+struct HealthRecord {
+    at: Instant,
+    error: Option<String>,
+}
+
+// Not derived: that would require `B: Clone` rather than cloning the `Arc`s
+impl<B: PooledBrowser> Clone for TrackedBrowser<B> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            browser: Arc::clone(&self.browser),
+            last_ping: Arc::clone(&self.last_ping),
+            last_health: Arc::clone(&self.last_health),
+            uses: Arc::clone(&self.uses),
+            ping_failures: Arc::clone(&self.ping_failures),
+            tabs_in_use: Arc::clone(&self.tabs_in_use),
+            reusable_tab: Arc::clone(&self.reusable_tab),
+            ttl_jitter: self.ttl_jitter,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl<B: PooledBrowser> TrackedBrowser<B> {
     /// Create a new tracked browser with validation.
     ///
     /// Performs an immediate health check to ensure the browser is functional
@@ -126,7 +202,7 @@ impl TrackedBrowser {
     ///
     /// println!("Browser ID: {}", tracked.id());
     /// ```
-    pub(crate) fn new(browser: Browser) -> Result<Self> {
+    pub(crate) fn new(browser: B) -> Result<Self> {
         // Thread-safe monotonic ID generator
         static NEXT_ID: AtomicU64 = AtomicU64::new(0);
 
@@ -137,21 +213,7 @@ impl TrackedBrowser {
 
         // Critical: Validate browser is functional before accepting it
         // This prevents adding dead browsers to the pool
-        let tab = browser.new_tab().map_err(|e| {
-            log::error!("❌ Browser validation failed at new_tab(): {}", e);
-            BrowserPoolError::BrowserCreation(e.to_string())
-        })?;
-
-        // Test navigation capability
-        tab.navigate_to("data:text/html,<html></html>")
-            .map_err(|e| {
-                log::error!("❌ Browser validation failed at navigate_to(): {}", e);
-                let _ = tab.close(true); // Best effort cleanup
-                BrowserPoolError::BrowserCreation(e.to_string())
-            })?;
-
-        // Clean up test tab
-        let _ = tab.close(true);
+        browser.validate()?;
 
         log::debug!("✅ Browser validation passed");
 
@@ -159,10 +221,31 @@ impl TrackedBrowser {
             id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
             browser,
             last_ping: Arc::new(Mutex::new(Instant::now())),
+            last_health: Arc::new(Mutex::new(None)),
+            uses: Arc::new(AtomicU64::new(0)),
+            ping_failures: Arc::new(AtomicU32::new(0)),
+            tabs_in_use: Arc::new(AtomicUsize::new(0)),
+            reusable_tab: Arc::new(Mutex::new(None)),
+            ttl_jitter: Duration::ZERO,
             created_at,
         })
     }
 
+    /// Draw this browser's TTL offset uniformly from `0..=max`.
+    ///
+    /// Uses the standard library's randomly seeded hasher, so no RNG
+    /// dependency is needed for this non-cryptographic purpose.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tracked = TrackedBrowser::new(browser)?.with_ttl_jitter(Duration::from_secs(300));
+    /// ```
+    pub(crate) fn with_ttl_jitter(mut self, max: Duration) -> Self {
+        self.ttl_jitter = random_jitter(self.id, max);
+        self
+    }
+
     /// Get the unique identifier for this browser.
     ///
     /// This ID is assigned sequentially and is unique across all browsers
@@ -181,8 +264,8 @@ impl TrackedBrowser {
 
     /// Get a reference to the underlying browser.
     ///
-    /// Returns a reference to the [`Arc<Browser>`], allowing shared access
-    /// to the browser instance.
+    /// Returns a reference to the [`Arc`]-wrapped browser, allowing shared
+    /// access to the browser instance.
     ///
     /// # Example
     ///
@@ -191,19 +274,21 @@ impl TrackedBrowser {
     /// let tab = tracked.browser().new_tab()?;
     /// ```
     #[inline]
-    pub(crate) fn browser(&self) -> &Arc<Browser> {
+    pub(crate) fn browser(&self) -> &Arc<B> {
         &self.browser
     }
 
     /// Check if browser has exceeded its time-to-live.
     ///
+    /// The browser's TTL jitter is subtracted from `ttl` first.
+    ///
     /// # Parameters
     ///
     /// * `ttl` - Maximum age before browser should be retired.
     ///
     /// # Returns
     ///
-    /// `true` if browser age > ttl, `false` otherwise.
+    /// `true` if browser age > ttl - jitter, `false` otherwise.
     ///
     /// # Example
     ///
@@ -219,7 +304,7 @@ impl TrackedBrowser {
     /// ```
     #[inline]
     pub(crate) fn is_expired(&self, ttl: Duration) -> bool {
-        self.created_at.elapsed() > ttl
+        self.created_at.elapsed() > ttl.saturating_sub(self.ttl_jitter)
     }
 
     /// Get the browser's age (time since creation).
@@ -278,13 +363,159 @@ impl TrackedBrowser {
     ///     log::debug!("Last ping was {:?} ago", since_ping);
     /// }
     /// ```
-    #[allow(dead_code)]
     pub(crate) fn last_ping_time(&self) -> Option<Instant> {
         self.last_ping.lock().ok().map(|guard| *guard)
     }
+
+    /// Record the outcome of a health check for the debug snapshot.
+    pub(crate) fn record_health(&self, error: Option<String>) {
+        if let Ok(mut last_health) = self.last_health.lock() {
+            *last_health = Some(HealthRecord {
+                at: Instant::now(),
+                error,
+            });
+        }
+    }
+
+    /// Get the most recent health check, if one has run.
+    pub(crate) fn last_health_check(&self) -> Option<HealthCheckResult> {
+        let last_health = self.last_health.lock().ok()?;
+        last_health.as_ref().map(|record| HealthCheckResult {
+            age: record.at.elapsed(),
+            error: record.error.clone(),
+        })
+    }
+
+    /// Count a checkout of this browser.
+    #[inline]
+    pub(crate) fn record_use(&self) {
+        self.uses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Claim a tab slot for a checkout of this (otherwise idle) browser.
+    #[inline]
+    pub(crate) fn claim_tab(&self) {
+        self.tabs_in_use.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Claim a tab slot on a browser that is already checked out.
+    ///
+    /// Succeeds only while between one and `limit - 1` slots are taken, so
+    /// idle browsers (in the available list) are never claimed this way.
+    pub(crate) fn try_share_tab(&self, limit: usize) -> bool {
+        self.tabs_in_use
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n > 0 && n < limit).then_some(n + 1)
+            })
+            .is_ok()
+    }
+
+    /// Release a tab slot, returning how many remain in use.
+    pub(crate) fn release_tab(&self) -> usize {
+        match self
+            .tabs_in_use
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1))
+        {
+            Ok(previous) => previous - 1,
+            Err(_) => 0,
+        }
+    }
+
+    /// Get the number of renders currently using this browser.
+    #[inline]
+    pub(crate) fn tabs_in_use(&self) -> usize {
+        self.tabs_in_use.load(Ordering::Acquire)
+    }
+
+    /// Get the number of times this browser has been checked out.
+    #[inline]
+    pub(crate) fn uses(&self) -> u64 {
+        self.uses.load(Ordering::Relaxed)
+    }
+
+    /// Record the keep-alive thread's count of consecutive failed pings.
+    #[inline]
+    pub(crate) fn set_ping_failures(&self, failures: u32) {
+        self.ping_failures.store(failures, Ordering::Relaxed);
+    }
+
+    /// Get the number of consecutive failed keep-alive pings.
+    #[inline]
+    pub(crate) fn ping_failures(&self) -> u32 {
+        self.ping_failures.load(Ordering::Relaxed)
+    }
+
+    /// Check if this browser has served `max_uses` checkouts.
+    ///
+    /// Always `false` when no ceiling is set.
+    #[inline]
+    pub(crate) fn is_used_up(&self, max_uses: Option<u32>) -> bool {
+        max_uses.is_some_and(|max| self.uses() >= u64::from(max))
+    }
+
+    /// Get the number of tabs open in this browser.
+    pub(crate) fn open_tab_count(&self) -> usize {
+        self.browser.open_tab_count()
+    }
+
+    /// Get the resident memory of the browser and its child processes.
+    ///
+    /// Chrome runs each renderer and the GPU in separate processes, so the
+    /// whole process tree is summed. Returns `None` for browsers without a
+    /// local process and on platforms other than Linux.
+    pub(crate) fn memory_bytes(&self) -> Option<u64> {
+        process_tree_rss(self.browser.process_id()?)
+    }
+
+    /// Open a tab ahead of time and park it for the first render.
+    ///
+    /// Called off the request path (warmup and replacement creation) so
+    /// the first render on a fresh browser doesn't pay for tab
+    /// initialization on top of browser startup. Failures are logged and
+    /// otherwise ignored; the render then opens its own tab.
+    pub(crate) fn open_standby_tab(&self) {
+        match self.browser.new_tab() {
+            Ok(tab) => {
+                log::trace!("Opened standby tab for browser {}", self.id);
+                self.store_reusable_tab(tab);
+            }
+            Err(e) => {
+                log::warn!(
+                    "⚠️ Failed to open standby tab for browser {}: {}",
+                    self.id,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Take the parked reusable tab, if any.
+    ///
+    /// The slot is left empty so the tab is never handed out twice.
+    pub(crate) fn take_reusable_tab(&self) -> Option<Arc<B::Tab>> {
+        self.reusable_tab
+            .lock()
+            .ok()
+            .and_then(|mut slot| slot.take())
+    }
+
+    /// Park a tab for the next render on this browser.
+    ///
+    /// A tab that was already parked (or cannot be parked because the lock
+    /// is poisoned) is closed rather than leaked.
+    pub(crate) fn store_reusable_tab(&self, tab: Arc<B::Tab>) {
+        let replaced = match self.reusable_tab.lock() {
+            Ok(mut slot) => slot.replace(tab),
+            Err(_) => Some(tab),
+        };
+
+        if let Some(old) = replaced {
+            let _ = old.close();
+        }
+    }
 }
 
-impl Healthcheck for TrackedBrowser {
+impl<B: PooledBrowser> Healthcheck for TrackedBrowser<B> {
     /// Perform health check by creating and closing a tab.
     ///
     /// This is a lightweight operation that verifies:
@@ -324,7 +555,7 @@ impl Healthcheck for TrackedBrowser {
         })?;
 
         // Clean up immediately
-        let _ = tab.close(true);
+        let _ = tab.close();
 
         // Update last ping timestamp (best effort - don't fail ping if this fails)
         // This is defensive: if we can't update timestamp, ping still succeeded
@@ -347,16 +578,85 @@ impl Healthcheck for TrackedBrowser {
     }
 }
 
-impl std::fmt::Debug for TrackedBrowser {
+impl<B: PooledBrowser> std::fmt::Debug for TrackedBrowser<B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TrackedBrowser")
             .field("id", &self.id)
             .field("created_at", &self.created_at)
             .field("age_minutes", &self.age_minutes())
+            .field("ttl_jitter", &self.ttl_jitter)
             .finish_non_exhaustive()
     }
 }
 
+/// Sum the resident memory of process `root` and all its descendants.
+#[cfg(target_os = "linux")]
+fn process_tree_rss(root: u32) -> Option<u64> {
+    use std::collections::HashMap;
+
+    // Parent of every process, from /proc/<pid>/stat
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+            continue;
+        };
+        let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+            continue;
+        };
+        if let Some(ppid) = parse_stat_ppid(&stat) {
+            children.entry(ppid).or_default().push(pid);
+        }
+    }
+
+    let mut total = 0;
+    let mut stack = vec![root];
+    while let Some(pid) = stack.pop() {
+        let status = std::fs::read_to_string(format!("/proc/{}/status", pid));
+        // The root must exist; children may exit while we walk the tree
+        match status.ok().and_then(|status| parse_status_rss(&status)) {
+            Some(rss) => total += rss,
+            None if pid == root => return None,
+            None => {}
+        }
+        stack.extend(children.get(&pid).into_iter().flatten());
+    }
+    Some(total)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_tree_rss(_root: u32) -> Option<u64> {
+    None
+}
+
+/// Parent PID from the contents of `/proc/<pid>/stat`.
+///
+/// The command name may contain spaces and parentheses, so fields are
+/// counted from its closing parenthesis.
+#[cfg(any(target_os = "linux", test))]
+fn parse_stat_ppid(stat: &str) -> Option<u32> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// `VmRSS` in bytes from the contents of `/proc/<pid>/status`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_status_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Pick a pseudo-random duration in `0..=max`, seeded per call.
+fn random_jitter(seed: u64, max: Duration) -> Duration {
+    let max_nanos = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+    if max_nanos == 0 {
+        return Duration::ZERO;
+    }
+
+    let random = RandomState::new().hash_one(seed);
+    Duration::from_nanos(random % max_nanos.saturating_add(1))
+}
+
 // ============================================================================
 // Unit Tests
 // ============================================================================
@@ -469,6 +769,17 @@ mod tests {
         assert!(age_expired > ttl, "Browser over TTL should be expired");
     }
 
+    /// Verifies TTL jitter stays within its bound.
+    #[test]
+    fn test_random_jitter_bounds() {
+        assert_eq!(random_jitter(1, Duration::ZERO), Duration::ZERO);
+
+        let max = Duration::from_secs(300);
+        for seed in 0..100 {
+            assert!(random_jitter(seed, max) <= max);
+        }
+    }
+
     /// Verifies age_minutes calculation.
     #[test]
     fn test_age_minutes_calculation() {
@@ -483,4 +794,26 @@ mod tests {
         assert_eq!(119u64 / 60, 1); // Just under two minutes
         assert_eq!(120u64 / 60, 2); // Exactly two minutes
     }
+
+    /// Verifies /proc parsing copes with spaces and parentheses in the
+    /// command name.
+    #[test]
+    fn test_parse_proc_files() {
+        let stat = "4242 (chrome (renderer) x) S 4200 4242 4242 0 -1";
+        assert_eq!(parse_stat_ppid(stat), Some(4200));
+        assert_eq!(parse_stat_ppid("garbage"), None);
+
+        let status = "Name:\tchrome\nVmPeak:\t  900 kB\nVmRSS:\t  512 kB\n";
+        assert_eq!(parse_status_rss(status), Some(512 * 1024));
+        assert_eq!(parse_status_rss("Name:\tkthreadd\n"), None);
+    }
+
+    /// Verifies the memory of a live process tree can be read.
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_process_tree_rss() {
+        let rss = process_tree_rss(std::process::id()).unwrap();
+        assert!(rss > 0);
+        assert_eq!(process_tree_rss(u32::MAX), None);
+    }
 }
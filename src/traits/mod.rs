@@ -4,6 +4,8 @@
 //! the browser pool. These traits enable:
 //!
 //! - **Health monitoring**: [`Healthcheck`] for verifying browser health
+//! - **Browser backends**: [`PooledBrowser`] and [`BrowserTab`] for the browsers the pool manages
+//! - **Post-processing**: [`PdfPostProcessor`] for steps applied to every rendered PDF
 //! - **Extensibility**: Custom implementations for different use cases
 //!
 //! # Implementing Custom Health Checks
@@ -27,5 +29,11 @@
 //! ```
 
 mod healthcheck;
+mod pooled;
+mod post_process;
 
 pub use healthcheck::Healthcheck;
+pub use pooled::{BrowserTab, PooledBrowser};
+pub use post_process::{PdfPostProcessor, PostProcessError};
+
+pub(crate) use post_process::FnPostProcessor;
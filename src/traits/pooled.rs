@@ -0,0 +1,329 @@
+//! The browser backend the pool manages.
+//!
+//! This module provides the [`PooledBrowser`] trait, which collects
+//! everything the pool, its handles and tracking need from a browser:
+//! opening tabs, validating a fresh instance, the cheap checkout ping, and
+//! the thorough check used by
+//! [`deep_health_check`](crate::BrowserPoolConfig::deep_health_check).
+//! Tabs are driven through [`BrowserTab`].
+//!
+//! # Backends
+//!
+//! [`headless_chrome::Browser`] is the default and the only backend the
+//! crate ships. [`Pool`](crate::Pool) (aliased as
+//! [`BrowserPool`](crate::BrowserPool)),
+//! [`BrowserHandle`](crate::BrowserHandle) and
+//! [`BrowserFactory`](crate::BrowserFactory) take the browser type as a
+//! parameter defaulting to it, so another backend (a remote CDP client,
+//! chromiumoxide, WebDriver) can reuse the pool's sizing, health checks,
+//! TTL and queueing by implementing these two traits and a factory:
+//!
+//! ```rust,ignore
+//! use html2pdf_api::{BrowserPoolBuilder, PooledBrowser};
+//!
+//! let pool = BrowserPoolBuilder::<MyBrowser>::with_backend()
+//!     .factory(Box::new(MyFactory))
+//!     .build()?;
+//! let browser = pool.get()?;
+//! let tab = browser.checkout_tab()?;
+//! ```
+//!
+//! The [`service`](crate::service) layer and the web integrations render
+//! through Chrome DevTools Protocol features (request interception,
+//! emulation, tracing) and therefore require the Chrome backend.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use html2pdf_api::PooledBrowser;
+//!
+//! fn is_usable(browser: &impl PooledBrowser) -> bool {
+//!     browser.ping().is_ok()
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use headless_chrome::protocol::cdp::{Emulation, Page};
+use headless_chrome::types::PrintToPdfOptions;
+use headless_chrome::{Browser, Tab};
+
+use crate::error::{BrowserPoolError, Result};
+
+/// Page used to validate and health-check browsers through their tabs.
+const PROBE_URL: &str = "data:text/html,<html><body>Health check</body></html>";
+
+/// A browser instance the pool can manage.
+///
+/// The lifecycle checks have defaults built on [`new_tab()`](Self::new_tab)
+/// and [`BrowserTab`], so a backend only has to provide tabs and a ping.
+///
+/// # Thread Safety
+///
+/// Requires `Send + Sync` because checkout checks run on request threads
+/// while the keep-alive thread checks the same instance.
+pub trait PooledBrowser: Send + Sync + 'static {
+    /// The backend's tab type.
+    type Tab: BrowserTab;
+
+    /// Open a new tab.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`] if the browser can't open one.
+    fn new_tab(&self) -> Result<Arc<Self::Tab>>;
+
+    /// Verify a newly launched instance works before it joins the pool.
+    ///
+    /// Defaults to opening a tab, navigating it and closing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::BrowserCreation`] describing the failed step.
+    fn validate(&self) -> Result<()> {
+        probe(self).map_err(|e| BrowserPoolError::BrowserCreation(e.to_string()))
+    }
+
+    /// Cheap liveness check run before every checkout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::HealthCheckFailed`] if the instance does
+    /// not respond.
+    fn ping(&self) -> Result<()>;
+
+    /// Thorough check that exercises real work, used instead of
+    /// [`ping()`](Self::ping) when
+    /// [`deep_health_check`](crate::BrowserPoolConfig::deep_health_check)
+    /// is enabled.
+    ///
+    /// Defaults to opening a tab, navigating it and closing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::HealthCheckFailed`] describing the failed step.
+    fn deep_check(&self) -> Result<()> {
+        probe(self).map_err(|e| BrowserPoolError::HealthCheckFailed(e.to_string()))
+    }
+
+    /// Number of tabs open in the browser, for the pool's debug snapshot.
+    ///
+    /// Defaults to `0` for backends that can't tell.
+    fn open_tab_count(&self) -> usize {
+        0
+    }
+
+    /// ID of the browser's local process, used to report its memory.
+    ///
+    /// Defaults to `None` (remote or unknown process).
+    fn process_id(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// A tab of a [`PooledBrowser`].
+///
+/// Covers what the pool does with tabs (warmup, prefetch, reuse) and the
+/// basic render steps.
+pub trait BrowserTab: Send + Sync + 'static {
+    /// Load `url` and wait for the navigation to finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`] if the page can't be loaded.
+    fn navigate(&self, url: &str) -> Result<()>;
+
+    /// Print the loaded page to PDF.
+    ///
+    /// Backends map the Chrome-style `options` onto their own print
+    /// settings, ignoring those they don't support.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`] if printing fails.
+    fn print_to_pdf(&self, options: Option<PrintToPdfOptions>) -> Result<Vec<u8>>;
+
+    /// Capture the visible part of the page as PNG.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`] if the capture fails.
+    fn screenshot(&self) -> Result<Vec<u8>>;
+
+    /// Close the tab.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`] if the browser refuses.
+    fn close(&self) -> Result<()>;
+
+    /// Prepare the tab before it is handed to a render, e.g. refuse
+    /// downloads so pages can't write files to the server.
+    ///
+    /// Called by [`BrowserHandle::checkout_tab`](crate::BrowserHandle::checkout_tab)
+    /// before the factory's [`prepare_tab`](crate::BrowserFactory::prepare_tab).
+    /// An error discards the tab. Defaults to doing nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`] if the tab can't be made safe.
+    fn prepare(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Clear the page so the tab can be reused by the next render.
+    ///
+    /// Defaults to navigating to `about:blank`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BrowserPoolError::TabCreation`] if the reset fails.
+    fn reset(&self) -> Result<()> {
+        self.navigate("about:blank")
+    }
+}
+
+/// Open a tab, navigate it to [`PROBE_URL`] and close it.
+fn probe<B: PooledBrowser + ?Sized>(browser: &B) -> Result<()> {
+    let tab = browser.new_tab()?;
+    let result = tab.navigate(PROBE_URL);
+    let closed = tab.close();
+    result.and(closed)
+}
+
+impl PooledBrowser for Browser {
+    type Tab = Tab;
+
+    fn new_tab(&self) -> Result<Arc<Tab>> {
+        Browser::new_tab(self).map_err(|e| BrowserPoolError::TabCreation(e.to_string()))
+    }
+
+    /// Open a tab, navigate it to an empty data URL and close it.
+    fn validate(&self) -> Result<()> {
+        let tab = self.new_tab().map_err(|e| {
+            log::error!("❌ Browser validation failed at new_tab(): {}", e);
+            BrowserPoolError::BrowserCreation(e.to_string())
+        })?;
+
+        // Test navigation capability
+        if let Err(e) = tab.navigate_to("data:text/html,<html></html>") {
+            log::error!("❌ Browser validation failed at navigate_to(): {}", e);
+            let _ = tab.close(true); // Best effort cleanup
+            return Err(BrowserPoolError::BrowserCreation(e.to_string()));
+        }
+
+        // Clean up test tab
+        let _ = tab.close(true);
+        Ok(())
+    }
+
+    /// A single CDP `Browser.getVersion` round-trip, which proves the
+    /// browser process and its connection are alive.
+    fn ping(&self) -> Result<()> {
+        self.get_version()
+            .map(|_| ())
+            .map_err(|e| BrowserPoolError::HealthCheckFailed(format!("version ping failed: {}", e)))
+    }
+
+    /// Open a tab, navigate it and close it again.
+    fn deep_check(&self) -> Result<()> {
+        let tab = self
+            .new_tab()
+            .map_err(|e| BrowserPoolError::HealthCheckFailed(format!("new_tab() failed: {}", e)))?;
+
+        tab.navigate_to("data:text/html,<html><body>Health check</body></html>")
+            .map_err(|e| {
+                BrowserPoolError::HealthCheckFailed(format!("navigation failed: {}", e))
+            })?;
+
+        tab.close(true)
+            .map_err(|e| BrowserPoolError::HealthCheckFailed(format!("tab close failed: {}", e)))?;
+        Ok(())
+    }
+
+    fn open_tab_count(&self) -> usize {
+        self.get_tabs().lock().map(|tabs| tabs.len()).unwrap_or(0)
+    }
+
+    fn process_id(&self) -> Option<u32> {
+        self.get_process_id()
+    }
+}
+
+impl BrowserTab for Tab {
+    fn navigate(&self, url: &str) -> Result<()> {
+        self.navigate_to(url)
+            .and_then(|tab| tab.wait_until_navigated())
+            .map(|_| ())
+            .map_err(|e| BrowserPoolError::TabCreation(format!("navigation failed: {}", e)))
+    }
+
+    fn print_to_pdf(&self, options: Option<PrintToPdfOptions>) -> Result<Vec<u8>> {
+        Tab::print_to_pdf(self, options)
+            .map_err(|e| BrowserPoolError::TabCreation(format!("printing failed: {}", e)))
+    }
+
+    fn screenshot(&self) -> Result<Vec<u8>> {
+        self.capture_screenshot(Page::CaptureScreenshotFormatOption::Png, None, None, true)
+            .map_err(|e| BrowserPoolError::TabCreation(format!("screenshot failed: {}", e)))
+    }
+
+    fn close(&self) -> Result<()> {
+        Tab::close(self, true)
+            .map(|_| ())
+            .map_err(|e| BrowserPoolError::TabCreation(format!("tab close failed: {}", e)))
+    }
+
+    /// Refuse downloads, and keep the tab active and focused so its timers
+    /// and animations run at full speed.
+    fn prepare(&self) -> Result<()> {
+        // A tab that could still write downloads to the server is never
+        // handed out
+        deny_downloads(self).map_err(|e| {
+            BrowserPoolError::TabCreation(format!("failed to deny downloads: {}", e))
+        })?;
+
+        // Best effort: a throttled tab still renders, just possibly early
+        if let Err(e) = keep_active(self) {
+            log::warn!(
+                "⚠️ Failed to keep tab active (timers may be throttled): {}",
+                e
+            );
+        }
+        Ok(())
+    }
+
+    fn reset(&self) -> Result<()> {
+        BrowserTab::navigate(self, "about:blank")
+    }
+}
+
+/// Make the tab refuse downloads.
+///
+/// Chrome would otherwise save files a page triggers (a
+/// `Content-Disposition: attachment` response, `<a download>`) to the
+/// server's download directory.
+fn deny_downloads(tab: &Tab) -> std::result::Result<(), String> {
+    tab.call_method(Page::SetDownloadBehavior {
+        behavior: Page::SetDownloadBehaviorBehaviorOption::Deny,
+        download_path: None,
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Stop Chrome from throttling the tab as a background page.
+///
+/// Headless tabs are never in front, so Chrome may freeze them and slow
+/// their timers and `requestAnimationFrame` callbacks down; charts that
+/// animate into place are then printed half-drawn. This marks the page
+/// lifecycle active and emulates focus.
+fn keep_active(tab: &Tab) -> std::result::Result<(), String> {
+    tab.call_method(Page::SetWebLifecycleState {
+        state: Page::SetWebLifecycleStateStateOption::Active,
+    })
+    .map_err(|e| e.to_string())?;
+    tab.call_method(Emulation::SetFocusEmulationEnabled { enabled: true })
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
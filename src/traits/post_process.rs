@@ -0,0 +1,123 @@
+//! Post-processing trait for rendered PDFs.
+//!
+//! This module provides the [`PdfPostProcessor`] trait, a hook that receives
+//! the bytes of every PDF the service renders and returns the bytes to send
+//! instead. It covers steps that otherwise need a second pipeline stage,
+//! such as digital signatures, archiving a copy, or stamping with an
+//! in-house tool.
+//!
+//! # Where It Runs
+//!
+//! ```text
+//! Chrome ──→ metadata ──→ pdf_a ──→ linearize ──→ post-processors ──→ split_pages ──→ response
+//! ```
+//!
+//! Post-processors see the finished document, after every request option
+//! that rewrites the file, so nothing undoes their changes (a signature
+//! stays valid). They form a pipeline: each gets the previous one's output,
+//! in the order they were registered, so register a signer last.
+//!
+//! The crate ships one implementation, `PdfSigner` (in the service module),
+//! behind the `pdf-signing` feature. Watermarks, document properties, and
+//! page numbers are cheaper as the request options `watermark`, `metadata`,
+//! and `footer_template`, which Chrome or the service apply before the
+//! pipeline runs.
+
+use std::error::Error;
+use std::fmt;
+
+/// The error a [`PdfPostProcessor`] fails with.
+pub type PostProcessError = Box<dyn Error + Send + Sync>;
+
+/// A step applied to every rendered PDF.
+///
+/// Register implementations with
+/// [`BrowserPoolConfigBuilder::post_processor`](crate::BrowserPoolConfigBuilder::post_processor).
+///
+/// # Thread Safety
+///
+/// This trait requires `Send + Sync` because one instance is shared by all
+/// renders, which run on many threads at once.
+///
+/// # Example Implementation
+///
+/// ```rust
+/// use std::error::Error;
+/// use std::path::PathBuf;
+/// use html2pdf_api::PdfPostProcessor;
+///
+/// /// Keeps a copy of every PDF served.
+/// #[derive(Debug)]
+/// struct Archive {
+///     dir: PathBuf,
+/// }
+///
+/// impl PdfPostProcessor for Archive {
+///     fn name(&self) -> &str {
+///         "archive"
+///     }
+///
+///     fn process(&self, pdf: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+///         let nanos = std::time::UNIX_EPOCH.elapsed()?.as_nanos();
+///         std::fs::write(self.dir.join(format!("{}.pdf", nanos)), &pdf)?;
+///         Ok(pdf)
+///     }
+/// }
+///
+/// let archive = Archive { dir: std::env::temp_dir() };
+/// let pdf = archive.process(b"%PDF-1.4".to_vec()).unwrap();
+/// assert_eq!(pdf, b"%PDF-1.4");
+/// ```
+///
+/// # Considerations
+///
+/// - `process` runs on the thread rendering the PDF; keep it quick or the
+///   browser stays checked out longer
+/// - An error fails the render with a `PdfGenerationFailed` error naming
+///   the processor
+/// - Streamed renders (`stream`) are refused while post-processors are
+///   configured, since the bytes are sent as Chrome produces them
+pub trait PdfPostProcessor: Send + Sync + fmt::Debug {
+    /// A short name for logs and errors, such as `"signature"`.
+    fn name(&self) -> &str;
+
+    /// Transform a finished PDF.
+    ///
+    /// Returns the PDF to send in place of `pdf`; return it unchanged for
+    /// steps that only observe it.
+    ///
+    /// # Errors
+    ///
+    /// Any error fails the render.
+    fn process(&self, pdf: Vec<u8>) -> Result<Vec<u8>, PostProcessError>;
+}
+
+/// [`PdfPostProcessor`] calling a closure for every PDF.
+///
+/// Created by
+/// [`BrowserPoolConfigBuilder::post_processor_fn`](crate::BrowserPoolConfigBuilder::post_processor_fn).
+pub(crate) struct FnPostProcessor<F> {
+    pub(crate) name: String,
+    pub(crate) process: F,
+}
+
+impl<F> PdfPostProcessor for FnPostProcessor<F>
+where
+    F: Fn(Vec<u8>) -> Result<Vec<u8>, PostProcessError> + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(&self, pdf: Vec<u8>) -> Result<Vec<u8>, PostProcessError> {
+        (self.process)(pdf)
+    }
+}
+
+impl<F> fmt::Debug for FnPostProcessor<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnPostProcessor")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
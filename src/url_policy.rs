@@ -0,0 +1,376 @@
+//! Which hosts URL renders may reach.
+//!
+//! A service that renders `GET /pdf?url=` for the public hands every caller
+//! a browser inside its own network: `http://169.254.169.254/` returns
+//! cloud credentials, `http://localhost:8080/admin` an internal console.
+//! [`UrlPolicy`], set as the pool's
+//! [`url_policy`](crate::BrowserPoolConfig::url_policy), keeps renders on
+//! the hosts they are meant for.
+//!
+//! # Enforcement
+//!
+//! | When | What is checked |
+//! |------|-----------------|
+//! | Before a browser is checked out | Scheme, host lists, IP literals, `localhost` |
+//! | Every document request, including redirects and iframes | The same, plus the addresses the host resolves to |
+//!
+//! Requests are rejected with `INVALID_URL`, or `REDIRECT_REJECTED` when a
+//! redirect leads to a forbidden host.
+//!
+//! # Limits
+//!
+//! - Subresources (images, scripts, `fetch()`) are not checked. Combine
+//!   with `javascript_enabled: false` or `block_resources` for untrusted
+//!   pages.
+//! - Chrome resolves the host again when it connects, so a DNS server that
+//!   answers differently the second time (DNS rebinding) can get past the
+//!   address check. Firewall the renderer's egress for full protection.
+//! - `file://` URLs are governed by
+//!   [`allowed_file_dirs`](crate::BrowserPoolConfig::allowed_file_dirs)
+//!   instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! use html2pdf_api::UrlPolicy;
+//!
+//! let policy = UrlPolicy::public().deny_host("internal.example.com");
+//!
+//! assert!(policy.check(&"https://example.com/report".parse().unwrap()).is_ok());
+//! assert!(policy.check(&"http://127.0.0.1:8080/".parse().unwrap()).is_err());
+//! assert!(policy.check(&"http://10.0.0.5/".parse().unwrap()).is_err());
+//! assert!(policy.check(&"https://api.internal.example.com/".parse().unwrap()).is_err());
+//! ```
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use url::{Host, Url};
+
+/// Hosts and address ranges URL renders may reach.
+///
+/// The default allows everything, matching earlier releases. See the
+/// [module documentation](self) for where it is enforced.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UrlPolicy {
+    /// Reject loopback, private, link-local and other non-public
+    /// addresses, and `localhost`.
+    pub deny_private_networks: bool,
+
+    /// Hosts renders are limited to; empty allows any host.
+    ///
+    /// An entry also covers its subdomains (`example.com` allows
+    /// `reports.example.com`). Listed hosts are trusted and skip the
+    /// private network check, so internal servers can be allowed by name.
+    pub allowed_hosts: Vec<String>,
+
+    /// Hosts renders may never reach, with their subdomains. Takes
+    /// precedence over [`allowed_hosts`](Self::allowed_hosts).
+    pub denied_hosts: Vec<String>,
+}
+
+impl UrlPolicy {
+    /// A policy for services open to the public: private networks and
+    /// `localhost` are denied.
+    pub fn public() -> Self {
+        Self {
+            deny_private_networks: true,
+            ..Self::default()
+        }
+    }
+
+    /// Limit renders to `host` and its subdomains (in addition to hosts
+    /// already allowed).
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.allowed_hosts.push(host.into());
+        self
+    }
+
+    /// Forbid `host` and its subdomains.
+    pub fn deny_host(mut self, host: impl Into<String>) -> Self {
+        self.denied_hosts.push(host.into());
+        self
+    }
+
+    /// Whether the policy rejects anything at all.
+    pub fn is_restricted(&self) -> bool {
+        self.deny_private_networks
+            || !self.allowed_hosts.is_empty()
+            || !self.denied_hosts.is_empty()
+    }
+
+    /// Check `url` without DNS lookups: its scheme, the host lists, and
+    /// hosts given as IP addresses or `localhost`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason `url` is not allowed.
+    pub fn check(&self, url: &Url) -> Result<(), String> {
+        self.check_url(url, Vec::new)
+    }
+
+    /// Like [`check`](Self::check), and with
+    /// [`deny_private_networks`](Self::deny_private_networks) also resolve
+    /// the host and reject it if any address is private.
+    ///
+    /// Blocks while the host is resolved. A host that doesn't resolve is
+    /// let through: the browser can't reach it either.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason `url` is not allowed.
+    pub fn check_resolved(&self, url: &Url) -> Result<(), String> {
+        self.check_url(url, || url.socket_addrs(|| None).unwrap_or_default())
+    }
+
+    /// Like [`check_resolved`](Self::check_resolved), with `addrs` as the
+    /// addresses the host resolved to.
+    ///
+    /// Connecting to exactly these addresses afterwards leaves no second
+    /// lookup for a rebinding DNS server to answer differently.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    pub(crate) fn check_addrs(&self, url: &Url, addrs: &[SocketAddr]) -> Result<(), String> {
+        self.check_url(url, || addrs.to_vec())
+    }
+
+    fn check_url(
+        &self,
+        url: &Url,
+        resolve: impl FnOnce() -> Vec<SocketAddr>,
+    ) -> Result<(), String> {
+        if !self.is_restricted() {
+            return Ok(());
+        }
+
+        match url.scheme() {
+            "http" | "https" => {}
+            // Checked against allowed_file_dirs
+            "file" => return Ok(()),
+            scheme => return Err(format!("{}: URLs are not allowed", scheme)),
+        }
+
+        let host = match url.host() {
+            Some(host) => host,
+            None => return Err("URL has no host".to_string()),
+        };
+        let name = match &host {
+            Host::Domain(domain) => domain.trim_end_matches('.').to_ascii_lowercase(),
+            Host::Ipv4(ip) => ip.to_string(),
+            Host::Ipv6(ip) => ip.to_string(),
+        };
+
+        if matches_any(&self.denied_hosts, &name) {
+            return Err(format!("host {} is denied", name));
+        }
+        if !self.allowed_hosts.is_empty() {
+            return if matches_any(&self.allowed_hosts, &name) {
+                Ok(())
+            } else {
+                Err(format!("host {} is not in the allowed hosts", name))
+            };
+        }
+        if !self.deny_private_networks {
+            return Ok(());
+        }
+
+        let private = match host {
+            Host::Ipv4(ip) => is_private_ip(IpAddr::V4(ip)),
+            Host::Ipv6(ip) => is_private_ip(IpAddr::V6(ip)),
+            Host::Domain(_) if name == "localhost" || name.ends_with(".localhost") => true,
+            Host::Domain(_) => resolve().iter().any(|addr| is_private_ip(addr.ip())),
+        };
+        if private {
+            return Err(format!("host {} is on a private network", name));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `host` equals an entry of `hosts` or is a subdomain of one.
+///
+/// Entries are compared case-insensitively; a leading `*.` or `.` is
+/// ignored.
+fn matches_any(hosts: &[String], host: &str) -> bool {
+    hosts.iter().any(|entry| {
+        let entry = entry
+            .trim()
+            .trim_start_matches("*.")
+            .trim_start_matches('.');
+        let entry = entry.trim_end_matches('.').to_ascii_lowercase();
+        !entry.is_empty()
+            && (host == entry
+                || host
+                    .strip_suffix(entry.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.')))
+    })
+}
+
+/// Whether `ip` is outside the public internet: loopback, private,
+/// link-local (cloud metadata), carrier-grade NAT, unspecified,
+/// multicast or reserved.
+pub fn is_private_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_ipv4(ip),
+        IpAddr::V6(ip) => is_private_ipv6(ip),
+    }
+}
+
+fn is_private_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        // 0.0.0.0/8 "this network"
+        || a == 0
+        // 100.64.0.0/10 carrier-grade NAT
+        || (a == 100 && (b & 0xc0) == 64)
+        // 240.0.0.0/4 reserved
+        || a >= 240
+}
+
+fn is_private_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        return is_private_ipv4(ipv4);
+    }
+    let [first, second, ..] = ip.segments();
+    // 64:ff9b::/96 NAT64 embeds an IPv4 address
+    if first == 0x64 && second == 0xff9b {
+        let [.., high, low] = ip.segments();
+        return is_private_ipv4(Ipv4Addr::from(((high as u32) << 16) | low as u32));
+    }
+    ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // fc00::/7 unique local
+        || (first & 0xfe00) == 0xfc00
+        // fe80::/10 link-local
+        || (first & 0xffc0) == 0xfe80
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    /// Verifies the default policy lets everything through.
+    #[test]
+    fn test_default_allows_everything() {
+        let policy = UrlPolicy::default();
+        assert!(!policy.is_restricted());
+        assert!(policy.check(&url("http://127.0.0.1/")).is_ok());
+        assert!(policy.check(&url("data:text/html,hi")).is_ok());
+    }
+
+    /// Verifies private addresses are rejected in every spelling.
+    #[test]
+    fn test_deny_private_networks() {
+        let policy = UrlPolicy::public();
+        for denied in [
+            "http://127.0.0.1/",
+            "http://2130706433/",
+            "http://0x7f.1/",
+            "http://10.1.2.3/",
+            "http://172.16.0.1/",
+            "http://192.168.1.1/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://100.64.0.1/",
+            "http://0.0.0.0/",
+            "http://[::1]/",
+            "http://[::ffff:127.0.0.1]/",
+            "http://[fd00::1]/",
+            "http://[fe80::1]/",
+            "http://[64:ff9b::a00:1]/",
+            "http://localhost:8080/",
+            "http://LOCALHOST./",
+            "http://app.localhost/",
+            "ftp://example.com/",
+            "javascript:alert(1)",
+        ] {
+            assert!(policy.check(&url(denied)).is_err(), "{}", denied);
+        }
+
+        for allowed in [
+            "https://example.com/",
+            "http://8.8.8.8/",
+            "http://172.32.0.1/",
+            "http://[2606:4700::1111]/",
+            "file:///srv/reports/index.html",
+        ] {
+            assert!(policy.check(&url(allowed)).is_ok(), "{}", allowed);
+        }
+    }
+
+    /// Verifies host lists cover subdomains, and denials win.
+    #[test]
+    fn test_host_lists() {
+        let policy = UrlPolicy::default()
+            .allow_host("example.com")
+            .allow_host("*.reports.internal")
+            .deny_host("admin.example.com");
+        assert!(policy.is_restricted());
+        assert!(policy.check(&url("https://example.com/")).is_ok());
+        assert!(policy.check(&url("https://www.Example.com/")).is_ok());
+        assert!(policy.check(&url("http://q1.reports.internal/")).is_ok());
+        assert!(policy.check(&url("https://admin.example.com/")).is_err());
+        assert!(policy.check(&url("https://x.admin.example.com/")).is_err());
+        assert!(policy.check(&url("https://notexample.com/")).is_err());
+        assert!(
+            policy
+                .check(&url("https://example.com.evil.test/"))
+                .is_err()
+        );
+
+        // Allowed hosts are trusted even when private networks are denied
+        let policy = UrlPolicy::public().allow_host("10.0.0.5");
+        assert!(policy.check(&url("http://10.0.0.5/")).is_ok());
+        assert!(policy.check(&url("http://10.0.0.6/")).is_err());
+    }
+
+    /// Verifies a host that doesn't resolve is let through, while
+    /// literals are still checked.
+    #[test]
+    fn test_check_resolved() {
+        let policy = UrlPolicy::public();
+        let localhost = url("http://localhost.localdomain.test:1/");
+        assert!(policy.check(&localhost).is_ok());
+        assert!(policy.check_resolved(&localhost).is_ok());
+        assert!(policy.check_resolved(&url("http://127.0.0.1/")).is_err());
+    }
+
+    /// Verifies a public-looking host is rejected when it resolved to a
+    /// private address, unless it is allowed by name.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    #[test]
+    fn test_check_addrs() {
+        let callback = url("https://callback.example.com/hook");
+        let private: SocketAddr = "10.0.0.5:443".parse().unwrap();
+        let public: SocketAddr = "93.184.216.34:443".parse().unwrap();
+
+        let policy = UrlPolicy::public();
+        assert!(policy.check(&callback).is_ok());
+        assert!(policy.check_addrs(&callback, &[public]).is_ok());
+        assert!(policy.check_addrs(&callback, &[public, private]).is_err());
+
+        let policy = UrlPolicy::public().allow_host("example.com");
+        assert!(policy.check_addrs(&callback, &[private]).is_ok());
+    }
+}
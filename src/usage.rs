@@ -0,0 +1,471 @@
+//! Per-API-key usage accounting.
+//!
+//! This module provides [`UsageTracker`], which counts renders, PDF bytes,
+//! and browser time per API key for the current UTC day, the current UTC
+//! month, and since the pool started. Every [`BrowserPool`](crate::BrowserPool)
+//! owns one; the service layer records into it and enforces
+//! [`daily_render_quota`](crate::BrowserPoolConfig::daily_render_quota) and
+//! [`monthly_render_quota`](crate::BrowserPoolConfig::monthly_render_quota).
+//!
+//! Usage is kept in memory only, so it resets when the process restarts.
+//! Export it regularly (e.g. by scraping `GET /usage`) if you bill from it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use html2pdf_api::UsageTracker;
+//! use std::time::Duration;
+//!
+//! let tracker = UsageTracker::new();
+//! tracker.record("team-a", 48_000, Duration::from_millis(1500));
+//!
+//! let usage = tracker.usage("team-a");
+//! assert_eq!(usage.today.renders, 1);
+//! assert_eq!(usage.this_month.bytes, 48_000);
+//! assert_eq!(usage.total.browser_time, Duration::from_millis(1500));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Key that requests without an API key are accounted under.
+pub const ANONYMOUS_KEY: &str = "anonymous";
+
+/// Seconds per UTC day.
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Keys a [`UsageTracker`] keeps before forgetting the least recently used.
+pub const DEFAULT_MAX_KEYS: usize = 10_000;
+
+/// Consumption over one period.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    /// Successful renders, counting metered renders still in progress.
+    pub renders: u64,
+
+    /// PDF bytes produced.
+    pub bytes: u64,
+
+    /// Time spent rendering, from checkout to PDF.
+    pub browser_time: Duration,
+}
+
+impl Usage {
+    fn add(&mut self, bytes: u64, browser_time: Duration) {
+        self.renders += 1;
+        self.bytes += bytes;
+        self.browser_time += browser_time;
+    }
+}
+
+/// Snapshot of one API key's usage.
+///
+/// Days and months are UTC calendar periods; `today` and `this_month` are
+/// zero once a period has rolled over without new renders.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KeyUsage {
+    /// Usage during the current UTC day.
+    pub today: Usage,
+
+    /// Usage during the current UTC month.
+    pub this_month: Usage,
+
+    /// Usage since the pool started.
+    pub total: Usage,
+}
+
+/// Running totals for one key.
+#[derive(Debug, Default)]
+struct Ledger {
+    /// Days since the Unix epoch that `today` belongs to.
+    day: u64,
+
+    /// Month index (`year * 12 + month - 1`) that `this_month` belongs to.
+    month: i64,
+
+    /// When the key was last used, on the tracker's clock.
+    last_used: u64,
+
+    today: Usage,
+    this_month: Usage,
+    total: Usage,
+}
+
+impl Ledger {
+    /// Usage as seen on `day`, without the periods that have ended.
+    fn snapshot(&self, day: u64) -> KeyUsage {
+        KeyUsage {
+            today: if self.day == day {
+                self.today
+            } else {
+                Usage::default()
+            },
+            this_month: if self.month == month_index(day) {
+                self.this_month
+            } else {
+                Usage::default()
+            },
+            total: self.total,
+        }
+    }
+
+    /// Start new periods if `day` is past the current ones.
+    fn roll_over(&mut self, day: u64) {
+        if self.day != day {
+            self.day = day;
+            self.today = Usage::default();
+        }
+        let month = month_index(day);
+        if self.month != month {
+            self.month = month;
+            self.this_month = Usage::default();
+        }
+    }
+}
+
+/// The tracker's state, behind its lock.
+#[derive(Debug, Default)]
+struct Ledgers {
+    keys: HashMap<String, Ledger>,
+
+    /// Totals of keys evicted to stay within the key limit.
+    evicted: Usage,
+
+    /// Counter ordering key uses, for eviction.
+    clock: u64,
+}
+
+impl Ledgers {
+    /// The ledger of `api_key`, rolled over to `day`, evicting the least
+    /// recently used key if a new one would exceed `max_keys`.
+    fn ledger(&mut self, api_key: &str, day: u64, max_keys: usize) -> &mut Ledger {
+        if !self.keys.contains_key(api_key) && self.keys.len() >= max_keys {
+            let oldest = self
+                .keys
+                .iter()
+                .min_by_key(|(_, ledger)| ledger.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(ledger) = oldest.and_then(|key| self.keys.remove(&key)) {
+                self.evicted.renders += ledger.total.renders;
+                self.evicted.bytes += ledger.total.bytes;
+                self.evicted.browser_time += ledger.total.browser_time;
+            }
+        }
+
+        self.clock += 1;
+        let ledger = self.keys.entry(api_key.to_string()).or_default();
+        ledger.last_used = self.clock;
+        ledger.roll_over(day);
+        ledger
+    }
+}
+
+/// Thread-safe per-API-key usage counters.
+///
+/// Keys are stored as given; the tracker does not authenticate them. When
+/// quotas matter, validate keys in front of the service (API gateway or
+/// middleware), since a client could otherwise pick a fresh key per request.
+///
+/// At most [`DEFAULT_MAX_KEYS`] keys are tracked (see
+/// [`with_max_keys`](Self::with_max_keys)); past that, the least recently
+/// used key is forgotten, so a flood of made-up keys cannot exhaust memory.
+/// Its renders still count toward [`total`](Self::total).
+#[derive(Debug)]
+pub struct UsageTracker {
+    ledgers: Mutex<Ledgers>,
+    max_keys: usize,
+}
+
+impl Default for UsageTracker {
+    fn default() -> Self {
+        Self::with_max_keys(DEFAULT_MAX_KEYS)
+    }
+}
+
+impl UsageTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty tracker that keeps at most `max_keys` keys (at
+    /// least one).
+    pub fn with_max_keys(max_keys: usize) -> Self {
+        Self {
+            ledgers: Mutex::default(),
+            max_keys: max_keys.max(1),
+        }
+    }
+
+    /// Record one successful render for `api_key`.
+    ///
+    /// # Parameters
+    ///
+    /// * `api_key` - Key to account the render to ([`ANONYMOUS_KEY`] for none).
+    /// * `bytes` - Size of the produced PDF.
+    /// * `browser_time` - Time the render held a browser.
+    pub fn record(&self, api_key: &str, bytes: u64, browser_time: Duration) {
+        self.record_on(api_key, bytes, browser_time, today());
+    }
+
+    /// Get the usage of `api_key`; all zero for keys never seen.
+    pub fn usage(&self, api_key: &str) -> KeyUsage {
+        self.usage_on(api_key, today())
+    }
+
+    /// Get the usage of all keys together since the pool started.
+    pub fn total(&self) -> Usage {
+        let ledgers = self.lock();
+        ledgers
+            .keys
+            .values()
+            .fold(ledgers.evicted, |mut sum, ledger| {
+                sum.renders += ledger.total.renders;
+                sum.bytes += ledger.total.bytes;
+                sum.browser_time += ledger.total.browser_time;
+                sum
+            })
+    }
+
+    fn record_on(&self, api_key: &str, bytes: u64, browser_time: Duration, day: u64) {
+        let mut ledgers = self.lock();
+        let ledger = ledgers.ledger(api_key, day, self.max_keys);
+
+        ledger.today.add(bytes, browser_time);
+        ledger.this_month.add(bytes, browser_time);
+        ledger.total.add(bytes, browser_time);
+    }
+
+    fn usage_on(&self, api_key: &str, day: u64) -> KeyUsage {
+        self.lock()
+            .keys
+            .get(api_key)
+            .map(|ledger| ledger.snapshot(day))
+            .unwrap_or_default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Ledgers> {
+        self.ledgers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Quota reservations, used by the service layer's metered renders.
+#[cfg(any(
+    feature = "actix-integration",
+    feature = "rocket-integration",
+    feature = "axum-integration",
+    feature = "cli"
+))]
+impl UsageTracker {
+    /// Count a render for `api_key` before it runs, unless the key has
+    /// reached `daily_quota` or `monthly_quota` (0 = unlimited).
+    ///
+    /// Checking and counting under one lock keeps concurrent renders from
+    /// all passing a quota with one render left. Returns the day the
+    /// render was counted on, for [`complete`](Self::complete) or
+    /// [`refund`](Self::refund).
+    ///
+    /// # Errors
+    ///
+    /// Returns which quota was reached.
+    pub(crate) fn reserve(
+        &self,
+        api_key: &str,
+        daily_quota: u64,
+        monthly_quota: u64,
+    ) -> Result<u64, String> {
+        self.reserve_on(api_key, daily_quota, monthly_quota, today())
+    }
+
+    /// Add the size and browser time of a render counted by
+    /// [`reserve`](Self::reserve).
+    pub(crate) fn complete(&self, api_key: &str, bytes: u64, browser_time: Duration) {
+        let mut ledgers = self.lock();
+        let ledger = ledgers.ledger(api_key, today(), self.max_keys);
+        for usage in [&mut ledger.today, &mut ledger.this_month, &mut ledger.total] {
+            usage.bytes += bytes;
+            usage.browser_time += browser_time;
+        }
+    }
+
+    /// Take back a render counted by [`reserve`](Self::reserve) on `day`
+    /// that did not produce a PDF.
+    pub(crate) fn refund(&self, api_key: &str, day: u64) {
+        let mut ledgers = self.lock();
+        let Some(ledger) = ledgers.keys.get_mut(api_key) else {
+            return;
+        };
+        if ledger.day == day {
+            ledger.today.renders = ledger.today.renders.saturating_sub(1);
+        }
+        if ledger.month == month_index(day) {
+            ledger.this_month.renders = ledger.this_month.renders.saturating_sub(1);
+        }
+        ledger.total.renders = ledger.total.renders.saturating_sub(1);
+    }
+
+    fn reserve_on(
+        &self,
+        api_key: &str,
+        daily_quota: u64,
+        monthly_quota: u64,
+        day: u64,
+    ) -> Result<u64, String> {
+        let mut ledgers = self.lock();
+        let ledger = ledgers.ledger(api_key, day, self.max_keys);
+
+        if daily_quota > 0 && ledger.today.renders >= daily_quota {
+            return Err(format!("daily render quota of {} reached", daily_quota));
+        }
+        if monthly_quota > 0 && ledger.this_month.renders >= monthly_quota {
+            return Err(format!("monthly render quota of {} reached", monthly_quota));
+        }
+
+        ledger.today.renders += 1;
+        ledger.this_month.renders += 1;
+        ledger.total.renders += 1;
+        Ok(day)
+    }
+}
+
+/// Current UTC day as days since the Unix epoch.
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / SECS_PER_DAY
+}
+
+/// Month index (`year * 12 + month - 1`) of a day since the Unix epoch.
+fn month_index(day: u64) -> i64 {
+    let (year, month, _) = civil_from_days(day);
+    (year * 12 + month - 1) as i64
+}
+
+/// `(year, month, day)` of a day since the Unix epoch.
+///
+/// Uses the days-to-civil conversion from Howard Hinnant's date algorithms.
+pub(crate) fn civil_from_days(day: u64) -> (u64, u64, u64) {
+    let z = day + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153; // March = 0
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day_of_month)
+}
+
+// ============================================================================
+// Unit Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies day numbers map to the right calendar date and month.
+    #[test]
+    fn test_month_index() {
+        assert_eq!(month_index(0), 1970 * 12); // 1970-01-01
+        assert_eq!(month_index(31), 1970 * 12 + 1); // 1970-02-01
+        assert_eq!(month_index(59), 1970 * 12 + 2); // 1970-03-01
+        assert_eq!(month_index(11_016), 2000 * 12 + 1); // 2000-02-29
+        assert_eq!(month_index(20_742), 2026 * 12 + 9); // 2026-10-16
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+
+    /// Verifies daily and monthly totals reset when their period ends.
+    #[test]
+    fn test_usage_rolls_over() {
+        let tracker = UsageTracker::new();
+        let day = 20_742; // 2026-10-16
+
+        tracker.record_on("team-a", 100, Duration::from_secs(2), day);
+        tracker.record_on("team-a", 50, Duration::from_secs(1), day);
+        tracker.record_on("team-b", 10, Duration::from_secs(1), day);
+
+        let usage = tracker.usage_on("team-a", day);
+        assert_eq!(usage.today.renders, 2);
+        assert_eq!(usage.today.bytes, 150);
+        assert_eq!(usage.today.browser_time, Duration::from_secs(3));
+        assert_eq!(usage.this_month, usage.today);
+
+        // Next day, same month
+        let usage = tracker.usage_on("team-a", day + 1);
+        assert_eq!(usage.today, Usage::default());
+        assert_eq!(usage.this_month.renders, 2);
+
+        // Next month (2026-11-01)
+        tracker.record_on("team-a", 7, Duration::ZERO, day + 16);
+        let usage = tracker.usage_on("team-a", day + 16);
+        assert_eq!(usage.today.renders, 1);
+        assert_eq!(usage.this_month.renders, 1);
+        assert_eq!(usage.total.renders, 3);
+        assert_eq!(usage.total.bytes, 157);
+
+        assert_eq!(tracker.usage_on("team-b", day).total.renders, 1);
+        assert_eq!(tracker.total().renders, 4);
+        assert_eq!(tracker.total().bytes, 167);
+        assert_eq!(tracker.usage_on("unknown", day), KeyUsage::default());
+    }
+
+    /// Verifies reservations count against quotas until refunded.
+    #[cfg(any(
+        feature = "actix-integration",
+        feature = "rocket-integration",
+        feature = "axum-integration",
+        feature = "cli"
+    ))]
+    #[test]
+    fn test_reserve_and_refund() {
+        let tracker = UsageTracker::new();
+        let day = 20_742;
+
+        assert_eq!(tracker.reserve_on("team-a", 2, 0, day), Ok(day));
+        assert_eq!(tracker.reserve_on("team-a", 2, 0, day), Ok(day));
+        assert!(tracker.reserve_on("team-a", 2, 0, day).is_err());
+        assert!(tracker.reserve_on("team-a", 0, 2, day).is_err());
+        assert!(tracker.reserve_on("team-a", 0, 0, day).is_ok());
+
+        tracker.refund("team-a", day);
+        tracker.refund("team-a", day);
+        let usage = tracker.usage_on("team-a", day);
+        assert_eq!(usage.today.renders, 1);
+        assert_eq!(usage.total.renders, 1);
+
+        // A refund after the day rolled over leaves the new day alone
+        tracker.refund("team-a", day - 1);
+        assert_eq!(tracker.usage_on("team-a", day).today.renders, 1);
+    }
+
+    /// Verifies the least recently used key is evicted past the limit,
+    /// and its renders stay in the total.
+    #[test]
+    fn test_max_keys() {
+        let tracker = UsageTracker::with_max_keys(2);
+        let day = 20_742;
+
+        tracker.record_on("team-a", 10, Duration::ZERO, day);
+        tracker.record_on("team-b", 20, Duration::ZERO, day);
+        tracker.record_on("team-a", 10, Duration::ZERO, day);
+        tracker.record_on("team-c", 40, Duration::ZERO, day);
+
+        assert_eq!(tracker.usage_on("team-a", day).total.renders, 2);
+        assert_eq!(tracker.usage_on("team-b", day), KeyUsage::default());
+        assert_eq!(tracker.usage_on("team-c", day).total.renders, 1);
+        assert_eq!(tracker.total().renders, 4);
+        assert_eq!(tracker.total().bytes, 80);
+    }
+}